@@ -0,0 +1,577 @@
+//! Device authentication for sigil-bridge
+//!
+//! Paired devices hold a secp256k1 key and sign every request. The signature
+//! covers the method, path, a timestamp, a one-time nonce and the body hash,
+//! so a captured request can neither be altered nor replayed. Each device
+//! carries a capability scope and can be revoked at any time.
+
+use axum::{
+    body::{to_bytes, Body},
+    extract::{Request, State},
+    http::{HeaderMap, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+    Json,
+};
+use k256::ecdsa::{signature::Verifier, Signature, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex, RwLock};
+use std::time::{SystemTime, UNIX_EPOCH};
+use thiserror::Error;
+use tracing::{info, warn};
+
+pub use sigil_daemon::ipc::auth::Capability;
+
+/// Header naming the device that signed the request
+pub const DEVICE_HEADER: &str = "x-sigil-device";
+
+/// Header carrying the request time (unix seconds)
+pub const TIMESTAMP_HEADER: &str = "x-sigil-timestamp";
+
+/// Header carrying the one-time request nonce (hex)
+pub const NONCE_HEADER: &str = "x-sigil-nonce";
+
+/// Header carrying the hex compact ECDSA signature
+pub const SIGNATURE_HEADER: &str = "x-sigil-signature";
+
+/// Domain separator for request signatures
+const SIGNING_DOMAIN: &str = "sigil-bridge-request-v1";
+
+/// How far a request timestamp may differ from the bridge clock
+pub const MAX_CLOCK_SKEW_SECS: u64 = 300;
+
+/// Shortest accepted nonce, in bytes
+const MIN_NONCE_BYTES: usize = 16;
+
+/// Largest request body the bridge will authenticate
+const MAX_BODY_BYTES: usize = 1024 * 1024;
+
+/// Reason a request was refused
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum AuthError {
+    #[error("missing {0} header")]
+    MissingHeader(&'static str),
+
+    #[error("malformed {0} header")]
+    MalformedHeader(&'static str),
+
+    #[error("unknown device")]
+    UnknownDevice,
+
+    #[error("device has been revoked")]
+    Revoked,
+
+    #[error("request timestamp is outside the allowed window")]
+    StaleTimestamp,
+
+    #[error("nonce has already been used")]
+    Replay,
+
+    #[error("invalid request signature")]
+    BadSignature,
+
+    #[error("request body too large")]
+    BodyTooLarge,
+
+    #[error("device '{device}' lacks the {required:?} capability")]
+    InsufficientCapability {
+        device: String,
+        required: Capability,
+    },
+}
+
+impl IntoResponse for AuthError {
+    fn into_response(self) -> Response {
+        let status = match self {
+            AuthError::InsufficientCapability { .. } | AuthError::Revoked => StatusCode::FORBIDDEN,
+            AuthError::BodyTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            _ => StatusCode::UNAUTHORIZED,
+        };
+        (
+            status,
+            Json(serde_json::json!({
+                "type": "Error",
+                "message": format!("Access denied: {}", self)
+            })),
+        )
+            .into_response()
+    }
+}
+
+/// A paired device
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Device {
+    /// Short identifier derived from the public key
+    pub id: String,
+
+    /// Name the device gave when pairing
+    pub name: String,
+
+    /// Compressed SEC1 public key (hex)
+    pub public_key: String,
+
+    /// What the device may do
+    pub capability: Capability,
+
+    /// Pairing time (unix seconds)
+    pub paired_at: u64,
+
+    /// Revocation time (unix seconds), if revoked
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub revoked_at: Option<u64>,
+}
+
+impl Device {
+    /// Whether the device may still make requests
+    pub fn is_active(&self) -> bool {
+        self.revoked_at.is_none()
+    }
+}
+
+/// Derive the device id for a public key
+pub fn device_id(public_key: &VerifyingKey) -> String {
+    let digest = Sha256::digest(public_key.to_encoded_point(true).as_bytes());
+    hex::encode(&digest[..8])
+}
+
+/// Parse a hex SEC1 public key
+pub fn parse_public_key(hex_key: &str) -> Option<VerifyingKey> {
+    let bytes = hex::decode(hex_key.trim_start_matches("0x")).ok()?;
+    VerifyingKey::from_sec1_bytes(&bytes).ok()
+}
+
+/// Paired devices, persisted as JSON
+///
+/// The file is re-read whenever it changes on disk, so devices revoked with
+/// `sigil-bridge devices revoke` are locked out of a running bridge.
+pub struct DeviceRegistry {
+    path: PathBuf,
+    devices: RwLock<Vec<Device>>,
+    modified: Mutex<Option<SystemTime>>,
+}
+
+impl DeviceRegistry {
+    /// Load the registry, starting empty if the file doesn't exist yet
+    pub fn load(path: impl Into<PathBuf>) -> std::io::Result<Self> {
+        let path = path.into();
+        let devices = read_devices(&path)?;
+        let modified = modified_time(&path);
+        Ok(Self {
+            path,
+            devices: RwLock::new(devices),
+            modified: Mutex::new(modified),
+        })
+    }
+
+    /// All devices, including revoked ones
+    pub fn devices(&self) -> Vec<Device> {
+        self.refresh();
+        self.devices.read().unwrap().clone()
+    }
+
+    /// Look up a device by id
+    pub fn get(&self, id: &str) -> Option<Device> {
+        self.refresh();
+        self.devices
+            .read()
+            .unwrap()
+            .iter()
+            .find(|d| d.id == id)
+            .cloned()
+    }
+
+    /// Whether any device is still allowed to connect
+    pub fn has_active_devices(&self) -> bool {
+        self.devices().iter().any(Device::is_active)
+    }
+
+    /// Add (or re-pair) a device
+    pub fn add(&self, device: Device) -> std::io::Result<()> {
+        self.refresh();
+        let mut devices = self.devices.write().unwrap();
+        devices.retain(|d| d.id != device.id);
+        info!(
+            "Paired device '{}' ({}) with {:?} capability",
+            device.name, device.id, device.capability
+        );
+        devices.push(device);
+        self.save(&devices)
+    }
+
+    /// Revoke a device, returning false if it isn't paired
+    pub fn revoke(&self, id: &str) -> std::io::Result<bool> {
+        self.refresh();
+        let mut devices = self.devices.write().unwrap();
+        let Some(device) = devices.iter_mut().find(|d| d.id == id) else {
+            return Ok(false);
+        };
+        if device.revoked_at.is_none() {
+            device.revoked_at = Some(unix_now());
+            info!("Revoked device '{}' ({})", device.name, device.id);
+        }
+        self.save(&devices)?;
+        Ok(true)
+    }
+
+    /// Pick up changes made to the file by another process
+    fn refresh(&self) {
+        let modified = modified_time(&self.path);
+        if modified == *self.modified.lock().unwrap() {
+            return;
+        }
+        // Never hold both locks here; `save` takes them devices-first
+        match read_devices(&self.path) {
+            Ok(devices) => {
+                *self.devices.write().unwrap() = devices;
+                *self.modified.lock().unwrap() = modified;
+            }
+            Err(e) => warn!("Failed to reload device registry: {}", e),
+        }
+    }
+
+    fn save(&self, devices: &[Device]) -> std::io::Result<()> {
+        if let Some(parent) = self.path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let json = serde_json::to_vec_pretty(devices)?;
+        let tmp = self.path.with_extension("json.tmp");
+        std::fs::write(&tmp, json)?;
+        std::fs::rename(&tmp, &self.path)?;
+        *self.modified.lock().unwrap() = modified_time(&self.path);
+        Ok(())
+    }
+}
+
+fn read_devices(path: &Path) -> std::io::Result<Vec<Device>> {
+    match std::fs::read(path) {
+        Ok(bytes) => Ok(serde_json::from_slice(&bytes)?),
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(Vec::new()),
+        Err(e) => Err(e),
+    }
+}
+
+fn modified_time(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Current unix time in seconds
+pub fn unix_now() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_secs())
+        .unwrap_or(0)
+}
+
+/// Bytes a device signs for a request
+pub fn signing_payload(
+    method: &str,
+    path: &str,
+    timestamp: u64,
+    nonce: &str,
+    body: &[u8],
+) -> Vec<u8> {
+    format!(
+        "{}\n{}\n{}\n{}\n{}\n{}",
+        SIGNING_DOMAIN,
+        method.to_uppercase(),
+        path,
+        timestamp,
+        nonce,
+        hex::encode(Sha256::digest(body))
+    )
+    .into_bytes()
+}
+
+/// Signed-request verifier with replay protection
+pub struct Authenticator {
+    registry: Arc<DeviceRegistry>,
+    /// Nonces seen inside the timestamp window, with the time they expire
+    seen_nonces: Mutex<HashMap<(String, String), u64>>,
+}
+
+impl Authenticator {
+    pub fn new(registry: Arc<DeviceRegistry>) -> Self {
+        Self {
+            registry,
+            seen_nonces: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// The device registry this authenticator checks against
+    pub fn registry(&self) -> &Arc<DeviceRegistry> {
+        &self.registry
+    }
+
+    /// Verify a signed request and return the device that sent it
+    pub fn authenticate(
+        &self,
+        method: &str,
+        path: &str,
+        headers: &HeaderMap,
+        body: &[u8],
+        required: Capability,
+        now: u64,
+    ) -> Result<Device, AuthError> {
+        let device_id = header(headers, DEVICE_HEADER)?;
+        let timestamp: u64 = header(headers, TIMESTAMP_HEADER)?
+            .parse()
+            .map_err(|_| AuthError::MalformedHeader(TIMESTAMP_HEADER))?;
+        let nonce = header(headers, NONCE_HEADER)?;
+        let signature = header(headers, SIGNATURE_HEADER)?;
+
+        if hex::decode(nonce).map_or(true, |n| n.len() < MIN_NONCE_BYTES) {
+            return Err(AuthError::MalformedHeader(NONCE_HEADER));
+        }
+        let signature = hex::decode(signature.trim_start_matches("0x"))
+            .ok()
+            .and_then(|bytes| Signature::from_slice(&bytes).ok())
+            .ok_or(AuthError::MalformedHeader(SIGNATURE_HEADER))?;
+
+        let device = self
+            .registry
+            .get(device_id)
+            .ok_or(AuthError::UnknownDevice)?;
+        if !device.is_active() {
+            return Err(AuthError::Revoked);
+        }
+        if timestamp.abs_diff(now) > MAX_CLOCK_SKEW_SECS {
+            return Err(AuthError::StaleTimestamp);
+        }
+
+        let key = parse_public_key(&device.public_key).ok_or(AuthError::UnknownDevice)?;
+        let payload = signing_payload(method, path, timestamp, nonce, body);
+        key.verify(&payload, &signature)
+            .map_err(|_| AuthError::BadSignature)?;
+
+        // Only remember nonces of correctly signed requests, so unauthenticated
+        // callers can't fill the cache
+        self.use_nonce(&device.id, nonce, now)?;
+
+        if device.capability < required {
+            return Err(AuthError::InsufficientCapability {
+                device: device.name,
+                required,
+            });
+        }
+        Ok(device)
+    }
+
+    fn use_nonce(&self, device_id: &str, nonce: &str, now: u64) -> Result<(), AuthError> {
+        let mut seen = self.seen_nonces.lock().unwrap();
+        seen.retain(|_, expires| *expires > now);
+        let key = (device_id.to_string(), nonce.to_lowercase());
+        if seen.contains_key(&key) {
+            return Err(AuthError::Replay);
+        }
+        // A replay must fall outside the timestamp window once forgotten
+        seen.insert(key, now + 2 * MAX_CLOCK_SKEW_SECS + 1);
+        Ok(())
+    }
+}
+
+fn header<'a>(headers: &'a HeaderMap, name: &'static str) -> Result<&'a str, AuthError> {
+    headers
+        .get(name)
+        .ok_or(AuthError::MissingHeader(name))?
+        .to_str()
+        .map_err(|_| AuthError::MalformedHeader(name))
+}
+
+/// Middleware state: who checks requests and what they must be allowed to do
+pub type RequireCapability = (Arc<Authenticator>, Capability);
+
+/// Reject requests that aren't signed by a paired device with the capability
+pub async fn require_capability(
+    State((authenticator, required)): State<RequireCapability>,
+    request: Request,
+    next: Next,
+) -> Response {
+    match authenticate_request(&authenticator, required, request).await {
+        Ok(request) => next.run(request).await,
+        Err(e) => e.into_response(),
+    }
+}
+
+/// Authenticate a request, returning it with the sender's [`Device`] as an
+/// extension
+///
+/// The body is buffered to check its hash and handed back unchanged, as are
+/// the request's other extensions (so connection upgrades still work).
+pub async fn authenticate_request(
+    authenticator: &Authenticator,
+    required: Capability,
+    request: Request,
+) -> Result<Request, AuthError> {
+    let (parts, body) = request.into_parts();
+    let body = to_bytes(body, MAX_BODY_BYTES)
+        .await
+        .map_err(|_| AuthError::BodyTooLarge)?;
+
+    let path = parts
+        .uri
+        .path_and_query()
+        .map(|pq| pq.as_str())
+        .unwrap_or("/");
+    match authenticator.authenticate(
+        parts.method.as_str(),
+        path,
+        &parts.headers,
+        &body,
+        required,
+        unix_now(),
+    ) {
+        Ok(device) => {
+            let mut request = Request::from_parts(parts, Body::from(body));
+            request.extensions_mut().insert(device);
+            Ok(request)
+        }
+        Err(e) => {
+            warn!("Rejected {} {}: {}", parts.method, path, e);
+            Err(e)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::ecdsa::{signature::Signer, SigningKey};
+
+    fn setup(capability: Capability) -> (tempfile::TempDir, Authenticator, SigningKey, String) {
+        let dir = tempfile::tempdir().unwrap();
+        let registry = Arc::new(DeviceRegistry::load(dir.path().join("devices.json")).unwrap());
+        let key = SigningKey::random(&mut rand::thread_rng());
+        let id = device_id(key.verifying_key());
+        registry
+            .add(Device {
+                id: id.clone(),
+                name: "phone".to_string(),
+                public_key: hex::encode(key.verifying_key().to_encoded_point(true).as_bytes()),
+                capability,
+                paired_at: unix_now(),
+                revoked_at: None,
+            })
+            .unwrap();
+        (dir, Authenticator::new(registry), key, id)
+    }
+
+    fn signed_headers(
+        key: &SigningKey,
+        id: &str,
+        path: &str,
+        body: &[u8],
+        timestamp: u64,
+        nonce: &str,
+    ) -> HeaderMap {
+        let signature: Signature = key.sign(&signing_payload("POST", path, timestamp, nonce, body));
+        let mut headers = HeaderMap::new();
+        headers.insert(DEVICE_HEADER, id.parse().unwrap());
+        headers.insert(TIMESTAMP_HEADER, timestamp.to_string().parse().unwrap());
+        headers.insert(NONCE_HEADER, nonce.parse().unwrap());
+        headers.insert(
+            SIGNATURE_HEADER,
+            hex::encode(signature.to_bytes()).parse().unwrap(),
+        );
+        headers
+    }
+
+    const NONCE: &str = "00112233445566778899aabbccddeeff";
+
+    #[test]
+    fn test_signed_request_accepted_once() {
+        let (_dir, auth, key, id) = setup(Capability::Sign);
+        let now = unix_now();
+        let body = br#"{"chain_id":1}"#;
+        let headers = signed_headers(&key, &id, "/api/sign", body, now, NONCE);
+
+        let device = auth
+            .authenticate("POST", "/api/sign", &headers, body, Capability::Sign, now)
+            .unwrap();
+        assert_eq!(device.id, id);
+
+        assert_eq!(
+            auth.authenticate("POST", "/api/sign", &headers, body, Capability::Sign, now),
+            Err(AuthError::Replay)
+        );
+    }
+
+    #[test]
+    fn test_tampered_or_stale_requests_rejected() {
+        let (_dir, auth, key, id) = setup(Capability::Sign);
+        let now = unix_now();
+        let headers = signed_headers(&key, &id, "/api/sign", b"{}", now, NONCE);
+
+        // Body, path or method changed after signing
+        assert_eq!(
+            auth.authenticate("POST", "/api/sign", &headers, b"{ }", Capability::Sign, now),
+            Err(AuthError::BadSignature)
+        );
+        assert_eq!(
+            auth.authenticate(
+                "POST",
+                "/api/sign-batch",
+                &headers,
+                b"{}",
+                Capability::Sign,
+                now
+            ),
+            Err(AuthError::BadSignature)
+        );
+
+        let old = now - MAX_CLOCK_SKEW_SECS - 1;
+        let headers = signed_headers(&key, &id, "/api/sign", b"{}", old, NONCE);
+        assert_eq!(
+            auth.authenticate("POST", "/api/sign", &headers, b"{}", Capability::Sign, now),
+            Err(AuthError::StaleTimestamp)
+        );
+
+        assert_eq!(
+            auth.authenticate(
+                "POST",
+                "/api/sign",
+                &HeaderMap::new(),
+                b"{}",
+                Capability::Sign,
+                now
+            ),
+            Err(AuthError::MissingHeader(DEVICE_HEADER))
+        );
+    }
+
+    #[test]
+    fn test_capability_and_revocation() {
+        let (_dir, auth, key, id) = setup(Capability::Status);
+        let now = unix_now();
+        let headers = signed_headers(&key, &id, "/api/sign", b"{}", now, NONCE);
+        assert!(matches!(
+            auth.authenticate("POST", "/api/sign", &headers, b"{}", Capability::Sign, now),
+            Err(AuthError::InsufficientCapability { .. })
+        ));
+
+        assert!(auth.registry().revoke(&id).unwrap());
+        let nonce = "ffeeddccbbaa99887766554433221100";
+        let headers = signed_headers(&key, &id, "/api/ping", b"", now, nonce);
+        assert_eq!(
+            auth.authenticate("POST", "/api/ping", &headers, b"", Capability::Status, now),
+            Err(AuthError::Revoked)
+        );
+    }
+
+    #[test]
+    fn test_registry_picks_up_external_revocation() {
+        let (dir, auth, _key, id) = setup(Capability::Sign);
+        let path = dir.path().join("devices.json");
+
+        // Another process (the `devices revoke` command) edits the file
+        let other = DeviceRegistry::load(&path).unwrap();
+        // Make sure the mtime differs on coarse-grained filesystems
+        std::thread::sleep(std::time::Duration::from_millis(20));
+        assert!(other.revoke(&id).unwrap());
+
+        assert!(!auth.registry().get(&id).unwrap().is_active());
+        assert!(!auth.registry().has_active_devices());
+    }
+}
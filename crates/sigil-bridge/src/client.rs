@@ -1,198 +1,548 @@
 //! Daemon IPC client for sigil-bridge
+//!
+//! Speaks the daemon's gRPC `SignerService`. The `*_json` functions convert
+//! responses to the JSON shapes served by the unversioned `/api` routes; the
+//! `/v1` API converts them to its typed structs instead.
 
-use anyhow::{anyhow, Result};
-use serde::{Deserialize, Serialize};
 use serde_json::Value;
 use std::path::PathBuf;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
-use tokio::net::UnixStream;
+use thiserror::Error;
 use tracing::debug;
 
+use sigil_daemon::ipc::{error_code, proto, IpcClient, SignerClient};
+
+/// Result type for daemon requests
+pub type Result<T> = std::result::Result<T, ClientError>;
+
+/// Why a daemon request failed
+#[derive(Debug, Error)]
+pub enum ClientError {
+    /// The daemon socket could not be reached
+    #[error("{0}")]
+    Unavailable(String),
+
+    /// The daemon answered with an error
+    #[error("{message}")]
+    Daemon {
+        status: tonic::Code,
+        /// `DaemonError` kind, when the daemon reported one
+        kind: Option<String>,
+        message: String,
+    },
+
+    /// The daemon has no RPC for this request
+    #[error("{0}")]
+    Unsupported(String),
+}
+
 /// Client for communicating with sigil-daemon via IPC
 pub struct DaemonClient {
-    socket_path: PathBuf,
+    inner: IpcClient,
 }
 
 impl DaemonClient {
     pub fn new(socket_path: &str) -> Self {
         Self {
-            socket_path: PathBuf::from(socket_path),
+            inner: IpcClient::new(PathBuf::from(socket_path)),
         }
     }
 
-    async fn send_request(&self, request: Value) -> Result<Value> {
-        let stream = UnixStream::connect(&self.socket_path).await?;
-        let (reader, mut writer) = stream.into_split();
-        let mut reader = BufReader::new(reader);
-
-        // Send request
-        let request_str = serde_json::to_string(&request)?;
-        debug!("Sending request: {}", request_str);
-        writer.write_all(request_str.as_bytes()).await?;
-        writer.write_all(b"\n").await?;
-        writer.flush().await?;
-
-        // Read response
-        let mut response_str = String::new();
-        reader.read_line(&mut response_str).await?;
-        debug!("Received response: {}", response_str);
-
-        let response: Value = serde_json::from_str(&response_str)?;
-
-        // Check for error response
-        if let Some(msg) = response.get("message") {
-            if response.get("type") == Some(&Value::String("Error".to_string())) {
-                return Err(anyhow!("{}", msg.as_str().unwrap_or("Unknown error")));
-            }
-        }
-
-        Ok(response)
+    /// Open a gRPC connection to the daemon
+    async fn connect(&self) -> Result<SignerClient> {
+        self.inner
+            .connect()
+            .await
+            .map_err(|e| ClientError::Unavailable(e.to_string()))
     }
 
     /// Ping the daemon
-    pub async fn ping(&self) -> Result<String> {
+    pub async fn ping(&self) -> Result<proto::PingResponse> {
         let response = self
-            .send_request(serde_json::json!({
-                "type": "Ping"
-            }))
-            .await?;
+            .connect()
+            .await?
+            .ping(proto::PingRequest {})
+            .await
+            .map_err(status_error)?
+            .into_inner();
+        debug!("Daemon uptime: {}s", response.uptime_seconds);
 
-        Ok(response["version"]
-            .as_str()
-            .unwrap_or("unknown")
-            .to_string())
+        Ok(response)
     }
 
     /// Get disk status
-    pub async fn get_disk_status(&self) -> Result<Value> {
-        let response = self
-            .send_request(serde_json::json!({
-                "type": "GetDiskStatus"
-            }))
-            .await?;
-        Ok(response)
+    pub async fn get_disk_status(&self) -> Result<proto::GetDiskStatusResponse> {
+        Ok(self
+            .connect()
+            .await?
+            .get_disk_status(proto::GetDiskStatusRequest {})
+            .await
+            .map_err(status_error)?
+            .into_inner())
     }
 
-    /// Get presignature count
-    pub async fn get_presig_count(&self) -> Result<Value> {
-        let response = self
-            .send_request(serde_json::json!({
-                "type": "GetPresigCount"
-            }))
-            .await?;
-        Ok(response)
+    /// Get presignature count of the selected disk
+    pub async fn get_presig_count(
+        &self,
+        child_id: Option<&str>,
+    ) -> Result<proto::GetPresigCountResponse> {
+        Ok(self
+            .connect()
+            .await?
+            .get_presig_count(proto::GetPresigCountRequest {
+                child_id: child_id.unwrap_or_default().to_string(),
+            })
+            .await
+            .map_err(status_error)?
+            .into_inner())
+    }
+
+    /// Query the usage log of the selected disk, newest first
+    pub async fn query_usage_log(
+        &self,
+        request: proto::QueryUsageLogRequest,
+    ) -> Result<proto::QueryUsageLogResponse> {
+        Ok(self
+            .connect()
+            .await?
+            .query_usage_log(request)
+            .await
+            .map_err(status_error)?
+            .into_inner())
     }
 
     /// Sign a message (ECDSA)
-    pub async fn sign(
+    ///
+    /// The response carries `pending_approval` instead of a signature when
+    /// the daemon holds the request for approval.
+    pub async fn sign(&self, request: proto::SignRequest) -> Result<proto::SignResponse> {
+        Ok(self
+            .connect()
+            .await?
+            .sign(request)
+            .await
+            .map_err(status_error)?
+            .into_inner())
+    }
+
+    /// Sign several messages atomically (ECDSA)
+    pub async fn sign_batch(
         &self,
-        message_hash: &str,
-        chain_id: u32,
-        description: &str,
-    ) -> Result<Value> {
-        let response = self
-            .send_request(serde_json::json!({
-                "type": "Sign",
-                "message_hash": message_hash,
-                "chain_id": chain_id,
-                "description": description
-            }))
-            .await?;
-        Ok(response)
+        child_id: Option<&str>,
+        items: Vec<proto::SignRequest>,
+    ) -> Result<proto::SignBatchResponse> {
+        Ok(self
+            .connect()
+            .await?
+            .sign_batch(proto::SignBatchRequest {
+                items,
+                child_id: child_id.unwrap_or_default().to_string(),
+            })
+            .await
+            .map_err(status_error)?
+            .into_inner())
     }
 
     /// Sign with FROST
     pub async fn sign_frost(
         &self,
         scheme: &str,
-        message_hash: &str,
-        description: &str,
+        _message_hash: &str,
+        _description: &str,
     ) -> Result<Value> {
-        // FROST signing would go through a separate endpoint if supported by daemon
-        // For now, simulate the response format
-        let response = self
-            .send_request(serde_json::json!({
-                "type": "SignFrost",
-                "scheme": scheme,
-                "message_hash": message_hash,
-                "description": description
-            }))
-            .await?;
-        Ok(response)
+        // The daemon's SignerService has no FROST signing RPC yet
+        Err(ClientError::Unsupported(format!(
+            "FROST signing ({}) is not supported by the daemon",
+            scheme
+        )))
     }
 
     /// Get address
     pub async fn get_address(
         &self,
-        scheme: Option<&str>,
+        _scheme: Option<&str>,
         format: &str,
-        cosmos_prefix: Option<&str>,
+        _cosmos_prefix: Option<&str>,
     ) -> Result<Value> {
-        let mut request = serde_json::json!({
-            "type": "GetAddress",
-            "format": format
-        });
-
-        if let Some(s) = scheme {
-            request["scheme"] = Value::String(s.to_string());
-        }
-        if let Some(p) = cosmos_prefix {
-            request["cosmos_prefix"] = Value::String(p.to_string());
-        }
-
-        let response = self.send_request(request).await?;
-        Ok(response)
+        // The daemon's SignerService has no address derivation RPC yet
+        Err(ClientError::Unsupported(format!(
+            "Address lookup ({}) is not supported by the daemon",
+            format
+        )))
     }
 
     /// Update transaction hash
-    pub async fn update_tx_hash(&self, presig_index: u32, tx_hash: &str) -> Result<()> {
-        self.send_request(serde_json::json!({
-            "type": "UpdateTxHash",
-            "presig_index": presig_index,
-            "tx_hash": tx_hash
-        }))
-        .await?;
+    pub async fn update_tx_hash(
+        &self,
+        child_id: Option<&str>,
+        presig_index: u32,
+        tx_hash: &str,
+    ) -> Result<()> {
+        self.connect()
+            .await?
+            .update_tx_hash(proto::UpdateTxHashRequest {
+                presig_index,
+                tx_hash: tx_hash.to_string(),
+                child_id: child_id.unwrap_or_default().to_string(),
+            })
+            .await
+            .map_err(status_error)?;
         Ok(())
     }
 
-    /// List children
-    pub async fn list_children(&self) -> Result<Vec<String>> {
-        let response = self
-            .send_request(serde_json::json!({
-                "type": "ListChildren"
-            }))
-            .await?;
-
-        let children: Vec<String> = response["child_ids"]
-            .as_array()
-            .map(|arr| {
-                arr.iter()
-                    .filter_map(|v| v.as_str().map(String::from))
-                    .collect()
-            })
-            .unwrap_or_default();
-
-        Ok(children)
+    /// List children with agent shares
+    pub async fn list_children(&self) -> Result<Vec<proto::ChildInfo>> {
+        Ok(self
+            .connect()
+            .await?
+            .list_children(proto::ListChildrenRequest {})
+            .await
+            .map_err(status_error)?
+            .into_inner()
+            .children)
     }
 
     /// Import agent shard
     pub async fn import_agent_shard(&self, shard_hex: &str) -> Result<()> {
-        self.send_request(serde_json::json!({
-            "type": "ImportAgentShard",
-            "agent_shard_hex": shard_hex
-        }))
-        .await?;
+        self.connect()
+            .await?
+            .import_agent_shard(proto::ImportAgentShardRequest {
+                agent_shard_hex: shard_hex.to_string(),
+            })
+            .await
+            .map_err(status_error)?;
         Ok(())
     }
 
     /// Import child shares
     pub async fn import_child_shares(&self, shares_json: &str, replace: bool) -> Result<()> {
-        self.send_request(serde_json::json!({
-            "type": "ImportChildShares",
-            "shares_json": shares_json,
-            "replace": replace
-        }))
-        .await?;
+        self.connect()
+            .await?
+            .import_child_shares(proto::ImportChildSharesRequest {
+                shares_json: shares_json.to_string(),
+                replace,
+            })
+            .await
+            .map_err(status_error)?;
         Ok(())
     }
+
+    /// List signing requests held for approval
+    pub async fn list_approvals(&self, include_decided: bool) -> Result<Vec<proto::Approval>> {
+        Ok(self
+            .connect()
+            .await?
+            .list_approvals(proto::ListApprovalsRequest { include_decided })
+            .await
+            .map_err(status_error)?
+            .into_inner()
+            .approvals)
+    }
+
+    /// Get one approval
+    pub async fn get_approval(&self, approval_id: &str) -> Result<proto::Approval> {
+        Ok(self
+            .connect()
+            .await?
+            .get_approval(proto::GetApprovalRequest {
+                approval_id: approval_id.to_string(),
+            })
+            .await
+            .map_err(status_error)?
+            .into_inner())
+    }
+
+    /// Approve or reject a held signing request on behalf of `approver`
+    pub async fn decide_approval(
+        &self,
+        approval_id: &str,
+        approve: bool,
+        approver: &str,
+        reason: Option<&str>,
+    ) -> Result<proto::Approval> {
+        Ok(self
+            .connect()
+            .await?
+            .decide_approval(proto::DecideApprovalRequest {
+                approval_id: approval_id.to_string(),
+                approve,
+                approver: approver.to_string(),
+                reason: reason.unwrap_or_default().to_string(),
+            })
+            .await
+            .map_err(status_error)?
+            .into_inner())
+    }
+
+    /// Subscribe to disk and signing events
+    pub async fn watch_events(&self) -> Result<tonic::Streaming<proto::DaemonEvent>> {
+        Ok(self
+            .connect()
+            .await?
+            .watch_events(proto::WatchEventsRequest {})
+            .await
+            .map_err(status_error)?
+            .into_inner())
+    }
+}
+
+/// JSON for the disk status, with the single-disk fields set only when
+/// exactly one disk is inserted
+pub fn disk_status_json(status: proto::GetDiskStatusResponse) -> Value {
+    let disks: Vec<Value> = status
+        .disks
+        .iter()
+        .map(|disk| {
+            serde_json::json!({
+                "path": disk.path,
+                "child_id": disk.child_id,
+                "presigs_remaining": disk.presigs_remaining,
+                "presigs_total": disk.presigs_total,
+                "days_until_expiry": disk.days_until_expiry,
+                "is_valid": disk.is_valid,
+                "status_message": disk.status_message
+            })
+        })
+        .collect();
+
+    // The single-disk fields are only meaningful with exactly one disk
+    if !status.detected || disks.len() > 1 {
+        return serde_json::json!({
+            "type": "DiskStatus",
+            "detected": status.detected,
+            "child_id": null,
+            "presigs_remaining": null,
+            "presigs_total": null,
+            "days_until_expiry": null,
+            "is_valid": null,
+            "message": status.status_message,
+            "disks": disks
+        });
+    }
+
+    serde_json::json!({
+        "type": "DiskStatus",
+        "detected": true,
+        "child_id": status.child_id,
+        "presigs_remaining": status.presigs_remaining,
+        "presigs_total": status.presigs_total,
+        "days_until_expiry": status.days_until_expiry,
+        "is_valid": status.is_valid,
+        "message": status.status_message,
+        "disks": disks
+    })
+}
+
+/// JSON for a presig count
+pub fn presig_count_json(count: proto::GetPresigCountResponse) -> Value {
+    serde_json::json!({
+        "type": "PresigCount",
+        "remaining": count.remaining,
+        "total": count.total
+    })
+}
+
+/// JSON for a page of the usage log
+pub fn usage_page_json(page: proto::QueryUsageLogResponse) -> Value {
+    let entries: Vec<Value> = page
+        .entries
+        .into_iter()
+        .map(|entry| {
+            serde_json::json!({
+                "presig_index": entry.presig_index,
+                "timestamp": entry.timestamp,
+                "message_hash": entry.message_hash,
+                "signature": entry.signature,
+                "chain_id": entry.chain_id,
+                "tx_hash": Some(entry.tx_hash).filter(|tx_hash| !tx_hash.is_empty()),
+                "proof_hash": entry.proof_hash,
+                "description": entry.description
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "type": "UsageLog",
+        "child_id": page.child_id,
+        "entries": entries,
+        "total": page.total
+    })
+}
+
+/// JSON for a signature, or `ApprovalPending` when the request is held
+pub fn sign_json(result: proto::SignResponse) -> Value {
+    if let Some(pending) = result.pending_approval {
+        return pending_json(pending);
+    }
+    serde_json::json!({
+        "type": "SignResult",
+        "signature": result.signature,
+        "presig_index": result.presig_index,
+        "proof_hash": result.proof_hash,
+        "cached": result.cached
+    })
+}
+
+/// JSON for batch signatures, or `ApprovalPending` when the batch is held
+pub fn sign_batch_json(response: proto::SignBatchResponse) -> Value {
+    if let Some(pending) = response.pending_approval {
+        return pending_json(pending);
+    }
+    serde_json::json!({
+        "type": "SignBatchResult",
+        "results": response.results.into_iter().map(result_json).collect::<Vec<_>>()
+    })
+}
+
+/// JSON for a list of approvals
+pub fn approvals_json(approvals: Vec<proto::Approval>) -> Value {
+    serde_json::json!({
+        "type": "Approvals",
+        "approvals": approvals.into_iter().map(approval_json).collect::<Vec<_>>()
+    })
+}
+
+/// Convert a daemon event to its SSE event name and JSON payload
+pub fn event_json(event: proto::DaemonEvent) -> Option<(&'static str, Value)> {
+    use proto::daemon_event::Event;
+
+    let timestamp = event.timestamp;
+    let (name, mut payload) = match event.event? {
+        Event::DiskInserted(e) => (
+            "disk_inserted",
+            serde_json::json!({
+                "type": "DiskInserted",
+                "child_id": e.child_id,
+                "presigs_remaining": e.presigs_remaining,
+                "days_until_expiry": e.days_until_expiry
+            }),
+        ),
+        Event::DiskRemoved(_) => ("disk_removed", serde_json::json!({ "type": "DiskRemoved" })),
+        Event::DiskValidationFailed(e) => (
+            "disk_validation_failed",
+            serde_json::json!({
+                "type": "DiskValidationFailed",
+                "reason": e.reason
+            }),
+        ),
+        Event::PresigConsumed(e) => (
+            "presig_consumed",
+            serde_json::json!({
+                "type": "PresigConsumed",
+                "child_id": e.child_id,
+                "presig_index": e.presig_index,
+                "presigs_remaining": e.presigs_remaining
+            }),
+        ),
+        Event::LowPresigWarning(e) => (
+            "low_presig_warning",
+            serde_json::json!({
+                "type": "LowPresigWarning",
+                "child_id": e.child_id,
+                "presigs_remaining": e.presigs_remaining,
+                "threshold": e.threshold
+            }),
+        ),
+        Event::ExpiryWarning(e) => (
+            "expiry_warning",
+            serde_json::json!({
+                "type": "ExpiryWarning",
+                "child_id": e.child_id,
+                "days_until_expiry": e.days_until_expiry
+            }),
+        ),
+        Event::ApprovalRequested(e) => (
+            "approval_requested",
+            serde_json::json!({
+                "type": "ApprovalRequested",
+                "approval_id": e.approval_id,
+                "requested_by": e.requested_by,
+                "descriptions": e.descriptions,
+                "expires_at": e.expires_at
+            }),
+        ),
+        Event::ApprovalResolved(e) => (
+            "approval_resolved",
+            serde_json::json!({
+                "type": "ApprovalResolved",
+                "approval_id": e.approval_id,
+                "status": e.status
+            }),
+        ),
+        Event::SignatureNotLanded(e) => (
+            "signature_not_landed",
+            serde_json::json!({
+                "type": "SignatureNotLanded",
+                "child_id": e.child_id,
+                "presig_index": e.presig_index,
+                "chain_id": e.chain_id,
+                "message_hash": e.message_hash,
+                "signed_at": e.signed_at
+            }),
+        ),
+    };
+
+    payload["timestamp"] = timestamp.into();
+    Some((name, payload))
+}
+
+/// JSON for one signature
+fn result_json(result: proto::SignResponse) -> Value {
+    serde_json::json!({
+        "signature": result.signature,
+        "presig_index": result.presig_index,
+        "proof_hash": result.proof_hash,
+        "cached": result.cached
+    })
+}
+
+/// JSON telling the app a request is waiting for approval
+fn pending_json(pending: proto::PendingApproval) -> Value {
+    serde_json::json!({
+        "type": "ApprovalPending",
+        "approval_id": pending.approval_id,
+        "expires_at": pending.expires_at
+    })
+}
+
+/// JSON for an approval, with unset fields as null
+pub fn approval_json(approval: proto::Approval) -> Value {
+    let non_empty = |s: String| Some(s).filter(|s| !s.is_empty());
+    let items: Vec<Value> = approval
+        .items
+        .into_iter()
+        .map(|item| {
+            serde_json::json!({
+                "message_hash": item.message_hash,
+                "chain_id": item.chain_id,
+                "description": item.description,
+                "value": non_empty(item.value),
+                "child_id": non_empty(item.child_id)
+            })
+        })
+        .collect();
+
+    serde_json::json!({
+        "type": "Approval",
+        "approval_id": approval.approval_id,
+        "status": approval.status,
+        "items": items,
+        "requested_by": approval.requested_by,
+        "created_at": approval.created_at,
+        "expires_at": approval.expires_at,
+        "approver": non_empty(approval.approver),
+        "decided_by": non_empty(approval.decided_by),
+        "reason": non_empty(approval.reason),
+        "decided_at": Some(approval.decided_at).filter(|at| *at > 0),
+        "results": approval.results.into_iter().map(result_json).collect::<Vec<_>>(),
+        "error": non_empty(approval.error)
+    })
+}
+
+/// Convert a gRPC status into an error carrying the daemon's message
+fn status_error(status: tonic::Status) -> ClientError {
+    ClientError::Daemon {
+        status: status.code(),
+        kind: error_code(&status).map(str::to_string),
+        message: status.message().to_string(),
+    }
 }
@@ -1,35 +1,54 @@
 //! sigil-bridge: HTTP bridge server for sigil-daemon
 //!
-//! This server provides an HTTP API that proxies requests to the sigil-daemon's
+//! This server provides an HTTPS API that proxies requests to the sigil-daemon's
 //! IPC interface, enabling mobile apps and other HTTP clients to communicate
-//! with the daemon.
+//! with the daemon. Clients pair once via a QR code and then sign every
+//! request with their device key (see [`auth`] and [`pairing`]).
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use axum::{
-    extract::State,
-    http::StatusCode,
-    response::IntoResponse,
-    routing::{get, post},
-    Json, Router,
+    extract::{Path as UrlPath, Query, State},
+    http::{header, HeaderName, HeaderValue, Method, StatusCode},
+    middleware,
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    routing::{delete, get, post},
+    Extension, Json, Router,
 };
-use clap::Parser;
-use serde::{Deserialize, Serialize};
-use std::net::SocketAddr;
+use clap::{Parser, Subcommand};
+use futures_util::StreamExt;
+use serde::Deserialize;
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
 use std::sync::Arc;
-use tower_http::cors::{Any, CorsLayer};
+use tower_http::cors::CorsLayer;
 use tower_http::trace::TraceLayer;
 use tracing::{info, warn};
 
+mod auth;
 mod client;
+mod pairing;
+mod tls;
+mod v1;
 
+use auth::{require_capability, unix_now, Authenticator, Capability, Device, DeviceRegistry};
 use client::DaemonClient;
+use pairing::{PairRequest, Pairing, PairingOffer};
+use tls::TlsIdentity;
+use v1::types::{
+    ApprovalsQuery, ChildQuery, DecisionRequest, HistoryQuery, ImportAgentShardRequest,
+    ImportChildSharesRequest, SignBatchRequest, SignRequest, UpdateTxHashRequest,
+};
 
 #[derive(Parser, Debug)]
 #[command(name = "sigil-bridge")]
 #[command(about = "HTTP bridge server for sigil-daemon")]
 struct Args {
-    /// Host to bind to
-    #[arg(long, default_value = "0.0.0.0")]
+    /// Host to bind to; pass a LAN address (or 0.0.0.0) to serve paired
+    /// devices on the network
+    #[arg(long, default_value = "127.0.0.1")]
     host: String,
 
     /// Port to bind to
@@ -40,20 +59,127 @@ struct Args {
     #[arg(long, default_value = "/tmp/sigil.sock")]
     socket_path: String,
 
+    /// Directory for the device registry and generated TLS certificate
+    #[arg(long)]
+    state_dir: Option<PathBuf>,
+
+    /// PEM certificate to serve instead of the generated one
+    #[arg(long, requires = "tls_key")]
+    tls_cert: Option<PathBuf>,
+
+    /// PEM private key for --tls-cert
+    #[arg(long, requires = "tls_cert")]
+    tls_key: Option<PathBuf>,
+
+    /// Serve plain HTTP (requests are still authenticated; development only)
+    #[arg(long)]
+    insecure_http: bool,
+
+    /// Base URL the app should use, included in the pairing QR code
+    /// (e.g. https://192.168.1.5:8080)
+    #[arg(long)]
+    advertise_url: Option<String>,
+
+    /// Browser origin allowed to call the API (repeatable); none by default
+    #[arg(long = "cors-origin")]
+    cors_origins: Vec<String>,
+
+    /// Open a pairing session at startup even if devices are already paired
+    #[arg(long)]
+    pair: bool,
+
+    /// Capability granted to devices paired from the startup QR code
+    #[arg(long, default_value = "sign", value_parser = parse_capability)]
+    pair_capability: Capability,
+
+    /// How long a pairing QR code stays valid
+    #[arg(long, default_value = "300")]
+    pair_ttl_secs: u64,
+
     /// Enable verbose logging
     #[arg(short, long)]
     verbose: bool,
+
+    #[command(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Subcommand, Debug)]
+enum Command {
+    /// Manage paired devices
+    Devices {
+        #[command(subcommand)]
+        action: DevicesCommand,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+enum DevicesCommand {
+    /// List paired devices
+    List,
+    /// Revoke a device so it can no longer make requests
+    Revoke {
+        /// Device id from `devices list`
+        device_id: String,
+    },
+}
+
+fn parse_capability(value: &str) -> Result<Capability, String> {
+    serde_json::from_value(serde_json::Value::String(value.to_string())).map_err(|_| {
+        format!(
+            "unknown capability '{}' (status, sign, approve, admin)",
+            value
+        )
+    })
+}
+
+impl Args {
+    fn state_dir(&self) -> PathBuf {
+        self.state_dir.clone().unwrap_or_else(|| {
+            dirs::data_dir()
+                .unwrap_or_else(|| PathBuf::from("."))
+                .join("sigil-bridge")
+        })
+    }
+
+    fn devices_path(&self) -> PathBuf {
+        self.state_dir().join("devices.json")
+    }
+
+    /// Bind address, if it names a specific interface
+    fn specific_host(&self) -> Option<IpAddr> {
+        self.host
+            .parse::<IpAddr>()
+            .ok()
+            .filter(|ip| !ip.is_unspecified())
+    }
 }
 
 #[derive(Clone)]
 struct AppState {
     daemon_client: Arc<DaemonClient>,
+    authenticator: Arc<Authenticator>,
+    pairing: Arc<Pairing>,
+    /// Included in pairing QR codes so the app knows where to connect
+    advertise_url: Option<String>,
+    /// TLS certificate fingerprint the app pins
+    fingerprint: Option<String>,
+}
+
+impl AppState {
+    fn pairing_uri(&self, offer: &PairingOffer) -> String {
+        offer.uri(self.advertise_url.as_deref(), self.fingerprint.as_deref())
+    }
 }
 
 #[tokio::main]
 async fn main() -> Result<()> {
     let args = Args::parse();
 
+    if let Some(Command::Devices { action }) = &args.command {
+        return manage_devices(&args, action);
+    }
+
     // Initialize logging
     let filter = if args.verbose {
         "sigil_bridge=debug,tower_http=debug"
@@ -65,43 +191,292 @@ async fn main() -> Result<()> {
     info!("Starting sigil-bridge HTTP server");
     info!("Daemon socket: {}", args.socket_path);
 
-    let daemon_client = Arc::new(DaemonClient::new(&args.socket_path));
-    let state = AppState { daemon_client };
+    let state_dir = args.state_dir();
+    let registry = Arc::new(DeviceRegistry::load(args.devices_path())?);
+    info!("Device registry: {:?}", args.devices_path());
+
+    let identity = if args.insecure_http {
+        warn!("Serving plain HTTP: requests are signed but not encrypted");
+        None
+    } else {
+        let identity = match (&args.tls_cert, &args.tls_key) {
+            (Some(cert), Some(key)) => TlsIdentity::load(cert, key)?,
+            _ => {
+                let mut names = vec!["sigil-bridge".to_string(), "localhost".to_string()];
+                names.extend(args.specific_host().map(|ip| ip.to_string()));
+                TlsIdentity::load_or_generate(
+                    &state_dir.join("bridge-cert.pem"),
+                    &state_dir.join("bridge-key.pem"),
+                    &names,
+                )?
+            }
+        };
+        info!("TLS certificate fingerprint: {}", identity.fingerprint);
+        Some(identity)
+    };
+
+    let scheme = if identity.is_some() { "https" } else { "http" };
+    let advertise_url = args.advertise_url.clone().or_else(|| {
+        args.specific_host()
+            .map(|ip| format!("{}://{}", scheme, SocketAddr::new(ip, args.port)))
+    });
 
-    // Configure CORS for mobile app access
-    let cors = CorsLayer::new()
-        .allow_origin(Any)
-        .allow_methods(Any)
-        .allow_headers(Any);
+    let state = AppState {
+        daemon_client: Arc::new(DaemonClient::new(&args.socket_path)),
+        authenticator: Arc::new(Authenticator::new(Arc::clone(&registry))),
+        pairing: Arc::new(Pairing::new()),
+        advertise_url,
+        fingerprint: identity.as_ref().map(|id| id.fingerprint.clone()),
+    };
 
-    let app = Router::new()
-        // Health check
-        .route("/health", get(health))
-        // API routes
+    if args.pair || !registry.has_active_devices() {
+        let offer = state
+            .pairing
+            .open(args.pair_capability, args.pair_ttl_secs, unix_now());
+        show_pairing_code(&state, &offer);
+    }
+
+    let mut app = router(state).layer(TraceLayer::new_for_http());
+    if let Some(cors) = cors_layer(&args.cors_origins)? {
+        app = app.layer(cors);
+    }
+
+    let addr: SocketAddr = format!("{}:{}", args.host, args.port).parse()?;
+    info!("Listening on {}://{}", scheme, addr);
+
+    let listener = tokio::net::TcpListener::bind(addr).await?;
+    match identity {
+        Some(identity) => tls::serve(listener, &identity, app).await?,
+        None => axum::serve(listener, app).await?,
+    }
+
+    Ok(())
+}
+
+/// Build the API, with each route behind the capability it needs
+fn router(state: AppState) -> Router {
+    let authenticator = Arc::clone(&state.authenticator);
+    let require = |capability: Capability| {
+        middleware::from_fn_with_state((Arc::clone(&authenticator), capability), require_capability)
+    };
+
+    let status_routes = Router::new()
         .route("/api/ping", post(ping))
         .route("/api/disk-status", post(get_disk_status))
         .route("/api/presig-count", post(get_presig_count))
+        .route("/api/address", post(get_address))
+        .route("/api/list-children", post(list_children))
+        .route("/api/schemes", get(list_schemes))
+        .route("/api/events", get(events))
+        .route("/api/history", get(history))
+        .route("/api/approvals", get(list_approvals))
+        .route("/api/approvals/:approval_id", get(get_approval))
+        .route_layer(require(Capability::Status));
+
+    let sign_routes = Router::new()
         .route("/api/sign", post(sign))
+        .route("/api/sign-batch", post(sign_batch))
         .route("/api/sign-frost", post(sign_frost))
-        .route("/api/address", post(get_address))
         .route("/api/update-tx-hash", post(update_tx_hash))
-        .route("/api/list-children", post(list_children))
+        .route_layer(require(Capability::Sign));
+
+    let approve_routes = Router::new()
+        .route("/api/approvals/:approval_id/approve", post(approve))
+        .route("/api/approvals/:approval_id/reject", post(reject))
+        .route_layer(require(Capability::Approve));
+
+    let admin_routes = Router::new()
         .route("/api/import-agent-shard", post(import_agent_shard))
         .route("/api/import-child-shares", post(import_child_shares))
-        .route("/api/schemes", get(list_schemes))
+        .route("/api/devices", get(list_devices))
+        .route("/api/devices/:device_id", delete(revoke_device))
+        .route("/api/pairing", post(open_pairing))
+        .route_layer(require(Capability::Admin));
+
+    Router::new()
+        // Unauthenticated: health check and enrollment
+        .route("/health", get(health))
+        .route("/api/pair", post(pair))
+        .merge(status_routes)
+        .merge(sign_routes)
+        .merge(approve_routes)
+        .merge(admin_routes)
+        .merge(v1::router(&authenticator))
         .with_state(state)
-        .layer(cors)
-        .layer(TraceLayer::new_for_http());
+}
 
-    let addr: SocketAddr = format!("{}:{}", args.host, args.port).parse()?;
-    info!("Listening on http://{}", addr);
+/// CORS for the configured browser origins; `None` leaves cross-origin
+/// requests blocked
+fn cors_layer(origins: &[String]) -> Result<Option<CorsLayer>> {
+    if origins.is_empty() {
+        return Ok(None);
+    }
+    let origins = origins
+        .iter()
+        .map(|origin| {
+            HeaderValue::from_str(origin).map_err(|_| anyhow!("Invalid CORS origin: {}", origin))
+        })
+        .collect::<Result<Vec<_>>>()?;
 
-    let listener = tokio::net::TcpListener::bind(addr).await?;
-    axum::serve(listener, app).await?;
+    Ok(Some(
+        CorsLayer::new()
+            .allow_origin(origins)
+            .allow_methods([Method::GET, Method::POST, Method::DELETE])
+            .allow_headers([
+                header::CONTENT_TYPE,
+                HeaderName::from_static(auth::DEVICE_HEADER),
+                HeaderName::from_static(auth::TIMESTAMP_HEADER),
+                HeaderName::from_static(auth::NONCE_HEADER),
+                HeaderName::from_static(auth::SIGNATURE_HEADER),
+            ]),
+    ))
+}
 
+/// Print the pairing QR code for the app to scan
+fn show_pairing_code(state: &AppState, offer: &PairingOffer) {
+    let uri = state.pairing_uri(offer);
+    if let Some(qr) = pairing::render_qr(&uri) {
+        println!("{}", qr);
+    }
+    println!(
+        "Scan with the Sigil app to pair a device ({:?} capability):",
+        offer.capability
+    );
+    println!("{}", uri);
+    if state.advertise_url.is_none() {
+        println!("(no --advertise-url set; enter the bridge address in the app)");
+    }
+}
+
+/// `sigil-bridge devices ...`
+fn manage_devices(args: &Args, action: &DevicesCommand) -> Result<()> {
+    let registry = DeviceRegistry::load(args.devices_path())?;
+    match action {
+        DevicesCommand::List => {
+            let devices = registry.devices();
+            if devices.is_empty() {
+                println!("No paired devices");
+            }
+            for device in devices {
+                println!(
+                    "{}  {:<20} {:<8} {}",
+                    device.id,
+                    device.name,
+                    format!("{:?}", device.capability).to_lowercase(),
+                    if device.is_active() {
+                        "active"
+                    } else {
+                        "revoked"
+                    }
+                );
+            }
+        }
+        DevicesCommand::Revoke { device_id } => {
+            if !registry.revoke(device_id)? {
+                return Err(anyhow!("No paired device with id {}", device_id));
+            }
+            println!("Revoked {}", device_id);
+        }
+    }
     Ok(())
 }
 
+// Enroll a device with the open pairing session
+async fn pair(State(state): State<AppState>, Json(req): Json<PairRequest>) -> Response {
+    let device = match state.pairing.complete(&req, unix_now()) {
+        Ok(device) => device,
+        Err(e) => {
+            warn!("Pairing failed: {}", e);
+            return e.into_response();
+        }
+    };
+
+    match state.authenticator.registry().add(device.clone()) {
+        Ok(()) => (
+            StatusCode::OK,
+            Json(serde_json::json!({
+                "type": "Paired",
+                "device_id": device.id,
+                "capability": device.capability
+            })),
+        )
+            .into_response(),
+        Err(e) => {
+            warn!("Saving paired device failed: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({
+                    "type": "Error",
+                    "message": e.to_string()
+                })),
+            )
+                .into_response()
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct OpenPairingRequest {
+    #[serde(default)]
+    capability: Option<Capability>,
+    #[serde(default)]
+    ttl_secs: Option<u64>,
+}
+
+// Open a pairing session from an already paired admin device
+async fn open_pairing(
+    State(state): State<AppState>,
+    req: Option<Json<OpenPairingRequest>>,
+) -> impl IntoResponse {
+    let Json(req) = req.unwrap_or_default();
+    let offer = state.pairing.open(
+        req.capability.unwrap_or(Capability::Sign),
+        req.ttl_secs.unwrap_or(300),
+        unix_now(),
+    );
+    Json(serde_json::json!({
+        "type": "Pairing",
+        "uri": state.pairing_uri(&offer),
+        "capability": offer.capability,
+        "expires_at": offer.expires_at
+    }))
+}
+
+// List paired devices
+async fn list_devices(State(state): State<AppState>) -> impl IntoResponse {
+    Json(serde_json::json!({
+        "type": "Devices",
+        "devices": state.authenticator.registry().devices()
+    }))
+}
+
+// Revoke a paired device
+async fn revoke_device(
+    State(state): State<AppState>,
+    UrlPath(device_id): UrlPath<String>,
+) -> impl IntoResponse {
+    match state.authenticator.registry().revoke(&device_id) {
+        Ok(true) => (StatusCode::OK, Json(serde_json::json!({ "type": "Ok" }))),
+        Ok(false) => (
+            StatusCode::NOT_FOUND,
+            Json(serde_json::json!({
+                "type": "Error",
+                "message": format!("No paired device with id {}", device_id)
+            })),
+        ),
+        Err(e) => {
+            warn!("Revoking device failed: {}", e);
+            (
+                StatusCode::INTERNAL_SERVER_ERROR,
+                Json(serde_json::json!({
+                    "type": "Error",
+                    "message": e.to_string()
+                })),
+            )
+        }
+    }
+}
+
 // Health check endpoint
 async fn health() -> impl IntoResponse {
     Json(serde_json::json!({ "status": "ok" }))
@@ -110,11 +485,11 @@ async fn health() -> impl IntoResponse {
 // Ping daemon
 async fn ping(State(state): State<AppState>) -> impl IntoResponse {
     match state.daemon_client.ping().await {
-        Ok(version) => (
+        Ok(pong) => (
             StatusCode::OK,
             Json(serde_json::json!({
                 "type": "Pong",
-                "version": version
+                "version": pong.version
             })),
         ),
         Err(e) => {
@@ -133,7 +508,7 @@ async fn ping(State(state): State<AppState>) -> impl IntoResponse {
 // Get disk status
 async fn get_disk_status(State(state): State<AppState>) -> impl IntoResponse {
     match state.daemon_client.get_disk_status().await {
-        Ok(status) => (StatusCode::OK, Json(status)),
+        Ok(status) => (StatusCode::OK, Json(client::disk_status_json(status))),
         Err(e) => {
             warn!("Get disk status failed: {}", e);
             (
@@ -147,10 +522,18 @@ async fn get_disk_status(State(state): State<AppState>) -> impl IntoResponse {
     }
 }
 
-// Get presig count
-async fn get_presig_count(State(state): State<AppState>) -> impl IntoResponse {
-    match state.daemon_client.get_presig_count().await {
-        Ok(count) => (StatusCode::OK, Json(count)),
+// Get presig count (the body is optional unless several disks are inserted)
+async fn get_presig_count(
+    State(state): State<AppState>,
+    req: Option<Json<ChildQuery>>,
+) -> impl IntoResponse {
+    let Json(req) = req.unwrap_or_default();
+    match state
+        .daemon_client
+        .get_presig_count(req.child_id.as_deref())
+        .await
+    {
+        Ok(count) => (StatusCode::OK, Json(client::presig_count_json(count))),
         Err(e) => {
             warn!("Get presig count failed: {}", e);
             (
@@ -164,23 +547,141 @@ async fn get_presig_count(State(state): State<AppState>) -> impl IntoResponse {
     }
 }
 
-#[derive(Debug, Deserialize)]
-struct SignRequest {
-    message_hash: String,
-    chain_id: u32,
-    description: String,
-}
-
 // Sign EVM transaction
 async fn sign(State(state): State<AppState>, Json(req): Json<SignRequest>) -> impl IntoResponse {
+    match state.daemon_client.sign(req.into()).await {
+        Ok(result) => (StatusCode::OK, Json(client::sign_json(result))),
+        Err(e) => {
+            warn!("Sign failed: {}", e);
+            (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({
+                    "type": "Error",
+                    "message": e.to_string()
+                })),
+            )
+        }
+    }
+}
+
+// Sign several EVM transactions atomically
+async fn sign_batch(
+    State(state): State<AppState>,
+    Json(req): Json<SignBatchRequest>,
+) -> impl IntoResponse {
+    let items = req.items.into_iter().map(Into::into).collect();
+
     match state
         .daemon_client
-        .sign(&req.message_hash, req.chain_id, &req.description)
+        .sign_batch(req.child_id.as_deref(), items)
         .await
     {
-        Ok(result) => (StatusCode::OK, Json(result)),
+        Ok(response) => (StatusCode::OK, Json(client::sign_batch_json(response))),
         Err(e) => {
-            warn!("Sign failed: {}", e);
+            warn!("Batch sign failed: {}", e);
+            (
+                StatusCode::BAD_REQUEST,
+                Json(serde_json::json!({
+                    "type": "Error",
+                    "message": e.to_string()
+                })),
+            )
+        }
+    }
+}
+
+// Signatures from a child's usage log, newest first
+async fn history(
+    State(state): State<AppState>,
+    Query(query): Query<HistoryQuery>,
+) -> impl IntoResponse {
+    daemon_json(
+        state
+            .daemon_client
+            .query_usage_log(query.into())
+            .await
+            .map(client::usage_page_json),
+    )
+}
+
+// List signing requests held for approval
+async fn list_approvals(
+    State(state): State<AppState>,
+    Query(query): Query<ApprovalsQuery>,
+) -> impl IntoResponse {
+    daemon_json(
+        state
+            .daemon_client
+            .list_approvals(query.all)
+            .await
+            .map(client::approvals_json),
+    )
+}
+
+// Get one held signing request
+async fn get_approval(
+    State(state): State<AppState>,
+    UrlPath(approval_id): UrlPath<String>,
+) -> impl IntoResponse {
+    daemon_json(
+        state
+            .daemon_client
+            .get_approval(&approval_id)
+            .await
+            .map(client::approval_json),
+    )
+}
+
+// Approve a held signing request; the paired device is recorded as approver
+async fn approve(
+    State(state): State<AppState>,
+    Extension(device): Extension<Device>,
+    UrlPath(approval_id): UrlPath<String>,
+    req: Option<Json<DecisionRequest>>,
+) -> impl IntoResponse {
+    decide(state, device, approval_id, true, req).await
+}
+
+// Reject a held signing request
+async fn reject(
+    State(state): State<AppState>,
+    Extension(device): Extension<Device>,
+    UrlPath(approval_id): UrlPath<String>,
+    req: Option<Json<DecisionRequest>>,
+) -> impl IntoResponse {
+    decide(state, device, approval_id, false, req).await
+}
+
+async fn decide(
+    state: AppState,
+    device: Device,
+    approval_id: String,
+    approve: bool,
+    req: Option<Json<DecisionRequest>>,
+) -> (StatusCode, Json<serde_json::Value>) {
+    let Json(req) = req.unwrap_or_default();
+    let approver = format!("{} (device {})", device.name, device.id);
+    info!(
+        "{} signing request {} from {}",
+        if approve { "Approving" } else { "Rejecting" },
+        approval_id,
+        approver
+    );
+    daemon_json(
+        state
+            .daemon_client
+            .decide_approval(&approval_id, approve, &approver, req.reason.as_deref())
+            .await
+            .map(client::approval_json),
+    )
+}
+
+/// Answer with a daemon response, or its error as a 400
+fn daemon_json(result: client::Result<serde_json::Value>) -> (StatusCode, Json<serde_json::Value>) {
+    match result {
+        Ok(value) => (StatusCode::OK, Json(value)),
+        Err(e) => {
+            warn!("Daemon request failed: {}", e);
             (
                 StatusCode::BAD_REQUEST,
                 Json(serde_json::json!({
@@ -258,12 +759,6 @@ async fn get_address(
     }
 }
 
-#[derive(Debug, Deserialize)]
-struct UpdateTxHashRequest {
-    presig_index: u32,
-    tx_hash: String,
-}
-
 // Update transaction hash
 async fn update_tx_hash(
     State(state): State<AppState>,
@@ -271,7 +766,7 @@ async fn update_tx_hash(
 ) -> impl IntoResponse {
     match state
         .daemon_client
-        .update_tx_hash(req.presig_index, &req.tx_hash)
+        .update_tx_hash(req.child_id.as_deref(), req.presig_index, &req.tx_hash)
         .await
     {
         Ok(_) => (
@@ -301,6 +796,9 @@ async fn list_children(State(state): State<AppState>) -> impl IntoResponse {
             Json(serde_json::json!({
                 "type": "Children",
                 "child_ids": children
+                    .into_iter()
+                    .map(|child| child.child_id)
+                    .collect::<Vec<_>>()
             })),
         ),
         Err(e) => {
@@ -316,11 +814,6 @@ async fn list_children(State(state): State<AppState>) -> impl IntoResponse {
     }
 }
 
-#[derive(Debug, Deserialize)]
-struct ImportAgentShardRequest {
-    agent_shard_hex: String,
-}
-
 // Import agent shard
 async fn import_agent_shard(
     State(state): State<AppState>,
@@ -350,12 +843,6 @@ async fn import_agent_shard(
     }
 }
 
-#[derive(Debug, Deserialize)]
-struct ImportChildSharesRequest {
-    shares_json: String,
-    replace: bool,
-}
-
 // Import child shares
 async fn import_child_shares(
     State(state): State<AppState>,
@@ -385,6 +872,39 @@ async fn import_child_shares(
     }
 }
 
+// Stream daemon events as Server-Sent Events
+async fn events(State(state): State<AppState>) -> Response {
+    let stream = match state.daemon_client.watch_events().await {
+        Ok(stream) => stream,
+        Err(e) => {
+            warn!("Event subscription failed: {}", e);
+            return (
+                StatusCode::SERVICE_UNAVAILABLE,
+                Json(serde_json::json!({
+                    "type": "Error",
+                    "message": e.to_string()
+                })),
+            )
+                .into_response();
+        }
+    };
+
+    let events = stream.filter_map(|event| async move {
+        match event {
+            Ok(event) => client::event_json(event)
+                .map(|(name, payload)| Event::default().event(name).json_data(payload)),
+            Err(e) => {
+                warn!("Daemon event stream failed: {}", e);
+                None
+            }
+        }
+    });
+
+    Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
 // List supported schemes
 async fn list_schemes() -> impl IntoResponse {
     Json(serde_json::json!({
@@ -412,3 +932,141 @@ async fn list_schemes() -> impl IntoResponse {
         ]
     }))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::Body;
+    use axum::http::Request;
+    use k256::ecdsa::{signature::Signer, Signature, SigningKey};
+    use tower::ServiceExt;
+
+    pub(crate) fn test_state(
+        dir: &std::path::Path,
+        capability: Capability,
+    ) -> (AppState, SigningKey) {
+        let registry = Arc::new(DeviceRegistry::load(dir.join("devices.json")).unwrap());
+        let key = SigningKey::random(&mut rand::thread_rng());
+        registry
+            .add(auth::Device {
+                id: auth::device_id(key.verifying_key()),
+                name: "phone".to_string(),
+                public_key: hex::encode(key.verifying_key().to_encoded_point(true).as_bytes()),
+                capability,
+                paired_at: unix_now(),
+                revoked_at: None,
+            })
+            .unwrap();
+        let state = AppState {
+            daemon_client: Arc::new(DaemonClient::new("/nonexistent/sigil.sock")),
+            authenticator: Arc::new(Authenticator::new(registry)),
+            pairing: Arc::new(Pairing::new()),
+            advertise_url: None,
+            fingerprint: None,
+        };
+        (state, key)
+    }
+
+    pub(crate) fn signed(
+        key: &SigningKey,
+        method: &str,
+        path: &str,
+        body: &'static str,
+    ) -> Request<Body> {
+        let timestamp = unix_now();
+        let nonce = hex::encode(rand::random::<[u8; 16]>());
+        let signature: Signature = key.sign(&auth::signing_payload(
+            method,
+            path,
+            timestamp,
+            &nonce,
+            body.as_bytes(),
+        ));
+        Request::builder()
+            .method(method)
+            .uri(path)
+            .header(auth::DEVICE_HEADER, auth::device_id(key.verifying_key()))
+            .header(auth::TIMESTAMP_HEADER, timestamp.to_string())
+            .header(auth::NONCE_HEADER, nonce)
+            .header(auth::SIGNATURE_HEADER, hex::encode(signature.to_bytes()))
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from(body))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_routes_require_matching_capability() {
+        let dir = tempfile::tempdir().unwrap();
+        let (state, key) = test_state(dir.path(), Capability::Status);
+        let app = router(state);
+
+        let health = Request::get("/health").body(Body::empty()).unwrap();
+        let response = app.clone().oneshot(health).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let unsigned = Request::post("/api/sign")
+            .header(header::CONTENT_TYPE, "application/json")
+            .body(Body::from("{}"))
+            .unwrap();
+        let response = app.clone().oneshot(unsigned).await.unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        let response = app
+            .clone()
+            .oneshot(signed(&key, "GET", "/api/schemes", ""))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let response = app
+            .clone()
+            .oneshot(signed(&key, "POST", "/api/import-agent-shard", "{}"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let response = app
+            .clone()
+            .oneshot(signed(&key, "POST", "/api/approvals/ab12/approve", "{}"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[tokio::test]
+    async fn test_approve_device_can_decide() {
+        let dir = tempfile::tempdir().unwrap();
+        let (state, key) = test_state(dir.path(), Capability::Approve);
+        let app = router(state);
+
+        // Passes authentication and reaches the (absent) daemon
+        for (method, path) in [
+            ("GET", "/api/approvals"),
+            ("POST", "/api/approvals/ab12/reject"),
+        ] {
+            let body = if method == "GET" { "" } else { "{}" };
+            let response = app
+                .clone()
+                .oneshot(signed(&key, method, path, body))
+                .await
+                .unwrap();
+            assert_eq!(response.status(), StatusCode::BAD_REQUEST, "{}", path);
+        }
+
+        let response = app
+            .clone()
+            .oneshot(signed(&key, "POST", "/api/pairing", "{}"))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+    }
+
+    #[test]
+    fn test_cors_is_closed_by_default() {
+        assert!(cors_layer(&[]).unwrap().is_none());
+        assert!(cors_layer(&["https://app.example".to_string()])
+            .unwrap()
+            .is_some());
+        assert!(cors_layer(&["bad\norigin".to_string()]).is_err());
+    }
+}
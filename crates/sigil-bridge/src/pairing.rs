@@ -0,0 +1,296 @@
+//! One-time device pairing
+//!
+//! The bridge opens a short-lived pairing session and shows its secret and
+//! TLS certificate fingerprint as a QR code. The app scans it, pins the
+//! certificate, generates a device key and enrolls it by proving knowledge of
+//! the secret with an HMAC over the key. The secret never crosses the
+//! network, and each session pairs at most one device.
+
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    Json,
+};
+use hmac::{Hmac, Mac};
+use qrcode::{render::unicode, QrCode};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::sync::Mutex;
+use thiserror::Error;
+
+use crate::auth::{device_id, parse_public_key, Capability, Device};
+
+/// Domain separator for the pairing proof
+const PAIRING_DOMAIN: &str = "sigil-bridge-pair-v1";
+
+/// Wrong proofs tolerated before the session is closed
+const MAX_FAILED_ATTEMPTS: u32 = 5;
+
+/// Reason a pairing attempt failed
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum PairingError {
+    #[error("no pairing session is open")]
+    NoSession,
+
+    #[error("pairing session has expired")]
+    Expired,
+
+    #[error("invalid pairing proof")]
+    BadProof,
+
+    #[error("invalid device public key")]
+    InvalidPublicKey,
+
+    #[error("device name must not be empty")]
+    MissingName,
+}
+
+impl IntoResponse for PairingError {
+    fn into_response(self) -> Response {
+        let status = match self {
+            PairingError::InvalidPublicKey | PairingError::MissingName => StatusCode::BAD_REQUEST,
+            _ => StatusCode::UNAUTHORIZED,
+        };
+        (
+            status,
+            Json(serde_json::json!({
+                "type": "Error",
+                "message": format!("Pairing failed: {}", self)
+            })),
+        )
+            .into_response()
+    }
+}
+
+/// Pairing request sent by the app
+#[derive(Debug, Clone, Deserialize)]
+pub struct PairRequest {
+    /// Human-readable device name
+    pub device_name: String,
+
+    /// Compressed or uncompressed SEC1 secp256k1 public key (hex)
+    pub public_key: String,
+
+    /// `HMAC-SHA256(secret, pairing_message(name, public_key))` (hex)
+    pub proof: String,
+}
+
+/// What the QR code carries
+#[derive(Debug, Clone, Serialize)]
+pub struct PairingOffer {
+    /// Hex pairing secret
+    pub secret: String,
+
+    /// Capability the paired device will get
+    pub capability: Capability,
+
+    /// Session expiry (unix seconds)
+    pub expires_at: u64,
+}
+
+impl PairingOffer {
+    /// `sigil-bridge://pair` URI encoded in the QR code
+    pub fn uri(&self, url: Option<&str>, fingerprint: Option<&str>) -> String {
+        let mut uri = format!(
+            "sigil-bridge://pair?v=1&secret={}&capability={}&expires={}",
+            self.secret,
+            capability_name(self.capability),
+            self.expires_at
+        );
+        if let Some(url) = url {
+            uri.push_str("&url=");
+            uri.push_str(url);
+        }
+        if let Some(fingerprint) = fingerprint {
+            uri.push_str("&fingerprint=");
+            uri.push_str(fingerprint);
+        }
+        uri
+    }
+}
+
+fn capability_name(capability: Capability) -> &'static str {
+    match capability {
+        Capability::Status => "status",
+        Capability::Sign => "sign",
+        Capability::Approve => "approve",
+        Capability::Admin => "admin",
+    }
+}
+
+/// Render text as a terminal QR code
+pub fn render_qr(data: &str) -> Option<String> {
+    let code = QrCode::new(data.as_bytes()).ok()?;
+    Some(
+        code.render::<unicode::Dense1x2>()
+            .dark_color(unicode::Dense1x2::Light)
+            .light_color(unicode::Dense1x2::Dark)
+            .build(),
+    )
+}
+
+/// Message the app authenticates with the pairing secret
+pub fn pairing_message(device_name: &str, public_key: &str) -> Vec<u8> {
+    format!(
+        "{}\n{}\n{}",
+        PAIRING_DOMAIN,
+        device_name,
+        public_key.trim_start_matches("0x").to_lowercase()
+    )
+    .into_bytes()
+}
+
+struct Session {
+    secret: [u8; 32],
+    capability: Capability,
+    expires_at: u64,
+    failed_attempts: u32,
+}
+
+/// The currently open pairing session, if any
+#[derive(Default)]
+pub struct Pairing {
+    session: Mutex<Option<Session>>,
+}
+
+impl Pairing {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Open a new session, replacing any previous one
+    pub fn open(&self, capability: Capability, ttl_secs: u64, now: u64) -> PairingOffer {
+        let mut secret = [0u8; 32];
+        rand::thread_rng().fill_bytes(&mut secret);
+        let expires_at = now + ttl_secs;
+
+        *self.session.lock().unwrap() = Some(Session {
+            secret,
+            capability,
+            expires_at,
+            failed_attempts: 0,
+        });
+
+        PairingOffer {
+            secret: hex::encode(secret),
+            capability,
+            expires_at,
+        }
+    }
+
+    /// Check a pairing request and, if valid, close the session and return
+    /// the device to enroll
+    pub fn complete(&self, req: &PairRequest, now: u64) -> Result<Device, PairingError> {
+        let mut guard = self.session.lock().unwrap();
+        let session = guard.as_mut().ok_or(PairingError::NoSession)?;
+        if now >= session.expires_at {
+            *guard = None;
+            return Err(PairingError::Expired);
+        }
+
+        let proof = hex::decode(req.proof.trim_start_matches("0x")).unwrap_or_default();
+        let mut mac = Hmac::<Sha256>::new_from_slice(&session.secret).expect("any key length");
+        mac.update(&pairing_message(&req.device_name, &req.public_key));
+        if mac.verify_slice(&proof).is_err() {
+            session.failed_attempts += 1;
+            if session.failed_attempts >= MAX_FAILED_ATTEMPTS {
+                *guard = None;
+            }
+            return Err(PairingError::BadProof);
+        }
+
+        let name = req.device_name.trim();
+        if name.is_empty() {
+            return Err(PairingError::MissingName);
+        }
+        let key = parse_public_key(&req.public_key).ok_or(PairingError::InvalidPublicKey)?;
+        let capability = session.capability;
+        *guard = None;
+
+        Ok(Device {
+            id: device_id(&key),
+            name: name.to_string(),
+            public_key: hex::encode(key.to_encoded_point(true).as_bytes()),
+            capability,
+            paired_at: now,
+            revoked_at: None,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k256::ecdsa::SigningKey;
+
+    fn request(secret_hex: &str, name: &str) -> PairRequest {
+        let key = SigningKey::random(&mut rand::thread_rng());
+        let public_key = hex::encode(key.verifying_key().to_encoded_point(false).as_bytes());
+        let mut mac = Hmac::<Sha256>::new_from_slice(&hex::decode(secret_hex).unwrap()).unwrap();
+        mac.update(&pairing_message(name, &public_key));
+        PairRequest {
+            device_name: name.to_string(),
+            public_key,
+            proof: hex::encode(mac.finalize().into_bytes()),
+        }
+    }
+
+    #[test]
+    fn test_pairing_is_one_time() {
+        let pairing = Pairing::new();
+        let offer = pairing.open(Capability::Sign, 300, 1_000);
+        let req = request(&offer.secret, "phone");
+
+        let device = pairing.complete(&req, 1_010).unwrap();
+        assert_eq!(device.capability, Capability::Sign);
+        assert_eq!(device.name, "phone");
+        assert!(device.is_active());
+
+        assert_eq!(pairing.complete(&req, 1_020), Err(PairingError::NoSession));
+    }
+
+    #[test]
+    fn test_wrong_secret_and_expiry() {
+        let pairing = Pairing::new();
+        let offer = pairing.open(Capability::Status, 300, 1_000);
+
+        let wrong = request(&hex::encode([7u8; 32]), "phone");
+        assert_eq!(pairing.complete(&wrong, 1_010), Err(PairingError::BadProof));
+
+        // Proof is bound to the name it was made for
+        let mut renamed = request(&offer.secret, "phone");
+        renamed.device_name = "laptop".to_string();
+        assert_eq!(
+            pairing.complete(&renamed, 1_010),
+            Err(PairingError::BadProof)
+        );
+
+        let req = request(&offer.secret, "phone");
+        assert_eq!(pairing.complete(&req, 1_300), Err(PairingError::Expired));
+    }
+
+    #[test]
+    fn test_session_closes_after_repeated_failures() {
+        let pairing = Pairing::new();
+        let offer = pairing.open(Capability::Sign, 300, 1_000);
+        let wrong = request(&hex::encode([7u8; 32]), "phone");
+        for _ in 0..MAX_FAILED_ATTEMPTS {
+            assert_eq!(pairing.complete(&wrong, 1_010), Err(PairingError::BadProof));
+        }
+
+        let req = request(&offer.secret, "phone");
+        assert_eq!(pairing.complete(&req, 1_010), Err(PairingError::NoSession));
+    }
+
+    #[test]
+    fn test_offer_uri_and_qr() {
+        let pairing = Pairing::new();
+        let offer = pairing.open(Capability::Sign, 300, 1_000);
+        let uri = offer.uri(Some("https://192.168.1.5:8080"), Some("ab12"));
+        assert!(uri.starts_with("sigil-bridge://pair?v=1&secret="));
+        assert!(uri.contains("&capability=sign&expires=1300"));
+        assert!(uri.ends_with("&url=https://192.168.1.5:8080&fingerprint=ab12"));
+        assert!(render_qr(&uri).is_some());
+    }
+}
@@ -0,0 +1,166 @@
+//! TLS for sigil-bridge
+//!
+//! The bridge serves HTTPS with a certificate it generates on first start
+//! (or one supplied by the operator). Apps don't rely on a CA: they pin the
+//! SHA-256 fingerprint shown in the pairing QR code.
+
+use anyhow::{anyhow, Context, Result};
+use axum::Router;
+use hyper_util::rt::{TokioExecutor, TokioIo};
+use hyper_util::server::conn::auto;
+use hyper_util::service::TowerToHyperService;
+use sha2::{Digest, Sha256};
+use std::path::Path;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::net::TcpListener;
+use tokio_rustls::rustls::pki_types::{CertificateDer, PrivateKeyDer};
+use tokio_rustls::rustls::{crypto::ring, ServerConfig};
+use tokio_rustls::TlsAcceptor;
+use tracing::{debug, info, warn};
+
+/// Time a client gets to finish the TLS handshake
+const HANDSHAKE_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Server certificate and the fingerprint apps pin
+pub struct TlsIdentity {
+    pub config: Arc<ServerConfig>,
+
+    /// Hex SHA-256 of the DER leaf certificate
+    pub fingerprint: String,
+}
+
+impl TlsIdentity {
+    /// Load the certificate and key, generating a self-signed pair if neither
+    /// file exists yet
+    pub fn load_or_generate(cert_path: &Path, key_path: &Path, names: &[String]) -> Result<Self> {
+        if !cert_path.exists() && !key_path.exists() {
+            generate(cert_path, key_path, names)?;
+            info!("Generated self-signed TLS certificate at {:?}", cert_path);
+        }
+        Self::load(cert_path, key_path)
+    }
+
+    /// Load a PEM certificate chain and private key
+    pub fn load(cert_path: &Path, key_path: &Path) -> Result<Self> {
+        let cert_pem = std::fs::read(cert_path)
+            .with_context(|| format!("Failed to read TLS certificate {:?}", cert_path))?;
+        let key_pem = std::fs::read(key_path)
+            .with_context(|| format!("Failed to read TLS key {:?}", key_path))?;
+
+        let certs: Vec<CertificateDer<'static>> =
+            rustls_pemfile::certs(&mut cert_pem.as_slice()).collect::<Result<_, _>>()?;
+        let key: PrivateKeyDer<'static> = rustls_pemfile::private_key(&mut key_pem.as_slice())?
+            .ok_or_else(|| anyhow!("No private key found in {:?}", key_path))?;
+        let leaf = certs
+            .first()
+            .ok_or_else(|| anyhow!("No certificate found in {:?}", cert_path))?;
+        let fingerprint = hex::encode(Sha256::digest(leaf.as_ref()));
+
+        let mut config = ServerConfig::builder_with_provider(Arc::new(ring::default_provider()))
+            .with_safe_default_protocol_versions()?
+            .with_no_client_auth()
+            .with_single_cert(certs, key)?;
+        config.alpn_protocols = vec![b"h2".to_vec(), b"http/1.1".to_vec()];
+
+        Ok(Self {
+            config: Arc::new(config),
+            fingerprint,
+        })
+    }
+}
+
+/// Write a new self-signed certificate and key
+fn generate(cert_path: &Path, key_path: &Path, names: &[String]) -> Result<()> {
+    let certified = rcgen::generate_simple_self_signed(names.to_vec())?;
+
+    for path in [cert_path, key_path] {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+    }
+    std::fs::write(cert_path, certified.cert.pem())?;
+    write_private(key_path, certified.key_pair.serialize_pem().as_bytes())?;
+    Ok(())
+}
+
+/// Write a file only the current user can read
+fn write_private(path: &Path, contents: &[u8]) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)?.write_all(contents)
+}
+
+/// Serve the app over TLS until the listener fails
+pub async fn serve(listener: TcpListener, identity: &TlsIdentity, app: Router) -> Result<()> {
+    let acceptor = TlsAcceptor::from(Arc::clone(&identity.config));
+
+    loop {
+        let (stream, peer) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(e) => {
+                // Usually out of file descriptors; back off instead of exiting
+                warn!("Accept failed: {}", e);
+                tokio::time::sleep(Duration::from_millis(100)).await;
+                continue;
+            }
+        };
+
+        let acceptor = acceptor.clone();
+        let app = app.clone();
+        tokio::spawn(async move {
+            let stream =
+                match tokio::time::timeout(HANDSHAKE_TIMEOUT, acceptor.accept(stream)).await {
+                    Ok(Ok(stream)) => stream,
+                    Ok(Err(e)) => {
+                        debug!("TLS handshake with {} failed: {}", peer, e);
+                        return;
+                    }
+                    Err(_) => {
+                        debug!("TLS handshake with {} timed out", peer);
+                        return;
+                    }
+                };
+
+            let service = TowerToHyperService::new(app);
+            if let Err(e) = auto::Builder::new(TokioExecutor::new())
+                .serve_connection_with_upgrades(TokioIo::new(stream), service)
+                .await
+            {
+                debug!("Connection from {} ended: {}", peer, e);
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_generated_certificate_is_reused() {
+        let dir = tempfile::tempdir().unwrap();
+        let cert = dir.path().join("cert.pem");
+        let key = dir.path().join("key.pem");
+        let names = vec!["sigil-bridge".to_string()];
+
+        let first = TlsIdentity::load_or_generate(&cert, &key, &names).unwrap();
+        let second = TlsIdentity::load_or_generate(&cert, &key, &names).unwrap();
+        assert_eq!(first.fingerprint, second.fingerprint);
+        assert_eq!(first.fingerprint.len(), 64);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&key).unwrap().permissions().mode();
+            assert_eq!(mode & 0o077, 0);
+        }
+    }
+}
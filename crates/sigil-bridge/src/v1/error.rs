@@ -0,0 +1,342 @@
+//! Errors of the `/v1` API
+//!
+//! Every failure is answered with `{"error": {"code", "message"}}`. Codes are
+//! stable: daemon failures carry the daemon's `DaemonError` kind, and the
+//! bridge adds its own codes for requests that never reach the daemon.
+
+use axum::{
+    async_trait,
+    extract::{
+        rejection::{JsonRejection, QueryRejection},
+        FromRequest, FromRequestParts, Request,
+    },
+    http::{request::Parts, StatusCode},
+    response::{IntoResponse, Response},
+    Json,
+};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
+use tracing::warn;
+
+use super::types::{ErrorBody, ErrorDetail};
+use crate::auth::AuthError;
+use crate::client::ClientError;
+
+/// Stable identifier of an API error
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ErrorCode {
+    // Daemon error kinds
+    Core,
+    Io,
+    Serialization,
+    NoDiskDetected,
+    MultipleDisksDetected,
+    ChildDiskNotFound,
+    NotASigilDisk,
+    DiskValidationFailed,
+    AgentShardNotFound,
+    PresigMismatch,
+    SigningFailed,
+    ZkProofFailed,
+    Ipc,
+    Config,
+    ChainRpc,
+    Udev,
+    Store,
+    Crypto,
+    IdempotencyConflict,
+    ClockRollback,
+    ClockJump,
+    TimeAttestation,
+    ApprovalNotFound,
+    ApprovalClosed,
+    SelfApproval,
+    ApprovalRejected,
+    Timeout,
+    Cancelled,
+    ShuttingDown,
+
+    // Raised by the bridge, or by the daemon without a kind
+    InvalidRequest,
+    Unauthorized,
+    Forbidden,
+    NotFound,
+    Conflict,
+    PayloadTooLarge,
+    DaemonUnavailable,
+    Unsupported,
+    Internal,
+}
+
+impl ErrorCode {
+    /// Every code, in documentation order
+    pub const ALL: &'static [ErrorCode] = &[
+        ErrorCode::Core,
+        ErrorCode::Io,
+        ErrorCode::Serialization,
+        ErrorCode::NoDiskDetected,
+        ErrorCode::MultipleDisksDetected,
+        ErrorCode::ChildDiskNotFound,
+        ErrorCode::NotASigilDisk,
+        ErrorCode::DiskValidationFailed,
+        ErrorCode::AgentShardNotFound,
+        ErrorCode::PresigMismatch,
+        ErrorCode::SigningFailed,
+        ErrorCode::ZkProofFailed,
+        ErrorCode::Ipc,
+        ErrorCode::Config,
+        ErrorCode::ChainRpc,
+        ErrorCode::Udev,
+        ErrorCode::Store,
+        ErrorCode::Crypto,
+        ErrorCode::IdempotencyConflict,
+        ErrorCode::ClockRollback,
+        ErrorCode::ClockJump,
+        ErrorCode::TimeAttestation,
+        ErrorCode::ApprovalNotFound,
+        ErrorCode::ApprovalClosed,
+        ErrorCode::SelfApproval,
+        ErrorCode::ApprovalRejected,
+        ErrorCode::Timeout,
+        ErrorCode::Cancelled,
+        ErrorCode::ShuttingDown,
+        ErrorCode::InvalidRequest,
+        ErrorCode::Unauthorized,
+        ErrorCode::Forbidden,
+        ErrorCode::NotFound,
+        ErrorCode::Conflict,
+        ErrorCode::PayloadTooLarge,
+        ErrorCode::DaemonUnavailable,
+        ErrorCode::Unsupported,
+        ErrorCode::Internal,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ErrorCode::Core => "core",
+            ErrorCode::Io => "io",
+            ErrorCode::Serialization => "serialization",
+            ErrorCode::NoDiskDetected => "no_disk_detected",
+            ErrorCode::MultipleDisksDetected => "multiple_disks_detected",
+            ErrorCode::ChildDiskNotFound => "child_disk_not_found",
+            ErrorCode::NotASigilDisk => "not_a_sigil_disk",
+            ErrorCode::DiskValidationFailed => "disk_validation_failed",
+            ErrorCode::AgentShardNotFound => "agent_shard_not_found",
+            ErrorCode::PresigMismatch => "presig_mismatch",
+            ErrorCode::SigningFailed => "signing_failed",
+            ErrorCode::ZkProofFailed => "zk_proof_failed",
+            ErrorCode::Ipc => "ipc",
+            ErrorCode::Config => "config",
+            ErrorCode::ChainRpc => "chain_rpc",
+            ErrorCode::Udev => "udev",
+            ErrorCode::Store => "store",
+            ErrorCode::Crypto => "crypto",
+            ErrorCode::IdempotencyConflict => "idempotency_conflict",
+            ErrorCode::ClockRollback => "clock_rollback",
+            ErrorCode::ClockJump => "clock_jump",
+            ErrorCode::TimeAttestation => "time_attestation",
+            ErrorCode::ApprovalNotFound => "approval_not_found",
+            ErrorCode::ApprovalClosed => "approval_closed",
+            ErrorCode::SelfApproval => "self_approval",
+            ErrorCode::ApprovalRejected => "approval_rejected",
+            ErrorCode::Timeout => "timeout",
+            ErrorCode::Cancelled => "cancelled",
+            ErrorCode::ShuttingDown => "shutting_down",
+            ErrorCode::InvalidRequest => "invalid_request",
+            ErrorCode::Unauthorized => "unauthorized",
+            ErrorCode::Forbidden => "forbidden",
+            ErrorCode::NotFound => "not_found",
+            ErrorCode::Conflict => "conflict",
+            ErrorCode::PayloadTooLarge => "payload_too_large",
+            ErrorCode::DaemonUnavailable => "daemon_unavailable",
+            ErrorCode::Unsupported => "unsupported",
+            ErrorCode::Internal => "internal",
+        }
+    }
+
+    /// Code for a daemon error, from its kind or else its gRPC status
+    pub fn from_daemon(kind: Option<&str>, status: tonic::Code) -> ErrorCode {
+        if let Some(code) = kind.and_then(|kind| Self::ALL.iter().find(|c| c.as_str() == kind)) {
+            return *code;
+        }
+        match status {
+            tonic::Code::InvalidArgument => ErrorCode::InvalidRequest,
+            tonic::Code::NotFound => ErrorCode::NotFound,
+            tonic::Code::AlreadyExists | tonic::Code::FailedPrecondition => ErrorCode::Conflict,
+            tonic::Code::PermissionDenied => ErrorCode::Forbidden,
+            tonic::Code::Unauthenticated => ErrorCode::Unauthorized,
+            tonic::Code::Unavailable => ErrorCode::DaemonUnavailable,
+            tonic::Code::DeadlineExceeded => ErrorCode::Timeout,
+            tonic::Code::Cancelled => ErrorCode::Cancelled,
+            tonic::Code::Unimplemented => ErrorCode::Unsupported,
+            _ => ErrorCode::Internal,
+        }
+    }
+
+    /// HTTP status answered with this code
+    pub fn http_status(&self) -> StatusCode {
+        match self {
+            ErrorCode::InvalidRequest => StatusCode::BAD_REQUEST,
+            ErrorCode::Unauthorized => StatusCode::UNAUTHORIZED,
+            ErrorCode::Forbidden | ErrorCode::SelfApproval | ErrorCode::ApprovalRejected => {
+                StatusCode::FORBIDDEN
+            }
+            ErrorCode::NotFound
+            | ErrorCode::ChildDiskNotFound
+            | ErrorCode::AgentShardNotFound
+            | ErrorCode::ApprovalNotFound => StatusCode::NOT_FOUND,
+            ErrorCode::Conflict
+            | ErrorCode::NoDiskDetected
+            | ErrorCode::MultipleDisksDetected
+            | ErrorCode::NotASigilDisk
+            | ErrorCode::DiskValidationFailed
+            | ErrorCode::PresigMismatch
+            | ErrorCode::IdempotencyConflict
+            | ErrorCode::ClockRollback
+            | ErrorCode::ClockJump
+            | ErrorCode::TimeAttestation
+            | ErrorCode::ApprovalClosed
+            | ErrorCode::Cancelled => StatusCode::CONFLICT,
+            ErrorCode::PayloadTooLarge => StatusCode::PAYLOAD_TOO_LARGE,
+            ErrorCode::Unsupported => StatusCode::NOT_IMPLEMENTED,
+            ErrorCode::DaemonUnavailable | ErrorCode::ShuttingDown => {
+                StatusCode::SERVICE_UNAVAILABLE
+            }
+            ErrorCode::Timeout => StatusCode::GATEWAY_TIMEOUT,
+            ErrorCode::Core
+            | ErrorCode::Io
+            | ErrorCode::Serialization
+            | ErrorCode::SigningFailed
+            | ErrorCode::ZkProofFailed
+            | ErrorCode::Ipc
+            | ErrorCode::Config
+            | ErrorCode::ChainRpc
+            | ErrorCode::Udev
+            | ErrorCode::Store
+            | ErrorCode::Crypto
+            | ErrorCode::Internal => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+/// A failed `/v1` request
+#[derive(Debug)]
+pub struct ApiError {
+    pub code: ErrorCode,
+    pub message: String,
+}
+
+impl ApiError {
+    pub fn new(code: ErrorCode, message: impl Into<String>) -> Self {
+        Self {
+            code,
+            message: message.into(),
+        }
+    }
+}
+
+impl IntoResponse for ApiError {
+    fn into_response(self) -> Response {
+        let status = self.code.http_status();
+        if status.is_server_error() {
+            warn!("Request failed ({}): {}", self.code.as_str(), self.message);
+        }
+        let body = ErrorBody {
+            error: ErrorDetail {
+                code: self.code,
+                message: self.message,
+            },
+        };
+        (status, Json(body)).into_response()
+    }
+}
+
+impl From<ClientError> for ApiError {
+    fn from(e: ClientError) -> Self {
+        match e {
+            ClientError::Unavailable(message) => ApiError::new(
+                ErrorCode::DaemonUnavailable,
+                format!("Daemon unavailable: {}", message),
+            ),
+            ClientError::Daemon {
+                status,
+                kind,
+                message,
+            } => ApiError::new(ErrorCode::from_daemon(kind.as_deref(), status), message),
+            ClientError::Unsupported(message) => ApiError::new(ErrorCode::Unsupported, message),
+        }
+    }
+}
+
+impl From<AuthError> for ApiError {
+    fn from(e: AuthError) -> Self {
+        let code = match e {
+            AuthError::InsufficientCapability { .. } | AuthError::Revoked => ErrorCode::Forbidden,
+            AuthError::BodyTooLarge => ErrorCode::PayloadTooLarge,
+            _ => ErrorCode::Unauthorized,
+        };
+        ApiError::new(code, format!("Access denied: {}", e))
+    }
+}
+
+impl From<JsonRejection> for ApiError {
+    fn from(e: JsonRejection) -> Self {
+        ApiError::new(ErrorCode::InvalidRequest, e.body_text())
+    }
+}
+
+impl From<QueryRejection> for ApiError {
+    fn from(e: QueryRejection) -> Self {
+        ApiError::new(ErrorCode::InvalidRequest, e.body_text())
+    }
+}
+
+/// JSON body extractor that answers malformed bodies with an [`ApiError`]
+pub struct ApiJson<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequest<S> for ApiJson<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request(request: Request, state: &S) -> Result<Self, Self::Rejection> {
+        let Json(value) = Json::<T>::from_request(request, state).await?;
+        Ok(ApiJson(value))
+    }
+}
+
+/// Query string extractor that answers malformed queries with an [`ApiError`]
+pub struct ApiQuery<T>(pub T);
+
+#[async_trait]
+impl<T, S> FromRequestParts<S> for ApiQuery<T>
+where
+    T: DeserializeOwned,
+    S: Send + Sync,
+{
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let axum::extract::Query(value) =
+            axum::extract::Query::<T>::from_request_parts(parts, state).await?;
+        Ok(ApiQuery(value))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sigil_daemon::DaemonError;
+
+    #[test]
+    fn test_every_daemon_code_has_an_error_code() {
+        for kind in DaemonError::CODES {
+            let code = ErrorCode::from_daemon(Some(kind), tonic::Code::Unknown);
+            assert_eq!(code.as_str(), *kind, "daemon code {} is not mapped", kind);
+        }
+    }
+}
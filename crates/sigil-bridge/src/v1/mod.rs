@@ -0,0 +1,828 @@
+//! Versioned HTTP API (`/v1`)
+//!
+//! Typed request and response bodies (see [`types`]), errors with stable
+//! codes (see [`error`]), an OpenAPI document generated from the route table
+//! (see [`openapi`]) and a WebSocket carrying daemon events. Authentication is
+//! the same signed-request scheme as the unversioned `/api` routes.
+
+pub mod error;
+pub mod openapi;
+pub mod types;
+
+use axum::{
+    extract::{
+        ws::{
+            rejection::WebSocketUpgradeRejection, CloseFrame, Message, WebSocket, WebSocketUpgrade,
+        },
+        Path as UrlPath, Request, State,
+    },
+    http::StatusCode,
+    middleware::{self, Next},
+    response::{IntoResponse, Response},
+    routing::{get, post},
+    Extension, Json, Router,
+};
+use futures_util::StreamExt;
+use std::sync::Arc;
+use tracing::{debug, info, warn};
+
+use crate::auth::{self, Authenticator, Capability, Device, RequireCapability};
+use crate::AppState;
+use error::{ApiError, ApiJson, ApiQuery, ErrorCode};
+use sigil_daemon::ipc::proto;
+use types::*;
+
+type ApiResult<T> = Result<Json<T>, ApiError>;
+
+/// WebSocket close codes (RFC 6455 section 7.4.1)
+const CLOSE_NORMAL: u16 = 1000;
+const CLOSE_GOING_AWAY: u16 = 1001;
+const CLOSE_INTERNAL_ERROR: u16 = 1011;
+
+/// `/v1` routes, each behind the capability listed in [`openapi::OPERATIONS`]
+pub fn router(authenticator: &Arc<Authenticator>) -> Router<AppState> {
+    let require = |capability: Capability| {
+        middleware::from_fn_with_state((Arc::clone(authenticator), capability), require)
+    };
+
+    let status_routes = Router::new()
+        .route("/v1/ping", get(ping))
+        .route("/v1/disks", get(disks))
+        .route("/v1/presigs", get(presigs))
+        .route("/v1/children", get(children))
+        .route("/v1/history", get(history))
+        .route("/v1/approvals", get(approvals))
+        .route("/v1/approvals/:approval_id", get(approval))
+        .route("/v1/events", get(events))
+        .route_layer(require(Capability::Status));
+
+    let sign_routes = Router::new()
+        .route("/v1/sign", post(sign))
+        .route("/v1/sign-batch", post(sign_batch))
+        .route("/v1/tx-hash", post(update_tx_hash))
+        .route_layer(require(Capability::Sign));
+
+    let approve_routes = Router::new()
+        .route("/v1/approvals/:approval_id/approve", post(approve))
+        .route("/v1/approvals/:approval_id/reject", post(reject))
+        .route_layer(require(Capability::Approve));
+
+    let admin_routes = Router::new()
+        .route("/v1/agent-shard", post(import_agent_shard))
+        .route("/v1/child-shares", post(import_child_shares))
+        .route_layer(require(Capability::Admin));
+
+    Router::new()
+        .route("/v1/openapi.json", get(openapi_document))
+        .merge(status_routes)
+        .merge(sign_routes)
+        .merge(approve_routes)
+        .merge(admin_routes)
+}
+
+/// Like [`auth::require_capability`], answering refusals with an [`ApiError`]
+async fn require(
+    State((authenticator, required)): State<RequireCapability>,
+    request: Request,
+    next: Next,
+) -> Response {
+    match auth::authenticate_request(&authenticator, required, request).await {
+        Ok(request) => next.run(request).await,
+        Err(e) => ApiError::from(e).into_response(),
+    }
+}
+
+async fn openapi_document() -> Json<serde_json::Value> {
+    Json(openapi::document())
+}
+
+async fn ping(State(state): State<AppState>) -> ApiResult<Pong> {
+    let pong = state.daemon_client.ping().await?;
+    Ok(Json(Pong {
+        version: pong.version,
+        uptime_seconds: pong.uptime_seconds,
+    }))
+}
+
+async fn disks(State(state): State<AppState>) -> ApiResult<DiskStatus> {
+    Ok(Json(state.daemon_client.get_disk_status().await?.into()))
+}
+
+async fn presigs(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<ChildQuery>,
+) -> ApiResult<PresigCount> {
+    let count = state
+        .daemon_client
+        .get_presig_count(query.child_id.as_deref())
+        .await?;
+    Ok(Json(count.into()))
+}
+
+async fn children(State(state): State<AppState>) -> ApiResult<Children> {
+    let children = state.daemon_client.list_children().await?;
+    Ok(Json(Children {
+        children: children.into_iter().map(Child::from).collect(),
+    }))
+}
+
+async fn history(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<HistoryQuery>,
+) -> ApiResult<UsageLog> {
+    Ok(Json(
+        state
+            .daemon_client
+            .query_usage_log(query.into())
+            .await?
+            .into(),
+    ))
+}
+
+async fn approvals(
+    State(state): State<AppState>,
+    ApiQuery(query): ApiQuery<ApprovalsQuery>,
+) -> ApiResult<Approvals> {
+    let approvals = state.daemon_client.list_approvals(query.all).await?;
+    Ok(Json(Approvals {
+        approvals: approvals.into_iter().map(Approval::from).collect(),
+    }))
+}
+
+async fn approval(
+    State(state): State<AppState>,
+    UrlPath(approval_id): UrlPath<String>,
+) -> ApiResult<Approval> {
+    Ok(Json(
+        state.daemon_client.get_approval(&approval_id).await?.into(),
+    ))
+}
+
+/// Answer 202 with the approval when the daemon holds a request
+fn pending(pending: proto::PendingApproval) -> Response {
+    (StatusCode::ACCEPTED, Json(PendingApproval::from(pending))).into_response()
+}
+
+async fn sign(
+    State(state): State<AppState>,
+    ApiJson(req): ApiJson<SignRequest>,
+) -> Result<Response, ApiError> {
+    let result = state.daemon_client.sign(req.into()).await?;
+    Ok(match result.pending_approval {
+        Some(approval) => pending(approval),
+        None => Json(SignResult::from(result)).into_response(),
+    })
+}
+
+async fn sign_batch(
+    State(state): State<AppState>,
+    ApiJson(req): ApiJson<SignBatchRequest>,
+) -> Result<Response, ApiError> {
+    let items = req.items.into_iter().map(Into::into).collect();
+    let response = state
+        .daemon_client
+        .sign_batch(req.child_id.as_deref(), items)
+        .await?;
+    Ok(match response.pending_approval {
+        Some(approval) => pending(approval),
+        None => Json(SignBatchResult {
+            results: response.results.into_iter().map(SignResult::from).collect(),
+        })
+        .into_response(),
+    })
+}
+
+async fn update_tx_hash(
+    State(state): State<AppState>,
+    ApiJson(req): ApiJson<UpdateTxHashRequest>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .daemon_client
+        .update_tx_hash(req.child_id.as_deref(), req.presig_index, &req.tx_hash)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn approve(
+    State(state): State<AppState>,
+    Extension(device): Extension<Device>,
+    UrlPath(approval_id): UrlPath<String>,
+    req: Option<ApiJson<DecisionRequest>>,
+) -> ApiResult<Approval> {
+    decide(state, device, approval_id, true, req).await
+}
+
+async fn reject(
+    State(state): State<AppState>,
+    Extension(device): Extension<Device>,
+    UrlPath(approval_id): UrlPath<String>,
+    req: Option<ApiJson<DecisionRequest>>,
+) -> ApiResult<Approval> {
+    decide(state, device, approval_id, false, req).await
+}
+
+async fn decide(
+    state: AppState,
+    device: Device,
+    approval_id: String,
+    approve: bool,
+    req: Option<ApiJson<DecisionRequest>>,
+) -> ApiResult<Approval> {
+    let reason = req.and_then(|ApiJson(req)| req.reason);
+    let approver = format!("{} (device {})", device.name, device.id);
+    info!(
+        "{} signing request {} from {}",
+        if approve { "Approving" } else { "Rejecting" },
+        approval_id,
+        approver
+    );
+    let approval = state
+        .daemon_client
+        .decide_approval(&approval_id, approve, &approver, reason.as_deref())
+        .await?;
+    Ok(Json(approval.into()))
+}
+
+async fn import_agent_shard(
+    State(state): State<AppState>,
+    ApiJson(req): ApiJson<ImportAgentShardRequest>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .daemon_client
+        .import_agent_shard(&req.agent_shard_hex)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+async fn import_child_shares(
+    State(state): State<AppState>,
+    ApiJson(req): ApiJson<ImportChildSharesRequest>,
+) -> Result<StatusCode, ApiError> {
+    state
+        .daemon_client
+        .import_child_shares(&req.shares_json, req.replace)
+        .await?;
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Upgrade to a WebSocket that forwards daemon events as JSON text messages
+///
+/// The daemon subscription is made before upgrading, so an unreachable
+/// daemon is reported as an ordinary error response.
+async fn events(
+    State(state): State<AppState>,
+    upgrade: Result<WebSocketUpgrade, WebSocketUpgradeRejection>,
+) -> Result<Response, ApiError> {
+    let upgrade = upgrade.map_err(|e| ApiError::new(ErrorCode::InvalidRequest, e.body_text()))?;
+    let stream = state.daemon_client.watch_events().await?;
+    Ok(upgrade.on_upgrade(move |socket| forward_events(socket, stream)))
+}
+
+async fn forward_events(mut socket: WebSocket, mut stream: tonic::Streaming<proto::DaemonEvent>) {
+    loop {
+        tokio::select! {
+            event = stream.next() => match event {
+                Some(Ok(event)) => {
+                    let Some(event) = Event::from_proto(event) else {
+                        continue;
+                    };
+                    let text = serde_json::to_string(&event).expect("events serialize");
+                    if socket.send(Message::Text(text)).await.is_err() {
+                        return;
+                    }
+                }
+                Some(Err(e)) => {
+                    warn!("Daemon event stream failed: {}", e);
+                    close(socket, CLOSE_INTERNAL_ERROR).await;
+                    return;
+                }
+                None => {
+                    close(socket, CLOSE_GOING_AWAY).await;
+                    return;
+                }
+            },
+            // Pings are answered by the socket itself; anything else the
+            // client sends is ignored until it closes
+            message = socket.recv() => match message {
+                Some(Ok(Message::Close(_))) | None => {
+                    close(socket, CLOSE_NORMAL).await;
+                    return;
+                }
+                Some(Ok(_)) => {}
+                Some(Err(e)) => {
+                    debug!("WebSocket read ended: {}", e);
+                    return;
+                }
+            },
+        }
+    }
+}
+
+/// Send a close frame with a status code (RFC 6455 section 7.4)
+async fn close(mut socket: WebSocket, code: u16) {
+    let frame = CloseFrame {
+        code,
+        reason: "".into(),
+    };
+    let _ = socket.send(Message::Close(Some(frame))).await;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::DaemonClient;
+    use crate::tests::{signed, test_state};
+    use axum::body::{to_bytes, Body};
+    use serde_json::Value;
+    use sigil_daemon::ipc::daemon_status;
+    use sigil_daemon::ipc::proto::signer_service_server::{SignerService, SignerServiceServer};
+    use sigil_daemon::DaemonError;
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tonic::codegen::BoxStream;
+    use tonic::{Request as GrpcRequest, Response as GrpcResponse, Status};
+    use tower::ServiceExt;
+
+    type GrpcResult<T> = std::result::Result<GrpcResponse<T>, Status>;
+
+    /// Daemon stand-in answering with fixed responses and real daemon errors
+    struct MockDaemon;
+
+    fn sign_response(presig_index: u32) -> proto::SignResponse {
+        proto::SignResponse {
+            signature: "ab".repeat(64),
+            presig_index,
+            proof_hash: "cd".repeat(32),
+            ..Default::default()
+        }
+    }
+
+    #[tonic::async_trait]
+    impl SignerService for MockDaemon {
+        async fn ping(
+            &self,
+            _: GrpcRequest<proto::PingRequest>,
+        ) -> GrpcResult<proto::PingResponse> {
+            Ok(GrpcResponse::new(proto::PingResponse {
+                version: "0.1.0".to_string(),
+                uptime_seconds: 42,
+            }))
+        }
+
+        async fn get_disk_status(
+            &self,
+            _: GrpcRequest<proto::GetDiskStatusRequest>,
+        ) -> GrpcResult<proto::GetDiskStatusResponse> {
+            Err(daemon_status("Disk status", DaemonError::NoDiskDetected))
+        }
+
+        async fn sign(
+            &self,
+            request: GrpcRequest<proto::SignRequest>,
+        ) -> GrpcResult<proto::SignResponse> {
+            // Large values are held for approval, as with a threshold policy
+            let response = if request.into_inner().value == "1000" {
+                proto::SignResponse {
+                    pending_approval: Some(proto::PendingApproval {
+                        approval_id: "ab12".to_string(),
+                        expires_at: 1_700_000_600,
+                    }),
+                    ..Default::default()
+                }
+            } else {
+                sign_response(3)
+            };
+            Ok(GrpcResponse::new(response))
+        }
+
+        async fn sign_batch(
+            &self,
+            _: GrpcRequest<proto::SignBatchRequest>,
+        ) -> GrpcResult<proto::SignBatchResponse> {
+            Err(daemon_status(
+                "Batch sign",
+                DaemonError::MultipleDisksDetected,
+            ))
+        }
+
+        type SignStreamStream = BoxStream<proto::SignProgress>;
+
+        async fn sign_stream(
+            &self,
+            _: GrpcRequest<proto::SignRequest>,
+        ) -> GrpcResult<Self::SignStreamStream> {
+            Err(Status::unimplemented("not mocked"))
+        }
+
+        async fn cancel_sign(
+            &self,
+            _: GrpcRequest<proto::CancelSignRequest>,
+        ) -> GrpcResult<proto::CancelSignResponse> {
+            Err(Status::unimplemented("not mocked"))
+        }
+
+        async fn update_tx_hash(
+            &self,
+            _: GrpcRequest<proto::UpdateTxHashRequest>,
+        ) -> GrpcResult<proto::UpdateTxHashResponse> {
+            Ok(GrpcResponse::new(proto::UpdateTxHashResponse {}))
+        }
+
+        async fn get_presig_count(
+            &self,
+            _: GrpcRequest<proto::GetPresigCountRequest>,
+        ) -> GrpcResult<proto::GetPresigCountResponse> {
+            Ok(GrpcResponse::new(proto::GetPresigCountResponse {
+                remaining: 7,
+                total: 10,
+            }))
+        }
+
+        async fn query_usage_log(
+            &self,
+            _: GrpcRequest<proto::QueryUsageLogRequest>,
+        ) -> GrpcResult<proto::QueryUsageLogResponse> {
+            Err(Status::invalid_argument("Invalid tx_hash filter"))
+        }
+
+        async fn list_children(
+            &self,
+            _: GrpcRequest<proto::ListChildrenRequest>,
+        ) -> GrpcResult<proto::ListChildrenResponse> {
+            Ok(GrpcResponse::new(proto::ListChildrenResponse {
+                children: vec![proto::ChildInfo {
+                    child_id: "ab".repeat(32),
+                    presigs_remaining: 7,
+                    presigs_total: 10,
+                    status: "active".to_string(),
+                }],
+            }))
+        }
+
+        type WatchDiskStream = BoxStream<proto::DiskEvent>;
+
+        async fn watch_disk(
+            &self,
+            _: GrpcRequest<proto::WatchDiskRequest>,
+        ) -> GrpcResult<Self::WatchDiskStream> {
+            Err(Status::unimplemented("not mocked"))
+        }
+
+        type WatchEventsStream = BoxStream<proto::DaemonEvent>;
+
+        async fn watch_events(
+            &self,
+            _: GrpcRequest<proto::WatchEventsRequest>,
+        ) -> GrpcResult<Self::WatchEventsStream> {
+            use proto::daemon_event::Event as E;
+
+            let events = vec![
+                Ok(proto::DaemonEvent {
+                    timestamp: 1_700_000_000,
+                    event: Some(E::DiskInserted(proto::DiskInserted {
+                        path: "/media/sigil".to_string(),
+                        child_id: "ab12cd34".to_string(),
+                        presigs_remaining: 7,
+                        days_until_expiry: 30,
+                    })),
+                }),
+                Ok(proto::DaemonEvent {
+                    timestamp: 1_700_000_001,
+                    event: Some(E::PresigConsumed(proto::PresigConsumed {
+                        child_id: "ab12cd34".to_string(),
+                        presig_index: 3,
+                        presigs_remaining: 6,
+                    })),
+                }),
+            ];
+            Ok(GrpcResponse::new(Box::pin(futures_util::stream::iter(
+                events,
+            ))))
+        }
+
+        async fn import_agent_shard(
+            &self,
+            _: GrpcRequest<proto::ImportAgentShardRequest>,
+        ) -> GrpcResult<proto::ImportAgentShardResponse> {
+            Ok(GrpcResponse::new(proto::ImportAgentShardResponse {}))
+        }
+
+        async fn import_child_shares(
+            &self,
+            _: GrpcRequest<proto::ImportChildSharesRequest>,
+        ) -> GrpcResult<proto::ImportChildSharesResponse> {
+            Ok(GrpcResponse::new(proto::ImportChildSharesResponse {}))
+        }
+
+        async fn export_audit_bundle(
+            &self,
+            _: GrpcRequest<proto::ExportAuditBundleRequest>,
+        ) -> GrpcResult<proto::ExportAuditBundleResponse> {
+            Err(Status::unimplemented("not mocked"))
+        }
+
+        async fn list_approvals(
+            &self,
+            _: GrpcRequest<proto::ListApprovalsRequest>,
+        ) -> GrpcResult<proto::ListApprovalsResponse> {
+            Ok(GrpcResponse::new(proto::ListApprovalsResponse::default()))
+        }
+
+        async fn get_approval(
+            &self,
+            request: GrpcRequest<proto::GetApprovalRequest>,
+        ) -> GrpcResult<proto::Approval> {
+            let approval_id = request.into_inner().approval_id;
+            Err(daemon_status(
+                "Get approval",
+                DaemonError::ApprovalNotFound(approval_id),
+            ))
+        }
+
+        async fn decide_approval(
+            &self,
+            _: GrpcRequest<proto::DecideApprovalRequest>,
+        ) -> GrpcResult<proto::Approval> {
+            Err(daemon_status("Decide approval", DaemonError::SelfApproval))
+        }
+    }
+
+    /// Serve the mock daemon on a socket in `dir` and return its path
+    fn spawn_mock_daemon(dir: &std::path::Path) -> String {
+        let socket_path = dir.join("sigil.sock");
+        let listener = tokio::net::UnixListener::bind(&socket_path).unwrap();
+        let incoming = futures_util::stream::unfold(listener, |listener| async move {
+            let stream = listener.accept().await.map(|(stream, _)| stream);
+            Some((stream, listener))
+        });
+        tokio::spawn(
+            tonic::transport::Server::builder()
+                .add_service(SignerServiceServer::new(MockDaemon))
+                .serve_with_incoming(incoming),
+        );
+        socket_path.to_str().unwrap().to_string()
+    }
+
+    /// App state for a device with `capability`, talking to the mock daemon
+    fn mock_state(
+        dir: &std::path::Path,
+        capability: Capability,
+    ) -> (AppState, k256::ecdsa::SigningKey) {
+        let (mut state, key) = test_state(dir, capability);
+        state.daemon_client = Arc::new(DaemonClient::new(&spawn_mock_daemon(dir)));
+        (state, key)
+    }
+
+    async fn call(app: &Router, request: Request) -> (StatusCode, Value) {
+        let response = app.clone().oneshot(request).await.unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let json = if body.is_empty() {
+            Value::Null
+        } else {
+            serde_json::from_slice(&body).unwrap()
+        };
+        (status, json)
+    }
+
+    #[tokio::test]
+    async fn test_responses_match_contract() {
+        let dir = tempfile::tempdir().unwrap();
+        let (state, key) = mock_state(dir.path(), Capability::Admin);
+        let app = crate::router(state);
+
+        let (status, body) = call(&app, signed(&key, "GET", "/v1/ping", "")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(
+            body,
+            serde_json::json!({ "version": "0.1.0", "uptime_seconds": 42 })
+        );
+
+        let (status, body) = call(&app, signed(&key, "GET", "/v1/presigs?child_id=ab12", "")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, serde_json::json!({ "remaining": 7, "total": 10 }));
+
+        let (status, body) = call(&app, signed(&key, "GET", "/v1/children", "")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["children"][0]["status"], "active");
+
+        let sign = r#"{"message_hash":"00","chain_id":1,"description":"transfer"}"#;
+        let (status, body) = call(&app, signed(&key, "POST", "/v1/sign", sign)).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body["presig_index"], 3);
+        assert_eq!(body["cached"], false);
+        assert!(body.get("type").is_none());
+
+        let held = r#"{"message_hash":"00","chain_id":1,"description":"transfer","value":"1000"}"#;
+        let (status, body) = call(&app, signed(&key, "POST", "/v1/sign", held)).await;
+        assert_eq!(status, StatusCode::ACCEPTED);
+        assert_eq!(
+            body,
+            serde_json::json!({ "approval_id": "ab12", "expires_at": 1_700_000_600u64 })
+        );
+
+        let tx_hash = r#"{"presig_index":3,"tx_hash":"00"}"#;
+        let (status, body) = call(&app, signed(&key, "POST", "/v1/tx-hash", tx_hash)).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+        assert_eq!(body, Value::Null);
+
+        let shard = r#"{"agent_shard_hex":"00"}"#;
+        let (status, _) = call(&app, signed(&key, "POST", "/v1/agent-shard", shard)).await;
+        assert_eq!(status, StatusCode::NO_CONTENT);
+
+        let (status, body) = call(&app, signed(&key, "GET", "/v1/approvals", "")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, serde_json::json!({ "approvals": [] }));
+    }
+
+    #[tokio::test]
+    async fn test_errors_carry_daemon_kinds() {
+        let dir = tempfile::tempdir().unwrap();
+        let (state, key) = mock_state(dir.path(), Capability::Approve);
+        let app = crate::router(state);
+
+        let cases = [
+            (
+                "GET",
+                "/v1/disks",
+                "",
+                StatusCode::CONFLICT,
+                "no_disk_detected",
+            ),
+            (
+                "GET",
+                "/v1/approvals/ab12",
+                "",
+                StatusCode::NOT_FOUND,
+                "approval_not_found",
+            ),
+            (
+                "POST",
+                "/v1/approvals/ab12/approve",
+                "{}",
+                StatusCode::FORBIDDEN,
+                "self_approval",
+            ),
+            (
+                "POST",
+                "/v1/sign-batch",
+                r#"{"items":[]}"#,
+                StatusCode::CONFLICT,
+                "multiple_disks_detected",
+            ),
+            // Raised without a kind, so the gRPC status decides
+            (
+                "GET",
+                "/v1/history?tx_hash=maybe",
+                "",
+                StatusCode::BAD_REQUEST,
+                "invalid_request",
+            ),
+            // Rejected by the bridge before reaching the daemon
+            (
+                "POST",
+                "/v1/sign",
+                r#"{"chain_id":1}"#,
+                StatusCode::BAD_REQUEST,
+                "invalid_request",
+            ),
+            (
+                "GET",
+                "/v1/history?limit=-1",
+                "",
+                StatusCode::BAD_REQUEST,
+                "invalid_request",
+            ),
+            (
+                "POST",
+                "/v1/child-shares",
+                "{}",
+                StatusCode::FORBIDDEN,
+                "forbidden",
+            ),
+        ];
+        for (method, path, body, expected_status, expected_code) in cases {
+            let (status, body) = call(&app, signed(&key, method, path, body)).await;
+            assert_eq!(status, expected_status, "{} {}", method, path);
+            assert_eq!(body["error"]["code"], expected_code, "{} {}", method, path);
+            assert!(body["error"]["message"].is_string());
+        }
+
+        let unsigned = Request::get("/v1/ping").body(Body::empty()).unwrap();
+        let (status, body) = call(&app, unsigned).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body["error"]["code"], "unauthorized");
+
+        // No daemon at all
+        let (state, key) = test_state(dir.path(), Capability::Status);
+        let app = crate::router(state);
+        let (status, body) = call(&app, signed(&key, "GET", "/v1/ping", "")).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(body["error"]["code"], "daemon_unavailable");
+    }
+
+    #[tokio::test]
+    async fn test_documented_routes_exist() {
+        let dir = tempfile::tempdir().unwrap();
+        let (state, _key) = test_state(dir.path(), Capability::Admin);
+        let app = crate::router(state);
+
+        // Every documented route answers, unsigned, with its documented auth
+        for operation in openapi::OPERATIONS {
+            let mut path = operation.path.to_string();
+            for param in openapi::path_params(operation.path) {
+                path = path.replace(&format!("{{{}}}", param), "ab12");
+            }
+            let request = Request::builder()
+                .method(operation.method.to_uppercase().as_str())
+                .uri(&path)
+                .body(Body::empty())
+                .unwrap();
+            let (status, _) = call(&app, request).await;
+            let expected = match operation.capability {
+                Some(_) => StatusCode::UNAUTHORIZED,
+                None => StatusCode::OK,
+            };
+            assert_eq!(status, expected, "{} {}", operation.method, path);
+        }
+
+        // And a route missing from the table doesn't exist
+        let request = Request::get("/v1/sign-frost").body(Body::empty()).unwrap();
+        let (status, _) = call(&app, request).await;
+        assert_eq!(status, StatusCode::NOT_FOUND);
+    }
+
+    /// Read one unmasked server frame: `(opcode, payload)`
+    async fn read_server_frame(stream: &mut tokio::net::TcpStream) -> (u8, Vec<u8>) {
+        let mut head = [0u8; 2];
+        stream.read_exact(&mut head).await.unwrap();
+        let len = match head[1] {
+            126 => stream.read_u16().await.unwrap() as usize,
+            len => len as usize,
+        };
+        let mut payload = vec![0u8; len];
+        stream.read_exact(&mut payload).await.unwrap();
+        (head[0] & 0x0f, payload)
+    }
+
+    #[tokio::test]
+    async fn test_events_websocket() {
+        let dir = tempfile::tempdir().unwrap();
+        let (state, key) = mock_state(dir.path(), Capability::Status);
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, crate::router(state)).await });
+
+        let request = signed(&key, "GET", "/v1/events", "");
+        let mut handshake = format!("GET /v1/events HTTP/1.1\r\nHost: {}\r\n", addr);
+        for name in [
+            auth::DEVICE_HEADER,
+            auth::TIMESTAMP_HEADER,
+            auth::NONCE_HEADER,
+            auth::SIGNATURE_HEADER,
+        ] {
+            let value = request.headers()[name].to_str().unwrap();
+            handshake.push_str(&format!("{}: {}\r\n", name, value));
+        }
+        handshake.push_str(
+            "Connection: Upgrade\r\nUpgrade: websocket\r\nSec-WebSocket-Version: 13\r\nSec-WebSocket-Key: dGhlIHNhbXBsZSBub25jZQ==\r\n\r\n",
+        );
+
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        stream.write_all(handshake.as_bytes()).await.unwrap();
+
+        // Read the response head byte by byte so no frame bytes are consumed
+        let mut head = Vec::new();
+        while !head.ends_with(b"\r\n\r\n") {
+            head.push(stream.read_u8().await.unwrap());
+        }
+        let head = String::from_utf8(head).unwrap().to_lowercase();
+        assert!(head.starts_with("http/1.1 101"), "{}", head);
+        assert!(head.contains("sec-websocket-accept: s3pplmbitxaq9kygzzhzrbk+xoo="));
+
+        let (opcode, payload) = read_server_frame(&mut stream).await;
+        assert_eq!(opcode, 0x1);
+        let event: Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(
+            event,
+            serde_json::json!({
+                "type": "disk_inserted",
+                "timestamp": 1_700_000_000u64,
+                "path": "/media/sigil",
+                "child_id": "ab12cd34",
+                "presigs_remaining": 7,
+                "days_until_expiry": 30
+            })
+        );
+
+        let (_, payload) = read_server_frame(&mut stream).await;
+        let event: Value = serde_json::from_slice(&payload).unwrap();
+        assert_eq!(event["type"], "presig_consumed");
+        assert_eq!(event["presig_index"], 3);
+
+        // The mock's stream ends, which closes the socket as going away
+        let (opcode, payload) = read_server_frame(&mut stream).await;
+        assert_eq!(opcode, 0x8);
+        assert_eq!(payload, CLOSE_GOING_AWAY.to_be_bytes());
+    }
+}
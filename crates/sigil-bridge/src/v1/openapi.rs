@@ -0,0 +1,537 @@
+//! OpenAPI document for the `/v1` API
+//!
+//! The document is generated from [`OPERATIONS`] and the [`Schema`] of each
+//! request and response type. Tests check the table against the router, so
+//! an undocumented route or a documented route that doesn't exist fails the
+//! build rather than reaching an app developer.
+
+use serde_json::{json, Map, Value};
+
+use super::error::ErrorCode;
+use super::types::*;
+use crate::auth::{self, Capability};
+
+/// JSON schema of a request or response type
+pub trait Schema {
+    /// Name under `#/components/schemas`
+    const NAME: &'static str;
+
+    fn schema() -> Value;
+}
+
+/// One documented endpoint
+pub struct Operation {
+    pub method: &'static str,
+    /// Path template, with parameters in braces
+    pub path: &'static str,
+    pub summary: &'static str,
+    /// Capability the device must hold; `None` for unauthenticated routes
+    pub capability: Option<Capability>,
+    pub query: &'static [Param],
+    /// Schema name of the JSON body, and whether it is required
+    pub body: Option<(&'static str, bool)>,
+    pub responses: &'static [Outcome],
+}
+
+/// A query parameter
+pub struct Param {
+    pub name: &'static str,
+    /// JSON schema type
+    pub kind: &'static str,
+    pub description: &'static str,
+}
+
+/// A successful response
+pub struct Outcome {
+    pub status: u16,
+    pub description: &'static str,
+    /// Schema name of the JSON body, if it has one
+    pub body: Option<&'static str>,
+}
+
+const CHILD_ID_PARAM: Param = Param {
+    name: "child_id",
+    kind: "string",
+    description: "Hex child ID or unique prefix; required when several disks are inserted",
+};
+
+const SIGNED: &[Outcome] = &[
+    Outcome {
+        status: 200,
+        description: "Signed",
+        body: Some(SignResult::NAME),
+    },
+    Outcome {
+        status: 202,
+        description: "Held for approval",
+        body: Some(PendingApproval::NAME),
+    },
+];
+
+const BATCH_SIGNED: &[Outcome] = &[
+    Outcome {
+        status: 200,
+        description: "Signed, one result per item",
+        body: Some(SignBatchResult::NAME),
+    },
+    Outcome {
+        status: 202,
+        description: "Held for approval",
+        body: Some(PendingApproval::NAME),
+    },
+];
+
+const DONE: &[Outcome] = &[Outcome {
+    status: 204,
+    description: "Done",
+    body: None,
+}];
+
+const APPROVAL: &[Outcome] = &[Outcome {
+    status: 200,
+    description: "The approval",
+    body: Some(Approval::NAME),
+}];
+
+/// Every `/v1` endpoint
+pub const OPERATIONS: &[Operation] = &[
+    Operation {
+        method: "get",
+        path: "/v1/openapi.json",
+        summary: "This document",
+        capability: None,
+        query: &[],
+        body: None,
+        responses: &[Outcome {
+            status: 200,
+            description: "OpenAPI document",
+            body: None,
+        }],
+    },
+    Operation {
+        method: "get",
+        path: "/v1/ping",
+        summary: "Check that the daemon is reachable",
+        capability: Some(Capability::Status),
+        query: &[],
+        body: None,
+        responses: &[Outcome {
+            status: 200,
+            description: "Daemon version and uptime",
+            body: Some(Pong::NAME),
+        }],
+    },
+    Operation {
+        method: "get",
+        path: "/v1/disks",
+        summary: "Inserted disks",
+        capability: Some(Capability::Status),
+        query: &[],
+        body: None,
+        responses: &[Outcome {
+            status: 200,
+            description: "Disk status",
+            body: Some(DiskStatus::NAME),
+        }],
+    },
+    Operation {
+        method: "get",
+        path: "/v1/presigs",
+        summary: "Presignatures left on a disk",
+        capability: Some(Capability::Status),
+        query: &[CHILD_ID_PARAM],
+        body: None,
+        responses: &[Outcome {
+            status: 200,
+            description: "Presignature count",
+            body: Some(PresigCount::NAME),
+        }],
+    },
+    Operation {
+        method: "get",
+        path: "/v1/children",
+        summary: "Children the daemon holds agent shares for",
+        capability: Some(Capability::Status),
+        query: &[],
+        body: None,
+        responses: &[Outcome {
+            status: 200,
+            description: "Children",
+            body: Some(Children::NAME),
+        }],
+    },
+    Operation {
+        method: "get",
+        path: "/v1/history",
+        summary: "Signatures from a child's usage log, newest first",
+        capability: Some(Capability::Status),
+        query: &[
+            CHILD_ID_PARAM,
+            Param {
+                name: "since",
+                kind: "integer",
+                description: "Unix time; signed at or after",
+            },
+            Param {
+                name: "until",
+                kind: "integer",
+                description: "Unix time; signed before",
+            },
+            Param {
+                name: "chain_id",
+                kind: "integer",
+                description: "Chain the signature was made for",
+            },
+            Param {
+                name: "tx_hash",
+                kind: "string",
+                description: "pending or filled",
+            },
+            Param {
+                name: "search",
+                kind: "string",
+                description: "Text to find in the description",
+            },
+            Param {
+                name: "offset",
+                kind: "integer",
+                description: "Matching entries to skip",
+            },
+            Param {
+                name: "limit",
+                kind: "integer",
+                description: "At most how many entries to return (default 50)",
+            },
+        ],
+        body: None,
+        responses: &[Outcome {
+            status: 200,
+            description: "A page of the usage log",
+            body: Some(UsageLog::NAME),
+        }],
+    },
+    Operation {
+        method: "get",
+        path: "/v1/approvals",
+        summary: "Signing requests held for approval, newest first",
+        capability: Some(Capability::Status),
+        query: &[Param {
+            name: "all",
+            kind: "boolean",
+            description: "Also list requests that are no longer pending",
+        }],
+        body: None,
+        responses: &[Outcome {
+            status: 200,
+            description: "Approvals",
+            body: Some(Approvals::NAME),
+        }],
+    },
+    Operation {
+        method: "get",
+        path: "/v1/approvals/{approval_id}",
+        summary: "One held signing request",
+        capability: Some(Capability::Status),
+        query: &[],
+        body: None,
+        responses: APPROVAL,
+    },
+    Operation {
+        method: "get",
+        path: "/v1/events",
+        summary: "WebSocket of disk and signing events; each text message is an Event",
+        capability: Some(Capability::Status),
+        query: &[],
+        body: None,
+        responses: &[Outcome {
+            status: 101,
+            description: "Switching to the WebSocket protocol",
+            body: Some(Event::NAME),
+        }],
+    },
+    Operation {
+        method: "post",
+        path: "/v1/sign",
+        summary: "Sign a message hash (ECDSA)",
+        capability: Some(Capability::Sign),
+        query: &[],
+        body: Some((SignRequest::NAME, true)),
+        responses: SIGNED,
+    },
+    Operation {
+        method: "post",
+        path: "/v1/sign-batch",
+        summary: "Sign several message hashes atomically (ECDSA)",
+        capability: Some(Capability::Sign),
+        query: &[],
+        body: Some((SignBatchRequest::NAME, true)),
+        responses: BATCH_SIGNED,
+    },
+    Operation {
+        method: "post",
+        path: "/v1/tx-hash",
+        summary: "Record the transaction a signature was broadcast in",
+        capability: Some(Capability::Sign),
+        query: &[],
+        body: Some((UpdateTxHashRequest::NAME, true)),
+        responses: DONE,
+    },
+    Operation {
+        method: "post",
+        path: "/v1/approvals/{approval_id}/approve",
+        summary: "Approve a held signing request; the device is recorded as approver",
+        capability: Some(Capability::Approve),
+        query: &[],
+        body: Some((DecisionRequest::NAME, false)),
+        responses: APPROVAL,
+    },
+    Operation {
+        method: "post",
+        path: "/v1/approvals/{approval_id}/reject",
+        summary: "Reject a held signing request",
+        capability: Some(Capability::Approve),
+        query: &[],
+        body: Some((DecisionRequest::NAME, false)),
+        responses: APPROVAL,
+    },
+    Operation {
+        method: "post",
+        path: "/v1/agent-shard",
+        summary: "Import the agent master shard",
+        capability: Some(Capability::Admin),
+        query: &[],
+        body: Some((ImportAgentShardRequest::NAME, true)),
+        responses: DONE,
+    },
+    Operation {
+        method: "post",
+        path: "/v1/child-shares",
+        summary: "Import a child's presignature shares",
+        capability: Some(Capability::Admin),
+        query: &[],
+        body: Some((ImportChildSharesRequest::NAME, true)),
+        responses: DONE,
+    },
+];
+
+/// Build the OpenAPI document
+pub fn document() -> Value {
+    let mut paths = Map::new();
+    for operation in OPERATIONS {
+        let entry = paths
+            .entry(operation.path)
+            .or_insert_with(|| Value::Object(Map::new()));
+        entry[operation.method] = operation_json(operation);
+    }
+
+    json!({
+        "openapi": "3.1.0",
+        "info": {
+            "title": "sigil-bridge",
+            "version": env!("CARGO_PKG_VERSION"),
+            "description": "HTTP API of the Sigil signing daemon. Errors are answered with an Error body whose code is stable across releases."
+        },
+        "paths": paths,
+        "components": {
+            "schemas": components(),
+            "securitySchemes": {
+                "device": {
+                    "type": "apiKey",
+                    "in": "header",
+                    "name": auth::DEVICE_HEADER,
+                    "description": format!(
+                        "Paired device ID. Every request also carries {}, {} and {}: a compact secp256k1 signature by the device key over the method, path, timestamp, nonce and body hash.",
+                        auth::TIMESTAMP_HEADER,
+                        auth::NONCE_HEADER,
+                        auth::SIGNATURE_HEADER
+                    )
+                }
+            }
+        }
+    })
+}
+
+fn operation_json(operation: &Operation) -> Value {
+    let mut parameters: Vec<Value> = path_params(operation.path)
+        .map(|name| {
+            json!({
+                "name": name,
+                "in": "path",
+                "required": true,
+                "schema": { "type": "string" }
+            })
+        })
+        .collect();
+    parameters.extend(operation.query.iter().map(|param| {
+        json!({
+            "name": param.name,
+            "in": "query",
+            "required": false,
+            "description": param.description,
+            "schema": { "type": param.kind }
+        })
+    }));
+
+    let mut responses = Map::new();
+    for outcome in operation.responses {
+        let mut response = json!({ "description": outcome.description });
+        if let Some(body) = outcome.body {
+            response["content"] = json!({ "application/json": { "schema": reference(body) } });
+        }
+        responses.insert(outcome.status.to_string(), response);
+    }
+    responses.insert(
+        "default".to_string(),
+        json!({
+            "description": "Error",
+            "content": { "application/json": { "schema": reference(ErrorBody::NAME) } }
+        }),
+    );
+
+    let mut json = json!({
+        "summary": operation.summary,
+        "parameters": parameters,
+        "responses": responses
+    });
+    if let Some((body, required)) = operation.body {
+        json["requestBody"] = json!({
+            "required": required,
+            "content": { "application/json": { "schema": reference(body) } }
+        });
+    }
+    match operation.capability {
+        Some(capability) => {
+            json["security"] = json!([{ "device": [] }]);
+            json["x-sigil-capability"] = json!(capability);
+        }
+        None => json["security"] = json!([]),
+    }
+    json
+}
+
+/// Names of the `{parameters}` in a path template
+pub fn path_params(path: &str) -> impl Iterator<Item = &str> {
+    path.split('/')
+        .filter_map(|segment| segment.strip_prefix('{')?.strip_suffix('}'))
+}
+
+fn components() -> Map<String, Value> {
+    [
+        (ErrorBody::NAME, ErrorBody::schema()),
+        (Pong::NAME, Pong::schema()),
+        (DiskStatus::NAME, DiskStatus::schema()),
+        (Disk::NAME, Disk::schema()),
+        (PresigCount::NAME, PresigCount::schema()),
+        (Children::NAME, Children::schema()),
+        (Child::NAME, Child::schema()),
+        (UsageLog::NAME, UsageLog::schema()),
+        (UsageEntry::NAME, UsageEntry::schema()),
+        (SignRequest::NAME, SignRequest::schema()),
+        (SignBatchRequest::NAME, SignBatchRequest::schema()),
+        (SignResult::NAME, SignResult::schema()),
+        (SignBatchResult::NAME, SignBatchResult::schema()),
+        (PendingApproval::NAME, PendingApproval::schema()),
+        (UpdateTxHashRequest::NAME, UpdateTxHashRequest::schema()),
+        (Approvals::NAME, Approvals::schema()),
+        (Approval::NAME, Approval::schema()),
+        (ApprovalItem::NAME, ApprovalItem::schema()),
+        (DecisionRequest::NAME, DecisionRequest::schema()),
+        (
+            ImportAgentShardRequest::NAME,
+            ImportAgentShardRequest::schema(),
+        ),
+        (
+            ImportChildSharesRequest::NAME,
+            ImportChildSharesRequest::schema(),
+        ),
+        (Event::NAME, Event::schema()),
+    ]
+    .into_iter()
+    .map(|(name, schema)| (name.to_string(), schema))
+    .collect()
+}
+
+/// Reference to a named schema
+pub fn reference(name: &str) -> Value {
+    json!({ "$ref": format!("#/components/schemas/{}", name) })
+}
+
+/// Object schema; `required` names the properties that are always present
+pub fn object(description: &str, properties: &[(&str, Value)], required: &[&str]) -> Value {
+    let properties: Map<String, Value> = properties
+        .iter()
+        .map(|(name, schema)| (name.to_string(), schema.clone()))
+        .collect();
+    json!({
+        "type": "object",
+        "description": description,
+        "properties": properties,
+        "required": required
+    })
+}
+
+pub fn string(description: &str) -> Value {
+    json!({ "type": "string", "description": description })
+}
+
+pub fn integer(description: &str) -> Value {
+    json!({ "type": "integer", "minimum": 0, "description": description })
+}
+
+pub fn boolean(description: &str) -> Value {
+    json!({ "type": "boolean", "description": description })
+}
+
+pub fn array(items: Value) -> Value {
+    json!({ "type": "array", "items": items })
+}
+
+/// Schema of every error code
+pub fn error_codes() -> Value {
+    json!({
+        "type": "string",
+        "enum": ErrorCode::ALL.iter().map(ErrorCode::as_str).collect::<Vec<_>>()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Collect every `$ref` in a JSON value
+    fn refs<'a>(value: &'a Value, found: &mut Vec<&'a str>) {
+        match value {
+            Value::Object(map) => {
+                if let Some(Value::String(target)) = map.get("$ref") {
+                    found.push(target);
+                }
+                map.values().for_each(|v| refs(v, found));
+            }
+            Value::Array(items) => items.iter().for_each(|v| refs(v, found)),
+            _ => {}
+        }
+    }
+
+    #[test]
+    fn test_document_references_resolve() {
+        let document = document();
+        let schemas = document["components"]["schemas"].as_object().unwrap();
+
+        let mut found = Vec::new();
+        refs(&document, &mut found);
+        assert!(!found.is_empty());
+        for target in found {
+            let name = target.strip_prefix("#/components/schemas/").unwrap();
+            assert!(schemas.contains_key(name), "unresolved {}", target);
+        }
+
+        let approve = &document["paths"]["/v1/approvals/{approval_id}/approve"]["post"];
+        assert_eq!(approve["x-sigil-capability"], "approve");
+        assert_eq!(approve["parameters"][0]["name"], "approval_id");
+        assert_eq!(approve["requestBody"]["required"], false);
+        assert_eq!(
+            document["paths"]["/v1/openapi.json"]["get"]["security"],
+            json!([])
+        );
+    }
+}
@@ -0,0 +1,986 @@
+//! Request and response bodies of the `/v1` API
+//!
+//! Request types are shared with the unversioned `/api` routes, which accept
+//! the same fields. Responses carry no `type` tag; the endpoint determines the
+//! shape. Optional fields are left out when unset rather than sent as null.
+
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+use sigil_daemon::ipc::proto;
+
+use super::error::ErrorCode;
+use super::openapi::{array, boolean, error_codes, integer, object, reference, string, Schema};
+
+/// Error response body
+#[derive(Debug, Serialize)]
+pub struct ErrorBody {
+    pub error: ErrorDetail,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ErrorDetail {
+    pub code: ErrorCode,
+    pub message: String,
+}
+
+impl Schema for ErrorBody {
+    const NAME: &'static str = "Error";
+
+    fn schema() -> Value {
+        object(
+            "A failed request",
+            &[(
+                "error",
+                object(
+                    "What went wrong",
+                    &[
+                        ("code", error_codes()),
+                        ("message", string("Human-readable detail")),
+                    ],
+                    &["code", "message"],
+                ),
+            )],
+            &["error"],
+        )
+    }
+}
+
+/// Query naming the child to act on
+#[derive(Debug, Default, Deserialize)]
+pub struct ChildQuery {
+    #[serde(default)]
+    pub child_id: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SignRequest {
+    pub message_hash: String,
+    pub chain_id: u32,
+    pub description: String,
+    #[serde(default)]
+    pub idempotency_key: Option<String>,
+    #[serde(default)]
+    pub child_id: Option<String>,
+    /// Value moved, in the chain's smallest unit (decimal string)
+    #[serde(default)]
+    pub value: Option<String>,
+    /// Unsigned transaction (hex) whose signing hash is `message_hash`
+    #[serde(default)]
+    pub transaction: Option<String>,
+}
+
+impl From<SignRequest> for proto::SignRequest {
+    fn from(req: SignRequest) -> Self {
+        proto::SignRequest {
+            message_hash: req.message_hash,
+            chain_id: req.chain_id,
+            description: req.description,
+            idempotency_key: req.idempotency_key.unwrap_or_default(),
+            child_id: req.child_id.unwrap_or_default(),
+            value: req.value.unwrap_or_default(),
+            transaction: req.transaction.unwrap_or_default(),
+        }
+    }
+}
+
+impl Schema for SignRequest {
+    const NAME: &'static str = "SignRequest";
+
+    fn schema() -> Value {
+        object(
+            "A message hash to sign",
+            &[
+                ("message_hash", string("32-byte hash to sign (hex)")),
+                ("chain_id", integer("Chain the signature is for")),
+                ("description", string("Human-readable description, logged on the disk")),
+                (
+                    "idempotency_key",
+                    string("Retrying with the same key returns the original signature instead of consuming another presignature"),
+                ),
+                (
+                    "child_id",
+                    string("Hex child ID or unique prefix; required when several disks are inserted"),
+                ),
+                (
+                    "value",
+                    string("Value moved, in the chain's smallest unit (decimal); shown to approvers"),
+                ),
+                (
+                    "transaction",
+                    string("Unsigned EVM transaction (hex) whose signing hash is message_hash; approval thresholds only trust its value"),
+                ),
+            ],
+            &["message_hash", "chain_id", "description"],
+        )
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SignBatchRequest {
+    pub items: Vec<SignRequest>,
+    #[serde(default)]
+    pub child_id: Option<String>,
+}
+
+impl Schema for SignBatchRequest {
+    const NAME: &'static str = "SignBatchRequest";
+
+    fn schema() -> Value {
+        object(
+            "Message hashes signed in order with consecutive presignatures; the batch fails as a whole if any item fails",
+            &[
+                ("items", array(reference(SignRequest::NAME))),
+                ("child_id", string("Child for every item that doesn't set its own")),
+            ],
+            &["items"],
+        )
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct HistoryQuery {
+    pub child_id: Option<String>,
+    /// Unix time; signed at or after
+    pub since: Option<u64>,
+    /// Unix time; signed before
+    pub until: Option<u64>,
+    pub chain_id: Option<u32>,
+    /// pending or filled
+    pub tx_hash: Option<String>,
+    /// Text to find in the description
+    pub search: Option<String>,
+    #[serde(default)]
+    pub offset: u32,
+    #[serde(default)]
+    pub limit: u32,
+}
+
+impl From<HistoryQuery> for proto::QueryUsageLogRequest {
+    fn from(query: HistoryQuery) -> Self {
+        proto::QueryUsageLogRequest {
+            child_id: query.child_id.unwrap_or_default(),
+            since: query.since.unwrap_or(0),
+            until: query.until.unwrap_or(0),
+            chain_id: query
+                .chain_id
+                .map(|chain_id| chain_id.to_string())
+                .unwrap_or_default(),
+            tx_hash_status: query.tx_hash.unwrap_or_default(),
+            search: query.search.unwrap_or_default(),
+            offset: query.offset,
+            limit: query.limit,
+        }
+    }
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct ApprovalsQuery {
+    /// Also list requests that are no longer pending
+    #[serde(default)]
+    pub all: bool,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct DecisionRequest {
+    #[serde(default)]
+    pub reason: Option<String>,
+}
+
+impl Schema for DecisionRequest {
+    const NAME: &'static str = "DecisionRequest";
+
+    fn schema() -> Value {
+        object(
+            "An approval decision",
+            &[("reason", string("Note recorded with the decision"))],
+            &[],
+        )
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct UpdateTxHashRequest {
+    pub presig_index: u32,
+    pub tx_hash: String,
+    #[serde(default)]
+    pub child_id: Option<String>,
+}
+
+impl Schema for UpdateTxHashRequest {
+    const NAME: &'static str = "UpdateTxHashRequest";
+
+    fn schema() -> Value {
+        object(
+            "The transaction a signature was broadcast in",
+            &[
+                (
+                    "presig_index",
+                    integer("Presignature index of the signature"),
+                ),
+                ("tx_hash", string("Transaction hash (hex)")),
+                ("child_id", string("Child whose usage log to update")),
+            ],
+            &["presig_index", "tx_hash"],
+        )
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportAgentShardRequest {
+    pub agent_shard_hex: String,
+}
+
+impl Schema for ImportAgentShardRequest {
+    const NAME: &'static str = "ImportAgentShardRequest";
+
+    fn schema() -> Value {
+        object(
+            "The agent master shard",
+            &[("agent_shard_hex", string("32-byte shard (hex)"))],
+            &["agent_shard_hex"],
+        )
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ImportChildSharesRequest {
+    pub shares_json: String,
+    pub replace: bool,
+}
+
+impl Schema for ImportChildSharesRequest {
+    const NAME: &'static str = "ImportChildSharesRequest";
+
+    fn schema() -> Value {
+        object(
+            "A child's presignature shares",
+            &[
+                ("shares_json", string("JSON-encoded agent child data")),
+                (
+                    "replace",
+                    boolean("Replace shares already imported for the child"),
+                ),
+            ],
+            &["shares_json", "replace"],
+        )
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Pong {
+    pub version: String,
+    pub uptime_seconds: u64,
+}
+
+impl Schema for Pong {
+    const NAME: &'static str = "Pong";
+
+    fn schema() -> Value {
+        object(
+            "The daemon is reachable",
+            &[
+                ("version", string("Daemon version")),
+                (
+                    "uptime_seconds",
+                    integer("Seconds since the daemon started"),
+                ),
+            ],
+            &["version", "uptime_seconds"],
+        )
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct DiskStatus {
+    /// Whether any disk is inserted
+    pub detected: bool,
+    pub message: String,
+    pub disks: Vec<Disk>,
+}
+
+impl From<proto::GetDiskStatusResponse> for DiskStatus {
+    fn from(status: proto::GetDiskStatusResponse) -> Self {
+        DiskStatus {
+            detected: status.detected,
+            message: status.status_message,
+            disks: status.disks.into_iter().map(Disk::from).collect(),
+        }
+    }
+}
+
+impl Schema for DiskStatus {
+    const NAME: &'static str = "DiskStatus";
+
+    fn schema() -> Value {
+        object(
+            "Inserted disks",
+            &[
+                ("detected", boolean("Whether any disk is inserted")),
+                ("message", string("Summary of the disk status")),
+                ("disks", array(reference(Disk::NAME))),
+            ],
+            &["detected", "message", "disks"],
+        )
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Disk {
+    pub path: String,
+    pub child_id: String,
+    pub presigs_remaining: u32,
+    pub presigs_total: u32,
+    pub days_until_expiry: u32,
+    pub is_valid: bool,
+    pub status_message: String,
+}
+
+impl From<proto::DiskInfo> for Disk {
+    fn from(disk: proto::DiskInfo) -> Self {
+        Disk {
+            path: disk.path,
+            child_id: disk.child_id,
+            presigs_remaining: disk.presigs_remaining,
+            presigs_total: disk.presigs_total,
+            days_until_expiry: disk.days_until_expiry,
+            is_valid: disk.is_valid,
+            status_message: disk.status_message,
+        }
+    }
+}
+
+impl Schema for Disk {
+    const NAME: &'static str = "Disk";
+
+    fn schema() -> Value {
+        object(
+            "An inserted disk",
+            &[
+                ("path", string("Mount point")),
+                ("child_id", string("Short child ID (hex)")),
+                ("presigs_remaining", integer("Presignatures left")),
+                (
+                    "presigs_total",
+                    integer("Presignatures the disk was created with"),
+                ),
+                (
+                    "days_until_expiry",
+                    integer("Days until the disk must be reconciled"),
+                ),
+                ("is_valid", boolean("Whether the disk can sign")),
+                (
+                    "status_message",
+                    string("Why the disk can't sign, or a summary"),
+                ),
+            ],
+            &[
+                "path",
+                "child_id",
+                "presigs_remaining",
+                "presigs_total",
+                "days_until_expiry",
+                "is_valid",
+                "status_message",
+            ],
+        )
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PresigCount {
+    pub remaining: u32,
+    pub total: u32,
+}
+
+impl From<proto::GetPresigCountResponse> for PresigCount {
+    fn from(count: proto::GetPresigCountResponse) -> Self {
+        PresigCount {
+            remaining: count.remaining,
+            total: count.total,
+        }
+    }
+}
+
+impl Schema for PresigCount {
+    const NAME: &'static str = "PresigCount";
+
+    fn schema() -> Value {
+        object(
+            "Presignatures on a disk",
+            &[
+                ("remaining", integer("Presignatures left")),
+                ("total", integer("Presignatures the disk was created with")),
+            ],
+            &["remaining", "total"],
+        )
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Children {
+    pub children: Vec<Child>,
+}
+
+impl Schema for Children {
+    const NAME: &'static str = "Children";
+
+    fn schema() -> Value {
+        object(
+            "Children the daemon holds agent shares for",
+            &[("children", array(reference(Child::NAME)))],
+            &["children"],
+        )
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Child {
+    pub child_id: String,
+    pub presigs_remaining: u32,
+    pub presigs_total: u32,
+    pub status: String,
+}
+
+impl From<proto::ChildInfo> for Child {
+    fn from(child: proto::ChildInfo) -> Self {
+        Child {
+            child_id: child.child_id,
+            presigs_remaining: child.presigs_remaining,
+            presigs_total: child.presigs_total,
+            status: child.status,
+        }
+    }
+}
+
+impl Schema for Child {
+    const NAME: &'static str = "Child";
+
+    fn schema() -> Value {
+        object(
+            "A child key",
+            &[
+                ("child_id", string("Child ID (hex)")),
+                (
+                    "presigs_remaining",
+                    integer("Agent presignature shares left"),
+                ),
+                (
+                    "presigs_total",
+                    integer("Agent presignature shares imported"),
+                ),
+                ("status", string("Child status")),
+            ],
+            &["child_id", "presigs_remaining", "presigs_total", "status"],
+        )
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct UsageLog {
+    pub child_id: String,
+    pub entries: Vec<UsageEntry>,
+    /// Matching entries before pagination
+    pub total: u32,
+}
+
+impl From<proto::QueryUsageLogResponse> for UsageLog {
+    fn from(page: proto::QueryUsageLogResponse) -> Self {
+        UsageLog {
+            child_id: page.child_id,
+            entries: page.entries.into_iter().map(UsageEntry::from).collect(),
+            total: page.total,
+        }
+    }
+}
+
+impl Schema for UsageLog {
+    const NAME: &'static str = "UsageLog";
+
+    fn schema() -> Value {
+        object(
+            "A page of a child's usage log, newest first",
+            &[
+                ("child_id", string("Full child ID (hex)")),
+                ("entries", array(reference(UsageEntry::NAME))),
+                ("total", integer("Matching entries before pagination")),
+            ],
+            &["child_id", "entries", "total"],
+        )
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct UsageEntry {
+    pub presig_index: u32,
+    pub timestamp: u64,
+    pub message_hash: String,
+    pub signature: String,
+    pub chain_id: u32,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub tx_hash: Option<String>,
+    pub proof_hash: String,
+    pub description: String,
+}
+
+impl From<proto::UsageLogEntry> for UsageEntry {
+    fn from(entry: proto::UsageLogEntry) -> Self {
+        UsageEntry {
+            presig_index: entry.presig_index,
+            timestamp: entry.timestamp,
+            message_hash: entry.message_hash,
+            signature: entry.signature,
+            chain_id: entry.chain_id,
+            tx_hash: non_empty(entry.tx_hash),
+            proof_hash: entry.proof_hash,
+            description: entry.description,
+        }
+    }
+}
+
+impl Schema for UsageEntry {
+    const NAME: &'static str = "UsageEntry";
+
+    fn schema() -> Value {
+        object(
+            "A logged signature",
+            &[
+                ("presig_index", integer("Presignature index")),
+                ("timestamp", integer("Unix time of signing")),
+                ("message_hash", string("Hash that was signed (hex)")),
+                ("signature", string("64-byte signature (hex)")),
+                ("chain_id", integer("Chain the signature was made for")),
+                ("tx_hash", string("Transaction hash, once known")),
+                ("proof_hash", string("zkVM proof hash (hex)")),
+                ("description", string("Description given when signing")),
+            ],
+            &[
+                "presig_index",
+                "timestamp",
+                "message_hash",
+                "signature",
+                "chain_id",
+                "proof_hash",
+                "description",
+            ],
+        )
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SignResult {
+    pub signature: String,
+    pub presig_index: u32,
+    pub proof_hash: String,
+    /// True when this is the earlier result for a retried request
+    pub cached: bool,
+}
+
+impl From<proto::SignResponse> for SignResult {
+    fn from(result: proto::SignResponse) -> Self {
+        SignResult {
+            signature: result.signature,
+            presig_index: result.presig_index,
+            proof_hash: result.proof_hash,
+            cached: result.cached,
+        }
+    }
+}
+
+impl Schema for SignResult {
+    const NAME: &'static str = "SignResult";
+
+    fn schema() -> Value {
+        object(
+            "A signature",
+            &[
+                ("signature", string("64-byte ECDSA signature r || s (hex)")),
+                ("presig_index", integer("Presignature index used")),
+                ("proof_hash", string("zkVM proof hash (hex)")),
+                (
+                    "cached",
+                    boolean("True when this is the earlier result for a retried request"),
+                ),
+            ],
+            &["signature", "presig_index", "proof_hash", "cached"],
+        )
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct SignBatchResult {
+    pub results: Vec<SignResult>,
+}
+
+impl Schema for SignBatchResult {
+    const NAME: &'static str = "SignBatchResult";
+
+    fn schema() -> Value {
+        object(
+            "Signatures, one per request item in request order",
+            &[("results", array(reference(SignResult::NAME)))],
+            &["results"],
+        )
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct PendingApproval {
+    pub approval_id: String,
+    /// Unix time after which the request expires unapproved
+    pub expires_at: u64,
+}
+
+impl From<proto::PendingApproval> for PendingApproval {
+    fn from(pending: proto::PendingApproval) -> Self {
+        PendingApproval {
+            approval_id: pending.approval_id,
+            expires_at: pending.expires_at,
+        }
+    }
+}
+
+impl Schema for PendingApproval {
+    const NAME: &'static str = "PendingApproval";
+
+    fn schema() -> Value {
+        object(
+            "A request held until someone approves it",
+            &[
+                ("approval_id", string("Approval to watch or decide")),
+                (
+                    "expires_at",
+                    integer("Unix time after which the request expires unapproved"),
+                ),
+            ],
+            &["approval_id", "expires_at"],
+        )
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Approvals {
+    pub approvals: Vec<Approval>,
+}
+
+impl Schema for Approvals {
+    const NAME: &'static str = "Approvals";
+
+    fn schema() -> Value {
+        object(
+            "Held signing requests, newest first",
+            &[("approvals", array(reference(Approval::NAME)))],
+            &["approvals"],
+        )
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Approval {
+    pub approval_id: String,
+    /// pending, approved, signing, signed, rejected or expired
+    pub status: String,
+    pub items: Vec<ApprovalItem>,
+    pub requested_by: String,
+    pub created_at: u64,
+    pub expires_at: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub approver: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decided_by: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub decided_at: Option<u64>,
+    pub results: Vec<SignResult>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+}
+
+impl From<proto::Approval> for Approval {
+    fn from(approval: proto::Approval) -> Self {
+        Approval {
+            approval_id: approval.approval_id,
+            status: approval.status,
+            items: approval.items.into_iter().map(ApprovalItem::from).collect(),
+            requested_by: approval.requested_by,
+            created_at: approval.created_at,
+            expires_at: approval.expires_at,
+            approver: non_empty(approval.approver),
+            decided_by: non_empty(approval.decided_by),
+            reason: non_empty(approval.reason),
+            decided_at: Some(approval.decided_at).filter(|at| *at > 0),
+            results: approval.results.into_iter().map(SignResult::from).collect(),
+            error: non_empty(approval.error),
+        }
+    }
+}
+
+impl Schema for Approval {
+    const NAME: &'static str = "Approval";
+
+    fn schema() -> Value {
+        let status = json!({
+            "type": "string",
+            "enum": ["pending", "approved", "signing", "signed", "rejected", "expired"]
+        });
+        object(
+            "A signing request held for approval",
+            &[
+                ("approval_id", string("Approval ID")),
+                ("status", status),
+                ("items", array(reference(ApprovalItem::NAME))),
+                ("requested_by", string("Client that submitted the request")),
+                ("created_at", integer("Unix time the request was held")),
+                (
+                    "expires_at",
+                    integer("Unix time after which it expires unapproved"),
+                ),
+                ("approver", string("Person who decided")),
+                ("decided_by", string("Client that recorded the decision")),
+                ("reason", string("Note recorded with the decision")),
+                ("decided_at", integer("Unix time of the decision")),
+                ("results", array(reference(SignResult::NAME))),
+                ("error", string("Why signing the approved request failed")),
+            ],
+            &[
+                "approval_id",
+                "status",
+                "items",
+                "requested_by",
+                "created_at",
+                "expires_at",
+                "results",
+            ],
+        )
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct ApprovalItem {
+    pub message_hash: String,
+    pub chain_id: u32,
+    pub description: String,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub value: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub child_id: Option<String>,
+}
+
+impl From<proto::SignRequest> for ApprovalItem {
+    fn from(item: proto::SignRequest) -> Self {
+        ApprovalItem {
+            message_hash: item.message_hash,
+            chain_id: item.chain_id,
+            description: item.description,
+            value: non_empty(item.value),
+            child_id: non_empty(item.child_id),
+        }
+    }
+}
+
+impl Schema for ApprovalItem {
+    const NAME: &'static str = "ApprovalItem";
+
+    fn schema() -> Value {
+        object(
+            "A requested signature, as submitted",
+            &[
+                ("message_hash", string("Hash to sign (hex)")),
+                ("chain_id", integer("Chain the signature is for")),
+                ("description", string("Human-readable description")),
+                ("value", string("Value moved (decimal)")),
+                ("child_id", string("Child to sign with")),
+            ],
+            &["message_hash", "chain_id", "description"],
+        )
+    }
+}
+
+/// A disk or signing event, sent as one WebSocket text message
+#[derive(Debug, Serialize)]
+pub struct Event {
+    /// Unix time the daemon raised the event
+    pub timestamp: u64,
+    #[serde(flatten)]
+    pub kind: EventKind,
+}
+
+#[derive(Debug, Serialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum EventKind {
+    DiskInserted {
+        path: String,
+        child_id: String,
+        presigs_remaining: u32,
+        days_until_expiry: u32,
+    },
+    DiskRemoved {
+        path: String,
+    },
+    DiskValidationFailed {
+        path: String,
+        reason: String,
+    },
+    PresigConsumed {
+        child_id: String,
+        presig_index: u32,
+        presigs_remaining: u32,
+    },
+    LowPresigWarning {
+        child_id: String,
+        presigs_remaining: u32,
+        threshold: u32,
+    },
+    ExpiryWarning {
+        child_id: String,
+        days_until_expiry: u32,
+    },
+    ApprovalRequested {
+        approval_id: String,
+        requested_by: String,
+        descriptions: Vec<String>,
+        expires_at: u64,
+    },
+    ApprovalResolved {
+        approval_id: String,
+        status: String,
+    },
+    SignatureNotLanded {
+        child_id: String,
+        presig_index: u32,
+        chain_id: u32,
+        message_hash: String,
+        signed_at: u64,
+    },
+}
+
+impl Event {
+    /// Convert a daemon event; `None` if it carries no event
+    pub fn from_proto(event: proto::DaemonEvent) -> Option<Self> {
+        use proto::daemon_event::Event as E;
+
+        let kind = match event.event? {
+            E::DiskInserted(e) => EventKind::DiskInserted {
+                path: e.path,
+                child_id: e.child_id,
+                presigs_remaining: e.presigs_remaining,
+                days_until_expiry: e.days_until_expiry,
+            },
+            E::DiskRemoved(e) => EventKind::DiskRemoved { path: e.path },
+            E::DiskValidationFailed(e) => EventKind::DiskValidationFailed {
+                path: e.path,
+                reason: e.reason,
+            },
+            E::PresigConsumed(e) => EventKind::PresigConsumed {
+                child_id: e.child_id,
+                presig_index: e.presig_index,
+                presigs_remaining: e.presigs_remaining,
+            },
+            E::LowPresigWarning(e) => EventKind::LowPresigWarning {
+                child_id: e.child_id,
+                presigs_remaining: e.presigs_remaining,
+                threshold: e.threshold,
+            },
+            E::ExpiryWarning(e) => EventKind::ExpiryWarning {
+                child_id: e.child_id,
+                days_until_expiry: e.days_until_expiry,
+            },
+            E::ApprovalRequested(e) => EventKind::ApprovalRequested {
+                approval_id: e.approval_id,
+                requested_by: e.requested_by,
+                descriptions: e.descriptions,
+                expires_at: e.expires_at,
+            },
+            E::ApprovalResolved(e) => EventKind::ApprovalResolved {
+                approval_id: e.approval_id,
+                status: e.status,
+            },
+            E::SignatureNotLanded(e) => EventKind::SignatureNotLanded {
+                child_id: e.child_id,
+                presig_index: e.presig_index,
+                chain_id: e.chain_id,
+                message_hash: e.message_hash,
+                signed_at: e.signed_at,
+            },
+        };
+
+        Some(Event {
+            timestamp: event.timestamp,
+            kind,
+        })
+    }
+}
+
+impl Schema for Event {
+    const NAME: &'static str = "Event";
+
+    fn schema() -> Value {
+        let variant = |name: &str, description: &str, properties: &[(&str, Value)]| {
+            let mut all = vec![
+                ("type", json!({ "const": name })),
+                (
+                    "timestamp",
+                    integer("Unix time the daemon raised the event"),
+                ),
+            ];
+            all.extend(properties.iter().cloned());
+            let required: Vec<&str> = all.iter().map(|(name, _)| *name).collect();
+            object(description, &all, &required)
+        };
+
+        json!({
+            "description": "A disk or signing event, told apart by type",
+            "oneOf": [
+                variant("disk_inserted", "A disk was inserted", &[
+                    ("path", string("Mount point")),
+                    ("child_id", string("Short child ID (hex)")),
+                    ("presigs_remaining", integer("Presignatures left")),
+                    ("days_until_expiry", integer("Days until the disk must be reconciled")),
+                ]),
+                variant("disk_removed", "A disk was removed", &[
+                    ("path", string("Mount point")),
+                ]),
+                variant("disk_validation_failed", "An inserted disk failed validation", &[
+                    ("path", string("Mount point")),
+                    ("reason", string("Why validation failed")),
+                ]),
+                variant("presig_consumed", "A presignature was used", &[
+                    ("child_id", string("Short child ID (hex)")),
+                    ("presig_index", integer("Presignature index")),
+                    ("presigs_remaining", integer("Presignatures left")),
+                ]),
+                variant("low_presig_warning", "A disk is running out of presignatures", &[
+                    ("child_id", string("Short child ID (hex)")),
+                    ("presigs_remaining", integer("Presignatures left")),
+                    ("threshold", integer("Warning threshold")),
+                ]),
+                variant("expiry_warning", "A disk is close to expiry", &[
+                    ("child_id", string("Short child ID (hex)")),
+                    ("days_until_expiry", integer("Days until the disk must be reconciled")),
+                ]),
+                variant("approval_requested", "A signing request is waiting for approval", &[
+                    ("approval_id", string("Approval ID")),
+                    ("requested_by", string("Client that submitted the request")),
+                    ("descriptions", array(string("Description of a requested signature"))),
+                    ("expires_at", integer("Unix time after which it expires unapproved")),
+                ]),
+                variant("approval_resolved", "A held request left the pending state", &[
+                    ("approval_id", string("Approval ID")),
+                    ("status", string("approved, signed, rejected or expired")),
+                ]),
+                variant("signature_not_landed", "A logged signature never appeared on chain", &[
+                    ("child_id", string("Short child ID (hex)")),
+                    ("presig_index", integer("Presignature index")),
+                    ("chain_id", integer("Chain the signature was made for")),
+                    ("message_hash", string("Hash that was signed (hex)")),
+                    ("signed_at", integer("Unix time of signing")),
+                ]),
+            ]
+        })
+    }
+}
+
+fn non_empty(s: String) -> Option<String> {
+    Some(s).filter(|s| !s.is_empty())
+}
@@ -2,7 +2,9 @@
 
 use std::path::PathBuf;
 
-use sigil_daemon::ipc::{IpcClient, IpcRequest, IpcResponse};
+use sigil_core::TxHashStatus;
+use sigil_daemon::ipc::proto;
+use sigil_daemon::ipc::{IpcClient, SignerClient};
 
 /// Client for the Sigil daemon
 pub struct SigilClient {
@@ -35,6 +37,19 @@ pub enum ClientError {
 
     #[error("Daemon error: {0}")]
     DaemonError(String),
+
+    #[error("Signing request {approval_id} is awaiting approval")]
+    ApprovalPending {
+        approval_id: String,
+        /// Unix time the request expires unless approved
+        expires_at: u64,
+    },
+
+    #[error("Signing cancelled before the signature was committed")]
+    Cancelled,
+
+    #[error("Transaction decoding failed: {0}")]
+    Decode(#[from] crate::decode::DecodeError),
 }
 
 impl ClientError {
@@ -46,11 +61,21 @@ impl ClientError {
             _ => ClientError::DaemonError(e.to_string()),
         }
     }
+
+    fn from_status(status: tonic::Status) -> Self {
+        match status.code() {
+            tonic::Code::Unavailable => ClientError::DaemonNotRunning,
+            _ => ClientError::RequestFailed(status.message().to_string()),
+        }
+    }
 }
 
 pub type Result<T> = std::result::Result<T, ClientError>;
 
 /// Status of the signing disk
+///
+/// The single-disk fields are only set when exactly one disk is inserted;
+/// `disks` always lists every inserted disk.
 #[derive(Debug, Clone)]
 pub struct DiskStatus {
     pub detected: bool,
@@ -59,6 +84,36 @@ pub struct DiskStatus {
     pub presigs_total: Option<u32>,
     pub days_until_expiry: Option<u32>,
     pub is_valid: Option<bool>,
+    pub disks: Vec<DiskInfo>,
+}
+
+/// Status of one inserted disk
+#[derive(Debug, Clone)]
+pub struct DiskInfo {
+    pub path: String,
+    pub child_id: String,
+    pub presigs_remaining: u32,
+    pub presigs_total: u32,
+    pub days_until_expiry: u32,
+    pub is_valid: bool,
+    pub status_message: String,
+}
+
+/// Optional settings for a signing request
+#[derive(Debug, Clone, Default)]
+pub struct SignOptions {
+    /// Child to sign with (hex child ID or prefix); required when several
+    /// disks are inserted
+    pub child_id: Option<String>,
+    /// Retrying with the same key returns the original signature instead of
+    /// consuming another presig
+    pub idempotency_key: Option<String>,
+    /// Value the transaction moves, in the chain's smallest unit (decimal);
+    /// shown to approvers
+    pub value: Option<String>,
+    /// Unsigned transaction (hex) whose signing hash is the message; the
+    /// daemon's approval thresholds only trust the value it carries
+    pub transaction: Option<String>,
 }
 
 /// Result of a signing operation
@@ -67,6 +122,297 @@ pub struct SignResult {
     pub signature: String,
     pub presig_index: u32,
     pub proof_hash: String,
+    /// Whether the daemon replayed an earlier result for a retried request
+    pub cached: bool,
+}
+
+/// One message in a batch signing request
+#[derive(Debug, Clone)]
+pub struct SignBatchItem {
+    pub message_hash: String,
+    pub chain_id: u32,
+    pub description: String,
+    pub idempotency_key: Option<String>,
+    pub value: Option<String>,
+}
+
+/// A signing request held for human approval
+#[derive(Debug, Clone)]
+pub struct ApprovalInfo {
+    pub approval_id: String,
+    /// pending, approved, signing, signed, rejected or expired
+    pub status: String,
+    pub items: Vec<SignBatchItem>,
+    pub requested_by: String,
+    pub created_at: u64,
+    pub expires_at: u64,
+    /// Person who decided, if decided
+    pub approver: Option<String>,
+    pub reason: Option<String>,
+    /// Signatures, once signed
+    pub results: Vec<SignResult>,
+    /// Why signing the approved request failed
+    pub error: Option<String>,
+}
+
+impl ApprovalInfo {
+    fn from_proto(approval: proto::Approval) -> Self {
+        Self {
+            approval_id: approval.approval_id,
+            status: approval.status,
+            items: approval
+                .items
+                .into_iter()
+                .map(|item| SignBatchItem {
+                    message_hash: item.message_hash,
+                    chain_id: item.chain_id,
+                    description: item.description,
+                    idempotency_key: Some(item.idempotency_key).filter(|key| !key.is_empty()),
+                    value: Some(item.value).filter(|value| !value.is_empty()),
+                })
+                .collect(),
+            requested_by: approval.requested_by,
+            created_at: approval.created_at,
+            expires_at: approval.expires_at,
+            approver: Some(approval.approver).filter(|approver| !approver.is_empty()),
+            reason: Some(approval.reason).filter(|reason| !reason.is_empty()),
+            results: approval.results.into_iter().map(sign_result).collect(),
+            error: Some(approval.error).filter(|error| !error.is_empty()),
+        }
+    }
+}
+
+/// Filters and page for a usage log query; `None` fields don't filter
+#[derive(Debug, Clone, Default)]
+pub struct UsageQuery {
+    /// Child whose log to read (hex child ID or prefix); required when
+    /// several disks are inserted
+    pub child_id: Option<String>,
+    /// Signed at or after this Unix time
+    pub since: Option<u64>,
+    /// Signed before this Unix time
+    pub until: Option<u64>,
+    pub chain_id: Option<u32>,
+    pub tx_hash: Option<TxHashStatus>,
+    /// Text to find in the description, ignoring case
+    pub search: Option<String>,
+    /// Matching entries to skip
+    pub offset: u32,
+    /// At most this many entries; 0 takes the daemon's default
+    pub limit: u32,
+}
+
+/// One signature from a child's usage log
+#[derive(Debug, Clone)]
+pub struct UsageEntry {
+    pub presig_index: u32,
+    pub timestamp: u64,
+    pub message_hash: String,
+    pub signature: String,
+    pub chain_id: u32,
+    /// Transaction hash, once filled in
+    pub tx_hash: Option<String>,
+    pub proof_hash: String,
+    pub description: String,
+}
+
+/// One page of usage log entries, newest first
+#[derive(Debug, Clone)]
+pub struct UsagePage {
+    pub child_id: String,
+    pub entries: Vec<UsageEntry>,
+    /// Matching entries across all pages
+    pub total: u32,
+}
+
+/// Exported agent-side audit bundle
+#[derive(Debug, Clone)]
+pub struct AuditExport {
+    pub child_id: String,
+    pub record_count: u32,
+    pub bundle_json: String,
+    pub qr_chunks: Vec<String>,
+}
+
+/// Event pushed by the daemon over a `watch_events` subscription
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DaemonEvent {
+    /// A signing disk was inserted (also sent first for an already-present disk)
+    DiskInserted {
+        child_id: String,
+        presigs_remaining: u32,
+        days_until_expiry: u32,
+    },
+    /// The signing disk was removed
+    DiskRemoved,
+    /// An inserted disk failed validation
+    DiskValidationFailed { reason: String },
+    /// A presignature was consumed by a signing operation
+    PresigConsumed {
+        child_id: String,
+        presig_index: u32,
+        presigs_remaining: u32,
+    },
+    /// Remaining presigs fell to or below the daemon's warning threshold
+    LowPresigWarning {
+        child_id: String,
+        presigs_remaining: u32,
+        threshold: u32,
+    },
+    /// The disk is close to expiry
+    ExpiryWarning {
+        child_id: String,
+        days_until_expiry: u32,
+    },
+    /// A signing request is waiting for a human decision
+    ApprovalRequested {
+        approval_id: String,
+        requested_by: String,
+        descriptions: Vec<String>,
+        expires_at: u64,
+    },
+    /// A held signing request was approved, signed, rejected or expired
+    ApprovalResolved { approval_id: String, status: String },
+    /// A logged signature never showed up on chain
+    SignatureNotLanded {
+        child_id: String,
+        presig_index: u32,
+        chain_id: u32,
+        message_hash: String,
+        signed_at: u64,
+    },
+}
+
+impl DaemonEvent {
+    /// Convert from the wire representation, ignoring unknown event kinds
+    fn from_proto(event: proto::DaemonEvent) -> Option<Self> {
+        use proto::daemon_event::Event;
+
+        Some(match event.event? {
+            Event::DiskInserted(e) => Self::DiskInserted {
+                child_id: e.child_id,
+                presigs_remaining: e.presigs_remaining,
+                days_until_expiry: e.days_until_expiry,
+            },
+            Event::DiskRemoved(_) => Self::DiskRemoved,
+            Event::DiskValidationFailed(e) => Self::DiskValidationFailed { reason: e.reason },
+            Event::PresigConsumed(e) => Self::PresigConsumed {
+                child_id: e.child_id,
+                presig_index: e.presig_index,
+                presigs_remaining: e.presigs_remaining,
+            },
+            Event::LowPresigWarning(e) => Self::LowPresigWarning {
+                child_id: e.child_id,
+                presigs_remaining: e.presigs_remaining,
+                threshold: e.threshold,
+            },
+            Event::ExpiryWarning(e) => Self::ExpiryWarning {
+                child_id: e.child_id,
+                days_until_expiry: e.days_until_expiry,
+            },
+            Event::ApprovalRequested(e) => Self::ApprovalRequested {
+                approval_id: e.approval_id,
+                requested_by: e.requested_by,
+                descriptions: e.descriptions,
+                expires_at: e.expires_at,
+            },
+            Event::ApprovalResolved(e) => Self::ApprovalResolved {
+                approval_id: e.approval_id,
+                status: e.status,
+            },
+            Event::SignatureNotLanded(e) => Self::SignatureNotLanded {
+                child_id: e.child_id,
+                presig_index: e.presig_index,
+                chain_id: e.chain_id,
+                message_hash: e.message_hash,
+                signed_at: e.signed_at,
+            },
+        })
+    }
+}
+
+/// Update from a streamed signing operation
+#[derive(Debug, Clone)]
+pub enum SignUpdate {
+    /// The operation entered a stage (`reading_disk`, `checking_policy`,
+    /// `signing`, `proving` or `writing_disk`)
+    Stage(String),
+    /// The signature; always the last update
+    Done(SignResult),
+}
+
+/// A signing operation whose progress is streamed by the daemon
+///
+/// Dropping it before the signature is committed cancels the operation.
+pub struct SignOperation {
+    operation_id: String,
+    inner: tonic::Streaming<proto::SignProgress>,
+}
+
+impl SignOperation {
+    /// ID to pass to [`SigilClient::cancel_sign`]
+    pub fn id(&self) -> &str {
+        &self.operation_id
+    }
+
+    /// Wait for the next update
+    ///
+    /// Returns [`ClientError::Cancelled`] if the operation was cancelled in
+    /// time, and [`ClientError::ApprovalPending`] if it awaits approval.
+    pub async fn next(&mut self) -> Result<SignUpdate> {
+        loop {
+            let progress = self
+                .inner
+                .message()
+                .await
+                .map_err(sign_error)?
+                .ok_or_else(|| {
+                    ClientError::SigningFailed("Daemon closed the signing stream".to_string())
+                })?;
+
+            match progress.update {
+                Some(proto::sign_progress::Update::Stage(stage)) => {
+                    return Ok(SignUpdate::Stage(stage.stage))
+                }
+                Some(proto::sign_progress::Update::Result(result)) => {
+                    if let Some(pending) = result.pending_approval {
+                        return Err(ClientError::ApprovalPending {
+                            approval_id: pending.approval_id,
+                            expires_at: pending.expires_at,
+                        });
+                    }
+                    return Ok(SignUpdate::Done(sign_result(result)));
+                }
+                Some(proto::sign_progress::Update::Started(_)) | None => {}
+            }
+        }
+    }
+}
+
+/// Live subscription to daemon events
+pub struct EventStream {
+    inner: tonic::Streaming<proto::DaemonEvent>,
+}
+
+impl EventStream {
+    /// Wait for the next event, returning `None` when the daemon closes the stream
+    pub async fn next(&mut self) -> Result<Option<DaemonEvent>> {
+        loop {
+            match self
+                .inner
+                .message()
+                .await
+                .map_err(ClientError::from_status)?
+            {
+                Some(event) => {
+                    if let Some(event) = DaemonEvent::from_proto(event) {
+                        return Ok(Some(event));
+                    }
+                }
+                None => return Ok(None),
+            }
+        }
+    }
 }
 
 impl SigilClient {
@@ -93,46 +439,59 @@ impl SigilClient {
         }
     }
 
+    /// Open a gRPC connection to the daemon
+    async fn connect(&self) -> Result<SignerClient> {
+        self.inner
+            .connect()
+            .await
+            .map_err(ClientError::from_daemon_error)
+    }
+
     /// Check if the daemon is running
     pub async fn ping(&self) -> Result<String> {
-        match self.inner.request(&IpcRequest::Ping).await {
-            Ok(IpcResponse::Pong { version }) => Ok(version),
-            Ok(IpcResponse::Error { message }) => Err(ClientError::RequestFailed(message)),
-            Ok(_) => Err(ClientError::RequestFailed(
-                "Unexpected response".to_string(),
-            )),
-            Err(e) => Err(ClientError::from_daemon_error(e)),
-        }
+        let response = self
+            .connect()
+            .await?
+            .ping(proto::PingRequest {})
+            .await
+            .map_err(ClientError::from_status)?;
+
+        Ok(response.into_inner().version)
     }
 
     /// Get the current disk status
     pub async fn get_disk_status(&self) -> Result<DiskStatus> {
-        match self
-            .inner
-            .request(&IpcRequest::GetDiskStatus)
+        let status = self
+            .connect()
+            .await?
+            .get_disk_status(proto::GetDiskStatusRequest {})
             .await
-            .map_err(ClientError::from_daemon_error)?
-        {
-            IpcResponse::DiskStatus {
-                detected,
-                child_id,
-                presigs_remaining,
-                presigs_total,
-                days_until_expiry,
-                is_valid,
-            } => Ok(DiskStatus {
-                detected,
-                child_id,
-                presigs_remaining,
-                presigs_total,
-                days_until_expiry,
-                is_valid,
-            }),
-            IpcResponse::Error { message } => Err(ClientError::RequestFailed(message)),
-            _ => Err(ClientError::RequestFailed(
-                "Unexpected response".to_string(),
-            )),
-        }
+            .map_err(ClientError::from_status)?
+            .into_inner();
+
+        let detected = status.detected;
+        let single = detected && status.disks.len() <= 1;
+        Ok(DiskStatus {
+            detected,
+            child_id: single.then_some(status.child_id),
+            presigs_remaining: single.then_some(status.presigs_remaining),
+            presigs_total: single.then_some(status.presigs_total),
+            days_until_expiry: single.then_some(status.days_until_expiry),
+            is_valid: single.then_some(status.is_valid),
+            disks: status
+                .disks
+                .into_iter()
+                .map(|disk| DiskInfo {
+                    path: disk.path,
+                    child_id: disk.child_id,
+                    presigs_remaining: disk.presigs_remaining,
+                    presigs_total: disk.presigs_total,
+                    days_until_expiry: disk.days_until_expiry,
+                    is_valid: disk.is_valid,
+                    status_message: disk.status_message,
+                })
+                .collect(),
+        })
     }
 
     /// Sign a message hash
@@ -142,126 +501,387 @@ impl SigilClient {
         chain_id: u32,
         description: &str,
     ) -> Result<SignResult> {
-        let request = IpcRequest::Sign {
-            message_hash: message_hash.to_string(),
-            chain_id,
-            description: description.to_string(),
-        };
+        self.sign_with_options(message_hash, chain_id, description, &SignOptions::default())
+            .await
+    }
 
-        match self
-            .inner
-            .request(&request)
+    /// Sign a message hash with an explicit child, idempotency key or value
+    ///
+    /// Returns [`ClientError::ApprovalPending`] when the daemon holds the
+    /// request for approval; repeating the same call later returns the
+    /// signature once a person has approved it.
+    pub async fn sign_with_options(
+        &self,
+        message_hash: &str,
+        chain_id: u32,
+        description: &str,
+        options: &SignOptions,
+    ) -> Result<SignResult> {
+        let result = self
+            .connect()
+            .await?
+            .sign(sign_request(message_hash, chain_id, description, options))
             .await
-            .map_err(ClientError::from_daemon_error)?
-        {
-            IpcResponse::SignResult {
-                signature,
-                presig_index,
-                proof_hash,
-            } => Ok(SignResult {
-                signature,
-                presig_index,
-                proof_hash,
+            .map_err(sign_error)?
+            .into_inner();
+
+        if let Some(pending) = result.pending_approval {
+            return Err(ClientError::ApprovalPending {
+                approval_id: pending.approval_id,
+                expires_at: pending.expires_at,
+            });
+        }
+        Ok(sign_result(result))
+    }
+
+    /// Start signing a message hash, streaming the daemon's progress
+    ///
+    /// The operation can be cancelled until its presig is committed, with
+    /// [`SigilClient::cancel_sign`] or by dropping the returned handle.
+    pub async fn sign_streaming(
+        &self,
+        message_hash: &str,
+        chain_id: u32,
+        description: &str,
+        options: &SignOptions,
+    ) -> Result<SignOperation> {
+        let mut inner = self
+            .connect()
+            .await?
+            .sign_stream(sign_request(message_hash, chain_id, description, options))
+            .await
+            .map_err(sign_error)?
+            .into_inner();
+
+        match inner.message().await.map_err(sign_error)? {
+            Some(proto::SignProgress {
+                update: Some(proto::sign_progress::Update::Started(started)),
+            }) => Ok(SignOperation {
+                operation_id: started.operation_id,
+                inner,
             }),
-            IpcResponse::Error { message } => Err(ClientError::SigningFailed(message)),
-            _ => Err(ClientError::RequestFailed(
-                "Unexpected response".to_string(),
+            _ => Err(ClientError::SigningFailed(
+                "Daemon did not start the signing operation".to_string(),
             )),
         }
     }
 
+    /// Cancel a streamed signing operation
+    ///
+    /// Returns false if the signature was already committed; its stream
+    /// still delivers the result.
+    pub async fn cancel_sign(&self, operation_id: &str) -> Result<bool> {
+        let response = self
+            .connect()
+            .await?
+            .cancel_sign(proto::CancelSignRequest {
+                operation_id: operation_id.to_string(),
+            })
+            .await
+            .map_err(ClientError::from_status)?
+            .into_inner();
+
+        Ok(response.cancelled)
+    }
+
+    /// Sign several message hashes atomically
+    ///
+    /// Either every item is signed and committed with one disk write, or the
+    /// whole batch fails and no presig is consumed. `child_id` selects the
+    /// disk when several are inserted.
+    pub async fn sign_batch(
+        &self,
+        child_id: Option<&str>,
+        items: &[SignBatchItem],
+    ) -> Result<Vec<SignResult>> {
+        let request = proto::SignBatchRequest {
+            items: items
+                .iter()
+                .map(|item| proto::SignRequest {
+                    message_hash: item.message_hash.clone(),
+                    chain_id: item.chain_id,
+                    description: item.description.clone(),
+                    idempotency_key: item.idempotency_key.clone().unwrap_or_default(),
+                    child_id: String::new(),
+                    value: item.value.clone().unwrap_or_default(),
+                    transaction: String::new(),
+                })
+                .collect(),
+            child_id: child_id.unwrap_or_default().to_string(),
+        };
+
+        let response = self
+            .connect()
+            .await?
+            .sign_batch(request)
+            .await
+            .map_err(sign_error)?
+            .into_inner();
+
+        if let Some(pending) = response.pending_approval {
+            return Err(ClientError::ApprovalPending {
+                approval_id: pending.approval_id,
+                expires_at: pending.expires_at,
+            });
+        }
+        Ok(response.results.into_iter().map(sign_result).collect())
+    }
+
     /// Update transaction hash after broadcast
-    pub async fn update_tx_hash(&self, presig_index: u32, tx_hash: &str) -> Result<()> {
-        let request = IpcRequest::UpdateTxHash {
+    pub async fn update_tx_hash(
+        &self,
+        child_id: Option<&str>,
+        presig_index: u32,
+        tx_hash: &str,
+    ) -> Result<()> {
+        let request = proto::UpdateTxHashRequest {
             presig_index,
             tx_hash: tx_hash.to_string(),
+            child_id: child_id.unwrap_or_default().to_string(),
         };
 
-        match self
-            .inner
-            .request(&request)
+        self.connect()
+            .await?
+            .update_tx_hash(request)
             .await
-            .map_err(ClientError::from_daemon_error)?
-        {
-            IpcResponse::Ok => Ok(()),
-            IpcResponse::Error { message } => Err(ClientError::RequestFailed(message)),
-            _ => Err(ClientError::RequestFailed(
-                "Unexpected response".to_string(),
-            )),
-        }
+            .map_err(ClientError::from_status)?;
+        Ok(())
     }
 
-    /// Get the presig count
-    pub async fn get_presig_count(&self) -> Result<(u32, u32)> {
-        match self
-            .inner
-            .request(&IpcRequest::GetPresigCount)
+    /// Get the presig count of the selected disk
+    pub async fn get_presig_count(&self, child_id: Option<&str>) -> Result<(u32, u32)> {
+        let count = self
+            .connect()
+            .await?
+            .get_presig_count(proto::GetPresigCountRequest {
+                child_id: child_id.unwrap_or_default().to_string(),
+            })
             .await
-            .map_err(ClientError::from_daemon_error)?
-        {
-            IpcResponse::PresigCount { remaining, total } => Ok((remaining, total)),
-            IpcResponse::Error { message } => Err(ClientError::RequestFailed(message)),
-            _ => Err(ClientError::RequestFailed(
-                "Unexpected response".to_string(),
-            )),
-        }
+            .map_err(ClientError::from_status)?
+            .into_inner();
+
+        Ok((count.remaining, count.total))
+    }
+
+    /// Query the usage log of the selected disk
+    pub async fn query_usage_log(&self, query: &UsageQuery) -> Result<UsagePage> {
+        let request = proto::QueryUsageLogRequest {
+            child_id: query.child_id.clone().unwrap_or_default(),
+            since: query.since.unwrap_or(0),
+            until: query.until.unwrap_or(0),
+            chain_id: query
+                .chain_id
+                .map(|chain_id| chain_id.to_string())
+                .unwrap_or_default(),
+            tx_hash_status: query
+                .tx_hash
+                .map(|status| status.as_str().to_string())
+                .unwrap_or_default(),
+            search: query.search.clone().unwrap_or_default(),
+            offset: query.offset,
+            limit: query.limit,
+        };
+
+        let page = self
+            .connect()
+            .await?
+            .query_usage_log(request)
+            .await
+            .map_err(ClientError::from_status)?
+            .into_inner();
+
+        Ok(UsagePage {
+            child_id: page.child_id,
+            entries: page
+                .entries
+                .into_iter()
+                .map(|entry| UsageEntry {
+                    presig_index: entry.presig_index,
+                    timestamp: entry.timestamp,
+                    message_hash: entry.message_hash,
+                    signature: entry.signature,
+                    chain_id: entry.chain_id,
+                    tx_hash: Some(entry.tx_hash).filter(|tx_hash| !tx_hash.is_empty()),
+                    proof_hash: entry.proof_hash,
+                    description: entry.description,
+                })
+                .collect(),
+            total: page.total,
+        })
     }
 
     /// Import agent master shard
     pub async fn import_agent_shard(&self, agent_shard_hex: &str) -> Result<()> {
-        let request = IpcRequest::ImportAgentShard {
+        let request = proto::ImportAgentShardRequest {
             agent_shard_hex: agent_shard_hex.to_string(),
         };
 
-        match self
-            .inner
-            .request(&request)
+        self.connect()
+            .await?
+            .import_agent_shard(request)
             .await
-            .map_err(ClientError::from_daemon_error)?
-        {
-            IpcResponse::Ok => Ok(()),
-            IpcResponse::Error { message } => Err(ClientError::RequestFailed(message)),
-            _ => Err(ClientError::RequestFailed(
-                "Unexpected response".to_string(),
-            )),
-        }
+            .map_err(ClientError::from_status)?;
+        Ok(())
     }
 
     /// Import child presignature shares
     pub async fn import_child_shares(&self, shares_json: &str, replace: bool) -> Result<()> {
-        let request = IpcRequest::ImportChildShares {
+        let request = proto::ImportChildSharesRequest {
             shares_json: shares_json.to_string(),
             replace,
         };
 
-        match self
-            .inner
-            .request(&request)
+        self.connect()
+            .await?
+            .import_child_shares(request)
             .await
-            .map_err(ClientError::from_daemon_error)?
-        {
-            IpcResponse::Ok => Ok(()),
-            IpcResponse::Error { message } => Err(ClientError::RequestFailed(message)),
-            _ => Err(ClientError::RequestFailed(
-                "Unexpected response".to_string(),
-            )),
-        }
+            .map_err(ClientError::from_status)?;
+        Ok(())
     }
 
     /// List imported children
     pub async fn list_children(&self) -> Result<Vec<String>> {
-        match self
-            .inner
-            .request(&IpcRequest::ListChildren)
+        let response = self
+            .connect()
+            .await?
+            .list_children(proto::ListChildrenRequest {})
             .await
-            .map_err(ClientError::from_daemon_error)?
-        {
-            IpcResponse::Children { child_ids } => Ok(child_ids),
-            IpcResponse::Error { message } => Err(ClientError::RequestFailed(message)),
-            _ => Err(ClientError::RequestFailed(
-                "Unexpected response".to_string(),
-            )),
-        }
+            .map_err(ClientError::from_status)?
+            .into_inner();
+
+        Ok(response
+            .children
+            .into_iter()
+            .map(|child| child.child_id)
+            .collect())
+    }
+
+    /// Export the audit bundle for a child
+    pub async fn export_audit_bundle(&self, child_id: &str) -> Result<AuditExport> {
+        let request = proto::ExportAuditBundleRequest {
+            child_id: child_id.to_string(),
+        };
+
+        let export = self
+            .connect()
+            .await?
+            .export_audit_bundle(request)
+            .await
+            .map_err(ClientError::from_status)?
+            .into_inner();
+
+        Ok(AuditExport {
+            child_id: export.child_id,
+            record_count: export.record_count,
+            bundle_json: export.bundle_json,
+            qr_chunks: export.qr_chunks,
+        })
+    }
+
+    /// List signing requests held for approval, newest first
+    pub async fn list_approvals(&self, include_decided: bool) -> Result<Vec<ApprovalInfo>> {
+        let response = self
+            .connect()
+            .await?
+            .list_approvals(proto::ListApprovalsRequest { include_decided })
+            .await
+            .map_err(ClientError::from_status)?
+            .into_inner();
+
+        Ok(response
+            .approvals
+            .into_iter()
+            .map(ApprovalInfo::from_proto)
+            .collect())
+    }
+
+    /// Get one approval
+    pub async fn get_approval(&self, approval_id: &str) -> Result<ApprovalInfo> {
+        let approval = self
+            .connect()
+            .await?
+            .get_approval(proto::GetApprovalRequest {
+                approval_id: approval_id.to_string(),
+            })
+            .await
+            .map_err(ClientError::from_status)?
+            .into_inner();
+
+        Ok(ApprovalInfo::from_proto(approval))
+    }
+
+    /// Approve or reject a held signing request on behalf of `approver`
+    pub async fn decide_approval(
+        &self,
+        approval_id: &str,
+        approve: bool,
+        approver: &str,
+        reason: Option<&str>,
+    ) -> Result<ApprovalInfo> {
+        let approval = self
+            .connect()
+            .await?
+            .decide_approval(proto::DecideApprovalRequest {
+                approval_id: approval_id.to_string(),
+                approve,
+                approver: approver.to_string(),
+                reason: reason.unwrap_or_default().to_string(),
+            })
+            .await
+            .map_err(ClientError::from_status)?
+            .into_inner();
+
+        Ok(ApprovalInfo::from_proto(approval))
+    }
+
+    /// Subscribe to disk and signing events
+    pub async fn watch_events(&self) -> Result<EventStream> {
+        let inner = self
+            .connect()
+            .await?
+            .watch_events(proto::WatchEventsRequest {})
+            .await
+            .map_err(ClientError::from_status)?
+            .into_inner();
+
+        Ok(EventStream { inner })
+    }
+}
+
+/// Build the wire form of a signing request
+fn sign_request(
+    message_hash: &str,
+    chain_id: u32,
+    description: &str,
+    options: &SignOptions,
+) -> proto::SignRequest {
+    proto::SignRequest {
+        message_hash: message_hash.to_string(),
+        chain_id,
+        description: description.to_string(),
+        idempotency_key: options.idempotency_key.clone().unwrap_or_default(),
+        child_id: options.child_id.clone().unwrap_or_default(),
+        value: options.value.clone().unwrap_or_default(),
+        transaction: options.transaction.clone().unwrap_or_default(),
+    }
+}
+
+/// Map a failed signing RPC to a client error
+fn sign_error(status: tonic::Status) -> ClientError {
+    match status.code() {
+        tonic::Code::Unavailable => ClientError::DaemonNotRunning,
+        tonic::Code::Cancelled => ClientError::Cancelled,
+        _ => ClientError::SigningFailed(status.message().to_string()),
+    }
+}
+
+/// Convert a signature from its wire form
+fn sign_result(result: proto::SignResponse) -> SignResult {
+    SignResult {
+        signature: result.signature,
+        presig_index: result.presig_index,
+        proof_hash: result.proof_hash,
+        cached: result.cached,
     }
 }
 
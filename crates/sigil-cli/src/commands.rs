@@ -2,7 +2,10 @@
 
 use clap::{Parser, Subcommand};
 
-use crate::client::{ClientError, SigilClient};
+use sigil_core::TxHashStatus;
+
+use crate::client::{ApprovalInfo, ClientError, SigilClient, SignOptions, UsageQuery};
+use crate::decode::{self, CallInput, DecodedTransaction, SelectorRegistry};
 
 /// Sigil CLI - MPC-secured blockchain signing
 #[derive(Parser)]
@@ -32,19 +35,73 @@ pub enum Commands {
     /// Check signing disk status
     Disk,
 
-    /// Sign a message hash
+    /// Sign a message hash or an unsigned EVM transaction
     Sign {
         /// Message hash to sign (hex encoded)
+        #[arg(long, required_unless_present = "transaction")]
+        message: Option<String>,
+
+        /// Unsigned EVM transaction to sign (hex encoded); its hash is signed
+        /// and its decoded summary is logged ahead of --description
         #[arg(long)]
-        message: String,
+        transaction: Option<String>,
+
+        /// Selector registry file to decode --transaction with, on top of the built-in one
+        #[arg(long)]
+        registry: Option<std::path::PathBuf>,
 
         /// Chain ID
         #[arg(long, default_value = "1")]
         chain_id: u32,
 
-        /// Description for the usage log
-        #[arg(long, default_value = "CLI signing")]
-        description: String,
+        /// Description for the usage log [default: CLI signing]
+        #[arg(long)]
+        description: Option<String>,
+
+        /// Child to sign with (needed when several disks are inserted)
+        #[arg(long)]
+        child_id: Option<String>,
+
+        /// Value the transaction moves, in the chain's smallest unit (e.g. wei)
+        #[arg(long)]
+        value: Option<String>,
+    },
+
+    /// Decode an unsigned EVM transaction or calldata and flag risky calls
+    Decode {
+        /// Unsigned (or signed) EVM transaction (hex encoded)
+        #[arg(long, required_unless_present = "data", conflicts_with = "data")]
+        tx: Option<String>,
+
+        /// Calldata (hex encoded)
+        #[arg(long)]
+        data: Option<String>,
+
+        /// Contract the calldata is sent to
+        #[arg(long, requires = "data")]
+        to: Option<String>,
+
+        /// Value sent with the calldata, in wei
+        #[arg(long, requires = "data")]
+        value: Option<String>,
+
+        /// Chain the calldata is sent on
+        #[arg(long, requires = "data")]
+        chain_id: Option<u64>,
+
+        /// Selector registry file to use on top of the built-in one
+        #[arg(long)]
+        registry: Option<std::path::PathBuf>,
+
+        /// Print the full decoding as JSON
+        #[arg(long)]
+        json: bool,
+    },
+
+    /// Review signing requests held for approval
+    Approvals {
+        #[command(subcommand)]
+        command: ApprovalCommands,
     },
 
     /// Update transaction hash after broadcast
@@ -56,10 +113,57 @@ pub enum Commands {
         /// Transaction hash (hex encoded)
         #[arg(long)]
         tx_hash: String,
+
+        /// Child whose usage log to update (needed when several disks are inserted)
+        #[arg(long)]
+        child_id: Option<String>,
+    },
+
+    /// Show signatures from a child's usage log, newest first
+    History {
+        /// Child whose log to show (needed when several disks are inserted)
+        #[arg(long)]
+        child_id: Option<String>,
+
+        /// Only signatures made at or after this Unix time
+        #[arg(long)]
+        since: Option<u64>,
+
+        /// Only signatures made before this Unix time
+        #[arg(long)]
+        until: Option<u64>,
+
+        /// Only signatures for this chain (0 for FROST signatures)
+        #[arg(long)]
+        chain_id: Option<u32>,
+
+        /// Only signatures still waiting for their transaction hash
+        #[arg(long, conflicts_with = "filled")]
+        pending: bool,
+
+        /// Only signatures whose transaction hash has been recorded
+        #[arg(long)]
+        filled: bool,
+
+        /// Only signatures whose description contains this text
+        #[arg(long)]
+        search: Option<String>,
+
+        /// Number of signatures to show
+        #[arg(long, default_value = "20")]
+        limit: u32,
+
+        /// Number of matching signatures to skip
+        #[arg(long, default_value = "0")]
+        offset: u32,
     },
 
     /// Show presig count
-    PresigCount,
+    PresigCount {
+        /// Child to count (needed when several disks are inserted)
+        #[arg(long)]
+        child_id: Option<String>,
+    },
 
     /// Import agent shard (agent's portion of master key)
     ImportAgentShard {
@@ -84,6 +188,194 @@ pub enum Commands {
 
     /// List imported children
     ListChildren,
+
+    /// Export the agent audit log for a child (for mother reconciliation)
+    ExportAudit {
+        /// Child ID (full hex or short prefix)
+        #[arg(long)]
+        child_id: String,
+
+        /// Output file (defaults to sigil_audit_<child>.json)
+        #[arg(long)]
+        output: Option<std::path::PathBuf>,
+
+        /// Print QR chunk payloads instead of writing a file
+        #[arg(long)]
+        qr: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ApprovalCommands {
+    /// List signing requests awaiting approval
+    List {
+        /// Also show approved, signed, rejected and expired requests
+        #[arg(long)]
+        all: bool,
+    },
+
+    /// Show one request in full
+    Show {
+        /// Approval ID
+        approval_id: String,
+    },
+
+    /// Approve a signing request
+    Approve {
+        /// Approval ID
+        approval_id: String,
+
+        /// Name recorded as the approver (defaults to $USER)
+        #[arg(long)]
+        approver: Option<String>,
+
+        /// Note recorded with the decision
+        #[arg(long)]
+        reason: Option<String>,
+    },
+
+    /// Reject a signing request
+    Reject {
+        /// Approval ID
+        approval_id: String,
+
+        /// Name recorded as the approver (defaults to $USER)
+        #[arg(long)]
+        approver: Option<String>,
+
+        /// Note recorded with the decision
+        #[arg(long)]
+        reason: Option<String>,
+    },
+}
+
+/// Print an approval as a block of text
+/// The built-in selector registry, extended by a local file if given
+fn load_registry(path: Option<std::path::PathBuf>) -> Result<SelectorRegistry, ClientError> {
+    Ok(match path {
+        Some(path) => SelectorRegistry::load(&path)?,
+        None => SelectorRegistry::builtin(),
+    })
+}
+
+fn print_decoded(decoded: &DecodedTransaction) {
+    println!("{}", decoded.summary);
+    if let Some(chain_id) = decoded.chain_id {
+        println!("  Chain: {}", chain_id);
+    }
+    if let Some(to) = &decoded.to {
+        match &decoded.to_name {
+            Some(name) => println!("  To: {} ({})", to, name),
+            None => println!("  To: {}", to),
+        }
+    }
+    println!("  Value: {} wei", decoded.value);
+    if let Some(call) = &decoded.call {
+        print_call(call, 1);
+    }
+    for risk in &decoded.risks {
+        let severity = match risk.severity {
+            decode::Severity::High => "HIGH RISK",
+            decode::Severity::Warning => "Warning",
+        };
+        println!("  {}: {}", severity, risk.message);
+    }
+    if let Some(hash) = &decoded.signing_hash {
+        println!("  Signing hash: {}", hash);
+    }
+}
+
+fn print_call(call: &decode::DecodedCall, depth: usize) {
+    let indent = "  ".repeat(depth);
+    match &call.method {
+        Some(method) => println!(
+            "{}Method: {} [{}]",
+            indent,
+            call.label.as_deref().unwrap_or(method),
+            method
+        ),
+        None => println!("{}Method: unknown ({})", indent, call.selector),
+    }
+    for param in &call.params {
+        let raw = match param.value.as_str() {
+            Some(s) => s.to_string(),
+            None => param.value.to_string(),
+        };
+        let value = match &param.display {
+            Some(display) => format!("{} ({})", display, raw),
+            None => raw,
+        };
+        println!("{}  {}: {}", indent, param.name, value);
+    }
+    for nested in &call.calls {
+        print_call(nested, depth + 1);
+    }
+}
+
+fn print_approval(approval: &ApprovalInfo) {
+    println!(
+        "Approval {} ({}), requested by {}",
+        approval.approval_id, approval.status, approval.requested_by
+    );
+    for item in &approval.items {
+        println!(
+            "  - {} [chain {}{}] 0x{}",
+            item.description,
+            item.chain_id,
+            item.value
+                .as_ref()
+                .map(|value| format!(", value {}", value))
+                .unwrap_or_default(),
+            item.message_hash
+        );
+    }
+    if approval.status == "pending" {
+        println!("  Expires at {} (unix time)", approval.expires_at);
+    }
+    if let Some(approver) = &approval.approver {
+        println!(
+            "  Decided by {}{}",
+            approver,
+            approval
+                .reason
+                .as_ref()
+                .map(|reason| format!(": {}", reason))
+                .unwrap_or_default()
+        );
+    }
+    for result in &approval.results {
+        println!(
+            "  Signature: 0x{} (presig {})",
+            result.signature, result.presig_index
+        );
+    }
+    if let Some(error) = &approval.error {
+        println!("  Not signed yet: {}", error);
+    }
+}
+
+/// Approve or reject a request and report the outcome
+async fn decide(
+    client: &SigilClient,
+    approval_id: &str,
+    approve: bool,
+    approver: Option<String>,
+    reason: Option<String>,
+) -> Result<(), ClientError> {
+    let approver = approver
+        .or_else(|| std::env::var("USER").ok())
+        .unwrap_or_default();
+    let approval = client
+        .decide_approval(approval_id, approve, &approver, reason.as_deref())
+        .await?;
+
+    match approval.status.as_str() {
+        "signed" => println!("✓ Approved and signed"),
+        "approved" => println!("✓ Approved; it will be signed when the requester retries"),
+        _ => println!("✓ Rejected"),
+    }
+    print_approval(&approval);
+    Ok(())
 }
 
 /// Run the CLI
@@ -106,7 +398,21 @@ pub async fn run(cli: Cli) -> Result<(), ClientError> {
         Commands::Disk => {
             let status = client.get_disk_status().await?;
 
-            if status.detected {
+            if status.disks.len() > 1 {
+                println!("{} signing disks detected:", status.disks.len());
+                for disk in &status.disks {
+                    println!(
+                        "  sigil_{}  {}/{} presigs, expires in {} days, {} ({})",
+                        disk.child_id,
+                        disk.presigs_remaining,
+                        disk.presigs_total,
+                        disk.days_until_expiry,
+                        disk.status_message,
+                        disk.path
+                    );
+                }
+                println!("Select one with --child-id when signing");
+            } else if status.detected {
                 println!(
                     "Disk detected: sigil_{}",
                     status.child_id.unwrap_or_default()
@@ -133,9 +439,51 @@ pub async fn run(cli: Cli) -> Result<(), ClientError> {
 
         Commands::Sign {
             message,
+            transaction,
+            registry,
             chain_id,
             description,
+            child_id,
+            value,
         } => {
+            let (message, chain_id, description, value) = match &transaction {
+                Some(transaction) => {
+                    let decoded =
+                        decode::decode_transaction(transaction, &load_registry(registry)?)?;
+                    let signing_hash = decoded.signing_hash.clone().ok_or_else(|| {
+                        ClientError::RequestFailed("Transaction is already signed".to_string())
+                    })?;
+                    if let Some(message) = message {
+                        let message = message.strip_prefix("0x").unwrap_or(&message);
+                        if !signing_hash[2..].eq_ignore_ascii_case(message) {
+                            return Err(ClientError::RequestFailed(
+                                "--message does not match the transaction's signing hash"
+                                    .to_string(),
+                            ));
+                        }
+                    }
+                    print_decoded(&decoded);
+                    let chain_id = match decoded.chain_id {
+                        Some(id) => u32::try_from(id).map_err(|_| {
+                            ClientError::RequestFailed(format!("Chain ID {} is too large", id))
+                        })?,
+                        None => chain_id,
+                    };
+                    (
+                        signing_hash,
+                        chain_id,
+                        decoded.signing_description(description.as_deref().unwrap_or_default()),
+                        Some(decoded.value),
+                    )
+                }
+                None => (
+                    message.unwrap_or_default(),
+                    chain_id,
+                    description.unwrap_or_else(|| "CLI signing".to_string()),
+                    value,
+                ),
+            };
+
             // Check disk status first
             let status = client.get_disk_status().await?;
             if !status.detected {
@@ -145,23 +493,163 @@ pub async fn run(cli: Cli) -> Result<(), ClientError> {
             }
 
             println!("Signing message...");
-            let result = client.sign(&message, chain_id, &description).await?;
+            let options = SignOptions {
+                child_id,
+                value,
+                transaction,
+                ..Default::default()
+            };
+            let result = match client
+                .sign_with_options(&message, chain_id, &description, &options)
+                .await
+            {
+                Ok(result) => result,
+                Err(ClientError::ApprovalPending {
+                    approval_id,
+                    expires_at,
+                }) => {
+                    println!("Signing request {} is awaiting approval", approval_id);
+                    println!("Approve it with: sigil approvals approve {}", approval_id);
+                    println!(
+                        "Then run this command again before {} (unix time) to get the signature",
+                        expires_at
+                    );
+                    return Ok(());
+                }
+                Err(e) => return Err(e),
+            };
 
             println!("Signature: 0x{}", result.signature);
             println!("Presig index: {}", result.presig_index);
             println!("Proof hash: 0x{}", result.proof_hash);
         }
 
+        Commands::Decode {
+            tx,
+            data,
+            to,
+            value,
+            chain_id,
+            registry,
+            json,
+        } => {
+            let registry = load_registry(registry)?;
+            let decoded = match (tx, data) {
+                (Some(tx), _) => decode::decode_transaction(&tx, &registry)?,
+                (None, data) => decode::decode_calldata(
+                    &CallInput {
+                        chain_id,
+                        to,
+                        value,
+                        data: data.unwrap_or_default(),
+                    },
+                    &registry,
+                )?,
+            };
+            if json {
+                println!("{}", serde_json::to_string_pretty(&decoded)?);
+            } else {
+                print_decoded(&decoded);
+            }
+        }
+
+        Commands::Approvals { command } => match command {
+            ApprovalCommands::List { all } => {
+                let approvals = client.list_approvals(all).await?;
+                if approvals.is_empty() {
+                    println!("No signing requests awaiting approval");
+                }
+                for approval in &approvals {
+                    print_approval(approval);
+                }
+            }
+            ApprovalCommands::Show { approval_id } => {
+                print_approval(&client.get_approval(&approval_id).await?);
+            }
+            ApprovalCommands::Approve {
+                approval_id,
+                approver,
+                reason,
+            } => {
+                decide(&client, &approval_id, true, approver, reason).await?;
+            }
+            ApprovalCommands::Reject {
+                approval_id,
+                approver,
+                reason,
+            } => {
+                decide(&client, &approval_id, false, approver, reason).await?;
+            }
+        },
+
         Commands::UpdateTx {
             presig_index,
             tx_hash,
+            child_id,
         } => {
-            client.update_tx_hash(presig_index, &tx_hash).await?;
+            client
+                .update_tx_hash(child_id.as_deref(), presig_index, &tx_hash)
+                .await?;
             println!("Transaction hash updated");
         }
 
-        Commands::PresigCount => {
-            let (remaining, total) = client.get_presig_count().await?;
+        Commands::History {
+            child_id,
+            since,
+            until,
+            chain_id,
+            pending,
+            filled,
+            search,
+            limit,
+            offset,
+        } => {
+            let query = UsageQuery {
+                child_id,
+                since,
+                until,
+                chain_id,
+                tx_hash: if pending {
+                    Some(TxHashStatus::Pending)
+                } else if filled {
+                    Some(TxHashStatus::Filled)
+                } else {
+                    None
+                },
+                search,
+                offset,
+                limit,
+            };
+            let page = client.query_usage_log(&query).await?;
+
+            if page.entries.is_empty() {
+                println!("No matching signatures");
+            }
+            for entry in &page.entries {
+                println!(
+                    "#{} at {} (unix time) [chain {}] {}",
+                    entry.presig_index, entry.timestamp, entry.chain_id, entry.description
+                );
+                match &entry.tx_hash {
+                    Some(tx_hash) => println!("  Tx: 0x{}", tx_hash),
+                    None => println!("  Tx: pending"),
+                }
+                println!("  Signature: 0x{}", entry.signature);
+            }
+            let shown = offset as usize + page.entries.len();
+            if shown < page.total as usize {
+                println!(
+                    "Showing {}-{} of {}; see more with --offset {}",
+                    offset + 1,
+                    shown,
+                    page.total,
+                    shown
+                );
+            }
+        }
+
+        Commands::PresigCount { child_id } => {
+            let (remaining, total) = client.get_presig_count(child_id.as_deref()).await?;
             println!("Presigs: {}/{} remaining", remaining, total);
         }
 
@@ -214,6 +702,41 @@ pub async fn run(cli: Cli) -> Result<(), ClientError> {
                 }
             }
         }
+
+        Commands::ExportAudit {
+            child_id,
+            output,
+            qr,
+        } => {
+            let export = client.export_audit_bundle(&child_id).await?;
+            let bundle: sigil_core::AuditBundle = serde_json::from_str(&export.bundle_json)?;
+
+            if qr {
+                for chunk in &export.qr_chunks {
+                    println!("{}", chunk);
+                }
+            } else {
+                let path = output.unwrap_or_else(|| {
+                    let child_id = sigil_core::ChildId::from_hex(&export.child_id)
+                        .map(|c| sigil_core::AuditBundle::default_file_name(&c))
+                        .unwrap_or_else(|_| "sigil_audit.json".to_string());
+                    child_id.into()
+                });
+                std::fs::write(&path, &export.bundle_json).map_err(ClientError::Io)?;
+
+                println!(
+                    "✓ Exported {} audit records for child {}",
+                    export.record_count,
+                    &export.child_id[..8.min(export.child_id.len())]
+                );
+                println!("Written to: {}", path.display());
+                println!("Copy this file to the signing disk before reconciliation.");
+            }
+
+            // Printed to stderr so QR payloads on stdout stay scannable
+            eprintln!("Audit key: {}", bundle.auditor_pubkey.to_hex());
+            eprintln!("Pin it once on the mother with `sigil-mother pin-audit-key --key <hex>`.");
+        }
     }
 
     Ok(())
@@ -0,0 +1,1439 @@
+//! Decoding of unsigned EVM transactions and calldata
+//!
+//! Methods are looked up by selector in a [`SelectorRegistry`], a JSON file
+//! of function signatures, known contracts and tokens. The built-in registry
+//! covers ERC-20, ERC-721/1155, Permit2, common routers, WETH and Safe; a
+//! local file adds to it. Decoding flags risky calls (unlimited approvals,
+//! `setApprovalForAll`, delegatecall to unknown contracts) and produces a
+//! summary that is used as the signing description, so the usage log
+//! records what was really signed.
+
+use serde::{Deserialize, Serialize};
+use sigil_core::evm::{keccak256, Transaction};
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Registry compiled into the binary
+const BUILTIN_REGISTRY: &str = include_str!("../selectors.json");
+
+/// Calls nested deeper than this are left undecoded
+const MAX_NESTING: usize = 3;
+
+/// Decoding errors
+#[derive(Debug, thiserror::Error)]
+pub enum DecodeError {
+    #[error("Invalid hex: {0}")]
+    InvalidHex(String),
+
+    #[error("Invalid RLP: {0}")]
+    InvalidRlp(String),
+
+    #[error("Unsupported transaction type 0x{0:02x}")]
+    UnsupportedType(u8),
+
+    #[error("Invalid transaction: {0}")]
+    InvalidTransaction(String),
+
+    #[error("Invalid selector registry: {0}")]
+    InvalidRegistry(String),
+
+    #[error("IO error: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+pub type Result<T> = std::result::Result<T, DecodeError>;
+
+impl From<sigil_core::Error> for DecodeError {
+    fn from(e: sigil_core::Error) -> Self {
+        match e {
+            sigil_core::Error::InvalidRlp(message) => DecodeError::InvalidRlp(message),
+            sigil_core::Error::InvalidTransaction(message) => {
+                DecodeError::InvalidTransaction(message)
+            }
+            sigil_core::Error::UnsupportedTransactionType(ty) => DecodeError::UnsupportedType(ty),
+            e => DecodeError::InvalidTransaction(e.to_string()),
+        }
+    }
+}
+
+/// What the registry knows makes a function risky
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RiskRule {
+    /// Grants an allowance; flagged when the amount is effectively unlimited
+    Approval,
+    /// Grants an operator control of every token in a collection
+    ApprovalForAll,
+    /// Executes a call whose `operation` 1 is a delegatecall
+    Delegatecall,
+}
+
+/// A token whose amounts can be shown in its own units
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenInfo {
+    pub chain_id: u64,
+    pub address: String,
+    pub symbol: String,
+    pub decimals: u32,
+}
+
+/// Registry file layout
+#[derive(Debug, Deserialize)]
+struct RegistryFile {
+    #[serde(default)]
+    functions: Vec<FunctionSpec>,
+
+    /// Contract names by address
+    #[serde(default)]
+    contracts: HashMap<String, String>,
+
+    #[serde(default)]
+    tokens: Vec<TokenInfo>,
+}
+
+#[derive(Debug, Deserialize)]
+struct FunctionSpec {
+    /// `name(type name,...)`; parameter names are optional
+    signature: String,
+
+    #[serde(default)]
+    label: Option<String>,
+
+    #[serde(default)]
+    risk: Option<RiskRule>,
+
+    /// Parameter holding the approved amount (default `amount`)
+    #[serde(default)]
+    amount: Option<String>,
+
+    /// Address parameter that nested calls and delegatecalls go to (default `to`)
+    #[serde(default)]
+    target: Option<String>,
+
+    /// `bytes` or `bytes[]` parameter holding nested calls
+    #[serde(default)]
+    calls: Option<String>,
+
+    /// Moves `amount` of the token (the `token` parameter, or else the
+    /// called contract) to `to`
+    #[serde(default)]
+    transfer: bool,
+}
+
+#[derive(Debug, Clone)]
+struct Function {
+    signature: String,
+    label: Option<String>,
+    params: Vec<(String, AbiType)>,
+    risk: Option<RiskRule>,
+    amount: String,
+    target: String,
+    calls: Option<String>,
+    transfer: bool,
+}
+
+/// Known function selectors, contracts and tokens
+#[derive(Debug, Clone, Default)]
+pub struct SelectorRegistry {
+    functions: HashMap<[u8; 4], Function>,
+    contracts: HashMap<String, String>,
+    tokens: Vec<TokenInfo>,
+}
+
+impl SelectorRegistry {
+    /// The registry shipped with Sigil
+    pub fn builtin() -> Self {
+        Self::from_json(BUILTIN_REGISTRY).expect("built-in selector registry is valid")
+    }
+
+    /// Parse a registry file on its own
+    pub fn from_json(json: &str) -> Result<Self> {
+        let mut registry = Self::default();
+        registry.extend_json(json)?;
+        Ok(registry)
+    }
+
+    /// The built-in registry extended (and overridden) by a local file
+    pub fn load(path: &Path) -> Result<Self> {
+        let mut registry = Self::builtin();
+        registry.extend_json(&std::fs::read_to_string(path)?)?;
+        Ok(registry)
+    }
+
+    /// Add the functions, contracts and tokens of a registry file
+    pub fn extend_json(&mut self, json: &str) -> Result<()> {
+        let file: RegistryFile =
+            serde_json::from_str(json).map_err(|e| DecodeError::InvalidRegistry(e.to_string()))?;
+
+        for spec in file.functions {
+            let (name, params) = parse_signature(&spec.signature)?;
+            let signature = format!(
+                "{}({})",
+                name,
+                params
+                    .iter()
+                    .map(|(_, ty)| ty.canonical())
+                    .collect::<Vec<_>>()
+                    .join(",")
+            );
+            let function = Function {
+                label: spec.label,
+                params,
+                risk: spec.risk,
+                amount: spec.amount.unwrap_or_else(|| "amount".to_string()),
+                target: spec.target.unwrap_or_else(|| "to".to_string()),
+                calls: spec.calls,
+                transfer: spec.transfer,
+                signature,
+            };
+            self.functions
+                .insert(selector(&function.signature), function);
+        }
+        for (address, name) in file.contracts {
+            self.contracts.insert(address.to_ascii_lowercase(), name);
+        }
+        for mut token in file.tokens {
+            token.address = token.address.to_ascii_lowercase();
+            self.tokens
+                .retain(|t| !(t.chain_id == token.chain_id && t.address == token.address));
+            self.tokens.push(token);
+        }
+        Ok(())
+    }
+
+    /// Number of known function selectors
+    pub fn len(&self) -> usize {
+        self.functions.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.functions.is_empty()
+    }
+
+    fn token(&self, chain_id: Option<u64>, address: &str) -> Option<&TokenInfo> {
+        let address = address.to_ascii_lowercase();
+        self.tokens
+            .iter()
+            .find(|t| t.address == address && chain_id.map_or(true, |id| id == t.chain_id))
+    }
+
+    /// Name of a known contract or token
+    fn name(&self, chain_id: Option<u64>, address: &str) -> Option<String> {
+        self.contracts
+            .get(&address.to_ascii_lowercase())
+            .cloned()
+            .or_else(|| self.token(chain_id, address).map(|t| t.symbol.clone()))
+    }
+}
+
+/// How serious a flagged pattern is
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Severity {
+    /// Worth a look, e.g. a method the registry doesn't know
+    Warning,
+    /// Can hand over funds beyond this transaction
+    High,
+}
+
+/// A risky pattern found while decoding
+#[derive(Debug, Clone, Serialize)]
+pub struct Risk {
+    /// `unlimited_approval`, `approval_for_all`, `delegatecall_unknown`,
+    /// `no_replay_protection`, `unknown_method` or `undecodable_call`
+    pub code: String,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// One decoded argument
+#[derive(Debug, Clone, Serialize)]
+pub struct DecodedParam {
+    pub name: String,
+    #[serde(rename = "type")]
+    pub ty: String,
+    pub value: serde_json::Value,
+    /// Amount in token units, or `unlimited`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub display: Option<String>,
+}
+
+/// Tokens moved by a transfer call
+#[derive(Debug, Clone, Serialize)]
+pub struct TokenTransfer {
+    /// Token contract (checksummed)
+    pub token: String,
+    /// Token symbol, if the registry knows it
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub symbol: Option<String>,
+    /// Recipient (checksummed)
+    pub recipient: String,
+    /// Amount in the token's smallest unit (decimal)
+    pub amount: String,
+}
+
+/// A decoded contract call
+#[derive(Debug, Clone, Serialize)]
+pub struct DecodedCall {
+    /// 4-byte selector (hex with 0x prefix)
+    pub selector: String,
+    /// Canonical signature, if the selector is known
+    pub method: Option<String>,
+    pub label: Option<String>,
+    pub params: Vec<DecodedParam>,
+    /// Tokens this call moves, for ERC-20 and Permit2 transfers
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub transfer: Option<TokenTransfer>,
+    /// Calls made by this one (multicall, Safe transactions)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub calls: Vec<DecodedCall>,
+}
+
+/// A decoded transaction or call
+#[derive(Debug, Clone, Serialize)]
+pub struct DecodedTransaction {
+    /// `legacy`, `eip2930`, `eip1559`, or `calldata` for a bare call
+    pub kind: String,
+    pub chain_id: Option<u64>,
+    pub nonce: Option<u64>,
+    pub gas_limit: Option<u64>,
+    /// Recipient or called contract (checksummed); unset for deployments
+    pub to: Option<String>,
+    /// Name of `to` if the registry knows it
+    pub to_name: Option<String>,
+    /// Native value in wei (decimal)
+    pub value: String,
+    /// keccak256 of the unsigned transaction: the hash to sign
+    pub signing_hash: Option<String>,
+    pub call: Option<DecodedCall>,
+    pub risks: Vec<Risk>,
+    /// One-line description of what the transaction does
+    pub summary: String,
+}
+
+impl DecodedTransaction {
+    /// Whether anything of [`Severity::High`] was flagged
+    pub fn has_high_risk(&self) -> bool {
+        self.risks.iter().any(|r| r.severity == Severity::High)
+    }
+
+    /// Native value in wei, if it fits
+    pub fn value_wei(&self) -> Option<u128> {
+        self.value.parse().ok()
+    }
+
+    /// The tokens moved, if the transaction is a single token transfer
+    /// carrying no native value
+    ///
+    /// Anything else that calls a contract may move tokens to recipients and
+    /// amounts that only the contract knows.
+    pub fn token_transfer(&self) -> Option<&TokenTransfer> {
+        if self.value_wei() != Some(0) {
+            return None;
+        }
+        self.call.as_ref()?.transfer.as_ref()
+    }
+
+    /// Description to sign under: the decoded summary, then the caller's note
+    pub fn signing_description(&self, note: &str) -> String {
+        let note = note.trim();
+        if note.is_empty() || note == self.summary {
+            self.summary.clone()
+        } else {
+            format!("{} | {}", self.summary, note)
+        }
+    }
+}
+
+/// A bare call to decode when there is no encoded transaction
+#[derive(Debug, Clone, Default)]
+pub struct CallInput {
+    pub chain_id: Option<u64>,
+    /// Called address (hex)
+    pub to: Option<String>,
+    /// Native value in wei (decimal)
+    pub value: Option<String>,
+    /// Calldata (hex)
+    pub data: String,
+}
+
+/// Decode a hex-encoded legacy, EIP-2930 or EIP-1559 transaction
+///
+/// The signing hash is only set for unsigned transactions.
+pub fn decode_transaction(raw: &str, registry: &SelectorRegistry) -> Result<DecodedTransaction> {
+    let tx = Transaction::decode(&parse_hex(raw)?)?;
+    let mut value = [0u8; 32];
+    value[32 - tx.value.len()..].copy_from_slice(&tx.value);
+
+    let mut decoded = decode(
+        tx.kind.as_str(),
+        tx.chain_id,
+        tx.to,
+        &value,
+        &tx.data,
+        registry,
+    );
+    decoded.nonce = Some(tx.nonce);
+    decoded.gas_limit = Some(tx.gas_limit);
+    decoded.signing_hash = (!tx.signed).then(|| format!("0x{}", hex::encode(tx.signing_hash())));
+    if tx.chain_id.is_none() {
+        decoded.risks.push(Risk {
+            code: "no_replay_protection".to_string(),
+            severity: Severity::High,
+            message: "Legacy transaction without a chain ID (pre-EIP-155) can be replayed on \
+                      any chain"
+                .to_string(),
+        });
+        decoded.summary = summarize(&decoded);
+    }
+    Ok(decoded)
+}
+
+/// Decode calldata sent to a contract
+pub fn decode_calldata(
+    input: &CallInput,
+    registry: &SelectorRegistry,
+) -> Result<DecodedTransaction> {
+    let to = match &input.to {
+        Some(to) => Some(
+            <[u8; 20]>::try_from(parse_hex(to)?.as_slice())
+                .map_err(|_| DecodeError::InvalidHex(format!("{} is not an address", to)))?,
+        ),
+        None => None,
+    };
+    let value = match &input.value {
+        Some(value) => parse_decimal(value)?,
+        None => [0u8; 32],
+    };
+    Ok(decode(
+        "calldata",
+        input.chain_id,
+        to,
+        &value,
+        &parse_hex(&input.data)?,
+        registry,
+    ))
+}
+
+fn decode(
+    kind: &str,
+    chain_id: Option<u64>,
+    to: Option<[u8; 20]>,
+    value: &[u8],
+    data: &[u8],
+    registry: &SelectorRegistry,
+) -> DecodedTransaction {
+    let to = to.map(|to| checksum_address(&to));
+    let to_name = to.as_deref().and_then(|to| registry.name(chain_id, to));
+    let mut risks = Vec::new();
+    let call = (!data.is_empty())
+        .then(|| decode_call(data, to.as_deref(), chain_id, registry, 0, &mut risks));
+    let value = to_decimal(value);
+
+    let mut decoded = DecodedTransaction {
+        kind: kind.to_string(),
+        chain_id,
+        nonce: None,
+        gas_limit: None,
+        to,
+        to_name,
+        value,
+        signing_hash: None,
+        call,
+        risks,
+        summary: String::new(),
+    };
+    decoded.summary = summarize(&decoded);
+    decoded
+}
+
+fn decode_call(
+    data: &[u8],
+    contract: Option<&str>,
+    chain_id: Option<u64>,
+    registry: &SelectorRegistry,
+    depth: usize,
+    risks: &mut Vec<Risk>,
+) -> DecodedCall {
+    let selector_bytes: [u8; 4] = data
+        .get(..4)
+        .and_then(|s| s.try_into().ok())
+        .unwrap_or_default();
+    let mut call = DecodedCall {
+        selector: format!("0x{}", hex::encode(&data[..data.len().min(4)])),
+        method: None,
+        label: None,
+        params: Vec::new(),
+        transfer: None,
+        calls: Vec::new(),
+    };
+    let contract_name = contract
+        .map(|c| registry.name(chain_id, c).unwrap_or_else(|| c.to_string()))
+        .unwrap_or_else(|| "a new contract".to_string());
+
+    let Some(function) = registry.functions.get(&selector_bytes) else {
+        risks.push(Risk {
+            code: "unknown_method".to_string(),
+            severity: Severity::Warning,
+            message: format!("Unknown method {} on {}", call.selector, contract_name),
+        });
+        return call;
+    };
+    call.method = Some(function.signature.clone());
+    call.label = function.label.clone();
+
+    let types: Vec<AbiType> = function.params.iter().map(|(_, ty)| ty.clone()).collect();
+    let values = match abi_decode(&types, &data[4..]) {
+        Ok(values) => values,
+        Err(e) => {
+            risks.push(Risk {
+                code: "undecodable_call".to_string(),
+                severity: Severity::Warning,
+                message: format!(
+                    "Arguments of {} do not match its signature: {}",
+                    function.signature, e
+                ),
+            });
+            return call;
+        }
+    };
+    let arg = |name: &str| {
+        function
+            .params
+            .iter()
+            .position(|(param, _)| param == name)
+            .map(|i| &values[i])
+    };
+    let address_arg = |name: &str| match arg(name) {
+        Some(AbiValue::Address(address)) => Some(checksum_address(address)),
+        _ => None,
+    };
+
+    // Amounts are in units of the `token` argument, or else of the contract
+    let token = address_arg("token")
+        .or_else(|| contract.map(str::to_string))
+        .and_then(|token| registry.token(chain_id, &token));
+
+    for ((name, ty), value) in function.params.iter().zip(&values) {
+        let display = match value {
+            AbiValue::Uint(amount)
+                if function.risk == Some(RiskRule::Approval)
+                    && *name == function.amount
+                    && is_unlimited(amount) =>
+            {
+                Some("unlimited".to_string())
+            }
+            AbiValue::Uint(amount) if is_amount(name) => token.map(|token| {
+                format!(
+                    "{} {}",
+                    format_units(&to_decimal(amount), token.decimals),
+                    token.symbol
+                )
+            }),
+            _ => None,
+        };
+        call.params.push(DecodedParam {
+            name: name.clone(),
+            ty: ty.canonical(),
+            value: value.to_json(),
+            display,
+        });
+    }
+
+    if function.transfer {
+        let transfer_token = address_arg("token").or_else(|| contract.map(str::to_string));
+        if let (Some(transfer_token), Some(recipient), Some(AbiValue::Uint(amount))) =
+            (transfer_token, address_arg("to"), arg(&function.amount))
+        {
+            call.transfer = Some(TokenTransfer {
+                symbol: registry
+                    .token(chain_id, &transfer_token)
+                    .map(|t| t.symbol.clone()),
+                token: transfer_token,
+                recipient,
+                amount: to_decimal(amount),
+            });
+        }
+    }
+
+    let spender = address_arg("spender")
+        .or_else(|| address_arg("operator"))
+        .unwrap_or_else(|| "an unknown spender".to_string());
+    let target = address_arg(&function.target);
+    match function.risk {
+        Some(RiskRule::Approval) => {
+            if let Some(AbiValue::Uint(amount)) = arg(&function.amount) {
+                if is_unlimited(amount) {
+                    let asset = token
+                        .map(|t| t.symbol.clone())
+                        .or_else(|| address_arg("token"))
+                        .unwrap_or_else(|| contract_name.clone());
+                    risks.push(Risk {
+                        code: "unlimited_approval".to_string(),
+                        severity: Severity::High,
+                        message: format!("Unlimited {} allowance for {}", asset, spender),
+                    });
+                }
+            }
+        }
+        Some(RiskRule::ApprovalForAll)
+            if values.iter().any(|v| matches!(v, AbiValue::Bool(true))) =>
+        {
+            risks.push(Risk {
+                code: "approval_for_all".to_string(),
+                severity: Severity::High,
+                message: format!("{} may transfer every token of {}", spender, contract_name),
+            });
+        }
+        Some(RiskRule::Delegatecall) => {
+            let delegatecall = matches!(
+                arg("operation"),
+                Some(AbiValue::Uint(operation)) if to_decimal(operation) == "1"
+            );
+            if delegatecall {
+                let known = target
+                    .as_deref()
+                    .is_some_and(|t| registry.contracts.contains_key(&t.to_ascii_lowercase()));
+                if !known {
+                    risks.push(Risk {
+                        code: "delegatecall_unknown".to_string(),
+                        severity: Severity::High,
+                        message: format!(
+                            "Delegatecall to unknown contract {}",
+                            target.as_deref().unwrap_or("(none)")
+                        ),
+                    });
+                }
+            }
+        }
+        _ => {}
+    }
+
+    if let Some(calls) = function.calls.as_deref().and_then(arg) {
+        if depth + 1 < MAX_NESTING {
+            let nested_contract = target.as_deref().or(contract);
+            let nested: Vec<&[u8]> = match calls {
+                AbiValue::Bytes(data) => vec![data.as_slice()],
+                AbiValue::Array(items) => items
+                    .iter()
+                    .filter_map(|item| match item {
+                        AbiValue::Bytes(data) => Some(data.as_slice()),
+                        _ => None,
+                    })
+                    .collect(),
+                _ => Vec::new(),
+            };
+            for data in nested.into_iter().filter(|data| !data.is_empty()) {
+                call.calls.push(decode_call(
+                    data,
+                    nested_contract,
+                    chain_id,
+                    registry,
+                    depth + 1,
+                    risks,
+                ));
+            }
+        }
+    }
+
+    call
+}
+
+/// One-line description of a decoded transaction
+fn summarize(decoded: &DecodedTransaction) -> String {
+    let native = decoded.value_wei().filter(|wei| *wei > 0).map(|_| {
+        format!(
+            "{} {}",
+            format_units(&decoded.value, 18),
+            native_symbol(decoded.chain_id)
+        )
+    });
+    let destination = decoded
+        .to_name
+        .clone()
+        .or_else(|| decoded.to.clone())
+        .unwrap_or_default();
+
+    let mut summary = match (&decoded.call, &decoded.to) {
+        (None, None) => "Deploy contract".to_string(),
+        (None, Some(to)) => format!("Send {} to {}", native.as_deref().unwrap_or("0 ETH"), to),
+        (Some(_), None) => "Deploy contract".to_string(),
+        (Some(call), Some(_)) => {
+            let method = call
+                .label
+                .clone()
+                .or_else(|| call.method.clone())
+                .unwrap_or_else(|| format!("Unknown method {}", call.selector));
+            let details: Vec<String> = call
+                .params
+                .iter()
+                .filter(|p| {
+                    p.display.is_some()
+                        || matches!(
+                            p.name.as_str(),
+                            "to" | "recipient" | "spender" | "operator" | "token"
+                        )
+                })
+                .map(|p| {
+                    let value = p
+                        .display
+                        .clone()
+                        .unwrap_or_else(|| p.value.as_str().unwrap_or_default().to_string());
+                    format!("{}={}", p.name, value)
+                })
+                .collect();
+            let mut text = format!("{} on {}", method, destination);
+            if !details.is_empty() {
+                text.push_str(&format!(": {}", details.join(", ")));
+            }
+            if !call.calls.is_empty() {
+                text.push_str(&format!(" ({} nested calls)", call.calls.len()));
+            }
+            text
+        }
+    };
+    if decoded.call.is_some() {
+        if let Some(native) = native {
+            summary.push_str(&format!(" with {}", native));
+        }
+    }
+    let high: Vec<&str> = decoded
+        .risks
+        .iter()
+        .filter(|r| r.severity == Severity::High)
+        .map(|r| r.code.as_str())
+        .collect();
+    if !high.is_empty() {
+        summary.push_str(&format!(" [RISK: {}]", high.join(", ")));
+    }
+    summary
+}
+
+/// Whether a uint argument name denotes a token amount
+fn is_amount(name: &str) -> bool {
+    let name = name.to_ascii_lowercase();
+    name.contains("amount") || name == "value" || name == "wad"
+}
+
+/// Allowances of 2^128 or more are treated as unlimited
+fn is_unlimited(amount: &[u8; 32]) -> bool {
+    amount[..16].iter().any(|b| *b != 0)
+}
+
+fn native_symbol(chain_id: Option<u64>) -> &'static str {
+    match chain_id {
+        Some(56) => "BNB",
+        Some(100) => "xDAI",
+        Some(137) => "POL",
+        Some(250) => "FTM",
+        Some(43114) => "AVAX",
+        _ => "ETH",
+    }
+}
+
+/// First four bytes of the hash of a canonical signature
+fn selector(signature: &str) -> [u8; 4] {
+    let hash = keccak256(signature.as_bytes());
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+/// EIP-55 mixed-case address
+fn checksum_address(address: &[u8; 20]) -> String {
+    let lower = hex::encode(address);
+    let hash = keccak256(lower.as_bytes());
+    let mixed: String = lower
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let nibble = (hash[i / 2] >> (if i % 2 == 0 { 4 } else { 0 })) & 0x0f;
+            if nibble >= 8 {
+                c.to_ascii_uppercase()
+            } else {
+                c
+            }
+        })
+        .collect();
+    format!("0x{}", mixed)
+}
+
+fn parse_hex(s: &str) -> Result<Vec<u8>> {
+    let s = s.trim();
+    let s = s.strip_prefix("0x").unwrap_or(s);
+    hex::decode(s).map_err(|e| DecodeError::InvalidHex(e.to_string()))
+}
+
+/// Big-endian unsigned integer to decimal
+fn to_decimal(be: &[u8]) -> String {
+    let mut num: Vec<u8> = be.iter().skip_while(|b| **b == 0).copied().collect();
+    if num.is_empty() {
+        return "0".to_string();
+    }
+    let mut digits = Vec::new();
+    while !num.is_empty() {
+        let mut remainder = 0u32;
+        for byte in num.iter_mut() {
+            let current = remainder * 256 + *byte as u32;
+            *byte = (current / 10) as u8;
+            remainder = current % 10;
+        }
+        digits.push(b'0' + remainder as u8);
+        let zeros = num.iter().take_while(|b| **b == 0).count();
+        num.drain(..zeros);
+    }
+    digits.reverse();
+    String::from_utf8(digits).unwrap_or_default()
+}
+
+/// Decimal to a 256-bit big-endian integer
+fn parse_decimal(s: &str) -> Result<[u8; 32]> {
+    let invalid = || DecodeError::InvalidTransaction(format!("invalid value: {}", s));
+    if s.is_empty() {
+        return Err(invalid());
+    }
+    let mut num = [0u8; 32];
+    for c in s.chars() {
+        let mut carry = c.to_digit(10).ok_or_else(invalid)?;
+        for byte in num.iter_mut().rev() {
+            let current = *byte as u32 * 10 + carry;
+            *byte = current as u8;
+            carry = current >> 8;
+        }
+        if carry != 0 {
+            return Err(invalid());
+        }
+    }
+    Ok(num)
+}
+
+/// Place the decimal point of an integer amount
+fn format_units(decimal: &str, decimals: u32) -> String {
+    let decimals = decimals as usize;
+    if decimals == 0 {
+        return decimal.to_string();
+    }
+    let padded = format!("{:0>width$}", decimal, width = decimals + 1);
+    let (whole, fraction) = padded.split_at(padded.len() - decimals);
+    let fraction = fraction.trim_end_matches('0');
+    if fraction.is_empty() {
+        whole.to_string()
+    } else {
+        format!("{}.{}", whole, fraction)
+    }
+}
+
+/// Parse `name(type name,...)` into the function name and typed parameters
+fn parse_signature(signature: &str) -> Result<(String, Vec<(String, AbiType)>)> {
+    let invalid = |why: &str| DecodeError::InvalidRegistry(format!("{}: {}", signature, why));
+    let (name, rest) = signature
+        .split_once('(')
+        .ok_or_else(|| invalid("missing '('"))?;
+    let args = rest
+        .strip_suffix(')')
+        .ok_or_else(|| invalid("missing ')'"))?;
+    if name.trim().is_empty() {
+        return Err(invalid("missing function name"));
+    }
+
+    let mut params = Vec::new();
+    for (i, arg) in args.split(',').filter(|a| !a.trim().is_empty()).enumerate() {
+        let mut words = arg.split_whitespace();
+        let ty = words.next().ok_or_else(|| invalid("empty parameter"))?;
+        let ty = AbiType::parse(ty).ok_or_else(|| invalid(&format!("unsupported type {}", ty)))?;
+        let param = words
+            .next()
+            .map(str::to_string)
+            .unwrap_or_else(|| format!("arg{}", i));
+        params.push((param, ty));
+    }
+    Ok((name.trim().to_string(), params))
+}
+
+/// ABI types the decoder understands (no tuples)
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum AbiType {
+    Address,
+    Bool,
+    Uint(usize),
+    Int(usize),
+    FixedBytes(usize),
+    Bytes,
+    String,
+    Array(Box<AbiType>),
+}
+
+impl AbiType {
+    fn parse(s: &str) -> Option<Self> {
+        if let Some(inner) = s.strip_suffix("[]") {
+            return Some(AbiType::Array(Box::new(Self::parse(inner)?)));
+        }
+        let bits = |n: &str, default: usize| match n {
+            "" => Some(default),
+            n => n.parse().ok(),
+        };
+        match s {
+            "address" => Some(AbiType::Address),
+            "bool" => Some(AbiType::Bool),
+            "bytes" => Some(AbiType::Bytes),
+            "string" => Some(AbiType::String),
+            _ => {
+                if let Some(n) = s.strip_prefix("uint") {
+                    bits(n, 256)
+                        .filter(|n| *n > 0 && *n <= 256 && n % 8 == 0)
+                        .map(AbiType::Uint)
+                } else if let Some(n) = s.strip_prefix("int") {
+                    bits(n, 256)
+                        .filter(|n| *n > 0 && *n <= 256 && n % 8 == 0)
+                        .map(AbiType::Int)
+                } else if let Some(n) = s.strip_prefix("bytes") {
+                    n.parse()
+                        .ok()
+                        .filter(|n| (1..=32).contains(n))
+                        .map(AbiType::FixedBytes)
+                } else {
+                    None
+                }
+            }
+        }
+    }
+
+    fn canonical(&self) -> String {
+        match self {
+            AbiType::Address => "address".to_string(),
+            AbiType::Bool => "bool".to_string(),
+            AbiType::Uint(bits) => format!("uint{}", bits),
+            AbiType::Int(bits) => format!("int{}", bits),
+            AbiType::FixedBytes(n) => format!("bytes{}", n),
+            AbiType::Bytes => "bytes".to_string(),
+            AbiType::String => "string".to_string(),
+            AbiType::Array(inner) => format!("{}[]", inner.canonical()),
+        }
+    }
+
+    fn is_dynamic(&self) -> bool {
+        matches!(self, AbiType::Bytes | AbiType::String | AbiType::Array(_))
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum AbiValue {
+    Address([u8; 20]),
+    Bool(bool),
+    Uint([u8; 32]),
+    Int([u8; 32]),
+    FixedBytes(Vec<u8>),
+    Bytes(Vec<u8>),
+    String(String),
+    Array(Vec<AbiValue>),
+}
+
+impl AbiValue {
+    fn to_json(&self) -> serde_json::Value {
+        match self {
+            AbiValue::Address(address) => checksum_address(address).into(),
+            AbiValue::Bool(b) => (*b).into(),
+            AbiValue::Uint(n) => to_decimal(n).into(),
+            AbiValue::Int(n) if n[0] & 0x80 != 0 => {
+                // Two's complement: negate to get the magnitude
+                let mut magnitude = n.map(|b| !b);
+                for byte in magnitude.iter_mut().rev() {
+                    let (sum, overflow) = byte.overflowing_add(1);
+                    *byte = sum;
+                    if !overflow {
+                        break;
+                    }
+                }
+                format!("-{}", to_decimal(&magnitude)).into()
+            }
+            AbiValue::Int(n) => to_decimal(n).into(),
+            AbiValue::FixedBytes(bytes) | AbiValue::Bytes(bytes) => {
+                format!("0x{}", hex::encode(bytes)).into()
+            }
+            AbiValue::String(s) => s.clone().into(),
+            AbiValue::Array(items) => items.iter().map(AbiValue::to_json).collect(),
+        }
+    }
+}
+
+/// Decode ABI-encoded arguments
+fn abi_decode(types: &[AbiType], data: &[u8]) -> std::result::Result<Vec<AbiValue>, String> {
+    decode_sequence(types, data, 0)
+}
+
+/// Decode values laid out head-then-tail starting at `base`
+fn decode_sequence(
+    types: &[AbiType],
+    data: &[u8],
+    base: usize,
+) -> std::result::Result<Vec<AbiValue>, String> {
+    types
+        .iter()
+        .enumerate()
+        .map(|(i, ty)| {
+            let head = base + 32 * i;
+            if ty.is_dynamic() {
+                let offset = word_usize(data, head)?;
+                decode_dynamic(ty, data, base + offset)
+            } else {
+                decode_static(ty, word(data, head)?)
+            }
+        })
+        .collect()
+}
+
+fn decode_static(ty: &AbiType, word: &[u8; 32]) -> std::result::Result<AbiValue, String> {
+    match ty {
+        AbiType::Address => {
+            if word[..12].iter().any(|b| *b != 0) {
+                return Err("address has dirty high bytes".to_string());
+            }
+            Ok(AbiValue::Address(word[12..].try_into().unwrap()))
+        }
+        AbiType::Bool => match to_decimal(word).as_str() {
+            "0" => Ok(AbiValue::Bool(false)),
+            "1" => Ok(AbiValue::Bool(true)),
+            _ => Err("bool is neither 0 nor 1".to_string()),
+        },
+        AbiType::Uint(_) => Ok(AbiValue::Uint(*word)),
+        AbiType::Int(_) => Ok(AbiValue::Int(*word)),
+        AbiType::FixedBytes(n) => Ok(AbiValue::FixedBytes(word[..*n].to_vec())),
+        _ => Err(format!("{} is not static", ty.canonical())),
+    }
+}
+
+fn decode_dynamic(ty: &AbiType, data: &[u8], at: usize) -> std::result::Result<AbiValue, String> {
+    let len = word_usize(data, at)?;
+    match ty {
+        AbiType::Bytes | AbiType::String => {
+            let bytes = data
+                .get(at + 32..at + 32 + len)
+                .ok_or("bytes run past the end of the data")?
+                .to_vec();
+            if *ty == AbiType::Bytes {
+                Ok(AbiValue::Bytes(bytes))
+            } else {
+                Ok(AbiValue::String(
+                    String::from_utf8_lossy(&bytes).into_owned(),
+                ))
+            }
+        }
+        AbiType::Array(inner) => {
+            if len > data.len() / 32 {
+                return Err("array is longer than the data".to_string());
+            }
+            let types = vec![(**inner).clone(); len];
+            Ok(AbiValue::Array(decode_sequence(&types, data, at + 32)?))
+        }
+        _ => Err(format!("{} is not dynamic", ty.canonical())),
+    }
+}
+
+fn word(data: &[u8], at: usize) -> std::result::Result<&[u8; 32], String> {
+    data.get(at..at + 32)
+        .and_then(|w| w.try_into().ok())
+        .ok_or_else(|| "arguments are shorter than the signature".to_string())
+}
+
+/// A word used as an offset or length
+fn word_usize(data: &[u8], at: usize) -> std::result::Result<usize, String> {
+    let word = word(data, at)?;
+    if word[..24].iter().any(|b| *b != 0) {
+        return Err("offset or length out of range".to_string());
+    }
+    let n = u64::from_be_bytes(word[24..].try_into().unwrap()) as usize;
+    if n > data.len() {
+        return Err("offset or length out of range".to_string());
+    }
+    Ok(n)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sigil_core::evm::{rlp_bytes, rlp_list};
+
+    const USDC: &str = "a0b86991c6218b36c1d19d4a2e9eb0ce3606eb48";
+    const SPENDER: &str = "1111111254eeb25477b68fb85ed929f73a960582";
+
+    fn address_word(address: &str) -> String {
+        format!("{:0>64}", address)
+    }
+
+    fn uint_word(n: u128) -> String {
+        format!("{:064x}", n)
+    }
+
+    #[test]
+    fn test_builtin_selectors() {
+        let registry = SelectorRegistry::builtin();
+        for (signature, expected) in [
+            ("transfer(address,uint256)", "a9059cbb"),
+            ("approve(address,uint256)", "095ea7b3"),
+            ("setApprovalForAll(address,bool)", "a22cb465"),
+            (
+                "execTransaction(address,uint256,bytes,uint8,uint256,uint256,uint256,address,address,bytes)",
+                "6a761202",
+            ),
+        ] {
+            let selector = selector(signature);
+            assert_eq!(hex::encode(selector), expected);
+            assert_eq!(
+                registry.functions[&selector].signature, signature,
+                "{} missing from the built-in registry",
+                signature
+            );
+        }
+    }
+
+    #[test]
+    fn test_decode_eip1559_token_transfer() {
+        let data = hex::decode(format!(
+            "a9059cbb{}{}",
+            address_word(SPENDER),
+            uint_word(1_500_000)
+        ))
+        .unwrap();
+        let fields = [
+            rlp_bytes(&[1]),                      // chain ID
+            rlp_bytes(&[7]),                      // nonce
+            rlp_bytes(&[]),                       // max priority fee
+            rlp_bytes(&[0x04, 0xa8, 0x17, 0xc8]), // max fee
+            rlp_bytes(&[0xea, 0x60]),             // gas limit
+            rlp_bytes(&hex::decode(USDC).unwrap()),
+            rlp_bytes(&[]),
+            rlp_bytes(&data),
+            rlp_list(&[]),
+        ];
+        let raw = [vec![0x02], rlp_list(&fields)].concat();
+
+        let decoded = decode_transaction(&hex::encode(&raw), &SelectorRegistry::builtin()).unwrap();
+        assert_eq!(decoded.kind, "eip1559");
+        assert_eq!(decoded.chain_id, Some(1));
+        assert_eq!(decoded.nonce, Some(7));
+        assert_eq!(decoded.gas_limit, Some(60000));
+        assert_eq!(
+            decoded.signing_hash,
+            Some(format!("0x{}", hex::encode(keccak256(&raw))))
+        );
+        assert_eq!(decoded.to_name.as_deref(), Some("USDC"));
+        assert!(decoded.risks.is_empty());
+
+        let call = decoded.call.as_ref().unwrap();
+        assert_eq!(call.method.as_deref(), Some("transfer(address,uint256)"));
+        assert_eq!(call.params[1].value, "1500000");
+        assert_eq!(call.params[1].display.as_deref(), Some("1.5 USDC"));
+        let transfer = decoded.token_transfer().unwrap();
+        assert_eq!(transfer.token, "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+        assert_eq!(transfer.symbol.as_deref(), Some("USDC"));
+        assert_eq!(
+            transfer.recipient,
+            "0x1111111254EEB25477B68fb85Ed929f73A960582"
+        );
+        assert_eq!(transfer.amount, "1500000");
+        assert_eq!(
+            decoded.summary,
+            "ERC-20 transfer on USDC: to=0x1111111254EEB25477B68fb85Ed929f73A960582, amount=1.5 USDC"
+        );
+        assert_eq!(
+            decoded.signing_description("Pay invoice 7"),
+            format!("{} | Pay invoice 7", decoded.summary)
+        );
+    }
+
+    #[test]
+    fn test_decode_legacy_eip155_value_transfer() {
+        let to = hex::decode(SPENDER).unwrap();
+        let unsigned = rlp_list(&[
+            rlp_bytes(&[]),
+            rlp_bytes(&[0x04, 0xa8, 0x17, 0xc8]),
+            rlp_bytes(&[0x52, 0x08]),
+            rlp_bytes(&to),
+            rlp_bytes(&hex::decode("016345785d8a0000").unwrap()), // 0.1 ETH
+            rlp_bytes(&[]),
+            rlp_bytes(&[137]),
+            rlp_bytes(&[]),
+            rlp_bytes(&[]),
+        ]);
+
+        let decoded =
+            decode_transaction(&hex::encode(&unsigned), &SelectorRegistry::builtin()).unwrap();
+        assert_eq!(decoded.kind, "legacy");
+        assert_eq!(decoded.chain_id, Some(137));
+        assert!(decoded.signing_hash.is_some());
+        assert_eq!(decoded.value_wei(), Some(100_000_000_000_000_000));
+        assert_eq!(
+            decoded.summary,
+            "Send 0.1 POL to 0x1111111254EEB25477B68fb85Ed929f73A960582"
+        );
+        assert!(decoded.risks.is_empty());
+        assert!(decoded.token_transfer().is_none());
+    }
+
+    #[test]
+    fn test_flags_legacy_without_replay_protection() {
+        let unsigned = rlp_list(&[
+            rlp_bytes(&[]),
+            rlp_bytes(&[0x04, 0xa8, 0x17, 0xc8]),
+            rlp_bytes(&[0x52, 0x08]),
+            rlp_bytes(&hex::decode(SPENDER).unwrap()),
+            rlp_bytes(&[1]),
+            rlp_bytes(&[]),
+        ]);
+
+        let decoded =
+            decode_transaction(&hex::encode(&unsigned), &SelectorRegistry::builtin()).unwrap();
+        assert_eq!(decoded.chain_id, None);
+        assert!(decoded.has_high_risk());
+        assert_eq!(decoded.risks[0].code, "no_replay_protection");
+        assert!(decoded.summary.ends_with("[RISK: no_replay_protection]"));
+    }
+
+    #[test]
+    fn test_flags_unlimited_approval_and_approval_for_all() {
+        let registry = SelectorRegistry::builtin();
+        let approve = CallInput {
+            chain_id: Some(1),
+            to: Some(USDC.to_string()),
+            value: None,
+            data: format!("095ea7b3{}{}", address_word(SPENDER), "f".repeat(64)),
+        };
+        let decoded = decode_calldata(&approve, &registry).unwrap();
+        assert!(decoded.has_high_risk());
+        assert_eq!(decoded.risks[0].code, "unlimited_approval");
+        assert!(decoded.risks[0].message.contains("USDC"));
+        assert_eq!(
+            decoded.call.unwrap().params[1].display.as_deref(),
+            Some("unlimited")
+        );
+        assert!(decoded.summary.ends_with("[RISK: unlimited_approval]"));
+
+        let limited = CallInput {
+            data: format!("095ea7b3{}{}", address_word(SPENDER), uint_word(5_000_000)),
+            ..approve
+        };
+        assert!(!decode_calldata(&limited, &registry)
+            .unwrap()
+            .has_high_risk());
+
+        let for_all = CallInput {
+            chain_id: Some(1),
+            to: Some(format!("0x{}", "ab".repeat(20))),
+            value: None,
+            data: format!("a22cb465{}{}", address_word(SPENDER), uint_word(1)),
+        };
+        let decoded = decode_calldata(&for_all, &registry).unwrap();
+        assert_eq!(decoded.risks[0].code, "approval_for_all");
+    }
+
+    /// Safe execTransaction(to, 0, data, operation, 0, 0, 0, 0, 0, "")
+    fn exec_transaction(to: &str, operation: u128, data: &str) -> String {
+        let data_len = data.len() / 2;
+        let data_words = data_len.div_ceil(32);
+        let signatures_offset = 32 * 10 + 32 + 32 * data_words;
+        format!(
+            "6a761202{}{}{}{}{}{}{}{}{}{}{}{:0<width$}{}",
+            address_word(to),
+            uint_word(0),
+            uint_word(32 * 10),
+            uint_word(operation),
+            uint_word(0),
+            uint_word(0),
+            uint_word(0),
+            address_word("0"),
+            address_word("0"),
+            uint_word(signatures_offset as u128),
+            uint_word(data_len as u128),
+            data,
+            uint_word(0),
+            width = data_words * 64
+        )
+    }
+
+    #[test]
+    fn test_flags_delegatecall_to_unknown_contract() {
+        let registry = SelectorRegistry::builtin();
+        let safe = Some(format!("0x{}", "5a".repeat(20)));
+        let inner = format!("095ea7b3{}{}", address_word(SPENDER), "f".repeat(64));
+
+        let unknown = CallInput {
+            chain_id: Some(1),
+            to: safe.clone(),
+            value: None,
+            data: exec_transaction(&"66".repeat(20), 1, &inner),
+        };
+        let decoded = decode_calldata(&unknown, &registry).unwrap();
+        let codes: Vec<&str> = decoded.risks.iter().map(|r| r.code.as_str()).collect();
+        // The nested call is decoded against the delegatecall target
+        assert_eq!(codes, ["delegatecall_unknown", "unlimited_approval"]);
+
+        // Plain calls and delegatecalls to MultiSend are fine
+        let call = CallInput {
+            data: exec_transaction(&"66".repeat(20), 0, ""),
+            ..unknown.clone()
+        };
+        assert!(!decode_calldata(&call, &registry).unwrap().has_high_risk());
+        let multisend = CallInput {
+            data: exec_transaction("40a2accbd92bca938b02010e17a5b8929b49130d", 1, ""),
+            ..unknown
+        };
+        assert!(!decode_calldata(&multisend, &registry)
+            .unwrap()
+            .has_high_risk());
+    }
+
+    #[test]
+    fn test_nested_multicall_is_checked() {
+        let registry = SelectorRegistry::builtin();
+        let inner = format!("095ea7b3{}{}", address_word(SPENDER), "f".repeat(64));
+        // multicall(bytes[]) with a single 68-byte call
+        let data = format!(
+            "ac9650d8{}{}{}{}{}{}",
+            uint_word(32),
+            uint_word(1),
+            uint_word(32),
+            uint_word(68),
+            inner,
+            "0".repeat(56)
+        );
+        let decoded = decode_calldata(
+            &CallInput {
+                chain_id: Some(1),
+                to: Some(USDC.to_string()),
+                value: None,
+                data,
+            },
+            &registry,
+        )
+        .unwrap();
+        let call = decoded.call.as_ref().unwrap();
+        assert_eq!(call.label.as_deref(), Some("Multicall"));
+        assert_eq!(call.calls.len(), 1);
+        assert_eq!(decoded.risks[0].code, "unlimited_approval");
+    }
+
+    #[test]
+    fn test_permit2_transfer_moves_the_token_argument() {
+        let permit2 = "0x000000000022d473030f116ddee9f6b43ac78ba3";
+        let data = format!(
+            "{}{}{}{}{}",
+            hex::encode(selector("transferFrom(address,address,uint160,address)")),
+            address_word(&"ab".repeat(20)),
+            address_word(SPENDER),
+            uint_word(2_000_000),
+            address_word(USDC)
+        );
+        let mut input = CallInput {
+            chain_id: Some(1),
+            to: Some(permit2.to_string()),
+            value: None,
+            data,
+        };
+        let decoded = decode_calldata(&input, &SelectorRegistry::builtin()).unwrap();
+        let transfer = decoded.token_transfer().unwrap();
+        assert_eq!(transfer.token, "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+        assert_eq!(transfer.symbol.as_deref(), Some("USDC"));
+        assert_eq!(
+            transfer.recipient,
+            "0x1111111254EEB25477B68fb85Ed929f73A960582"
+        );
+        assert_eq!(transfer.amount, "2000000");
+
+        // Native value sent along isn't covered by the token transfer
+        input.value = Some("1".to_string());
+        let decoded = decode_calldata(&input, &SelectorRegistry::builtin()).unwrap();
+        assert!(decoded.call.as_ref().unwrap().transfer.is_some());
+        assert!(decoded.token_transfer().is_none());
+    }
+
+    #[test]
+    fn test_unknown_method_and_local_registry() {
+        let mut registry = SelectorRegistry::builtin();
+        let input = CallInput {
+            chain_id: Some(1),
+            to: Some(format!("0x{}", "ab".repeat(20))),
+            value: None,
+            data: format!("12345678{}", uint_word(5)),
+        };
+        let decoded = decode_calldata(&input, &registry).unwrap();
+        assert_eq!(decoded.risks[0].code, "unknown_method");
+        assert_eq!(decoded.risks[0].severity, Severity::Warning);
+
+        // 0x12345678 isn't a real selector; teach the registry one that is
+        registry
+            .extend_json(
+                r#"{
+                    "functions": [{ "signature": "stake(uint256 amount)", "label": "Stake" }],
+                    "contracts": { "0xABABABABABABABABABABABABABABABABABABABAB": "Staking pool" }
+                }"#,
+            )
+            .unwrap();
+        let input = CallInput {
+            data: format!(
+                "{}{}",
+                hex::encode(selector("stake(uint256)")),
+                uint_word(5)
+            ),
+            ..input
+        };
+        let decoded = decode_calldata(&input, &registry).unwrap();
+        assert!(decoded.risks.is_empty());
+        assert_eq!(decoded.summary, "Stake on Staking pool");
+
+        assert!(
+            SelectorRegistry::from_json(r#"{"functions":[{"signature":"f((uint256))"}]}"#).is_err()
+        );
+    }
+
+    #[test]
+    fn test_rejects_malformed_input() {
+        let registry = SelectorRegistry::builtin();
+        assert!(matches!(
+            decode_transaction("0xzz", &registry),
+            Err(DecodeError::InvalidHex(_))
+        ));
+        assert!(matches!(
+            decode_transaction("0x03c0", &registry),
+            Err(DecodeError::UnsupportedType(3))
+        ));
+        assert!(matches!(
+            decode_transaction("0xc3010203", &registry),
+            Err(DecodeError::InvalidTransaction(_))
+        ));
+        assert!(matches!(
+            decode_transaction("0xc5", &registry),
+            Err(DecodeError::InvalidRlp(_))
+        ));
+
+        // Arguments that don't fit the signature are reported, not fatal
+        let short = CallInput {
+            data: "a9059cbb0000".to_string(),
+            ..Default::default()
+        };
+        let decoded = decode_calldata(&short, &registry).unwrap();
+        assert_eq!(decoded.risks[0].code, "undecodable_call");
+    }
+
+    #[test]
+    fn test_number_formatting() {
+        assert_eq!(to_decimal(&[0x01, 0x00]), "256");
+        assert_eq!(to_decimal(&[]), "0");
+        assert_eq!(
+            to_decimal(&[0xff; 32]),
+            "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+        );
+        assert_eq!(
+            parse_decimal(
+                "115792089237316195423570985008687907853269984665640564039457584007913129639935"
+            )
+            .unwrap(),
+            [0xff; 32]
+        );
+        assert!(parse_decimal(
+            "115792089237316195423570985008687907853269984665640564039457584007913129639936"
+        )
+        .is_err());
+        assert_eq!(format_units("1500000", 6), "1.5");
+        assert_eq!(format_units("5", 6), "0.000005");
+        assert_eq!(format_units("2000000", 6), "2");
+        assert_eq!(AbiValue::Int([0xff; 32]).to_json(), serde_json::json!("-1"));
+    }
+}
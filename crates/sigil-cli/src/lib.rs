@@ -5,6 +5,7 @@
 
 pub mod client;
 pub mod commands;
+pub mod decode;
 pub mod tools;
 
 pub use client::SigilClient;
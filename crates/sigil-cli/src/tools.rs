@@ -4,7 +4,7 @@
 
 use serde::{Deserialize, Serialize};
 
-use crate::client::{ClientError, SigilClient};
+use crate::client::{ClientError, SigilClient, SignOptions};
 
 /// Tool: sign_blockchain_transaction
 ///
@@ -20,6 +20,10 @@ pub struct SignTransactionTool {
 
     /// Human-readable description of the transaction
     pub description: String,
+
+    /// Child disk to sign with; required when several disks are inserted
+    #[serde(default)]
+    pub child_id: Option<String>,
 }
 
 /// Result from the sign transaction tool
@@ -77,6 +81,10 @@ pub struct CheckDiskResult {
     /// Whether disk is valid for signing
     pub is_valid: Option<bool>,
 
+    /// Identifiers of every inserted disk
+    #[serde(default)]
+    pub disk_ids: Vec<String>,
+
     /// Human-readable status message
     pub message: String,
 }
@@ -144,7 +152,27 @@ pub async fn execute_sign_transaction(tool: SignTransactionTool) -> SignTransact
                 ),
             };
         }
-        Ok(status) if !status.is_valid.unwrap_or(false) => {
+        Ok(status) if status.disks.len() > 1 && tool.child_id.is_none() => {
+            let ids: Vec<_> = status
+                .disks
+                .iter()
+                .map(|disk| disk.child_id.as_str())
+                .collect();
+            return SignTransactionResult {
+                success: false,
+                signature: None,
+                v: None,
+                r: None,
+                s: None,
+                presig_index: None,
+                proof_hash: None,
+                error: Some(format!(
+                    "Multiple signing disks detected ({}). Specify child_id to choose one.",
+                    ids.join(", ")
+                )),
+            };
+        }
+        Ok(status) if status.disks.len() <= 1 && !status.is_valid.unwrap_or(false) => {
             return SignTransactionResult {
                 success: false,
                 signature: None,
@@ -187,8 +215,17 @@ pub async fn execute_sign_transaction(tool: SignTransactionTool) -> SignTransact
     }
 
     // Perform signing
+    let options = SignOptions {
+        child_id: tool.child_id.clone(),
+        ..Default::default()
+    };
     match client
-        .sign(&tool.transaction_hash, tool.chain_id, &tool.description)
+        .sign_with_options(
+            &tool.transaction_hash,
+            tool.chain_id,
+            &tool.description,
+            &options,
+        )
         .await
     {
         Ok(result) => {
@@ -236,8 +273,19 @@ pub async fn execute_check_disk(_tool: CheckDiskTool) -> CheckDiskResult {
 
     match client.get_disk_status().await {
         Ok(status) => {
+            let disk_ids: Vec<String> = status
+                .disks
+                .iter()
+                .map(|disk| disk.child_id.clone())
+                .collect();
             let message = if !status.detected {
                 "No signing disk detected. Please insert your Sigil floppy disk.".to_string()
+            } else if disk_ids.len() > 1 {
+                format!(
+                    "{} signing disks detected: sigil_{}. Specify a child_id when signing.",
+                    disk_ids.len(),
+                    disk_ids.join(", sigil_")
+                )
             } else if !status.is_valid.unwrap_or(false) {
                 format!(
                     "Disk sigil_{} detected but not valid for signing",
@@ -260,6 +308,7 @@ pub async fn execute_check_disk(_tool: CheckDiskTool) -> CheckDiskResult {
                 presigs_total: status.presigs_total,
                 days_until_expiry: status.days_until_expiry,
                 is_valid: status.is_valid,
+                disk_ids,
                 message,
             }
         }
@@ -270,6 +319,7 @@ pub async fn execute_check_disk(_tool: CheckDiskTool) -> CheckDiskResult {
             presigs_total: None,
             days_until_expiry: None,
             is_valid: None,
+            disk_ids: Vec::new(),
             message: "Sigil daemon is not running. Start it with: sigil-daemon".to_string(),
         },
         Err(e) => CheckDiskResult {
@@ -279,6 +329,7 @@ pub async fn execute_check_disk(_tool: CheckDiskTool) -> CheckDiskResult {
             presigs_total: None,
             days_until_expiry: None,
             is_valid: None,
+            disk_ids: Vec::new(),
             message: format!("Failed to check disk status: {}", e),
         },
     }
@@ -0,0 +1,578 @@
+//! Agent-side audit log and reconciliation bundle
+//!
+//! The daemon keeps its own append-only record of every signature it
+//! completes, independent of the usage log on the floppy. Each record is
+//! hash-chained to the previous record for the same child and signed with
+//! the daemon's audit key, so the mother can detect a tampered disk log by
+//! diffing it against the agent's view during reconciliation.
+//!
+//! Bundles travel to the mother either as a JSON file or as a sequence of
+//! QR payloads (`SIGIL:AUDIT:1:NN/MM:<base64>`).
+
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use k256::ecdsa::{
+    signature::{Signer as _, Verifier as _},
+    Signature as K256Signature, SigningKey, VerifyingKey,
+};
+use serde::{Deserialize, Serialize};
+
+use crate::crypto::{sha256_multi, PublicKey};
+use crate::error::{Error, Result};
+use crate::types::{
+    hex_bytes_32, hex_bytes_33, ChainId, ChildId, MessageHash, Signature, ZkProofHash,
+};
+use crate::usage::UsageLogEntry;
+
+/// Magic bytes identifying a serialized audit bundle
+pub const AUDIT_BUNDLE_MAGIC: &[u8; 8] = b"SGLAUDIT";
+
+/// Audit bundle format version
+pub const AUDIT_BUNDLE_VERSION: u32 = 1;
+
+/// Prefix for QR-encoded audit bundle chunks
+pub const AUDIT_QR_PREFIX: &str = "SIGIL:AUDIT:1:";
+
+/// Maximum payload characters per QR chunk (Version 40, Low ECC, minus header)
+const MAX_QR_CHUNK_CHARS: usize = 2900;
+
+/// Hash linking the first record of a chain
+pub const GENESIS_HASH: [u8; 32] = [0u8; 32];
+
+/// Longest description a record can carry (its length is encoded as a u16)
+pub const MAX_DESCRIPTION_LEN: usize = u16::MAX as usize;
+
+/// Serialized size of a record with an empty description
+const MIN_RECORD_LEN: usize = 251 + 64;
+
+/// Size of the fixed bundle header preceding the records
+const BUNDLE_HEADER_LEN: usize = 153;
+
+/// A single signed entry in the agent's audit log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditRecord {
+    /// Position of this record in the child's chain (starting at 0)
+    pub sequence: u64,
+
+    /// Child the signature was produced for
+    pub child_id: ChildId,
+
+    /// Index of the presignature used
+    pub presig_index: u32,
+
+    /// R point of the presignature (identifies the presig batch)
+    #[serde(with = "hex_bytes_33")]
+    pub r_point: [u8; 33],
+
+    /// Unix timestamp of the signing operation
+    pub timestamp: u64,
+
+    /// Hash of the message that was signed
+    pub message_hash: MessageHash,
+
+    /// The produced signature
+    pub signature: Signature,
+
+    /// Chain ID
+    pub chain_id: ChainId,
+
+    /// Hash of the zkVM proof
+    pub zkproof_hash: ZkProofHash,
+
+    /// Description as written to the disk usage log
+    pub description: String,
+
+    /// Hash of the previous record in this child's chain
+    #[serde(with = "hex_bytes_32")]
+    pub prev_hash: [u8; 32],
+
+    /// Audit key signature over `record_hash()`
+    pub auditor_signature: Signature,
+}
+
+impl AuditRecord {
+    /// Build an unsigned record mirroring a usage log entry
+    pub fn from_usage_entry(
+        sequence: u64,
+        child_id: ChildId,
+        r_point: [u8; 33],
+        entry: &UsageLogEntry,
+        prev_hash: [u8; 32],
+    ) -> Self {
+        Self {
+            sequence,
+            child_id,
+            presig_index: entry.presig_index,
+            r_point,
+            timestamp: entry.timestamp,
+            message_hash: entry.message_hash,
+            signature: entry.signature,
+            chain_id: entry.chain_id,
+            zkproof_hash: entry.zkproof_hash,
+            description: bounded_description(&entry.description).to_string(),
+            prev_hash,
+            auditor_signature: Signature::new([0u8; 64]),
+        }
+    }
+
+    /// Bytes covered by the record hash (everything except the signature)
+    ///
+    /// Descriptions longer than [`MAX_DESCRIPTION_LEN`] are cut at the last
+    /// character boundary that fits, so the encoded length never wraps.
+    pub fn signable_bytes(&self) -> Vec<u8> {
+        let desc = bounded_description(&self.description).as_bytes();
+        let mut bytes = Vec::with_capacity(256 + desc.len());
+
+        bytes.extend_from_slice(&self.sequence.to_le_bytes()); // 8
+        bytes.extend_from_slice(self.child_id.as_bytes()); // 32
+        bytes.extend_from_slice(&self.presig_index.to_le_bytes()); // 4
+        bytes.extend_from_slice(&self.r_point); // 33
+        bytes.extend_from_slice(&self.timestamp.to_le_bytes()); // 8
+        bytes.extend_from_slice(self.message_hash.as_bytes()); // 32
+        bytes.extend_from_slice(self.signature.as_bytes()); // 64
+        bytes.extend_from_slice(&self.chain_id.0.to_le_bytes()); // 4
+        bytes.extend_from_slice(self.zkproof_hash.as_bytes()); // 32
+        bytes.extend_from_slice(&self.prev_hash); // 32
+        bytes.extend_from_slice(&(desc.len() as u16).to_le_bytes()); // 2
+        bytes.extend_from_slice(desc);
+
+        bytes
+    }
+
+    /// Hash of this record, used as `prev_hash` by the next record
+    pub fn record_hash(&self) -> [u8; 32] {
+        sha256_multi(&[b"sigil-audit-record:", &self.signable_bytes()])
+    }
+
+    /// Sign the record with the audit key
+    pub fn sign(&mut self, key: &SigningKey) {
+        let sig: K256Signature = key.sign(&self.record_hash());
+        self.auditor_signature = Signature::new(sig.to_bytes().into());
+    }
+
+    /// Verify the audit signature on this record
+    pub fn verify_signature(&self, auditor: &PublicKey) -> Result<()> {
+        verify_with(auditor, &self.record_hash(), &self.auditor_signature)
+    }
+
+    /// Serialize to bytes (signable bytes followed by the signature)
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = self.signable_bytes();
+        bytes.extend_from_slice(self.auditor_signature.as_bytes());
+        bytes
+    }
+
+    /// Deserialize from bytes
+    pub fn from_bytes(bytes: &[u8]) -> Option<Self> {
+        // Fixed portion is 251 bytes, followed by description and signature
+        if bytes.len() < MIN_RECORD_LEN {
+            return None;
+        }
+
+        let sequence = u64::from_le_bytes(bytes[0..8].try_into().ok()?);
+        let child_id = ChildId::new(bytes[8..40].try_into().ok()?);
+        let presig_index = u32::from_le_bytes(bytes[40..44].try_into().ok()?);
+        let r_point: [u8; 33] = bytes[44..77].try_into().ok()?;
+        let timestamp = u64::from_le_bytes(bytes[77..85].try_into().ok()?);
+        let message_hash = MessageHash::new(bytes[85..117].try_into().ok()?);
+        let signature = Signature::new(bytes[117..181].try_into().ok()?);
+        let chain_id = ChainId::new(u32::from_le_bytes(bytes[181..185].try_into().ok()?));
+        let zkproof_hash = ZkProofHash::new(bytes[185..217].try_into().ok()?);
+        let prev_hash: [u8; 32] = bytes[217..249].try_into().ok()?;
+        let desc_len = u16::from_le_bytes(bytes[249..251].try_into().ok()?) as usize;
+
+        let desc_end = 251 + desc_len;
+        if bytes.len() != desc_end + 64 {
+            return None;
+        }
+        let description = String::from_utf8(bytes[251..desc_end].to_vec()).ok()?;
+        let auditor_signature = Signature::new(bytes[desc_end..desc_end + 64].try_into().ok()?);
+
+        Some(Self {
+            sequence,
+            child_id,
+            presig_index,
+            r_point,
+            timestamp,
+            message_hash,
+            signature,
+            chain_id,
+            zkproof_hash,
+            description,
+            prev_hash,
+            auditor_signature,
+        })
+    }
+}
+
+/// Export of one child's audit chain for the mother's reconciliation ceremony
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuditBundle {
+    /// Child the bundle covers
+    pub child_id: ChildId,
+
+    /// Public key of the daemon's audit key
+    pub auditor_pubkey: PublicKey,
+
+    /// Unix timestamp of the export
+    pub exported_at: u64,
+
+    /// Every record in the child's chain, in sequence order
+    pub records: Vec<AuditRecord>,
+
+    /// Audit key signature over `head_hash()` (detects tail truncation)
+    pub head_signature: Signature,
+}
+
+impl AuditBundle {
+    /// Assemble and sign a bundle from a child's chain
+    pub fn new(
+        child_id: ChildId,
+        key: &SigningKey,
+        exported_at: u64,
+        records: Vec<AuditRecord>,
+    ) -> Self {
+        let mut bundle = Self {
+            child_id,
+            auditor_pubkey: auditor_public_key(key),
+            exported_at,
+            records,
+            head_signature: Signature::new([0u8; 64]),
+        };
+        let sig: K256Signature = key.sign(&bundle.head_hash());
+        bundle.head_signature = Signature::new(sig.to_bytes().into());
+        bundle
+    }
+
+    /// Default file name used when exporting a bundle to removable media
+    pub fn default_file_name(child_id: &ChildId) -> String {
+        format!("sigil_audit_{}.json", child_id.short())
+    }
+
+    /// Hash committing to the child, export time and the chain head
+    pub fn head_hash(&self) -> [u8; 32] {
+        let last = self
+            .records
+            .last()
+            .map(|r| r.record_hash())
+            .unwrap_or(GENESIS_HASH);
+
+        sha256_multi(&[
+            b"sigil-audit-head:",
+            self.child_id.as_bytes(),
+            &self.exported_at.to_le_bytes(),
+            &(self.records.len() as u64).to_le_bytes(),
+            &last,
+        ])
+    }
+
+    /// Verify the hash chain, every record signature and the head signature
+    pub fn verify(&self) -> Result<()> {
+        let mut prev_hash = GENESIS_HASH;
+
+        for (i, record) in self.records.iter().enumerate() {
+            if record.child_id != self.child_id {
+                return Err(Error::AuditVerificationFailed(format!(
+                    "Record {} belongs to child {}",
+                    i,
+                    record.child_id.short()
+                )));
+            }
+            if record.sequence != i as u64 {
+                return Err(Error::AuditVerificationFailed(format!(
+                    "Sequence gap: expected {}, found {}",
+                    i, record.sequence
+                )));
+            }
+            if record.prev_hash != prev_hash {
+                return Err(Error::AuditVerificationFailed(format!(
+                    "Broken hash chain at sequence {}",
+                    record.sequence
+                )));
+            }
+            record.verify_signature(&self.auditor_pubkey).map_err(|_| {
+                Error::AuditVerificationFailed(format!(
+                    "Invalid signature on record {}",
+                    record.sequence
+                ))
+            })?;
+            prev_hash = record.record_hash();
+        }
+
+        verify_with(
+            &self.auditor_pubkey,
+            &self.head_hash(),
+            &self.head_signature,
+        )
+        .map_err(|_| Error::AuditVerificationFailed("Invalid bundle head signature".to_string()))
+    }
+
+    /// Serialize to bytes
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::new();
+
+        bytes.extend_from_slice(AUDIT_BUNDLE_MAGIC);
+        bytes.extend_from_slice(&AUDIT_BUNDLE_VERSION.to_le_bytes());
+        bytes.extend_from_slice(self.child_id.as_bytes());
+        bytes.extend_from_slice(self.auditor_pubkey.as_bytes());
+        bytes.extend_from_slice(&self.exported_at.to_le_bytes());
+        bytes.extend_from_slice(self.head_signature.as_bytes());
+        bytes.extend_from_slice(&(self.records.len() as u32).to_le_bytes());
+
+        for record in &self.records {
+            let record_bytes = record.to_bytes();
+            bytes.extend_from_slice(&(record_bytes.len() as u32).to_le_bytes());
+            bytes.extend_from_slice(&record_bytes);
+        }
+
+        bytes
+    }
+
+    /// Deserialize from bytes
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self> {
+        let malformed = || Error::Deserialization("Malformed audit bundle".to_string());
+
+        if bytes.len() < BUNDLE_HEADER_LEN || &bytes[0..8] != AUDIT_BUNDLE_MAGIC {
+            return Err(malformed());
+        }
+
+        let version = u32::from_le_bytes(bytes[8..12].try_into().map_err(|_| malformed())?);
+        if version != AUDIT_BUNDLE_VERSION {
+            return Err(Error::UnsupportedVersion(version));
+        }
+
+        let child_id = ChildId::new(bytes[12..44].try_into().map_err(|_| malformed())?);
+        let auditor_pubkey = PublicKey::new(bytes[44..77].try_into().map_err(|_| malformed())?);
+        let exported_at = u64::from_le_bytes(bytes[77..85].try_into().map_err(|_| malformed())?);
+        let head_signature = Signature::new(bytes[85..149].try_into().map_err(|_| malformed())?);
+        let count = u32::from_le_bytes(bytes[149..153].try_into().map_err(|_| malformed())?);
+
+        // Never reserve more records than the remaining bytes could hold
+        let max_records = (bytes.len() - BUNDLE_HEADER_LEN) / MIN_RECORD_LEN;
+        let mut records = Vec::with_capacity((count as usize).min(max_records));
+        let mut offset = BUNDLE_HEADER_LEN;
+        for _ in 0..count {
+            if offset + 4 > bytes.len() {
+                return Err(malformed());
+            }
+            let len = u32::from_le_bytes(
+                bytes[offset..offset + 4]
+                    .try_into()
+                    .map_err(|_| malformed())?,
+            ) as usize;
+            offset += 4;
+
+            if offset + len > bytes.len() {
+                return Err(malformed());
+            }
+            records
+                .push(AuditRecord::from_bytes(&bytes[offset..offset + len]).ok_or_else(malformed)?);
+            offset += len;
+        }
+
+        Ok(Self {
+            child_id,
+            auditor_pubkey,
+            exported_at,
+            records,
+            head_signature,
+        })
+    }
+
+    /// Encode as a sequence of QR payloads
+    pub fn to_qr_chunks(&self) -> Vec<String> {
+        let encoded = BASE64.encode(self.to_bytes());
+        let total = encoded.len().div_ceil(MAX_QR_CHUNK_CHARS).max(1);
+
+        (0..total)
+            .map(|i| {
+                let start = i * MAX_QR_CHUNK_CHARS;
+                let end = (start + MAX_QR_CHUNK_CHARS).min(encoded.len());
+                format!(
+                    "{}{:02}/{:02}:{}",
+                    AUDIT_QR_PREFIX,
+                    i + 1,
+                    total,
+                    &encoded[start..end]
+                )
+            })
+            .collect()
+    }
+
+    /// Decode from scanned QR payloads (any order)
+    pub fn from_qr_chunks<S: AsRef<str>>(chunks: &[S]) -> Result<Self> {
+        let invalid = |msg: &str| Error::Deserialization(format!("Audit QR: {}", msg));
+
+        let mut parts: Vec<Option<String>> = Vec::new();
+        for chunk in chunks {
+            let data = chunk
+                .as_ref()
+                .trim()
+                .strip_prefix(AUDIT_QR_PREFIX)
+                .ok_or_else(|| invalid("missing prefix"))?;
+            let (header, payload) = data
+                .split_once(':')
+                .ok_or_else(|| invalid("missing chunk header"))?;
+            let (index, total) = header
+                .split_once('/')
+                .ok_or_else(|| invalid("malformed chunk header"))?;
+            let index: usize = index.parse().map_err(|_| invalid("bad chunk index"))?;
+            let total: usize = total.parse().map_err(|_| invalid("bad chunk total"))?;
+            if total > chunks.len() {
+                return Err(invalid("incomplete chunk set"));
+            }
+
+            if parts.is_empty() {
+                parts = vec![None; total];
+            }
+            if total != parts.len() || index == 0 || index > total {
+                return Err(invalid("inconsistent chunk numbering"));
+            }
+            parts[index - 1] = Some(payload.to_string());
+        }
+
+        if parts.is_empty() || parts.iter().any(|p| p.is_none()) {
+            return Err(invalid("incomplete chunk set"));
+        }
+
+        let encoded: String = parts.into_iter().flatten().collect();
+        let bytes = BASE64
+            .decode(encoded)
+            .map_err(|e| invalid(&e.to_string()))?;
+        Self::from_bytes(&bytes)
+    }
+}
+
+/// Longest prefix of `description` that fits [`MAX_DESCRIPTION_LEN`] bytes
+fn bounded_description(description: &str) -> &str {
+    if description.len() <= MAX_DESCRIPTION_LEN {
+        return description;
+    }
+    let mut end = MAX_DESCRIPTION_LEN;
+    while !description.is_char_boundary(end) {
+        end -= 1;
+    }
+    &description[..end]
+}
+
+/// Compressed public key for an audit signing key
+pub fn auditor_public_key(key: &SigningKey) -> PublicKey {
+    let encoded = key.verifying_key().to_encoded_point(true);
+    let mut bytes = [0u8; 33];
+    bytes.copy_from_slice(encoded.as_bytes());
+    PublicKey::new(bytes)
+}
+
+/// Verify an audit signature over a 32-byte digest
+fn verify_with(pubkey: &PublicKey, digest: &[u8; 32], signature: &Signature) -> Result<()> {
+    let key = VerifyingKey::from_sec1_bytes(pubkey.as_bytes())
+        .map_err(|e| Error::Crypto(format!("Invalid auditor key: {}", e)))?;
+    let sig = K256Signature::from_slice(signature.as_bytes())
+        .map_err(|e| Error::Crypto(format!("Invalid signature format: {}", e)))?;
+    key.verify(digest, &sig)
+        .map_err(|_| Error::SignatureVerificationFailed)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::TxHash;
+
+    fn test_key() -> SigningKey {
+        SigningKey::from_bytes(&[7u8; 32].into()).unwrap()
+    }
+
+    fn test_chain(key: &SigningKey, child_id: ChildId, count: u32) -> Vec<AuditRecord> {
+        let mut prev = GENESIS_HASH;
+        (0..count)
+            .map(|i| {
+                let entry = UsageLogEntry::new(
+                    i,
+                    1_700_000_000 + i as u64,
+                    MessageHash::new([i as u8; 32]),
+                    Signature::new([i as u8; 64]),
+                    ChainId::ETHEREUM,
+                    TxHash::new([0u8; 32]),
+                    ZkProofHash::new([1u8; 32]),
+                    format!("tx {}", i),
+                );
+                let mut record =
+                    AuditRecord::from_usage_entry(i as u64, child_id, [2u8; 33], &entry, prev);
+                record.sign(key);
+                prev = record.record_hash();
+                record
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_bundle_verifies_and_roundtrips() {
+        let key = test_key();
+        let child_id = ChildId::new([9u8; 32]);
+        let bundle = AuditBundle::new(child_id, &key, 1_700_000_100, test_chain(&key, child_id, 3));
+
+        assert!(bundle.verify().is_ok());
+
+        let recovered = AuditBundle::from_bytes(&bundle.to_bytes()).unwrap();
+        assert_eq!(bundle, recovered);
+
+        let from_qr = AuditBundle::from_qr_chunks(&bundle.to_qr_chunks()).unwrap();
+        assert_eq!(bundle, from_qr);
+    }
+
+    #[test]
+    fn test_tampered_record_detected() {
+        let key = test_key();
+        let child_id = ChildId::new([9u8; 32]);
+        let mut bundle =
+            AuditBundle::new(child_id, &key, 1_700_000_100, test_chain(&key, child_id, 3));
+
+        bundle.records[1].chain_id = ChainId::BASE;
+        assert!(matches!(
+            bundle.verify(),
+            Err(Error::AuditVerificationFailed(_))
+        ));
+    }
+
+    #[test]
+    fn test_truncated_bundle_detected() {
+        let key = test_key();
+        let child_id = ChildId::new([9u8; 32]);
+        let mut bundle =
+            AuditBundle::new(child_id, &key, 1_700_000_100, test_chain(&key, child_id, 3));
+
+        bundle.records.pop();
+        assert!(bundle.verify().is_err());
+    }
+
+    #[test]
+    fn test_hostile_lengths_rejected() {
+        let key = test_key();
+        let child_id = ChildId::new([9u8; 32]);
+        let bundle = AuditBundle::new(child_id, &key, 1_700_000_100, test_chain(&key, child_id, 1));
+
+        // Record count far beyond what the bytes can hold
+        let mut bytes = bundle.to_bytes();
+        bytes[149..153].copy_from_slice(&u32::MAX.to_le_bytes());
+        assert!(AuditBundle::from_bytes(&bytes).is_err());
+
+        // Chunk total far beyond the chunks supplied
+        let chunk = format!("{}1/18446744073709551615:AAAA", AUDIT_QR_PREFIX);
+        assert!(AuditBundle::from_qr_chunks(&[chunk]).is_err());
+    }
+
+    #[test]
+    fn test_oversize_description_bounded() {
+        let key = test_key();
+        let child_id = ChildId::new([9u8; 32]);
+        let mut record = test_chain(&key, child_id, 1).remove(0);
+        record.description = "é".repeat(MAX_DESCRIPTION_LEN);
+        record.sign(&key);
+
+        let recovered = AuditRecord::from_bytes(&record.to_bytes()).unwrap();
+        assert!(recovered.description.len() <= MAX_DESCRIPTION_LEN);
+        assert!(record.description.starts_with(&recovered.description));
+        assert!(recovered
+            .verify_signature(&auditor_public_key(&key))
+            .is_ok());
+    }
+}
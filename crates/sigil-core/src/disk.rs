@@ -17,7 +17,8 @@
 //! 0x008D      4           max_uses_before_reconcile
 //! 0x0091      4           uses_since_reconcile
 //! 0x0095      64          mother_signature (signs header)
-//! 0x00D5      43          reserved
+//! 0x00D5      8           last_seen (agent clock high-water mark)
+//! 0x00DD      35          reserved
 //!
 //! 0x0100      256000      presig_table[1000] (256 bytes each)
 //!
@@ -81,6 +82,12 @@ pub struct DiskHeader {
 
     /// Mother's signature over the header (excluding this field)
     pub mother_signature: Signature,
+
+    /// Latest agent clock reading recorded while signing (0 if never)
+    ///
+    /// Not covered by the mother signature; the agent raises it on every
+    /// signature so a rolled-back clock can be detected.
+    pub last_seen: u64,
 }
 
 impl DiskHeader {
@@ -103,6 +110,7 @@ impl DiskHeader {
             created_at,
             expiry: DiskExpiry::new(created_at),
             mother_signature: Signature::new([0u8; 64]), // Placeholder until signed
+            last_seen: 0,
         }
     }
 
@@ -149,7 +157,10 @@ impl DiskHeader {
         // Mother signature (0x0095, 64 bytes)
         bytes[0x0095..0x00D5].copy_from_slice(self.mother_signature.as_bytes());
 
-        // Reserved (0x00D5, 43 bytes) - already zeroed
+        // Last seen (0x00D5, 8 bytes)
+        bytes[0x00D5..0x00DD].copy_from_slice(&self.last_seen.to_le_bytes());
+
+        // Reserved (0x00DD, 35 bytes) - already zeroed
 
         bytes
     }
@@ -207,6 +218,9 @@ impl DiskHeader {
         sig_bytes.copy_from_slice(&bytes[0x0095..0x00D5]);
         let mother_signature = Signature::new(sig_bytes);
 
+        // Parse clock high-water mark
+        let last_seen = u64::from_le_bytes(bytes[0x00D5..0x00DD].try_into().unwrap());
+
         Ok(Self {
             magic,
             version,
@@ -218,6 +232,7 @@ impl DiskHeader {
             created_at,
             expiry,
             mother_signature,
+            last_seen,
         })
     }
 
@@ -446,6 +461,64 @@ impl DiskFormat {
         Ok(())
     }
 
+    /// Plan the writes that turn the on-disk `previous` image into `self`
+    ///
+    /// Only changed bytes are returned, in the order they must reach the
+    /// media: new usage log records, then presig entries, then the log entry
+    /// count, and the header last. Until the header lands, an interrupted
+    /// write leaves more presigs marked used than the header counts, which
+    /// [`DiskFormat::validate`] rejects. Returns `None` when the presig table
+    /// itself was resized and the whole image must be rewritten.
+    pub fn dirty_regions(&self, previous: &DiskFormat) -> Option<Vec<DiskRegion>> {
+        if self.presigs.len() != previous.presigs.len() {
+            return None;
+        }
+
+        let mut regions = Vec::new();
+
+        // Usage log records past the first changed byte (the count comes later)
+        let log = self.usage_log.to_bytes();
+        let previous_log = previous.usage_log.to_bytes();
+        let first_change = log[4..]
+            .iter()
+            .zip(&previous_log[4..])
+            .position(|(a, b)| a != b)
+            .map(|i| i + 4)
+            .unwrap_or_else(|| previous_log.len().min(log.len()));
+        if first_change < log.len() {
+            regions.push(DiskRegion::new(
+                USAGE_LOG_OFFSET + first_change,
+                &log[first_change..],
+            ));
+        }
+
+        // Presig entries (normally just the status byte)
+        for (i, (presig, old)) in self.presigs.iter().zip(&previous.presigs).enumerate() {
+            let offset = PRESIG_TABLE_OFFSET + i * PRESIG_ENTRY_SIZE;
+            regions.extend(DiskRegion::diff(
+                offset,
+                &presig.to_bytes(),
+                &old.to_bytes(),
+            ));
+        }
+
+        // Usage log entry count commits the appended records
+        regions.extend(DiskRegion::diff(
+            USAGE_LOG_OFFSET,
+            &log[..4],
+            &previous_log[..4],
+        ));
+
+        // Header counters
+        regions.extend(DiskRegion::diff(
+            0,
+            &self.header.to_bytes(),
+            &previous.header.to_bytes(),
+        ));
+
+        Some(regions)
+    }
+
     /// Get disk status summary for display
     pub fn status_summary(&self, current_time: u64) -> DiskStatus {
         let expiry_status = crate::expiry::ExpiryStatus::from_expiry(
@@ -465,6 +538,38 @@ impl DiskFormat {
     }
 }
 
+/// A run of bytes to write at a fixed offset of the disk image
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DiskRegion {
+    /// Byte offset from the start of the image
+    pub offset: usize,
+
+    /// Bytes to write
+    pub bytes: Vec<u8>,
+}
+
+impl DiskRegion {
+    /// Create a region from a byte slice
+    pub fn new(offset: usize, bytes: &[u8]) -> Self {
+        Self {
+            offset,
+            bytes: bytes.to_vec(),
+        }
+    }
+
+    /// Smallest region covering the differences between two equal-length slices
+    fn diff(offset: usize, new: &[u8], old: &[u8]) -> Option<Self> {
+        let first = new.iter().zip(old).position(|(a, b)| a != b)?;
+        let last = new.iter().zip(old).rposition(|(a, b)| a != b)?;
+        Some(Self::new(offset + first, &new[first..=last]))
+    }
+
+    /// Offset one past the last byte of the region
+    pub fn end(&self) -> usize {
+        self.offset + self.bytes.len()
+    }
+}
+
 /// Summary status of a disk
 #[derive(Debug, Clone)]
 pub struct DiskStatus {
@@ -521,6 +626,16 @@ mod tests {
         assert_eq!(header.child_id, recovered.child_id);
         assert_eq!(header.presig_total, recovered.presig_total);
         assert_eq!(header.created_at, recovered.created_at);
+        assert_eq!(recovered.last_seen, 0);
+
+        // The clock high-water mark is outside the mother-signed bytes
+        let mut seen = header.clone();
+        seen.last_seen = 1700000500;
+        assert_eq!(seen.signable_bytes(), header.signable_bytes());
+        assert_eq!(
+            DiskHeader::from_bytes(&seen.to_bytes()).unwrap().last_seen,
+            1700000500
+        );
     }
 
     #[test]
@@ -553,4 +668,73 @@ mod tests {
         assert_eq!(disk.header.child_id, recovered.header.child_id);
         assert_eq!(disk.presigs.len(), recovered.presigs.len());
     }
+
+    #[test]
+    fn test_dirty_regions_after_signing() {
+        use crate::types::{ChainId, MessageHash, Signature, TxHash, ZkProofHash};
+        use crate::usage::UsageLogEntry;
+
+        let header = DiskHeader::new(
+            ChildId::new([1u8; 32]),
+            PublicKey::new([2u8; 33]),
+            DerivationPath::ethereum_hardened(0),
+            10,
+            1700000000,
+        );
+        let presigs: Vec<PresigColdShare> = (0..10)
+            .map(|i| PresigColdShare::new([i as u8; 33], [i as u8; 32], [i as u8; 32]))
+            .collect();
+        let previous = DiskFormat::new(header, presigs);
+
+        let mut disk = previous.clone();
+        disk.mark_presig_used(3).unwrap();
+        disk.usage_log
+            .push(UsageLogEntry::new(
+                3,
+                1700000100,
+                MessageHash::new([5u8; 32]),
+                Signature::new([6u8; 64]),
+                ChainId::ETHEREUM,
+                TxHash::new([0u8; 32]),
+                ZkProofHash::new([7u8; 32]),
+                "transfer".to_string(),
+            ))
+            .unwrap();
+
+        let regions = disk.dirty_regions(&previous).unwrap();
+        let offsets: Vec<usize> = regions.iter().map(|r| r.offset).collect();
+        assert_eq!(
+            offsets,
+            vec![
+                USAGE_LOG_OFFSET + 4,
+                PRESIG_TABLE_OFFSET + 3 * PRESIG_ENTRY_SIZE + 97,
+                USAGE_LOG_OFFSET,
+                0x0071,
+            ]
+        );
+        assert_eq!(regions[1].bytes, vec![PresigStatus::Used as u8]);
+
+        // Applying the regions to the old image yields the new one
+        let mut image = previous.to_bytes();
+        for region in &regions {
+            if image.len() < region.end() {
+                image.resize(region.end(), 0);
+            }
+            image[region.offset..region.end()].copy_from_slice(&region.bytes);
+        }
+        assert_eq!(image, disk.to_bytes());
+
+        // Stopping before the header leaves a detectable mismatch
+        let mut torn = previous.to_bytes();
+        for region in &regions[..3] {
+            if torn.len() < region.end() {
+                torn.resize(region.end(), 0);
+            }
+            torn[region.offset..region.end()].copy_from_slice(&region.bytes);
+        }
+        let torn = DiskFormat::from_bytes(&torn).unwrap();
+        assert!(torn.validate(1700000200).is_err());
+
+        assert!(disk.dirty_regions(&disk).unwrap().is_empty());
+    }
 }
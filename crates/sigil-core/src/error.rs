@@ -92,6 +92,22 @@ pub enum Error {
     /// Usage log anomaly detected
     #[error("Usage log anomaly: {0}")]
     UsageLogAnomaly(String),
+
+    /// Agent audit bundle failed verification
+    #[error("Audit verification failed: {0}")]
+    AuditVerificationFailed(String),
+
+    /// Malformed RLP encoding
+    #[error("Invalid RLP: {0}")]
+    InvalidRlp(String),
+
+    /// Malformed EVM transaction
+    #[error("Invalid transaction: {0}")]
+    InvalidTransaction(String),
+
+    /// EVM transaction type that can't be decoded
+    #[error("Unsupported transaction type 0x{0:02x}")]
+    UnsupportedTransactionType(u8),
 }
 
 impl From<bitcode::Error> for Error {
@@ -0,0 +1,498 @@
+//! EVM transaction encoding
+//!
+//! RLP encoding and decoding, and the signing hash of legacy (EIP-155),
+//! EIP-2930 and EIP-1559 transactions. The CLI uses it to decode what it is
+//! asked to sign and the daemon to match mined transactions to the usage log,
+//! so both agree on what a signing hash is.
+
+use sha3::{Digest, Keccak256};
+
+use crate::error::{Error, Result};
+
+/// Keccak-256 digest
+pub fn keccak256(data: &[u8]) -> [u8; 32] {
+    Keccak256::digest(data).into()
+}
+
+/// Big-endian bytes without leading zeros, as RLP encodes integers
+pub fn trim_zeros(bytes: &[u8]) -> &[u8] {
+    let start = bytes.iter().position(|b| *b != 0).unwrap_or(bytes.len());
+    &bytes[start..]
+}
+
+fn rlp_header(offset: u8, len: usize) -> Vec<u8> {
+    if len < 56 {
+        vec![offset + len as u8]
+    } else {
+        let len_bytes = trim_zeros(&(len as u64).to_be_bytes()).to_vec();
+        let mut header = vec![offset + 55 + len_bytes.len() as u8];
+        header.extend(len_bytes);
+        header
+    }
+}
+
+/// RLP-encode a byte string
+pub fn rlp_bytes(bytes: &[u8]) -> Vec<u8> {
+    if bytes.len() == 1 && bytes[0] < 0x80 {
+        return bytes.to_vec();
+    }
+    let mut out = rlp_header(0x80, bytes.len());
+    out.extend_from_slice(bytes);
+    out
+}
+
+/// RLP-encode an integer
+pub fn rlp_uint(n: u64) -> Vec<u8> {
+    rlp_bytes(trim_zeros(&n.to_be_bytes()))
+}
+
+/// RLP-encode a list of already encoded items
+pub fn rlp_list(items: &[Vec<u8>]) -> Vec<u8> {
+    let payload = items.concat();
+    let mut out = rlp_header(0xc0, payload.len());
+    out.extend(payload);
+    out
+}
+
+/// Decoded RLP item
+///
+/// Decoding is strict: only the canonical encoding of an item is accepted,
+/// so re-encoding a decoded transaction reproduces its bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rlp<'a> {
+    Bytes(&'a [u8]),
+    List(Vec<Rlp<'a>>),
+}
+
+impl<'a> Rlp<'a> {
+    /// Decode exactly one item
+    pub fn decode(data: &'a [u8]) -> Result<Self> {
+        let (item, rest) = Self::item(data)?;
+        if !rest.is_empty() {
+            return Err(invalid_rlp("trailing bytes"));
+        }
+        Ok(item)
+    }
+
+    fn item(data: &'a [u8]) -> Result<(Self, &'a [u8])> {
+        let (&prefix, rest) = data
+            .split_first()
+            .ok_or_else(|| invalid_rlp("unexpected end"))?;
+        match prefix {
+            0x00..=0x7f => Ok((Rlp::Bytes(&data[..1]), rest)),
+            0x80..=0xb7 => {
+                let (payload, rest) = take(rest, (prefix - 0x80) as usize)?;
+                if let [byte] = payload {
+                    if *byte < 0x80 {
+                        return Err(invalid_rlp("single byte encoded as a string"));
+                    }
+                }
+                Ok((Rlp::Bytes(payload), rest))
+            }
+            0xb8..=0xbf => {
+                let (len, rest) = take(rest, (prefix - 0xb7) as usize)?;
+                let (payload, rest) = take(rest, long_len(len)?)?;
+                Ok((Rlp::Bytes(payload), rest))
+            }
+            0xc0..=0xf7 => {
+                let (payload, rest) = take(rest, (prefix - 0xc0) as usize)?;
+                Ok((Rlp::List(Self::items(payload)?), rest))
+            }
+            0xf8..=0xff => {
+                let (len, rest) = take(rest, (prefix - 0xf7) as usize)?;
+                let (payload, rest) = take(rest, long_len(len)?)?;
+                Ok((Rlp::List(Self::items(payload)?), rest))
+            }
+        }
+    }
+
+    fn items(mut payload: &'a [u8]) -> Result<Vec<Self>> {
+        let mut items = Vec::new();
+        while !payload.is_empty() {
+            let (item, rest) = Self::item(payload)?;
+            items.push(item);
+            payload = rest;
+        }
+        Ok(items)
+    }
+
+    pub fn into_list(self) -> Result<Vec<Self>> {
+        match self {
+            Rlp::List(items) => Ok(items),
+            Rlp::Bytes(_) => Err(invalid_rlp("expected a list")),
+        }
+    }
+
+    pub fn bytes(&self) -> Result<&'a [u8]> {
+        match self {
+            Rlp::Bytes(bytes) => Ok(bytes),
+            Rlp::List(_) => Err(invalid_rlp("expected a string")),
+        }
+    }
+
+    /// Big-endian integer of at most `max_len` bytes
+    pub fn uint_bytes(&self, max_len: usize) -> Result<&'a [u8]> {
+        let bytes = self.bytes()?;
+        if bytes.first() == Some(&0) {
+            return Err(invalid_rlp("integer has leading zeros"));
+        }
+        if bytes.len() > max_len {
+            return Err(invalid_rlp(&format!(
+                "integer exceeds {} bits",
+                max_len * 8
+            )));
+        }
+        Ok(bytes)
+    }
+
+    pub fn uint(&self) -> Result<u64> {
+        let bytes = self.uint_bytes(8)?;
+        Ok(bytes.iter().fold(0u64, |n, b| (n << 8) | *b as u64))
+    }
+}
+
+fn invalid_rlp(message: &str) -> Error {
+    Error::InvalidRlp(message.to_string())
+}
+
+fn take(data: &[u8], len: usize) -> Result<(&[u8], &[u8])> {
+    if len > data.len() {
+        return Err(invalid_rlp("length runs past the end"));
+    }
+    Ok(data.split_at(len))
+}
+
+/// Length of a long-form item, which must not fit the short form
+fn long_len(bytes: &[u8]) -> Result<usize> {
+    if bytes.first() == Some(&0) || bytes.len() > 4 {
+        return Err(invalid_rlp("malformed length"));
+    }
+    let len = bytes.iter().fold(0usize, |n, b| (n << 8) | *b as usize);
+    if len < 56 {
+        return Err(invalid_rlp("short length in long form"));
+    }
+    Ok(len)
+}
+
+/// Transaction envelope
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxKind {
+    Legacy,
+    Eip2930,
+    Eip1559,
+}
+
+impl TxKind {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TxKind::Legacy => "legacy",
+            TxKind::Eip2930 => "eip2930",
+            TxKind::Eip1559 => "eip1559",
+        }
+    }
+}
+
+/// Entry of an EIP-2930 access list
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AccessListItem {
+    pub address: [u8; 20],
+    pub storage_keys: Vec<[u8; 32]>,
+}
+
+/// Fields of a transaction that go into its signing hash
+///
+/// Integers wider than 64 bits are big-endian bytes without leading zeros.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Transaction {
+    pub kind: TxKind,
+    /// Unset for a legacy transaction without EIP-155 replay protection
+    pub chain_id: Option<u64>,
+    pub nonce: u64,
+    /// Gas price of legacy and EIP-2930 transactions
+    pub gas_price: Vec<u8>,
+    /// EIP-1559 fees
+    pub max_priority_fee_per_gas: Vec<u8>,
+    pub max_fee_per_gas: Vec<u8>,
+    pub gas_limit: u64,
+    /// Unset for contract deployments
+    pub to: Option<[u8; 20]>,
+    /// Native value in wei
+    pub value: Vec<u8>,
+    pub data: Vec<u8>,
+    pub access_list: Vec<AccessListItem>,
+    /// Whether the encoding carried a signature
+    pub signed: bool,
+}
+
+impl Transaction {
+    /// Decode a legacy or typed transaction, signed or unsigned
+    pub fn decode(bytes: &[u8]) -> Result<Self> {
+        let (kind, payload) = match bytes.first() {
+            Some(0x01) => (TxKind::Eip2930, &bytes[1..]),
+            Some(0x02) => (TxKind::Eip1559, &bytes[1..]),
+            Some(&first) if first >= 0xc0 => (TxKind::Legacy, bytes),
+            Some(&first) if first <= 0x7f => return Err(Error::UnsupportedTransactionType(first)),
+            _ => {
+                return Err(invalid_transaction(
+                    "not an RLP-encoded transaction".to_string(),
+                ))
+            }
+        };
+        let fields = Rlp::decode(payload)?.into_list()?;
+
+        // Positions of nonce, gas limit and to, and the field count of the
+        // unsigned form
+        let (nonce, gas, to, unsigned_len) = match kind {
+            TxKind::Legacy => (0, 2, 3, 6),
+            TxKind::Eip2930 => (1, 3, 4, 8),
+            TxKind::Eip1559 => (1, 4, 5, 9),
+        };
+        let field = |index: usize| {
+            fields.get(index).ok_or_else(|| {
+                invalid_transaction(format!("{} transaction is too short", kind.as_str()))
+            })
+        };
+
+        let signed = match (kind, fields.len()) {
+            (_, len) if len == unsigned_len => false,
+            // EIP-155 signs [.., chain_id, 0, 0]
+            (TxKind::Legacy, 9) => {
+                !(field(7)?.bytes()?.is_empty() && field(8)?.bytes()?.is_empty())
+            }
+            (_, len) if len == unsigned_len + 3 => true,
+            (_, len) => {
+                return Err(invalid_transaction(format!(
+                    "{} transaction has {} fields",
+                    kind.as_str(),
+                    len
+                )))
+            }
+        };
+
+        let chain_id = match kind {
+            TxKind::Legacy if fields.len() == 9 => {
+                let v = field(6)?.uint()?;
+                match (signed, v) {
+                    (false, v) => Some(v),
+                    (true, v) if v >= 35 => Some((v - 35) / 2),
+                    (true, _) => None,
+                }
+            }
+            TxKind::Legacy => None,
+            _ => Some(field(0)?.uint()?),
+        };
+
+        let mut tx = Transaction {
+            kind,
+            chain_id,
+            nonce: field(nonce)?.uint()?,
+            gas_price: Vec::new(),
+            max_priority_fee_per_gas: Vec::new(),
+            max_fee_per_gas: Vec::new(),
+            gas_limit: field(gas)?.uint()?,
+            to: match field(to)?.bytes()? {
+                [] => None,
+                address => Some(
+                    <[u8; 20]>::try_from(address)
+                        .map_err(|_| invalid_transaction("to is not 20 bytes".to_string()))?,
+                ),
+            },
+            value: field(to + 1)?.uint_bytes(32)?.to_vec(),
+            data: field(to + 2)?.bytes()?.to_vec(),
+            access_list: Vec::new(),
+            signed,
+        };
+        match kind {
+            TxKind::Legacy => tx.gas_price = field(1)?.uint_bytes(32)?.to_vec(),
+            TxKind::Eip2930 => tx.gas_price = field(2)?.uint_bytes(32)?.to_vec(),
+            TxKind::Eip1559 => {
+                tx.max_priority_fee_per_gas = field(2)?.uint_bytes(32)?.to_vec();
+                tx.max_fee_per_gas = field(3)?.uint_bytes(32)?.to_vec();
+            }
+        }
+        if kind != TxKind::Legacy {
+            tx.access_list = decode_access_list(field(to + 3)?)?;
+        }
+        Ok(tx)
+    }
+
+    /// Bytes the sender signs
+    pub fn signing_payload(&self) -> Vec<u8> {
+        let to = self.to.as_ref().map(|to| to.as_slice()).unwrap_or_default();
+        let common = [
+            rlp_uint(self.gas_limit),
+            rlp_bytes(to),
+            rlp_bytes(&self.value),
+            rlp_bytes(&self.data),
+        ];
+
+        match self.kind {
+            TxKind::Legacy => {
+                let mut fields = vec![rlp_uint(self.nonce), rlp_bytes(&self.gas_price)];
+                fields.extend(common);
+                // EIP-155 replay protection
+                if let Some(chain_id) = self.chain_id {
+                    fields.extend([rlp_uint(chain_id), rlp_bytes(&[]), rlp_bytes(&[])]);
+                }
+                rlp_list(&fields)
+            }
+            TxKind::Eip2930 | TxKind::Eip1559 => {
+                let mut fields = vec![
+                    rlp_uint(self.chain_id.unwrap_or_default()),
+                    rlp_uint(self.nonce),
+                ];
+                if self.kind == TxKind::Eip2930 {
+                    fields.push(rlp_bytes(&self.gas_price));
+                } else {
+                    fields.push(rlp_bytes(&self.max_priority_fee_per_gas));
+                    fields.push(rlp_bytes(&self.max_fee_per_gas));
+                }
+                fields.extend(common);
+                fields.push(self.access_list_rlp());
+
+                let mut payload = vec![if self.kind == TxKind::Eip2930 { 1 } else { 2 }];
+                payload.extend(rlp_list(&fields));
+                payload
+            }
+        }
+    }
+
+    /// keccak256 of the signing payload: the hash the sender signs
+    pub fn signing_hash(&self) -> [u8; 32] {
+        keccak256(&self.signing_payload())
+    }
+
+    fn access_list_rlp(&self) -> Vec<u8> {
+        let items: Vec<Vec<u8>> = self
+            .access_list
+            .iter()
+            .map(|item| {
+                let keys: Vec<Vec<u8>> = item.storage_keys.iter().map(|k| rlp_bytes(k)).collect();
+                rlp_list(&[rlp_bytes(&item.address), rlp_list(&keys)])
+            })
+            .collect();
+        rlp_list(&items)
+    }
+}
+
+fn invalid_transaction(message: String) -> Error {
+    Error::InvalidTransaction(message)
+}
+
+fn decode_access_list(list: &Rlp) -> Result<Vec<AccessListItem>> {
+    let Rlp::List(items) = list else {
+        return Err(invalid_transaction("access list is not a list".to_string()));
+    };
+    items
+        .iter()
+        .map(|item| {
+            let Rlp::List(parts) = item else {
+                return Err(invalid_transaction(
+                    "access list entry is not a list".to_string(),
+                ));
+            };
+            let [address, Rlp::List(keys)] = parts.as_slice() else {
+                return Err(invalid_transaction(
+                    "malformed access list entry".to_string(),
+                ));
+            };
+            Ok(AccessListItem {
+                address: address
+                    .bytes()?
+                    .try_into()
+                    .map_err(|_| invalid_transaction("access list address".to_string()))?,
+                storage_keys: keys
+                    .iter()
+                    .map(|key| {
+                        key.bytes()?
+                            .try_into()
+                            .map_err(|_| invalid_transaction("access list key".to_string()))
+                    })
+                    .collect::<Result<_>>()?,
+            })
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The EIP-155 example: 1 ETH to 0x3535…35 at nonce 9 on mainnet
+    const EIP155_UNSIGNED: &str = "ec098504a817c800825208943535353535353535353535353535353535353535880de0b6b3a764000080018080";
+    const EIP155_SIGNING_HASH: &str =
+        "daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53";
+
+    #[test]
+    fn test_rlp_round_trip() {
+        let long = vec![0xaa; 60];
+        let encoded = rlp_list(&[rlp_bytes(b"dog"), rlp_bytes(&[0x7f]), rlp_bytes(&long)]);
+        let items = Rlp::decode(&encoded).unwrap().into_list().unwrap();
+        assert_eq!(items[0].bytes().unwrap(), b"dog");
+        assert_eq!(items[1].bytes().unwrap(), [0x7f]);
+        assert_eq!(items[2].bytes().unwrap(), long.as_slice());
+        assert_eq!(rlp_uint(0), [0x80]);
+        assert_eq!(rlp_uint(1024), [0x82, 0x04, 0x00]);
+
+        // Non-canonical encodings are refused
+        assert!(Rlp::decode(&[0x81, 0x01]).is_err());
+        assert!(Rlp::decode(&[0xb8, 0x01, 0xaa]).is_err());
+        assert!(Rlp::decode(&[0x82, 0x00, 0x01]).unwrap().uint().is_err());
+        assert!(Rlp::decode(&[0x83, b'd', b'o']).is_err());
+    }
+
+    #[test]
+    fn test_eip155_signing_hash() {
+        let bytes = hex::decode(EIP155_UNSIGNED).unwrap();
+        let tx = Transaction::decode(&bytes).unwrap();
+        assert_eq!(tx.kind, TxKind::Legacy);
+        assert_eq!(tx.chain_id, Some(1));
+        assert_eq!(tx.nonce, 9);
+        assert!(!tx.signed);
+        assert_eq!(tx.signing_payload(), bytes);
+        assert_eq!(hex::encode(tx.signing_hash()), EIP155_SIGNING_HASH);
+    }
+
+    #[test]
+    fn test_typed_transaction_round_trip() {
+        let tx = Transaction {
+            kind: TxKind::Eip1559,
+            chain_id: Some(10),
+            nonce: 3,
+            gas_price: Vec::new(),
+            max_priority_fee_per_gas: vec![0x3b, 0x9a, 0xca, 0x00],
+            max_fee_per_gas: vec![0x04, 0xa8, 0x17, 0xc8, 0x00],
+            gas_limit: 60_000,
+            to: Some([0x11; 20]),
+            value: Vec::new(),
+            data: vec![0xa9, 0x05, 0x9c, 0xbb],
+            access_list: vec![AccessListItem {
+                address: [0x22; 20],
+                storage_keys: vec![[0x33; 32]],
+            }],
+            signed: false,
+        };
+        let payload = tx.signing_payload();
+        assert_eq!(payload[0], 0x02);
+        assert_eq!(Transaction::decode(&payload).unwrap(), tx);
+
+        // A pre-EIP-155 legacy transaction has no chain ID
+        let legacy = Transaction {
+            kind: TxKind::Legacy,
+            chain_id: None,
+            gas_price: vec![0x01],
+            max_priority_fee_per_gas: Vec::new(),
+            max_fee_per_gas: Vec::new(),
+            access_list: Vec::new(),
+            ..tx
+        };
+        let decoded = Transaction::decode(&legacy.signing_payload()).unwrap();
+        assert_eq!(decoded.chain_id, None);
+        assert_eq!(decoded, legacy);
+
+        assert!(matches!(
+            Transaction::decode(&[0x03, 0xc0]),
+            Err(Error::UnsupportedTransactionType(3))
+        ));
+    }
+}
@@ -7,10 +7,12 @@
 
 pub mod accumulator;
 pub mod agent;
+pub mod audit;
 pub mod child;
 pub mod crypto;
 pub mod disk;
 pub mod error;
+pub mod evm;
 pub mod expiry;
 pub mod presig;
 pub mod types;
@@ -21,14 +23,17 @@ pub use accumulator::{
     StoredAccumulator, RSA_MODULUS_SIZE,
 };
 pub use agent::{AgentId, AgentMetadata, AgentRegistryEntry, AgentStatus};
+pub use audit::{AuditBundle, AuditRecord};
 pub use child::{ChildStatus, NullificationReason};
 pub use crypto::{ChildKeyPair, DerivationPath, PublicKey};
-pub use disk::{DiskFormat, DiskHeader, DISK_MAGIC, PRESIG_TABLE_OFFSET, USAGE_LOG_OFFSET};
+pub use disk::{
+    DiskFormat, DiskHeader, DiskRegion, DISK_MAGIC, PRESIG_TABLE_OFFSET, USAGE_LOG_OFFSET,
+};
 pub use error::{Error, Result};
 pub use expiry::DiskExpiry;
 pub use presig::{PresigColdShare, PresigStatus, PresigTableEntry};
 pub use types::{ChildId, MessageHash, Signature, TxHash, ZkProofHash};
-pub use usage::UsageLogEntry;
+pub use usage::{TxHashStatus, UsageFilter, UsageLog, UsageLogEntry};
 
 /// Disk format version
 pub const VERSION: u32 = 1;
@@ -139,6 +139,87 @@ impl UsageLogEntry {
             description,
         })
     }
+    /// Whether the transaction hash is still the placeholder written at signing
+    pub fn tx_hash_pending(&self) -> bool {
+        self.tx_hash.as_bytes() == &[0u8; 32]
+    }
+}
+
+/// Whether an entry's transaction hash has been recorded
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TxHashStatus {
+    /// Signed, but no transaction hash recorded yet
+    Pending,
+    /// Transaction hash recorded after broadcast
+    Filled,
+}
+
+impl TxHashStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TxHashStatus::Pending => "pending",
+            TxHashStatus::Filled => "filled",
+        }
+    }
+}
+
+impl std::str::FromStr for TxHashStatus {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pending" => Ok(TxHashStatus::Pending),
+            "filled" => Ok(TxHashStatus::Filled),
+            other => Err(format!(
+                "unknown tx hash status '{}' (expected pending or filled)",
+                other
+            )),
+        }
+    }
+}
+
+/// Criteria for selecting usage log entries; unset fields match every entry
+#[derive(Debug, Clone, Default)]
+pub struct UsageFilter {
+    /// Signed at or after this Unix time
+    pub since: Option<u64>,
+
+    /// Signed before this Unix time
+    pub until: Option<u64>,
+
+    pub chain_id: Option<ChainId>,
+
+    pub tx_hash: Option<TxHashStatus>,
+
+    /// Text to find in the description, ignoring case
+    pub search: Option<String>,
+}
+
+impl UsageFilter {
+    pub fn matches(&self, entry: &UsageLogEntry) -> bool {
+        if self.since.is_some_and(|since| entry.timestamp < since)
+            || self.until.is_some_and(|until| entry.timestamp >= until)
+            || self.chain_id.is_some_and(|chain| entry.chain_id != chain)
+        {
+            return false;
+        }
+
+        let pending = entry.tx_hash_pending();
+        match self.tx_hash {
+            Some(TxHashStatus::Pending) if !pending => return false,
+            Some(TxHashStatus::Filled) if pending => return false,
+            _ => {}
+        }
+
+        match &self.search {
+            Some(search) => entry
+                .description
+                .to_lowercase()
+                .contains(&search.to_lowercase()),
+            None => true,
+        }
+    }
 }
 
 /// Collection of usage log entries for a disk
@@ -189,6 +270,17 @@ impl UsageLog {
         self.entries.iter().find(|e| e.presig_index == index)
     }
 
+    /// Entries matching a filter, newest first
+    pub fn query<'a>(
+        &'a self,
+        filter: &'a UsageFilter,
+    ) -> impl Iterator<Item = &'a UsageLogEntry> + 'a {
+        self.entries
+            .iter()
+            .rev()
+            .filter(|entry| filter.matches(entry))
+    }
+
     /// Validate log integrity
     pub fn validate(&self) -> Result<(), crate::error::Error> {
         let mut last_index: Option<u32> = None;
@@ -324,4 +416,60 @@ mod tests {
 
         assert!(log.validate().is_ok());
     }
+
+    #[test]
+    fn test_usage_log_query() {
+        let mut log = UsageLog::new();
+        for (index, (timestamp, chain, tx, description)) in [
+            (1000, ChainId::ETHEREUM, [0u8; 32], "Pay invoice 7"),
+            (2000, ChainId::new(137), [9u8; 32], "Swap on Polygon"),
+            (3000, ChainId::ETHEREUM, [9u8; 32], "Pay INVOICE 8"),
+        ]
+        .into_iter()
+        .enumerate()
+        {
+            log.push(UsageLogEntry::new(
+                index as u32,
+                timestamp,
+                MessageHash::new([0u8; 32]),
+                Signature::new([0u8; 64]),
+                chain,
+                TxHash::new(tx),
+                ZkProofHash::new([0u8; 32]),
+                description.to_string(),
+            ))
+            .unwrap();
+        }
+
+        let indices = |filter: UsageFilter| -> Vec<u32> {
+            log.query(&filter).map(|e| e.presig_index).collect()
+        };
+
+        assert_eq!(indices(UsageFilter::default()), [2, 1, 0]);
+        assert_eq!(
+            indices(UsageFilter {
+                since: Some(2000),
+                until: Some(3000),
+                ..Default::default()
+            }),
+            [1]
+        );
+        assert_eq!(
+            indices(UsageFilter {
+                chain_id: Some(ChainId::ETHEREUM),
+                tx_hash: Some(TxHashStatus::Filled),
+                ..Default::default()
+            }),
+            [2]
+        );
+        assert_eq!(
+            indices(UsageFilter {
+                search: Some("invoice".to_string()),
+                ..Default::default()
+            }),
+            [2, 0]
+        );
+        assert_eq!("pending".parse(), Ok(TxHashStatus::Pending));
+        assert!("done".parse::<TxHashStatus>().is_err());
+    }
 }
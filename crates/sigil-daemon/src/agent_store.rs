@@ -21,6 +21,15 @@ pub struct AgentStore {
 
     /// Agent master shard (32 bytes) - agent's portion of the master key
     agent_master_shard: Option<[u8; 32]>,
+
+    /// Latest clock reading accepted for signing (unix seconds, 0 if never)
+    clock_high_water: u64,
+}
+
+/// Persisted clock high-water mark
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ClockRecord {
+    high_water: u64,
 }
 
 /// Data stored for each child
@@ -48,11 +57,18 @@ impl AgentStore {
             store_path,
             cache: HashMap::new(),
             agent_master_shard: None,
+            clock_high_water: 0,
         };
 
         // Try to load agent master shard if it exists
         store.load_agent_master_shard_from_disk()?;
 
+        let clock_path = store.clock_path();
+        if clock_path.exists() {
+            let record: ClockRecord = serde_json::from_str(&std::fs::read_to_string(clock_path)?)?;
+            store.clock_high_water = record.high_water;
+        }
+
         Ok(store)
     }
 
@@ -104,11 +120,18 @@ impl AgentStore {
 
     /// Mark a presig as used
     pub fn mark_presig_used(&mut self, child_id: &ChildId, index: u32) -> Result<()> {
+        self.mark_presigs_used(child_id, &[index])
+    }
+
+    /// Mark several presigs as used with a single store write
+    pub fn mark_presigs_used(&mut self, child_id: &ChildId, indices: &[u32]) -> Result<()> {
         // Update in cache
         {
             let data = self.get_child_mut(child_id)?;
-            if index >= data.next_presig_index {
-                data.next_presig_index = index + 1;
+            if let Some(&max) = indices.iter().max() {
+                if max >= data.next_presig_index {
+                    data.next_presig_index = max + 1;
+                }
             }
         }
         // Save to disk (borrow released)
@@ -210,6 +233,34 @@ impl AgentStore {
         })
     }
 
+    /// Latest clock reading accepted for signing (unix seconds, 0 if never)
+    pub fn clock_high_water(&self) -> u64 {
+        self.clock_high_water
+    }
+
+    /// Raise the persisted clock high-water mark; lower readings are ignored
+    pub fn raise_clock_high_water(&mut self, timestamp: u64) -> Result<()> {
+        if timestamp <= self.clock_high_water {
+            return Ok(());
+        }
+
+        let path = self.clock_path();
+        let content = serde_json::to_string(&ClockRecord {
+            high_water: timestamp,
+        })?;
+        let temp_path = path.with_extension("json.tmp");
+        std::fs::write(&temp_path, content)?;
+        std::fs::rename(&temp_path, &path)?;
+
+        self.clock_high_water = timestamp;
+        Ok(())
+    }
+
+    /// Get path for the clock high-water mark file
+    fn clock_path(&self) -> PathBuf {
+        self.store_path.join("clock.json")
+    }
+
     /// Get path for agent master shard file
     fn agent_master_shard_path(&self) -> PathBuf {
         self.store_path.join("agent_master_shard.bin")
@@ -297,4 +348,19 @@ mod tests {
         assert_eq!(loaded.child_id, child_id);
         assert_eq!(loaded.presig_shares.len(), 1);
     }
+
+    #[test]
+    fn test_clock_high_water_persists_and_never_lowers() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut store = AgentStore::new(temp_dir.path().to_path_buf()).unwrap();
+        assert_eq!(store.clock_high_water(), 0);
+
+        store.raise_clock_high_water(1_700_000_000).unwrap();
+        store.raise_clock_high_water(1_600_000_000).unwrap();
+        assert_eq!(store.clock_high_water(), 1_700_000_000);
+
+        let reopened = AgentStore::new(temp_dir.path().to_path_buf()).unwrap();
+        assert_eq!(reopened.clock_high_water(), 1_700_000_000);
+        assert!(reopened.list_children().unwrap().is_empty());
+    }
 }
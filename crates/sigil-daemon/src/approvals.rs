@@ -0,0 +1,761 @@
+//! Human approval queue for signing requests
+//!
+//! Under an [`ApprovalConfig`] policy, signing requests are held here instead
+//! of being signed straight away. A person approves or rejects each one
+//! (through sigil-cli or the bridge app); approved requests are signed and
+//! their results kept so the requester can collect them.
+//! Requests nobody decides on expire after the configured timeout.
+//!
+//! The queue is written next to the agent store so pending requests survive
+//! a restart, and every state change is appended to `audit/approvals.jsonl`
+//! with who made it.
+
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::io::Write;
+use std::path::PathBuf;
+use tokio::sync::broadcast;
+use tracing::warn;
+
+use sigil_core::types::{ChainId, MessageHash, Signature, ZkProofHash};
+
+use crate::config::ApprovalConfig;
+use crate::error::{DaemonError, Result};
+use crate::signer::{SigningRequest, SigningResult};
+
+/// Default time a request waits for a decision
+pub const DEFAULT_APPROVAL_TIMEOUT_SECS: u64 = 10 * 60;
+
+/// How long decided approvals are kept for lookups
+pub const DECIDED_RETENTION_SECS: u64 = 7 * 24 * 60 * 60;
+
+/// Queue file name under the agent store
+const QUEUE_FILE: &str = "approvals.json";
+
+/// Trail file name under the audit directory
+const TRAIL_FILE: &str = "approvals.jsonl";
+
+/// Where an approval is in its lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalStatus {
+    /// Waiting for a decision
+    Pending,
+    /// Approved but not signed yet (e.g. no disk was inserted)
+    Approved,
+    /// Approved and being signed
+    Signing,
+    /// Approved and signed
+    Signed,
+    /// Rejected
+    Rejected,
+    /// Expired before it was decided or signed
+    Expired,
+}
+
+impl ApprovalStatus {
+    /// Lowercase name used on the wire
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Pending => "pending",
+            Self::Approved => "approved",
+            Self::Signing => "signing",
+            Self::Signed => "signed",
+            Self::Rejected => "rejected",
+            Self::Expired => "expired",
+        }
+    }
+}
+
+/// One signature requested in an approval
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalItem {
+    pub message_hash: MessageHash,
+    pub chain_id: ChainId,
+    pub description: String,
+    /// Value the requester declared, in the chain's smallest unit
+    pub value: Option<u128>,
+    /// Value read from the transaction the requester sent, when it only
+    /// moves the chain's native currency; the only value thresholds trust
+    #[serde(default)]
+    pub verified_value: Option<u128>,
+    pub idempotency_key: Option<String>,
+    pub child_id: Option<String>,
+}
+
+impl ApprovalItem {
+    /// Describe a signing request, its declared value and its verified value
+    pub fn new(
+        request: &SigningRequest,
+        value: Option<u128>,
+        verified_value: Option<u128>,
+    ) -> Self {
+        Self {
+            message_hash: request.message_hash,
+            chain_id: request.chain_id,
+            description: request.description.clone(),
+            value,
+            verified_value,
+            idempotency_key: request.idempotency_key.clone(),
+            child_id: request.child_id.clone(),
+        }
+    }
+
+    /// The signing request to run once approved
+    pub fn signing_request(&self) -> SigningRequest {
+        SigningRequest {
+            message_hash: self.message_hash,
+            chain_id: self.chain_id,
+            description: self.description.clone(),
+            idempotency_key: self.idempotency_key.clone(),
+            child_id: self.child_id.clone(),
+        }
+    }
+
+    /// Whether `other` asks for the same signature
+    fn same_request(&self, other: &ApprovalItem) -> bool {
+        self.message_hash == other.message_hash
+            && self.chain_id == other.chain_id
+            && self.child_id == other.child_id
+            && self.idempotency_key == other.idempotency_key
+    }
+}
+
+/// A person's decision on an approval
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalDecision {
+    pub approved: bool,
+    /// Authenticated client (or uid) that submitted the decision
+    pub decided_by: String,
+    /// Person the client named as the approver
+    pub approver: String,
+    pub reason: Option<String>,
+    pub decided_at: u64,
+}
+
+/// A signature produced for an approved request
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovedSignature {
+    pub message_hash: MessageHash,
+    /// Signature bytes, hex encoded
+    pub signature: String,
+    pub presig_index: u32,
+    pub proof_hash: ZkProofHash,
+}
+
+impl ApprovedSignature {
+    fn to_result(&self) -> Result<SigningResult> {
+        let mut signature = [0u8; 64];
+        hex::decode_to_slice(&self.signature, &mut signature)
+            .map_err(|e| DaemonError::Store(format!("Corrupt approval result: {}", e)))?;
+
+        Ok(SigningResult {
+            signature: Signature::new(signature),
+            presig_index: self.presig_index,
+            proof_hash: self.proof_hash,
+            message_hash: self.message_hash,
+            cached: true,
+        })
+    }
+}
+
+/// A signing request held for a human decision
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Approval {
+    pub id: String,
+    pub items: Vec<ApprovalItem>,
+    /// Authenticated client (or uid) that submitted the request
+    pub requested_by: String,
+    pub created_at: u64,
+    pub expires_at: u64,
+    pub status: ApprovalStatus,
+    pub decision: Option<ApprovalDecision>,
+    /// Signatures, once signed
+    pub results: Vec<ApprovedSignature>,
+    /// Why the last attempt to sign an approved request failed
+    pub error: Option<String>,
+}
+
+impl Approval {
+    /// The signing requests to run once approved
+    pub fn signing_requests(&self) -> Vec<SigningRequest> {
+        self.items
+            .iter()
+            .map(ApprovalItem::signing_request)
+            .collect()
+    }
+
+    /// The stored signatures of a signed approval
+    pub fn signing_results(&self) -> Result<Vec<SigningResult>> {
+        self.results
+            .iter()
+            .map(ApprovedSignature::to_result)
+            .collect()
+    }
+
+    /// Whether this approval is for exactly these items from this client
+    fn matches(&self, requested_by: &str, items: &[ApprovalItem]) -> bool {
+        self.requested_by == requested_by
+            && self.items.len() == items.len()
+            && self.items.iter().zip(items).all(|(a, b)| a.same_request(b))
+    }
+
+    /// When this approval stops being useful to keep
+    fn settled_at(&self) -> u64 {
+        self.decision
+            .as_ref()
+            .map_or(self.expires_at, |decision| decision.decided_at)
+            .max(self.expires_at)
+    }
+}
+
+/// What happened to an approval, as recorded in the trail
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalAction {
+    Requested,
+    Approved,
+    Rejected,
+    Signed,
+    SigningFailed,
+    Expired,
+}
+
+/// One line of the approval trail
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ApprovalRecord {
+    pub timestamp: u64,
+    pub action: ApprovalAction,
+    /// The approval after the change
+    pub approval: Approval,
+}
+
+/// Persistent queue of signing requests awaiting approval
+pub struct ApprovalQueue {
+    /// Path of the queue file
+    path: PathBuf,
+
+    /// Path of the append-only trail
+    trail_path: PathBuf,
+
+    /// Which requests need approval
+    config: ApprovalConfig,
+
+    /// Approvals, oldest first
+    approvals: Vec<Approval>,
+
+    /// Broadcast of every approval after it changes
+    event_tx: broadcast::Sender<Approval>,
+}
+
+impl ApprovalQueue {
+    /// Open (or create) the queue under the agent store directory
+    ///
+    /// Approvals left signing by a daemon that stopped mid-signature are
+    /// approved again, so the next matching request signs them.
+    pub fn new(store_path: PathBuf, config: ApprovalConfig) -> Result<Self> {
+        let audit_dir = store_path.join("audit");
+        std::fs::create_dir_all(&audit_dir)?;
+        let path = store_path.join(QUEUE_FILE);
+
+        let mut approvals: Vec<Approval> = if path.exists() {
+            serde_json::from_slice(&std::fs::read(&path)?)?
+        } else {
+            Vec::new()
+        };
+        for approval in &mut approvals {
+            if approval.status == ApprovalStatus::Signing {
+                approval.status = ApprovalStatus::Approved;
+            }
+        }
+
+        Ok(Self {
+            path,
+            trail_path: audit_dir.join(TRAIL_FILE),
+            config,
+            approvals,
+            event_tx: broadcast::channel(16).0,
+        })
+    }
+
+    /// Replace the approval policy (e.g. after a config reload)
+    ///
+    /// Requests already queued keep waiting for a decision.
+    pub fn set_config(&mut self, config: ApprovalConfig) {
+        self.config = config;
+    }
+
+    /// Subscribe to approvals as they are requested and resolved
+    pub fn subscribe(&self) -> broadcast::Receiver<Approval> {
+        self.event_tx.subscribe()
+    }
+
+    /// Whether any of these items needs approval under the current policy
+    ///
+    /// Declared values are not trusted: an item without a verified value is
+    /// held under a threshold policy.
+    pub fn requires_approval(&self, items: &[ApprovalItem]) -> bool {
+        items.iter().any(|item| {
+            self.config
+                .requires_approval(item.chain_id.as_u32(), item.verified_value)
+        })
+    }
+
+    /// Find a live approval for the same request from the same client
+    ///
+    /// Expired approvals never match, so repeating an expired request queues
+    /// it again. A rejection matches until its original expiry, after which
+    /// the request may be asked for again.
+    pub fn find(
+        &mut self,
+        requested_by: &str,
+        items: &[ApprovalItem],
+        now: u64,
+    ) -> Result<Option<Approval>> {
+        self.expire(now)?;
+
+        Ok(self
+            .approvals
+            .iter()
+            .rev()
+            .filter(|approval| approval.matches(requested_by, items))
+            .find(|approval| match approval.status {
+                ApprovalStatus::Expired => false,
+                ApprovalStatus::Rejected => now < approval.expires_at,
+                _ => true,
+            })
+            .cloned())
+    }
+
+    /// Queue a request for approval
+    pub fn submit(
+        &mut self,
+        requested_by: &str,
+        items: Vec<ApprovalItem>,
+        now: u64,
+    ) -> Result<Approval> {
+        let mut id = [0u8; 8];
+        rand::thread_rng().fill_bytes(&mut id);
+
+        let approval = Approval {
+            id: hex::encode(id),
+            items,
+            requested_by: requested_by.to_string(),
+            created_at: now,
+            expires_at: now + self.config.timeout_secs,
+            status: ApprovalStatus::Pending,
+            decision: None,
+            results: Vec::new(),
+            error: None,
+        };
+        self.approvals.push(approval.clone());
+        self.save()?;
+        self.record(ApprovalAction::Requested, &approval, now);
+
+        Ok(approval)
+    }
+
+    /// Get an approval by ID
+    pub fn get(&mut self, id: &str, now: u64) -> Result<Approval> {
+        self.expire(now)?;
+        self.position(id).map(|index| self.approvals[index].clone())
+    }
+
+    /// List approvals, newest first
+    pub fn list(&mut self, include_decided: bool, now: u64) -> Result<Vec<Approval>> {
+        self.expire(now)?;
+        Ok(self
+            .approvals
+            .iter()
+            .rev()
+            .filter(|approval| include_decided || approval.status == ApprovalStatus::Pending)
+            .cloned()
+            .collect())
+    }
+
+    /// Approve or reject a pending request
+    ///
+    /// The client that submitted the request can't decide it.
+    pub fn decide(&mut self, id: &str, decision: ApprovalDecision, now: u64) -> Result<Approval> {
+        self.expire(now)?;
+        let index = self.position(id)?;
+        let approval = &mut self.approvals[index];
+
+        if approval.status != ApprovalStatus::Pending {
+            return Err(DaemonError::ApprovalClosed(id.to_string()));
+        }
+        if approval.requested_by == decision.decided_by {
+            return Err(DaemonError::SelfApproval);
+        }
+
+        let action = if decision.approved {
+            approval.status = ApprovalStatus::Approved;
+            ApprovalAction::Approved
+        } else {
+            approval.status = ApprovalStatus::Rejected;
+            ApprovalAction::Rejected
+        };
+        approval.decision = Some(decision);
+        let approval = approval.clone();
+
+        self.save()?;
+        self.record(action, &approval, now);
+        Ok(approval)
+    }
+
+    /// Claim an approved request for signing
+    ///
+    /// While it is signing, repeated requests see it as pending rather than
+    /// signing it again, so the queue can be unlocked during the signature.
+    pub fn begin_signing(&mut self, id: &str, now: u64) -> Result<Approval> {
+        self.expire(now)?;
+        let index = self.position(id)?;
+        let approval = &mut self.approvals[index];
+        if approval.status != ApprovalStatus::Approved {
+            return Err(DaemonError::ApprovalClosed(id.to_string()));
+        }
+        approval.status = ApprovalStatus::Signing;
+        let approval = approval.clone();
+
+        self.save()?;
+        Ok(approval)
+    }
+
+    /// Return a request whose signing was cancelled to the approved state
+    pub fn cancel_signing(&mut self, id: &str) -> Result<()> {
+        let index = self.position(id)?;
+        self.approvals[index].status = ApprovalStatus::Approved;
+        self.save()
+    }
+
+    /// Store the signatures of an approved request
+    pub fn record_signed(
+        &mut self,
+        id: &str,
+        results: &[SigningResult],
+        now: u64,
+    ) -> Result<Approval> {
+        let index = self.position(id)?;
+        let approval = &mut self.approvals[index];
+        approval.status = ApprovalStatus::Signed;
+        approval.error = None;
+        approval.results = results
+            .iter()
+            .map(|result| ApprovedSignature {
+                message_hash: result.message_hash,
+                signature: hex::encode(result.signature.as_bytes()),
+                presig_index: result.presig_index,
+                proof_hash: result.proof_hash,
+            })
+            .collect();
+        let approval = approval.clone();
+
+        self.save()?;
+        self.record(ApprovalAction::Signed, &approval, now);
+        Ok(approval)
+    }
+
+    /// Note that signing an approved request failed; it stays approved
+    pub fn record_failure(&mut self, id: &str, error: String, now: u64) -> Result<()> {
+        let index = self.position(id)?;
+        self.approvals[index].status = ApprovalStatus::Approved;
+        self.approvals[index].error = Some(error);
+        let approval = self.approvals[index].clone();
+
+        self.save()?;
+        self.record(ApprovalAction::SigningFailed, &approval, now);
+        Ok(())
+    }
+
+    /// Read the whole approval trail, oldest first
+    pub fn trail(&self) -> Result<Vec<ApprovalRecord>> {
+        if !self.trail_path.exists() {
+            return Ok(Vec::new());
+        }
+
+        std::fs::read_to_string(&self.trail_path)?
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).map_err(DaemonError::from))
+            .collect()
+    }
+
+    fn position(&self, id: &str) -> Result<usize> {
+        self.approvals
+            .iter()
+            .position(|approval| approval.id == id)
+            .ok_or_else(|| DaemonError::ApprovalNotFound(id.to_string()))
+    }
+
+    /// Expire undecided and unsigned approvals past their deadline and drop
+    /// settled ones past retention
+    fn expire(&mut self, now: u64) -> Result<()> {
+        let before = self.approvals.len();
+        self.approvals
+            .retain(|approval| now.saturating_sub(approval.settled_at()) < DECIDED_RETENTION_SECS);
+        let mut changed = self.approvals.len() != before;
+
+        let mut expired = Vec::new();
+        for approval in &mut self.approvals {
+            let open = matches!(
+                approval.status,
+                ApprovalStatus::Pending | ApprovalStatus::Approved
+            );
+            if open && now >= approval.expires_at {
+                approval.status = ApprovalStatus::Expired;
+                expired.push(approval.clone());
+            }
+        }
+        changed |= !expired.is_empty();
+
+        if changed {
+            self.save()?;
+        }
+        for approval in &expired {
+            self.record(ApprovalAction::Expired, approval, now);
+        }
+        Ok(())
+    }
+
+    /// Append to the trail and notify subscribers
+    ///
+    /// The queue file is already updated, so a trail write failure is logged
+    /// rather than undoing the change.
+    fn record(&self, action: ApprovalAction, approval: &Approval, now: u64) {
+        let record = ApprovalRecord {
+            timestamp: now,
+            action,
+            approval: approval.clone(),
+        };
+        if let Err(e) = self.append_trail(&record) {
+            warn!("Failed to record approval {}: {}", approval.id, e);
+        }
+        let _ = self.event_tx.send(approval.clone());
+    }
+
+    fn append_trail(&self, record: &ApprovalRecord) -> Result<()> {
+        let mut line = serde_json::to_string(record)?;
+        line.push('\n');
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.trail_path)?;
+        file.write_all(line.as_bytes())?;
+        file.sync_all()?;
+        Ok(())
+    }
+
+    /// Write the queue to disk atomically
+    fn save(&self) -> Result<()> {
+        let temp_path = self.path.with_extension("json.tmp");
+        std::fs::write(&temp_path, serde_json::to_vec(&self.approvals)?)?;
+        std::fs::rename(&temp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::ApprovalMode;
+    use tempfile::TempDir;
+
+    fn item(message: u8, chain_id: u32, value: Option<u128>) -> ApprovalItem {
+        ApprovalItem {
+            message_hash: MessageHash::new([message; 32]),
+            chain_id: ChainId::new(chain_id),
+            description: format!("transfer {}", message),
+            value,
+            verified_value: value,
+            idempotency_key: None,
+            child_id: None,
+        }
+    }
+
+    fn config(mode: ApprovalMode) -> ApprovalConfig {
+        ApprovalConfig {
+            mode,
+            value_thresholds: [(1, 1_000)].into_iter().collect(),
+            default_value_threshold: None,
+            timeout_secs: 600,
+        }
+    }
+
+    fn decision(approved: bool, decided_by: &str, now: u64) -> ApprovalDecision {
+        ApprovalDecision {
+            approved,
+            decided_by: decided_by.to_string(),
+            approver: "alice".to_string(),
+            reason: None,
+            decided_at: now,
+        }
+    }
+
+    #[test]
+    fn test_threshold_policy() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut queue =
+            ApprovalQueue::new(temp_dir.path().to_path_buf(), config(ApprovalMode::Off)).unwrap();
+        assert!(!queue.requires_approval(&[item(1, 1, None)]));
+
+        queue.set_config(config(ApprovalMode::Threshold));
+        assert!(!queue.requires_approval(&[item(1, 1, Some(1_000))]));
+        assert!(queue.requires_approval(&[item(1, 1, Some(1_001))]));
+        // Unverified values and chains without a threshold are held
+        assert!(queue.requires_approval(&[item(1, 1, None)]));
+        let mut declared = item(1, 1, Some(5));
+        declared.verified_value = None;
+        assert!(queue.requires_approval(&[declared]));
+        assert!(queue.requires_approval(&[item(1, 137, Some(1))]));
+        // One item over the threshold holds the whole batch
+        assert!(queue.requires_approval(&[item(1, 1, Some(5)), item(2, 1, Some(5_000))]));
+
+        queue.set_config(config(ApprovalMode::Always));
+        assert!(queue.requires_approval(&[item(1, 1, Some(0))]));
+    }
+
+    #[test]
+    fn test_approve_and_sign_survives_reopen() {
+        let temp_dir = TempDir::new().unwrap();
+        let now = 1_700_000_000;
+        let items = vec![item(7, 1, Some(5_000))];
+
+        let id = {
+            let mut queue =
+                ApprovalQueue::new(temp_dir.path().to_path_buf(), config(ApprovalMode::Always))
+                    .unwrap();
+            let mut events = queue.subscribe();
+            let approval = queue.submit("agent", items.clone(), now).unwrap();
+            assert_eq!(approval.expires_at, now + 600);
+            assert_eq!(events.try_recv().unwrap().status, ApprovalStatus::Pending);
+            approval.id
+        };
+
+        let mut queue =
+            ApprovalQueue::new(temp_dir.path().to_path_buf(), config(ApprovalMode::Always))
+                .unwrap();
+        let found = queue.find("agent", &items, now + 10).unwrap().unwrap();
+        assert_eq!(found.id, id);
+        assert_eq!(found.status, ApprovalStatus::Pending);
+        // Another client's identical request is a separate approval
+        assert!(queue.find("other", &items, now + 10).unwrap().is_none());
+
+        assert!(matches!(
+            queue.decide(&id, decision(true, "agent", now + 20), now + 20),
+            Err(DaemonError::SelfApproval)
+        ));
+        let approved = queue
+            .decide(&id, decision(true, "phone", now + 20), now + 20)
+            .unwrap();
+        assert_eq!(approved.status, ApprovalStatus::Approved);
+        assert!(matches!(
+            queue.decide(&id, decision(false, "phone", now + 30), now + 30),
+            Err(DaemonError::ApprovalClosed(_))
+        ));
+
+        // Once claimed for signing it can't be claimed again
+        assert_eq!(
+            queue.begin_signing(&id, now + 35).unwrap().status,
+            ApprovalStatus::Signing
+        );
+        assert!(matches!(
+            queue.begin_signing(&id, now + 35),
+            Err(DaemonError::ApprovalClosed(_))
+        ));
+        queue.cancel_signing(&id).unwrap();
+        queue.begin_signing(&id, now + 36).unwrap();
+        // A restart mid-signature leaves it approved
+        let mut queue =
+            ApprovalQueue::new(temp_dir.path().to_path_buf(), config(ApprovalMode::Always))
+                .unwrap();
+        assert_eq!(
+            queue.get(&id, now + 37).unwrap().status,
+            ApprovalStatus::Approved
+        );
+        queue.begin_signing(&id, now + 38).unwrap();
+
+        let result = SigningResult {
+            signature: Signature::new([9u8; 64]),
+            presig_index: 4,
+            proof_hash: ZkProofHash::new([0u8; 32]),
+            message_hash: MessageHash::new([7; 32]),
+            cached: false,
+        };
+        queue.record_signed(&id, &[result], now + 40).unwrap();
+
+        let queue_path = temp_dir.path().to_path_buf();
+        let mut queue = ApprovalQueue::new(queue_path, config(ApprovalMode::Always)).unwrap();
+        let signed = queue.find("agent", &items, now + 50).unwrap().unwrap();
+        assert_eq!(signed.status, ApprovalStatus::Signed);
+        let results = signed.signing_results().unwrap();
+        assert_eq!(results[0].presig_index, 4);
+        assert!(results[0].cached);
+
+        let actions: Vec<_> = queue
+            .trail()
+            .unwrap()
+            .into_iter()
+            .map(|record| record.action)
+            .collect();
+        assert_eq!(
+            actions,
+            vec![
+                ApprovalAction::Requested,
+                ApprovalAction::Approved,
+                ApprovalAction::Signed
+            ]
+        );
+    }
+
+    #[test]
+    fn test_expiry_and_rejection() {
+        let temp_dir = TempDir::new().unwrap();
+        let now = 1_700_000_000;
+        let mut queue =
+            ApprovalQueue::new(temp_dir.path().to_path_buf(), config(ApprovalMode::Always))
+                .unwrap();
+
+        let stale = queue.submit("agent", vec![item(1, 1, None)], now).unwrap();
+        assert_eq!(queue.list(false, now + 1).unwrap().len(), 1);
+        assert!(queue.list(false, now + 600).unwrap().is_empty());
+        assert_eq!(
+            queue.get(&stale.id, now + 600).unwrap().status,
+            ApprovalStatus::Expired
+        );
+        assert!(matches!(
+            queue.decide(&stale.id, decision(true, "phone", now + 601), now + 601),
+            Err(DaemonError::ApprovalClosed(_))
+        ));
+        // An expired request is queued afresh when asked for again
+        assert!(queue
+            .find("agent", &[item(1, 1, None)], now + 601)
+            .unwrap()
+            .is_none());
+
+        let rejected = queue.submit("agent", vec![item(2, 1, None)], now).unwrap();
+        queue
+            .decide(&rejected.id, decision(false, "phone", now + 5), now + 5)
+            .unwrap();
+        let found = queue
+            .find("agent", &[item(2, 1, None)], now + 10)
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.status, ApprovalStatus::Rejected);
+        assert_eq!(found.decision.unwrap().approver, "alice");
+        assert!(queue
+            .find("agent", &[item(2, 1, None)], now + 600)
+            .unwrap()
+            .is_none());
+
+        // Settled approvals are eventually dropped
+        assert!(queue
+            .list(true, now + 600 + DECIDED_RETENTION_SECS)
+            .unwrap()
+            .is_empty());
+        assert!(matches!(
+            queue.get(&stale.id, now + 600 + DECIDED_RETENTION_SECS),
+            Err(DaemonError::ApprovalNotFound(_))
+        ));
+    }
+}
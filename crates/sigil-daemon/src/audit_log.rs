@@ -0,0 +1,347 @@
+//! Agent-side audit log
+//!
+//! Append-only, hash-chained and signed record of every signature the daemon
+//! completes. The log lives alongside the agent store (one JSONL file per
+//! child) and is exported as an [`AuditBundle`] for the mother to diff against
+//! the disk's usage log during reconciliation.
+//!
+//! Refused IPC requests are kept in a separate unsigned `access_denied.jsonl`
+//! in the same directory; they never appear in exported bundles.
+
+use std::collections::HashMap;
+use std::io::{BufRead, BufReader, Seek, SeekFrom, Write};
+use std::path::PathBuf;
+
+use k256::ecdsa::SigningKey;
+use serde::{Deserialize, Serialize};
+use zeroize::Zeroize;
+
+use sigil_core::audit::{auditor_public_key, GENESIS_HASH};
+use sigil_core::{AuditBundle, AuditRecord, ChildId, PublicKey, UsageLogEntry};
+
+use crate::error::{DaemonError, Result};
+use crate::ipc::PeerCredentials;
+
+/// File name of the denied-request log
+const ACCESS_DENIED_LOG: &str = "access_denied.jsonl";
+
+/// A refused IPC request
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AccessDenial {
+    /// Unix time of the request
+    pub timestamp: u64,
+    /// RPC that was refused
+    pub method: String,
+    /// Peer credentials, if the transport provided them
+    pub peer: Option<PeerCredentials>,
+    /// Authenticated client name, if the token was valid
+    pub client: Option<String>,
+    /// Why the request was refused
+    pub reason: String,
+}
+
+/// Persistent audit log for all children handled by this agent
+pub struct AuditLog {
+    /// Directory holding the per-child log files
+    log_dir: PathBuf,
+
+    /// Key used to sign audit records
+    signing_key: SigningKey,
+
+    /// Cached chain heads (child_id -> (next sequence, last record hash))
+    heads: HashMap<ChildId, (u64, [u8; 32])>,
+}
+
+impl AuditLog {
+    /// Open (or create) the audit log under the agent store directory
+    pub fn new(store_path: PathBuf) -> Result<Self> {
+        let log_dir = store_path.join("audit");
+        std::fs::create_dir_all(&log_dir)?;
+
+        let signing_key = load_or_create_key(&store_path.join("audit_key.bin"))?;
+
+        Ok(Self {
+            log_dir,
+            signing_key,
+            heads: HashMap::new(),
+        })
+    }
+
+    /// Public key the mother uses to verify exported bundles
+    pub fn public_key(&self) -> PublicKey {
+        auditor_public_key(&self.signing_key)
+    }
+
+    /// Append a record for a completed signature and flush it to disk
+    pub fn append(
+        &mut self,
+        child_id: &ChildId,
+        r_point: [u8; 33],
+        entry: &UsageLogEntry,
+    ) -> Result<AuditRecord> {
+        let (sequence, prev_hash) = self.head(child_id)?;
+
+        let mut record =
+            AuditRecord::from_usage_entry(sequence, *child_id, r_point, entry, prev_hash);
+        record.sign(&self.signing_key);
+
+        let mut line = serde_json::to_string(&record)?;
+        line.push('\n');
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.log_path(child_id))?;
+        file.write_all(line.as_bytes())?;
+        file.sync_all()?;
+
+        self.heads
+            .insert(*child_id, (sequence + 1, record.record_hash()));
+
+        Ok(record)
+    }
+
+    /// Read every record for a child, in sequence order
+    pub fn records(&self, child_id: &ChildId) -> Result<Vec<AuditRecord>> {
+        let path = self.log_path(child_id);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).map_err(DaemonError::from))
+            .collect()
+    }
+
+    /// Read a child's records starting at byte `offset` of its log
+    ///
+    /// Each record comes with the offset just past it, so a reader can pick
+    /// up after the records it is done with.
+    pub fn records_from(&self, child_id: &ChildId, offset: u64) -> Result<Vec<(AuditRecord, u64)>> {
+        let path = self.log_path(child_id);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let mut file = std::fs::File::open(&path)?;
+        file.seek(SeekFrom::Start(offset))?;
+        let mut reader = BufReader::new(file);
+
+        let mut records = Vec::new();
+        let mut end = offset;
+        let mut line = String::new();
+        loop {
+            line.clear();
+            let read = reader.read_line(&mut line)?;
+            // Stop at a line still being appended
+            if read == 0 || !line.ends_with('\n') {
+                break;
+            }
+            end += read as u64;
+            if !line.trim().is_empty() {
+                records.push((serde_json::from_str(&line)?, end));
+            }
+        }
+        Ok(records)
+    }
+
+    /// Export a signed bundle covering the child's whole chain
+    pub fn export_bundle(&self, child_id: &ChildId) -> Result<AuditBundle> {
+        let records = self.records(child_id)?;
+        let exported_at = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs();
+
+        let bundle = AuditBundle::new(*child_id, &self.signing_key, exported_at, records);
+
+        // Refuse to export a chain that has been tampered with locally
+        bundle.verify()?;
+
+        Ok(bundle)
+    }
+
+    /// Record a refused IPC request
+    pub fn record_denial(&self, denial: &AccessDenial) -> Result<()> {
+        let mut line = serde_json::to_string(denial)?;
+        line.push('\n');
+
+        let mut file = std::fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(self.log_dir.join(ACCESS_DENIED_LOG))?;
+        file.write_all(line.as_bytes())?;
+        file.sync_all()?;
+
+        Ok(())
+    }
+
+    /// Read every recorded denial, oldest first
+    pub fn denials(&self) -> Result<Vec<AccessDenial>> {
+        let path = self.log_dir.join(ACCESS_DENIED_LOG);
+        if !path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).map_err(DaemonError::from))
+            .collect()
+    }
+
+    /// List children that have audit records
+    pub fn list_children(&self) -> Result<Vec<ChildId>> {
+        let mut children = Vec::new();
+
+        for entry in std::fs::read_dir(&self.log_dir)? {
+            let path = entry?.path();
+            if path.extension().map(|e| e == "jsonl").unwrap_or(false) {
+                if let Some(stem) = path.file_stem() {
+                    if let Ok(child_id) = ChildId::from_hex(&stem.to_string_lossy()) {
+                        children.push(child_id);
+                    }
+                }
+            }
+        }
+
+        Ok(children)
+    }
+
+    /// Get the next sequence number and previous hash for a child's chain
+    fn head(&mut self, child_id: &ChildId) -> Result<(u64, [u8; 32])> {
+        if let Some(head) = self.heads.get(child_id) {
+            return Ok(*head);
+        }
+
+        let records = self.records(child_id)?;
+        let head = match records.last() {
+            Some(last) => (last.sequence + 1, last.record_hash()),
+            None => (0, GENESIS_HASH),
+        };
+        self.heads.insert(*child_id, head);
+
+        Ok(head)
+    }
+
+    /// Get path for a child's log file
+    fn log_path(&self, child_id: &ChildId) -> PathBuf {
+        self.log_dir.join(format!("{}.jsonl", child_id.to_hex()))
+    }
+}
+
+/// Load the audit signing key, generating one on first use
+fn load_or_create_key(path: &PathBuf) -> Result<SigningKey> {
+    if path.exists() {
+        let mut bytes = std::fs::read(path)?;
+        let key = SigningKey::from_slice(&bytes)
+            .map_err(|e| DaemonError::Crypto(format!("Invalid audit key: {}", e)));
+        bytes.zeroize();
+        return key;
+    }
+
+    let key = SigningKey::random(&mut rand::thread_rng());
+
+    // Write to a temp file created owner-only (Unix), then rename for
+    // atomicity. A stale temp file may have looser permissions, so it is
+    // removed rather than reused.
+    let temp_path = path.with_extension("bin.tmp");
+    if temp_path.exists() {
+        std::fs::remove_file(&temp_path)?;
+    }
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options.open(&temp_path)?;
+    file.write_all(&key.to_bytes())?;
+    file.sync_all()?;
+    std::fs::rename(&temp_path, path)?;
+
+    Ok(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sigil_core::types::{ChainId, MessageHash, Signature, TxHash, ZkProofHash};
+    use tempfile::TempDir;
+
+    fn entry(index: u32) -> UsageLogEntry {
+        UsageLogEntry::new(
+            index,
+            1_700_000_000 + index as u64,
+            MessageHash::new([index as u8; 32]),
+            Signature::new([index as u8; 64]),
+            ChainId::ETHEREUM,
+            TxHash::new([0u8; 32]),
+            ZkProofHash::new([0u8; 32]),
+            "test".to_string(),
+        )
+    }
+
+    #[test]
+    fn test_records_from_offset() {
+        let temp_dir = TempDir::new().unwrap();
+        let child_id = ChildId::new([1u8; 32]);
+        let mut log = AuditLog::new(temp_dir.path().to_path_buf()).unwrap();
+        for index in 0..3 {
+            log.append(&child_id, [2u8; 33], &entry(index)).unwrap();
+        }
+
+        let all = log.records_from(&child_id, 0).unwrap();
+        assert_eq!(all.len(), 3);
+
+        // Resuming after the first record skips it
+        let rest = log.records_from(&child_id, all[0].1).unwrap();
+        let indices: Vec<u32> = rest.iter().map(|(r, _)| r.presig_index).collect();
+        assert_eq!(indices, vec![1, 2]);
+        assert_eq!(rest.last().unwrap().1, all[2].1);
+        assert!(log.records_from(&child_id, all[2].1).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_append_and_export_survives_reopen() {
+        let temp_dir = TempDir::new().unwrap();
+        let child_id = ChildId::new([1u8; 32]);
+
+        let pubkey = {
+            let mut log = AuditLog::new(temp_dir.path().to_path_buf()).unwrap();
+            log.append(&child_id, [2u8; 33], &entry(0)).unwrap();
+            log.append(&child_id, [2u8; 33], &entry(1)).unwrap();
+            log.public_key()
+        };
+
+        let mut log = AuditLog::new(temp_dir.path().to_path_buf()).unwrap();
+        assert_eq!(log.public_key(), pubkey);
+
+        log.append(&child_id, [2u8; 33], &entry(2)).unwrap();
+
+        let bundle = log.export_bundle(&child_id).unwrap();
+        assert_eq!(bundle.records.len(), 3);
+        assert_eq!(bundle.records[2].sequence, 2);
+        assert!(bundle.verify().is_ok());
+        assert_eq!(log.list_children().unwrap(), vec![child_id]);
+    }
+    #[cfg(unix)]
+    #[test]
+    fn test_audit_key_is_owner_only() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let temp_dir = TempDir::new().unwrap();
+        let path = temp_dir.path().join("audit_key.bin");
+        std::fs::write(path.with_extension("bin.tmp"), b"stale").unwrap();
+
+        let key = load_or_create_key(&path).unwrap();
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+        assert_eq!(mode & 0o777, 0o600);
+        assert_eq!(load_or_create_key(&path).unwrap(), key);
+    }
+}
@@ -0,0 +1,897 @@
+//! Transaction hash backfill from a local EVM JSON-RPC node
+//!
+//! Signatures are logged with a zero tx hash until the agent reports one
+//! through `UpdateTxHash`, which agents often never do. When a node is
+//! configured, the watcher scans each confirmed block and matches its
+//! transactions to logged signatures, first by `(r, s)` and then by the hash
+//! of the signed payload. Matches go to the [`TxBackfill`] store and reach
+//! the disk at its next write. Signatures still unmatched once the chain has
+//! moved well past them are reported as never having landed.
+//!
+//! Only plain `http://` endpoints are supported: the node is expected to be
+//! local (geth, reth, anvil), not a hosted provider.
+
+use std::collections::{HashMap, HashSet};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+
+use hyper::client::HttpConnector;
+use hyper::{Body, Client, Method, Request, Uri};
+use serde::de::DeserializeOwned;
+use serde::Deserialize;
+use tokio::sync::{Mutex, OnceCell, RwLock};
+use tracing::{debug, info, warn};
+
+use sigil_core::evm::{self, Transaction, TxKind};
+use sigil_core::types::TxHash;
+use sigil_core::{AuditRecord, ChildId};
+
+use crate::audit_log::AuditLog;
+use crate::config::ChainWatcherConfig;
+use crate::error::{DaemonError, Result};
+use crate::signer::{Signer, SigningEvent};
+use crate::tx_backfill::TxBackfill;
+
+/// Default interval between polls (seconds)
+pub const DEFAULT_POLL_INTERVAL_SECS: u64 = 12;
+
+/// Default number of blocks to wait before treating a block as final
+pub const DEFAULT_CONFIRMATIONS: u64 = 2;
+
+/// Default number of blocks fetched in one poll
+pub const DEFAULT_MAX_BLOCKS_PER_POLL: u64 = 100;
+
+/// Default chain time after which an unmatched signature is reported (seconds)
+pub const DEFAULT_NOT_LANDED_AFTER_SECS: u64 = 3600;
+
+/// Timeout for a single JSON-RPC request (seconds)
+const RPC_TIMEOUT_SECS: u64 = 10;
+
+/// Minimal JSON-RPC client for a local node
+pub struct RpcClient {
+    uri: Uri,
+    client: Client<HttpConnector>,
+    next_id: AtomicU64,
+}
+
+#[derive(Deserialize)]
+struct RpcResponse {
+    #[serde(default)]
+    result: Option<serde_json::Value>,
+    #[serde(default)]
+    error: Option<RpcError>,
+}
+
+#[derive(Deserialize)]
+struct RpcError {
+    code: i64,
+    message: String,
+}
+
+impl RpcClient {
+    /// Create a client for the node at `url`
+    pub fn new(url: &str) -> Result<Self> {
+        let uri: Uri = url
+            .parse()
+            .map_err(|e| DaemonError::Config(format!("Invalid RPC URL '{}': {}", url, e)))?;
+        if uri.scheme_str() != Some("http") {
+            return Err(DaemonError::Config(format!(
+                "RPC URL '{}' must use http:// (only local nodes are supported)",
+                url
+            )));
+        }
+
+        Ok(Self {
+            uri,
+            client: Client::new(),
+            next_id: AtomicU64::new(1),
+        })
+    }
+
+    /// Call a JSON-RPC method
+    pub async fn call<T: DeserializeOwned>(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+    ) -> Result<T> {
+        let body = serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": self.next_id.fetch_add(1, Ordering::Relaxed),
+            "method": method,
+            "params": params,
+        });
+        let request = Request::builder()
+            .method(Method::POST)
+            .uri(self.uri.clone())
+            .header("content-type", "application/json")
+            .body(Body::from(serde_json::to_vec(&body)?))
+            .map_err(|e| DaemonError::ChainRpc(e.to_string()))?;
+
+        let exchange = async {
+            let response = self.client.request(request).await?;
+            let status = response.status();
+            let body = hyper::body::to_bytes(response.into_body()).await?;
+            Ok::<_, hyper::Error>((status, body))
+        };
+        let (status, body) = tokio::time::timeout(Duration::from_secs(RPC_TIMEOUT_SECS), exchange)
+            .await
+            .map_err(|_| DaemonError::Timeout)?
+            .map_err(|e| DaemonError::ChainRpc(format!("{}: {}", method, e)))?;
+        if !status.is_success() {
+            return Err(DaemonError::ChainRpc(format!(
+                "{} returned HTTP {}",
+                method, status
+            )));
+        }
+
+        let response: RpcResponse = serde_json::from_slice(&body)
+            .map_err(|e| DaemonError::ChainRpc(format!("{}: invalid response: {}", method, e)))?;
+        if let Some(error) = response.error {
+            return Err(DaemonError::ChainRpc(format!(
+                "{} failed: {} ({})",
+                method, error.message, error.code
+            )));
+        }
+        serde_json::from_value(response.result.unwrap_or_default())
+            .map_err(|e| DaemonError::ChainRpc(format!("{}: unexpected result: {}", method, e)))
+    }
+
+    /// Current chain ID
+    pub async fn chain_id(&self) -> Result<u64> {
+        parse_quantity(
+            &self
+                .call::<String>("eth_chainId", serde_json::json!([]))
+                .await?,
+        )
+    }
+
+    /// Number of the latest block
+    pub async fn block_number(&self) -> Result<u64> {
+        parse_quantity(
+            &self
+                .call::<String>("eth_blockNumber", serde_json::json!([]))
+                .await?,
+        )
+    }
+
+    /// Block with its full transactions, if the node has it
+    pub async fn block(&self, number: u64) -> Result<Option<RpcBlock>> {
+        self.call(
+            "eth_getBlockByNumber",
+            serde_json::json!([format!("0x{:x}", number), true]),
+        )
+        .await
+    }
+}
+
+/// Block as returned by `eth_getBlockByNumber` with full transactions
+#[derive(Debug, Clone, Deserialize)]
+pub struct RpcBlock {
+    pub number: String,
+    pub timestamp: String,
+    #[serde(default)]
+    pub transactions: Vec<RpcTransaction>,
+}
+
+/// Transaction as returned inside an [`RpcBlock`]
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RpcTransaction {
+    pub hash: String,
+    #[serde(rename = "type", default)]
+    pub tx_type: Option<String>,
+    pub nonce: String,
+    #[serde(default)]
+    pub gas_price: Option<String>,
+    #[serde(default)]
+    pub max_fee_per_gas: Option<String>,
+    #[serde(default)]
+    pub max_priority_fee_per_gas: Option<String>,
+    pub gas: String,
+    #[serde(default)]
+    pub to: Option<String>,
+    pub value: String,
+    pub input: String,
+    #[serde(default)]
+    pub chain_id: Option<String>,
+    #[serde(default)]
+    pub access_list: Option<Vec<AccessListItem>>,
+    #[serde(default)]
+    pub v: Option<String>,
+    #[serde(default)]
+    pub r: Option<String>,
+    #[serde(default)]
+    pub s: Option<String>,
+}
+
+/// Entry of an EIP-2930 access list
+#[derive(Debug, Clone, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListItem {
+    pub address: String,
+    #[serde(default)]
+    pub storage_keys: Vec<String>,
+}
+
+impl RpcTransaction {
+    /// Signature as `r || s`, the layout the usage log stores
+    pub fn signature(&self) -> Option<[u8; 64]> {
+        let r = quantity_bytes(self.r.as_deref()?).ok()?;
+        let s = quantity_bytes(self.s.as_deref()?).ok()?;
+        if r.len() > 32 || s.len() > 32 {
+            return None;
+        }
+
+        let mut signature = [0u8; 64];
+        signature[32 - r.len()..32].copy_from_slice(&r);
+        signature[64 - s.len()..].copy_from_slice(&s);
+        Some(signature)
+    }
+
+    /// Hash the sender signed, for legacy, EIP-2930 and EIP-1559 transactions
+    ///
+    /// Returns `None` for other transaction types or malformed fields.
+    pub fn signing_hash(&self) -> Option<[u8; 32]> {
+        self.to_transaction()
+            .ok()
+            .flatten()
+            .map(|tx| tx.signing_hash())
+    }
+
+    fn to_transaction(&self) -> Result<Option<Transaction>> {
+        let kind = match &self.tx_type {
+            None => TxKind::Legacy,
+            Some(tx_type) => match parse_quantity(tx_type)? {
+                0 => TxKind::Legacy,
+                1 => TxKind::Eip2930,
+                2 => TxKind::Eip1559,
+                _ => return Ok(None),
+            },
+        };
+        let chain_id = match (&self.chain_id, &self.v) {
+            (Some(chain_id), _) => Some(parse_quantity(chain_id)?),
+            (None, _) if kind != TxKind::Legacy => return Err(missing_field()),
+            (None, Some(v)) => match parse_quantity(v)? {
+                v if v >= 35 => Some((v - 35) / 2),
+                _ => None,
+            },
+            (None, None) => None,
+        };
+
+        let mut tx = Transaction {
+            kind,
+            chain_id,
+            nonce: parse_quantity(&self.nonce)?,
+            gas_price: Vec::new(),
+            max_priority_fee_per_gas: Vec::new(),
+            max_fee_per_gas: Vec::new(),
+            gas_limit: parse_quantity(&self.gas)?,
+            to: self.to.as_deref().map(fixed_hex).transpose()?,
+            value: quantity_bytes(&self.value)?,
+            data: hex_data(&self.input)?,
+            access_list: Vec::new(),
+            signed: true,
+        };
+        if kind == TxKind::Eip1559 {
+            tx.max_priority_fee_per_gas =
+                quantity_bytes(required(&self.max_priority_fee_per_gas)?)?;
+            tx.max_fee_per_gas = quantity_bytes(required(&self.max_fee_per_gas)?)?;
+        } else {
+            tx.gas_price = quantity_bytes(required(&self.gas_price)?)?;
+        }
+        for item in self.access_list.iter().flatten() {
+            tx.access_list.push(evm::AccessListItem {
+                address: fixed_hex(&item.address)?,
+                storage_keys: item
+                    .storage_keys
+                    .iter()
+                    .map(|key| fixed_hex(key))
+                    .collect::<Result<_>>()?,
+            });
+        }
+        Ok(Some(tx))
+    }
+}
+
+/// What a poll did
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct PollSummary {
+    /// Blocks fetched and scanned
+    pub blocks_scanned: u64,
+
+    /// Signatures newly matched to a transaction
+    pub landed: u32,
+
+    /// Signatures newly reported as never landing
+    pub not_landed: u32,
+
+    /// Whether the scan reached the confirmed head
+    pub caught_up: bool,
+}
+
+/// Watches a local node and backfills transaction hashes
+pub struct ChainWatcher {
+    config: ChainWatcherConfig,
+    rpc: RpcClient,
+    signer: Arc<Signer>,
+    audit_log: Arc<RwLock<AuditLog>>,
+    chain_id: OnceCell<u32>,
+}
+
+impl ChainWatcher {
+    /// Create a watcher feeding the signer's backfill store
+    ///
+    /// The signer must have both an audit log (to find signatures) and a
+    /// [`TxBackfill`] store (to record matches).
+    pub fn new(config: ChainWatcherConfig, signer: Arc<Signer>) -> Result<Self> {
+        let rpc = RpcClient::new(&config.rpc_url)?;
+        let audit_log = signer.audit_log().cloned().ok_or_else(|| {
+            DaemonError::Config("Chain watcher needs the signer's audit log".to_string())
+        })?;
+        if signer.tx_backfill().is_none() {
+            return Err(DaemonError::Config(
+                "Chain watcher needs a tx backfill store on the signer".to_string(),
+            ));
+        }
+
+        let chain_id = OnceCell::new_with(config.chain_id);
+        Ok(Self {
+            config,
+            rpc,
+            signer,
+            audit_log,
+            chain_id,
+        })
+    }
+
+    /// Poll forever, logging failures and retrying at the next interval
+    pub async fn run(&self) {
+        let interval = Duration::from_secs(self.config.poll_interval_secs.max(1));
+        loop {
+            let caught_up = match self.poll().await {
+                Ok(summary) => {
+                    debug!("Chain watcher poll: {:?}", summary);
+                    summary.caught_up
+                }
+                Err(e) => {
+                    warn!("Chain watcher poll failed: {}", e);
+                    true
+                }
+            };
+            // Keep going without waiting while there is a backlog
+            if caught_up {
+                tokio::time::sleep(interval).await;
+            }
+        }
+    }
+
+    /// Scan the next range of confirmed blocks
+    pub async fn poll(&self) -> Result<PollSummary> {
+        let chain_id = *self
+            .chain_id
+            .get_or_try_init(|| async {
+                let chain_id = self.rpc.chain_id().await?;
+                u32::try_from(chain_id).map_err(|_| {
+                    DaemonError::ChainRpc(format!("Chain ID {} is out of range", chain_id))
+                })
+            })
+            .await?;
+
+        let safe_head = self
+            .rpc
+            .block_number()
+            .await?
+            .saturating_sub(self.config.confirmations);
+        let start = self
+            .backfill()
+            .lock()
+            .await
+            .next_block()
+            .or(self.config.start_block)
+            .unwrap_or(safe_head);
+
+        let mut summary = PollSummary::default();
+        if start > safe_head {
+            summary.caught_up = true;
+            return Ok(summary);
+        }
+        let end = safe_head.min(start + self.config.max_blocks_per_poll.max(1) - 1);
+
+        let unsettled = self.unsettled().await?;
+        let pending = self.pending(chain_id, &unsettled).await;
+        let by_signature: HashMap<[u8; 64], usize> = pending
+            .iter()
+            .enumerate()
+            .map(|(i, record)| (*record.signature.as_bytes(), i))
+            .collect();
+        let by_hash: HashMap<[u8; 32], usize> = pending
+            .iter()
+            .enumerate()
+            .map(|(i, record)| (*record.message_hash.as_bytes(), i))
+            .collect();
+        let mut matched = vec![false; pending.len()];
+
+        let mut first_block_time = None;
+        let mut last_block_time = 0;
+        for number in start..=end {
+            let block =
+                self.rpc.block(number).await?.ok_or_else(|| {
+                    DaemonError::ChainRpc(format!("Node has no block {}", number))
+                })?;
+            last_block_time = parse_quantity(&block.timestamp)?;
+            first_block_time.get_or_insert(last_block_time);
+            summary.blocks_scanned += 1;
+
+            for tx in &block.transactions {
+                let found = tx
+                    .signature()
+                    .and_then(|signature| by_signature.get(&signature))
+                    .or_else(|| tx.signing_hash().and_then(|hash| by_hash.get(&hash)));
+                let Some(&i) = found else {
+                    continue;
+                };
+                if matched[i] {
+                    continue;
+                }
+                let tx_hash = TxHash::from_hex(tx.hash.trim_start_matches("0x"))
+                    .map_err(|e| DaemonError::ChainRpc(format!("Invalid tx hash: {}", e)))?;
+
+                let record = &pending[i];
+                matched[i] = self.backfill().lock().await.record_landed(
+                    record.child_id,
+                    record.presig_index,
+                    tx_hash,
+                    number,
+                )?;
+                if matched[i] {
+                    info!(
+                        "Presig {} of child {} landed in tx {} (block {})",
+                        record.presig_index,
+                        record.child_id.short(),
+                        tx.hash,
+                        number
+                    );
+                    summary.landed += 1;
+                }
+            }
+        }
+
+        let mut backfill = self.backfill().lock().await;
+        backfill.record_scanned(end + 1, first_block_time.unwrap_or(last_block_time))?;
+        summary.caught_up = end == safe_head;
+
+        // Only signatures made after the first scanned block could have been
+        // seen, and only once the chain has moved well past them
+        let scanned_since = backfill.scanned_since().unwrap_or(u64::MAX);
+        let overdue = pending.iter().zip(matched).filter(|(record, matched)| {
+            summary.caught_up
+                && !matched
+                && record.timestamp >= scanned_since
+                && record
+                    .timestamp
+                    .saturating_add(self.config.not_landed_after_secs)
+                    <= last_block_time
+        });
+        for (record, _) in overdue {
+            if backfill.is_reported_not_landed(&record.child_id, record.presig_index) {
+                continue;
+            }
+            warn!(
+                "Presig {} of child {} (signed at {}) never landed on chain {}",
+                record.presig_index,
+                record.child_id.short(),
+                record.timestamp,
+                chain_id
+            );
+            backfill.record_not_landed(record.child_id, record.presig_index)?;
+            self.signer.notify(SigningEvent::SignatureNotLanded {
+                child_id: record.child_id,
+                presig_index: record.presig_index,
+                chain_id: record.chain_id,
+                message_hash: record.message_hash,
+                signed_at: record.timestamp,
+            });
+            summary.not_landed += 1;
+        }
+
+        settle(&mut backfill, chain_id, &unsettled)?;
+        Ok(summary)
+    }
+
+    fn backfill(&self) -> &Mutex<TxBackfill> {
+        self.signer
+            .tx_backfill()
+            .expect("checked when the watcher was created")
+    }
+
+    /// Audit records past each child's settled offset, each with the offset
+    /// just past it
+    async fn unsettled(&self) -> Result<Vec<(AuditRecord, u64)>> {
+        let children = self.audit_log.read().await.list_children()?;
+        let offsets: Vec<u64> = {
+            let backfill = self.backfill().lock().await;
+            children
+                .iter()
+                .map(|child_id| backfill.audit_offset(child_id))
+                .collect()
+        };
+
+        let log = self.audit_log.read().await;
+        let mut records = Vec::new();
+        for (child_id, offset) in children.iter().zip(offsets) {
+            records.extend(log.records_from(child_id, offset)?);
+        }
+        Ok(records)
+    }
+
+    /// Logged signatures on this chain not yet matched to a transaction
+    async fn pending(&self, chain_id: u32, unsettled: &[(AuditRecord, u64)]) -> Vec<AuditRecord> {
+        let backfill = self.backfill().lock().await;
+        unsettled
+            .iter()
+            .map(|(record, _)| record)
+            .filter(|record| {
+                record.chain_id.0 == chain_id
+                    && backfill
+                        .landed(&record.child_id, record.presig_index)
+                        .is_none()
+            })
+            .cloned()
+            .collect()
+    }
+}
+
+/// Move each child's audit log offset past the leading records the watcher
+/// is done with: matched, reported as never landing, signed before the first
+/// scanned block (so never matchable), or for another chain
+fn settle(backfill: &mut TxBackfill, chain_id: u32, records: &[(AuditRecord, u64)]) -> Result<()> {
+    let scanned_since = backfill.scanned_since().unwrap_or(0);
+    let mut done: HashMap<ChildId, u64> = HashMap::new();
+    let mut blocked: HashSet<ChildId> = HashSet::new();
+
+    for (record, end) in records {
+        // A child's offset stops at its first record that may still land
+        if blocked.contains(&record.child_id) {
+            continue;
+        }
+        let settled = record.chain_id.0 != chain_id
+            || record.timestamp < scanned_since
+            || backfill
+                .landed(&record.child_id, record.presig_index)
+                .is_some()
+            || backfill.is_reported_not_landed(&record.child_id, record.presig_index);
+        if settled {
+            done.insert(record.child_id, *end);
+        } else {
+            blocked.insert(record.child_id);
+        }
+    }
+
+    for (child_id, offset) in done {
+        backfill.record_audit_offset(child_id, offset)?;
+    }
+    Ok(())
+}
+
+fn required(field: &Option<String>) -> Result<&str> {
+    field.as_deref().ok_or_else(missing_field)
+}
+
+fn missing_field() -> DaemonError {
+    DaemonError::ChainRpc("Transaction is missing a field".to_string())
+}
+
+/// Parse a hex quantity (`0x1a`) that fits in a u64
+fn parse_quantity(value: &str) -> Result<u64> {
+    u64::from_str_radix(value.trim_start_matches("0x"), 16)
+        .map_err(|e| DaemonError::ChainRpc(format!("Invalid quantity '{}': {}", value, e)))
+}
+
+/// Big-endian bytes of a hex quantity, without leading zeros
+fn quantity_bytes(value: &str) -> Result<Vec<u8>> {
+    let digits = value.trim_start_matches("0x");
+    let padded = if digits.len() % 2 == 1 {
+        format!("0{}", digits)
+    } else {
+        digits.to_string()
+    };
+    let bytes = hex::decode(padded)
+        .map_err(|e| DaemonError::ChainRpc(format!("Invalid quantity '{}': {}", value, e)))?;
+    Ok(evm::trim_zeros(&bytes).to_vec())
+}
+
+/// Bytes of hex data (`0x` prefixed, even length)
+fn hex_data(value: &str) -> Result<Vec<u8>> {
+    hex::decode(value.trim_start_matches("0x"))
+        .map_err(|e| DaemonError::ChainRpc(format!("Invalid hex data: {}", e)))
+}
+
+/// Fixed-size hex data, such as an address or storage key
+fn fixed_hex<const N: usize>(value: &str) -> Result<[u8; N]> {
+    hex_data(value)?
+        .try_into()
+        .map_err(|_| DaemonError::ChainRpc(format!("Expected {} bytes: {}", N, value)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::agent_store::AgentStore;
+    use crate::disk_watcher::DiskWatcher;
+    use hyper::service::{make_service_fn, service_fn};
+    use sigil_core::types::{ChainId, MessageHash, Signature, ZkProofHash};
+    use sigil_core::{ChildId, UsageLogEntry};
+    use std::convert::Infallible;
+    use tempfile::TempDir;
+
+    /// The EIP-155 example: 1 ETH to 0x3535…35 at nonce 9 on mainnet
+    fn eip155_example() -> RpcTransaction {
+        RpcTransaction {
+            hash: format!("0x{}", "bb".repeat(32)),
+            tx_type: Some("0x0".to_string()),
+            nonce: "0x9".to_string(),
+            gas_price: Some("0x4a817c800".to_string()),
+            max_fee_per_gas: None,
+            max_priority_fee_per_gas: None,
+            gas: "0x5208".to_string(),
+            to: Some(format!("0x{}", "35".repeat(20))),
+            value: "0xde0b6b3a7640000".to_string(),
+            input: "0x".to_string(),
+            chain_id: None,
+            access_list: None,
+            v: Some("0x25".to_string()),
+            r: Some(format!("0x{}", "01".repeat(32))),
+            s: Some(format!("0x{}", "02".repeat(32))),
+        }
+    }
+
+    const EIP155_SIGNING_HASH: &str =
+        "daf5a779ae972f972197303d7b574746c7ef83eadac0f2791ad23db92e4c8e53";
+
+    #[test]
+    fn test_signing_hash_and_signature() {
+        let tx = eip155_example();
+        assert_eq!(hex::encode(tx.signing_hash().unwrap()), EIP155_SIGNING_HASH);
+
+        let mut short_r = eip155_example();
+        short_r.r = Some("0x1".to_string());
+        let signature = short_r.signature().unwrap();
+        assert_eq!(signature[31], 1);
+        assert_eq!(signature[..31], [0u8; 31]);
+        assert_eq!(signature[32..], [2u8; 32]);
+
+        // Unknown transaction types can only be matched by signature
+        let mut blob = eip155_example();
+        blob.tx_type = Some("0x3".to_string());
+        assert!(blob.signing_hash().is_none());
+    }
+
+    /// JSON-RPC node serving a fixed chain
+    async fn spawn_node(blocks: Vec<serde_json::Value>) -> String {
+        let blocks = Arc::new(blocks);
+        let make_service = make_service_fn(move |_| {
+            let blocks = Arc::clone(&blocks);
+            async move {
+                Ok::<_, Infallible>(service_fn(move |request: Request<Body>| {
+                    let blocks = Arc::clone(&blocks);
+                    async move {
+                        let body = hyper::body::to_bytes(request.into_body()).await.unwrap();
+                        let request: serde_json::Value = serde_json::from_slice(&body).unwrap();
+                        let result = match request["method"].as_str().unwrap() {
+                            "eth_chainId" => serde_json::json!("0x1"),
+                            "eth_blockNumber" => {
+                                serde_json::json!(format!("0x{:x}", blocks.len() - 1))
+                            }
+                            "eth_getBlockByNumber" => {
+                                let number =
+                                    parse_quantity(request["params"][0].as_str().unwrap()).unwrap();
+                                blocks.get(number as usize).cloned().unwrap_or_default()
+                            }
+                            _ => serde_json::Value::Null,
+                        };
+                        let response = serde_json::json!({
+                            "jsonrpc": "2.0",
+                            "id": request["id"],
+                            "result": result,
+                        });
+                        Ok::<_, Infallible>(hyper::Response::new(Body::from(response.to_string())))
+                    }
+                }))
+            }
+        });
+
+        let server = hyper::Server::bind(&"127.0.0.1:0".parse().unwrap()).serve(make_service);
+        let address = server.local_addr();
+        tokio::spawn(server);
+        format!("http://{}", address)
+    }
+
+    fn block(number: u64, transactions: Vec<serde_json::Value>) -> serde_json::Value {
+        serde_json::json!({
+            "number": format!("0x{:x}", number),
+            "timestamp": format!("0x{:x}", 1_700_000_000 + number * 600),
+            "transactions": transactions,
+        })
+    }
+
+    fn log_entry(index: u32, message_hash: [u8; 32], signature: [u8; 64]) -> UsageLogEntry {
+        UsageLogEntry::new(
+            index,
+            1_700_000_000 + index as u64,
+            MessageHash::new(message_hash),
+            Signature::new(signature),
+            ChainId::ETHEREUM,
+            TxHash::new([0u8; 32]),
+            ZkProofHash::new([0u8; 32]),
+            "test".to_string(),
+        )
+    }
+
+    #[tokio::test]
+    async fn test_poll_backfills_and_reports_not_landed() {
+        let temp_dir = TempDir::new().unwrap();
+        let store_path = temp_dir.path().join("store");
+        let child_id = ChildId::new([0x11; 32]);
+
+        // Presig 0 lands with its signature, presig 1 is found by its signing
+        // hash, presig 2 never lands and presig 3 is on another chain
+        let mut signature = [0u8; 64];
+        signature[..32].copy_from_slice(&[0xaa; 32]);
+        signature[32..].copy_from_slice(&[0x0b; 32]);
+        let signing_hash: [u8; 32] = hex::decode(EIP155_SIGNING_HASH)
+            .unwrap()
+            .try_into()
+            .unwrap();
+        let audit_log = Arc::new(RwLock::new(AuditLog::new(store_path.clone()).unwrap()));
+        {
+            let mut log = audit_log.write().await;
+            log.append(&child_id, [2u8; 33], &log_entry(0, [1u8; 32], signature))
+                .unwrap();
+            log.append(&child_id, [2u8; 33], &log_entry(1, signing_hash, [3u8; 64]))
+                .unwrap();
+            log.append(&child_id, [2u8; 33], &log_entry(2, [4u8; 32], [5u8; 64]))
+                .unwrap();
+            let mut other_chain = log_entry(3, [6u8; 32], [7u8; 64]);
+            other_chain.chain_id = ChainId::new(137);
+            log.append(&child_id, [2u8; 33], &other_chain).unwrap();
+        }
+
+        let eip1559 = serde_json::json!({
+            "hash": format!("0x{}", "aa".repeat(32)),
+            "type": "0x2",
+            "chainId": "0x1",
+            "nonce": "0x0",
+            "maxPriorityFeePerGas": "0x1",
+            "maxFeePerGas": "0x2",
+            "gas": "0x5208",
+            "to": format!("0x{}", "35".repeat(20)),
+            "value": "0x0",
+            "input": "0x",
+            "accessList": [],
+            "v": "0x0",
+            "r": format!("0x{}", "aa".repeat(32)),
+            "s": format!("0x{}", "0b".repeat(32)),
+        });
+        let legacy = eip155_example_json();
+        let mut blocks: Vec<_> = (0..10).map(|n| block(n, Vec::new())).collect();
+        blocks[2] = block(2, vec![eip1559]);
+        blocks[3] = block(3, vec![legacy]);
+        let url = spawn_node(blocks).await;
+
+        let signer = Arc::new(
+            Signer::new(
+                Arc::new(RwLock::new(AgentStore::new(store_path.clone()).unwrap())),
+                Arc::new(DiskWatcher::new(
+                    temp_dir.path().join("*.disk").display().to_string(),
+                )),
+                false,
+            )
+            .with_audit_log(Arc::clone(&audit_log))
+            .with_tx_backfill(TxBackfill::new(store_path.clone()).unwrap()),
+        );
+        let mut events = signer.subscribe();
+
+        let mut config = ChainWatcherConfig::new(url);
+        config.start_block = Some(0);
+        config.max_blocks_per_poll = 4;
+        let watcher = ChainWatcher::new(config, Arc::clone(&signer)).unwrap();
+
+        // Head is 9, so blocks up to 7 are confirmed
+        let summary = watcher.poll().await.unwrap();
+        assert_eq!(
+            summary,
+            PollSummary {
+                blocks_scanned: 4,
+                landed: 2,
+                not_landed: 0,
+                caught_up: false,
+            }
+        );
+
+        // Later polls only read the audit log from the first unsettled record
+        let offset = signer
+            .tx_backfill()
+            .unwrap()
+            .lock()
+            .await
+            .audit_offset(&child_id);
+        let rest = audit_log
+            .read()
+            .await
+            .records_from(&child_id, offset)
+            .unwrap();
+        assert_eq!(rest.len(), 2);
+        assert_eq!(rest[0].0.presig_index, 2);
+
+        let summary = watcher.poll().await.unwrap();
+        assert_eq!(summary.blocks_scanned, 4);
+        assert_eq!(summary.not_landed, 1);
+        assert!(summary.caught_up);
+        match events.try_recv().unwrap() {
+            SigningEvent::SignatureNotLanded {
+                presig_index,
+                chain_id,
+                ..
+            } => {
+                assert_eq!(presig_index, 2);
+                assert_eq!(chain_id, ChainId::ETHEREUM);
+            }
+            other => panic!("unexpected event {:?}", other),
+        }
+
+        // Nothing new: no blocks to scan and nothing reported twice
+        assert_eq!(
+            watcher.poll().await.unwrap(),
+            PollSummary {
+                caught_up: true,
+                ..Default::default()
+            }
+        );
+
+        let backfill = signer.tx_backfill().unwrap().lock().await;
+        let landed = backfill.landed(&child_id, 0).unwrap();
+        assert_eq!(landed.tx_hash, TxHash::new([0xaa; 32]));
+        assert_eq!(landed.block_number, 2);
+        assert_eq!(
+            backfill.landed(&child_id, 1).unwrap().tx_hash,
+            TxHash::new([0xbb; 32])
+        );
+        assert!(backfill.landed(&child_id, 2).is_none());
+        assert!(backfill.is_reported_not_landed(&child_id, 2));
+        assert!(!backfill.is_reported_not_landed(&child_id, 3));
+        assert_eq!(backfill.next_block(), Some(8));
+        assert!(audit_log
+            .read()
+            .await
+            .records_from(&child_id, backfill.audit_offset(&child_id))
+            .unwrap()
+            .is_empty());
+    }
+
+    fn eip155_example_json() -> serde_json::Value {
+        let tx = eip155_example();
+        serde_json::json!({
+            "hash": tx.hash,
+            "type": tx.tx_type,
+            "nonce": tx.nonce,
+            "gasPrice": tx.gas_price,
+            "gas": tx.gas,
+            "to": tx.to,
+            "value": tx.value,
+            "input": tx.input,
+            "v": tx.v,
+            "r": tx.r,
+            "s": tx.s,
+        })
+    }
+
+    #[test]
+    fn test_rpc_url_must_be_http() {
+        assert!(RpcClient::new("http://127.0.0.1:8545").is_ok());
+        assert!(matches!(
+            RpcClient::new("https://mainnet.example.com"),
+            Err(DaemonError::Config(_))
+        ));
+    }
+}
@@ -0,0 +1,443 @@
+//! Tamper-resistant time for expiry enforcement
+//!
+//! Disk expiry and reconciliation deadlines are only as good as the clock
+//! they are checked against. The daemon keeps a high-water mark of the latest
+//! time it signed at, both on the disk header and in the agent store, and
+//! refuses to sign when the wall clock falls behind it or steps backwards
+//! relative to the monotonic clock while running. A local time authority can
+//! additionally vouch for the current time with signed attestations.
+
+use std::net::SocketAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+use k256::ecdsa::signature::{Signer as _, Verifier as _};
+use k256::ecdsa::{Signature, SigningKey, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::net::{TcpListener, TcpStream};
+use tracing::{debug, warn};
+
+use crate::error::{DaemonError, Result};
+
+/// How far the wall clock may sit behind the high-water mark (NTP slew)
+pub const DEFAULT_ROLLBACK_TOLERANCE_SECS: u64 = 300;
+
+/// How far the wall clock may fall behind the monotonic clock between checks
+pub const DEFAULT_MAX_JUMP_SECS: u64 = 300;
+
+/// How far the wall clock may differ from an attested time
+pub const DEFAULT_MAX_SKEW_SECS: u64 = 60;
+
+/// Domain separator for attestation signatures
+const ATTESTATION_DOMAIN: &[u8] = b"sigil-time-attestation-v1";
+
+/// Time allowed for a time authority to answer
+const ATTESTATION_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// Current wall-clock time in unix seconds
+pub fn system_time() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// Decides whether the wall clock can be trusted for signing
+#[derive(Debug)]
+pub struct ClockGuard {
+    /// Allowed lag behind the high-water mark, in seconds
+    rollback_tolerance_secs: u64,
+
+    /// Allowed backward step of the wall clock between checks, in seconds
+    max_jump_secs: u64,
+
+    /// Previous accepted wall-clock reading and when it was taken
+    last_reading: Mutex<Option<(u64, Instant)>>,
+
+    /// Optional source of signed time attestations
+    authority: Option<TimeAuthorityClient>,
+}
+
+impl Default for ClockGuard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ClockGuard {
+    /// Create a guard with the default tolerances and no time authority
+    pub fn new() -> Self {
+        Self {
+            rollback_tolerance_secs: DEFAULT_ROLLBACK_TOLERANCE_SECS,
+            max_jump_secs: DEFAULT_MAX_JUMP_SECS,
+            last_reading: Mutex::new(None),
+            authority: None,
+        }
+    }
+
+    /// Allow the wall clock to sit this far behind the high-water mark
+    pub fn with_rollback_tolerance(mut self, secs: u64) -> Self {
+        self.rollback_tolerance_secs = secs;
+        self
+    }
+
+    /// Allow the wall clock to fall this far behind the monotonic clock
+    pub fn with_max_jump(mut self, secs: u64) -> Self {
+        self.max_jump_secs = secs;
+        self
+    }
+
+    /// Cross-check the wall clock against a time authority
+    pub fn with_authority(mut self, authority: TimeAuthorityClient) -> Self {
+        self.authority = Some(authority);
+        self
+    }
+
+    /// Current time to sign at, given the latest time already recorded
+    ///
+    /// Never returns less than `high_water`, so expiry can't be pushed back
+    /// by a clock that lags within the tolerance.
+    pub async fn trusted_now(&self, high_water: u64) -> Result<u64> {
+        let now = system_time();
+        self.check_reading(now, Instant::now(), high_water)?;
+
+        if let Some(authority) = &self.authority {
+            authority.check(now).await?;
+        }
+
+        Ok(now.max(high_water))
+    }
+
+    /// Check a wall-clock reading taken at monotonic instant `at`
+    ///
+    /// Only backward steps are refused. The monotonic clock stops while the
+    /// machine is suspended, so a wall clock that moved further ahead than
+    /// expected is taken as the new baseline; running fast can only bring
+    /// expiry closer.
+    fn check_reading(&self, now: u64, at: Instant, high_water: u64) -> Result<()> {
+        if now.saturating_add(self.rollback_tolerance_secs) < high_water {
+            return Err(DaemonError::ClockRollback { now, high_water });
+        }
+
+        let mut last = self.last_reading.lock().unwrap();
+        if let Some((last_now, last_at)) = *last {
+            let expected = last_now + at.saturating_duration_since(last_at).as_secs();
+            if now.saturating_add(self.max_jump_secs) < expected {
+                return Err(DaemonError::ClockJump(format!(
+                    "wall clock reads {} but at least {} was expected from the monotonic clock; \
+                     restart the daemon once the clock is correct",
+                    now, expected
+                )));
+            }
+            if now > expected + self.max_jump_secs {
+                debug!(
+                    "Wall clock is {}s ahead of the monotonic clock (suspend?), re-baselining",
+                    now - expected
+                );
+            }
+        }
+        *last = Some((now, at));
+
+        Ok(())
+    }
+}
+
+/// A time authority's signed statement of the current time
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeAttestation {
+    /// Attested unix time in seconds
+    pub timestamp: u64,
+
+    /// ECDSA signature over the nonce and timestamp (64 bytes, hex)
+    pub signature: String,
+}
+
+impl TimeAttestation {
+    /// Bytes signed by the authority
+    fn message(nonce: &[u8; 32], timestamp: u64) -> Vec<u8> {
+        [ATTESTATION_DOMAIN, nonce, &timestamp.to_le_bytes()].concat()
+    }
+
+    /// Check the signature against the authority key and request nonce
+    pub fn verify(&self, nonce: &[u8; 32], key: &VerifyingKey) -> Result<()> {
+        let bytes = hex::decode(&self.signature)
+            .map_err(|e| DaemonError::TimeAttestation(format!("bad signature hex: {}", e)))?;
+        let signature = Signature::from_slice(&bytes)
+            .map_err(|e| DaemonError::TimeAttestation(format!("bad signature: {}", e)))?;
+        key.verify(&Self::message(nonce, self.timestamp), &signature)
+            .map_err(|_| DaemonError::TimeAttestation("signature does not verify".to_string()))
+    }
+}
+
+/// Request sent to a time authority
+#[derive(Debug, Serialize, Deserialize)]
+struct AttestationRequest {
+    /// Fresh random nonce (32 bytes, hex) binding the reply to this request
+    nonce: String,
+}
+
+/// Client for a local time authority
+///
+/// The protocol is one JSON line each way over TCP: the daemon sends
+/// `{"nonce": "<hex>"}` and the authority answers with a [`TimeAttestation`].
+#[derive(Debug)]
+pub struct TimeAuthorityClient {
+    /// Authority address, e.g. `127.0.0.1:7411`
+    address: String,
+
+    /// Authority's attestation key
+    key: VerifyingKey,
+
+    /// Allowed difference between the wall clock and the attested time
+    max_skew_secs: u64,
+
+    /// Whether signing fails when the authority can't be reached
+    required: bool,
+}
+
+impl TimeAuthorityClient {
+    /// Create a client for the authority at `address` with a hex SEC1 public key
+    pub fn new(address: impl Into<String>, public_key: &str) -> Result<Self> {
+        let bytes = hex::decode(public_key.trim_start_matches("0x"))
+            .map_err(|e| DaemonError::Config(format!("Invalid time authority key: {}", e)))?;
+        let key = VerifyingKey::from_sec1_bytes(&bytes)
+            .map_err(|e| DaemonError::Config(format!("Invalid time authority key: {}", e)))?;
+
+        Ok(Self {
+            address: address.into(),
+            key,
+            max_skew_secs: DEFAULT_MAX_SKEW_SECS,
+            required: true,
+        })
+    }
+
+    /// Allow the wall clock to differ this far from the attested time
+    pub fn with_max_skew(mut self, secs: u64) -> Self {
+        self.max_skew_secs = secs;
+        self
+    }
+
+    /// Whether an unreachable authority blocks signing (the default)
+    pub fn required(mut self, required: bool) -> Self {
+        self.required = required;
+        self
+    }
+
+    /// Fetch and verify an attestation, returning the attested time
+    pub async fn attest(&self) -> Result<u64> {
+        let nonce: [u8; 32] = rand::random();
+        let attestation = tokio::time::timeout(ATTESTATION_TIMEOUT, self.request(&nonce))
+            .await
+            .map_err(|_| DaemonError::TimeAttestation("time authority timed out".to_string()))??;
+        attestation.verify(&nonce, &self.key)?;
+        Ok(attestation.timestamp)
+    }
+
+    /// Check the wall-clock reading `now` against an attestation
+    async fn check(&self, now: u64) -> Result<()> {
+        let attested = match self.attest().await {
+            Ok(attested) => attested,
+            Err(e) if !self.required => {
+                warn!(
+                    "Time authority unavailable, using local clock checks: {}",
+                    e
+                );
+                return Ok(());
+            }
+            Err(e) => return Err(e),
+        };
+
+        debug!("Time authority attests {} (local {})", attested, now);
+        if now.abs_diff(attested) > self.max_skew_secs {
+            return Err(DaemonError::TimeAttestation(format!(
+                "system clock {} differs from attested time {}",
+                now, attested
+            )));
+        }
+        Ok(())
+    }
+
+    async fn request(&self, nonce: &[u8; 32]) -> Result<TimeAttestation> {
+        let stream = TcpStream::connect(&self.address).await?;
+        let (reader, mut writer) = stream.into_split();
+
+        let mut line = serde_json::to_string(&AttestationRequest {
+            nonce: hex::encode(nonce),
+        })?;
+        line.push('\n');
+        writer.write_all(line.as_bytes()).await?;
+
+        let mut reply = String::new();
+        BufReader::new(reader).read_line(&mut reply).await?;
+        Ok(serde_json::from_str(&reply)?)
+    }
+}
+
+/// Stand-in time authority that attests its own system clock
+///
+/// Meant for tests and single-host setups; a real deployment would back it
+/// with a GPS or radio clock.
+pub struct LocalTimeAuthority {
+    /// Attestation signing key
+    key: SigningKey,
+
+    /// Seconds added to the system clock in attestations
+    offset_secs: i64,
+}
+
+impl LocalTimeAuthority {
+    /// Create an authority with a fresh random key
+    pub fn generate() -> Self {
+        Self {
+            key: SigningKey::random(&mut rand::rngs::OsRng),
+            offset_secs: 0,
+        }
+    }
+
+    /// Shift attested times by `secs` (to simulate a disagreeing clock)
+    pub fn with_offset(mut self, secs: i64) -> Self {
+        self.offset_secs = secs;
+        self
+    }
+
+    /// Public key clients verify against (hex SEC1, compressed)
+    pub fn public_key_hex(&self) -> String {
+        hex::encode(self.key.verifying_key().to_encoded_point(true).as_bytes())
+    }
+
+    /// Sign the current time for `nonce`
+    pub fn attest(&self, nonce: &[u8; 32]) -> TimeAttestation {
+        let timestamp = system_time().saturating_add_signed(self.offset_secs);
+        let signature: Signature = self.key.sign(&TimeAttestation::message(nonce, timestamp));
+        TimeAttestation {
+            timestamp,
+            signature: hex::encode(signature.to_bytes()),
+        }
+    }
+
+    /// Bind to `address` and answer attestation requests in the background
+    pub async fn spawn(self, address: &str) -> Result<SocketAddr> {
+        let listener = TcpListener::bind(address).await?;
+        let local = listener.local_addr()?;
+        let authority = std::sync::Arc::new(self);
+
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let authority = std::sync::Arc::clone(&authority);
+                tokio::spawn(async move {
+                    if let Err(e) = authority.handle(stream).await {
+                        debug!("Time authority request failed: {}", e);
+                    }
+                });
+            }
+        });
+
+        Ok(local)
+    }
+
+    async fn handle(&self, stream: TcpStream) -> Result<()> {
+        let (reader, mut writer) = stream.into_split();
+        let mut line = String::new();
+        BufReader::new(reader).read_line(&mut line).await?;
+
+        let request: AttestationRequest = serde_json::from_str(&line)?;
+        let mut nonce = [0u8; 32];
+        hex::decode_to_slice(&request.nonce, &mut nonce)
+            .map_err(|e| DaemonError::TimeAttestation(format!("bad nonce: {}", e)))?;
+
+        let mut reply = serde_json::to_string(&self.attest(&nonce))?;
+        reply.push('\n');
+        writer.write_all(reply.as_bytes()).await?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rollback_and_jump_detection() {
+        let guard = ClockGuard::new();
+        let start = Instant::now();
+
+        // Behind the high-water mark beyond the tolerance
+        assert!(matches!(
+            guard.check_reading(1_000_000, start, 1_000_000 + 301),
+            Err(DaemonError::ClockRollback { .. })
+        ));
+
+        // Small lag is tolerated
+        guard
+            .check_reading(1_000_000, start, 1_000_000 + 200)
+            .unwrap();
+
+        // Tracking the monotonic clock is fine
+        guard
+            .check_reading(1_000_000 + 60, start + Duration::from_secs(60), 0)
+            .unwrap();
+
+        // Wall clock steps back an hour while a minute of monotonic time passed
+        assert!(matches!(
+            guard.check_reading(1_000_000 - 3600, start + Duration::from_secs(120), 0),
+            Err(DaemonError::ClockJump(_))
+        ));
+    }
+
+    #[test]
+    fn test_suspend_is_not_a_jump() {
+        let guard = ClockGuard::new();
+        let start = Instant::now();
+        guard.check_reading(1_000_000, start, 0).unwrap();
+
+        // Suspended overnight: the monotonic clock saw five seconds
+        let resumed = start + Duration::from_secs(5);
+        guard
+            .check_reading(1_000_000 + 8 * 3600, resumed, 0)
+            .unwrap();
+
+        // The post-resume reading is the new baseline
+        guard
+            .check_reading(
+                1_000_000 + 8 * 3600 + 30,
+                resumed + Duration::from_secs(30),
+                0,
+            )
+            .unwrap();
+        assert!(matches!(
+            guard.check_reading(1_000_000 + 3600, resumed + Duration::from_secs(60), 0),
+            Err(DaemonError::ClockJump(_))
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_time_authority_attestations() {
+        let authority = LocalTimeAuthority::generate();
+        let key = authority.public_key_hex();
+        let address = authority.spawn("127.0.0.1:0").await.unwrap();
+
+        let guard = ClockGuard::new()
+            .with_authority(TimeAuthorityClient::new(address.to_string(), &key).unwrap());
+        guard.trusted_now(0).await.unwrap();
+
+        // An authority an hour ahead disagrees with the local clock
+        let skewed = LocalTimeAuthority::generate().with_offset(3600);
+        let skewed_key = skewed.public_key_hex();
+        let skewed_address = skewed.spawn("127.0.0.1:0").await.unwrap();
+        let guard = ClockGuard::new().with_authority(
+            TimeAuthorityClient::new(skewed_address.to_string(), &skewed_key).unwrap(),
+        );
+        assert!(matches!(
+            guard.trusted_now(0).await,
+            Err(DaemonError::TimeAttestation(_))
+        ));
+
+        // Attestations from another key are rejected
+        let impostor = TimeAuthorityClient::new(address.to_string(), &skewed_key).unwrap();
+        assert!(matches!(
+            impostor.attest().await,
+            Err(DaemonError::TimeAttestation(_))
+        ));
+    }
+}
@@ -1,8 +1,12 @@
 //! Daemon configuration
 
 use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 
+use crate::ipc::Capability;
+use crate::storage::RawDeviceMatch;
+
 /// Daemon configuration
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DaemonConfig {
@@ -18,11 +22,267 @@ pub struct DaemonConfig {
     /// Disk mount point pattern (for detecting Sigil disks)
     pub disk_mount_pattern: String,
 
+    /// Raw block devices to read Sigil disks from without mounting
+    #[serde(default)]
+    pub raw_devices: Vec<RawDeviceMatch>,
+
     /// Timeout for signing operations (seconds)
     pub signing_timeout_secs: u64,
 
     /// Whether to run in development mode
     pub dev_mode: bool,
+
+    /// Remaining presig count at or below which clients are warned
+    #[serde(default = "DaemonConfig::default_low_presig_threshold")]
+    pub low_presig_threshold: u32,
+
+    /// IPC client authentication policy
+    #[serde(default)]
+    pub ipc_auth: IpcAuthConfig,
+
+    /// Window in which a repeated `(child, message hash)` returns the earlier
+    /// signature instead of consuming a new presig (seconds, 0 disables)
+    #[serde(default = "DaemonConfig::default_sign_dedup_window_secs")]
+    pub sign_dedup_window_secs: u64,
+
+    /// Clock rollback protection
+    #[serde(default)]
+    pub clock: ClockConfig,
+
+    /// Which signing requests wait for a human to approve them
+    #[serde(default)]
+    pub approvals: ApprovalConfig,
+
+    /// Local JSON-RPC node to backfill transaction hashes from
+    #[serde(default)]
+    pub chain_watcher: Option<ChainWatcherConfig>,
+}
+
+/// When signing requests are held for human approval
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ApprovalMode {
+    /// Sign immediately
+    #[default]
+    Off,
+    /// Hold requests whose value exceeds the chain's threshold, or whose
+    /// value the daemon couldn't verify from the transaction
+    Threshold,
+    /// Hold every request
+    Always,
+}
+
+/// Human approval policy for signing requests
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApprovalConfig {
+    #[serde(default)]
+    pub mode: ApprovalMode,
+
+    /// Highest value signed without approval, per chain ID, in the chain's
+    /// smallest unit (e.g. wei)
+    #[serde(default)]
+    pub value_thresholds: HashMap<u32, u128>,
+
+    /// Threshold for chains not listed in `value_thresholds`; without one,
+    /// every request on an unlisted chain needs approval
+    #[serde(default)]
+    pub default_value_threshold: Option<u128>,
+
+    /// How long a request waits for a decision, and an approval for a disk
+    /// to sign with (seconds)
+    #[serde(default = "ApprovalConfig::default_timeout_secs")]
+    pub timeout_secs: u64,
+}
+
+impl Default for ApprovalConfig {
+    fn default() -> Self {
+        Self {
+            mode: ApprovalMode::Off,
+            value_thresholds: HashMap::new(),
+            default_value_threshold: None,
+            timeout_secs: Self::default_timeout_secs(),
+        }
+    }
+}
+
+impl ApprovalConfig {
+    fn default_timeout_secs() -> u64 {
+        crate::approvals::DEFAULT_APPROVAL_TIMEOUT_SECS
+    }
+
+    /// Whether a request on `chain_id` moving `value` needs approval
+    pub fn requires_approval(&self, chain_id: u32, value: Option<u128>) -> bool {
+        match self.mode {
+            ApprovalMode::Off => false,
+            ApprovalMode::Always => true,
+            ApprovalMode::Threshold => {
+                let threshold = self
+                    .value_thresholds
+                    .get(&chain_id)
+                    .copied()
+                    .or(self.default_value_threshold);
+                match (threshold, value) {
+                    (Some(threshold), Some(value)) => value > threshold,
+                    _ => true,
+                }
+            }
+        }
+    }
+}
+
+/// Clock rollback protection settings
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClockConfig {
+    /// How far the clock may sit behind the last recorded signing time (seconds)
+    #[serde(default = "ClockConfig::default_rollback_tolerance_secs")]
+    pub rollback_tolerance_secs: u64,
+
+    /// How far the clock may step back from the monotonic clock while running (seconds)
+    #[serde(default = "ClockConfig::default_max_jump_secs")]
+    pub max_jump_secs: u64,
+
+    /// Local time authority to cross-check the clock against
+    #[serde(default)]
+    pub time_authority: Option<TimeAuthorityConfig>,
+}
+
+/// A local time authority issuing signed time attestations
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TimeAuthorityConfig {
+    /// TCP address, e.g. `127.0.0.1:7411`
+    pub address: String,
+
+    /// Attestation public key (hex SEC1 secp256k1)
+    pub public_key: String,
+
+    /// Allowed difference between the system clock and attested time (seconds)
+    #[serde(default = "TimeAuthorityConfig::default_max_skew_secs")]
+    pub max_skew_secs: u64,
+
+    /// Refuse to sign when the authority can't be reached
+    #[serde(default = "TimeAuthorityConfig::default_required")]
+    pub required: bool,
+}
+
+impl Default for ClockConfig {
+    fn default() -> Self {
+        Self {
+            rollback_tolerance_secs: Self::default_rollback_tolerance_secs(),
+            max_jump_secs: Self::default_max_jump_secs(),
+            time_authority: None,
+        }
+    }
+}
+
+impl ClockConfig {
+    fn default_rollback_tolerance_secs() -> u64 {
+        crate::clock::DEFAULT_ROLLBACK_TOLERANCE_SECS
+    }
+
+    fn default_max_jump_secs() -> u64 {
+        crate::clock::DEFAULT_MAX_JUMP_SECS
+    }
+}
+
+impl TimeAuthorityConfig {
+    fn default_max_skew_secs() -> u64 {
+        crate::clock::DEFAULT_MAX_SKEW_SECS
+    }
+
+    fn default_required() -> bool {
+        true
+    }
+}
+
+/// Transaction hash backfill from a local EVM JSON-RPC node
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ChainWatcherConfig {
+    /// Node endpoint, e.g. `http://127.0.0.1:8545` (plain HTTP only)
+    pub rpc_url: String,
+
+    /// Chain the node serves; asked from the node when unset
+    #[serde(default)]
+    pub chain_id: Option<u32>,
+
+    /// How often to poll for new blocks (seconds)
+    #[serde(default = "ChainWatcherConfig::default_poll_interval_secs")]
+    pub poll_interval_secs: u64,
+
+    /// Blocks to wait before treating a block as final
+    #[serde(default = "ChainWatcherConfig::default_confirmations")]
+    pub confirmations: u64,
+
+    /// Block to start scanning from on first run (defaults to the head)
+    #[serde(default)]
+    pub start_block: Option<u64>,
+
+    /// Most blocks fetched in one poll
+    #[serde(default = "ChainWatcherConfig::default_max_blocks_per_poll")]
+    pub max_blocks_per_poll: u64,
+
+    /// Chain time after which an unmatched signature is reported as never
+    /// landing (seconds)
+    #[serde(default = "ChainWatcherConfig::default_not_landed_after_secs")]
+    pub not_landed_after_secs: u64,
+}
+
+impl ChainWatcherConfig {
+    /// Watch the node at `rpc_url` with default settings
+    pub fn new(rpc_url: impl Into<String>) -> Self {
+        Self {
+            rpc_url: rpc_url.into(),
+            chain_id: None,
+            poll_interval_secs: Self::default_poll_interval_secs(),
+            confirmations: Self::default_confirmations(),
+            start_block: None,
+            max_blocks_per_poll: Self::default_max_blocks_per_poll(),
+            not_landed_after_secs: Self::default_not_landed_after_secs(),
+        }
+    }
+
+    fn default_poll_interval_secs() -> u64 {
+        crate::chain_watcher::DEFAULT_POLL_INTERVAL_SECS
+    }
+
+    fn default_confirmations() -> u64 {
+        crate::chain_watcher::DEFAULT_CONFIRMATIONS
+    }
+
+    fn default_max_blocks_per_poll() -> u64 {
+        crate::chain_watcher::DEFAULT_MAX_BLOCKS_PER_POLL
+    }
+
+    fn default_not_landed_after_secs() -> u64 {
+        crate::chain_watcher::DEFAULT_NOT_LANDED_AFTER_SECS
+    }
+}
+
+/// IPC client authentication policy
+///
+/// With no clients configured, any process from an allowed user has full
+/// access. Once a client is listed, every request must carry a token.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct IpcAuthConfig {
+    /// Additional uids allowed to connect (root and the daemon's user always are)
+    #[serde(default)]
+    pub allowed_uids: Vec<u32>,
+
+    /// Token-authenticated clients
+    #[serde(default)]
+    pub clients: Vec<IpcClientConfig>,
+}
+
+/// A token-authenticated IPC client
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct IpcClientConfig {
+    /// Name recorded in logs and denials
+    pub name: String,
+
+    /// Secret presented in the `authorization` metadata
+    pub token: String,
+
+    /// Highest capability granted to this client
+    pub capability: Capability,
 }
 
 impl Default for DaemonConfig {
@@ -32,8 +292,15 @@ impl Default for DaemonConfig {
             ipc_socket_path: Self::default_ipc_path(),
             enable_zkvm_proving: true,
             disk_mount_pattern: Self::default_disk_pattern(),
+            raw_devices: Vec::new(),
             signing_timeout_secs: 60,
             dev_mode: false,
+            low_presig_threshold: Self::default_low_presig_threshold(),
+            ipc_auth: IpcAuthConfig::default(),
+            sign_dedup_window_secs: Self::default_sign_dedup_window_secs(),
+            clock: ClockConfig::default(),
+            approvals: ApprovalConfig::default(),
+            chain_watcher: None,
         }
     }
 }
@@ -65,6 +332,14 @@ impl DaemonConfig {
         r"*:\SIGIL*".to_string()
     }
 
+    fn default_low_presig_threshold() -> u32 {
+        crate::signer::DEFAULT_LOW_PRESIG_THRESHOLD
+    }
+
+    fn default_sign_dedup_window_secs() -> u64 {
+        crate::sign_cache::DEFAULT_DEDUP_WINDOW_SECS
+    }
+
     fn default_agent_store_path() -> PathBuf {
         dirs::data_local_dir()
             .unwrap_or_else(|| {
@@ -85,9 +360,50 @@ impl DaemonConfig {
     pub fn load(path: &std::path::Path) -> crate::Result<Self> {
         let content = std::fs::read_to_string(path)?;
         let config: Self = serde_json::from_str(&content)?;
+        config.validate()?;
         Ok(config)
     }
 
+    /// Check settings that depend on each other
+    ///
+    /// Approvals need token-authenticated clients with distinct names and
+    /// tokens, including a `sign` client to request signatures and an
+    /// `approve` (or `admin`) client to decide them. Otherwise an agent could
+    /// approve its own requests.
+    pub fn validate(&self) -> crate::Result<()> {
+        if self.approvals.mode == ApprovalMode::Off {
+            return Ok(());
+        }
+        let invalid = |reason: &str| {
+            Err(crate::DaemonError::Config(format!(
+                "approvals are enabled but {}",
+                reason
+            )))
+        };
+
+        let clients = &self.ipc_auth.clients;
+        if clients.is_empty() {
+            return invalid("no ipc_auth clients are configured");
+        }
+        let mut names = HashSet::new();
+        let mut tokens = HashSet::new();
+        for client in clients {
+            if !names.insert(client.name.as_str()) || !tokens.insert(client.token.as_str()) {
+                return invalid(&format!(
+                    "ipc_auth client '{}' shares its name or token with another client",
+                    client.name
+                ));
+            }
+        }
+        if !clients.iter().any(|c| c.capability == Capability::Sign) {
+            return invalid("no ipc_auth client has the sign capability");
+        }
+        if !clients.iter().any(|c| c.capability >= Capability::Approve) {
+            return invalid("no ipc_auth client has the approve capability");
+        }
+        Ok(())
+    }
+
     /// Save configuration to file
     pub fn save(&self, path: &std::path::Path) -> crate::Result<()> {
         let content = serde_json::to_string_pretty(self)?;
@@ -124,3 +440,40 @@ mod dirs {
             })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn client(name: &str, token: &str, capability: Capability) -> IpcClientConfig {
+        IpcClientConfig {
+            name: name.to_string(),
+            token: token.to_string(),
+            capability,
+        }
+    }
+
+    #[test]
+    fn test_approvals_need_separate_sign_and_approve_clients() {
+        let mut config = DaemonConfig::default();
+        config.validate().unwrap();
+
+        config.approvals.mode = ApprovalMode::Always;
+        assert!(config.validate().is_err());
+
+        config.ipc_auth.clients = vec![client("agent", "a", Capability::Sign)];
+        assert!(config.validate().is_err());
+
+        config
+            .ipc_auth
+            .clients
+            .push(client("phone", "a", Capability::Approve));
+        assert!(config.validate().is_err());
+
+        config.ipc_auth.clients[1].token = "b".to_string();
+        config.validate().unwrap();
+
+        config.ipc_auth.clients[0].capability = Capability::Approve;
+        assert!(config.validate().is_err());
+    }
+}
@@ -1,15 +1,24 @@
 //! Disk detection and monitoring via udev
 //!
-//! Watches for Sigil floppy disk insertion and removal.
+//! Watches for Sigil floppy disk insertion and removal. Disks are found as
+//! `sigil.disk` files under mounted filesystems or directly on configured raw
+//! block devices. Several disks can be present at once; they are tracked by
+//! child ID and selected per request.
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::sync::{broadcast, RwLock};
 use tracing::{debug, error, info, warn};
 
-use sigil_core::{DiskFormat, DiskHeader, DISK_MAGIC};
+use sigil_core::disk::HEADER_SIZE;
+use sigil_core::{ChildId, DiskFormat, DiskHeader, DISK_MAGIC};
 
 use crate::error::{DaemonError, Result};
+use crate::storage::{
+    discover_raw_devices, run_blocking, DiskBackend, FileImage, RawDevice, RawDeviceMatch,
+};
 
 /// Event emitted when a disk is detected or removed
 #[derive(Debug, Clone)]
@@ -24,21 +33,32 @@ pub enum DiskEvent {
 
 /// Watches for Sigil disk insertion/removal
 pub struct DiskWatcher {
-    /// Currently detected disk (if any)
-    current_disk: Arc<RwLock<Option<DetectedDisk>>>,
+    /// Currently detected disks, keyed by child ID
+    disks: Arc<RwLock<HashMap<ChildId, DetectedDisk>>>,
 
     /// Event broadcast channel
     event_tx: broadcast::Sender<DiskEvent>,
 
     /// Mount point pattern to watch
     mount_pattern: String,
+
+    /// Raw block devices that may hold a disk image directly
+    raw_devices: Vec<RawDeviceMatch>,
+
+    /// Last time the watch loop was known to be alive
+    heartbeat: std::sync::Mutex<Option<Instant>>,
 }
 
+/// How often the watch loop records a heartbeat while idle
+const HEARTBEAT_INTERVAL: Duration = Duration::from_secs(5);
+
 /// A detected Sigil disk
 #[derive(Debug, Clone)]
 pub struct DetectedDisk {
-    /// Path to the disk
+    /// Path to the disk image file or device node
     pub path: PathBuf,
+    /// Storage the disk is read from and written to
+    pub backend: Arc<dyn DiskBackend>,
     /// Disk header
     pub header: DiskHeader,
     /// Full disk data (loaded on demand)
@@ -51,25 +71,75 @@ impl DiskWatcher {
         let (event_tx, _) = broadcast::channel(16);
 
         Self {
-            current_disk: Arc::new(RwLock::new(None)),
+            disks: Arc::new(RwLock::new(HashMap::new())),
             event_tx,
             mount_pattern,
+            raw_devices: Vec::new(),
+            heartbeat: std::sync::Mutex::new(None),
         }
     }
 
+    /// Also look for disks written directly to matching raw block devices
+    pub fn with_raw_devices(mut self, raw_devices: Vec<RawDeviceMatch>) -> Self {
+        self.raw_devices = raw_devices;
+        self
+    }
+
+    /// Whether the watch loop is running and has checked in recently
+    pub fn is_healthy(&self) -> bool {
+        self.heartbeat
+            .lock()
+            .unwrap()
+            .is_some_and(|beat| beat.elapsed() < HEARTBEAT_INTERVAL * 3)
+    }
+
+    /// Record that the watch loop is alive
+    fn beat(&self) {
+        *self.heartbeat.lock().unwrap() = Some(Instant::now());
+    }
+
     /// Subscribe to disk events
     pub fn subscribe(&self) -> broadcast::Receiver<DiskEvent> {
         self.event_tx.subscribe()
     }
 
-    /// Get the currently detected disk
-    pub async fn current_disk(&self) -> Option<DetectedDisk> {
-        self.current_disk.read().await.clone()
+    /// All detected disks, ordered by mount path
+    pub async fn disks(&self) -> Vec<DetectedDisk> {
+        let mut disks: Vec<DetectedDisk> = self.disks.read().await.values().cloned().collect();
+        disks.sort_by(|a, b| a.path.cmp(&b.path));
+        disks
     }
 
-    /// Check if a disk is currently inserted
+    /// Select a detected disk
+    ///
+    /// `child` is a full hex child ID or a unique prefix of one (such as the
+    /// short form shown in status output). Without a selector, the disk is
+    /// only chosen if exactly one is inserted.
+    pub async fn select_disk(&self, child: Option<&str>) -> Result<DetectedDisk> {
+        let disks = self.disks.read().await;
+
+        let mut matches = disks.values().filter(|disk| match child {
+            Some(selector) => disk
+                .header
+                .child_id
+                .to_hex()
+                .starts_with(&selector.to_ascii_lowercase()),
+            None => true,
+        });
+
+        match (matches.next(), matches.next()) {
+            (Some(disk), None) => Ok(disk.clone()),
+            (Some(_), Some(_)) => Err(DaemonError::MultipleDisksDetected),
+            (None, _) => match child {
+                Some(selector) => Err(DaemonError::ChildDiskNotFound(selector.to_string())),
+                None => Err(DaemonError::NoDiskDetected),
+            },
+        }
+    }
+
+    /// Check if any disk is currently inserted
     pub async fn has_disk(&self) -> bool {
-        self.current_disk.read().await.is_some()
+        !self.disks.read().await.is_empty()
     }
 
     /// Start watching for disk events (blocking)
@@ -78,6 +148,7 @@ impl DiskWatcher {
 
         // Initial scan
         self.scan_for_disks().await?;
+        self.beat();
 
         // Watch for changes using udev
         #[cfg(target_os = "linux")]
@@ -95,11 +166,20 @@ impl DiskWatcher {
 
             let mut socket =
                 AsyncMonitorSocket::new(monitor).map_err(|e| DaemonError::Udev(e.to_string()))?;
+            let mut heartbeat = tokio::time::interval(HEARTBEAT_INTERVAL);
 
             loop {
                 use futures_util::StreamExt;
 
-                match socket.next().await {
+                let event = tokio::select! {
+                    event = socket.next() => event,
+                    _ = heartbeat.tick() => {
+                        self.beat();
+                        continue;
+                    }
+                };
+
+                match event {
                     Some(Ok(event)) => {
                         let action = event.action().map(|a| a.to_string_lossy().to_string());
                         debug!("Udev event: {:?} for {:?}", action, event.devpath());
@@ -111,9 +191,9 @@ impl DiskWatcher {
                                 self.scan_for_disks().await?;
                             }
                             Some("remove") => {
-                                // Always clear cache on remove event, then rescan
+                                // Drop any disk that went away, then rescan
                                 // to handle cases where kernel caching shows stale data
-                                self.handle_disk_removal().await?;
+                                self.remove_stale_disks().await;
                                 // Small delay then rescan to detect any new disk
                                 tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
                                 self.scan_for_disks().await?;
@@ -136,104 +216,117 @@ impl DiskWatcher {
             warn!("Udev not available, using polling fallback");
             loop {
                 self.scan_for_disks().await?;
+                self.beat();
                 tokio::time::sleep(tokio::time::Duration::from_secs(2)).await;
             }
         }
     }
 
     /// Scan for Sigil disks
-    async fn scan_for_disks(&self) -> Result<()> {
-        // First, verify any currently cached disk is still valid
-        // This handles the case where a disk was physically removed but
-        // the mount point or cached data still exists
-        if self.verify_current_disk().await.is_err() {
-            self.handle_disk_removal().await?;
-        }
+    pub(crate) async fn scan_for_disks(&self) -> Result<()> {
+        // First, drop any cached disk that is no longer readable. This handles
+        // the case where a disk was physically removed but the mount point or
+        // cached data still exists
+        self.remove_stale_disks().await;
+
+        let mut candidates: Vec<(Arc<dyn DiskBackend>, bool)> = Vec::new();
 
-        let paths = glob::glob(&self.mount_pattern)
+        // Look for actual Sigil disk files
+        for base_path in glob::glob(&self.mount_pattern)
             .map_err(|e| DaemonError::Config(e.to_string()))?
             .filter_map(|r| r.ok())
-            .collect::<Vec<_>>();
-
-        if paths.is_empty() {
-            // Check if disk was removed
-            if self.current_disk.read().await.is_some() {
-                self.handle_disk_removal().await?;
+        {
+            let disk_file = base_path.join("sigil.disk");
+            if disk_file.exists() {
+                candidates.push((Arc::new(FileImage::new(disk_file)), false));
             }
-            return Ok(());
         }
 
-        // Look for actual Sigil disk file
-        for base_path in paths {
-            let disk_file = base_path.join("sigil.disk");
-            if disk_file.exists() {
-                match self.try_load_disk(&disk_file).await {
-                    Ok(disk) => {
-                        // Check if this is a different disk than currently cached
-                        let is_new_disk = {
-                            let current = self.current_disk.read().await;
-                            match current.as_ref() {
-                                Some(current_disk) => {
-                                    current_disk.header.child_id != disk.header.child_id
-                                }
-                                None => true,
-                            }
-                        };
+        // And for raw devices carrying an image
+        for node in discover_raw_devices(&self.raw_devices) {
+            candidates.push((Arc::new(RawDevice::new(node)), true));
+        }
 
-                        if is_new_disk {
-                            let header = disk.header.clone();
-                            let path = disk_file.clone();
+        for (backend, raw) in candidates {
+            let path = backend.path().to_path_buf();
+
+            // Skip disks we already track without re-reading the whole image
+            if self
+                .disks
+                .read()
+                .await
+                .values()
+                .any(|disk| disk.path == path)
+            {
+                continue;
+            }
 
-                            let mut current = self.current_disk.write().await;
-                            *current = Some(disk);
+            match Self::try_load_disk(backend).await {
+                Ok(disk) => {
+                    let mut disks = self.disks.write().await;
+                    match disks.get(&disk.header.child_id) {
+                        Some(known) if known.path == disk.path => {}
+                        Some(known) => {
+                            warn!(
+                                "Ignoring {:?}: child {} is already present at {:?}",
+                                disk.path,
+                                disk.header.child_id.short(),
+                                known.path
+                            );
+                        }
+                        None => {
+                            let header = disk.header.clone();
+                            info!(
+                                "Sigil disk detected for child {} at {:?}",
+                                header.child_id.short(),
+                                path
+                            );
 
+                            disks.insert(header.child_id, disk);
                             let _ = self.event_tx.send(DiskEvent::Inserted { path, header });
-                            info!("Sigil disk detected");
                         }
-                        return Ok(());
-                    }
-                    Err(e) => {
-                        warn!("Failed to load disk at {:?}: {}", disk_file, e);
-                        let _ = self.event_tx.send(DiskEvent::ValidationFailed {
-                            path: disk_file,
-                            reason: e.to_string(),
-                        });
                     }
                 }
+                // Empty drives and foreign media on a raw device are not errors
+                Err(DaemonError::Io(_)) | Err(DaemonError::NotASigilDisk) if raw => {
+                    debug!("No Sigil disk on raw device {:?}", path);
+                }
+                Err(e) => {
+                    warn!("Failed to load disk at {:?}: {}", path, e);
+                    let _ = self.event_tx.send(DiskEvent::ValidationFailed {
+                        path,
+                        reason: e.to_string(),
+                    });
+                }
             }
         }
 
-        // No valid disk found - clear any stale cache
-        if self.current_disk.read().await.is_some() {
-            self.handle_disk_removal().await?;
-        }
-
         Ok(())
     }
 
-    /// Verify the currently cached disk is still accessible and valid
+    /// Verify a cached disk is still accessible and is the same disk
     /// Returns Ok(()) if disk is valid, Err if disk should be invalidated
-    async fn verify_current_disk(&self) -> Result<()> {
-        let current = self.current_disk.read().await;
-        let disk = match current.as_ref() {
-            Some(d) => d,
-            None => return Ok(()), // No disk cached, nothing to verify
-        };
+    async fn verify_disk(disk: &DetectedDisk) -> Result<()> {
+        // Re-read the header from the media to catch removal or replacement
+        let header = run_blocking(&disk.backend, |backend| {
+            let mut bytes = [0u8; HEADER_SIZE];
+            backend.read_at(0, &mut bytes)?;
+            Ok(bytes)
+        })
+        .await;
 
-        // Try to read the first few bytes to verify disk is still accessible
-        // Use a fresh read to bypass any kernel caching
-        match tokio::fs::read(&disk.path).await {
+        match header {
             Ok(bytes) => {
                 // Verify magic bytes are still valid
-                if bytes.len() < 8 || &bytes[0..8] != DISK_MAGIC {
+                if &bytes[0..8] != DISK_MAGIC {
                     debug!("Cached disk failed magic check - disk removed or corrupted");
                     return Err(DaemonError::DiskValidationFailed(
                         "Disk no longer valid".to_string(),
                     ));
                 }
                 // Verify it's the same disk by checking child_id
-                if let Ok(format) = DiskFormat::from_bytes(&bytes) {
-                    if format.header.child_id != disk.header.child_id {
+                if let Ok(header) = DiskHeader::from_bytes(&bytes) {
+                    if header.child_id != disk.header.child_id {
                         debug!("Disk child_id changed - different disk inserted");
                         return Err(DaemonError::DiskValidationFailed(
                             "Different disk detected".to_string(),
@@ -243,24 +336,19 @@ impl DiskWatcher {
                 Ok(())
             }
             Err(e) => {
-                debug!(
-                    "Failed to read cached disk path: {} - treating as removed",
-                    e
-                );
+                debug!("Failed to read cached disk: {} - treating as removed", e);
                 Err(DaemonError::DiskValidationFailed(e.to_string()))
             }
         }
     }
 
-    /// Try to load and validate a disk file
-    async fn try_load_disk(&self, path: &PathBuf) -> Result<DetectedDisk> {
-        let bytes = tokio::fs::read(path).await?;
+    /// Try to load and validate a disk image from a backend
+    async fn try_load_disk(backend: Arc<dyn DiskBackend>) -> Result<DetectedDisk> {
+        let bytes = run_blocking(&backend, |backend| backend.read_image()).await?;
 
         // Quick magic check
         if bytes.len() < 8 || &bytes[0..8] != DISK_MAGIC {
-            return Err(DaemonError::DiskValidationFailed(
-                "Invalid magic bytes".to_string(),
-            ));
+            return Err(DaemonError::NotASigilDisk);
         }
 
         let format = DiskFormat::from_bytes(&bytes)?;
@@ -274,37 +362,46 @@ impl DiskWatcher {
         format.validate(current_time)?;
 
         Ok(DetectedDisk {
-            path: path.clone(),
+            path: backend.path().to_path_buf(),
+            backend,
             header: format.header.clone(),
             format: Some(format),
         })
     }
 
-    /// Handle disk removal
-    async fn handle_disk_removal(&self) -> Result<()> {
-        let mut current = self.current_disk.write().await;
-        if let Some(disk) = current.take() {
-            info!("Sigil disk removed");
+    /// Drop every cached disk that fails verification
+    async fn remove_stale_disks(&self) {
+        let disks = self.disks().await;
+        for disk in disks {
+            if Self::verify_disk(&disk).await.is_err() {
+                self.remove_disk(&disk.header.child_id).await;
+            }
+        }
+    }
+
+    /// Forget a disk and announce its removal
+    async fn remove_disk(&self, child_id: &ChildId) {
+        if let Some(disk) = self.disks.write().await.remove(child_id) {
+            info!("Sigil disk removed for child {}", child_id.short());
             let _ = self.event_tx.send(DiskEvent::Removed { path: disk.path });
         }
-        Ok(())
     }
 
     /// Load the full disk format (for signing operations)
     /// This always re-reads from disk to ensure we have fresh data
     /// and the disk is still physically present
-    pub async fn load_full_disk(&self) -> Result<DiskFormat> {
-        // First verify the disk is still valid
-        self.verify_current_disk()
-            .await
-            .map_err(|_| DaemonError::NoDiskDetected)?;
+    pub async fn load_full_disk(&self, child: Option<&str>) -> Result<DiskFormat> {
+        let disk = self.select_disk(child).await?;
 
-        let current = self.current_disk.read().await;
-        let disk = current.as_ref().ok_or(DaemonError::NoDiskDetected)?;
+        // Verify the disk is still valid
+        if Self::verify_disk(&disk).await.is_err() {
+            self.remove_disk(&disk.header.child_id).await;
+            return Err(DaemonError::ChildDiskNotFound(disk.header.child_id.short()));
+        }
 
         // Always read fresh from disk for signing operations
         // This ensures we catch any disk removal between operations
-        let bytes = tokio::fs::read(&disk.path).await?;
+        let bytes = run_blocking(&disk.backend, |backend| backend.read_image()).await?;
         let format = DiskFormat::from_bytes(&bytes)?;
 
         // Verify this is still the expected disk
@@ -314,41 +411,64 @@ impl DiskWatcher {
             ));
         }
 
+        // Remember what is on the media so the next write only touches changes
+        if let Some(cached) = self.disks.write().await.get_mut(&disk.header.child_id) {
+            cached.header = format.header.clone();
+            cached.format = Some(format.clone());
+        }
+
         Ok(format)
     }
 
-    /// Force re-verification of the current disk
-    /// Returns true if a valid disk is present, false otherwise
+    /// Force re-verification of all detected disks
+    /// Returns true if at least one valid disk is present, false otherwise
     pub async fn force_verify(&self) -> bool {
-        match self.verify_current_disk().await {
-            Ok(()) => true,
-            Err(_) => {
-                // Clear stale cache
-                let _ = self.handle_disk_removal().await;
-                false
-            }
-        }
+        self.remove_stale_disks().await;
+        self.has_disk().await
     }
 
-    /// Write updated disk data back to disk
+    /// Write updated disk data back to the disk it was loaded from
+    ///
+    /// Only the regions that differ from the last image read or written are
+    /// touched (see [`DiskFormat::dirty_regions`]); the whole image is only
+    /// rewritten when nothing is cached or the presig table was resized.
+    /// Everything written is read back before returning.
     pub async fn write_disk(&self, format: &DiskFormat) -> Result<()> {
-        let path = {
-            let current = self.current_disk.read().await;
-            let disk = current.as_ref().ok_or(DaemonError::NoDiskDetected)?;
-            disk.path.clone()
+        let child_id = format.header.child_id;
+        let (backend, regions) = {
+            let disks = self.disks.read().await;
+            let disk = disks
+                .get(&child_id)
+                .ok_or_else(|| DaemonError::ChildDiskNotFound(child_id.short()))?;
+            let regions = disk
+                .format
+                .as_ref()
+                .and_then(|previous| format.dirty_regions(previous));
+            (Arc::clone(&disk.backend), regions)
         };
 
-        let bytes = format.to_bytes();
-        tokio::fs::write(&path, &bytes).await?;
-
-        // Sync to ensure data is flushed to physical disk (important for floppies)
-        if let Ok(file) = std::fs::File::open(&path) {
-            let _ = file.sync_all();
+        // The backend syncs to the physical media (important for floppies)
+        match regions {
+            Some(regions) => {
+                debug!(
+                    "Writing {} dirty region(s), {} bytes",
+                    regions.len(),
+                    regions.iter().map(|r| r.bytes.len()).sum::<usize>()
+                );
+                run_blocking(&backend, move |backend| backend.write_regions(&regions)).await?;
+            }
+            None => {
+                let bytes = format.to_bytes();
+                run_blocking(&backend, move |backend| {
+                    backend.write_image(&bytes)?;
+                    backend.verify_at(0, &bytes)
+                })
+                .await?;
+            }
         }
 
         // Update the cached disk with the new header
-        let mut current = self.current_disk.write().await;
-        if let Some(ref mut disk) = *current {
+        if let Some(disk) = self.disks.write().await.get_mut(&child_id) {
             disk.header = format.header.clone();
             disk.format = Some(format.clone());
         }
@@ -366,4 +486,124 @@ mod tests {
         let watcher = DiskWatcher::new("/tmp/test_sigil*".to_string());
         assert!(!watcher.has_disk().await);
     }
+
+    fn write_test_disk(dir: &std::path::Path, child: u8) {
+        use sigil_core::{crypto::DerivationPath, presig::PresigColdShare, PublicKey};
+
+        let now = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap()
+            .as_secs();
+        let header = DiskHeader::new(
+            ChildId::new([child; 32]),
+            PublicKey::new([2u8; 33]),
+            DerivationPath::ethereum_hardened(0),
+            4,
+            now,
+        );
+        let presigs = (0..4)
+            .map(|i| PresigColdShare::new([i as u8; 33], [i as u8; 32], [i as u8; 32]))
+            .collect();
+
+        std::fs::create_dir_all(dir).unwrap();
+        std::fs::write(
+            dir.join("sigil.disk"),
+            DiskFormat::new(header, presigs).to_bytes(),
+        )
+        .unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_multiple_disks_require_selection() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        write_test_disk(&temp_dir.path().join("mnt_a"), 0xaa);
+        write_test_disk(&temp_dir.path().join("mnt_b"), 0xbb);
+
+        let pattern = temp_dir.path().join("mnt_*").display().to_string();
+        let watcher = DiskWatcher::new(pattern);
+        watcher.scan_for_disks().await.unwrap();
+
+        assert_eq!(watcher.disks().await.len(), 2);
+        assert!(matches!(
+            watcher.select_disk(None).await,
+            Err(DaemonError::MultipleDisksDetected)
+        ));
+        assert!(matches!(
+            watcher.select_disk(Some("cc")).await,
+            Err(DaemonError::ChildDiskNotFound(_))
+        ));
+
+        let disk = watcher.load_full_disk(Some("BBBB")).await.unwrap();
+        assert_eq!(disk.header.child_id, ChildId::new([0xbb; 32]));
+
+        // Once one disk is removed the other is chosen implicitly
+        std::fs::remove_file(temp_dir.path().join("mnt_a/sigil.disk")).unwrap();
+        watcher.scan_for_disks().await.unwrap();
+        let disk = watcher.select_disk(None).await.unwrap();
+        assert_eq!(disk.header.child_id, ChildId::new([0xbb; 32]));
+    }
+
+    #[tokio::test]
+    async fn test_raw_device_disk_detected_and_written() {
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        write_test_disk(temp_dir.path(), 0xcc);
+
+        // A raw image without any filesystem, standing in for /dev/fd0
+        let device = temp_dir.path().join("fd0");
+        std::fs::rename(temp_dir.path().join("sigil.disk"), &device).unwrap();
+
+        let watcher = DiskWatcher::new(String::new()).with_raw_devices(vec![RawDeviceMatch {
+            path: Some(device.clone()),
+            ..Default::default()
+        }]);
+        watcher.scan_for_disks().await.unwrap();
+
+        let mut disk = watcher.load_full_disk(None).await.unwrap();
+        assert_eq!(disk.header.child_id, ChildId::new([0xcc; 32]));
+
+        disk.mark_presig_used(0).unwrap();
+        watcher.write_disk(&disk).await.unwrap();
+
+        let reread = DiskFormat::from_bytes(&std::fs::read(&device).unwrap()).unwrap();
+        assert_eq!(reread.header.presig_used, 1);
+    }
+
+    #[tokio::test]
+    async fn test_incremental_write_matches_full_image() {
+        use sigil_core::{
+            types::{ChainId, MessageHash, Signature, TxHash, ZkProofHash},
+            usage::UsageLogEntry,
+        };
+
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        write_test_disk(temp_dir.path(), 0xdd);
+        let path = temp_dir.path().join("sigil.disk");
+
+        let watcher = DiskWatcher::new(temp_dir.path().display().to_string());
+        watcher.scan_for_disks().await.unwrap();
+
+        for index in 0..2u32 {
+            let mut disk = watcher.load_full_disk(None).await.unwrap();
+            disk.mark_presig_used(index).unwrap();
+            disk.usage_log
+                .push(UsageLogEntry::new(
+                    index,
+                    disk.header.created_at + 1,
+                    MessageHash::new([index as u8; 32]),
+                    Signature::new([1u8; 64]),
+                    ChainId::ETHEREUM,
+                    TxHash::new([0u8; 32]),
+                    ZkProofHash::new([2u8; 32]),
+                    format!("tx {}", index),
+                ))
+                .unwrap();
+            watcher.write_disk(&disk).await.unwrap();
+
+            assert_eq!(std::fs::read(&path).unwrap(), disk.to_bytes());
+        }
+
+        let reread = watcher.load_full_disk(None).await.unwrap();
+        assert_eq!(reread.header.presig_used, 2);
+        assert_eq!(reread.usage_log.len(), 2);
+    }
 }
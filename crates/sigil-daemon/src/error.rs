@@ -25,9 +25,17 @@ pub enum DaemonError {
     NoDiskDetected,
 
     /// Multiple disks detected
-    #[error("Multiple signing disks detected - please insert only one")]
+    #[error("Multiple signing disks detected - specify a child_id to choose one")]
     MultipleDisksDetected,
 
+    /// No inserted disk matches the requested child
+    #[error("No signing disk inserted for child: {0}")]
+    ChildDiskNotFound(String),
+
+    /// Storage holds no Sigil disk image
+    #[error("Not a Sigil disk")]
+    NotASigilDisk,
+
     /// Disk validation failed
     #[error("Disk validation failed: {0}")]
     DiskValidationFailed(String),
@@ -56,6 +64,10 @@ pub enum DaemonError {
     #[error("Configuration error: {0}")]
     Config(String),
 
+    /// JSON-RPC node request failed
+    #[error("Chain RPC error: {0}")]
+    ChainRpc(String),
+
     /// Udev error
     #[error("Udev error: {0}")]
     Udev(String),
@@ -68,6 +80,44 @@ pub enum DaemonError {
     #[error("Crypto error: {0}")]
     Crypto(String),
 
+    /// Idempotency key reused for a different message
+    #[error("Idempotency key '{0}' was already used for a different message")]
+    IdempotencyConflict(String),
+
+    /// Wall clock is behind the recorded high-water mark
+    #[error(
+        "System clock ({now}) is behind the last recorded time ({high_water}); refusing to sign"
+    )]
+    ClockRollback { now: u64, high_water: u64 },
+
+    /// Wall clock stepped back relative to the monotonic clock
+    #[error("System clock jumped: {0}")]
+    ClockJump(String),
+
+    /// Time authority attestation missing or invalid
+    #[error("Time attestation failed: {0}")]
+    TimeAttestation(String),
+
+    /// No approval with this ID
+    #[error("Approval not found: {0}")]
+    ApprovalNotFound(String),
+
+    /// The approval has already been decided or has expired
+    #[error("Approval {0} is no longer pending")]
+    ApprovalClosed(String),
+
+    /// A client tried to approve its own signing request
+    #[error("Signing requests must be approved by a different client")]
+    SelfApproval,
+
+    /// A human rejected the signing request
+    #[error("Signing request {id} was rejected by {approver}{}", .reason.as_ref().map(|r| format!(": {}", r)).unwrap_or_default())]
+    ApprovalRejected {
+        id: String,
+        approver: String,
+        reason: Option<String>,
+    },
+
     /// Timeout
     #[error("Operation timed out")]
     Timeout,
@@ -75,6 +125,80 @@ pub enum DaemonError {
     /// Operation cancelled
     #[error("Operation cancelled")]
     Cancelled,
+
+    /// Daemon is shutting down and no longer accepts signing requests
+    #[error("Daemon is shutting down")]
+    ShuttingDown,
+}
+
+impl DaemonError {
+    /// Every value [`DaemonError::code`] can return
+    pub const CODES: &'static [&'static str] = &[
+        "core",
+        "io",
+        "serialization",
+        "no_disk_detected",
+        "multiple_disks_detected",
+        "child_disk_not_found",
+        "not_a_sigil_disk",
+        "disk_validation_failed",
+        "agent_shard_not_found",
+        "presig_mismatch",
+        "signing_failed",
+        "zk_proof_failed",
+        "ipc",
+        "config",
+        "chain_rpc",
+        "udev",
+        "store",
+        "crypto",
+        "idempotency_conflict",
+        "clock_rollback",
+        "clock_jump",
+        "time_attestation",
+        "approval_not_found",
+        "approval_closed",
+        "self_approval",
+        "approval_rejected",
+        "timeout",
+        "cancelled",
+        "shutting_down",
+    ];
+
+    /// Stable identifier of the error kind, passed on to IPC clients
+    pub fn code(&self) -> &'static str {
+        match self {
+            DaemonError::Core(_) => "core",
+            DaemonError::Io(_) => "io",
+            DaemonError::Serialization(_) => "serialization",
+            DaemonError::NoDiskDetected => "no_disk_detected",
+            DaemonError::MultipleDisksDetected => "multiple_disks_detected",
+            DaemonError::ChildDiskNotFound(_) => "child_disk_not_found",
+            DaemonError::NotASigilDisk => "not_a_sigil_disk",
+            DaemonError::DiskValidationFailed(_) => "disk_validation_failed",
+            DaemonError::AgentShardNotFound(_) => "agent_shard_not_found",
+            DaemonError::PresigMismatch(_) => "presig_mismatch",
+            DaemonError::SigningFailed(_) => "signing_failed",
+            DaemonError::ZkProofFailed(_) => "zk_proof_failed",
+            DaemonError::Ipc(_) => "ipc",
+            DaemonError::Config(_) => "config",
+            DaemonError::ChainRpc(_) => "chain_rpc",
+            DaemonError::Udev(_) => "udev",
+            DaemonError::Store(_) => "store",
+            DaemonError::Crypto(_) => "crypto",
+            DaemonError::IdempotencyConflict(_) => "idempotency_conflict",
+            DaemonError::ClockRollback { .. } => "clock_rollback",
+            DaemonError::ClockJump(_) => "clock_jump",
+            DaemonError::TimeAttestation(_) => "time_attestation",
+            DaemonError::ApprovalNotFound(_) => "approval_not_found",
+            DaemonError::ApprovalClosed(_) => "approval_closed",
+            DaemonError::SelfApproval => "self_approval",
+            DaemonError::ApprovalRejected { .. } => "approval_rejected",
+            DaemonError::Timeout => "timeout",
+            DaemonError::Cancelled => "cancelled",
+            DaemonError::ShuttingDown => "shutting_down",
+        }
+    }
 }
 
 impl From<bitcode::Error> for DaemonError {
@@ -88,3 +212,54 @@ impl From<serde_json::Error> for DaemonError {
         DaemonError::Serialization(e.to_string())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_codes_lists_every_variant() {
+        let s = String::new;
+        let errors = [
+            DaemonError::Core(sigil_core::Error::InvalidMagic),
+            DaemonError::Io(std::io::Error::other("io")),
+            DaemonError::Serialization(s()),
+            DaemonError::NoDiskDetected,
+            DaemonError::MultipleDisksDetected,
+            DaemonError::ChildDiskNotFound(s()),
+            DaemonError::NotASigilDisk,
+            DaemonError::DiskValidationFailed(s()),
+            DaemonError::AgentShardNotFound(s()),
+            DaemonError::PresigMismatch(s()),
+            DaemonError::SigningFailed(s()),
+            DaemonError::ZkProofFailed(s()),
+            DaemonError::Ipc(s()),
+            DaemonError::Config(s()),
+            DaemonError::ChainRpc(s()),
+            DaemonError::Udev(s()),
+            DaemonError::Store(s()),
+            DaemonError::Crypto(s()),
+            DaemonError::IdempotencyConflict(s()),
+            DaemonError::ClockRollback {
+                now: 0,
+                high_water: 0,
+            },
+            DaemonError::ClockJump(s()),
+            DaemonError::TimeAttestation(s()),
+            DaemonError::ApprovalNotFound(s()),
+            DaemonError::ApprovalClosed(s()),
+            DaemonError::SelfApproval,
+            DaemonError::ApprovalRejected {
+                id: s(),
+                approver: s(),
+                reason: None,
+            },
+            DaemonError::Timeout,
+            DaemonError::Cancelled,
+            DaemonError::ShuttingDown,
+        ];
+
+        let codes: Vec<&str> = errors.iter().map(DaemonError::code).collect();
+        assert_eq!(codes, DaemonError::CODES);
+    }
+}
@@ -0,0 +1,334 @@
+//! IPC client authentication
+//!
+//! Every request is checked twice: the connecting process must belong to an
+//! allowed user (peer credentials, Unix only), and when client tokens are
+//! configured the request must carry a token whose capability covers the RPC.
+
+use serde::{Deserialize, Serialize};
+use std::sync::RwLock;
+use thiserror::Error;
+use tonic::metadata::{Ascii, MetadataValue};
+use tonic::service::Interceptor;
+use tonic::Status;
+
+use crate::config::IpcAuthConfig;
+
+/// Metadata key carrying the client token
+pub const AUTHORIZATION_METADATA: &str = "authorization";
+
+/// Environment variable clients read their token from
+pub const IPC_TOKEN_ENV: &str = "SIGIL_IPC_TOKEN";
+
+/// What a client is allowed to do
+///
+/// Capabilities are ordered: each one includes everything below it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Capability {
+    /// Read disk status, presig counts and events
+    Status,
+    /// Sign and update transaction hashes
+    Sign,
+    /// Approve or reject signing requests held for a human decision
+    Approve,
+    /// Import shards and export audit bundles
+    Admin,
+}
+
+/// Credentials of the process on the other end of the socket
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PeerCredentials {
+    pub uid: u32,
+    pub gid: u32,
+    pub pid: Option<i32>,
+}
+
+/// Per-connection information made available to request handlers
+#[derive(Debug, Clone, Default)]
+pub struct IpcConnectInfo {
+    /// Peer credentials, when the transport provides them
+    pub peer: Option<PeerCredentials>,
+}
+
+/// Reason a request was refused
+#[derive(Debug, Error, PartialEq, Eq)]
+pub enum AccessDenied {
+    #[error("uid {0} is not allowed to use the daemon socket")]
+    PeerNotAllowed(u32),
+
+    #[error("peer credentials unavailable and no client token presented")]
+    UnknownPeer,
+
+    #[error("client token required")]
+    MissingToken,
+
+    #[error("invalid client token")]
+    InvalidToken,
+
+    #[error("client '{client}' lacks the {required:?} capability")]
+    InsufficientCapability {
+        client: String,
+        required: Capability,
+    },
+}
+
+impl AccessDenied {
+    /// Name of the authenticated client, if the token was valid
+    pub fn client(&self) -> Option<&str> {
+        match self {
+            Self::InsufficientCapability { client, .. } => Some(client),
+            _ => None,
+        }
+    }
+}
+
+impl From<AccessDenied> for Status {
+    fn from(denied: AccessDenied) -> Self {
+        match denied {
+            AccessDenied::MissingToken | AccessDenied::InvalidToken => {
+                Status::unauthenticated(format!("Access denied: {}", denied))
+            }
+            _ => Status::permission_denied(format!("Access denied: {}", denied)),
+        }
+    }
+}
+
+/// Checks requests against the configured peer and token policy
+pub struct Authorizer {
+    config: RwLock<IpcAuthConfig>,
+    daemon_uid: Option<u32>,
+}
+
+impl Authorizer {
+    /// Create an authorizer for the given policy
+    pub fn new(config: IpcAuthConfig) -> Self {
+        Self {
+            config: RwLock::new(config),
+            daemon_uid: current_uid(),
+        }
+    }
+
+    /// Replace the policy (e.g. after a config reload)
+    pub fn reload(&self, config: IpcAuthConfig) {
+        *self.config.write().unwrap() = config;
+    }
+
+    /// Whether client tokens are required
+    pub fn tokens_required(&self) -> bool {
+        !self.config.read().unwrap().clients.is_empty()
+    }
+
+    /// Authorize a request, returning the client name if a token was used
+    pub fn authorize(
+        &self,
+        peer: Option<&PeerCredentials>,
+        token: Option<&str>,
+        required: Capability,
+    ) -> std::result::Result<Option<String>, AccessDenied> {
+        let config = self.config.read().unwrap();
+
+        match peer {
+            Some(peer) if !self.peer_allowed(&config, peer.uid) => {
+                return Err(AccessDenied::PeerNotAllowed(peer.uid));
+            }
+            // Without peer credentials only a valid token identifies the caller
+            None if cfg!(unix) && config.clients.is_empty() => {
+                return Err(AccessDenied::UnknownPeer);
+            }
+            _ => {}
+        }
+
+        if config.clients.is_empty() {
+            return Ok(None);
+        }
+
+        let token = token.ok_or(AccessDenied::MissingToken)?;
+        let client = config
+            .clients
+            .iter()
+            .find(|client| constant_time_eq(client.token.as_bytes(), token.as_bytes()))
+            .ok_or(AccessDenied::InvalidToken)?;
+
+        if client.capability < required {
+            return Err(AccessDenied::InsufficientCapability {
+                client: client.name.clone(),
+                required,
+            });
+        }
+
+        Ok(Some(client.name.clone()))
+    }
+
+    /// Root, the daemon's own user and explicitly listed users may connect
+    fn peer_allowed(&self, config: &IpcAuthConfig, uid: u32) -> bool {
+        uid == 0 || Some(uid) == self.daemon_uid || config.allowed_uids.contains(&uid)
+    }
+}
+
+/// Client-side interceptor attaching the token to every request
+#[derive(Clone, Default)]
+pub struct TokenInterceptor {
+    token: Option<MetadataValue<Ascii>>,
+}
+
+impl TokenInterceptor {
+    /// Attach `token` to requests; tokens that aren't valid metadata are dropped
+    pub fn new(token: Option<&str>) -> Self {
+        Self {
+            token: token.and_then(|token| format!("Bearer {}", token).parse().ok()),
+        }
+    }
+}
+
+impl Interceptor for TokenInterceptor {
+    fn call(
+        &mut self,
+        mut request: tonic::Request<()>,
+    ) -> std::result::Result<tonic::Request<()>, Status> {
+        if let Some(token) = &self.token {
+            request
+                .metadata_mut()
+                .insert(AUTHORIZATION_METADATA, token.clone());
+        }
+        Ok(request)
+    }
+}
+
+/// Extract the bearer token from request metadata
+pub fn request_token<T>(request: &tonic::Request<T>) -> Option<&str> {
+    request
+        .metadata()
+        .get(AUTHORIZATION_METADATA)
+        .and_then(|value| value.to_str().ok())
+        .and_then(|value| value.strip_prefix("Bearer "))
+}
+
+/// Compare two byte strings without short-circuiting on the first mismatch
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(unix)]
+fn current_uid() -> Option<u32> {
+    // SAFETY: geteuid has no preconditions and cannot fail
+    Some(unsafe { libc::geteuid() })
+}
+
+#[cfg(not(unix))]
+fn current_uid() -> Option<u32> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::IpcClientConfig;
+
+    fn config() -> IpcAuthConfig {
+        IpcAuthConfig {
+            allowed_uids: vec![4242],
+            clients: vec![
+                IpcClientConfig {
+                    name: "monitor".to_string(),
+                    token: "status-token".to_string(),
+                    capability: Capability::Status,
+                },
+                IpcClientConfig {
+                    name: "wallet".to_string(),
+                    token: "sign-token".to_string(),
+                    capability: Capability::Sign,
+                },
+            ],
+        }
+    }
+
+    fn peer(uid: u32) -> PeerCredentials {
+        PeerCredentials {
+            uid,
+            gid: uid,
+            pid: Some(1),
+        }
+    }
+
+    #[test]
+    fn test_peer_credentials_checked() {
+        let authorizer = Authorizer::new(IpcAuthConfig::default());
+
+        assert_eq!(
+            authorizer.authorize(Some(&peer(0)), None, Capability::Admin),
+            Ok(None)
+        );
+        assert_eq!(
+            authorizer.authorize(Some(&peer(4242)), None, Capability::Status),
+            Err(AccessDenied::PeerNotAllowed(4242))
+        );
+
+        let authorizer = Authorizer::new(config());
+        assert!(authorizer
+            .authorize(Some(&peer(4242)), Some("status-token"), Capability::Status)
+            .is_ok());
+    }
+
+    #[test]
+    fn test_token_capabilities() {
+        let authorizer = Authorizer::new(config());
+
+        assert_eq!(
+            authorizer.authorize(None, None, Capability::Status),
+            Err(AccessDenied::MissingToken)
+        );
+        assert_eq!(
+            authorizer.authorize(None, Some("bogus"), Capability::Status),
+            Err(AccessDenied::InvalidToken)
+        );
+        assert_eq!(
+            authorizer.authorize(None, Some("sign-token"), Capability::Status),
+            Ok(Some("wallet".to_string()))
+        );
+        assert_eq!(
+            authorizer.authorize(None, Some("status-token"), Capability::Sign),
+            Err(AccessDenied::InsufficientCapability {
+                client: "monitor".to_string(),
+                required: Capability::Sign,
+            })
+        );
+        assert!(matches!(
+            authorizer.authorize(None, Some("sign-token"), Capability::Approve),
+            Err(AccessDenied::InsufficientCapability { .. })
+        ));
+        assert!(matches!(
+            authorizer.authorize(None, Some("sign-token"), Capability::Admin),
+            Err(AccessDenied::InsufficientCapability { .. })
+        ));
+    }
+
+    #[test]
+    fn test_reload_replaces_policy() {
+        let authorizer = Authorizer::new(config());
+        assert!(authorizer.tokens_required());
+
+        authorizer.reload(IpcAuthConfig::default());
+        assert!(!authorizer.tokens_required());
+        assert_eq!(
+            authorizer.authorize(Some(&peer(0)), None, Capability::Admin),
+            Ok(None)
+        );
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_unknown_peer_denied() {
+        let authorizer = Authorizer::new(IpcAuthConfig::default());
+        assert_eq!(
+            authorizer.authorize(None, None, Capability::Admin),
+            Err(AccessDenied::UnknownPeer)
+        );
+
+        // A valid token still identifies the caller
+        let authorizer = Authorizer::new(config());
+        assert_eq!(
+            authorizer.authorize(None, Some("sign-token"), Capability::Sign),
+            Ok(Some("wallet".to_string()))
+        );
+    }
+}
@@ -1,50 +1,103 @@
 //! IPC client implementation
 
 use std::path::PathBuf;
-use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
 
-use crate::error::Result;
+use tonic::codegen::{BoxFuture, Context, Poll, Service};
+use tonic::service::interceptor::InterceptedService;
+use tonic::transport::{Channel, Endpoint, Uri};
 
-use super::connection::{IpcClientTransport, PlatformClient};
-use super::types::{IpcRequest, IpcResponse};
+use crate::error::{DaemonError, Result};
+
+use super::auth::{TokenInterceptor, IPC_TOKEN_ENV};
+use super::connection::{IpcClientTransport, IpcConnection, PlatformClient};
+use super::proto::signer_service_client::SignerServiceClient;
+use super::proto::PingRequest;
+
+/// Typed gRPC client connected to the daemon
+pub type SignerClient = SignerServiceClient<InterceptedService<Channel, TokenInterceptor>>;
 
 /// IPC client for CLI use
 pub struct IpcClient {
     socket_path: PathBuf,
+    token: Option<String>,
 }
 
 impl IpcClient {
     /// Create a new IPC client
+    ///
+    /// The client token is taken from `SIGIL_IPC_TOKEN` if it is set.
     pub fn new(socket_path: PathBuf) -> Self {
-        Self { socket_path }
+        Self {
+            socket_path,
+            token: std::env::var(IPC_TOKEN_ENV).ok(),
+        }
     }
 
-    /// Send a request and get a response
-    pub async fn request(&self, request: &IpcRequest) -> Result<IpcResponse> {
-        let stream = PlatformClient::connect(&self.socket_path).await?;
-
-        let (reader, mut writer) = tokio::io::split(stream);
-        let mut reader = BufReader::new(reader);
-
-        // Send request
-        let json = serde_json::to_string(request)?;
-        writer.write_all(json.as_bytes()).await?;
-        writer.write_all(b"\n").await?;
-        writer.flush().await?;
+    /// Authenticate with the given client token
+    pub fn with_token(mut self, token: impl Into<String>) -> Self {
+        self.token = Some(token.into());
+        self
+    }
 
-        // Read response
-        let mut line = String::new();
-        reader.read_line(&mut line).await?;
+    /// Connect to the daemon and return a gRPC client
+    pub async fn connect(&self) -> Result<SignerClient> {
+        // The URI is required by tonic but ignored by the connector
+        let channel = Endpoint::from_static("http://sigil.local")
+            .connect_with_connector(IpcConnector {
+                socket_path: self.socket_path.clone(),
+            })
+            .await
+            .map_err(|e| {
+                // Surface the transport's own error (e.g. "Daemon not running")
+                let mut source = std::error::Error::source(&e);
+                while let Some(err) = source {
+                    if let Some(daemon_err) = err.downcast_ref::<DaemonError>() {
+                        return DaemonError::Ipc(match daemon_err {
+                            DaemonError::Ipc(msg) => msg.clone(),
+                            other => other.to_string(),
+                        });
+                    }
+                    source = err.source();
+                }
+                DaemonError::Ipc(format!("Failed to connect: {}", e))
+            })?;
 
-        let response: IpcResponse = serde_json::from_str(&line)?;
-        Ok(response)
+        Ok(SignerServiceClient::with_interceptor(
+            channel,
+            TokenInterceptor::new(self.token.as_deref()),
+        ))
     }
 
     /// Check if daemon is running
     pub async fn ping(&self) -> bool {
-        matches!(
-            self.request(&IpcRequest::Ping).await,
-            Ok(IpcResponse::Pong { .. })
-        )
+        match self.connect().await {
+            Ok(mut client) => client.ping(PingRequest {}).await.is_ok(),
+            Err(_) => false,
+        }
+    }
+}
+
+/// Connector that dials the daemon over the platform IPC transport
+#[derive(Clone)]
+struct IpcConnector {
+    socket_path: PathBuf,
+}
+
+impl Service<Uri> for IpcConnector {
+    type Response = IpcConnection<<PlatformClient as IpcClientTransport>::Stream>;
+    type Error = DaemonError;
+    type Future = BoxFuture<Self::Response, Self::Error>;
+
+    fn poll_ready(&mut self, _cx: &mut Context<'_>) -> Poll<Result<()>> {
+        Poll::Ready(Ok(()))
+    }
+
+    fn call(&mut self, _uri: Uri) -> Self::Future {
+        let socket_path = self.socket_path.clone();
+        Box::pin(async move {
+            PlatformClient::connect(&socket_path)
+                .await
+                .map(|stream| IpcConnection::new(stream, None))
+        })
     }
 }
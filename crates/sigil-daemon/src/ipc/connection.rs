@@ -1,11 +1,17 @@
 //! Platform-agnostic IPC transport abstraction
 
 use async_trait::async_trait;
+use std::io;
 use std::path::Path;
-use tokio::io::{AsyncRead, AsyncWrite};
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use tokio::io::{AsyncRead, AsyncWrite, ReadBuf};
+use tonic::transport::server::Connected;
 
 use crate::error::Result;
 
+use super::auth::{IpcConnectInfo, PeerCredentials};
+
 /// Server-side IPC transport trait
 #[async_trait]
 pub trait IpcTransport: Send + Sync {
@@ -20,6 +26,11 @@ pub trait IpcTransport: Send + Sync {
     /// Accept an incoming connection
     async fn accept(&self) -> Result<Self::Stream>;
 
+    /// Credentials of the connected peer, if the platform provides them
+    fn peer_credentials(_stream: &Self::Stream) -> Option<PeerCredentials> {
+        None
+    }
+
     /// Clean up resources (e.g., delete socket file on Unix)
     #[allow(dead_code)]
     async fn cleanup(&self) -> Result<()>;
@@ -35,6 +46,58 @@ pub trait IpcClientTransport: Send + Sync {
     async fn connect(path: &Path) -> Result<Self::Stream>;
 }
 
+/// A transport stream carrying the gRPC connection
+///
+/// Gives every platform stream the `Connected` impl tonic needs to serve it,
+/// exposing the peer credentials to handlers as [`IpcConnectInfo`].
+pub struct IpcConnection<S> {
+    stream: S,
+    peer: Option<PeerCredentials>,
+}
+
+impl<S> IpcConnection<S> {
+    /// Wrap a transport stream
+    pub fn new(stream: S, peer: Option<PeerCredentials>) -> Self {
+        Self { stream, peer }
+    }
+}
+
+impl<S> Connected for IpcConnection<S> {
+    type ConnectInfo = IpcConnectInfo;
+
+    fn connect_info(&self) -> Self::ConnectInfo {
+        IpcConnectInfo { peer: self.peer }
+    }
+}
+
+impl<S: AsyncRead + Unpin> AsyncRead for IpcConnection<S> {
+    fn poll_read(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &mut ReadBuf<'_>,
+    ) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_read(cx, buf)
+    }
+}
+
+impl<S: AsyncWrite + Unpin> AsyncWrite for IpcConnection<S> {
+    fn poll_write(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+        buf: &[u8],
+    ) -> Poll<io::Result<usize>> {
+        Pin::new(&mut self.stream).poll_write(cx, buf)
+    }
+
+    fn poll_flush(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_flush(cx)
+    }
+
+    fn poll_shutdown(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<io::Result<()>> {
+        Pin::new(&mut self.stream).poll_shutdown(cx)
+    }
+}
+
 // Platform-specific type aliases
 #[cfg(unix)]
 pub use super::unix::{UnixIpcClient as PlatformClient, UnixIpcTransport as PlatformTransport};
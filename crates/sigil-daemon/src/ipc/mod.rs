@@ -2,11 +2,15 @@
 //!
 //! Provides a platform-agnostic interface for the CLI to communicate with the daemon.
 //! Uses Unix domain sockets on Unix-like systems and named pipes on Windows.
+//!
+//! The wire protocol is the gRPC `SignerService` defined in `proto/signer.proto`.
+//! Requests are authorized by peer credentials and, when configured, per-client
+//! capability tokens (see [`auth`]).
 
+pub mod auth;
 mod client;
 mod connection;
 mod server;
-mod types;
 
 #[cfg(unix)]
 mod unix;
@@ -14,7 +18,47 @@ mod unix;
 #[cfg(windows)]
 mod windows;
 
+/// Protobuf messages and gRPC stubs generated from `proto/signer.proto`
+#[allow(clippy::all)]
+pub mod proto {
+    include!("proto/sigil.signer.v1.rs");
+}
+
 // Public API
-pub use client::IpcClient;
-pub use server::IpcServer;
-pub use types::{IpcRequest, IpcResponse};
+pub use auth::{Capability, PeerCredentials};
+pub use client::{IpcClient, SignerClient};
+pub use server::{daemon_status, error_code, IpcServer, ERROR_CODE_METADATA};
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    /// The bindings are checked in so building needs no `protoc`; this
+    /// regenerates them with tonic-build and fails if they differ. Run with
+    /// `SIGIL_REGENERATE_PROTO=1` after editing the proto to rewrite them.
+    #[test]
+    fn test_proto_bindings_match_proto() {
+        let manifest_dir = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let proto_dir = manifest_dir.join("../../proto");
+        let checked_in = manifest_dir.join("src/ipc/proto/sigil.signer.v1.rs");
+        let out_dir = tempfile::tempdir().unwrap();
+
+        std::env::set_var("PROTOC", protoc_bin_vendored::protoc_bin_path().unwrap());
+        tonic_build::configure()
+            .out_dir(out_dir.path())
+            .emit_rerun_if_changed(false)
+            .compile(&[proto_dir.join("signer.proto")], &[&proto_dir])
+            .unwrap();
+        let generated = std::fs::read_to_string(out_dir.path().join("sigil.signer.v1.rs")).unwrap();
+
+        if std::env::var_os("SIGIL_REGENERATE_PROTO").is_some() {
+            std::fs::write(&checked_in, &generated).unwrap();
+            return;
+        }
+        assert!(
+            std::fs::read_to_string(&checked_in).unwrap() == generated,
+            "{} is out of date with proto/signer.proto; rerun this test with SIGIL_REGENERATE_PROTO=1",
+            checked_in.display()
+        );
+    }
+}
@@ -0,0 +1,2290 @@
+// This file is @generated by prost-build.
+/// Ping request
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PingRequest {}
+/// Ping response
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PingResponse {
+    #[prost(string, tag = "1")]
+    pub version: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "2")]
+    pub uptime_seconds: u64,
+}
+/// Get disk status request
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetDiskStatusRequest {}
+/// Get disk status response
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetDiskStatusResponse {
+    #[prost(bool, tag = "1")]
+    pub detected: bool,
+    #[prost(string, tag = "2")]
+    pub child_id: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "3")]
+    pub presigs_remaining: u32,
+    #[prost(uint32, tag = "4")]
+    pub presigs_total: u32,
+    #[prost(uint32, tag = "5")]
+    pub days_until_expiry: u32,
+    #[prost(bool, tag = "6")]
+    pub is_valid: bool,
+    #[prost(string, tag = "7")]
+    pub status_message: ::prost::alloc::string::String,
+    /// Every inserted disk; the fields above describe the disk only when
+    /// exactly one is inserted
+    #[prost(message, repeated, tag = "8")]
+    pub disks: ::prost::alloc::vec::Vec<DiskInfo>,
+}
+/// Status of one inserted disk
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DiskInfo {
+    #[prost(string, tag = "1")]
+    pub path: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub child_id: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "3")]
+    pub presigs_remaining: u32,
+    #[prost(uint32, tag = "4")]
+    pub presigs_total: u32,
+    #[prost(uint32, tag = "5")]
+    pub days_until_expiry: u32,
+    #[prost(bool, tag = "6")]
+    pub is_valid: bool,
+    #[prost(string, tag = "7")]
+    pub status_message: ::prost::alloc::string::String,
+}
+/// Sign request
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignRequest {
+    /// Message hash to sign (32 bytes, hex encoded)
+    #[prost(string, tag = "1")]
+    pub message_hash: ::prost::alloc::string::String,
+    /// Chain ID for logging
+    #[prost(uint32, tag = "2")]
+    pub chain_id: u32,
+    /// Human-readable description
+    #[prost(string, tag = "3")]
+    pub description: ::prost::alloc::string::String,
+    /// Optional client-chosen key; retrying with the same key returns the
+    /// original signature instead of consuming another presig
+    #[prost(string, tag = "4")]
+    pub idempotency_key: ::prost::alloc::string::String,
+    /// Optional child to sign with (hex child ID or unique prefix); required
+    /// when more than one disk is inserted
+    #[prost(string, tag = "5")]
+    pub child_id: ::prost::alloc::string::String,
+    /// Optional value the transaction moves, in the chain's smallest unit
+    /// (decimal); shown to approvers, but not trusted by approval thresholds
+    #[prost(string, tag = "6")]
+    pub value: ::prost::alloc::string::String,
+    /// Optional unsigned EVM transaction (hex) whose signing hash is
+    /// message_hash; approval thresholds compare its native value, and treat
+    /// contract calls and requests without one as exceeding them
+    #[prost(string, tag = "7")]
+    pub transaction: ::prost::alloc::string::String,
+}
+/// Sign response
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignResponse {
+    /// ECDSA signature (64 bytes, hex encoded)
+    #[prost(string, tag = "2")]
+    pub signature: ::prost::alloc::string::String,
+    /// v, r, s components for Ethereum (v is 0 until recovery ids are tracked)
+    #[prost(uint32, tag = "3")]
+    pub v: u32,
+    #[prost(string, tag = "4")]
+    pub r: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub s: ::prost::alloc::string::String,
+    /// Presig index used
+    #[prost(uint32, tag = "6")]
+    pub presig_index: u32,
+    /// zkVM proof hash
+    #[prost(string, tag = "7")]
+    pub proof_hash: ::prost::alloc::string::String,
+    /// True when this is the earlier result for a retried request
+    #[prost(bool, tag = "9")]
+    pub cached: bool,
+    /// Set instead of a signature when the request awaits approval
+    #[prost(message, optional, tag = "10")]
+    pub pending_approval: ::core::option::Option<PendingApproval>,
+}
+/// A signing request held until a human approves it
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PendingApproval {
+    #[prost(string, tag = "1")]
+    pub approval_id: ::prost::alloc::string::String,
+    /// Unix time after which the request expires unapproved
+    #[prost(uint64, tag = "2")]
+    pub expires_at: u64,
+}
+/// Batch sign request
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignBatchRequest {
+    /// Items are signed in order with consecutive presigs; the batch fails
+    /// as a whole if any item fails
+    #[prost(message, repeated, tag = "1")]
+    pub items: ::prost::alloc::vec::Vec<SignRequest>,
+    /// Child for every item that doesn't set its own
+    #[prost(string, tag = "2")]
+    pub child_id: ::prost::alloc::string::String,
+}
+/// Batch sign response
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignBatchResponse {
+    /// One result per request item, in request order
+    #[prost(message, repeated, tag = "1")]
+    pub results: ::prost::alloc::vec::Vec<SignResponse>,
+    /// Set instead of results when the batch awaits approval
+    #[prost(message, optional, tag = "2")]
+    pub pending_approval: ::core::option::Option<PendingApproval>,
+}
+/// Progress of a streamed signing operation
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignProgress {
+    #[prost(oneof = "sign_progress::Update", tags = "1, 2, 3")]
+    pub update: ::core::option::Option<sign_progress::Update>,
+}
+/// Nested message and enum types in `SignProgress`.
+pub mod sign_progress {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Update {
+        /// Always the first message
+        #[prost(message, tag = "1")]
+        Started(super::SignStarted),
+        #[prost(message, tag = "2")]
+        Stage(super::SignStage),
+        /// Always the last message of a successful operation
+        #[prost(message, tag = "3")]
+        Result(super::SignResponse),
+    }
+}
+/// A streamed signing operation was accepted
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignStarted {
+    /// Identifies the operation to CancelSign
+    #[prost(string, tag = "1")]
+    pub operation_id: ::prost::alloc::string::String,
+}
+/// A streamed signing operation entered a new stage
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignStage {
+    /// reading_disk, checking_policy, signing, proving or writing_disk; the
+    /// presig is committed once writing_disk starts
+    #[prost(string, tag = "1")]
+    pub stage: ::prost::alloc::string::String,
+}
+/// Cancel sign request
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CancelSignRequest {
+    #[prost(string, tag = "1")]
+    pub operation_id: ::prost::alloc::string::String,
+}
+/// Cancel sign response
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct CancelSignResponse {
+    /// False if the cancellation came too late: the signature was already
+    /// committed and will still be returned on the stream
+    #[prost(bool, tag = "1")]
+    pub cancelled: bool,
+}
+/// Update tx hash request
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateTxHashRequest {
+    #[prost(uint32, tag = "1")]
+    pub presig_index: u32,
+    #[prost(string, tag = "2")]
+    pub tx_hash: ::prost::alloc::string::String,
+    /// Optional child whose usage log to update
+    #[prost(string, tag = "3")]
+    pub child_id: ::prost::alloc::string::String,
+}
+/// Update tx hash response
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UpdateTxHashResponse {}
+/// Get presig count request
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPresigCountRequest {
+    /// Optional child to count (hex child ID or unique prefix); required
+    /// when more than one disk is inserted
+    #[prost(string, tag = "1")]
+    pub child_id: ::prost::alloc::string::String,
+}
+/// Get presig count response
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetPresigCountResponse {
+    #[prost(uint32, tag = "1")]
+    pub remaining: u32,
+    #[prost(uint32, tag = "2")]
+    pub total: u32,
+}
+/// Query usage log request; empty or zero fields don't filter
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryUsageLogRequest {
+    /// Optional child (hex child ID or unique prefix); required when more
+    /// than one disk is inserted
+    #[prost(string, tag = "1")]
+    pub child_id: ::prost::alloc::string::String,
+    /// Unix timestamps; since is inclusive, until exclusive
+    #[prost(uint64, tag = "2")]
+    pub since: u64,
+    #[prost(uint64, tag = "3")]
+    pub until: u64,
+    /// Decimal chain ID; FROST signatures are logged with chain 0
+    #[prost(string, tag = "4")]
+    pub chain_id: ::prost::alloc::string::String,
+    /// "pending" or "filled"
+    #[prost(string, tag = "5")]
+    pub tx_hash_status: ::prost::alloc::string::String,
+    /// Case-insensitive text to find in the description
+    #[prost(string, tag = "6")]
+    pub search: ::prost::alloc::string::String,
+    /// Matching entries to skip, and at most how many to return (default 50)
+    #[prost(uint32, tag = "7")]
+    pub offset: u32,
+    #[prost(uint32, tag = "8")]
+    pub limit: u32,
+}
+/// Query usage log response
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct QueryUsageLogResponse {
+    /// Full child ID (hex encoded)
+    #[prost(string, tag = "1")]
+    pub child_id: ::prost::alloc::string::String,
+    /// Newest first
+    #[prost(message, repeated, tag = "2")]
+    pub entries: ::prost::alloc::vec::Vec<UsageLogEntry>,
+    /// Matching entries before pagination
+    #[prost(uint32, tag = "3")]
+    pub total: u32,
+}
+/// One signature from a child's usage log
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct UsageLogEntry {
+    #[prost(uint32, tag = "1")]
+    pub presig_index: u32,
+    #[prost(uint64, tag = "2")]
+    pub timestamp: u64,
+    #[prost(string, tag = "3")]
+    pub message_hash: ::prost::alloc::string::String,
+    #[prost(string, tag = "4")]
+    pub signature: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "5")]
+    pub chain_id: u32,
+    /// Empty until the transaction hash has been filled in
+    #[prost(string, tag = "6")]
+    pub tx_hash: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub proof_hash: ::prost::alloc::string::String,
+    #[prost(string, tag = "8")]
+    pub description: ::prost::alloc::string::String,
+}
+/// List children request
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListChildrenRequest {}
+/// List children response
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListChildrenResponse {
+    #[prost(message, repeated, tag = "1")]
+    pub children: ::prost::alloc::vec::Vec<ChildInfo>,
+}
+/// Child information
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ChildInfo {
+    #[prost(string, tag = "1")]
+    pub child_id: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "2")]
+    pub presigs_remaining: u32,
+    #[prost(uint32, tag = "3")]
+    pub presigs_total: u32,
+    #[prost(string, tag = "4")]
+    pub status: ::prost::alloc::string::String,
+}
+/// Watch disk request
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WatchDiskRequest {}
+/// Disk event
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DiskEvent {
+    #[prost(oneof = "disk_event::Event", tags = "1, 2, 3")]
+    pub event: ::core::option::Option<disk_event::Event>,
+}
+/// Nested message and enum types in `DiskEvent`.
+pub mod disk_event {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Event {
+        #[prost(message, tag = "1")]
+        Inserted(super::DiskInserted),
+        #[prost(message, tag = "2")]
+        Removed(super::DiskRemoved),
+        #[prost(message, tag = "3")]
+        ValidationFailed(super::DiskValidationFailed),
+    }
+}
+/// Disk inserted event
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DiskInserted {
+    #[prost(string, tag = "1")]
+    pub path: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub child_id: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "3")]
+    pub presigs_remaining: u32,
+    #[prost(uint32, tag = "4")]
+    pub days_until_expiry: u32,
+}
+/// Disk removed event
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DiskRemoved {
+    #[prost(string, tag = "1")]
+    pub path: ::prost::alloc::string::String,
+}
+/// Disk validation failed event
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DiskValidationFailed {
+    #[prost(string, tag = "1")]
+    pub path: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub reason: ::prost::alloc::string::String,
+}
+/// Watch events request
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct WatchEventsRequest {}
+/// Daemon event
+///
+/// A disk_inserted event for the current disk (if any) is sent first.
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DaemonEvent {
+    /// Unix timestamp the event was raised
+    #[prost(uint64, tag = "1")]
+    pub timestamp: u64,
+    #[prost(oneof = "daemon_event::Event", tags = "2, 3, 4, 5, 6, 7, 8, 9, 10")]
+    pub event: ::core::option::Option<daemon_event::Event>,
+}
+/// Nested message and enum types in `DaemonEvent`.
+pub mod daemon_event {
+    #[allow(clippy::derive_partial_eq_without_eq)]
+    #[derive(Clone, PartialEq, ::prost::Oneof)]
+    pub enum Event {
+        #[prost(message, tag = "2")]
+        DiskInserted(super::DiskInserted),
+        #[prost(message, tag = "3")]
+        DiskRemoved(super::DiskRemoved),
+        #[prost(message, tag = "4")]
+        DiskValidationFailed(super::DiskValidationFailed),
+        #[prost(message, tag = "5")]
+        PresigConsumed(super::PresigConsumed),
+        #[prost(message, tag = "6")]
+        LowPresigWarning(super::LowPresigWarning),
+        #[prost(message, tag = "7")]
+        ExpiryWarning(super::ExpiryWarning),
+        #[prost(message, tag = "8")]
+        ApprovalRequested(super::ApprovalRequested),
+        #[prost(message, tag = "9")]
+        ApprovalResolved(super::ApprovalResolved),
+        #[prost(message, tag = "10")]
+        SignatureNotLanded(super::SignatureNotLanded),
+    }
+}
+/// A presignature was consumed by a signing operation
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct PresigConsumed {
+    #[prost(string, tag = "1")]
+    pub child_id: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "2")]
+    pub presig_index: u32,
+    #[prost(uint32, tag = "3")]
+    pub presigs_remaining: u32,
+}
+/// Remaining presignatures fell to or below the warning threshold
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct LowPresigWarning {
+    #[prost(string, tag = "1")]
+    pub child_id: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "2")]
+    pub presigs_remaining: u32,
+    #[prost(uint32, tag = "3")]
+    pub threshold: u32,
+}
+/// The disk is within its expiry warning period
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExpiryWarning {
+    #[prost(string, tag = "1")]
+    pub child_id: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "2")]
+    pub days_until_expiry: u32,
+}
+/// A signing request is waiting for a human decision
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ApprovalRequested {
+    #[prost(string, tag = "1")]
+    pub approval_id: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub requested_by: ::prost::alloc::string::String,
+    /// Descriptions of the requested signatures
+    #[prost(string, repeated, tag = "3")]
+    pub descriptions: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+    #[prost(uint64, tag = "4")]
+    pub expires_at: u64,
+}
+/// An approval left the pending state or was signed
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ApprovalResolved {
+    #[prost(string, tag = "1")]
+    pub approval_id: ::prost::alloc::string::String,
+    /// approved, signed, rejected or expired
+    #[prost(string, tag = "2")]
+    pub status: ::prost::alloc::string::String,
+}
+/// A logged signature never showed up in a mined transaction
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignatureNotLanded {
+    #[prost(string, tag = "1")]
+    pub child_id: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "2")]
+    pub presig_index: u32,
+    #[prost(uint32, tag = "3")]
+    pub chain_id: u32,
+    /// Message hash that was signed (hex)
+    #[prost(string, tag = "4")]
+    pub message_hash: ::prost::alloc::string::String,
+    /// Unix timestamp the signature was made
+    #[prost(uint64, tag = "5")]
+    pub signed_at: u64,
+}
+/// Import agent shard request
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ImportAgentShardRequest {
+    /// Agent master shard (32 bytes, hex encoded)
+    #[prost(string, tag = "1")]
+    pub agent_shard_hex: ::prost::alloc::string::String,
+}
+/// Import agent shard response
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ImportAgentShardResponse {}
+/// Import child shares request
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ImportChildSharesRequest {
+    /// JSON-encoded AgentChildData
+    #[prost(string, tag = "1")]
+    pub shares_json: ::prost::alloc::string::String,
+    /// Replace existing shares if true
+    #[prost(bool, tag = "2")]
+    pub replace: bool,
+}
+/// Import child shares response
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ImportChildSharesResponse {}
+/// Export audit bundle request
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExportAuditBundleRequest {
+    /// Child ID (hex encoded, full or short prefix)
+    #[prost(string, tag = "1")]
+    pub child_id: ::prost::alloc::string::String,
+}
+/// Export audit bundle response
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ExportAuditBundleResponse {
+    /// Full child ID (hex encoded)
+    #[prost(string, tag = "1")]
+    pub child_id: ::prost::alloc::string::String,
+    #[prost(uint32, tag = "2")]
+    pub record_count: u32,
+    #[prost(string, tag = "3")]
+    pub bundle_json: ::prost::alloc::string::String,
+    #[prost(string, repeated, tag = "4")]
+    pub qr_chunks: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+/// List approvals request
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListApprovalsRequest {
+    /// Also list approvals that are no longer pending
+    #[prost(bool, tag = "1")]
+    pub include_decided: bool,
+}
+/// List approvals response
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ListApprovalsResponse {
+    /// Newest first
+    #[prost(message, repeated, tag = "1")]
+    pub approvals: ::prost::alloc::vec::Vec<Approval>,
+}
+/// Get approval request
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct GetApprovalRequest {
+    #[prost(string, tag = "1")]
+    pub approval_id: ::prost::alloc::string::String,
+}
+/// Decide approval request
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DecideApprovalRequest {
+    #[prost(string, tag = "1")]
+    pub approval_id: ::prost::alloc::string::String,
+    /// True to approve, false to reject
+    #[prost(bool, tag = "2")]
+    pub approve: bool,
+    /// Person deciding, recorded in the approval trail
+    #[prost(string, tag = "3")]
+    pub approver: ::prost::alloc::string::String,
+    /// Optional note recorded with the decision
+    #[prost(string, tag = "4")]
+    pub reason: ::prost::alloc::string::String,
+}
+/// A signing request held for approval
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct Approval {
+    #[prost(string, tag = "1")]
+    pub approval_id: ::prost::alloc::string::String,
+    /// pending, approved, signing, signed, rejected or expired
+    #[prost(string, tag = "2")]
+    pub status: ::prost::alloc::string::String,
+    /// Requested signatures, as submitted
+    #[prost(message, repeated, tag = "3")]
+    pub items: ::prost::alloc::vec::Vec<SignRequest>,
+    /// Client or uid that submitted the request
+    #[prost(string, tag = "4")]
+    pub requested_by: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "5")]
+    pub created_at: u64,
+    #[prost(uint64, tag = "6")]
+    pub expires_at: u64,
+    /// Client or uid that decided, and the person it named
+    #[prost(string, tag = "7")]
+    pub decided_by: ::prost::alloc::string::String,
+    #[prost(string, tag = "8")]
+    pub approver: ::prost::alloc::string::String,
+    #[prost(string, tag = "9")]
+    pub reason: ::prost::alloc::string::String,
+    #[prost(uint64, tag = "10")]
+    pub decided_at: u64,
+    /// Signatures, once signed
+    #[prost(message, repeated, tag = "11")]
+    pub results: ::prost::alloc::vec::Vec<SignResponse>,
+    /// Why signing an approved request failed (it is retried on the next
+    /// matching Sign or SignBatch)
+    #[prost(string, tag = "12")]
+    pub error: ::prost::alloc::string::String,
+}
+/// Generated client implementations.
+pub mod signer_service_client {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    use tonic::codegen::http::Uri;
+    /// Service for signing operations
+    #[derive(Debug, Clone)]
+    pub struct SignerServiceClient<T> {
+        inner: tonic::client::Grpc<T>,
+    }
+    impl SignerServiceClient<tonic::transport::Channel> {
+        /// Attempt to create a new client by connecting to a given endpoint.
+        pub async fn connect<D>(dst: D) -> Result<Self, tonic::transport::Error>
+        where
+            D: TryInto<tonic::transport::Endpoint>,
+            D::Error: Into<StdError>,
+        {
+            let conn = tonic::transport::Endpoint::new(dst)?.connect().await?;
+            Ok(Self::new(conn))
+        }
+    }
+    impl<T> SignerServiceClient<T>
+    where
+        T: tonic::client::GrpcService<tonic::body::BoxBody>,
+        T::Error: Into<StdError>,
+        T::ResponseBody: Body<Data = Bytes> + Send + 'static,
+        <T::ResponseBody as Body>::Error: Into<StdError> + Send,
+    {
+        pub fn new(inner: T) -> Self {
+            let inner = tonic::client::Grpc::new(inner);
+            Self { inner }
+        }
+        pub fn with_origin(inner: T, origin: Uri) -> Self {
+            let inner = tonic::client::Grpc::with_origin(inner, origin);
+            Self { inner }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> SignerServiceClient<InterceptedService<T, F>>
+        where
+            F: tonic::service::Interceptor,
+            T::ResponseBody: Default,
+            T: tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+                Response = http::Response<
+                    <T as tonic::client::GrpcService<tonic::body::BoxBody>>::ResponseBody,
+                >,
+            >,
+            <T as tonic::codegen::Service<
+                http::Request<tonic::body::BoxBody>,
+            >>::Error: Into<StdError> + Send + Sync,
+        {
+            SignerServiceClient::new(InterceptedService::new(inner, interceptor))
+        }
+        /// Compress requests with the given encoding.
+        ///
+        /// This requires the server to support it otherwise it might respond with an
+        /// error.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.send_compressed(encoding);
+            self
+        }
+        /// Enable decompressing responses.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.inner = self.inner.accept_compressed(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_decoding_message_size(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.inner = self.inner.max_encoding_message_size(limit);
+            self
+        }
+        /// Check daemon health
+        pub async fn ping(
+            &mut self,
+            request: impl tonic::IntoRequest<super::PingRequest>,
+        ) -> std::result::Result<tonic::Response<super::PingResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sigil.signer.v1.SignerService/Ping",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("sigil.signer.v1.SignerService", "Ping"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Get disk status
+        pub async fn get_disk_status(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetDiskStatusRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetDiskStatusResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sigil.signer.v1.SignerService/GetDiskStatus",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("sigil.signer.v1.SignerService", "GetDiskStatus"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// Sign a message
+        pub async fn sign(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SignRequest>,
+        ) -> std::result::Result<tonic::Response<super::SignResponse>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sigil.signer.v1.SignerService/Sign",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("sigil.signer.v1.SignerService", "Sign"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Sign several messages atomically with a single disk write
+        pub async fn sign_batch(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SignBatchRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SignBatchResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sigil.signer.v1.SignerService/SignBatch",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("sigil.signer.v1.SignerService", "SignBatch"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Sign a message, streaming its progress before the result; closing the
+        /// stream before the signature is committed cancels it
+        pub async fn sign_stream(
+            &mut self,
+            request: impl tonic::IntoRequest<super::SignRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::SignProgress>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sigil.signer.v1.SignerService/SignStream",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("sigil.signer.v1.SignerService", "SignStream"));
+            self.inner.server_streaming(req, path, codec).await
+        }
+        /// Cancel a streamed signing operation
+        pub async fn cancel_sign(
+            &mut self,
+            request: impl tonic::IntoRequest<super::CancelSignRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CancelSignResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sigil.signer.v1.SignerService/CancelSign",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("sigil.signer.v1.SignerService", "CancelSign"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Update transaction hash after broadcast
+        pub async fn update_tx_hash(
+            &mut self,
+            request: impl tonic::IntoRequest<super::UpdateTxHashRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::UpdateTxHashResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sigil.signer.v1.SignerService/UpdateTxHash",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("sigil.signer.v1.SignerService", "UpdateTxHash"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// Get presig count
+        pub async fn get_presig_count(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetPresigCountRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetPresigCountResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sigil.signer.v1.SignerService/GetPresigCount",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("sigil.signer.v1.SignerService", "GetPresigCount"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// Query a child's usage log, newest first
+        pub async fn query_usage_log(
+            &mut self,
+            request: impl tonic::IntoRequest<super::QueryUsageLogRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::QueryUsageLogResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sigil.signer.v1.SignerService/QueryUsageLog",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("sigil.signer.v1.SignerService", "QueryUsageLog"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// List stored children
+        pub async fn list_children(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListChildrenRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListChildrenResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sigil.signer.v1.SignerService/ListChildren",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("sigil.signer.v1.SignerService", "ListChildren"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// Stream disk events (insertion/removal)
+        pub async fn watch_disk(
+            &mut self,
+            request: impl tonic::IntoRequest<super::WatchDiskRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::DiskEvent>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sigil.signer.v1.SignerService/WatchDisk",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("sigil.signer.v1.SignerService", "WatchDisk"));
+            self.inner.server_streaming(req, path, codec).await
+        }
+        /// Stream disk and signing events for as long as the client stays connected
+        pub async fn watch_events(
+            &mut self,
+            request: impl tonic::IntoRequest<super::WatchEventsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::DaemonEvent>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sigil.signer.v1.SignerService/WatchEvents",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("sigil.signer.v1.SignerService", "WatchEvents"));
+            self.inner.server_streaming(req, path, codec).await
+        }
+        /// Import the agent master shard
+        pub async fn import_agent_shard(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ImportAgentShardRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ImportAgentShardResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sigil.signer.v1.SignerService/ImportAgentShard",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("sigil.signer.v1.SignerService", "ImportAgentShard"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// Import child presignature shares
+        pub async fn import_child_shares(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ImportChildSharesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ImportChildSharesResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sigil.signer.v1.SignerService/ImportChildShares",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("sigil.signer.v1.SignerService", "ImportChildShares"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// Export the agent-side audit log for a child as a reconciliation bundle
+        pub async fn export_audit_bundle(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ExportAuditBundleRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ExportAuditBundleResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sigil.signer.v1.SignerService/ExportAuditBundle",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("sigil.signer.v1.SignerService", "ExportAuditBundle"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// List signing requests held for approval
+        pub async fn list_approvals(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ListApprovalsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListApprovalsResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sigil.signer.v1.SignerService/ListApprovals",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("sigil.signer.v1.SignerService", "ListApprovals"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+        /// Get one approval, including its signatures once signed
+        pub async fn get_approval(
+            &mut self,
+            request: impl tonic::IntoRequest<super::GetApprovalRequest>,
+        ) -> std::result::Result<tonic::Response<super::Approval>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sigil.signer.v1.SignerService/GetApproval",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("sigil.signer.v1.SignerService", "GetApproval"));
+            self.inner.unary(req, path, codec).await
+        }
+        /// Approve or reject a pending signing request
+        pub async fn decide_approval(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DecideApprovalRequest>,
+        ) -> std::result::Result<tonic::Response<super::Approval>, tonic::Status> {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::new(
+                        tonic::Code::Unknown,
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic::codec::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/sigil.signer.v1.SignerService/DecideApproval",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("sigil.signer.v1.SignerService", "DecideApproval"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
+    }
+}
+/// Generated server implementations.
+pub mod signer_service_server {
+    #![allow(unused_variables, dead_code, missing_docs, clippy::let_unit_value)]
+    use tonic::codegen::*;
+    /// Generated trait containing gRPC methods that should be implemented for use with SignerServiceServer.
+    #[async_trait]
+    pub trait SignerService: Send + Sync + 'static {
+        /// Check daemon health
+        async fn ping(
+            &self,
+            request: tonic::Request<super::PingRequest>,
+        ) -> std::result::Result<tonic::Response<super::PingResponse>, tonic::Status>;
+        /// Get disk status
+        async fn get_disk_status(
+            &self,
+            request: tonic::Request<super::GetDiskStatusRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetDiskStatusResponse>,
+            tonic::Status,
+        >;
+        /// Sign a message
+        async fn sign(
+            &self,
+            request: tonic::Request<super::SignRequest>,
+        ) -> std::result::Result<tonic::Response<super::SignResponse>, tonic::Status>;
+        /// Sign several messages atomically with a single disk write
+        async fn sign_batch(
+            &self,
+            request: tonic::Request<super::SignBatchRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::SignBatchResponse>,
+            tonic::Status,
+        >;
+        /// Server streaming response type for the SignStream method.
+        type SignStreamStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::SignProgress, tonic::Status>,
+            >
+            + Send
+            + 'static;
+        /// Sign a message, streaming its progress before the result; closing the
+        /// stream before the signature is committed cancels it
+        async fn sign_stream(
+            &self,
+            request: tonic::Request<super::SignRequest>,
+        ) -> std::result::Result<tonic::Response<Self::SignStreamStream>, tonic::Status>;
+        /// Cancel a streamed signing operation
+        async fn cancel_sign(
+            &self,
+            request: tonic::Request<super::CancelSignRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::CancelSignResponse>,
+            tonic::Status,
+        >;
+        /// Update transaction hash after broadcast
+        async fn update_tx_hash(
+            &self,
+            request: tonic::Request<super::UpdateTxHashRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::UpdateTxHashResponse>,
+            tonic::Status,
+        >;
+        /// Get presig count
+        async fn get_presig_count(
+            &self,
+            request: tonic::Request<super::GetPresigCountRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::GetPresigCountResponse>,
+            tonic::Status,
+        >;
+        /// Query a child's usage log, newest first
+        async fn query_usage_log(
+            &self,
+            request: tonic::Request<super::QueryUsageLogRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::QueryUsageLogResponse>,
+            tonic::Status,
+        >;
+        /// List stored children
+        async fn list_children(
+            &self,
+            request: tonic::Request<super::ListChildrenRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListChildrenResponse>,
+            tonic::Status,
+        >;
+        /// Server streaming response type for the WatchDisk method.
+        type WatchDiskStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::DiskEvent, tonic::Status>,
+            >
+            + Send
+            + 'static;
+        /// Stream disk events (insertion/removal)
+        async fn watch_disk(
+            &self,
+            request: tonic::Request<super::WatchDiskRequest>,
+        ) -> std::result::Result<tonic::Response<Self::WatchDiskStream>, tonic::Status>;
+        /// Server streaming response type for the WatchEvents method.
+        type WatchEventsStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::DaemonEvent, tonic::Status>,
+            >
+            + Send
+            + 'static;
+        /// Stream disk and signing events for as long as the client stays connected
+        async fn watch_events(
+            &self,
+            request: tonic::Request<super::WatchEventsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::WatchEventsStream>,
+            tonic::Status,
+        >;
+        /// Import the agent master shard
+        async fn import_agent_shard(
+            &self,
+            request: tonic::Request<super::ImportAgentShardRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ImportAgentShardResponse>,
+            tonic::Status,
+        >;
+        /// Import child presignature shares
+        async fn import_child_shares(
+            &self,
+            request: tonic::Request<super::ImportChildSharesRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ImportChildSharesResponse>,
+            tonic::Status,
+        >;
+        /// Export the agent-side audit log for a child as a reconciliation bundle
+        async fn export_audit_bundle(
+            &self,
+            request: tonic::Request<super::ExportAuditBundleRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ExportAuditBundleResponse>,
+            tonic::Status,
+        >;
+        /// List signing requests held for approval
+        async fn list_approvals(
+            &self,
+            request: tonic::Request<super::ListApprovalsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ListApprovalsResponse>,
+            tonic::Status,
+        >;
+        /// Get one approval, including its signatures once signed
+        async fn get_approval(
+            &self,
+            request: tonic::Request<super::GetApprovalRequest>,
+        ) -> std::result::Result<tonic::Response<super::Approval>, tonic::Status>;
+        /// Approve or reject a pending signing request
+        async fn decide_approval(
+            &self,
+            request: tonic::Request<super::DecideApprovalRequest>,
+        ) -> std::result::Result<tonic::Response<super::Approval>, tonic::Status>;
+    }
+    /// Service for signing operations
+    #[derive(Debug)]
+    pub struct SignerServiceServer<T: SignerService> {
+        inner: _Inner<T>,
+        accept_compression_encodings: EnabledCompressionEncodings,
+        send_compression_encodings: EnabledCompressionEncodings,
+        max_decoding_message_size: Option<usize>,
+        max_encoding_message_size: Option<usize>,
+    }
+    struct _Inner<T>(Arc<T>);
+    impl<T: SignerService> SignerServiceServer<T> {
+        pub fn new(inner: T) -> Self {
+            Self::from_arc(Arc::new(inner))
+        }
+        pub fn from_arc(inner: Arc<T>) -> Self {
+            let inner = _Inner(inner);
+            Self {
+                inner,
+                accept_compression_encodings: Default::default(),
+                send_compression_encodings: Default::default(),
+                max_decoding_message_size: None,
+                max_encoding_message_size: None,
+            }
+        }
+        pub fn with_interceptor<F>(
+            inner: T,
+            interceptor: F,
+        ) -> InterceptedService<Self, F>
+        where
+            F: tonic::service::Interceptor,
+        {
+            InterceptedService::new(Self::new(inner), interceptor)
+        }
+        /// Enable decompressing requests with the given encoding.
+        #[must_use]
+        pub fn accept_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.accept_compression_encodings.enable(encoding);
+            self
+        }
+        /// Compress responses with the given encoding, if the client supports it.
+        #[must_use]
+        pub fn send_compressed(mut self, encoding: CompressionEncoding) -> Self {
+            self.send_compression_encodings.enable(encoding);
+            self
+        }
+        /// Limits the maximum size of a decoded message.
+        ///
+        /// Default: `4MB`
+        #[must_use]
+        pub fn max_decoding_message_size(mut self, limit: usize) -> Self {
+            self.max_decoding_message_size = Some(limit);
+            self
+        }
+        /// Limits the maximum size of an encoded message.
+        ///
+        /// Default: `usize::MAX`
+        #[must_use]
+        pub fn max_encoding_message_size(mut self, limit: usize) -> Self {
+            self.max_encoding_message_size = Some(limit);
+            self
+        }
+    }
+    impl<T, B> tonic::codegen::Service<http::Request<B>> for SignerServiceServer<T>
+    where
+        T: SignerService,
+        B: Body + Send + 'static,
+        B::Error: Into<StdError> + Send + 'static,
+    {
+        type Response = http::Response<tonic::body::BoxBody>;
+        type Error = std::convert::Infallible;
+        type Future = BoxFuture<Self::Response, Self::Error>;
+        fn poll_ready(
+            &mut self,
+            _cx: &mut Context<'_>,
+        ) -> Poll<std::result::Result<(), Self::Error>> {
+            Poll::Ready(Ok(()))
+        }
+        fn call(&mut self, req: http::Request<B>) -> Self::Future {
+            let inner = self.inner.clone();
+            match req.uri().path() {
+                "/sigil.signer.v1.SignerService/Ping" => {
+                    #[allow(non_camel_case_types)]
+                    struct PingSvc<T: SignerService>(pub Arc<T>);
+                    impl<
+                        T: SignerService,
+                    > tonic::server::UnaryService<super::PingRequest> for PingSvc<T> {
+                        type Response = super::PingResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PingRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SignerService>::ping(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = PingSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sigil.signer.v1.SignerService/GetDiskStatus" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetDiskStatusSvc<T: SignerService>(pub Arc<T>);
+                    impl<
+                        T: SignerService,
+                    > tonic::server::UnaryService<super::GetDiskStatusRequest>
+                    for GetDiskStatusSvc<T> {
+                        type Response = super::GetDiskStatusResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetDiskStatusRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SignerService>::get_disk_status(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetDiskStatusSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sigil.signer.v1.SignerService/Sign" => {
+                    #[allow(non_camel_case_types)]
+                    struct SignSvc<T: SignerService>(pub Arc<T>);
+                    impl<
+                        T: SignerService,
+                    > tonic::server::UnaryService<super::SignRequest> for SignSvc<T> {
+                        type Response = super::SignResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SignRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SignerService>::sign(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SignSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sigil.signer.v1.SignerService/SignBatch" => {
+                    #[allow(non_camel_case_types)]
+                    struct SignBatchSvc<T: SignerService>(pub Arc<T>);
+                    impl<
+                        T: SignerService,
+                    > tonic::server::UnaryService<super::SignBatchRequest>
+                    for SignBatchSvc<T> {
+                        type Response = super::SignBatchResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SignBatchRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SignerService>::sign_batch(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SignBatchSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sigil.signer.v1.SignerService/SignStream" => {
+                    #[allow(non_camel_case_types)]
+                    struct SignStreamSvc<T: SignerService>(pub Arc<T>);
+                    impl<
+                        T: SignerService,
+                    > tonic::server::ServerStreamingService<super::SignRequest>
+                    for SignStreamSvc<T> {
+                        type Response = super::SignProgress;
+                        type ResponseStream = T::SignStreamStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::SignRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SignerService>::sign_stream(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = SignStreamSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sigil.signer.v1.SignerService/CancelSign" => {
+                    #[allow(non_camel_case_types)]
+                    struct CancelSignSvc<T: SignerService>(pub Arc<T>);
+                    impl<
+                        T: SignerService,
+                    > tonic::server::UnaryService<super::CancelSignRequest>
+                    for CancelSignSvc<T> {
+                        type Response = super::CancelSignResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::CancelSignRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SignerService>::cancel_sign(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = CancelSignSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sigil.signer.v1.SignerService/UpdateTxHash" => {
+                    #[allow(non_camel_case_types)]
+                    struct UpdateTxHashSvc<T: SignerService>(pub Arc<T>);
+                    impl<
+                        T: SignerService,
+                    > tonic::server::UnaryService<super::UpdateTxHashRequest>
+                    for UpdateTxHashSvc<T> {
+                        type Response = super::UpdateTxHashResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::UpdateTxHashRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SignerService>::update_tx_hash(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = UpdateTxHashSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sigil.signer.v1.SignerService/GetPresigCount" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetPresigCountSvc<T: SignerService>(pub Arc<T>);
+                    impl<
+                        T: SignerService,
+                    > tonic::server::UnaryService<super::GetPresigCountRequest>
+                    for GetPresigCountSvc<T> {
+                        type Response = super::GetPresigCountResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetPresigCountRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SignerService>::get_presig_count(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetPresigCountSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sigil.signer.v1.SignerService/QueryUsageLog" => {
+                    #[allow(non_camel_case_types)]
+                    struct QueryUsageLogSvc<T: SignerService>(pub Arc<T>);
+                    impl<
+                        T: SignerService,
+                    > tonic::server::UnaryService<super::QueryUsageLogRequest>
+                    for QueryUsageLogSvc<T> {
+                        type Response = super::QueryUsageLogResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::QueryUsageLogRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SignerService>::query_usage_log(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = QueryUsageLogSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sigil.signer.v1.SignerService/ListChildren" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListChildrenSvc<T: SignerService>(pub Arc<T>);
+                    impl<
+                        T: SignerService,
+                    > tonic::server::UnaryService<super::ListChildrenRequest>
+                    for ListChildrenSvc<T> {
+                        type Response = super::ListChildrenResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListChildrenRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SignerService>::list_children(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListChildrenSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sigil.signer.v1.SignerService/WatchDisk" => {
+                    #[allow(non_camel_case_types)]
+                    struct WatchDiskSvc<T: SignerService>(pub Arc<T>);
+                    impl<
+                        T: SignerService,
+                    > tonic::server::ServerStreamingService<super::WatchDiskRequest>
+                    for WatchDiskSvc<T> {
+                        type Response = super::DiskEvent;
+                        type ResponseStream = T::WatchDiskStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::WatchDiskRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SignerService>::watch_disk(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = WatchDiskSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sigil.signer.v1.SignerService/WatchEvents" => {
+                    #[allow(non_camel_case_types)]
+                    struct WatchEventsSvc<T: SignerService>(pub Arc<T>);
+                    impl<
+                        T: SignerService,
+                    > tonic::server::ServerStreamingService<super::WatchEventsRequest>
+                    for WatchEventsSvc<T> {
+                        type Response = super::DaemonEvent;
+                        type ResponseStream = T::WatchEventsStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::WatchEventsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SignerService>::watch_events(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = WatchEventsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sigil.signer.v1.SignerService/ImportAgentShard" => {
+                    #[allow(non_camel_case_types)]
+                    struct ImportAgentShardSvc<T: SignerService>(pub Arc<T>);
+                    impl<
+                        T: SignerService,
+                    > tonic::server::UnaryService<super::ImportAgentShardRequest>
+                    for ImportAgentShardSvc<T> {
+                        type Response = super::ImportAgentShardResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ImportAgentShardRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SignerService>::import_agent_shard(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ImportAgentShardSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sigil.signer.v1.SignerService/ImportChildShares" => {
+                    #[allow(non_camel_case_types)]
+                    struct ImportChildSharesSvc<T: SignerService>(pub Arc<T>);
+                    impl<
+                        T: SignerService,
+                    > tonic::server::UnaryService<super::ImportChildSharesRequest>
+                    for ImportChildSharesSvc<T> {
+                        type Response = super::ImportChildSharesResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ImportChildSharesRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SignerService>::import_child_shares(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ImportChildSharesSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sigil.signer.v1.SignerService/ExportAuditBundle" => {
+                    #[allow(non_camel_case_types)]
+                    struct ExportAuditBundleSvc<T: SignerService>(pub Arc<T>);
+                    impl<
+                        T: SignerService,
+                    > tonic::server::UnaryService<super::ExportAuditBundleRequest>
+                    for ExportAuditBundleSvc<T> {
+                        type Response = super::ExportAuditBundleResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ExportAuditBundleRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SignerService>::export_audit_bundle(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ExportAuditBundleSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sigil.signer.v1.SignerService/ListApprovals" => {
+                    #[allow(non_camel_case_types)]
+                    struct ListApprovalsSvc<T: SignerService>(pub Arc<T>);
+                    impl<
+                        T: SignerService,
+                    > tonic::server::UnaryService<super::ListApprovalsRequest>
+                    for ListApprovalsSvc<T> {
+                        type Response = super::ListApprovalsResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ListApprovalsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SignerService>::list_approvals(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = ListApprovalsSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sigil.signer.v1.SignerService/GetApproval" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetApprovalSvc<T: SignerService>(pub Arc<T>);
+                    impl<
+                        T: SignerService,
+                    > tonic::server::UnaryService<super::GetApprovalRequest>
+                    for GetApprovalSvc<T> {
+                        type Response = super::Approval;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::GetApprovalRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SignerService>::get_approval(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = GetApprovalSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/sigil.signer.v1.SignerService/DecideApproval" => {
+                    #[allow(non_camel_case_types)]
+                    struct DecideApprovalSvc<T: SignerService>(pub Arc<T>);
+                    impl<
+                        T: SignerService,
+                    > tonic::server::UnaryService<super::DecideApprovalRequest>
+                    for DecideApprovalSvc<T> {
+                        type Response = super::Approval;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DecideApprovalRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SignerService>::decide_approval(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let inner = inner.0;
+                        let method = DecideApprovalSvc(inner);
+                        let codec = tonic::codec::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                _ => {
+                    Box::pin(async move {
+                        Ok(
+                            http::Response::builder()
+                                .status(200)
+                                .header("grpc-status", "12")
+                                .header("content-type", "application/grpc")
+                                .body(empty_body())
+                                .unwrap(),
+                        )
+                    })
+                }
+            }
+        }
+    }
+    impl<T: SignerService> Clone for SignerServiceServer<T> {
+        fn clone(&self) -> Self {
+            let inner = self.inner.clone();
+            Self {
+                inner,
+                accept_compression_encodings: self.accept_compression_encodings,
+                send_compression_encodings: self.send_compression_encodings,
+                max_decoding_message_size: self.max_decoding_message_size,
+                max_encoding_message_size: self.max_encoding_message_size,
+            }
+        }
+    }
+    impl<T: SignerService> Clone for _Inner<T> {
+        fn clone(&self) -> Self {
+            Self(Arc::clone(&self.0))
+        }
+    }
+    impl<T: std::fmt::Debug> std::fmt::Debug for _Inner<T> {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "{:?}", self.0)
+        }
+    }
+    impl<T: SignerService> tonic::server::NamedService for SignerServiceServer<T> {
+        const NAME: &'static str = "sigil.signer.v1.SignerService";
+    }
+}
@@ -1,20 +1,50 @@
 //! IPC server implementation
+//!
+//! Serves the gRPC `SignerService` over the platform IPC transport. Every RPC
+//! except `Ping` is authorized against the configured [`IpcAuthConfig`];
+//! refusals are written to the audit log. With an [`ApprovalQueue`], sign
+//! requests its policy selects are held until a person approves them.
+//! `SignStream` reports each signing stage and can be cancelled until the
+//! presig is committed, by `CancelSign` or by closing the stream.
 
+use std::collections::HashMap;
 use std::path::PathBuf;
 use std::sync::Arc;
-use tokio::io::{AsyncBufReadExt, AsyncRead, AsyncWrite, AsyncWriteExt, BufReader};
-use tokio::sync::RwLock;
-use tracing::{debug, error, info};
+use std::time::Instant;
+use tokio::sync::broadcast::error::RecvError;
+use tokio::sync::{mpsc, Mutex, MutexGuard, RwLock};
+use tonic::codegen::BoxStream;
+use tonic::transport::Server;
+use tonic::{Request, Response, Status};
+use tracing::{debug, error, info, warn};
 
-use sigil_core::types::ChainId;
+use sigil_core::evm::Transaction;
+use sigil_core::types::{ChainId, MessageHash, TxHash};
+use sigil_core::{DiskHeader, TxHashStatus, UsageFilter, UsageLogEntry};
 
 use crate::agent_store::AgentStore;
-use crate::disk_watcher::DiskWatcher;
-use crate::error::Result;
-use crate::signer::{Signer, SigningRequest};
+use crate::approvals::{Approval, ApprovalDecision, ApprovalItem, ApprovalQueue, ApprovalStatus};
+use crate::audit_log::AccessDenial;
+use crate::config::{ApprovalConfig, IpcAuthConfig};
+use crate::disk_watcher::{DetectedDisk, DiskEvent as WatcherEvent, DiskWatcher};
+use crate::error::{DaemonError, Result};
+use crate::signer::{
+    SignControl, Signer, SigningEvent, SigningRequest, SigningResult, SigningStage,
+};
 
-use super::connection::{IpcTransport, PlatformTransport};
-use super::types::{parse_message_hash, parse_tx_hash, IpcRequest, IpcResponse};
+use super::auth::{request_token, Authorizer, Capability, IpcConnectInfo};
+use super::connection::{IpcConnection, IpcTransport, PlatformTransport};
+use super::proto::signer_service_server::{SignerService, SignerServiceServer};
+use super::proto::{self, daemon_event, disk_event, sign_progress};
+
+/// Maximum number of items in a `SignBatch` request
+pub const MAX_SIGN_BATCH: usize = 32;
+
+/// Usage log entries returned by `QueryUsageLog` when no limit is given
+pub const DEFAULT_USAGE_QUERY_LIMIT: usize = 50;
+
+/// Most usage log entries one `QueryUsageLog` response may carry
+pub const MAX_USAGE_QUERY_LIMIT: usize = 500;
 
 /// IPC server
 pub struct IpcServer {
@@ -29,6 +59,19 @@ pub struct IpcServer {
 
     /// Signer
     signer: Arc<Signer>,
+
+    /// Request authorization policy
+    authorizer: Arc<Authorizer>,
+
+    /// Queue of sign requests awaiting human approval
+    approvals: Option<Arc<Mutex<ApprovalQueue>>>,
+
+    /// When the server was created (reported as uptime)
+    started_at: Instant,
+
+    /// Socket handed over by the service manager instead of binding our own
+    #[cfg(unix)]
+    listener: std::sync::Mutex<Option<std::os::unix::net::UnixListener>>,
 }
 
 impl IpcServer {
@@ -44,266 +87,1830 @@ impl IpcServer {
             disk_watcher,
             agent_store,
             signer,
+            authorizer: Arc::new(Authorizer::new(IpcAuthConfig::default())),
+            approvals: None,
+            started_at: Instant::now(),
+            #[cfg(unix)]
+            listener: std::sync::Mutex::new(None),
         }
     }
 
-    /// Start the IPC server
-    pub async fn run(&self) -> Result<()> {
-        let transport = PlatformTransport::bind(&self.socket_path).await?;
+    /// Authorize clients with the given policy
+    pub fn with_auth(mut self, config: IpcAuthConfig) -> Self {
+        self.authorizer = Arc::new(Authorizer::new(config));
+        self
+    }
+
+    /// Hold sign requests for approval according to the queue's policy
+    pub fn with_approvals(mut self, approvals: ApprovalQueue) -> Self {
+        self.approvals = Some(Arc::new(Mutex::new(approvals)));
+        self
+    }
 
+    /// Serve on this listening socket rather than binding `socket_path`
+    #[cfg(unix)]
+    pub fn with_listener(self, listener: std::os::unix::net::UnixListener) -> Self {
+        *self.listener.lock().unwrap() = Some(listener);
+        self
+    }
+
+    /// Replace the authorization policy of the running server
+    pub fn reload_auth(&self, config: IpcAuthConfig) {
+        self.authorizer.reload(config);
+    }
+
+    /// Replace the approval policy of the running server
+    pub async fn reload_approvals(&self, config: ApprovalConfig) {
+        if let Some(approvals) = &self.approvals {
+            approvals.lock().await.set_config(config);
+        }
+    }
+
+    /// Adopt the socket passed by the service manager, or bind `socket_path`
+    async fn transport(&self) -> Result<PlatformTransport> {
+        #[cfg(unix)]
+        {
+            let activated = self.listener.lock().unwrap().take();
+            if let Some(listener) = activated {
+                info!("IPC server using socket passed by the service manager");
+                return PlatformTransport::from_std(listener);
+            }
+        }
+
+        let transport = PlatformTransport::bind(&self.socket_path).await?;
         info!("IPC server listening on {:?}", self.socket_path);
+        Ok(transport)
+    }
 
-        loop {
-            match transport.accept().await {
-                Ok(stream) => {
-                    let disk_watcher = Arc::clone(&self.disk_watcher);
-                    let agent_store = Arc::clone(&self.agent_store);
-                    let signer = Arc::clone(&self.signer);
-
-                    tokio::spawn(async move {
-                        if let Err(e) =
-                            handle_connection(stream, disk_watcher, agent_store, signer).await
-                        {
-                            error!("Connection error: {}", e);
-                        }
-                    });
-                }
-                Err(e) => {
-                    error!("Accept error: {}", e);
+    /// Start the IPC server
+    pub async fn run(&self) -> Result<()> {
+        let transport = self.transport().await?;
+
+        // Accept errors are logged and skipped so one bad client can't stop the server
+        let incoming = futures_util::stream::unfold(transport, |transport| async move {
+            loop {
+                match transport.accept().await {
+                    Ok(stream) => {
+                        let peer = PlatformTransport::peer_credentials(&stream);
+                        debug!("Accepted IPC connection from {:?}", peer);
+                        let connection = IpcConnection::new(stream, peer);
+                        return Some((Ok::<_, DaemonError>(connection), transport));
+                    }
+                    Err(e) => error!("Accept error: {}", e),
                 }
             }
-        }
+        });
+
+        let service = DaemonSignerService {
+            disk_watcher: Arc::clone(&self.disk_watcher),
+            agent_store: Arc::clone(&self.agent_store),
+            signer: Arc::clone(&self.signer),
+            authorizer: Arc::clone(&self.authorizer),
+            approvals: self.approvals.clone(),
+            operations: Arc::default(),
+            started_at: self.started_at,
+        };
+
+        Server::builder()
+            .add_service(SignerServiceServer::new(service))
+            .serve_with_incoming(incoming)
+            .await
+            .map_err(|e| DaemonError::Ipc(format!("gRPC server failed: {}", e)))
     }
 }
 
-/// Handle a single IPC connection
-async fn handle_connection<S>(
-    stream: S,
+/// `SignerService` implementation backed by the daemon components
+#[derive(Clone)]
+struct DaemonSignerService {
     disk_watcher: Arc<DiskWatcher>,
     agent_store: Arc<RwLock<AgentStore>>,
     signer: Arc<Signer>,
-) -> Result<()>
-where
-    S: AsyncRead + AsyncWrite + Unpin,
-{
-    let (reader, mut writer) = tokio::io::split(stream);
-    let mut reader = BufReader::new(reader);
-    let mut line = String::new();
-
-    while reader.read_line(&mut line).await? > 0 {
-        let request: IpcRequest = match serde_json::from_str(&line) {
-            Ok(req) => req,
-            Err(e) => {
-                let response = IpcResponse::Error {
-                    message: format!("Invalid request: {}", e),
-                };
-                send_response(&mut writer, &response).await?;
-                line.clear();
-                continue;
+    authorizer: Arc<Authorizer>,
+    approvals: Option<Arc<Mutex<ApprovalQueue>>>,
+    operations: Arc<std::sync::Mutex<HashMap<String, Operation>>>,
+    started_at: Instant,
+}
+
+/// A `SignStream` signing operation in progress
+struct Operation {
+    /// Caller that started it; only they may cancel it
+    requested_by: String,
+    control: SignControl,
+}
+
+/// Outcome of a sign request that may need approval
+enum Gated {
+    /// Signatures, new or from an earlier approval
+    Signed(Vec<SigningResult>),
+    /// Held for a decision
+    Pending(proto::PendingApproval),
+}
+
+impl DaemonSignerService {
+    /// Check the caller may use an RPC, recording any refusal in the audit log
+    ///
+    /// Returns the caller's identity: the client name when a token was used,
+    /// otherwise its uid.
+    async fn authorize<T>(
+        &self,
+        request: &Request<T>,
+        required: Capability,
+        method: &str,
+    ) -> std::result::Result<String, Status> {
+        let peer = request
+            .extensions()
+            .get::<IpcConnectInfo>()
+            .and_then(|info| info.peer);
+
+        match self
+            .authorizer
+            .authorize(peer.as_ref(), request_token(request), required)
+        {
+            Ok(client) => {
+                debug!("Authorized {} for {:?}", method, client);
+                Ok(client
+                    .or_else(|| peer.map(|peer| format!("uid {}", peer.uid)))
+                    .unwrap_or_else(|| "local".to_string()))
+            }
+            Err(denied) => {
+                warn!("Denied {} request from {:?}: {}", method, peer, denied);
+
+                if let Some(audit_log) = self.signer.audit_log() {
+                    let denial = AccessDenial {
+                        timestamp: now(),
+                        method: method.to_string(),
+                        peer,
+                        client: denied.client().map(str::to_string),
+                        reason: denied.to_string(),
+                    };
+                    if let Err(e) = audit_log.read().await.record_denial(&denial) {
+                        warn!("Failed to record access denial: {}", e);
+                    }
+                }
+
+                Err(denied.into())
             }
+        }
+    }
+
+    /// Sign now, or hold the request for approval if the policy says so
+    ///
+    /// A repeated request is matched to its earlier approval: it stays
+    /// pending, is signed once approved, returns the stored signatures once
+    /// signed, and is refused once rejected.
+    async fn sign_gated(
+        &self,
+        requested_by: &str,
+        requests: Vec<SigningRequest>,
+        values: Vec<RequestValue>,
+        control: &SignControl,
+    ) -> Result<Gated> {
+        let Some(approvals) = &self.approvals else {
+            return self
+                .signer
+                .sign_batch_with_control(requests, control)
+                .await
+                .map(Gated::Signed);
         };
 
-        debug!("Received IPC request: {:?}", request);
+        let items: Vec<ApprovalItem> = requests
+            .iter()
+            .zip(values)
+            .map(|(request, value)| ApprovalItem::new(request, value.declared, value.verified))
+            .collect();
 
-        let response = handle_request(
-            request,
-            Arc::clone(&disk_watcher),
-            Arc::clone(&agent_store),
-            Arc::clone(&signer),
-        )
-        .await;
+        let mut queue = approvals.lock().await;
+        let current_time = now();
+        let Some(approval) = queue.find(requested_by, &items, current_time)? else {
+            if !queue.requires_approval(&items) {
+                drop(queue);
+                return self
+                    .signer
+                    .sign_batch_with_control(requests, control)
+                    .await
+                    .map(Gated::Signed);
+            }
+
+            let approval = queue.submit(requested_by, items, current_time)?;
+            info!(
+                "Holding signing request {} from {} for approval",
+                approval.id, requested_by
+            );
+            return Ok(Gated::Pending(pending_approval(&approval)));
+        };
 
-        send_response(&mut writer, &response).await?;
-        line.clear();
+        match approval.status {
+            ApprovalStatus::Pending | ApprovalStatus::Signing => {
+                Ok(Gated::Pending(pending_approval(&approval)))
+            }
+            ApprovalStatus::Approved => self
+                .execute(approvals, queue, &approval, control)
+                .await
+                .map(Gated::Signed),
+            ApprovalStatus::Signed => approval.signing_results().map(Gated::Signed),
+            ApprovalStatus::Rejected | ApprovalStatus::Expired => {
+                let (approver, reason) = approval
+                    .decision
+                    .map(|decision| (decision.approver, decision.reason))
+                    .unwrap_or_default();
+                Err(DaemonError::ApprovalRejected {
+                    id: approval.id,
+                    approver,
+                    reason,
+                })
+            }
+        }
     }
 
-    Ok(())
+    /// Sign an approved request and store the result in the queue
+    ///
+    /// The request is marked signing before `queue` is unlocked, so it is
+    /// signed once while other approvals can be listed and decided.
+    async fn execute(
+        &self,
+        approvals: &Mutex<ApprovalQueue>,
+        mut queue: MutexGuard<'_, ApprovalQueue>,
+        approval: &Approval,
+        control: &SignControl,
+    ) -> Result<Vec<SigningResult>> {
+        queue.begin_signing(&approval.id, now())?;
+        drop(queue);
+
+        let signed = self
+            .signer
+            .sign_batch_with_control(approval.signing_requests(), control)
+            .await;
+
+        let mut queue = approvals.lock().await;
+        match signed {
+            Ok(results) => {
+                queue.record_signed(&approval.id, &results, now())?;
+                info!("Signed approved request {}", approval.id);
+                Ok(results)
+            }
+            // Nothing failed; the approval is signed on the next request
+            Err(DaemonError::Cancelled) => {
+                queue.cancel_signing(&approval.id)?;
+                Err(DaemonError::Cancelled)
+            }
+            Err(e) => {
+                queue.record_failure(&approval.id, e.to_string(), now())?;
+                Err(e)
+            }
+        }
+    }
 }
 
-/// Handle a single request
-async fn handle_request(
-    request: IpcRequest,
-    disk_watcher: Arc<DiskWatcher>,
-    agent_store: Arc<RwLock<AgentStore>>,
-    signer: Arc<Signer>,
-) -> IpcResponse {
-    match request {
-        IpcRequest::Ping => IpcResponse::Pong {
-            version: env!("CARGO_PKG_VERSION").to_string(),
-        },
+impl DaemonSignerService {
+    /// Sign for `SignStream`, forwarding stages and then the outcome to `tx`
+    ///
+    /// The operation is cancelled if the client closes the stream.
+    #[allow(clippy::too_many_arguments)]
+    async fn run_operation(
+        &self,
+        caller: &str,
+        operation_id: &str,
+        request: SigningRequest,
+        value: RequestValue,
+        control: SignControl,
+        mut stages: mpsc::UnboundedReceiver<SigningStage>,
+        tx: mpsc::Sender<std::result::Result<proto::SignProgress, Status>>,
+    ) {
+        let signing = self.sign_gated(caller, vec![request], vec![value], &control);
+        tokio::pin!(signing);
 
-        IpcRequest::GetDiskStatus => match disk_watcher.current_disk().await {
-            Some(disk) => {
-                let current_time = std::time::SystemTime::now()
-                    .duration_since(std::time::UNIX_EPOCH)
-                    .unwrap()
-                    .as_secs();
-
-                let days_until_expiry = disk.header.expiry.days_until_expiry(current_time);
-                let is_valid = disk.header.validate(current_time).is_ok();
-
-                IpcResponse::DiskStatus {
-                    detected: true,
-                    child_id: Some(disk.header.child_id.short()),
-                    presigs_remaining: Some(disk.header.presigs_remaining()),
-                    presigs_total: Some(disk.header.presig_total),
-                    days_until_expiry: Some(days_until_expiry),
-                    is_valid: Some(is_valid),
+        let outcome = loop {
+            tokio::select! {
+                outcome = &mut signing => break outcome,
+                Some(stage) = stages.recv() => {
+                    let _ = tx.send(Ok(sign_stage(stage))).await;
+                }
+                _ = tx.closed() => {
+                    if control.cancel() {
+                        info!("Signing stream {} closed; cancelling", operation_id);
+                    }
+                    break signing.await;
                 }
             }
-            None => IpcResponse::DiskStatus {
-                detected: false,
-                child_id: None,
-                presigs_remaining: None,
-                presigs_total: None,
-                days_until_expiry: None,
-                is_valid: None,
+        };
+
+        // Stages reported just before the outcome are still queued
+        while let Ok(stage) = stages.try_recv() {
+            let _ = tx.send(Ok(sign_stage(stage))).await;
+        }
+
+        let result = match outcome {
+            Ok(Gated::Signed(mut results)) => Ok(sign_response(results.remove(0))),
+            Ok(Gated::Pending(pending)) => Ok(proto::SignResponse {
+                pending_approval: Some(pending),
+                ..Default::default()
+            }),
+            Err(e) => Err(daemon_status("Signing failed", e)),
+        };
+        let progress = result.map(|result| proto::SignProgress {
+            update: Some(sign_progress::Update::Result(result)),
+        });
+        if tx.send(progress).await.is_err() {
+            debug!("Signing stream {} closed before its result", operation_id);
+        }
+    }
+}
+
+#[tonic::async_trait]
+impl SignerService for DaemonSignerService {
+    async fn ping(
+        &self,
+        _request: Request<proto::PingRequest>,
+    ) -> std::result::Result<Response<proto::PingResponse>, Status> {
+        Ok(Response::new(proto::PingResponse {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            uptime_seconds: self.started_at.elapsed().as_secs(),
+        }))
+    }
+
+    async fn get_disk_status(
+        &self,
+        request: Request<proto::GetDiskStatusRequest>,
+    ) -> std::result::Result<Response<proto::GetDiskStatusResponse>, Status> {
+        self.authorize(&request, Capability::Status, "GetDiskStatus")
+            .await?;
+
+        let disks: Vec<proto::DiskInfo> = self
+            .disk_watcher
+            .disks()
+            .await
+            .iter()
+            .map(|disk| disk_info(disk, now()))
+            .collect();
+
+        let response = match disks.as_slice() {
+            [] => proto::GetDiskStatusResponse {
+                status_message: "No disk detected".to_string(),
+                ..Default::default()
             },
-        },
+            [disk] => proto::GetDiskStatusResponse {
+                detected: true,
+                child_id: disk.child_id.clone(),
+                presigs_remaining: disk.presigs_remaining,
+                presigs_total: disk.presigs_total,
+                days_until_expiry: disk.days_until_expiry,
+                is_valid: disk.is_valid,
+                status_message: disk.status_message.clone(),
+                disks,
+            },
+            _ => proto::GetDiskStatusResponse {
+                detected: true,
+                status_message: format!(
+                    "{} disks detected - specify a child_id to select one",
+                    disks.len()
+                ),
+                disks,
+                ..Default::default()
+            },
+        };
 
-        IpcRequest::Sign {
-            message_hash,
-            chain_id,
-            description,
-        } => {
-            // Parse message hash
-            let message_hash = match parse_message_hash(&message_hash) {
-                Ok(h) => h,
-                Err(e) => {
-                    return IpcResponse::Error {
-                        message: format!("Invalid message hash: {}", e),
-                    }
+        Ok(Response::new(response))
+    }
+
+    async fn sign(
+        &self,
+        request: Request<proto::SignRequest>,
+    ) -> std::result::Result<Response<proto::SignResponse>, Status> {
+        let caller = self.authorize(&request, Capability::Sign, "Sign").await?;
+
+        let request = request.into_inner();
+        let value = request_value(&request).map_err(Status::invalid_argument)?;
+        let request = signing_request(request)
+            .map_err(|e| Status::invalid_argument(format!("Invalid message hash: {}", e)))?;
+
+        let response = match self
+            .sign_gated(&caller, vec![request], vec![value], &SignControl::new())
+            .await
+            .map_err(|e| daemon_status("Signing failed", e))?
+        {
+            Gated::Signed(mut results) => sign_response(results.remove(0)),
+            Gated::Pending(pending) => proto::SignResponse {
+                pending_approval: Some(pending),
+                ..Default::default()
+            },
+        };
+
+        Ok(Response::new(response))
+    }
+
+    async fn sign_batch(
+        &self,
+        request: Request<proto::SignBatchRequest>,
+    ) -> std::result::Result<Response<proto::SignBatchResponse>, Status> {
+        let caller = self
+            .authorize(&request, Capability::Sign, "SignBatch")
+            .await?;
+
+        let proto::SignBatchRequest { items, child_id } = request.into_inner();
+        if items.is_empty() || items.len() > MAX_SIGN_BATCH {
+            return Err(Status::invalid_argument(format!(
+                "Batch must contain 1 to {} items",
+                MAX_SIGN_BATCH
+            )));
+        }
+
+        let values = items
+            .iter()
+            .enumerate()
+            .map(|(i, item)| request_value(item).map_err(|e| format!("{} in item {}", e, i)))
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(Status::invalid_argument)?;
+
+        let requests = items
+            .into_iter()
+            .enumerate()
+            .map(|(i, mut item)| {
+                if item.child_id.is_empty() {
+                    item.child_id = child_id.clone();
                 }
-            };
+                signing_request(item).map_err(|e| format!("item {}: {}", i, e))
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(|e| Status::invalid_argument(format!("Invalid message hash in {}", e)))?;
 
-            let request = SigningRequest {
-                message_hash,
-                chain_id: ChainId::new(chain_id),
-                description,
-            };
+        let response = match self
+            .sign_gated(&caller, requests, values, &SignControl::new())
+            .await
+            .map_err(|e| daemon_status("Batch signing failed", e))?
+        {
+            Gated::Signed(results) => proto::SignBatchResponse {
+                results: results.into_iter().map(sign_response).collect(),
+                pending_approval: None,
+            },
+            Gated::Pending(pending) => proto::SignBatchResponse {
+                results: Vec::new(),
+                pending_approval: Some(pending),
+            },
+        };
+
+        Ok(Response::new(response))
+    }
+
+    type SignStreamStream = BoxStream<proto::SignProgress>;
+
+    async fn sign_stream(
+        &self,
+        request: Request<proto::SignRequest>,
+    ) -> std::result::Result<Response<Self::SignStreamStream>, Status> {
+        let caller = self
+            .authorize(&request, Capability::Sign, "SignStream")
+            .await?;
+
+        let request = request.into_inner();
+        let value = request_value(&request).map_err(Status::invalid_argument)?;
+        let request = signing_request(request)
+            .map_err(|e| Status::invalid_argument(format!("Invalid message hash: {}", e)))?;
 
-            match signer.sign(request).await {
-                Ok(result) => IpcResponse::SignResult {
-                    signature: hex::encode(result.signature.as_bytes()),
-                    presig_index: result.presig_index,
-                    proof_hash: hex::encode(result.proof_hash.as_bytes()),
-                },
-                Err(e) => IpcResponse::Error {
-                    message: format!("Signing failed: {}", e),
-                },
+        let (stage_tx, stages) = mpsc::unbounded_channel();
+        let control = SignControl::new().with_progress(stage_tx);
+
+        let mut id = [0u8; 8];
+        rand::RngCore::fill_bytes(&mut rand::thread_rng(), &mut id);
+        let operation_id = hex::encode(id);
+        self.operations.lock().unwrap().insert(
+            operation_id.clone(),
+            Operation {
+                requested_by: caller.clone(),
+                control: control.clone(),
+            },
+        );
+
+        let (tx, rx) = mpsc::channel(8);
+        let started = proto::SignProgress {
+            update: Some(sign_progress::Update::Started(proto::SignStarted {
+                operation_id: operation_id.clone(),
+            })),
+        };
+        // Can't fail: the channel is empty and the receiver is held below
+        let _ = tx.try_send(Ok(started));
+
+        let service = self.clone();
+        tokio::spawn(async move {
+            service
+                .run_operation(&caller, &operation_id, request, value, control, stages, tx)
+                .await;
+            service.operations.lock().unwrap().remove(&operation_id);
+        });
+
+        let stream = futures_util::stream::unfold(rx, |mut rx| async move {
+            rx.recv().await.map(|item| (item, rx))
+        });
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    async fn cancel_sign(
+        &self,
+        request: Request<proto::CancelSignRequest>,
+    ) -> std::result::Result<Response<proto::CancelSignResponse>, Status> {
+        let caller = self
+            .authorize(&request, Capability::Sign, "CancelSign")
+            .await?;
+
+        let operation_id = request.into_inner().operation_id;
+        let control = match self.operations.lock().unwrap().get(&operation_id) {
+            Some(operation) if operation.requested_by == caller => operation.control.clone(),
+            _ => {
+                return Err(Status::not_found(format!(
+                    "No signing operation in progress: {}",
+                    operation_id
+                )))
             }
+        };
+
+        let cancelled = control.cancel();
+        if cancelled {
+            info!("Signing operation {} cancelled by {}", operation_id, caller);
+        } else {
+            info!(
+                "Signing operation {} already committed; cancel from {} ignored",
+                operation_id, caller
+            );
         }
 
-        IpcRequest::UpdateTxHash {
-            presig_index,
-            tx_hash,
-        } => {
-            let tx_hash = match parse_tx_hash(&tx_hash) {
-                Ok(h) => h,
+        Ok(Response::new(proto::CancelSignResponse { cancelled }))
+    }
+
+    async fn update_tx_hash(
+        &self,
+        request: Request<proto::UpdateTxHashRequest>,
+    ) -> std::result::Result<Response<proto::UpdateTxHashResponse>, Status> {
+        self.authorize(&request, Capability::Sign, "UpdateTxHash")
+            .await?;
+
+        let request = request.into_inner();
+        let tx_hash = parse_tx_hash(&request.tx_hash)
+            .map_err(|e| Status::invalid_argument(format!("Invalid tx hash: {}", e)))?;
+
+        self.signer
+            .update_tx_hash(
+                child_selector(&request.child_id),
+                request.presig_index,
+                tx_hash,
+            )
+            .await
+            .map_err(|e| daemon_status("Failed to update tx hash", e))?;
+
+        Ok(Response::new(proto::UpdateTxHashResponse {}))
+    }
+
+    async fn get_presig_count(
+        &self,
+        request: Request<proto::GetPresigCountRequest>,
+    ) -> std::result::Result<Response<proto::GetPresigCountResponse>, Status> {
+        self.authorize(&request, Capability::Status, "GetPresigCount")
+            .await?;
+
+        let request = request.into_inner();
+        let disk = self
+            .disk_watcher
+            .select_disk(child_selector(&request.child_id))
+            .await
+            .map_err(|e| daemon_status("Failed to select disk", e))?;
+
+        Ok(Response::new(proto::GetPresigCountResponse {
+            remaining: disk.header.presigs_remaining(),
+            total: disk.header.presig_total,
+        }))
+    }
+
+    async fn query_usage_log(
+        &self,
+        request: Request<proto::QueryUsageLogRequest>,
+    ) -> std::result::Result<Response<proto::QueryUsageLogResponse>, Status> {
+        self.authorize(&request, Capability::Status, "QueryUsageLog")
+            .await?;
+
+        let request = request.into_inner();
+        let filter = usage_filter(&request).map_err(Status::invalid_argument)?;
+        let disk = self
+            .disk_watcher
+            .load_full_disk(child_selector(&request.child_id))
+            .await
+            .map_err(|e| daemon_status("Failed to read disk", e))?;
+
+        let limit = match request.limit as usize {
+            0 => DEFAULT_USAGE_QUERY_LIMIT,
+            limit => limit.min(MAX_USAGE_QUERY_LIMIT),
+        };
+        let matching: Vec<_> = disk.usage_log.query(&filter).collect();
+        let entries = matching
+            .iter()
+            .skip(request.offset as usize)
+            .take(limit)
+            .map(|entry| usage_entry_to_proto(entry))
+            .collect();
+
+        Ok(Response::new(proto::QueryUsageLogResponse {
+            child_id: disk.header.child_id.to_hex(),
+            entries,
+            total: matching.len() as u32,
+        }))
+    }
+
+    async fn list_children(
+        &self,
+        request: Request<proto::ListChildrenRequest>,
+    ) -> std::result::Result<Response<proto::ListChildrenResponse>, Status> {
+        self.authorize(&request, Capability::Status, "ListChildren")
+            .await?;
+
+        let mut store = self.agent_store.write().await;
+        let child_ids = store
+            .list_children()
+            .map_err(|e| daemon_status("Failed to list children", e))?;
+
+        let mut children = Vec::with_capacity(child_ids.len());
+        for child_id in child_ids {
+            let info = match store.load_child(&child_id) {
+                Ok(data) => {
+                    let remaining = data.total_presigs.saturating_sub(data.next_presig_index);
+                    proto::ChildInfo {
+                        child_id: child_id.short(),
+                        presigs_remaining: remaining,
+                        presigs_total: data.total_presigs,
+                        status: if remaining > 0 { "active" } else { "exhausted" }.to_string(),
+                    }
+                }
                 Err(e) => {
-                    return IpcResponse::Error {
-                        message: format!("Invalid tx hash: {}", e),
+                    warn!("Failed to load child {}: {}", child_id.short(), e);
+                    proto::ChildInfo {
+                        child_id: child_id.short(),
+                        status: "unreadable".to_string(),
+                        ..Default::default()
                     }
                 }
             };
-
-            match signer.update_tx_hash(presig_index, tx_hash).await {
-                Ok(()) => IpcResponse::Ok,
-                Err(e) => IpcResponse::Error {
-                    message: format!("Failed to update tx hash: {}", e),
-                },
-            }
+            children.push(info);
         }
 
-        IpcRequest::ListChildren => {
-            let store = agent_store.read().await;
-            match store.list_children() {
-                Ok(children) => IpcResponse::Children {
-                    child_ids: children.iter().map(|c| c.short()).collect(),
-                },
-                Err(e) => IpcResponse::Error {
-                    message: format!("Failed to list children: {}", e),
-                },
-            }
-        }
+        Ok(Response::new(proto::ListChildrenResponse { children }))
+    }
 
-        IpcRequest::GetPresigCount => match disk_watcher.current_disk().await {
-            Some(disk) => IpcResponse::PresigCount {
-                remaining: disk.header.presigs_remaining(),
-                total: disk.header.presig_total,
-            },
-            None => IpcResponse::Error {
-                message: "No disk detected".to_string(),
-            },
-        },
+    type WatchDiskStream = BoxStream<proto::DiskEvent>;
+
+    async fn watch_disk(
+        &self,
+        request: Request<proto::WatchDiskRequest>,
+    ) -> std::result::Result<Response<Self::WatchDiskStream>, Status> {
+        self.authorize(&request, Capability::Status, "WatchDisk")
+            .await?;
 
-        IpcRequest::ImportAgentShard { agent_shard_hex } => {
-            // Parse hex string
-            let agent_shard_hex = agent_shard_hex
-                .strip_prefix("0x")
-                .unwrap_or(&agent_shard_hex);
-            let mut shard = [0u8; 32];
-            match hex::decode_to_slice(agent_shard_hex, &mut shard) {
-                Ok(()) => {
-                    let mut store = agent_store.write().await;
-                    match store.import_agent_master_shard(shard) {
-                        Ok(()) => IpcResponse::Ok,
-                        Err(e) => IpcResponse::Error {
-                            message: format!("Failed to import agent shard: {}", e),
-                        },
+        let events = self.disk_watcher.subscribe();
+
+        let stream = futures_util::stream::unfold(events, |mut events| async move {
+            loop {
+                match events.recv().await {
+                    Ok(event) => return Some((Ok(disk_event_to_proto(event)), events)),
+                    Err(RecvError::Lagged(missed)) => {
+                        warn!("Disk event subscriber lagged, {} events dropped", missed)
                     }
+                    Err(RecvError::Closed) => return None,
                 }
-                Err(e) => IpcResponse::Error {
-                    message: format!("Invalid hex string: {}", e),
-                },
             }
-        }
+        });
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    type WatchEventsStream = BoxStream<proto::DaemonEvent>;
+
+    async fn watch_events(
+        &self,
+        request: Request<proto::WatchEventsRequest>,
+    ) -> std::result::Result<Response<Self::WatchEventsStream>, Status> {
+        self.authorize(&request, Capability::Status, "WatchEvents")
+            .await?;
 
-        IpcRequest::ImportChildShares {
-            shares_json,
-            replace,
-        } => {
-            // Parse JSON
-            match serde_json::from_str::<crate::agent_store::AgentChildData>(&shares_json) {
-                Ok(child_data) => {
-                    let mut store = agent_store.write().await;
-
-                    // Check if child already exists
-                    let child_id = child_data.child_id;
-                    let exists = store.load_child(&child_id).is_ok();
-
-                    if exists && !replace {
-                        IpcResponse::Error {
-                            message: format!(
-                                "Child {} already exists. Use --replace to overwrite.",
-                                child_id.short()
-                            ),
+        use futures_util::StreamExt;
+
+        // Subscribe before reading the current disks so nothing is missed
+        let disk_events = self.disk_watcher.subscribe();
+        let signing_events = self.signer.subscribe();
+        let approval_events = match &self.approvals {
+            Some(approvals) => Some(approvals.lock().await.subscribe()),
+            None => None,
+        };
+
+        let initial: Vec<_> = self
+            .disk_watcher
+            .disks()
+            .await
+            .into_iter()
+            .map(|disk| {
+                daemon_event(daemon_event::Event::DiskInserted(disk_inserted(
+                    disk.path,
+                    &disk.header,
+                )))
+            })
+            .collect();
+
+        let disk_stream = futures_util::stream::unfold(disk_events, |mut events| async move {
+            loop {
+                match events.recv().await {
+                    Ok(event) => {
+                        let event = disk_event_to_proto(event)
+                            .event
+                            .map(|event| daemon_event(disk_event_to_daemon(event)));
+                        if let Some(event) = event {
+                            return Some((event, events));
                         }
-                    } else {
-                        match store.store_child(child_data) {
-                            Ok(()) => IpcResponse::Ok,
-                            Err(e) => IpcResponse::Error {
-                                message: format!("Failed to import child shares: {}", e),
-                            },
+                    }
+                    Err(RecvError::Lagged(missed)) => {
+                        warn!("Event subscriber lagged, {} disk events dropped", missed)
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        });
+
+        let signing_stream =
+            futures_util::stream::unfold(signing_events, |mut events| async move {
+                loop {
+                    match events.recv().await {
+                        Ok(event) => return Some((signing_event_to_proto(event), events)),
+                        Err(RecvError::Lagged(missed)) => {
+                            warn!("Event subscriber lagged, {} signing events dropped", missed)
                         }
+                        Err(RecvError::Closed) => return None,
+                    }
+                }
+            });
+
+        let approval_stream = futures_util::stream::unfold(approval_events, |events| async move {
+            let mut events = events?;
+            loop {
+                match events.recv().await {
+                    Ok(approval) => return Some((approval_event(&approval), Some(events))),
+                    Err(RecvError::Lagged(missed)) => {
+                        warn!(
+                            "Event subscriber lagged, {} approval events dropped",
+                            missed
+                        )
                     }
+                    Err(RecvError::Closed) => return None,
                 }
-                Err(e) => IpcResponse::Error {
-                    message: format!("Invalid JSON: {}", e),
-                },
             }
+        });
+
+        let stream = futures_util::stream::iter(initial)
+            .chain(futures_util::stream::select(
+                futures_util::stream::select(disk_stream, signing_stream),
+                approval_stream,
+            ))
+            .map(Ok);
+
+        Ok(Response::new(Box::pin(stream)))
+    }
+
+    async fn import_agent_shard(
+        &self,
+        request: Request<proto::ImportAgentShardRequest>,
+    ) -> std::result::Result<Response<proto::ImportAgentShardResponse>, Status> {
+        self.authorize(&request, Capability::Admin, "ImportAgentShard")
+            .await?;
+
+        let request = request.into_inner();
+        let agent_shard_hex = request
+            .agent_shard_hex
+            .strip_prefix("0x")
+            .unwrap_or(&request.agent_shard_hex);
+
+        let mut shard = [0u8; 32];
+        hex::decode_to_slice(agent_shard_hex, &mut shard)
+            .map_err(|e| Status::invalid_argument(format!("Invalid hex string: {}", e)))?;
+
+        self.agent_store
+            .write()
+            .await
+            .import_agent_master_shard(shard)
+            .map_err(|e| daemon_status("Failed to import agent shard", e))?;
+
+        Ok(Response::new(proto::ImportAgentShardResponse {}))
+    }
+
+    async fn import_child_shares(
+        &self,
+        request: Request<proto::ImportChildSharesRequest>,
+    ) -> std::result::Result<Response<proto::ImportChildSharesResponse>, Status> {
+        self.authorize(&request, Capability::Admin, "ImportChildShares")
+            .await?;
+
+        let request = request.into_inner();
+        let child_data: crate::agent_store::AgentChildData =
+            serde_json::from_str(&request.shares_json)
+                .map_err(|e| Status::invalid_argument(format!("Invalid JSON: {}", e)))?;
+
+        let mut store = self.agent_store.write().await;
+
+        // Check if child already exists
+        let child_id = child_data.child_id;
+        if store.load_child(&child_id).is_ok() && !request.replace {
+            return Err(Status::already_exists(format!(
+                "Child {} already exists. Use --replace to overwrite.",
+                child_id.short()
+            )));
+        }
+
+        store
+            .store_child(child_data)
+            .map_err(|e| daemon_status("Failed to import child shares", e))?;
+
+        Ok(Response::new(proto::ImportChildSharesResponse {}))
+    }
+
+    async fn export_audit_bundle(
+        &self,
+        request: Request<proto::ExportAuditBundleRequest>,
+    ) -> std::result::Result<Response<proto::ExportAuditBundleResponse>, Status> {
+        self.authorize(&request, Capability::Admin, "ExportAuditBundle")
+            .await?;
+
+        let child_id = request.into_inner().child_id;
+        let Some(audit_log) = self.signer.audit_log() else {
+            return Err(Status::failed_precondition("Audit log is not enabled"));
+        };
+        let log = audit_log.read().await;
+
+        let prefix = child_id
+            .strip_prefix("0x")
+            .unwrap_or(&child_id)
+            .to_lowercase();
+        let matches: Vec<_> = log
+            .list_children()
+            .map_err(|e| daemon_status("Failed to list audit logs", e))?
+            .into_iter()
+            .filter(|c| c.to_hex().starts_with(&prefix))
+            .collect();
+
+        let child = match matches.as_slice() {
+            [child] => *child,
+            [] => {
+                return Err(Status::not_found(format!(
+                    "No audit records for child {}",
+                    child_id
+                )))
+            }
+            _ => {
+                return Err(Status::invalid_argument(format!(
+                    "Child ID {} is ambiguous",
+                    child_id
+                )))
+            }
+        };
+
+        let bundle = log
+            .export_bundle(&child)
+            .map_err(|e| daemon_status("Failed to export audit bundle", e))?;
+        let bundle_json = serde_json::to_string_pretty(&bundle)
+            .map_err(|e| Status::internal(format!("Failed to serialize audit bundle: {}", e)))?;
+
+        Ok(Response::new(proto::ExportAuditBundleResponse {
+            child_id: child.to_hex(),
+            record_count: bundle.records.len() as u32,
+            bundle_json,
+            qr_chunks: bundle.to_qr_chunks(),
+        }))
+    }
+
+    async fn list_approvals(
+        &self,
+        request: Request<proto::ListApprovalsRequest>,
+    ) -> std::result::Result<Response<proto::ListApprovalsResponse>, Status> {
+        self.authorize(&request, Capability::Status, "ListApprovals")
+            .await?;
+
+        let Some(approvals) = &self.approvals else {
+            return Err(Status::failed_precondition("Approvals are not enabled"));
+        };
+        let include_decided = request.into_inner().include_decided;
+        let approvals = approvals
+            .lock()
+            .await
+            .list(include_decided, now())
+            .map_err(|e| daemon_status("Failed to list approvals", e))?;
+
+        Ok(Response::new(proto::ListApprovalsResponse {
+            approvals: approvals.iter().map(approval_to_proto).collect(),
+        }))
+    }
+
+    async fn get_approval(
+        &self,
+        request: Request<proto::GetApprovalRequest>,
+    ) -> std::result::Result<Response<proto::Approval>, Status> {
+        self.authorize(&request, Capability::Status, "GetApproval")
+            .await?;
+
+        let Some(approvals) = &self.approvals else {
+            return Err(Status::failed_precondition("Approvals are not enabled"));
+        };
+        let approval_id = request.into_inner().approval_id;
+        let approval = approvals
+            .lock()
+            .await
+            .get(&approval_id, now())
+            .map_err(|e| daemon_status("Failed to get approval", e))?;
+
+        Ok(Response::new(approval_to_proto(&approval)))
+    }
+
+    async fn decide_approval(
+        &self,
+        request: Request<proto::DecideApprovalRequest>,
+    ) -> std::result::Result<Response<proto::Approval>, Status> {
+        let caller = self
+            .authorize(&request, Capability::Approve, "DecideApproval")
+            .await?;
+
+        let Some(approvals) = &self.approvals else {
+            return Err(Status::failed_precondition("Approvals are not enabled"));
+        };
+        let request = request.into_inner();
+        let decision = ApprovalDecision {
+            approved: request.approve,
+            approver: Some(request.approver.trim())
+                .filter(|approver| !approver.is_empty())
+                .unwrap_or(&caller)
+                .to_string(),
+            decided_by: caller,
+            reason: Some(request.reason).filter(|reason| !reason.is_empty()),
+            decided_at: now(),
+        };
+
+        let mut queue = approvals.lock().await;
+        let approval = queue
+            .decide(&request.approval_id, decision.clone(), now())
+            .map_err(|e| daemon_status("Failed to decide approval", e))?;
+        info!(
+            "Signing request {} {} by {} ({})",
+            approval.id,
+            approval.status.as_str(),
+            decision.approver,
+            decision.decided_by
+        );
+
+        if approval.status != ApprovalStatus::Approved {
+            return Ok(Response::new(approval_to_proto(&approval)));
+        }
+
+        // Sign now if a disk is ready; otherwise the requester's next attempt
+        // signs it
+        if let Err(e) = self
+            .execute(approvals, queue, &approval, &SignControl::new())
+            .await
+        {
+            warn!("Approved request {} not signed yet: {}", approval.id, e);
+        }
+        let approval = approvals
+            .lock()
+            .await
+            .get(&approval.id, now())
+            .map_err(|e| daemon_status("Failed to get approval", e))?;
+
+        Ok(Response::new(approval_to_proto(&approval)))
+    }
+}
+
+/// Metadata key carrying [`DaemonError::code`] on error statuses
+pub const ERROR_CODE_METADATA: &str = "sigil-error-code";
+
+/// Map a daemon error to a gRPC status with a context prefix
+///
+/// The error kind travels in the [`ERROR_CODE_METADATA`] metadata so clients
+/// can tell apart errors that share a gRPC code.
+pub fn daemon_status(context: &str, e: DaemonError) -> Status {
+    let message = format!("{}: {}", context, e);
+    let code = e.code();
+    let mut status = match e {
+        DaemonError::NoDiskDetected | DaemonError::MultipleDisksDetected => {
+            Status::failed_precondition(message)
+        }
+        DaemonError::AgentShardNotFound(_) | DaemonError::ChildDiskNotFound(_) => {
+            Status::not_found(message)
+        }
+        DaemonError::IdempotencyConflict(_) => Status::already_exists(message),
+        DaemonError::DiskValidationFailed(_) | DaemonError::PresigMismatch(_) => {
+            Status::failed_precondition(message)
+        }
+        DaemonError::ClockRollback { .. }
+        | DaemonError::ClockJump(_)
+        | DaemonError::TimeAttestation(_) => Status::failed_precondition(message),
+        DaemonError::ShuttingDown => Status::unavailable(message),
+        DaemonError::Cancelled => Status::cancelled(message),
+        DaemonError::ApprovalNotFound(_) => Status::not_found(message),
+        DaemonError::ApprovalClosed(_) => Status::failed_precondition(message),
+        DaemonError::SelfApproval | DaemonError::ApprovalRejected { .. } => {
+            Status::permission_denied(message)
         }
+        _ => Status::internal(message),
+    };
+    status.metadata_mut().insert(
+        ERROR_CODE_METADATA,
+        tonic::metadata::MetadataValue::from_static(code),
+    );
+    status
+}
+
+/// Daemon error kind of a status returned by the daemon, if it has one
+pub fn error_code(status: &Status) -> Option<&str> {
+    status.metadata().get(ERROR_CODE_METADATA)?.to_str().ok()
+}
+
+/// Report a signing stage on a `SignStream`
+fn sign_stage(stage: SigningStage) -> proto::SignProgress {
+    proto::SignProgress {
+        update: Some(sign_progress::Update::Stage(proto::SignStage {
+            stage: stage.as_str().to_string(),
+        })),
     }
 }
 
-/// Send a response over the socket
-async fn send_response<W>(writer: &mut W, response: &IpcResponse) -> Result<()>
-where
-    W: AsyncWrite + Unpin,
-{
-    let json = serde_json::to_string(response)?;
-    writer.write_all(json.as_bytes()).await?;
-    writer.write_all(b"\n").await?;
-    writer.flush().await?;
-    Ok(())
+/// Convert a disk watcher event to its protobuf form
+fn disk_event_to_proto(event: WatcherEvent) -> proto::DiskEvent {
+    let event = match event {
+        WatcherEvent::Inserted { path, header } => {
+            disk_event::Event::Inserted(disk_inserted(path, &header))
+        }
+        WatcherEvent::Removed { path } => disk_event::Event::Removed(proto::DiskRemoved {
+            path: path.display().to_string(),
+        }),
+        WatcherEvent::ValidationFailed { path, reason } => {
+            disk_event::Event::ValidationFailed(proto::DiskValidationFailed {
+                path: path.display().to_string(),
+                reason,
+            })
+        }
+    };
+
+    proto::DiskEvent { event: Some(event) }
+}
+
+/// Re-wrap a disk event as a daemon event
+fn disk_event_to_daemon(event: disk_event::Event) -> daemon_event::Event {
+    match event {
+        disk_event::Event::Inserted(inserted) => daemon_event::Event::DiskInserted(inserted),
+        disk_event::Event::Removed(removed) => daemon_event::Event::DiskRemoved(removed),
+        disk_event::Event::ValidationFailed(failed) => {
+            daemon_event::Event::DiskValidationFailed(failed)
+        }
+    }
+}
+
+/// Convert a signer event to its protobuf form
+fn signing_event_to_proto(event: SigningEvent) -> proto::DaemonEvent {
+    let event = match event {
+        SigningEvent::PresigConsumed {
+            child_id,
+            presig_index,
+            presigs_remaining,
+        } => daemon_event::Event::PresigConsumed(proto::PresigConsumed {
+            child_id: child_id.short(),
+            presig_index,
+            presigs_remaining,
+        }),
+        SigningEvent::LowPresigs {
+            child_id,
+            presigs_remaining,
+            threshold,
+        } => daemon_event::Event::LowPresigWarning(proto::LowPresigWarning {
+            child_id: child_id.short(),
+            presigs_remaining,
+            threshold,
+        }),
+        SigningEvent::ExpiryWarning {
+            child_id,
+            days_until_expiry,
+        } => daemon_event::Event::ExpiryWarning(proto::ExpiryWarning {
+            child_id: child_id.short(),
+            days_until_expiry,
+        }),
+        SigningEvent::SignatureNotLanded {
+            child_id,
+            presig_index,
+            chain_id,
+            message_hash,
+            signed_at,
+        } => daemon_event::Event::SignatureNotLanded(proto::SignatureNotLanded {
+            child_id: child_id.short(),
+            presig_index,
+            chain_id: chain_id.0,
+            message_hash: message_hash.to_hex(),
+            signed_at,
+        }),
+    };
+
+    daemon_event(event)
+}
+
+/// Event announcing an approval request or its resolution
+fn approval_event(approval: &Approval) -> proto::DaemonEvent {
+    let event = match approval.status {
+        ApprovalStatus::Pending => {
+            daemon_event::Event::ApprovalRequested(proto::ApprovalRequested {
+                approval_id: approval.id.clone(),
+                requested_by: approval.requested_by.clone(),
+                descriptions: approval
+                    .items
+                    .iter()
+                    .map(|item| item.description.clone())
+                    .collect(),
+                expires_at: approval.expires_at,
+            })
+        }
+        status => daemon_event::Event::ApprovalResolved(proto::ApprovalResolved {
+            approval_id: approval.id.clone(),
+            status: status.as_str().to_string(),
+        }),
+    };
+
+    daemon_event(event)
+}
+
+/// Timestamp a daemon event
+fn daemon_event(event: daemon_event::Event) -> proto::DaemonEvent {
+    proto::DaemonEvent {
+        timestamp: now(),
+        event: Some(event),
+    }
+}
+
+/// Build a `DiskInserted` event from a disk header
+fn disk_inserted(path: PathBuf, header: &DiskHeader) -> proto::DiskInserted {
+    proto::DiskInserted {
+        path: path.display().to_string(),
+        child_id: header.child_id.short(),
+        presigs_remaining: header.presigs_remaining(),
+        days_until_expiry: header.expiry.days_until_expiry(now()),
+    }
+}
+
+/// Status of one detected disk
+fn disk_info(disk: &DetectedDisk, current_time: u64) -> proto::DiskInfo {
+    let validation = disk.header.validate(current_time);
+
+    proto::DiskInfo {
+        path: disk.path.display().to_string(),
+        child_id: disk.header.child_id.short(),
+        presigs_remaining: disk.header.presigs_remaining(),
+        presigs_total: disk.header.presig_total,
+        days_until_expiry: disk.header.expiry.days_until_expiry(current_time),
+        is_valid: validation.is_ok(),
+        status_message: match validation {
+            Ok(()) => "Disk ready".to_string(),
+            Err(e) => e.to_string(),
+        },
+    }
+}
+
+/// Treat an empty child field as "no selection"
+fn child_selector(child_id: &str) -> Option<&str> {
+    Some(child_id).filter(|child_id| !child_id.is_empty())
+}
+
+/// Current Unix time in seconds
+fn now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+/// Convert a protobuf sign request, validating the message hash
+fn signing_request(request: proto::SignRequest) -> std::result::Result<SigningRequest, String> {
+    Ok(SigningRequest {
+        message_hash: parse_message_hash(&request.message_hash)?,
+        chain_id: ChainId::new(request.chain_id),
+        description: request.description,
+        idempotency_key: Some(request.idempotency_key).filter(|key| !key.is_empty()),
+        child_id: child_selector(&request.child_id).map(str::to_string),
+    })
+}
+
+/// Convert a signing result to its protobuf form
+fn sign_response(result: SigningResult) -> proto::SignResponse {
+    let signature = result.signature.as_bytes();
+    proto::SignResponse {
+        signature: hex::encode(signature),
+        v: 0,
+        r: hex::encode(&signature[..32]),
+        s: hex::encode(&signature[32..]),
+        presig_index: result.presig_index,
+        proof_hash: hex::encode(result.proof_hash.as_bytes()),
+        cached: result.cached,
+        pending_approval: None,
+    }
+}
+
+/// Convert a `QueryUsageLog` request to a usage log filter
+fn usage_filter(request: &proto::QueryUsageLogRequest) -> std::result::Result<UsageFilter, String> {
+    let chain_id = match request.chain_id.as_str() {
+        "" => None,
+        chain_id => Some(ChainId::new(
+            chain_id
+                .parse()
+                .map_err(|_| format!("Invalid chain ID: {}", chain_id))?,
+        )),
+    };
+    let tx_hash = match request.tx_hash_status.as_str() {
+        "" => None,
+        status => Some(status.parse::<TxHashStatus>()?),
+    };
+
+    Ok(UsageFilter {
+        since: Some(request.since).filter(|since| *since > 0),
+        until: Some(request.until).filter(|until| *until > 0),
+        chain_id,
+        tx_hash,
+        search: Some(request.search.clone()).filter(|search| !search.is_empty()),
+    })
+}
+
+/// Convert a usage log entry to its protobuf form
+fn usage_entry_to_proto(entry: &UsageLogEntry) -> proto::UsageLogEntry {
+    proto::UsageLogEntry {
+        presig_index: entry.presig_index,
+        timestamp: entry.timestamp,
+        message_hash: entry.message_hash.to_hex(),
+        signature: entry.signature.to_hex(),
+        chain_id: entry.chain_id.0,
+        tx_hash: if entry.tx_hash_pending() {
+            String::new()
+        } else {
+            entry.tx_hash.to_hex()
+        },
+        proof_hash: entry.zkproof_hash.to_hex(),
+        description: entry.description.clone(),
+    }
+}
+
+/// Tell the requester its request is waiting for approval
+fn pending_approval(approval: &Approval) -> proto::PendingApproval {
+    proto::PendingApproval {
+        approval_id: approval.id.clone(),
+        expires_at: approval.expires_at,
+    }
+}
+
+/// Convert an approval to its protobuf form
+fn approval_to_proto(approval: &Approval) -> proto::Approval {
+    let decision = approval.decision.clone();
+    proto::Approval {
+        approval_id: approval.id.clone(),
+        status: approval.status.as_str().to_string(),
+        items: approval
+            .items
+            .iter()
+            .map(|item| proto::SignRequest {
+                message_hash: item.message_hash.to_hex(),
+                chain_id: item.chain_id.as_u32(),
+                description: item.description.clone(),
+                idempotency_key: item.idempotency_key.clone().unwrap_or_default(),
+                child_id: item.child_id.clone().unwrap_or_default(),
+                value: item
+                    .value
+                    .map(|value| value.to_string())
+                    .unwrap_or_default(),
+                ..Default::default()
+            })
+            .collect(),
+        requested_by: approval.requested_by.clone(),
+        created_at: approval.created_at,
+        expires_at: approval.expires_at,
+        decided_by: decision
+            .as_ref()
+            .map(|d| d.decided_by.clone())
+            .unwrap_or_default(),
+        approver: decision
+            .as_ref()
+            .map(|d| d.approver.clone())
+            .unwrap_or_default(),
+        reason: decision
+            .as_ref()
+            .and_then(|d| d.reason.clone())
+            .unwrap_or_default(),
+        decided_at: decision.as_ref().map_or(0, |d| d.decided_at),
+        results: approval
+            .signing_results()
+            .unwrap_or_default()
+            .into_iter()
+            .map(sign_response)
+            .collect(),
+        error: approval.error.clone().unwrap_or_default(),
+    }
+}
+
+/// Value of a sign request, as declared and as read from its transaction
+#[derive(Debug, Clone, Copy, Default)]
+struct RequestValue {
+    declared: Option<u128>,
+    verified: Option<u128>,
+}
+
+/// Parse the declared value of a sign request and verify its transaction
+///
+/// The transaction must be unsigned and hash to the request's message hash.
+/// Its value counts as verified only when it is a plain transfer with
+/// EIP-155 replay protection: calldata may move tokens worth any amount.
+fn request_value(request: &proto::SignRequest) -> std::result::Result<RequestValue, String> {
+    let declared = parse_value(&request.value).map_err(|e| format!("Invalid value: {}", e))?;
+    if request.transaction.is_empty() {
+        return Ok(RequestValue {
+            declared,
+            verified: None,
+        });
+    }
+
+    let invalid = |e: String| format!("Invalid transaction: {}", e);
+    let raw = request
+        .transaction
+        .strip_prefix("0x")
+        .unwrap_or(&request.transaction);
+    let tx = Transaction::decode(&hex::decode(raw).map_err(|e| invalid(e.to_string()))?)
+        .map_err(|e| invalid(e.to_string()))?;
+    if tx.signed {
+        return Err(invalid("it is already signed".to_string()));
+    }
+    let message_hash = parse_message_hash(&request.message_hash)
+        .map_err(|e| format!("Invalid message hash: {}", e))?;
+    if tx.signing_hash() != *message_hash.as_bytes() {
+        return Err(invalid("its signing hash is not message_hash".to_string()));
+    }
+    if let Some(chain_id) = tx.chain_id {
+        if chain_id != u64::from(request.chain_id) {
+            return Err(invalid(format!("it is for chain {}", chain_id)));
+        }
+    }
+
+    let verified = (tx.data.is_empty() && tx.chain_id.is_some() && tx.value.len() <= 16)
+        .then(|| tx.value.iter().fold(0u128, |n, b| n << 8 | u128::from(*b)));
+    Ok(RequestValue { declared, verified })
+}
+
+/// Parse an optional decimal value
+fn parse_value(s: &str) -> std::result::Result<Option<u128>, String> {
+    if s.is_empty() {
+        return Ok(None);
+    }
+    s.parse().map(Some).map_err(|e| format!("{}", e))
+}
+
+/// Parse a hex-encoded message hash
+fn parse_message_hash(s: &str) -> std::result::Result<MessageHash, String> {
+    let s = s.strip_prefix("0x").unwrap_or(s);
+    let mut bytes = [0u8; 32];
+    hex::decode_to_slice(s, &mut bytes).map_err(|e| e.to_string())?;
+    Ok(MessageHash::new(bytes))
+}
+
+/// Parse a hex-encoded transaction hash
+fn parse_tx_hash(s: &str) -> std::result::Result<TxHash, String> {
+    let s = s.strip_prefix("0x").unwrap_or(s);
+    let mut bytes = [0u8; 32];
+    hex::decode_to_slice(s, &mut bytes).map_err(|e| e.to_string())?;
+    Ok(TxHash::new(bytes))
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+    use crate::ipc::IpcClient;
+
+    #[tokio::test]
+    async fn test_grpc_round_trip_over_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let socket_path = dir.path().join("sigil.sock");
+
+        let agent_store = Arc::new(RwLock::new(
+            AgentStore::new(dir.path().join("store")).unwrap(),
+        ));
+        let disk_watcher = Arc::new(DiskWatcher::new(String::new()));
+        let signer = Arc::new(Signer::new(
+            Arc::clone(&agent_store),
+            Arc::clone(&disk_watcher),
+            false,
+        ));
+        let server = IpcServer::new(socket_path.clone(), disk_watcher, agent_store, signer);
+        tokio::spawn(async move { server.run().await });
+
+        let client = IpcClient::new(socket_path);
+        for _ in 0..50 {
+            if client.ping().await {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+
+        let mut grpc = client.connect().await.unwrap();
+        let pong = grpc.ping(proto::PingRequest {}).await.unwrap().into_inner();
+        assert_eq!(pong.version, env!("CARGO_PKG_VERSION"));
+
+        let status = grpc
+            .get_disk_status(proto::GetDiskStatusRequest {})
+            .await
+            .unwrap()
+            .into_inner();
+        assert!(!status.detected);
+
+        let err = grpc
+            .get_presig_count(proto::GetPresigCountRequest::default())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+        assert_eq!(error_code(&err), Some("no_disk_detected"));
+
+        let err = grpc
+            .get_presig_count(proto::GetPresigCountRequest {
+                child_id: "abcd".to_string(),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::NotFound);
+
+        let err = grpc
+            .query_usage_log(proto::QueryUsageLogRequest {
+                tx_hash_status: "broadcast".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+        let err = grpc
+            .query_usage_log(proto::QueryUsageLogRequest {
+                chain_id: "1".to_string(),
+                tx_hash_status: "pending".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+
+        let err = grpc
+            .sign(proto::SignRequest {
+                message_hash: "zz".to_string(),
+                ..Default::default()
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+        let err = grpc
+            .sign_batch(proto::SignBatchRequest::default())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+        // The batch fails as a whole when there is no disk to sign with
+        let item = proto::SignRequest {
+            message_hash: "11".repeat(32),
+            ..Default::default()
+        };
+        let err = grpc
+            .sign_batch(proto::SignBatchRequest {
+                items: vec![item.clone(), item],
+                child_id: String::new(),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+
+        // A streamed signature fails the same way; messages sent just before
+        // an error may be dropped along with it
+        let mut progress = grpc
+            .sign_stream(proto::SignRequest {
+                message_hash: "11".repeat(32),
+                ..Default::default()
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        let mut operation_id = String::new();
+        let err = loop {
+            match progress.message().await {
+                Ok(Some(proto::SignProgress {
+                    update: Some(sign_progress::Update::Started(started)),
+                })) => operation_id = started.operation_id,
+                Ok(Some(_)) => {}
+                Ok(None) => panic!("stream ended without a result"),
+                Err(err) => break err,
+            }
+        };
+        assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+
+        // Once finished the operation can no longer be cancelled
+        let err = grpc
+            .cancel_sign(proto::CancelSignRequest { operation_id })
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::NotFound);
+
+        // With no disk present the event stream opens without an initial event
+        let mut events = grpc
+            .watch_events(proto::WatchEventsRequest {})
+            .await
+            .unwrap()
+            .into_inner();
+        let next = tokio::time::timeout(std::time::Duration::from_millis(50), events.message());
+        assert!(next.await.is_err());
+    }
+
+    #[test]
+    fn test_signing_event_to_proto() {
+        let event = signing_event_to_proto(SigningEvent::LowPresigs {
+            child_id: sigil_core::ChildId::new([0xab; 32]),
+            presigs_remaining: 5,
+            threshold: 20,
+        });
+
+        assert!(event.timestamp > 0);
+        match event.event {
+            Some(daemon_event::Event::LowPresigWarning(warning)) => {
+                assert_eq!(
+                    warning.child_id,
+                    sigil_core::ChildId::new([0xab; 32]).short()
+                );
+                assert_eq!(warning.presigs_remaining, 5);
+                assert_eq!(warning.threshold, 20);
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+    }
+
+    #[tokio::test]
+    async fn test_token_capabilities_enforced_and_audited() {
+        use crate::audit_log::AuditLog;
+        use crate::config::IpcClientConfig;
+
+        let dir = tempfile::tempdir().unwrap();
+        let socket_path = dir.path().join("sigil.sock");
+
+        let agent_store = Arc::new(RwLock::new(
+            AgentStore::new(dir.path().join("store")).unwrap(),
+        ));
+        let audit_log = Arc::new(RwLock::new(
+            AuditLog::new(dir.path().join("store")).unwrap(),
+        ));
+        let disk_watcher = Arc::new(DiskWatcher::new(String::new()));
+        let signer = Arc::new(
+            Signer::new(Arc::clone(&agent_store), Arc::clone(&disk_watcher), false)
+                .with_audit_log(Arc::clone(&audit_log)),
+        );
+        let server = IpcServer::new(socket_path.clone(), disk_watcher, agent_store, signer)
+            .with_auth(IpcAuthConfig {
+                allowed_uids: Vec::new(),
+                clients: vec![IpcClientConfig {
+                    name: "monitor".to_string(),
+                    token: "status-token".to_string(),
+                    capability: Capability::Status,
+                }],
+            });
+        tokio::spawn(async move { server.run().await });
+
+        let anonymous = IpcClient::new(socket_path.clone());
+        for _ in 0..50 {
+            if anonymous.ping().await {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+
+        let err = anonymous
+            .connect()
+            .await
+            .unwrap()
+            .get_disk_status(proto::GetDiskStatusRequest {})
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::Unauthenticated);
+
+        let mut monitor = IpcClient::new(socket_path)
+            .with_token("status-token")
+            .connect()
+            .await
+            .unwrap();
+        assert!(monitor
+            .get_disk_status(proto::GetDiskStatusRequest {})
+            .await
+            .is_ok());
+
+        let err = monitor
+            .import_agent_shard(proto::ImportAgentShardRequest {
+                agent_shard_hex: "00".repeat(32),
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err.code(), tonic::Code::PermissionDenied);
+
+        let denials = audit_log.read().await.denials().unwrap();
+        assert_eq!(denials.len(), 2);
+        assert_eq!(denials[0].method, "GetDiskStatus");
+        assert!(denials[0].peer.is_some());
+        assert_eq!(denials[1].method, "ImportAgentShard");
+        assert_eq!(denials[1].client.as_deref(), Some("monitor"));
+    }
+
+    #[tokio::test]
+    async fn test_sign_held_for_approval() {
+        use crate::config::{ApprovalMode, IpcClientConfig};
+        use sigil_core::evm::{keccak256, rlp_bytes, rlp_list, rlp_uint};
+
+        let dir = tempfile::tempdir().unwrap();
+        let socket_path = dir.path().join("sigil.sock");
+        let store_path = dir.path().join("store");
+
+        let agent_store = Arc::new(RwLock::new(AgentStore::new(store_path.clone()).unwrap()));
+        let disk_watcher = Arc::new(DiskWatcher::new(String::new()));
+        let signer = Arc::new(Signer::new(
+            Arc::clone(&agent_store),
+            Arc::clone(&disk_watcher),
+            false,
+        ));
+        let client = |name: &str, capability| IpcClientConfig {
+            name: name.to_string(),
+            token: format!("{}-token", name),
+            capability,
+        };
+        let approvals = ApprovalQueue::new(
+            store_path,
+            ApprovalConfig {
+                mode: ApprovalMode::Threshold,
+                value_thresholds: [(1, 1_000)].into_iter().collect(),
+                ..Default::default()
+            },
+        )
+        .unwrap();
+        let server = IpcServer::new(socket_path.clone(), disk_watcher, agent_store, signer)
+            .with_auth(IpcAuthConfig {
+                allowed_uids: Vec::new(),
+                clients: vec![
+                    client("agent", Capability::Sign),
+                    client("human", Capability::Approve),
+                ],
+            })
+            .with_approvals(approvals);
+        tokio::spawn(async move { server.run().await });
+
+        let agent = IpcClient::new(socket_path.clone()).with_token("agent-token");
+        for _ in 0..50 {
+            if agent.ping().await {
+                break;
+            }
+            tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+        }
+        let mut agent = agent.connect().await.unwrap();
+        let mut human = IpcClient::new(socket_path)
+            .with_token("human-token")
+            .connect()
+            .await
+            .unwrap();
+        let mut events = human
+            .watch_events(proto::WatchEventsRequest {})
+            .await
+            .unwrap()
+            .into_inner();
+
+        // Under the threshold, as read from the transaction: signed straight
+        // away (and fails for lack of a disk)
+        let unsigned = rlp_list(&[
+            rlp_uint(0),
+            rlp_uint(1_000_000_000),
+            rlp_uint(21_000),
+            rlp_bytes(&[0x11; 20]),
+            rlp_uint(1_000),
+            rlp_bytes(&[]),
+            rlp_uint(1),
+            rlp_uint(0),
+            rlp_uint(0),
+        ]);
+        let small = proto::SignRequest {
+            message_hash: hex::encode(keccak256(&unsigned)),
+            chain_id: 1,
+            description: "Send 0.000001 ETH".to_string(),
+            value: "1000".to_string(),
+            transaction: hex::encode(&unsigned),
+            ..Default::default()
+        };
+        let err = agent.sign(small.clone()).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+
+        // The transaction must be the one being signed
+        let mismatched = proto::SignRequest {
+            message_hash: "11".repeat(32),
+            ..small
+        };
+        let err = agent.sign(mismatched).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::InvalidArgument);
+
+        let large = proto::SignRequest {
+            message_hash: "22".repeat(32),
+            chain_id: 1,
+            description: "Send 5 ETH".to_string(),
+            value: "5000000000000000000".to_string(),
+            ..Default::default()
+        };
+        let pending = agent
+            .sign(large.clone())
+            .await
+            .unwrap()
+            .into_inner()
+            .pending_approval
+            .unwrap();
+        let again = agent.sign(large.clone()).await.unwrap().into_inner();
+        assert_eq!(
+            again.pending_approval.unwrap().approval_id,
+            pending.approval_id
+        );
+
+        match events.message().await.unwrap().unwrap().event {
+            Some(daemon_event::Event::ApprovalRequested(requested)) => {
+                assert_eq!(requested.approval_id, pending.approval_id);
+                assert_eq!(requested.requested_by, "agent");
+                assert_eq!(requested.descriptions, vec!["Send 5 ETH".to_string()]);
+            }
+            other => panic!("unexpected event: {:?}", other),
+        }
+
+        // The agent can't approve its own request
+        let decide = proto::DecideApprovalRequest {
+            approval_id: pending.approval_id.clone(),
+            approve: true,
+            approver: "alice".to_string(),
+            reason: String::new(),
+        };
+        let err = agent.decide_approval(decide.clone()).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::PermissionDenied);
+
+        let listed = human
+            .list_approvals(proto::ListApprovalsRequest::default())
+            .await
+            .unwrap()
+            .into_inner()
+            .approvals;
+        assert_eq!(listed.len(), 1);
+        assert_eq!(listed[0].items[0].value, "5000000000000000000");
+
+        // Approved, but there is no disk to sign with yet
+        let approval = human.decide_approval(decide).await.unwrap().into_inner();
+        assert_eq!(approval.status, "approved");
+        assert_eq!(approval.approver, "alice");
+        assert_eq!(approval.decided_by, "human");
+        assert!(!approval.error.is_empty());
+
+        let err = agent.sign(large).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::FailedPrecondition);
+
+        // A rejected request is refused when repeated
+        let undeclared = proto::SignRequest {
+            message_hash: "33".repeat(32),
+            chain_id: 1,
+            ..Default::default()
+        };
+        let pending = agent
+            .sign(undeclared.clone())
+            .await
+            .unwrap()
+            .into_inner()
+            .pending_approval
+            .unwrap();
+        let rejected = human
+            .decide_approval(proto::DecideApprovalRequest {
+                approval_id: pending.approval_id,
+                approve: false,
+                approver: String::new(),
+                reason: "unknown recipient".to_string(),
+            })
+            .await
+            .unwrap()
+            .into_inner();
+        assert_eq!(rejected.status, "rejected");
+        assert_eq!(rejected.approver, "human");
+
+        let err = agent.sign(undeclared).await.unwrap_err();
+        assert_eq!(err.code(), tonic::Code::PermissionDenied);
+        assert!(err.message().contains("unknown recipient"), "{}", err);
+
+        // A declared value alone isn't trusted
+        let declared = proto::SignRequest {
+            message_hash: "44".repeat(32),
+            chain_id: 1,
+            value: "1000".to_string(),
+            ..Default::default()
+        };
+        let response = agent.sign(declared).await.unwrap().into_inner();
+        assert!(response.pending_approval.is_some());
+    }
+
+    #[tokio::test]
+    async fn test_connect_reports_daemon_not_running() {
+        let dir = tempfile::tempdir().unwrap();
+        let client = IpcClient::new(dir.path().join("missing.sock"));
+
+        let err = client.connect().await.unwrap_err();
+        assert!(err.to_string().contains("Daemon not running"), "{}", err);
+    }
 }
@@ -6,6 +6,7 @@ use tokio::net::{UnixListener, UnixStream};
 
 use crate::error::{DaemonError, Result};
 
+use super::auth::PeerCredentials;
 use super::connection::{IpcClientTransport, IpcTransport};
 
 /// Unix domain socket server transport
@@ -15,6 +16,25 @@ pub struct UnixIpcTransport {
     socket_path: PathBuf,
 }
 
+impl UnixIpcTransport {
+    /// Serve on an already-listening socket (e.g. from systemd socket activation)
+    pub fn from_std(listener: std::os::unix::net::UnixListener) -> Result<Self> {
+        let socket_path = listener
+            .local_addr()?
+            .as_pathname()
+            .map(Path::to_path_buf)
+            .unwrap_or_default();
+        listener.set_nonblocking(true)?;
+        let listener = UnixListener::from_std(listener)
+            .map_err(|e| DaemonError::Ipc(format!("Failed to adopt socket: {}", e)))?;
+
+        Ok(Self {
+            listener,
+            socket_path,
+        })
+    }
+}
+
 #[async_trait]
 impl IpcTransport for UnixIpcTransport {
     type Stream = UnixStream;
@@ -43,6 +63,14 @@ impl IpcTransport for UnixIpcTransport {
         Ok(stream)
     }
 
+    fn peer_credentials(stream: &Self::Stream) -> Option<PeerCredentials> {
+        stream.peer_cred().ok().map(|cred| PeerCredentials {
+            uid: cred.uid(),
+            gid: cred.gid(),
+            pid: cred.pid(),
+        })
+    }
+
     async fn cleanup(&self) -> Result<()> {
         if self.socket_path.exists() {
             std::fs::remove_file(&self.socket_path)?;
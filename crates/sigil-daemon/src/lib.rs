@@ -2,20 +2,39 @@
 //!
 //! This crate provides:
 //! - Disk detection and monitoring via udev
+//! - File, raw block-device and in-memory disk storage backends
 //! - Agent shard storage and management
 //! - Signing operations with zkVM proof generation
+//! - Human approval queue for signing requests
+//! - Clock rollback protection and time authority attestations
 //! - IPC server for CLI communication
+//! - Transaction hash backfill from a local JSON-RPC node
+//! - systemd socket activation, readiness and watchdog notifications
 
 pub mod agent_store;
+pub mod approvals;
+pub mod audit_log;
+pub mod chain_watcher;
+pub mod clock;
 pub mod config;
 pub mod disk_watcher;
 pub mod error;
 pub mod ipc;
+pub mod sign_cache;
 pub mod signer;
+pub mod storage;
+pub mod systemd;
+pub mod tx_backfill;
 
 pub use agent_store::AgentStore;
+pub use approvals::ApprovalQueue;
+pub use audit_log::AuditLog;
+pub use chain_watcher::ChainWatcher;
+pub use clock::ClockGuard;
 pub use config::DaemonConfig;
 pub use disk_watcher::DiskWatcher;
 pub use error::{DaemonError, Result};
 pub use ipc::IpcServer;
+pub use sign_cache::SignCache;
 pub use signer::Signer;
+pub use tx_backfill::TxBackfill;
@@ -5,10 +5,15 @@
 use std::sync::Arc;
 use std::thread;
 use tokio::sync::RwLock;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
-use sigil_daemon::{AgentStore, DaemonConfig, DiskWatcher, IpcServer, Signer};
+use sigil_daemon::clock::TimeAuthorityClient;
+use sigil_daemon::systemd::Notifier;
+use sigil_daemon::{
+    AgentStore, ApprovalQueue, AuditLog, ChainWatcher, ClockGuard, DaemonConfig, DiskWatcher,
+    IpcServer, SignCache, Signer, TxBackfill,
+};
 
 #[tokio::main]
 async fn main() -> anyhow::Result<()> {
@@ -58,24 +63,76 @@ async fn main() -> anyhow::Result<()> {
         config.agent_store_path.clone(),
     )?));
 
-    let disk_watcher = Arc::new(DiskWatcher::new(config.disk_mount_pattern.clone()));
+    let audit_log = Arc::new(RwLock::new(AuditLog::new(config.agent_store_path.clone())?));
 
-    let signer = Arc::new(Signer::new(
+    let sign_cache = SignCache::new(
+        config.agent_store_path.clone(),
+        config.sign_dedup_window_secs,
+    )?;
+
+    let disk_watcher = Arc::new(
+        DiskWatcher::new(config.disk_mount_pattern.clone())
+            .with_raw_devices(config.raw_devices.clone()),
+    );
+
+    let mut clock = ClockGuard::new()
+        .with_rollback_tolerance(config.clock.rollback_tolerance_secs)
+        .with_max_jump(config.clock.max_jump_secs);
+    if let Some(authority) = &config.clock.time_authority {
+        info!(
+            "Cross-checking the clock against time authority {}",
+            authority.address
+        );
+        clock = clock.with_authority(
+            TimeAuthorityClient::new(authority.address.clone(), &authority.public_key)?
+                .with_max_skew(authority.max_skew_secs)
+                .required(authority.required),
+        );
+    }
+
+    let mut signer = Signer::new(
         Arc::clone(&agent_store),
         Arc::clone(&disk_watcher),
         config.enable_zkvm_proving,
-    ));
+    )
+    .with_audit_log(audit_log)
+    .with_sign_cache(sign_cache)
+    .with_clock_guard(clock)
+    .with_low_presig_threshold(config.low_presig_threshold);
+    if config.chain_watcher.is_some() {
+        signer = signer.with_tx_backfill(TxBackfill::new(config.agent_store_path.clone())?);
+    }
+    let signer = Arc::new(signer);
+
+    let notifier = Arc::new(Notifier::from_env());
 
     let ipc_server = IpcServer::new(
         config.ipc_socket_path.clone(),
         Arc::clone(&disk_watcher),
         Arc::clone(&agent_store),
         Arc::clone(&signer),
-    );
+    )
+    .with_auth(config.ipc_auth.clone())
+    .with_approvals(ApprovalQueue::new(
+        config.agent_store_path.clone(),
+        config.approvals.clone(),
+    )?);
+
+    // Prefer the socket systemd listens on for us (socket activation)
+    #[cfg(unix)]
+    let ipc_server = match sigil_daemon::systemd::take_listener()? {
+        Some(listener) => ipc_server.with_listener(listener),
+        None => ipc_server,
+    };
+    let ipc_server = Arc::new(ipc_server);
+
+    if config.ipc_auth.clients.is_empty() {
+        warn!("No IPC clients configured; any process of an allowed user has full access");
+    }
 
     // Start disk watcher in background on a dedicated thread
     // (udev types are not Send, so we need a separate runtime)
-    let disk_watcher_handle = {
+    let mut disk_watcher_handle = {
         let disk_watcher = Arc::clone(&disk_watcher);
         let (tx, rx) = tokio::sync::oneshot::channel::<()>();
 
@@ -97,32 +154,166 @@ async fn main() -> anyhow::Result<()> {
     };
 
     // Start IPC server
-    let ipc_handle = tokio::spawn(async move {
-        if let Err(e) = ipc_server.run().await {
-            error!("IPC server error: {}", e);
-        }
-    });
+    let mut ipc_handle = {
+        let ipc_server = Arc::clone(&ipc_server);
+        tokio::spawn(async move {
+            if let Err(e) = ipc_server.run().await {
+                error!("IPC server error: {}", e);
+            }
+        })
+    };
+
+    // Backfill tx hashes from a local node
+    if let Some(watcher_config) = &config.chain_watcher {
+        info!(
+            "Watching {} for signed transactions",
+            watcher_config.rpc_url
+        );
+        let watcher = ChainWatcher::new(watcher_config.clone(), Arc::clone(&signer))?;
+        tokio::spawn(async move { watcher.run().await });
+    }
 
     info!("Daemon started successfully");
+    notifier.ready(&health_status(&disk_watcher).await);
 
-    // Wait for shutdown signal
-    tokio::select! {
-        _ = tokio::signal::ctrl_c() => {
-            info!("Received shutdown signal");
-        }
-        _ = disk_watcher_handle => {
-            error!("Disk watcher exited unexpectedly");
-        }
-        _ = ipc_handle => {
-            error!("IPC server exited unexpectedly");
+    // Ping the systemd watchdog only while the disk watcher is alive, so a
+    // stalled watcher gets the service restarted
+    if let Some(interval) = notifier.watchdog_interval() {
+        let notifier = Arc::clone(&notifier);
+        let disk_watcher = Arc::clone(&disk_watcher);
+        tokio::spawn(async move {
+            let mut ticker = tokio::time::interval(interval / 2);
+            loop {
+                ticker.tick().await;
+                if disk_watcher.is_healthy() {
+                    notifier.watchdog();
+                    notifier.status(&health_status(&disk_watcher).await);
+                } else {
+                    warn!("Disk watcher is not responding; withholding watchdog ping");
+                    notifier.status("Disk watcher is not responding");
+                }
+            }
+        });
+    }
+
+    // Run until asked to stop; SIGHUP reloads the config in place
+    let mut signals = Signals::new()?;
+    loop {
+        tokio::select! {
+            signal = signals.next() => match signal {
+                DaemonSignal::Reload => {
+                    notifier.reloading();
+                    reload_config(&config_path, &ipc_server, &signer).await;
+                    notifier.ready(&health_status(&disk_watcher).await);
+                }
+                DaemonSignal::Shutdown(name) => {
+                    info!("Received {}", name);
+                    break;
+                }
+            },
+            _ = &mut disk_watcher_handle => {
+                error!("Disk watcher exited unexpectedly");
+                break;
+            }
+            _ = &mut ipc_handle => {
+                error!("IPC server exited unexpectedly");
+                break;
+            }
         }
     }
 
+    // Let signatures in progress reach the disk before exiting
     info!("Daemon shutting down");
+    notifier.stopping("Draining in-flight signatures");
+    let drain_timeout = std::time::Duration::from_secs(config.signing_timeout_secs);
+    if tokio::time::timeout(drain_timeout, signer.drain())
+        .await
+        .is_err()
+    {
+        warn!(
+            "In-flight signatures did not finish within {:?}",
+            drain_timeout
+        );
+    }
+    ipc_handle.abort();
 
     Ok(())
 }
 
+/// One-line daemon state for `systemctl status`
+async fn health_status(disk_watcher: &DiskWatcher) -> String {
+    match disk_watcher.disks().await.len() {
+        0 => "Waiting for a Sigil disk".to_string(),
+        1 => "1 Sigil disk inserted".to_string(),
+        n => format!("{} Sigil disks inserted", n),
+    }
+}
+
+/// Re-read the config file and apply the settings that can change at runtime
+///
+/// IPC authorization, the approval policy and the low-presig threshold take
+/// effect immediately; paths, storage and clock settings need a restart.
+async fn reload_config(config_path: &std::path::Path, ipc_server: &IpcServer, signer: &Signer) {
+    match DaemonConfig::load(config_path) {
+        Ok(config) => {
+            ipc_server.reload_auth(config.ipc_auth);
+            ipc_server.reload_approvals(config.approvals).await;
+            signer.set_low_presig_threshold(config.low_presig_threshold);
+            info!("Reloaded config from {:?}", config_path);
+        }
+        Err(e) => error!("Config reload failed, keeping current settings: {}", e),
+    }
+}
+
+/// Process signals the daemon reacts to
+enum DaemonSignal {
+    /// Stop the daemon (SIGINT or SIGTERM)
+    Shutdown(&'static str),
+    /// Reload the configuration (SIGHUP)
+    Reload,
+}
+
+/// Listens for shutdown and reload signals
+struct Signals {
+    #[cfg(unix)]
+    terminate: tokio::signal::unix::Signal,
+    #[cfg(unix)]
+    hangup: tokio::signal::unix::Signal,
+}
+
+impl Signals {
+    fn new() -> std::io::Result<Self> {
+        #[cfg(unix)]
+        {
+            use tokio::signal::unix::{signal, SignalKind};
+            Ok(Self {
+                terminate: signal(SignalKind::terminate())?,
+                hangup: signal(SignalKind::hangup())?,
+            })
+        }
+        #[cfg(not(unix))]
+        {
+            Ok(Self {})
+        }
+    }
+
+    async fn next(&mut self) -> DaemonSignal {
+        #[cfg(unix)]
+        {
+            tokio::select! {
+                _ = tokio::signal::ctrl_c() => DaemonSignal::Shutdown("SIGINT"),
+                _ = self.terminate.recv() => DaemonSignal::Shutdown("SIGTERM"),
+                _ = self.hangup.recv() => DaemonSignal::Reload,
+            }
+        }
+        #[cfg(not(unix))]
+        {
+            let _ = tokio::signal::ctrl_c().await;
+            DaemonSignal::Shutdown("Ctrl-C")
+        }
+    }
+}
+
 /// Helper module for dirs functionality
 mod dirs {
     use std::path::PathBuf;
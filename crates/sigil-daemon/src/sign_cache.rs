@@ -0,0 +1,239 @@
+//! Persistent signing result cache
+//!
+//! Lets a client safely retry a `Sign` that timed out without consuming a
+//! second presig. Results are looked up by the client's idempotency key, or
+//! by `(child_id, message_hash)` within the configured dedup window. The cache
+//! is written next to the agent store so retries work across daemon restarts.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use sigil_core::types::{MessageHash, Signature, ZkProofHash};
+use sigil_core::ChildId;
+
+use crate::error::{DaemonError, Result};
+use crate::signer::SigningResult;
+
+/// How long results stay retrievable by idempotency key
+pub const IDEMPOTENCY_KEY_TTL_SECS: u64 = 24 * 60 * 60;
+
+/// Default window for deduplicating repeated message hashes
+pub const DEFAULT_DEDUP_WINDOW_SECS: u64 = 10 * 60;
+
+/// Cache file name under the agent store
+const CACHE_FILE: &str = "sign_cache.json";
+
+/// A completed signature kept for replay
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct CachedSignature {
+    child_id: ChildId,
+    message_hash: MessageHash,
+    idempotency_key: Option<String>,
+    /// Signature bytes, hex encoded
+    signature: String,
+    presig_index: u32,
+    proof_hash: ZkProofHash,
+    signed_at: u64,
+}
+
+impl CachedSignature {
+    fn to_result(&self) -> Result<SigningResult> {
+        let mut signature = [0u8; 64];
+        hex::decode_to_slice(&self.signature, &mut signature)
+            .map_err(|e| DaemonError::Store(format!("Corrupt sign cache entry: {}", e)))?;
+
+        Ok(SigningResult {
+            signature: Signature::new(signature),
+            presig_index: self.presig_index,
+            proof_hash: self.proof_hash,
+            message_hash: self.message_hash,
+            cached: true,
+        })
+    }
+}
+
+/// Persistent cache of recent signing results
+pub struct SignCache {
+    /// Path of the cache file
+    path: PathBuf,
+
+    /// Window for `(child_id, message_hash)` dedup, in seconds (0 disables it)
+    dedup_window_secs: u64,
+
+    /// Cached results, oldest first
+    entries: Vec<CachedSignature>,
+}
+
+impl SignCache {
+    /// Open (or create) the cache under the agent store directory
+    pub fn new(store_path: PathBuf, dedup_window_secs: u64) -> Result<Self> {
+        std::fs::create_dir_all(&store_path)?;
+        let path = store_path.join(CACHE_FILE);
+
+        let entries = if path.exists() {
+            serde_json::from_slice(&std::fs::read(&path)?)?
+        } else {
+            Vec::new()
+        };
+
+        Ok(Self {
+            path,
+            dedup_window_secs,
+            entries,
+        })
+    }
+
+    /// Find a previous result for this request
+    ///
+    /// A key that was used for a different message is an error rather than a
+    /// miss, so a client bug can never be answered with the wrong signature.
+    pub fn lookup(
+        &self,
+        child_id: &ChildId,
+        message_hash: &MessageHash,
+        idempotency_key: Option<&str>,
+        now: u64,
+    ) -> Result<Option<SigningResult>> {
+        if let Some(key) = idempotency_key {
+            let keyed = self.entries.iter().rev().find(|entry| {
+                entry.idempotency_key.as_deref() == Some(key)
+                    && now.saturating_sub(entry.signed_at) < IDEMPOTENCY_KEY_TTL_SECS
+            });
+
+            if let Some(entry) = keyed {
+                if entry.child_id != *child_id || entry.message_hash != *message_hash {
+                    return Err(DaemonError::IdempotencyConflict(key.to_string()));
+                }
+                return entry.to_result().map(Some);
+            }
+        }
+
+        self.entries
+            .iter()
+            .rev()
+            .find(|entry| {
+                entry.child_id == *child_id
+                    && entry.message_hash == *message_hash
+                    && now.saturating_sub(entry.signed_at) < self.dedup_window_secs
+            })
+            .map(CachedSignature::to_result)
+            .transpose()
+    }
+
+    /// Record a completed signature and persist the cache
+    pub fn insert(
+        &mut self,
+        child_id: ChildId,
+        idempotency_key: Option<String>,
+        result: &SigningResult,
+        now: u64,
+    ) -> Result<()> {
+        let retention = self.dedup_window_secs.max(IDEMPOTENCY_KEY_TTL_SECS);
+        self.entries
+            .retain(|entry| now.saturating_sub(entry.signed_at) < retention);
+
+        self.entries.push(CachedSignature {
+            child_id,
+            message_hash: result.message_hash,
+            idempotency_key,
+            signature: hex::encode(result.signature.as_bytes()),
+            presig_index: result.presig_index,
+            proof_hash: result.proof_hash,
+            signed_at: now,
+        });
+
+        self.save()
+    }
+
+    /// Write the cache to disk atomically
+    fn save(&self) -> Result<()> {
+        let temp_path = self.path.with_extension("json.tmp");
+        std::fs::write(&temp_path, serde_json::to_vec(&self.entries)?)?;
+        std::fs::rename(&temp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::TempDir;
+
+    fn result(message: u8, presig_index: u32) -> SigningResult {
+        SigningResult {
+            signature: Signature::new([message; 64]),
+            presig_index,
+            proof_hash: ZkProofHash::new([0u8; 32]),
+            message_hash: MessageHash::new([message; 32]),
+            cached: false,
+        }
+    }
+
+    #[test]
+    fn test_idempotency_key_survives_reopen() {
+        let temp_dir = TempDir::new().unwrap();
+        let child_id = ChildId::new([1u8; 32]);
+        let now = 1_700_000_000;
+
+        {
+            let mut cache = SignCache::new(temp_dir.path().to_path_buf(), 0).unwrap();
+            cache
+                .insert(child_id, Some("retry-1".to_string()), &result(7, 3), now)
+                .unwrap();
+        }
+
+        let cache = SignCache::new(temp_dir.path().to_path_buf(), 0).unwrap();
+        let hit = cache
+            .lookup(
+                &child_id,
+                &MessageHash::new([7; 32]),
+                Some("retry-1"),
+                now + 60,
+            )
+            .unwrap()
+            .unwrap();
+        assert_eq!(hit.presig_index, 3);
+        assert!(hit.cached);
+
+        // Same key for a different message must not return the old signature
+        let err = cache
+            .lookup(
+                &child_id,
+                &MessageHash::new([8; 32]),
+                Some("retry-1"),
+                now + 60,
+            )
+            .unwrap_err();
+        assert!(matches!(err, DaemonError::IdempotencyConflict(_)));
+
+        // Dedup window is disabled, so an unkeyed retry is a miss
+        assert!(cache
+            .lookup(&child_id, &MessageHash::new([7; 32]), None, now + 60)
+            .unwrap()
+            .is_none());
+    }
+
+    #[test]
+    fn test_message_dedup_window() {
+        let temp_dir = TempDir::new().unwrap();
+        let child_id = ChildId::new([1u8; 32]);
+        let now = 1_700_000_000;
+
+        let mut cache = SignCache::new(temp_dir.path().to_path_buf(), 300).unwrap();
+        cache.insert(child_id, None, &result(7, 0), now).unwrap();
+
+        let hash = MessageHash::new([7; 32]);
+        assert!(cache
+            .lookup(&child_id, &hash, None, now + 299)
+            .unwrap()
+            .is_some());
+        assert!(cache
+            .lookup(&child_id, &hash, None, now + 300)
+            .unwrap()
+            .is_none());
+        assert!(cache
+            .lookup(&ChildId::new([2u8; 32]), &hash, None, now)
+            .unwrap()
+            .is_none());
+    }
+}
@@ -1,8 +1,9 @@
 //! Signing operations with zkVM proof generation
 
+use std::sync::atomic::{AtomicU32, Ordering};
 use std::sync::Arc;
-use tokio::sync::RwLock;
-use tracing::{debug, info};
+use tokio::sync::{broadcast, mpsc, watch, Mutex, RwLock};
+use tracing::{debug, info, warn};
 
 use sigil_core::{
     accumulator::{NonMembershipWitness, StoredAccumulator},
@@ -10,11 +11,16 @@ use sigil_core::{
     presig::PresigAgentShare,
     types::{ChainId, MessageHash, Signature, TxHash, ZkProofHash},
     usage::UsageLogEntry,
+    ChildId, DiskHeader,
 };
 
 use crate::agent_store::AgentStore;
+use crate::audit_log::AuditLog;
+use crate::clock::ClockGuard;
 use crate::disk_watcher::DiskWatcher;
 use crate::error::{DaemonError, Result};
+use crate::sign_cache::SignCache;
+use crate::tx_backfill::TxBackfill;
 
 /// Signer handles MPC signature completion
 pub struct Signer {
@@ -35,6 +41,60 @@ pub struct Signer {
 
     /// Agent ID for this daemon (derived from agent's master shard)
     agent_id: Option<AgentId>,
+
+    /// Agent-side audit log of completed signatures
+    audit_log: Option<Arc<RwLock<AuditLog>>>,
+
+    /// Remaining presig count at or below which a warning is raised
+    low_presig_threshold: AtomicU32,
+
+    /// Event broadcast channel
+    event_tx: broadcast::Sender<SigningEvent>,
+
+    /// Cache of recent results for deduplicating retried requests
+    sign_cache: Option<Mutex<SignCache>>,
+
+    /// Transaction hashes found on chain, written at the next disk write
+    tx_backfill: Option<Mutex<TxBackfill>>,
+
+    /// Guards the signing time against clock rollback
+    clock: ClockGuard,
+
+    /// Held shared by each signature in progress; set once draining starts
+    drain_gate: RwLock<bool>,
+}
+
+/// Default remaining presig count that triggers a low-presig warning
+pub const DEFAULT_LOW_PRESIG_THRESHOLD: u32 = 100;
+
+/// Event emitted after a signing operation
+#[derive(Debug, Clone)]
+pub enum SigningEvent {
+    /// A presignature was consumed
+    PresigConsumed {
+        child_id: ChildId,
+        presig_index: u32,
+        presigs_remaining: u32,
+    },
+    /// Remaining presigs fell to or below the warning threshold
+    LowPresigs {
+        child_id: ChildId,
+        presigs_remaining: u32,
+        threshold: u32,
+    },
+    /// The disk is within its expiry warning period
+    ExpiryWarning {
+        child_id: ChildId,
+        days_until_expiry: u32,
+    },
+    /// A logged signature never showed up in a mined transaction
+    SignatureNotLanded {
+        child_id: ChildId,
+        presig_index: u32,
+        chain_id: ChainId,
+        message_hash: MessageHash,
+        signed_at: u64,
+    },
 }
 
 /// Result of a signing operation
@@ -51,6 +111,9 @@ pub struct SigningResult {
 
     /// The message that was signed
     pub message_hash: MessageHash,
+
+    /// Whether this is a replayed earlier result rather than a new signature
+    pub cached: bool,
 }
 
 /// Request for a signing operation
@@ -64,6 +127,135 @@ pub struct SigningRequest {
 
     /// Human-readable description
     pub description: String,
+
+    /// Client-chosen key identifying retries of the same request
+    pub idempotency_key: Option<String>,
+
+    /// Child to sign with (hex child ID or prefix); required when several
+    /// disks are inserted
+    pub child_id: Option<String>,
+}
+
+/// Stage of a signing operation, reported as it starts
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SigningStage {
+    /// Loading the disk and its presigs
+    ReadingDisk,
+    /// Validating the disk against the trusted clock
+    CheckingPolicy,
+    /// Completing a signature from its presig shares
+    Signing,
+    /// Generating the zkVM proof of a signature
+    Proving,
+    /// Committing consumed presigs to the disk; no longer cancellable
+    WritingDisk,
+}
+
+impl SigningStage {
+    /// Name of the stage on the IPC stream
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SigningStage::ReadingDisk => "reading_disk",
+            SigningStage::CheckingPolicy => "checking_policy",
+            SigningStage::Signing => "signing",
+            SigningStage::Proving => "proving",
+            SigningStage::WritingDisk => "writing_disk",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum ControlState {
+    Running,
+    Cancelled,
+    Committed,
+}
+
+/// Progress reporting and cancellation for a signing operation
+///
+/// Cancelling succeeds until the signer commits the consumed presigs (the
+/// start of [`SigningStage::WritingDisk`]); from then on the signature is
+/// completed regardless.
+#[derive(Clone)]
+pub struct SignControl {
+    state: Arc<watch::Sender<ControlState>>,
+    progress: Option<mpsc::UnboundedSender<SigningStage>>,
+}
+
+impl SignControl {
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(watch::channel(ControlState::Running).0),
+            progress: None,
+        }
+    }
+
+    /// Report each stage on this channel as it starts
+    pub fn with_progress(mut self, progress: mpsc::UnboundedSender<SigningStage>) -> Self {
+        self.progress = Some(progress);
+        self
+    }
+
+    /// Cancel the operation; false if its presigs were already committed
+    pub fn cancel(&self) -> bool {
+        self.state.send_if_modified(|state| {
+            let running = *state == ControlState::Running;
+            if running {
+                *state = ControlState::Cancelled;
+            }
+            running
+        });
+        self.is_cancelled()
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        *self.state.borrow() == ControlState::Cancelled
+    }
+
+    /// Wait until the operation is cancelled
+    async fn cancelled(&self) {
+        let mut state = self.state.subscribe();
+        // The sender lives as long as self, so this only returns on cancel
+        let _ = state
+            .wait_for(|state| *state == ControlState::Cancelled)
+            .await;
+    }
+
+    /// Start a stage unless the operation was cancelled
+    fn enter(&self, stage: SigningStage) -> Result<()> {
+        if self.is_cancelled() {
+            info!("Signing cancelled before {}", stage.as_str());
+            return Err(DaemonError::Cancelled);
+        }
+        self.report(stage);
+        Ok(())
+    }
+
+    /// Pass the point of no return: after this, cancelling has no effect
+    fn commit(&self) -> Result<()> {
+        self.state.send_if_modified(|state| {
+            let running = *state == ControlState::Running;
+            if running {
+                *state = ControlState::Committed;
+            }
+            running
+        });
+        self.enter(SigningStage::WritingDisk)
+    }
+
+    fn report(&self, stage: SigningStage) {
+        debug!("Signing stage: {}", stage.as_str());
+        if let Some(progress) = &self.progress {
+            // The receiver going away is handled as a cancel by its owner
+            let _ = progress.send(stage);
+        }
+    }
+}
+
+impl Default for SignControl {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Signer {
@@ -80,6 +272,13 @@ impl Signer {
             accumulator: Arc::new(RwLock::new(None)),
             witnesses: Arc::new(RwLock::new(std::collections::HashMap::new())),
             agent_id: None,
+            audit_log: None,
+            low_presig_threshold: AtomicU32::new(DEFAULT_LOW_PRESIG_THRESHOLD),
+            event_tx: broadcast::channel(16).0,
+            sign_cache: None,
+            tx_backfill: None,
+            clock: ClockGuard::new(),
+            drain_gate: RwLock::new(false),
         }
     }
 
@@ -97,9 +296,81 @@ impl Signer {
             accumulator: Arc::new(RwLock::new(None)),
             witnesses: Arc::new(RwLock::new(std::collections::HashMap::new())),
             agent_id: Some(agent_id),
+            audit_log: None,
+            low_presig_threshold: AtomicU32::new(DEFAULT_LOW_PRESIG_THRESHOLD),
+            event_tx: broadcast::channel(16).0,
+            sign_cache: None,
+            tx_backfill: None,
+            clock: ClockGuard::new(),
+            drain_gate: RwLock::new(false),
         }
     }
 
+    /// Record every completed signature in the given audit log
+    pub fn with_audit_log(mut self, audit_log: Arc<RwLock<AuditLog>>) -> Self {
+        self.audit_log = Some(audit_log);
+        self
+    }
+
+    /// Raise a low-presig warning at or below this many remaining presigs
+    pub fn with_low_presig_threshold(self, threshold: u32) -> Self {
+        self.set_low_presig_threshold(threshold);
+        self
+    }
+
+    /// Change the low-presig warning threshold (e.g. after a config reload)
+    pub fn set_low_presig_threshold(&self, threshold: u32) {
+        self.low_presig_threshold
+            .store(threshold, Ordering::Relaxed);
+    }
+
+    /// Wait for signatures in progress to finish and refuse new ones
+    ///
+    /// Used on shutdown so a signature is never cut off between completing
+    /// and being written to the disk.
+    pub async fn drain(&self) {
+        *self.drain_gate.write().await = true;
+    }
+
+    /// Replay results of retried requests from this cache
+    pub fn with_sign_cache(mut self, sign_cache: SignCache) -> Self {
+        self.sign_cache = Some(Mutex::new(sign_cache));
+        self
+    }
+
+    /// Check signing time with this clock guard
+    pub fn with_clock_guard(mut self, clock: ClockGuard) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// Backfill transaction hashes found on chain at the next disk write
+    pub fn with_tx_backfill(mut self, tx_backfill: TxBackfill) -> Self {
+        self.tx_backfill = Some(Mutex::new(tx_backfill));
+        self
+    }
+
+    /// Get the transaction backfill store, if one is configured
+    pub fn tx_backfill(&self) -> Option<&Mutex<TxBackfill>> {
+        self.tx_backfill.as_ref()
+    }
+
+    /// Broadcast an event raised outside of signing
+    pub(crate) fn notify(&self, event: SigningEvent) {
+        // Send errors only mean nobody is subscribed
+        let _ = self.event_tx.send(event);
+    }
+
+    /// Subscribe to signing events
+    pub fn subscribe(&self) -> broadcast::Receiver<SigningEvent> {
+        self.event_tx.subscribe()
+    }
+
+    /// Get the audit log, if one is configured
+    pub fn audit_log(&self) -> Option<&Arc<RwLock<AuditLog>>> {
+        self.audit_log.as_ref()
+    }
+
     /// Load accumulator from file
     ///
     /// The accumulator should be exported from the mother device and
@@ -187,84 +458,245 @@ impl Signer {
 
     /// Sign a message
     pub async fn sign(&self, request: SigningRequest) -> Result<SigningResult> {
-        info!("Starting signing operation");
+        let mut results = self.sign_batch(vec![request]).await?;
+        Ok(results.remove(0))
+    }
 
-        // 1. Load disk
-        let mut disk = self.disk_watcher.load_full_disk().await?;
+    /// Sign several messages with consecutive presigs
+    ///
+    /// See [`Signer::sign_batch_with_control`].
+    pub async fn sign_batch(&self, requests: Vec<SigningRequest>) -> Result<Vec<SigningResult>> {
+        self.sign_batch_with_control(requests, &SignControl::new())
+            .await
+    }
 
-        // 2. Validate disk
-        let current_time = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap()
-            .as_secs();
+    /// Sign several messages, reporting progress and honoring cancellation
+    ///
+    /// All signatures are completed in memory and committed with a single disk
+    /// write and a single agent-store update. If any item fails, or the
+    /// operation is cancelled before the commit, nothing is written and no
+    /// presig is consumed. Items that replay a cached result don't consume a
+    /// presig either. Every item must select the same child.
+    pub async fn sign_batch_with_control(
+        &self,
+        requests: Vec<SigningRequest>,
+        control: &SignControl,
+    ) -> Result<Vec<SigningResult>> {
+        let draining = self.drain_gate.read().await;
+        if *draining {
+            return Err(DaemonError::ShuttingDown);
+        }
 
-        disk.validate(current_time)?;
+        if requests.is_empty() {
+            return Err(DaemonError::SigningFailed(
+                "Empty signing batch".to_string(),
+            ));
+        }
 
-        // 3. Get next available presig from disk
-        let (presig_index, cold_share) = disk.get_next_presig()?;
-        debug!("Using presig index: {}", presig_index);
+        let child = requests[0].child_id.clone();
+        if requests.iter().any(|request| request.child_id != child) {
+            return Err(DaemonError::SigningFailed(
+                "All batch items must select the same child".to_string(),
+            ));
+        }
 
-        // 4. Get corresponding agent share
-        let child_id = disk.header.child_id;
-        let agent_share = {
-            let mut store = self.agent_store.write().await;
-            store.get_presig_share(&child_id, presig_index)?.clone()
+        info!("Starting signing operation ({} message(s))", requests.len());
+
+        // Held until results are cached so concurrent retries can't both sign
+        let mut sign_cache = match &self.sign_cache {
+            Some(cache) => Some(cache.lock().await),
+            None => None,
         };
 
-        // 5. Verify R points match
-        if cold_share.r_point != agent_share.r_point {
-            return Err(DaemonError::PresigMismatch(format!(
-                "R point mismatch at index {}",
-                presig_index
-            )));
+        // 1. Load disk
+        control.enter(SigningStage::ReadingDisk)?;
+        let mut disk = self.disk_watcher.load_full_disk(child.as_deref()).await?;
+
+        // 2. Validate disk against a clock that hasn't been rolled back past
+        // the latest time recorded on the disk or in the agent store
+        control.enter(SigningStage::CheckingPolicy)?;
+        let high_water = self
+            .agent_store
+            .read()
+            .await
+            .clock_high_water()
+            .max(disk.header.last_seen);
+        let current_time = self.clock.trusted_now(high_water).await?;
+
+        disk.validate(current_time)?;
+
+        // Replay retried requests instead of consuming more presigs
+        let child_id = disk.header.child_id;
+        let mut results = Vec::with_capacity(requests.len());
+        for request in &requests {
+            let cached = match &sign_cache {
+                Some(cache) => cache.lookup(
+                    &child_id,
+                    &request.message_hash,
+                    request.idempotency_key.as_deref(),
+                    current_time,
+                )?,
+                None => None,
+            };
+            results.push(cached);
         }
 
-        // 6. Complete the signature
-        let (signature, proof_hash) = self
-            .complete_signature(
-                &disk.header.child_pubkey,
-                &request.message_hash,
+        // Signed items awaiting commit: (batch position, agent R point, log entry)
+        let mut signed = Vec::new();
+
+        for (position, request) in requests.iter().enumerate() {
+            if let Some(result) = &results[position] {
+                info!(
+                    "Returning cached signature, presig index: {}",
+                    result.presig_index
+                );
+                continue;
+            }
+
+            // 3. Get next available presig from disk
+            control.enter(SigningStage::Signing)?;
+            let (presig_index, cold_share) = disk.get_next_presig()?;
+            debug!("Using presig index: {}", presig_index);
+
+            // 4. Get corresponding agent share
+            let agent_share = {
+                let mut store = self.agent_store.write().await;
+                store.get_presig_share(&child_id, presig_index)?.clone()
+            };
+
+            // 5. Verify R points match
+            if cold_share.r_point != agent_share.r_point {
+                return Err(DaemonError::PresigMismatch(format!(
+                    "R point mismatch at index {}",
+                    presig_index
+                )));
+            }
+
+            // 6. Complete the signature
+            let (signature, proof_hash) = self
+                .complete_signature(
+                    &disk.header.child_pubkey,
+                    &request.message_hash,
+                    presig_index,
+                    cold_share,
+                    &agent_share,
+                    control,
+                )
+                .await?;
+
+            // 7. Mark presig as used on the in-memory disk
+            disk.mark_presig_used(presig_index)?;
+
+            // 8. Create usage log entry
+            // Note: tx_hash would be populated after broadcast
+            let log_entry = UsageLogEntry::new(
                 presig_index,
-                cold_share,
-                &agent_share,
-            )
-            .await?;
-
-        // 7. Mark presig as used on disk
-        disk.mark_presig_used(presig_index)?;
-
-        // 8. Create usage log entry
-        // Note: tx_hash would be populated after broadcast
-        let log_entry = UsageLogEntry::new(
-            presig_index,
-            current_time,
-            request.message_hash,
-            signature,
-            request.chain_id,
-            TxHash::new([0u8; 32]), // Placeholder until broadcast
-            proof_hash,
-            request.description,
-        );
+                current_time,
+                request.message_hash,
+                signature,
+                request.chain_id,
+                TxHash::new([0u8; 32]), // Placeholder until broadcast
+                proof_hash,
+                request.description.clone(),
+            );
+
+            disk.usage_log.push(log_entry.clone())?;
+
+            results[position] = Some(SigningResult {
+                signature,
+                presig_index,
+                proof_hash,
+                message_hash: request.message_hash,
+                cached: false,
+            });
+            signed.push((position, agent_share.r_point, log_entry));
+        }
 
-        disk.usage_log.push(log_entry)?;
+        if !signed.is_empty() {
+            // 9. Write updated disk, raising its clock high-water mark
+            control.commit()?;
+            disk.header.last_seen = current_time;
+            let backfilled = self.apply_tx_backfill(&mut disk).await;
+            self.disk_watcher.write_disk(&disk).await?;
+            self.mark_tx_backfill_written(&child_id, &backfilled).await;
+
+            // 10. Mark agent presigs as used
+            let presig_indices: Vec<u32> = signed
+                .iter()
+                .map(|(_, _, entry)| entry.presig_index)
+                .collect();
+            {
+                let mut store = self.agent_store.write().await;
+                store.mark_presigs_used(&child_id, &presig_indices)?;
+                if let Err(e) = store.raise_clock_high_water(current_time) {
+                    warn!("Failed to persist clock high-water mark: {}", e);
+                }
+            }
 
-        // 9. Write updated disk
-        self.disk_watcher.write_disk(&disk).await?;
+            // 11. Record in the agent-side audit log
+            // The signatures are already on disk, so a failure here is surfaced
+            // at reconciliation as a missing audit entry rather than failing
+            if let Some(audit_log) = &self.audit_log {
+                let mut log = audit_log.write().await;
+                for (_, r_point, log_entry) in &signed {
+                    if let Err(e) = log.append(&child_id, *r_point, log_entry) {
+                        warn!("Failed to append audit record: {}", e);
+                    }
+                }
+            }
 
-        // 10. Mark agent presig as used
-        {
-            let mut store = self.agent_store.write().await;
-            store.mark_presig_used(&child_id, presig_index)?;
+            // 12. Cache results so a retry doesn't consume more presigs
+            if let Some(cache) = &mut sign_cache {
+                for (position, _, _) in &signed {
+                    let result = results[*position]
+                        .as_ref()
+                        .expect("signed item has a result");
+                    let key = requests[*position].idempotency_key.clone();
+                    if let Err(e) = cache.insert(child_id, key, result, current_time) {
+                        warn!("Failed to cache signing result: {}", e);
+                    }
+                }
+            }
+
+            // 13. Notify event subscribers
+            self.emit_signing_events(&disk.header, &presig_indices, current_time);
+
+            info!("Signing complete, presig indices: {:?}", presig_indices);
+        }
+
+        Ok(results.into_iter().flatten().collect())
+    }
+
+    /// Broadcast consumption and warning events for completed signatures
+    fn emit_signing_events(&self, header: &DiskHeader, presig_indices: &[u32], current_time: u64) {
+        let child_id = header.child_id;
+        let presigs_remaining = header.presigs_remaining();
+
+        // Send errors only mean nobody is subscribed
+        for (i, &presig_index) in presig_indices.iter().enumerate() {
+            let _ = self.event_tx.send(SigningEvent::PresigConsumed {
+                child_id,
+                presig_index,
+                presigs_remaining: presigs_remaining + (presig_indices.len() - 1 - i) as u32,
+            });
         }
 
-        info!("Signing complete, presig index: {}", presig_index);
+        let threshold = self.low_presig_threshold.load(Ordering::Relaxed);
+        if presigs_remaining <= threshold {
+            warn!("Only {} presigs remaining", presigs_remaining);
+            let _ = self.event_tx.send(SigningEvent::LowPresigs {
+                child_id,
+                presigs_remaining,
+                threshold,
+            });
+        }
 
-        Ok(SigningResult {
-            signature,
-            presig_index,
-            proof_hash,
-            message_hash: request.message_hash,
-        })
+        if header.expiry.is_warning_period(current_time) {
+            let _ = self.event_tx.send(SigningEvent::ExpiryWarning {
+                child_id,
+                days_until_expiry: header.expiry.days_until_expiry(current_time),
+            });
+        }
     }
 
     /// Complete the ECDSA signature from presig shares
@@ -275,6 +707,7 @@ impl Signer {
         presig_index: u32,
         cold_share: &sigil_core::presig::PresigColdShare,
         agent_share: &PresigAgentShare,
+        control: &SignControl,
     ) -> Result<(Signature, ZkProofHash)> {
         use k256::{
             elliptic_curve::{
@@ -363,15 +796,21 @@ impl Signer {
 
         // Generate proof hash
         let proof_hash = if self.enable_proving {
-            self.generate_zkvm_proof(
-                pubkey,
-                message_hash,
-                presig_index,
-                cold_share,
-                agent_share,
-                &signature,
-            )
-            .await?
+            control.enter(SigningStage::Proving)?;
+            tokio::select! {
+                proof = self.generate_zkvm_proof(
+                    pubkey,
+                    message_hash,
+                    presig_index,
+                    cold_share,
+                    agent_share,
+                    &signature,
+                ) => proof?,
+                _ = control.cancelled() => {
+                    info!("Signing cancelled during proof generation");
+                    return Err(DaemonError::Cancelled);
+                }
+            }
         } else {
             // In non-proving mode, just hash the signature as placeholder
             let hash = sigil_core::crypto::sha256(&sig_bytes);
@@ -414,8 +853,18 @@ impl Signer {
 
     /// Update transaction hash in usage log after broadcast
     #[allow(dead_code)]
-    pub async fn update_tx_hash(&self, presig_index: u32, tx_hash: TxHash) -> Result<()> {
-        let mut disk = self.disk_watcher.load_full_disk().await?;
+    pub async fn update_tx_hash(
+        &self,
+        child: Option<&str>,
+        presig_index: u32,
+        tx_hash: TxHash,
+    ) -> Result<()> {
+        let draining = self.drain_gate.read().await;
+        if *draining {
+            return Err(DaemonError::ShuttingDown);
+        }
+
+        let mut disk = self.disk_watcher.load_full_disk(child).await?;
 
         // Find the log entry and update it
         for entry in &mut disk.usage_log.entries {
@@ -425,10 +874,53 @@ impl Signer {
             }
         }
 
+        let backfilled = self.apply_tx_backfill(&mut disk).await;
         self.disk_watcher.write_disk(&disk).await?;
 
+        let child_id = disk.header.child_id;
+        self.mark_tx_backfill_written(&child_id, &backfilled).await;
+        if let Some(tx_backfill) = &self.tx_backfill {
+            // The hash given by hand wins over anything found on chain later
+            if let Err(e) = tx_backfill
+                .lock()
+                .await
+                .record_written(child_id, presig_index, tx_hash)
+            {
+                warn!("Failed to record tx hash in backfill store: {}", e);
+            }
+        }
+
         Ok(())
     }
+
+    /// Fill transaction hashes found on chain into a disk about to be written
+    async fn apply_tx_backfill(&self, disk: &mut sigil_core::DiskFormat) -> Vec<u32> {
+        match &self.tx_backfill {
+            Some(tx_backfill) => tx_backfill.lock().await.apply(disk),
+            None => Vec::new(),
+        }
+    }
+
+    /// Record that backfilled hashes reached the disk
+    async fn mark_tx_backfill_written(&self, child_id: &ChildId, presig_indices: &[u32]) {
+        if presig_indices.is_empty() {
+            return;
+        }
+        if let Some(tx_backfill) = &self.tx_backfill {
+            info!(
+                "Backfilled tx hashes for presig indices {:?}",
+                presig_indices
+            );
+            // Left unmarked, the hashes are simply applied again next time
+            if let Err(e) = tx_backfill
+                .lock()
+                .await
+                .mark_written(child_id, presig_indices)
+            {
+                warn!("Failed to update tx backfill store: {}", e);
+            }
+        }
+    }
 }
 
 /// Normalize s to low-S form per BIP-62
@@ -475,4 +967,88 @@ fn scalar_gt_bytes(a: &[u8], b: &[u8; 32]) -> bool {
 #[cfg(test)]
 mod tests {
     // Integration tests would require full setup with disk and agent store
+
+    use super::*;
+    use sigil_core::{crypto::DerivationPath, presig::PresigColdShare, DiskFormat, PublicKey};
+
+    #[tokio::test]
+    async fn test_sign_refused_when_clock_behind_disk() {
+        let dir = tempfile::tempdir().unwrap();
+        let now = crate::clock::system_time();
+
+        let mut header = DiskHeader::new(
+            ChildId::new([0x11; 32]),
+            PublicKey::new([2u8; 33]),
+            DerivationPath::ethereum_hardened(0),
+            1,
+            now,
+        );
+        // A previous signature was recorded a day from "now"
+        header.last_seen = now + 86_400;
+        let presigs = vec![PresigColdShare::new([1u8; 33], [1u8; 32], [1u8; 32])];
+        let disk_dir = dir.path().join("disk");
+        std::fs::create_dir_all(&disk_dir).unwrap();
+        std::fs::write(
+            disk_dir.join("sigil.disk"),
+            DiskFormat::new(header, presigs).to_bytes(),
+        )
+        .unwrap();
+
+        let disk_watcher = Arc::new(DiskWatcher::new(disk_dir.display().to_string()));
+        disk_watcher.scan_for_disks().await.unwrap();
+        let agent_store = Arc::new(RwLock::new(
+            AgentStore::new(dir.path().join("store")).unwrap(),
+        ));
+        let signer = Signer::new(agent_store, disk_watcher, false);
+
+        let result = signer
+            .sign(SigningRequest {
+                message_hash: MessageHash::new([3u8; 32]),
+                chain_id: ChainId::ETHEREUM,
+                description: "test".to_string(),
+                idempotency_key: None,
+                child_id: None,
+            })
+            .await;
+        assert!(matches!(result, Err(DaemonError::ClockRollback { .. })));
+
+        // Once drained, new signatures are refused outright
+        signer.drain().await;
+        let result = signer
+            .sign(SigningRequest {
+                message_hash: MessageHash::new([3u8; 32]),
+                chain_id: ChainId::ETHEREUM,
+                description: "test".to_string(),
+                idempotency_key: None,
+                child_id: None,
+            })
+            .await;
+        assert!(matches!(result, Err(DaemonError::ShuttingDown)));
+    }
+
+    #[tokio::test]
+    async fn test_sign_control_cancel_and_commit() {
+        let (tx, mut stages) = mpsc::unbounded_channel();
+        let control = SignControl::new().with_progress(tx);
+        control.enter(SigningStage::ReadingDisk).unwrap();
+        assert_eq!(stages.recv().await, Some(SigningStage::ReadingDisk));
+
+        // Cancelling stops the next stage, and the commit
+        let cancelled = control.clone();
+        assert!(cancelled.cancel());
+        tokio::time::timeout(std::time::Duration::from_secs(1), control.cancelled())
+            .await
+            .unwrap();
+        assert!(matches!(
+            control.enter(SigningStage::Signing),
+            Err(DaemonError::Cancelled)
+        ));
+        assert!(matches!(control.commit(), Err(DaemonError::Cancelled)));
+
+        // After the commit a cancel is refused
+        let control = SignControl::new();
+        control.commit().unwrap();
+        assert!(!control.cancel());
+        assert!(!control.is_cancelled());
+    }
 }
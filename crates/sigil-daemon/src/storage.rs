@@ -0,0 +1,610 @@
+//! Disk storage backends
+//!
+//! A Sigil disk is a fixed-layout image (see `sigil_core::disk`). It can live
+//! in a `sigil.disk` file on a mounted filesystem, directly on a raw block
+//! device with no filesystem at all, or in memory for tests. Reading the raw
+//! device keeps desktop auto-mounters and FAT metadata writes out of the
+//! signing path.
+
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use serde::{Deserialize, Serialize};
+use sigil_core::DiskRegion;
+use tracing::debug;
+
+use crate::error::{DaemonError, Result};
+
+/// Sector size used to align raw device I/O when the device reports none
+pub const SECTOR_SIZE: usize = 512;
+
+/// Minimum buffer alignment for direct I/O (covers 512-byte and 4K-sector
+/// devices; larger logical blocks align to the block size)
+const DIRECT_IO_ALIGN: usize = 4096;
+
+/// Largest image read from a backend (a 1.44MB floppy)
+pub const MAX_IMAGE_SIZE: u64 = 1_474_560;
+
+/// Random-access storage holding a Sigil disk image
+///
+/// Operations are blocking; async callers should go through [`run_blocking`].
+pub trait DiskBackend: Send + Sync + std::fmt::Debug {
+    /// File path or device node backing this disk
+    fn path(&self) -> &Path;
+
+    /// Size of the underlying storage in bytes
+    fn len(&self) -> Result<u64>;
+
+    /// Read exactly `buf.len()` bytes starting at `offset`
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<()>;
+
+    /// Write `data` starting at `offset`
+    fn write_at(&self, offset: u64, data: &[u8]) -> Result<()>;
+
+    /// Flush written data to stable storage
+    fn sync(&self) -> Result<()>;
+
+    /// Whether the storage is empty
+    fn is_empty(&self) -> Result<bool> {
+        Ok(self.len()? == 0)
+    }
+
+    /// Read the whole image
+    fn read_image(&self) -> Result<Vec<u8>> {
+        let len = self.len()?.min(MAX_IMAGE_SIZE) as usize;
+        let mut bytes = vec![0u8; len];
+        self.read_at(0, &mut bytes)?;
+        Ok(bytes)
+    }
+
+    /// Replace the image and flush it to stable storage
+    fn write_image(&self, bytes: &[u8]) -> Result<()> {
+        self.write_at(0, bytes)?;
+        self.sync()
+    }
+
+    /// Write `regions` in order, then read them back
+    ///
+    /// Each region is synced before the next is written, so an interrupted
+    /// update leaves only a prefix of the regions on the media.
+    fn write_regions(&self, regions: &[DiskRegion]) -> Result<()> {
+        for region in regions {
+            self.write_at(region.offset as u64, &region.bytes)?;
+            self.sync()?;
+        }
+        for region in regions {
+            self.verify_at(region.offset as u64, &region.bytes)?;
+        }
+        Ok(())
+    }
+
+    /// Check that the media holds `expected` at `offset`
+    fn verify_at(&self, offset: u64, expected: &[u8]) -> Result<()> {
+        let mut actual = vec![0u8; expected.len()];
+        self.read_at(offset, &mut actual)?;
+        if actual != expected {
+            return Err(DaemonError::DiskValidationFailed(format!(
+                "Read-back verification failed at offset {:#x}",
+                offset
+            )));
+        }
+        Ok(())
+    }
+}
+
+/// Run a blocking backend operation without stalling the async runtime
+pub async fn run_blocking<T, F>(backend: &Arc<dyn DiskBackend>, op: F) -> Result<T>
+where
+    T: Send + 'static,
+    F: FnOnce(&dyn DiskBackend) -> Result<T> + Send + 'static,
+{
+    let backend = Arc::clone(backend);
+    tokio::task::spawn_blocking(move || op(backend.as_ref()))
+        .await
+        .map_err(|e| DaemonError::Io(std::io::Error::other(e)))?
+}
+
+/// A `sigil.disk` image file on a mounted filesystem
+#[derive(Debug)]
+pub struct FileImage {
+    path: PathBuf,
+}
+
+impl FileImage {
+    /// Use the image file at `path`
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl DiskBackend for FileImage {
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn len(&self) -> Result<u64> {
+        Ok(std::fs::metadata(&self.path)?.len())
+    }
+
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<()> {
+        let mut file = File::open(&self.path)?;
+        file.seek(SeekFrom::Start(offset))?;
+        file.read_exact(buf)?;
+        Ok(())
+    }
+
+    fn write_at(&self, offset: u64, data: &[u8]) -> Result<()> {
+        let mut file = OpenOptions::new().write(true).open(&self.path)?;
+        file.seek(SeekFrom::Start(offset))?;
+        file.write_all(data)?;
+        Ok(())
+    }
+
+    fn sync(&self) -> Result<()> {
+        OpenOptions::new()
+            .write(true)
+            .open(&self.path)?
+            .sync_all()?;
+        Ok(())
+    }
+
+    fn write_image(&self, bytes: &[u8]) -> Result<()> {
+        // Unlike a device, a file can shrink; drop any stale tail
+        let mut file = OpenOptions::new().write(true).open(&self.path)?;
+        file.write_all(bytes)?;
+        file.set_len(bytes.len() as u64)?;
+        file.sync_all()?;
+        Ok(())
+    }
+}
+
+/// A Sigil disk written directly to a block device (e.g. `/dev/fd0`)
+///
+/// The image starts at offset 0 of the device. On Linux the device is opened
+/// with `O_DIRECT | O_SYNC` so writes bypass the page cache and reach the
+/// media before returning; all I/O is done in whole, aligned sectors of the
+/// device's logical block size.
+#[derive(Debug)]
+pub struct RawDevice {
+    path: PathBuf,
+    direct: bool,
+}
+
+impl RawDevice {
+    /// Use the block device at `path` with direct I/O where supported
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self {
+            path: path.into(),
+            direct: true,
+        }
+    }
+
+    /// Enable or disable direct I/O (buffered I/O is still fsynced)
+    pub fn with_direct_io(mut self, direct: bool) -> Self {
+        self.direct = direct;
+        self
+    }
+
+    /// Open the device, falling back to buffered I/O where `O_DIRECT` is refused
+    fn open(&self, write: bool) -> Result<File> {
+        #[cfg(target_os = "linux")]
+        if self.direct {
+            use std::os::unix::fs::OpenOptionsExt;
+
+            match OpenOptions::new()
+                .read(true)
+                .write(write)
+                .custom_flags(libc::O_DIRECT | libc::O_SYNC)
+                .open(&self.path)
+            {
+                Ok(file) => return Ok(file),
+                Err(e) if e.raw_os_error() == Some(libc::EINVAL) => {
+                    debug!(
+                        "{:?} does not support O_DIRECT, using buffered I/O",
+                        self.path
+                    );
+                }
+                Err(e) => return Err(e.into()),
+            }
+        }
+
+        Ok(OpenOptions::new()
+            .read(true)
+            .write(write)
+            .open(&self.path)?)
+    }
+
+    /// Read whole sectors covering `[offset, offset + len)`
+    ///
+    /// Returns the buffer and how many bytes of it came from the device; an
+    /// image that doesn't end on a sector boundary leaves the rest zeroed.
+    fn read_sectors(
+        file: &mut File,
+        sector: usize,
+        offset: u64,
+        len: usize,
+    ) -> Result<(AlignedBuf, usize)> {
+        let start = offset - offset % sector as u64;
+        let end = (offset + len as u64).div_ceil(sector as u64) * sector as u64;
+
+        let mut buf = AlignedBuf::new((end - start) as usize, sector.max(DIRECT_IO_ALIGN));
+        file.seek(SeekFrom::Start(start))?;
+
+        let mut filled = 0;
+        let slice = buf.as_mut_slice();
+        while filled < slice.len() {
+            match file.read(&mut slice[filled..])? {
+                0 => break,
+                n => filled += n,
+            }
+        }
+
+        Ok((buf, filled))
+    }
+
+    /// Read `buf.len()` bytes at `offset` in whole `sector`-sized units
+    fn read_with(file: &mut File, sector: usize, offset: u64, buf: &mut [u8]) -> Result<()> {
+        let (sectors, filled) = Self::read_sectors(file, sector, offset, buf.len())?;
+        let skip = (offset % sector as u64) as usize;
+        if filled < skip + buf.len() {
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+        }
+        buf.copy_from_slice(&sectors.as_slice()[skip..skip + buf.len()]);
+        Ok(())
+    }
+
+    /// Write `data` at `offset` in whole `sector`-sized units
+    fn write_with(file: &mut File, sector: usize, offset: u64, data: &[u8]) -> Result<()> {
+        // Read-modify-write so partial sectors keep their neighbours
+        let (mut sectors, _) = Self::read_sectors(file, sector, offset, data.len())?;
+        let skip = (offset % sector as u64) as usize;
+        sectors.as_mut_slice()[skip..skip + data.len()].copy_from_slice(data);
+
+        file.seek(SeekFrom::Start(offset - skip as u64))?;
+        file.write_all(sectors.as_slice())?;
+        file.sync_all()?;
+        Ok(())
+    }
+}
+
+/// Logical block size of the device behind `file`
+///
+/// `O_DIRECT` transfers must be aligned to it; regular files and devices
+/// that don't report one use [`SECTOR_SIZE`].
+#[cfg(target_os = "linux")]
+fn logical_block_size(file: &File) -> usize {
+    use std::os::unix::fs::FileTypeExt;
+    use std::os::unix::io::AsRawFd;
+
+    let is_block_device = file
+        .metadata()
+        .map(|m| m.file_type().is_block_device())
+        .unwrap_or(false);
+    if !is_block_device {
+        return SECTOR_SIZE;
+    }
+
+    let mut size: libc::c_int = 0;
+    // SAFETY: BLKSSZGET writes a single c_int through the pointer
+    let rc = unsafe { libc::ioctl(file.as_raw_fd(), libc::BLKSSZGET, &mut size) };
+    match usize::try_from(size) {
+        Ok(size) if rc == 0 && size.is_power_of_two() && size >= SECTOR_SIZE => size,
+        _ => SECTOR_SIZE,
+    }
+}
+
+#[cfg(not(target_os = "linux"))]
+fn logical_block_size(_file: &File) -> usize {
+    SECTOR_SIZE
+}
+
+impl DiskBackend for RawDevice {
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn len(&self) -> Result<u64> {
+        // Block devices report a zero metadata length; ask the device instead
+        let mut file = self.open(false)?;
+        Ok(file.seek(SeekFrom::End(0))?)
+    }
+
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<()> {
+        let mut file = self.open(false)?;
+        let sector = logical_block_size(&file);
+        Self::read_with(&mut file, sector, offset, buf)
+    }
+
+    fn write_at(&self, offset: u64, data: &[u8]) -> Result<()> {
+        let mut file = self.open(true)?;
+        let sector = logical_block_size(&file);
+        Self::write_with(&mut file, sector, offset, data)
+    }
+
+    fn sync(&self) -> Result<()> {
+        self.open(true)?.sync_all()?;
+        Ok(())
+    }
+}
+
+/// In-memory disk image, for tests and dry runs
+#[derive(Debug)]
+pub struct MemoryImage {
+    path: PathBuf,
+    bytes: Mutex<Vec<u8>>,
+}
+
+impl MemoryImage {
+    /// Wrap an image; `name` is reported as its path
+    pub fn new(name: impl Into<PathBuf>, bytes: Vec<u8>) -> Self {
+        Self {
+            path: name.into(),
+            bytes: Mutex::new(bytes),
+        }
+    }
+
+    /// Copy of the current image contents
+    pub fn bytes(&self) -> Vec<u8> {
+        self.lock().clone()
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Vec<u8>> {
+        self.bytes.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+impl DiskBackend for MemoryImage {
+    fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn len(&self) -> Result<u64> {
+        Ok(self.lock().len() as u64)
+    }
+
+    fn read_at(&self, offset: u64, buf: &mut [u8]) -> Result<()> {
+        let bytes = self.lock();
+        let start = offset as usize;
+        let src = bytes
+            .get(start..start + buf.len())
+            .ok_or_else(|| std::io::Error::from(std::io::ErrorKind::UnexpectedEof))?;
+        buf.copy_from_slice(src);
+        Ok(())
+    }
+
+    fn write_at(&self, offset: u64, data: &[u8]) -> Result<()> {
+        let mut bytes = self.lock();
+        let start = offset as usize;
+        if bytes.len() < start + data.len() {
+            bytes.resize(start + data.len(), 0);
+        }
+        bytes[start..start + data.len()].copy_from_slice(data);
+        Ok(())
+    }
+
+    fn sync(&self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// How to recognise a raw Sigil device
+///
+/// A device matches when its node equals `path` (if set) and it carries every
+/// listed udev property, e.g. `{"ID_MODEL": "USB_Floppy"}` or
+/// `{"ID_SERIAL": "..."}`. Property matching needs udev and is Linux only.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RawDeviceMatch {
+    /// Device node, e.g. `/dev/fd0`
+    #[serde(default)]
+    pub path: Option<PathBuf>,
+
+    /// udev properties the device must have
+    #[serde(default)]
+    pub properties: std::collections::BTreeMap<String, String>,
+}
+
+/// Find the device nodes currently matching any of `matches`
+pub fn discover_raw_devices(matches: &[RawDeviceMatch]) -> Vec<PathBuf> {
+    let mut found = Vec::new();
+
+    for rule in matches {
+        let nodes = if rule.properties.is_empty() {
+            rule.path
+                .iter()
+                .filter(|path| path.exists())
+                .cloned()
+                .collect()
+        } else {
+            match udev_devices(&rule.properties) {
+                Ok(nodes) => nodes
+                    .into_iter()
+                    .filter(|node| rule.path.as_ref().map_or(true, |path| path == node))
+                    .collect(),
+                Err(e) => {
+                    debug!("udev device lookup failed: {}", e);
+                    Vec::new()
+                }
+            }
+        };
+
+        for node in nodes {
+            if !found.contains(&node) {
+                found.push(node);
+            }
+        }
+    }
+
+    found
+}
+
+/// Block device nodes carrying all of `properties`
+#[cfg(target_os = "linux")]
+fn udev_devices(
+    properties: &std::collections::BTreeMap<String, String>,
+) -> std::io::Result<Vec<PathBuf>> {
+    let mut enumerator = tokio_udev::Enumerator::new()?;
+    enumerator.match_subsystem("block")?;
+
+    Ok(enumerator
+        .scan_devices()?
+        .filter(|device| {
+            properties.iter().all(|(key, value)| {
+                device
+                    .property_value(key)
+                    .is_some_and(|actual| actual == value.as_str())
+            })
+        })
+        .filter_map(|device| device.devnode().map(Path::to_path_buf))
+        .collect())
+}
+
+#[cfg(not(target_os = "linux"))]
+fn udev_devices(
+    _properties: &std::collections::BTreeMap<String, String>,
+) -> std::io::Result<Vec<PathBuf>> {
+    Err(std::io::Error::new(
+        std::io::ErrorKind::Unsupported,
+        "udev property matching is only available on Linux",
+    ))
+}
+
+/// Heap buffer whose usable slice is aligned for direct I/O
+struct AlignedBuf {
+    storage: Vec<u8>,
+    start: usize,
+    len: usize,
+}
+
+impl AlignedBuf {
+    fn new(len: usize, align: usize) -> Self {
+        let storage = vec![0u8; len + align];
+        let start = storage.as_ptr().align_offset(align);
+        Self {
+            storage,
+            start,
+            len,
+        }
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        &self.storage[self.start..self.start + self.len]
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [u8] {
+        &mut self.storage[self.start..self.start + self.len]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn exercise(backend: &dyn DiskBackend) {
+        backend.write_image(&[0xAA; 2048]).unwrap();
+
+        // Unaligned write spanning a sector boundary keeps its neighbours
+        backend.write_at(510, &[1, 2, 3, 4]).unwrap();
+        backend.sync().unwrap();
+
+        let mut buf = [0u8; 6];
+        backend.read_at(509, &mut buf).unwrap();
+        assert_eq!(buf, [0xAA, 1, 2, 3, 4, 0xAA]);
+
+        let image = backend.read_image().unwrap();
+        assert_eq!(image.len(), 2048);
+        assert_eq!(&image[510..514], &[1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn test_file_image_backend() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sigil.disk");
+        std::fs::write(&path, [0u8; 4096]).unwrap();
+
+        let backend = FileImage::new(&path);
+        exercise(&backend);
+        // Writing a whole image truncates the file to the image size
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), 2048);
+    }
+
+    #[test]
+    fn test_raw_device_backend() {
+        // A regular file stands in for the block device
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("fd0");
+        std::fs::write(&path, [0u8; 2048]).unwrap();
+
+        exercise(&RawDevice::new(&path));
+        exercise(&RawDevice::new(&path).with_direct_io(false));
+    }
+
+    #[test]
+    fn test_raw_device_4k_sectors() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("sda");
+        std::fs::write(&path, [0xAA; 8192]).unwrap();
+        let mut file = OpenOptions::new()
+            .read(true)
+            .write(true)
+            .open(&path)
+            .unwrap();
+        assert_eq!(logical_block_size(&file), SECTOR_SIZE);
+
+        // A write straddling a 4K block keeps both blocks' neighbours
+        RawDevice::write_with(&mut file, 4096, 4094, &[1, 2, 3, 4]).unwrap();
+        let mut buf = [0u8; 6];
+        RawDevice::read_with(&mut file, 4096, 4093, &mut buf).unwrap();
+        assert_eq!(buf, [0xAA, 1, 2, 3, 4, 0xAA]);
+        assert_eq!(std::fs::metadata(&path).unwrap().len(), 8192);
+    }
+
+    #[test]
+    fn test_memory_backend() {
+        let backend = MemoryImage::new("memory", Vec::new());
+        exercise(&backend);
+        assert_eq!(backend.bytes().len(), 2048);
+    }
+
+    #[test]
+    fn test_discover_raw_devices_by_path() {
+        let dir = tempfile::tempdir().unwrap();
+        let present = dir.path().join("fd0");
+        std::fs::write(&present, []).unwrap();
+
+        let matches = vec![
+            RawDeviceMatch {
+                path: Some(present.clone()),
+                ..Default::default()
+            },
+            RawDeviceMatch {
+                path: Some(dir.path().join("fd1")),
+                ..Default::default()
+            },
+        ];
+        assert_eq!(discover_raw_devices(&matches), vec![present]);
+    }
+
+    #[test]
+    fn test_write_regions_verifies_each_region() {
+        let backend = MemoryImage::new("mem", vec![0u8; 1024]);
+        let regions = vec![
+            DiskRegion::new(600, &[9, 9, 9]),
+            DiskRegion::new(1020, &[1, 2, 3, 4, 5, 6]),
+            DiskRegion::new(0, &[7]),
+        ];
+        backend.write_regions(&regions).unwrap();
+
+        let bytes = backend.bytes();
+        assert_eq!(bytes.len(), 1026);
+        assert_eq!(&bytes[600..603], &[9, 9, 9]);
+        assert_eq!(&bytes[1020..], &[1, 2, 3, 4, 5, 6]);
+        assert_eq!(bytes[0], 7);
+
+        assert!(matches!(
+            backend.verify_at(600, &[9, 9, 8]),
+            Err(DaemonError::DiskValidationFailed(_))
+        ));
+    }
+}
@@ -0,0 +1,211 @@
+//! systemd integration
+//!
+//! Implements the small parts of the systemd protocols the daemon needs
+//! without linking libsystemd: taking socket-activated listeners from
+//! `LISTEN_FDS`, and `sd_notify` readiness, status and watchdog messages sent
+//! to `NOTIFY_SOCKET`. Everything is a no-op when not started by systemd,
+//! and on platforms without it.
+
+#[cfg(unix)]
+use std::os::fd::{FromRawFd, RawFd};
+#[cfg(unix)]
+use std::os::unix::net::{UnixDatagram, UnixListener};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+#[cfg(unix)]
+use tracing::debug;
+use tracing::warn;
+
+#[cfg(unix)]
+use crate::error::{DaemonError, Result};
+
+/// First file descriptor passed by socket activation
+#[cfg(unix)]
+const LISTEN_FDS_START: RawFd = 3;
+
+/// Take the first socket-activated listener passed by systemd
+///
+/// Follows `sd_listen_fds(3)`: the descriptors are only used when
+/// `LISTEN_PID` names this process, and the variables are cleared so child
+/// processes don't inherit them.
+#[cfg(unix)]
+pub fn take_listener() -> Result<Option<UnixListener>> {
+    let pid = std::env::var("LISTEN_PID").ok();
+    let fds = std::env::var("LISTEN_FDS").ok();
+    std::env::remove_var("LISTEN_PID");
+    std::env::remove_var("LISTEN_FDS");
+    std::env::remove_var("LISTEN_FDNAMES");
+
+    let (Some(pid), Some(fds)) = (pid, fds) else {
+        return Ok(None);
+    };
+    if pid.parse::<u32>().ok() != Some(std::process::id()) {
+        debug!("LISTEN_PID {} is not ours, ignoring passed sockets", pid);
+        return Ok(None);
+    }
+
+    let count: RawFd = fds
+        .parse()
+        .map_err(|_| DaemonError::Config(format!("Invalid LISTEN_FDS: {}", fds)))?;
+    if count < 1 {
+        return Ok(None);
+    }
+    if count > 1 {
+        warn!("systemd passed {} sockets, using the first", count);
+    }
+
+    for fd in LISTEN_FDS_START..LISTEN_FDS_START + count {
+        // SAFETY: fcntl on a descriptor we were handed; failure is reported
+        if unsafe { libc::fcntl(fd, libc::F_SETFD, libc::FD_CLOEXEC) } < 0 {
+            return Err(std::io::Error::last_os_error().into());
+        }
+    }
+
+    // SAFETY: systemd passes ownership of the listening socket at fd 3
+    let listener = unsafe { UnixListener::from_raw_fd(LISTEN_FDS_START) };
+    listener.set_nonblocking(true)?;
+    Ok(Some(listener))
+}
+
+/// Sends `sd_notify` state updates to the service manager
+#[derive(Debug, Default)]
+pub struct Notifier {
+    /// `NOTIFY_SOCKET` address, when running under systemd
+    socket: Option<PathBuf>,
+
+    /// Interval the service manager expects watchdog pings at
+    watchdog: Option<Duration>,
+}
+
+impl Notifier {
+    /// Read `NOTIFY_SOCKET` and `WATCHDOG_USEC` from the environment
+    pub fn from_env() -> Self {
+        let socket = std::env::var_os("NOTIFY_SOCKET").map(PathBuf::from);
+
+        let watchdog_pid_ok = std::env::var("WATCHDOG_PID")
+            .map(|pid| pid.parse::<u32>().ok() == Some(std::process::id()))
+            .unwrap_or(true);
+        let watchdog = std::env::var("WATCHDOG_USEC")
+            .ok()
+            .and_then(|usec| usec.parse::<u64>().ok())
+            .filter(|&usec| usec > 0 && watchdog_pid_ok)
+            .map(Duration::from_micros);
+
+        Self { socket, watchdog }
+    }
+
+    /// Whether the daemon was started with a notification socket
+    pub fn is_enabled(&self) -> bool {
+        self.socket.is_some()
+    }
+
+    /// Interval the watchdog must be pinged at, if the watchdog is enabled
+    pub fn watchdog_interval(&self) -> Option<Duration> {
+        self.watchdog
+    }
+
+    /// Startup finished; the daemon is serving requests
+    pub fn ready(&self, status: &str) {
+        self.notify(&format!("READY=1\nSTATUS={}", status));
+    }
+
+    /// Free-form status shown by `systemctl status`
+    pub fn status(&self, status: &str) {
+        self.notify(&format!("STATUS={}", status));
+    }
+
+    /// Keep-alive ping for `WatchdogSec=`
+    pub fn watchdog(&self) {
+        self.notify("WATCHDOG=1");
+    }
+
+    /// Configuration reload has started
+    pub fn reloading(&self) {
+        #[cfg(unix)]
+        {
+            let mut now = libc::timespec {
+                tv_sec: 0,
+                tv_nsec: 0,
+            };
+            // SAFETY: clock_gettime only writes into the timespec we pass
+            unsafe { libc::clock_gettime(libc::CLOCK_MONOTONIC, &mut now) };
+            let usec = now.tv_sec as u64 * 1_000_000 + now.tv_nsec as u64 / 1_000;
+            self.notify(&format!("RELOADING=1\nMONOTONIC_USEC={}", usec));
+        }
+        #[cfg(not(unix))]
+        self.notify("RELOADING=1");
+    }
+
+    /// Shutdown has started
+    pub fn stopping(&self, status: &str) {
+        self.notify(&format!("STOPPING=1\nSTATUS={}", status));
+    }
+
+    /// Send a raw notification; errors are logged, never fatal
+    pub fn notify(&self, state: &str) {
+        let Some(socket) = &self.socket else {
+            return;
+        };
+        if let Err(e) = send(socket, state) {
+            warn!("sd_notify failed: {}", e);
+        }
+    }
+}
+
+/// Send one datagram to the notification socket
+#[cfg(unix)]
+fn send(socket: &Path, state: &str) -> std::io::Result<()> {
+    let datagram = UnixDatagram::unbound()?;
+
+    // A leading '@' names a Linux abstract socket
+    #[cfg(target_os = "linux")]
+    if let Some(name) = socket.to_str().and_then(|s| s.strip_prefix('@')) {
+        use std::os::linux::net::SocketAddrExt;
+
+        let addr = std::os::unix::net::SocketAddr::from_abstract_name(name)?;
+        datagram.send_to_addr(state.as_bytes(), &addr)?;
+        return Ok(());
+    }
+
+    datagram.send_to(state.as_bytes(), socket)?;
+    Ok(())
+}
+
+#[cfg(not(unix))]
+fn send(_socket: &Path, _state: &str) -> std::io::Result<()> {
+    Ok(())
+}
+
+#[cfg(all(test, unix))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_notifications_reach_socket() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("notify.sock");
+        let receiver = UnixDatagram::bind(&path).unwrap();
+
+        let notifier = Notifier {
+            socket: Some(path),
+            watchdog: None,
+        };
+        notifier.ready("1 disk");
+        notifier.watchdog();
+
+        let mut buf = [0u8; 256];
+        let n = receiver.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..n], b"READY=1\nSTATUS=1 disk");
+        let n = receiver.recv(&mut buf).unwrap();
+        assert_eq!(&buf[..n], b"WATCHDOG=1");
+    }
+
+    #[test]
+    fn test_disabled_without_socket() {
+        let notifier = Notifier::default();
+        assert!(!notifier.is_enabled());
+        // Nothing to send to, but must not fail
+        notifier.ready("idle");
+    }
+}
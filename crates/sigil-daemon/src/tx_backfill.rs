@@ -0,0 +1,318 @@
+//! Transaction hashes found on chain, waiting to be written to their disk
+//!
+//! The [`ChainWatcher`](crate::chain_watcher::ChainWatcher) records here
+//! which logged signature landed in which transaction. The disk may not be
+//! inserted when that happens, so the signer fills the hashes into the usage
+//! log at its next write to that child's disk. The store also remembers
+//! which signatures were reported as never landing, and how far the watcher
+//! has scanned the chain and each child's audit log, so all of it survives
+//! daemon restarts.
+
+use serde::{Deserialize, Serialize};
+use std::path::PathBuf;
+
+use sigil_core::types::TxHash;
+use sigil_core::{ChildId, DiskFormat};
+
+use crate::error::Result;
+
+/// Store file name under the agent store
+const BACKFILL_FILE: &str = "tx_backfill.json";
+
+/// A signature matched to a mined transaction
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct LandedTx {
+    pub child_id: ChildId,
+    pub presig_index: u32,
+    pub tx_hash: TxHash,
+    /// Block the transaction was mined in (0 when recorded by hand)
+    pub block_number: u64,
+    /// Whether the hash has reached the disk's usage log
+    pub written: bool,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct BackfillState {
+    /// First block the watcher hasn't scanned yet
+    next_block: Option<u64>,
+
+    /// Timestamp of the first block the watcher scanned
+    scanned_since: Option<u64>,
+
+    landed: Vec<LandedTx>,
+
+    /// Signatures already reported as never landing `(child, presig index)`
+    not_landed: Vec<(ChildId, u32)>,
+
+    /// Per child, the audit log offset before which every record is settled
+    #[serde(default)]
+    audit_offsets: Vec<(ChildId, u64)>,
+}
+
+/// Persistent record of landed transactions and watcher progress
+pub struct TxBackfill {
+    /// Path of the store file
+    path: PathBuf,
+
+    state: BackfillState,
+}
+
+impl TxBackfill {
+    /// Open (or create) the store under the agent store directory
+    pub fn new(store_path: PathBuf) -> Result<Self> {
+        std::fs::create_dir_all(&store_path)?;
+        let path = store_path.join(BACKFILL_FILE);
+
+        let state = if path.exists() {
+            serde_json::from_slice(&std::fs::read(&path)?)?
+        } else {
+            BackfillState::default()
+        };
+
+        Ok(Self { path, state })
+    }
+
+    /// First block not yet scanned, if the watcher has run before
+    pub fn next_block(&self) -> Option<u64> {
+        self.state.next_block
+    }
+
+    /// Timestamp of the first block scanned; older signatures can't be matched
+    pub fn scanned_since(&self) -> Option<u64> {
+        self.state.scanned_since
+    }
+
+    /// Record that every block before `next_block` has been scanned
+    ///
+    /// `first_block_time` is the timestamp of the first block in the scanned
+    /// range and is only kept on the first call.
+    pub fn record_scanned(&mut self, next_block: u64, first_block_time: u64) -> Result<()> {
+        self.state.next_block = Some(next_block);
+        self.state.scanned_since.get_or_insert(first_block_time);
+        self.save()
+    }
+
+    /// Transaction a signature landed in, if known
+    pub fn landed(&self, child_id: &ChildId, presig_index: u32) -> Option<&LandedTx> {
+        self.state
+            .landed
+            .iter()
+            .find(|tx| tx.child_id == *child_id && tx.presig_index == presig_index)
+    }
+
+    /// Matches not yet written to their disk
+    pub fn unwritten(&self) -> impl Iterator<Item = &LandedTx> {
+        self.state.landed.iter().filter(|tx| !tx.written)
+    }
+
+    /// Record the transaction a signature landed in
+    ///
+    /// Returns false if the signature was already matched.
+    pub fn record_landed(
+        &mut self,
+        child_id: ChildId,
+        presig_index: u32,
+        tx_hash: TxHash,
+        block_number: u64,
+    ) -> Result<bool> {
+        if self.landed(&child_id, presig_index).is_some() {
+            return Ok(false);
+        }
+        self.state.landed.push(LandedTx {
+            child_id,
+            presig_index,
+            tx_hash,
+            block_number,
+            written: false,
+        });
+        self.save()?;
+        Ok(true)
+    }
+
+    /// Record a hash written to the disk by hand (`UpdateTxHash`)
+    pub fn record_written(
+        &mut self,
+        child_id: ChildId,
+        presig_index: u32,
+        tx_hash: TxHash,
+    ) -> Result<()> {
+        self.state
+            .landed
+            .retain(|tx| !(tx.child_id == child_id && tx.presig_index == presig_index));
+        self.state.landed.push(LandedTx {
+            child_id,
+            presig_index,
+            tx_hash,
+            block_number: 0,
+            written: true,
+        });
+        self.save()
+    }
+
+    /// Whether a signature has been reported as never landing
+    pub fn is_reported_not_landed(&self, child_id: &ChildId, presig_index: u32) -> bool {
+        self.state.not_landed.contains(&(*child_id, presig_index))
+    }
+
+    /// Remember that a signature was reported as never landing
+    pub fn record_not_landed(&mut self, child_id: ChildId, presig_index: u32) -> Result<()> {
+        if !self.is_reported_not_landed(&child_id, presig_index) {
+            self.state.not_landed.push((child_id, presig_index));
+            self.save()?;
+        }
+        Ok(())
+    }
+
+    /// Offset in a child's audit log before which the watcher is done
+    pub fn audit_offset(&self, child_id: &ChildId) -> u64 {
+        self.state
+            .audit_offsets
+            .iter()
+            .find(|(child, _)| child == child_id)
+            .map_or(0, |(_, offset)| *offset)
+    }
+
+    /// Record that every record before `offset` in a child's audit log is
+    /// settled, so later polls don't read it again
+    pub fn record_audit_offset(&mut self, child_id: ChildId, offset: u64) -> Result<()> {
+        if self.audit_offset(&child_id) == offset {
+            return Ok(());
+        }
+        self.state
+            .audit_offsets
+            .retain(|(child, _)| *child != child_id);
+        self.state.audit_offsets.push((child_id, offset));
+        self.save()
+    }
+
+    /// Fill known transaction hashes into a disk's usage log
+    ///
+    /// Only entries still holding the zero placeholder are changed; a hash
+    /// the agent already recorded is kept. Returns the presig indices whose
+    /// match is settled by this disk, to pass to [`TxBackfill::mark_written`]
+    /// once the disk has been written.
+    pub fn apply(&self, disk: &mut DiskFormat) -> Vec<u32> {
+        let child_id = disk.header.child_id;
+        let mut settled = Vec::new();
+
+        for tx in self.unwritten().filter(|tx| tx.child_id == child_id) {
+            let entry = disk
+                .usage_log
+                .entries
+                .iter_mut()
+                .find(|entry| entry.presig_index == tx.presig_index);
+            if let Some(entry) = entry {
+                if entry.tx_hash_pending() {
+                    entry.tx_hash = tx.tx_hash;
+                }
+                settled.push(tx.presig_index);
+            }
+        }
+
+        settled
+    }
+
+    /// Mark matches as written after the disk write succeeded
+    pub fn mark_written(&mut self, child_id: &ChildId, presig_indices: &[u32]) -> Result<()> {
+        if presig_indices.is_empty() {
+            return Ok(());
+        }
+        for tx in &mut self.state.landed {
+            if tx.child_id == *child_id && presig_indices.contains(&tx.presig_index) {
+                tx.written = true;
+            }
+        }
+        self.save()
+    }
+
+    /// Write the store to disk atomically
+    fn save(&self) -> Result<()> {
+        let temp_path = self.path.with_extension("json.tmp");
+        std::fs::write(&temp_path, serde_json::to_vec(&self.state)?)?;
+        std::fs::rename(&temp_path, &self.path)?;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sigil_core::crypto::DerivationPath;
+    use sigil_core::types::{ChainId, MessageHash, Signature, ZkProofHash};
+    use sigil_core::{DiskHeader, PublicKey, UsageLogEntry};
+    use tempfile::TempDir;
+
+    fn disk(child_id: ChildId, tx_hashes: &[[u8; 32]]) -> DiskFormat {
+        let header = DiskHeader::new(
+            child_id,
+            PublicKey::new([2u8; 33]),
+            DerivationPath::ethereum_hardened(0),
+            tx_hashes.len() as u32,
+            1_700_000_000,
+        );
+        let mut disk = DiskFormat::new(header, Vec::new());
+        for (index, tx_hash) in tx_hashes.iter().enumerate() {
+            disk.usage_log
+                .push(UsageLogEntry::new(
+                    index as u32,
+                    1_700_000_000,
+                    MessageHash::new([index as u8; 32]),
+                    Signature::new([0u8; 64]),
+                    ChainId::ETHEREUM,
+                    TxHash::new(*tx_hash),
+                    ZkProofHash::new([0u8; 32]),
+                    "test".to_string(),
+                ))
+                .unwrap();
+        }
+        disk
+    }
+
+    #[test]
+    fn test_apply_fills_placeholders_and_survives_reopen() {
+        let temp_dir = TempDir::new().unwrap();
+        let child_id = ChildId::new([1u8; 32]);
+
+        {
+            let mut store = TxBackfill::new(temp_dir.path().to_path_buf()).unwrap();
+            assert!(store
+                .record_landed(child_id, 0, TxHash::new([0xaa; 32]), 100)
+                .unwrap());
+            assert!(store
+                .record_landed(child_id, 1, TxHash::new([0xbb; 32]), 101)
+                .unwrap());
+            // A second match for the same signature is ignored
+            assert!(!store
+                .record_landed(child_id, 0, TxHash::new([0xcc; 32]), 102)
+                .unwrap());
+            store.record_not_landed(child_id, 2).unwrap();
+            store.record_scanned(103, 1_700_000_100).unwrap();
+            store.record_scanned(104, 1_700_000_200).unwrap();
+            store.record_audit_offset(child_id, 512).unwrap();
+        }
+
+        let mut store = TxBackfill::new(temp_dir.path().to_path_buf()).unwrap();
+        assert_eq!(store.next_block(), Some(104));
+        assert_eq!(store.scanned_since(), Some(1_700_000_100));
+        assert!(store.is_reported_not_landed(&child_id, 2));
+        assert_eq!(store.audit_offset(&child_id), 512);
+        assert_eq!(store.audit_offset(&ChildId::new([2u8; 32])), 0);
+
+        // Entry 1 already carries a hash from UpdateTxHash, which is kept
+        let mut disk = disk(child_id, &[[0u8; 32], [0x11; 32]]);
+        let settled = store.apply(&mut disk);
+        assert_eq!(settled, [0, 1]);
+        assert_eq!(disk.usage_log.entries[0].tx_hash, TxHash::new([0xaa; 32]));
+        assert_eq!(disk.usage_log.entries[1].tx_hash, TxHash::new([0x11; 32]));
+
+        // Other children's disks are left alone
+        let mut other = self::disk(ChildId::new([2u8; 32]), &[[0u8; 32]]);
+        assert!(store.apply(&mut other).is_empty());
+
+        store.mark_written(&child_id, &settled).unwrap();
+        assert_eq!(store.unwritten().count(), 0);
+        let store = TxBackfill::new(temp_dir.path().to_path_buf()).unwrap();
+        assert_eq!(store.unwritten().count(), 0);
+        assert_eq!(store.landed(&child_id, 0).unwrap().block_number, 100);
+    }
+}
@@ -2,14 +2,21 @@
 //!
 //! Provides a unified interface for interacting with the Sigil daemon in both mock and real modes.
 
+use crate::progress::{Cancellation, ProgressReporter, SIGNING_STAGES};
 use crate::tools::DiskState;
-use sigil_cli::client::{ClientError as CliClientError, SigilClient};
+use sigil_cli::client::{
+    ClientError as CliClientError, EventStream, SigilClient, SignOptions, SignUpdate, UsageEntry,
+    UsagePage, UsageQuery,
+};
+use sigil_core::types::{ChainId, MessageHash, Signature, TxHash, ZkProofHash};
+use sigil_core::{UsageFilter, UsageLog, UsageLogEntry};
+use tracing::info;
 
 /// Daemon operation mode
 pub enum DaemonMode {
     /// Mock mode - returns predefined data without connecting to daemon
     Mock(DiskState),
-    /// Real mode - connects to actual daemon via IPC
+    /// Real mode - connects to actual daemon over its gRPC IPC socket
     Real(SigilClient),
 }
 
@@ -41,6 +48,15 @@ pub enum ClientError {
 
     #[error("Daemon error: {0}")]
     DaemonError(String),
+
+    #[error("Signing request {approval_id} is awaiting approval")]
+    ApprovalPending {
+        approval_id: String,
+        expires_at: u64,
+    },
+
+    #[error("Signing cancelled before the signature was committed")]
+    Cancelled,
 }
 
 impl From<CliClientError> for ClientError {
@@ -54,6 +70,15 @@ impl From<CliClientError> for ClientError {
             CliClientError::DaemonError(msg) => ClientError::DaemonError(msg),
             CliClientError::Io(e) => ClientError::ConnectionFailed(e.to_string()),
             CliClientError::Serialization(e) => ClientError::RequestFailed(e.to_string()),
+            CliClientError::ApprovalPending {
+                approval_id,
+                expires_at,
+            } => ClientError::ApprovalPending {
+                approval_id,
+                expires_at,
+            },
+            CliClientError::Cancelled => ClientError::Cancelled,
+            CliClientError::Decode(e) => ClientError::RequestFailed(e.to_string()),
         }
     }
 }
@@ -64,6 +89,8 @@ pub struct SignResult {
     pub signature: String,
     pub presig_index: u32,
     pub proof_hash: String,
+    /// The call was cancelled, but only after the presig was committed
+    pub cancelled_after_commit: bool,
 }
 
 impl DaemonClient {
@@ -104,65 +131,239 @@ impl DaemonClient {
                     days_until_expiry: status.days_until_expiry,
                     is_valid: status.is_valid,
                     public_key: None, // TODO: Add public_key to daemon's DiskStatus
+                    disks: status
+                        .disks
+                        .into_iter()
+                        .map(|disk| DiskState {
+                            detected: true,
+                            child_id: Some(disk.child_id),
+                            presigs_remaining: Some(disk.presigs_remaining),
+                            presigs_total: Some(disk.presigs_total),
+                            days_until_expiry: Some(disk.days_until_expiry),
+                            is_valid: Some(disk.is_valid),
+                            ..Default::default()
+                        })
+                        .collect(),
                 })
             }
         }
     }
 
-    /// Sign a message hash
+    /// Sign a message hash, optionally with an explicitly selected child disk,
+    /// the value the transaction moves and the unsigned transaction itself
+    /// (whose value the daemon's approval thresholds check)
+    ///
+    /// Each stage is reported to `progress`. Once `cancellation` fires the
+    /// daemon is asked to stop: the call fails with [`ClientError::Cancelled`]
+    /// if it did so before committing the presig, and otherwise returns the
+    /// signature marked as cancelled after commit.
+    #[allow(clippy::too_many_arguments)]
     pub async fn sign(
         &self,
         message_hash: &str,
         chain_id: u32,
         description: &str,
+        child_id: Option<&str>,
+        value: Option<&str>,
+        transaction: Option<&str>,
+        progress: Option<&ProgressReporter>,
+        cancellation: &Cancellation,
     ) -> Result<SignResult> {
         match &self.mode {
             DaemonMode::Mock(_) => {
+                for (stage, _) in SIGNING_STAGES {
+                    if stage == "writing_disk" && cancellation.is_cancelled() {
+                        return Err(ClientError::Cancelled);
+                    }
+                    if let Some(progress) = progress {
+                        progress.signing_stage(stage).await;
+                    }
+                }
+
                 // Return mock signature
                 Ok(SignResult {
                     signature: "0xaabbccdd11223344556677889900aabbccdd11223344556677889900aabbccdd11223344556677889900aabbccdd11223344556677889900aabbccdd1122334400".to_string(),
                     presig_index: 0,
                     proof_hash: "0x1111222233334444555566667777888899990000aaaabbbbccccddddeeeeffff".to_string(),
+                    cancelled_after_commit: false,
                 })
             }
             DaemonMode::Real(client) => {
-                let result = client.sign(message_hash, chain_id, description).await?;
+                let options = SignOptions {
+                    child_id: child_id.map(str::to_string),
+                    value: value.map(str::to_string),
+                    transaction: transaction.map(str::to_string),
+                    ..Default::default()
+                };
+                let mut operation = client
+                    .sign_streaming(message_hash, chain_id, description, &options)
+                    .await?;
 
-                Ok(SignResult {
-                    signature: result.signature,
-                    presig_index: result.presig_index,
-                    proof_hash: result.proof_hash,
-                })
+                let mut cancel_sent = false;
+                loop {
+                    tokio::select! {
+                        update = operation.next() => match update? {
+                            SignUpdate::Stage(stage) => {
+                                if let Some(progress) = progress {
+                                    progress.signing_stage(&stage).await;
+                                }
+                            }
+                            SignUpdate::Done(result) => {
+                                return Ok(SignResult {
+                                    signature: result.signature,
+                                    presig_index: result.presig_index,
+                                    proof_hash: result.proof_hash,
+                                    cancelled_after_commit: cancel_sent,
+                                });
+                            }
+                        },
+                        _ = cancellation.cancelled(), if !cancel_sent => {
+                            cancel_sent = true;
+                            // The stream tells us how it ended either way
+                            match client.cancel_sign(operation.id()).await {
+                                Ok(true) => info!("Cancelled signing operation {}", operation.id()),
+                                Ok(false) => info!(
+                                    "Signing operation {} already committed",
+                                    operation.id()
+                                ),
+                                Err(e) => info!(
+                                    "Could not cancel signing operation {}: {}",
+                                    operation.id(),
+                                    e
+                                ),
+                            }
+                        }
+                    }
+                }
             }
         }
     }
 
     /// Update transaction hash in audit log
-    pub async fn update_tx_hash(&self, presig_index: u32, tx_hash: &str) -> Result<()> {
+    pub async fn update_tx_hash(
+        &self,
+        child_id: Option<&str>,
+        presig_index: u32,
+        tx_hash: &str,
+    ) -> Result<()> {
         match &self.mode {
             DaemonMode::Mock(_) => {
                 // Mock mode - no-op
                 Ok(())
             }
             DaemonMode::Real(client) => {
-                client.update_tx_hash(presig_index, tx_hash).await?;
+                client
+                    .update_tx_hash(child_id, presig_index, tx_hash)
+                    .await?;
                 Ok(())
             }
         }
     }
 
-    /// Get presignature count
-    pub async fn get_presig_count(&self) -> Result<(u32, u32)> {
+    /// Get presignature count of the selected disk
+    pub async fn get_presig_count(&self, child_id: Option<&str>) -> Result<(u32, u32)> {
         match &self.mode {
             DaemonMode::Mock(state) => {
+                let state = state.select(child_id).map_err(ClientError::RequestFailed)?;
                 let remaining = state.presigs_remaining.unwrap_or(0);
                 let total = state.presigs_total.unwrap_or(0);
                 Ok((remaining, total))
             }
             DaemonMode::Real(client) => {
-                let (remaining, total) = client.get_presig_count().await?;
+                let (remaining, total) = client.get_presig_count(child_id).await?;
                 Ok((remaining, total))
             }
         }
     }
+
+    /// Query the usage log of the selected disk, newest first
+    pub async fn query_usage_log(&self, query: &UsageQuery) -> Result<UsagePage> {
+        match &self.mode {
+            DaemonMode::Mock(state) => {
+                let state = state
+                    .select(query.child_id.as_deref())
+                    .map_err(ClientError::RequestFailed)?;
+                if !state.detected {
+                    return Err(ClientError::NoDiskDetected);
+                }
+                Ok(mock_usage_page(state.child_id.unwrap_or_default(), query))
+            }
+            DaemonMode::Real(client) => Ok(client.query_usage_log(query).await?),
+        }
+    }
+
+    /// Subscribe to daemon events
+    ///
+    /// Returns `None` in mock mode, where nothing ever changes.
+    pub async fn watch_events(&self) -> Result<Option<EventStream>> {
+        match &self.mode {
+            DaemonMode::Mock(_) => Ok(None),
+            DaemonMode::Real(client) => Ok(Some(client.watch_events().await?)),
+        }
+    }
+}
+
+/// Filter and page a small fixed usage log the way the daemon would
+fn mock_usage_page(child_id: String, query: &UsageQuery) -> UsagePage {
+    let mut log = UsageLog::new();
+    for (index, (timestamp, chain_id, tx_hash, description)) in [
+        (
+            1_700_000_000,
+            1,
+            [0x5au8; 32],
+            "Transfer 0.1 ETH to alice.eth",
+        ),
+        (1_700_003_600, 137, [0x6bu8; 32], "Swap 100 USDC for MATIC"),
+        (1_700_007_200, 1, [0u8; 32], "Approve USDC for Uniswap"),
+    ]
+    .into_iter()
+    .enumerate()
+    {
+        let entry = UsageLogEntry::new(
+            index as u32,
+            timestamp,
+            MessageHash::new([index as u8 + 1; 32]),
+            Signature::new([0xaa; 64]),
+            ChainId::new(chain_id),
+            TxHash::new(tx_hash),
+            ZkProofHash::new([0x11; 32]),
+            description.to_string(),
+        );
+        // Indices and timestamps increase, so the log accepts every entry
+        let _ = log.push(entry);
+    }
+
+    let filter = UsageFilter {
+        since: query.since,
+        until: query.until,
+        chain_id: query.chain_id.map(ChainId::new),
+        tx_hash: query.tx_hash,
+        search: query.search.clone(),
+    };
+    let matching: Vec<_> = log.query(&filter).collect();
+    let limit = match query.limit {
+        0 => usize::MAX,
+        limit => limit as usize,
+    };
+    let entries = matching
+        .iter()
+        .skip(query.offset as usize)
+        .take(limit)
+        .map(|entry| UsageEntry {
+            presig_index: entry.presig_index,
+            timestamp: entry.timestamp,
+            message_hash: entry.message_hash.to_hex(),
+            signature: entry.signature.to_hex(),
+            chain_id: entry.chain_id.0,
+            tx_hash: Some(entry.tx_hash.to_hex()).filter(|_| !entry.tx_hash_pending()),
+            proof_hash: entry.zkproof_hash.to_hex(),
+            description: entry.description.clone(),
+        })
+        .collect();
+
+    UsagePage {
+        child_id,
+        entries,
+        total: matching.len() as u32,
+    }
 }
@@ -0,0 +1,459 @@
+//! User confirmation for high-risk signatures
+//!
+//! Before a signing tool consumes a presig it checks the request against the
+//! [`ConfirmationPolicy`]. If any rule triggers, the user is asked through
+//! MCP elicitation (`elicitation/create`) and the signature goes ahead only
+//! if they accept. Clients that can't elicit get a refusal instead.
+
+use serde::{Deserialize, Serialize};
+use std::collections::{HashMap, HashSet};
+use std::io;
+use std::path::Path;
+use std::sync::Mutex;
+use std::time::Duration;
+use tracing::{info, warn};
+
+use crate::peer::ClientPeer;
+
+/// How long the user has to answer a confirmation prompt
+pub const ELICITATION_TIMEOUT: Duration = Duration::from_secs(300);
+
+/// Rules deciding which signatures need the user's confirmation
+///
+/// Chains are named by their EVM chain ID (`"1"`, `"137"`) or, for FROST
+/// signatures, by scheme (`"taproot"`, `"ed25519"`, `"ristretto255"`).
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ConfirmationPolicy {
+    /// Confirm every signature
+    pub always: bool,
+
+    /// Confirm amounts above these, per chain, in the chain's smallest unit
+    pub value_thresholds: HashMap<String, u128>,
+
+    /// Threshold for chains without their own entry
+    pub default_value_threshold: Option<u128>,
+
+    /// Confirm token transfers above these, per token contract address, in
+    /// the token's smallest unit
+    pub token_thresholds: HashMap<String, u128>,
+
+    /// Chains that can be signed for without confirmation (unset: all)
+    pub allowed_chains: Option<Vec<String>>,
+
+    /// Confirm the first signature to each destination
+    pub confirm_new_destinations: bool,
+
+    /// Destinations that never count as new
+    pub known_destinations: Vec<String>,
+
+    /// Confirm transactions decoded as high-risk (unlimited approvals,
+    /// `setApprovalForAll`, delegatecall to unknown contracts)
+    pub confirm_risky_calls: bool,
+}
+
+impl ConfirmationPolicy {
+    /// Load a policy from a JSON file
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let contents = std::fs::read_to_string(path)?;
+        serde_json::from_str(&contents).map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+    }
+
+    fn value_threshold(&self, chain: &str) -> Option<u128> {
+        self.value_thresholds
+            .get(chain)
+            .copied()
+            .or(self.default_value_threshold)
+    }
+
+    fn token_threshold(&self, token: &str) -> Option<u128> {
+        self.token_thresholds
+            .iter()
+            .find(|(address, _)| normalize(address) == normalize(token))
+            .map(|(_, threshold)| *threshold)
+    }
+
+    /// Whether any rule looks at amounts or destinations
+    fn checks_transfers(&self, chain: &str) -> bool {
+        self.value_threshold(chain).is_some()
+            || !self.token_thresholds.is_empty()
+            || self.confirm_new_destinations
+    }
+}
+
+/// What a signing tool is about to sign, as shown to the user
+#[derive(Debug, Clone)]
+pub struct SigningSummary {
+    /// Policy name of the chain (`"1"`, `"taproot"`, ...)
+    pub chain: String,
+
+    /// Human-readable chain name
+    pub chain_name: String,
+
+    pub description: String,
+    pub message_hash: String,
+
+    /// Recipient address, if the caller gave one
+    pub destination: Option<String>,
+
+    /// Amount moved in the smallest unit of the chain, or of `token`
+    pub value: Option<u128>,
+
+    /// Token contract when `value` is a token amount
+    pub token: Option<String>,
+
+    /// Contract call whose recipients and amounts couldn't be decoded, so
+    /// `destination` and `value` don't cover every token it may move
+    pub unchecked_call: Option<String>,
+
+    /// High-risk patterns found by decoding the transaction
+    pub risks: Vec<String>,
+
+    pub presigs_remaining: u32,
+}
+
+impl SigningSummary {
+    /// Text of the confirmation prompt
+    fn message(&self, warnings: &[String]) -> String {
+        let mut lines = vec![
+            "Confirm signature".to_string(),
+            format!("Chain: {}", self.chain_name),
+            format!("Description: {}", self.description),
+            format!("Hash: {}", self.message_hash),
+        ];
+        if let Some(destination) = &self.destination {
+            lines.push(format!("To: {}", destination));
+        }
+        match (self.value, &self.token) {
+            (Some(value), Some(token)) => {
+                lines.push(format!("Value: {} of token {}", value, token))
+            }
+            (Some(value), None) => lines.push(format!("Value: {}", value)),
+            _ => {}
+        }
+        lines.push(format!(
+            "Presigs remaining: {} (this uses one)",
+            self.presigs_remaining
+        ));
+        lines.push(String::new());
+        lines.push("Why you are being asked:".to_string());
+        lines.extend(warnings.iter().map(|w| format!("- {}", w)));
+        lines.join("\n")
+    }
+}
+
+/// Applies a [`ConfirmationPolicy`] and remembers destinations signed for
+pub struct Confirmer {
+    policy: ConfirmationPolicy,
+    known_destinations: Mutex<HashSet<String>>,
+}
+
+impl Confirmer {
+    pub fn new(policy: ConfirmationPolicy) -> Self {
+        let known = policy
+            .known_destinations
+            .iter()
+            .map(|d| normalize(d))
+            .collect();
+        Self {
+            policy,
+            known_destinations: Mutex::new(known),
+        }
+    }
+
+    /// Reasons this signature needs confirmation; empty if it doesn't
+    pub fn warnings(&self, summary: &SigningSummary) -> Vec<String> {
+        let policy = &self.policy;
+        let mut warnings = Vec::new();
+
+        if policy.always {
+            warnings.push("Every signature requires confirmation".to_string());
+        }
+
+        match &summary.token {
+            Some(token) => match policy.token_threshold(token) {
+                Some(threshold) => match summary.value {
+                    Some(value) if value > threshold => warnings.push(format!(
+                        "Amount {} of token {} exceeds the confirmation threshold of {}",
+                        value, token, threshold
+                    )),
+                    Some(_) => {}
+                    None => warnings.push(format!("Amount of token {} is unknown", token)),
+                },
+                // A native threshold must not be sidestepped by moving tokens
+                None if policy.value_threshold(&summary.chain).is_some()
+                    || !policy.token_thresholds.is_empty() =>
+                {
+                    warnings.push(format!("Token {} has no confirmation threshold", token))
+                }
+                None => {}
+            },
+            None => {
+                if let Some(threshold) = policy.value_threshold(&summary.chain) {
+                    match summary.value {
+                        Some(value) if value > threshold => warnings.push(format!(
+                            "Amount {} exceeds the confirmation threshold of {}",
+                            value, threshold
+                        )),
+                        Some(_) => {}
+                        None => warnings.push("Amount was not provided".to_string()),
+                    }
+                }
+            }
+        }
+
+        if let Some(call) = &summary.unchecked_call {
+            if policy.checks_transfers(&summary.chain) {
+                warnings.push(format!(
+                    "{} may move tokens to recipients or amounts the policy can't check",
+                    call
+                ));
+            }
+        }
+
+        if let Some(allowed) = &policy.allowed_chains {
+            if !allowed.iter().any(|c| c == &summary.chain) {
+                warnings.push(format!(
+                    "{} is not an allowlisted chain",
+                    summary.chain_name
+                ));
+            }
+        }
+
+        if policy.confirm_new_destinations {
+            match &summary.destination {
+                Some(destination) => {
+                    let known = self.known_destinations.lock().unwrap();
+                    if !known.contains(&normalize(destination)) {
+                        warnings.push(format!("{} has not been signed for before", destination));
+                    }
+                }
+                None => warnings.push("Destination was not provided".to_string()),
+            }
+        }
+
+        if policy.confirm_risky_calls {
+            warnings.extend(summary.risks.iter().cloned());
+        }
+
+        warnings
+    }
+
+    /// Ask the user to confirm if the policy requires it
+    ///
+    /// Returns the reason for refusing when the user declines, doesn't
+    /// answer, or can't be asked.
+    pub async fn confirm(
+        &self,
+        elicitation: Option<&ClientPeer>,
+        summary: &SigningSummary,
+    ) -> Result<(), String> {
+        let warnings = self.warnings(summary);
+        if warnings.is_empty() {
+            return Ok(());
+        }
+
+        let Some(peer) = elicitation else {
+            warn!("Refusing signature needing confirmation: client can't elicit");
+            return Err(format!(
+                "This signature requires user confirmation ({}), but the MCP client does not \
+                 support elicitation. Use a client that supports elicitation, or sign with \
+                 the sigil CLI.",
+                warnings.join("; ")
+            ));
+        };
+
+        let params = serde_json::json!({
+            "message": summary.message(&warnings),
+            "requestedSchema": {
+                "type": "object",
+                "properties": {
+                    "confirm": {
+                        "type": "boolean",
+                        "title": "Sign this transaction",
+                        "description": "Consumes one presignature"
+                    }
+                },
+                "required": ["confirm"]
+            }
+        });
+
+        let result = peer
+            .request("elicitation/create", params, ELICITATION_TIMEOUT)
+            .await
+            .map_err(|e| format!("Could not get user confirmation: {}", e))?;
+
+        let action = result.get("action").and_then(|a| a.as_str());
+        let confirmed = result
+            .pointer("/content/confirm")
+            .and_then(|c| c.as_bool())
+            .unwrap_or(false);
+        if action == Some("accept") && confirmed {
+            info!("User confirmed signature: {}", summary.description);
+            Ok(())
+        } else {
+            info!(
+                "User did not confirm signature ({}): {}",
+                action.unwrap_or("no action"),
+                summary.description
+            );
+            Err("The user declined to sign this transaction.".to_string())
+        }
+    }
+
+    /// Record a destination that has now been signed for
+    pub fn remember(&self, summary: &SigningSummary) {
+        if let Some(destination) = &summary.destination {
+            self.known_destinations
+                .lock()
+                .unwrap()
+                .insert(normalize(destination));
+        }
+    }
+}
+
+impl Default for Confirmer {
+    fn default() -> Self {
+        Self::new(ConfirmationPolicy::default())
+    }
+}
+
+fn normalize(destination: &str) -> String {
+    destination.trim().to_ascii_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::peer::PendingRequests;
+    use crate::protocol::{JsonRpcRequest, JsonRpcResponse};
+    use std::sync::Arc;
+    use tokio::sync::mpsc;
+
+    fn summary(value: Option<u128>, destination: Option<&str>) -> SigningSummary {
+        SigningSummary {
+            chain: "1".to_string(),
+            chain_name: "Ethereum Mainnet (1)".to_string(),
+            description: "Pay invoice".to_string(),
+            message_hash: format!("0x{}", "ab".repeat(32)),
+            destination: destination.map(str::to_string),
+            value,
+            token: None,
+            unchecked_call: None,
+            risks: Vec::new(),
+            presigs_remaining: 10,
+        }
+    }
+
+    #[test]
+    fn test_policy_rules() {
+        assert!(Confirmer::default()
+            .warnings(&summary(None, None))
+            .is_empty());
+
+        let policy: ConfirmationPolicy = serde_json::from_value(serde_json::json!({
+            "value_thresholds": { "1": 1000 },
+            "allowed_chains": ["1", "taproot"],
+            "confirm_new_destinations": true,
+            "known_destinations": ["0xAbC"]
+        }))
+        .unwrap();
+        let confirmer = Confirmer::new(policy);
+
+        assert!(confirmer
+            .warnings(&summary(Some(1000), Some("0xabc")))
+            .is_empty());
+        assert_eq!(
+            confirmer
+                .warnings(&summary(Some(1001), Some("0xdef")))
+                .len(),
+            2
+        );
+
+        let mut other_chain = summary(Some(5), Some("0xabc"));
+        other_chain.chain = "137".to_string();
+        assert_eq!(confirmer.warnings(&other_chain).len(), 1);
+
+        let new_destination = summary(Some(1), Some("0xdef"));
+        assert_eq!(confirmer.warnings(&new_destination).len(), 1);
+        confirmer.remember(&new_destination);
+        assert!(confirmer.warnings(&new_destination).is_empty());
+    }
+
+    #[test]
+    fn test_token_transfers_and_contract_calls() {
+        const USDC: &str = "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48";
+        let policy: ConfirmationPolicy = serde_json::from_value(serde_json::json!({
+            "value_thresholds": { "1": 1000 },
+            "token_thresholds": { "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48": 5000000 }
+        }))
+        .unwrap();
+        let confirmer = Confirmer::new(policy);
+
+        // Token amounts are held to the token's threshold, not the chain's
+        let mut transfer = summary(Some(5_000_000), Some("0xabc"));
+        transfer.token = Some(USDC.to_string());
+        assert!(confirmer.warnings(&transfer).is_empty());
+        transfer.value = Some(5_000_001);
+        assert_eq!(confirmer.warnings(&transfer).len(), 1);
+
+        // A token without a threshold is not waved through
+        transfer.token = Some("0xdef".to_string());
+        transfer.value = Some(1);
+        assert_eq!(
+            confirmer.warnings(&transfer),
+            vec!["Token 0xdef has no confirmation threshold".to_string()]
+        );
+
+        // Nor is a call whose token movements can't be decoded
+        let mut call = summary(Some(0), Some("0xabc"));
+        call.unchecked_call = Some("Uniswap V2 swap".to_string());
+        assert_eq!(confirmer.warnings(&call).len(), 1);
+        assert!(Confirmer::default().warnings(&call).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_confirm_via_elicitation() {
+        let confirmer = Confirmer::new(ConfirmationPolicy {
+            always: true,
+            ..Default::default()
+        });
+
+        // Without elicitation the signature is refused
+        let err = confirmer
+            .confirm(None, &summary(None, None))
+            .await
+            .unwrap_err();
+        assert!(err.contains("does not support elicitation"));
+
+        for (answer, expected) in [
+            (
+                serde_json::json!({ "action": "accept", "content": { "confirm": true } }),
+                true,
+            ),
+            (
+                serde_json::json!({ "action": "accept", "content": { "confirm": false } }),
+                false,
+            ),
+            (serde_json::json!({ "action": "decline" }), false),
+        ] {
+            let pending = Arc::new(PendingRequests::new());
+            let (tx, mut rx) = mpsc::channel(4);
+            let peer = ClientPeer::new(Arc::clone(&pending), tx);
+            let client = tokio::spawn(async move {
+                let sent: JsonRpcRequest = serde_json::from_str(&rx.recv().await.unwrap()).unwrap();
+                assert_eq!(sent.method, "elicitation/create");
+                let message = sent.params.unwrap()["message"]
+                    .as_str()
+                    .unwrap()
+                    .to_string();
+                assert!(message.contains("Every signature requires confirmation"));
+                pending.resolve(JsonRpcResponse::success(sent.id, answer));
+            });
+
+            let result = confirmer.confirm(Some(&peer), &summary(None, None)).await;
+            assert_eq!(result.is_ok(), expected);
+            client.await.unwrap();
+        }
+    }
+}
@@ -2,11 +2,16 @@
 //!
 //! This module contains handlers for all MCP protocol methods.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 use tracing::{debug, info, warn};
 
+use sigil_cli::decode::SelectorRegistry;
+
 use crate::client::{ClientError, DaemonClient};
+use crate::confirmation::Confirmer;
+use crate::peer::ClientPeer;
+use crate::progress::{InFlightRequests, ProgressReporter};
 use crate::prompts;
 use crate::protocol::*;
 use crate::resources;
@@ -29,38 +34,73 @@ pub struct McpServerState {
     /// Daemon client for signing operations
     pub daemon_client: Arc<DaemonClient>,
 
-    /// Resource subscriptions
-    pub subscriptions: HashMap<String, Vec<String>>, // uri -> session_ids
+    /// URIs of the resources this session is subscribed to
+    pub subscriptions: HashSet<String>,
+
+    /// Confirmation policy for signing tools, shared by all sessions
+    pub confirmer: Arc<Confirmer>,
+
+    /// Selector registry for decoding transactions, shared by all sessions
+    pub selectors: Arc<SelectorRegistry>,
+
+    /// Channel to the client for the request being handled, set by the transport
+    pub peer: Option<ClientPeer>,
+
+    /// Requests being handled, shared with the transport so cancellations
+    /// reach them while the state is locked
+    pub in_flight: Arc<InFlightRequests>,
 }
 
 impl McpServerState {
     /// Create new server state with mock daemon client
     pub fn new_with_mock() -> Self {
-        Self {
-            protocol_version: None,
-            initialized: false,
-            client_capabilities: None,
-            client_info: None,
-            daemon_client: Arc::new(DaemonClient::new_mock(DiskState::mock_detected())),
-            subscriptions: HashMap::new(),
-        }
+        Self::with_client(Arc::new(DaemonClient::new_mock(DiskState::mock_detected())))
     }
 
     /// Create new server state with real daemon client
     pub fn new_with_daemon() -> Result<Self, ClientError> {
-        Ok(Self {
+        Ok(Self::with_client(Arc::new(DaemonClient::new_real()?)))
+    }
+
+    /// Create fresh (uninitialized) state sharing an existing daemon client
+    pub fn with_client(daemon_client: Arc<DaemonClient>) -> Self {
+        Self {
             protocol_version: None,
             initialized: false,
             client_capabilities: None,
             client_info: None,
-            daemon_client: Arc::new(DaemonClient::new_real()?),
-            subscriptions: HashMap::new(),
-        })
+            daemon_client,
+            subscriptions: HashSet::new(),
+            confirmer: Arc::new(Confirmer::default()),
+            selectors: Arc::new(SelectorRegistry::builtin()),
+            peer: None,
+            in_flight: Arc::new(InFlightRequests::new()),
+        }
+    }
+
+    /// Fresh state for another session, sharing the daemon client, policy
+    /// and selector registry
+    pub fn new_session(&self) -> Self {
+        Self {
+            confirmer: Arc::clone(&self.confirmer),
+            selectors: Arc::clone(&self.selectors),
+            ..Self::with_client(Arc::clone(&self.daemon_client))
+        }
+    }
+
+    /// Whether the client declared the elicitation capability
+    pub fn supports_elicitation(&self) -> bool {
+        self.client_capabilities
+            .as_ref()
+            .is_some_and(|caps| caps.elicitation.is_some())
     }
 
     pub fn tool_context(&self) -> ToolContext {
         ToolContext {
-            daemon_client: Arc::clone(&self.daemon_client),
+            elicitation: self.peer.clone().filter(|_| self.supports_elicitation()),
+            confirmer: Arc::clone(&self.confirmer),
+            selectors: Arc::clone(&self.selectors),
+            ..ToolContext::new(Arc::clone(&self.daemon_client))
         }
     }
 }
@@ -100,6 +140,7 @@ pub async fn handle_request(
         "resources/read" => handle_resources_read(state, request).await,
         "resources/templates/list" => handle_resources_templates_list(state, request).await,
         "resources/subscribe" => handle_resources_subscribe(state, request).await,
+        "resources/unsubscribe" => handle_resources_unsubscribe(state, request).await,
 
         // Prompts
         "prompts/list" => handle_prompts_list(state, request).await,
@@ -129,12 +170,9 @@ pub async fn handle_notification(
             None
         }
         "notifications/cancelled" => {
-            // Handle cancellation
-            if let Some(params) = &notification.params {
-                if let Some(request_id) = params.get("requestId") {
-                    warn!("Request cancelled: {:?}", request_id);
-                }
-            }
+            // Transports deliver these while the request is running; by now
+            // it has usually finished
+            state.in_flight.cancel(notification);
             None
         }
         _ => {
@@ -230,8 +268,16 @@ async fn handle_tools_call(
 
     debug!("Calling tool: {}", params.name);
 
-    let ctx = state.tool_context();
+    let mut ctx = state.tool_context();
+    ctx.cancellation = state.in_flight.start(&request.id);
+    ctx.progress = params
+        .meta
+        .and_then(|meta| meta.progress_token)
+        .zip(state.peer.clone())
+        .map(|(token, peer)| ProgressReporter::new(token, peer));
+
     let result = tools::execute_tool(&ctx, &params.name, params.arguments).await;
+    state.in_flight.finish(&request.id);
 
     serde_json::to_value(result).map_err(|e| JsonRpcError::internal_error(e.to_string()))
 }
@@ -318,14 +364,31 @@ async fn handle_resources_subscribe(
                 .map_err(|e| JsonRpcError::invalid_params(format!("Invalid params: {}", e)))
         })?;
 
+    if !resources::is_known_uri(&params.uri) {
+        return Err(JsonRpcError::resource_not_found(&params.uri));
+    }
+
     debug!("Subscribing to resource: {}", params.uri);
+    state.subscriptions.insert(params.uri);
+
+    Ok(serde_json::json!({}))
+}
+
+async fn handle_resources_unsubscribe(
+    state: &mut McpServerState,
+    request: &JsonRpcRequest,
+) -> Result<serde_json::Value, JsonRpcError> {
+    let params: ResourcesUnsubscribeParams = request
+        .params
+        .as_ref()
+        .ok_or_else(|| JsonRpcError::invalid_params("Missing params"))
+        .and_then(|p| {
+            serde_json::from_value(p.clone())
+                .map_err(|e| JsonRpcError::invalid_params(format!("Invalid params: {}", e)))
+        })?;
 
-    // Add subscription (simplified - in real impl would track by session)
-    state
-        .subscriptions
-        .entry(params.uri)
-        .or_default()
-        .push("default".to_string());
+    debug!("Unsubscribing from resource: {}", params.uri);
+    state.subscriptions.remove(&params.uri);
 
     Ok(serde_json::json!({}))
 }
@@ -56,7 +56,7 @@ pub fn validate_hex_string(s: &str, expected_bytes: Option<usize>) -> Validation
     let hex_part = &s[2..];
 
     // Invariant: Must have even length
-    if !hex_part.len().is_multiple_of(2) {
+    if hex_part.len() % 2 != 0 {
         return Err(JsonRpcError::invalid_params(
             "Hex string must have even number of characters",
         ));
@@ -14,7 +14,8 @@
 //! # Transport Support
 //!
 //! - **stdio**: Standard input/output (for Claude Desktop, VS Code, etc.)
-//! - **HTTP+SSE**: Coming soon (for web clients and remote agents)
+//! - **Streamable HTTP**: JSON-RPC over POST with an SSE stream for server
+//!   messages (for agents in containers or on other hosts)
 //!
 //! # Example Usage
 //!
@@ -33,8 +34,11 @@
 //! This implementation targets MCP version 2025-11-25.
 
 pub mod client;
+pub mod confirmation;
 pub mod handlers;
 pub mod invariants;
+pub mod peer;
+pub mod progress;
 pub mod prompts;
 pub mod protocol;
 pub mod resources;
@@ -4,9 +4,14 @@
 //! transactions using Sigil's MPC infrastructure.
 
 use clap::{Parser, ValueEnum};
+use std::net::{IpAddr, SocketAddr};
+use std::path::PathBuf;
 use tracing::{info, Level};
 use tracing_subscriber::FmtSubscriber;
 
+use sigil_cli::decode::SelectorRegistry;
+use sigil_mcp::confirmation::ConfirmationPolicy;
+use sigil_mcp::transport::{HttpConfig, HttpListen, HTTP_TOKEN_ENV};
 use sigil_mcp::McpServer;
 
 /// Sigil MCP Server - MPC signing for AI agents
@@ -18,14 +23,35 @@ struct Args {
     #[arg(short, long, value_enum, default_value = "stdio")]
     transport: Transport,
 
+    /// Address for HTTP transport (ignored for stdio)
+    #[arg(long, default_value = "127.0.0.1")]
+    host: IpAddr,
+
     /// Port for HTTP transport (ignored for stdio)
     #[arg(short, long, default_value = "3000")]
     port: u16,
 
+    /// Serve HTTP on this Unix socket instead of TCP
+    #[arg(long, conflicts_with_all = ["host", "port"])]
+    socket: Option<PathBuf>,
+
+    /// Browser origin allowed to call the HTTP endpoint (repeatable)
+    #[arg(long = "allowed-origin")]
+    allowed_origins: Vec<String>,
+
     /// Enable verbose logging (to stderr)
     #[arg(short, long)]
     verbose: bool,
 
+    /// JSON file with the rules for asking the user to confirm signatures
+    #[arg(long, value_name = "PATH")]
+    confirmation_policy: Option<PathBuf>,
+
+    /// JSON file of extra function selectors, contracts and tokens for
+    /// decoding transactions
+    #[arg(long, value_name = "PATH")]
+    selector_registry: Option<PathBuf>,
+
     /// Use mock disk state (for testing without physical disk)
     #[arg(long)]
     mock: bool,
@@ -39,7 +65,7 @@ struct Args {
 enum Transport {
     /// Standard input/output (for Claude Desktop, VS Code, etc.)
     Stdio,
-    /// HTTP with Server-Sent Events (for web clients) - coming soon
+    /// Streamable HTTP (for agents in containers or on other hosts)
     Http,
 }
 
@@ -96,6 +122,34 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         }
     };
 
+    let server = match &args.confirmation_policy {
+        Some(path) => match ConfirmationPolicy::load(path) {
+            Ok(policy) => {
+                info!("Loaded confirmation policy from {:?}", path);
+                server.with_confirmation_policy(policy)
+            }
+            Err(e) => {
+                eprintln!("Failed to load confirmation policy {:?}: {}", path, e);
+                std::process::exit(1);
+            }
+        },
+        None => server,
+    };
+
+    let server = match &args.selector_registry {
+        Some(path) => match SelectorRegistry::load(path) {
+            Ok(registry) => {
+                info!("Loaded selector registry from {:?}", path);
+                server.with_selector_registry(registry)
+            }
+            Err(e) => {
+                eprintln!("Failed to load selector registry {:?}: {}", path, e);
+                std::process::exit(1);
+            }
+        },
+        None => server,
+    };
+
     // Run server with selected transport
     match args.transport {
         Transport::Stdio => {
@@ -103,9 +157,17 @@ async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
             server.run_stdio().await?;
         }
         Transport::Http => {
-            info!("HTTP transport not yet implemented");
-            eprintln!("HTTP transport coming soon. Use --transport stdio for now.");
-            std::process::exit(1);
+            let listen = match args.socket {
+                Some(path) => HttpListen::Unix(path),
+                None => HttpListen::Tcp(SocketAddr::new(args.host, args.port)),
+            };
+            server
+                .run_http(HttpConfig {
+                    listen,
+                    bearer_token: std::env::var(HTTP_TOKEN_ENV).ok().filter(|t| !t.is_empty()),
+                    allowed_origins: args.allowed_origins,
+                })
+                .await?;
         }
     }
 
@@ -0,0 +1,173 @@
+//! Server-initiated messages to the MCP client
+//!
+//! Tools sometimes need to talk back to the client mid-call, e.g. to ask the
+//! user for confirmation with `elicitation/create`. The transport hands the
+//! server a [`ClientPeer`] for the request being handled; responses from the
+//! client are routed back through the session's [`PendingRequests`].
+
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use thiserror::Error;
+use tokio::sync::{mpsc, oneshot};
+use tracing::{debug, trace};
+
+use crate::protocol::{JsonRpcNotification, JsonRpcRequest, JsonRpcResponse, RequestId};
+
+/// Why a request to the client produced no result
+#[derive(Debug, Error)]
+pub enum PeerError {
+    #[error("Client connection closed")]
+    Closed,
+
+    #[error("Client did not respond within {0:?}")]
+    Timeout(Duration),
+
+    #[error("Client returned error {code}: {message}")]
+    Rejected { code: i32, message: String },
+}
+
+/// Requests sent to the client that are awaiting a response
+#[derive(Default)]
+pub struct PendingRequests {
+    next_id: AtomicI64,
+    waiting: Mutex<HashMap<RequestId, oneshot::Sender<JsonRpcResponse>>>,
+}
+
+impl PendingRequests {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    fn register(&self) -> (RequestId, oneshot::Receiver<JsonRpcResponse>) {
+        // Prefixed so they can't collide with the client's own request IDs
+        let id = RequestId::String(format!(
+            "sigil-{}",
+            self.next_id.fetch_add(1, Ordering::Relaxed) + 1
+        ));
+        let (tx, rx) = oneshot::channel();
+        self.waiting.lock().unwrap().insert(id.clone(), tx);
+        (id, rx)
+    }
+
+    fn forget(&self, id: &RequestId) {
+        self.waiting.lock().unwrap().remove(id);
+    }
+
+    /// Deliver a response from the client; false if nothing was waiting for it
+    pub fn resolve(&self, response: JsonRpcResponse) -> bool {
+        let waiter = self.waiting.lock().unwrap().remove(&response.id);
+        match waiter {
+            Some(tx) => tx.send(response).is_ok(),
+            None => {
+                debug!("Response to unknown request {}", response.id);
+                false
+            }
+        }
+    }
+}
+
+/// Channel to the client for the request currently being handled
+#[derive(Clone)]
+pub struct ClientPeer {
+    pending: Arc<PendingRequests>,
+    outgoing: mpsc::Sender<String>,
+}
+
+impl ClientPeer {
+    pub fn new(pending: Arc<PendingRequests>, outgoing: mpsc::Sender<String>) -> Self {
+        Self { pending, outgoing }
+    }
+
+    /// Send a request to the client and wait for its result
+    pub async fn request(
+        &self,
+        method: &str,
+        params: serde_json::Value,
+        timeout: Duration,
+    ) -> Result<serde_json::Value, PeerError> {
+        let (id, rx) = self.pending.register();
+        let request = JsonRpcRequest::new(id.clone(), method).with_params(params);
+        let json = serde_json::to_string(&request).map_err(|_| PeerError::Closed)?;
+        trace!("Sending request to client: {}", json);
+
+        if self.outgoing.send(json).await.is_err() {
+            self.pending.forget(&id);
+            return Err(PeerError::Closed);
+        }
+
+        let response = match tokio::time::timeout(timeout, rx).await {
+            Ok(Ok(response)) => response,
+            Ok(Err(_)) => return Err(PeerError::Closed),
+            Err(_) => {
+                self.pending.forget(&id);
+                return Err(PeerError::Timeout(timeout));
+            }
+        };
+
+        match (response.result, response.error) {
+            (_, Some(error)) => Err(PeerError::Rejected {
+                code: error.code,
+                message: error.message,
+            }),
+            (Some(result), None) => Ok(result),
+            (None, None) => Ok(serde_json::Value::Null),
+        }
+    }
+
+    /// Send a notification to the client
+    pub async fn notify(&self, notification: &JsonRpcNotification) {
+        if let Ok(json) = serde_json::to_string(notification) {
+            if self.outgoing.send(json).await.is_err() {
+                debug!("Client gone; dropped {}", notification.method);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn test_request_round_trip() {
+        let pending = Arc::new(PendingRequests::new());
+        let (tx, mut rx) = mpsc::channel(4);
+        let peer = ClientPeer::new(Arc::clone(&pending), tx);
+
+        let client = tokio::spawn(async move {
+            let sent: JsonRpcRequest = serde_json::from_str(&rx.recv().await.unwrap()).unwrap();
+            assert_eq!(sent.method, "elicitation/create");
+            assert!(!pending.resolve(JsonRpcResponse::success(
+                RequestId::from("other"),
+                serde_json::json!({})
+            )));
+            assert!(pending.resolve(JsonRpcResponse::success(
+                sent.id,
+                serde_json::json!({ "action": "accept" })
+            )));
+        });
+
+        let result = peer
+            .request(
+                "elicitation/create",
+                serde_json::json!({}),
+                Duration::from_secs(5),
+            )
+            .await
+            .unwrap();
+        assert_eq!(result["action"], "accept");
+        client.await.unwrap();
+    }
+
+    #[tokio::test]
+    async fn test_request_times_out() {
+        let (tx, _rx) = mpsc::channel(4);
+        let peer = ClientPeer::new(Arc::new(PendingRequests::new()), tx);
+        let result = peer
+            .request("ping", serde_json::json!({}), Duration::from_millis(10))
+            .await;
+        assert!(matches!(result, Err(PeerError::Timeout(_))));
+    }
+}
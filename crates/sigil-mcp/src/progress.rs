@@ -0,0 +1,224 @@
+//! Progress notifications and cancellation of client requests
+//!
+//! A request can carry `_meta.progressToken`; long-running tools then report
+//! what they are doing with `notifications/progress`. The client cancels a
+//! request with `notifications/cancelled`, which the transport delivers
+//! through the session's [`InFlightRequests`] while the handler is running.
+
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use tokio::sync::watch;
+use tracing::{debug, info};
+
+use crate::peer::ClientPeer;
+use crate::protocol::{
+    CancelledNotification, JsonRpcNotification, ProgressNotification, RequestId,
+};
+
+/// Stages of a signature as named by the daemon, with their descriptions
+pub const SIGNING_STAGES: [(&str, &str); 5] = [
+    ("reading_disk", "Reading disk"),
+    ("checking_policy", "Checking policy"),
+    ("signing", "Signing"),
+    ("proving", "Generating proof"),
+    ("writing_disk", "Writing disk"),
+];
+
+/// Cancellation of one request by the client
+#[derive(Clone)]
+pub struct Cancellation {
+    cancelled: Arc<watch::Sender<bool>>,
+}
+
+impl Cancellation {
+    pub fn new() -> Self {
+        Self {
+            cancelled: Arc::new(watch::channel(false).0),
+        }
+    }
+
+    pub fn cancel(&self) {
+        self.cancelled.send_replace(true);
+    }
+
+    pub fn is_cancelled(&self) -> bool {
+        *self.cancelled.borrow()
+    }
+
+    /// Wait until the request is cancelled
+    pub async fn cancelled(&self) {
+        let mut cancelled = self.cancelled.subscribe();
+        // The sender lives as long as self, so this only returns on cancel
+        let _ = cancelled.wait_for(|cancelled| *cancelled).await;
+    }
+}
+
+impl Default for Cancellation {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Client requests being handled, by ID, so they can be cancelled
+#[derive(Default)]
+pub struct InFlightRequests {
+    requests: Mutex<HashMap<RequestId, Cancellation>>,
+}
+
+impl InFlightRequests {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Track a request until [`InFlightRequests::finish`]
+    pub fn start(&self, id: &RequestId) -> Cancellation {
+        let cancellation = Cancellation::new();
+        self.requests
+            .lock()
+            .unwrap()
+            .insert(id.clone(), cancellation.clone());
+        cancellation
+    }
+
+    pub fn finish(&self, id: &RequestId) {
+        self.requests.lock().unwrap().remove(id);
+    }
+
+    /// Apply a `notifications/cancelled`; false if its request isn't in flight
+    pub fn cancel(&self, notification: &JsonRpcNotification) -> bool {
+        let Some(params) = notification
+            .params
+            .clone()
+            .and_then(|params| serde_json::from_value::<CancelledNotification>(params).ok())
+        else {
+            debug!("Ignoring malformed cancellation");
+            return false;
+        };
+
+        match self.requests.lock().unwrap().get(&params.request_id) {
+            Some(cancellation) => {
+                info!(
+                    "Request {} cancelled by client: {}",
+                    params.request_id,
+                    params.reason.as_deref().unwrap_or("no reason given")
+                );
+                cancellation.cancel();
+                true
+            }
+            None => {
+                debug!(
+                    "Cancellation for request {} not in flight",
+                    params.request_id
+                );
+                false
+            }
+        }
+    }
+}
+
+/// Sends `notifications/progress` for one request
+pub struct ProgressReporter {
+    token: RequestId,
+    peer: ClientPeer,
+    last: Mutex<f64>,
+}
+
+impl ProgressReporter {
+    pub fn new(token: RequestId, peer: ClientPeer) -> Self {
+        Self {
+            token,
+            peer,
+            last: Mutex::new(0.0),
+        }
+    }
+
+    /// Report progress; anything that doesn't move forward is dropped
+    pub async fn report(&self, progress: f64, total: Option<f64>, message: &str) {
+        {
+            let mut last = self.last.lock().unwrap();
+            if progress <= *last {
+                return;
+            }
+            *last = progress;
+        }
+
+        let params = ProgressNotification {
+            progress_token: self.token.clone(),
+            progress,
+            total,
+            message: Some(message.to_string()),
+        };
+        if let Ok(params) = serde_json::to_value(params) {
+            self.peer
+                .notify(&JsonRpcNotification::new("notifications/progress").with_params(params))
+                .await;
+        }
+    }
+
+    /// Report that a signature reached one of [`SIGNING_STAGES`]
+    pub async fn signing_stage(&self, stage: &str) {
+        let Some(position) = SIGNING_STAGES.iter().position(|(name, _)| *name == stage) else {
+            debug!("Unknown signing stage: {}", stage);
+            return;
+        };
+        self.report(
+            (position + 1) as f64,
+            Some(SIGNING_STAGES.len() as f64),
+            SIGNING_STAGES[position].1,
+        )
+        .await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::peer::PendingRequests;
+    use tokio::sync::mpsc;
+
+    #[tokio::test]
+    async fn test_signing_stages_only_move_forward() {
+        let (tx, mut rx) = mpsc::channel(8);
+        let progress = ProgressReporter::new(
+            RequestId::from("token"),
+            ClientPeer::new(Arc::new(PendingRequests::new()), tx),
+        );
+
+        for stage in ["reading_disk", "checking_policy", "reading_disk", "proving"] {
+            progress.signing_stage(stage).await;
+        }
+        drop(progress);
+
+        let mut sent = Vec::new();
+        while let Some(json) = rx.recv().await {
+            let notification: JsonRpcNotification = serde_json::from_str(&json).unwrap();
+            assert_eq!(notification.method, "notifications/progress");
+            let params = notification.params.unwrap();
+            assert_eq!(params["progressToken"], "token");
+            assert_eq!(params["total"], 5.0);
+            sent.push(params["message"].as_str().unwrap().to_string());
+        }
+        assert_eq!(
+            sent,
+            ["Reading disk", "Checking policy", "Generating proof"]
+        );
+    }
+
+    #[test]
+    fn test_cancel_in_flight_request() {
+        let in_flight = InFlightRequests::new();
+        let cancellation = in_flight.start(&RequestId::Number(7));
+
+        let cancel = |id: serde_json::Value| {
+            JsonRpcNotification::new("notifications/cancelled")
+                .with_params(serde_json::json!({ "requestId": id, "reason": "user" }))
+        };
+        assert!(!in_flight.cancel(&cancel(serde_json::json!(8))));
+        assert!(!cancellation.is_cancelled());
+        assert!(in_flight.cancel(&cancel(serde_json::json!(7))));
+        assert!(cancellation.is_cancelled());
+
+        in_flight.finish(&RequestId::Number(7));
+        assert!(!in_flight.cancel(&cancel(serde_json::json!(7))));
+    }
+}
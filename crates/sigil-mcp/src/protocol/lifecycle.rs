@@ -80,15 +80,19 @@ pub struct CancelledNotification {
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub struct ProgressNotification {
-    /// Token identifying this progress stream
-    pub progress_token: String,
+    /// Token the client gave in the request's `_meta.progressToken`
+    pub progress_token: super::jsonrpc::RequestId,
 
-    /// Progress value (0.0 to 1.0)
+    /// Progress so far; increases with every notification
     pub progress: f64,
 
     /// Optional total for the operation
     #[serde(skip_serializing_if = "Option::is_none")]
     pub total: Option<f64>,
+
+    /// Optional description of the current step
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
 }
 
 /// Log level for logging messages
@@ -2,6 +2,8 @@
 
 use serde::{Deserialize, Serialize};
 
+use super::jsonrpc::RequestId;
+
 // ============================================================================
 // Tool Types
 // ============================================================================
@@ -78,6 +80,19 @@ pub struct ToolsCallParams {
     /// Tool arguments
     #[serde(default)]
     pub arguments: serde_json::Value,
+
+    /// Request metadata
+    #[serde(rename = "_meta", default, skip_serializing_if = "Option::is_none")]
+    pub meta: Option<RequestMeta>,
+}
+
+/// `_meta` of a request
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RequestMeta {
+    /// Token to send `notifications/progress` with, if the client wants them
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub progress_token: Option<RequestId>,
 }
 
 /// Tool call result content types
@@ -302,6 +317,12 @@ pub struct ResourcesSubscribeParams {
     pub uri: String,
 }
 
+/// resources/unsubscribe request parameters
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourcesUnsubscribeParams {
+    pub uri: String,
+}
+
 // ============================================================================
 // Prompt Types
 // ============================================================================
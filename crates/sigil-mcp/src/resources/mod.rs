@@ -2,9 +2,79 @@
 //!
 //! Resources provide readable context data to agents.
 
+use sigil_cli::client::DaemonEvent;
+
 use crate::protocol::{Resource, ResourceContent, ResourceTemplate, ResourcesReadResult};
+use crate::tools::signing_history::{self, SigningHistoryParams};
 use crate::tools::{DiskState, ToolContext};
 
+/// Prefix of the per-child resource URIs
+const CHILD_URI_PREFIX: &str = "sigil://children/";
+
+/// Usage log resource; filters and cursor go in the URI query
+const USAGE_LOG_URI: &str = "sigil://usage-log";
+
+/// Split a usage log URI into its query, if it is one
+fn usage_log_query(uri: &str) -> Option<&str> {
+    match uri.strip_prefix(USAGE_LOG_URI)? {
+        "" => Some(""),
+        rest => rest.strip_prefix('?'),
+    }
+}
+
+/// Whether a URI names a resource this server can serve
+pub fn is_known_uri(uri: &str) -> bool {
+    match uri {
+        "sigil://disk/status" | "sigil://presigs/info" | "sigil://supported-chains" => true,
+        _ if usage_log_query(uri).is_some() => true,
+        _ => uri
+            .strip_prefix(CHILD_URI_PREFIX)
+            .is_some_and(|child_id| !child_id.is_empty()),
+    }
+}
+
+/// Whether a daemon event changes the contents of the resource at `uri`
+pub fn affects(event: &DaemonEvent, uri: &str) -> bool {
+    let child_id = uri.strip_prefix(CHILD_URI_PREFIX);
+    let usage_log = usage_log_query(uri).is_some();
+    match event {
+        // Which disk is current changed, so every child resource did too
+        DaemonEvent::DiskInserted { .. } | DaemonEvent::DiskRemoved => {
+            matches!(uri, "sigil://disk/status" | "sigil://presigs/info")
+                || child_id.is_some()
+                || usage_log
+        }
+        DaemonEvent::DiskValidationFailed { .. } => {
+            matches!(uri, "sigil://disk/status" | "sigil://presigs/info")
+        }
+        // A new signature was logged
+        DaemonEvent::PresigConsumed { child_id: id, .. } => {
+            matches!(uri, "sigil://disk/status" | "sigil://presigs/info")
+                || child_id == Some(id.as_str())
+                || usage_log
+        }
+        DaemonEvent::LowPresigWarning { child_id: id, .. } => {
+            matches!(uri, "sigil://disk/status" | "sigil://presigs/info")
+                || child_id == Some(id.as_str())
+        }
+        DaemonEvent::ExpiryWarning { child_id: id, .. } => {
+            uri == "sigil://disk/status" || child_id == Some(id.as_str())
+        }
+        // Nothing is written until the disk is next inserted
+        DaemonEvent::ApprovalRequested { .. }
+        | DaemonEvent::ApprovalResolved { .. }
+        | DaemonEvent::SignatureNotLanded { .. } => false,
+    }
+}
+
+/// Whether a daemon event changes which resources are listed
+pub fn changes_resource_list(event: &DaemonEvent) -> bool {
+    matches!(
+        event,
+        DaemonEvent::DiskInserted { .. } | DaemonEvent::DiskRemoved
+    )
+}
+
 /// Get all resource definitions
 pub fn get_all_resources(disk_state: &DiskState) -> Vec<Resource> {
     let mut resources = vec![Resource {
@@ -53,13 +123,30 @@ pub fn get_all_resources(disk_state: &DiskState) -> Vec<Resource> {
 
 /// Get all resource templates
 pub fn get_all_templates() -> Vec<ResourceTemplate> {
-    vec![ResourceTemplate {
-        uri_template: "sigil://children/{child_id}".to_string(),
-        name: "Child Disk Info".to_string(),
-        title: Some("Child Disk Information".to_string()),
-        description: Some("Information about a specific child disk by ID".to_string()),
-        mime_type: Some("application/json".to_string()),
-    }]
+    vec![
+        ResourceTemplate {
+            uri_template: "sigil://children/{child_id}".to_string(),
+            name: "Child Disk Info".to_string(),
+            title: Some("Child Disk Information".to_string()),
+            description: Some("Information about a specific child disk by ID".to_string()),
+            mime_type: Some("application/json".to_string()),
+        },
+        ResourceTemplate {
+            uri_template: format!(
+                "{}{{?child_id,since,until,chain_id,tx_hash,search,cursor,limit}}",
+                USAGE_LOG_URI
+            ),
+            name: "Usage Log".to_string(),
+            title: Some("Signing History".to_string()),
+            description: Some(
+                "Signatures from the disk's usage log, newest first. since and until are Unix \
+                 times, tx_hash is pending or filled, and cursor is the next_cursor of the \
+                 previous page"
+                    .to_string(),
+            ),
+            mime_type: Some("application/json".to_string()),
+        },
+    ]
 }
 
 /// Read a resource by URI
@@ -68,8 +155,9 @@ pub async fn read_resource(ctx: &ToolContext, uri: &str) -> Result<ResourcesRead
         "sigil://disk/status" => read_disk_status(ctx).await,
         "sigil://presigs/info" => read_presigs_info(ctx).await,
         "sigil://supported-chains" => read_supported_chains().await,
-        _ if uri.starts_with("sigil://children/") => {
-            let child_id = uri.strip_prefix("sigil://children/").unwrap();
+        _ if usage_log_query(uri).is_some() => read_usage_log(ctx, uri).await,
+        _ if uri.starts_with(CHILD_URI_PREFIX) => {
+            let child_id = uri.strip_prefix(CHILD_URI_PREFIX).unwrap();
             read_child_info(ctx, child_id).await
         }
         _ => Err(format!("Unknown resource: {}", uri)),
@@ -201,6 +289,22 @@ async fn read_supported_chains() -> Result<ResourcesReadResult, String> {
     })
 }
 
+/// Read one page of the usage log resource
+async fn read_usage_log(ctx: &ToolContext, uri: &str) -> Result<ResourcesReadResult, String> {
+    let params = SigningHistoryParams::from_uri_query(usage_log_query(uri).unwrap_or_default())?;
+    let content = signing_history::query(ctx, params).await?;
+
+    Ok(ResourcesReadResult {
+        contents: vec![ResourceContent {
+            uri: uri.to_string(),
+            mime_type: Some("application/json".to_string()),
+            text: Some(serde_json::to_string_pretty(&content).unwrap()),
+            blob: None,
+            annotations: None,
+        }],
+    })
+}
+
 /// Read child disk info resource
 async fn read_child_info(ctx: &ToolContext, child_id: &str) -> Result<ResourcesReadResult, String> {
     let state = ctx
@@ -223,7 +327,7 @@ async fn read_child_info(ctx: &ToolContext, child_id: &str) -> Result<ResourcesR
 
         Ok(ResourcesReadResult {
             contents: vec![ResourceContent {
-                uri: format!("sigil://children/{}", child_id),
+                uri: format!("{}{}", CHILD_URI_PREFIX, child_id),
                 mime_type: Some("application/json".to_string()),
                 text: Some(serde_json::to_string_pretty(&content).unwrap()),
                 blob: None,
@@ -264,6 +368,48 @@ mod tests {
         assert!(resources.iter().any(|r| r.uri == "sigil://presigs/info"));
     }
 
+    #[test]
+    fn test_affected_by_events() {
+        let consumed = DaemonEvent::PresigConsumed {
+            child_id: "abcd".to_string(),
+            presig_index: 3,
+            presigs_remaining: 10,
+        };
+        assert!(affects(&consumed, "sigil://presigs/info"));
+        assert!(affects(&consumed, "sigil://children/abcd"));
+        assert!(!affects(&consumed, "sigil://children/ef01"));
+        assert!(!affects(&consumed, "sigil://supported-chains"));
+        assert!(affects(&consumed, "sigil://usage-log?tx_hash=pending"));
+        assert!(!changes_resource_list(&consumed));
+
+        let expiry = DaemonEvent::ExpiryWarning {
+            child_id: "abcd".to_string(),
+            days_until_expiry: 2,
+        };
+        assert!(affects(&expiry, "sigil://disk/status"));
+        assert!(!affects(&expiry, "sigil://presigs/info"));
+
+        assert!(changes_resource_list(&DaemonEvent::DiskRemoved));
+        assert!(affects(&DaemonEvent::DiskRemoved, "sigil://children/ef01"));
+
+        let resolved = DaemonEvent::ApprovalResolved {
+            approval_id: "ab12".to_string(),
+            status: "signed".to_string(),
+        };
+        assert!(!affects(&resolved, "sigil://disk/status"));
+    }
+
+    #[test]
+    fn test_known_uris() {
+        assert!(is_known_uri("sigil://disk/status"));
+        assert!(is_known_uri("sigil://children/abcd"));
+        assert!(!is_known_uri("sigil://children/"));
+        assert!(is_known_uri("sigil://usage-log"));
+        assert!(is_known_uri("sigil://usage-log?chain_id=1&cursor=20"));
+        assert!(!is_known_uri("sigil://usage-logs"));
+        assert!(!is_known_uri("sigil://nope"));
+    }
+
     #[tokio::test]
     async fn test_read_supported_chains() {
         let result = read_supported_chains().await.unwrap();
@@ -273,9 +419,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_read_disk_status() {
-        let ctx = ToolContext {
-            daemon_client: Arc::new(DaemonClient::new_mock(DiskState::mock_detected())),
-        };
+        let ctx = ToolContext::new(Arc::new(DaemonClient::new_mock(DiskState::mock_detected())));
 
         let result = read_disk_status(&ctx).await.unwrap();
         assert_eq!(result.contents.len(), 1);
@@ -283,4 +427,25 @@ mod tests {
         let text = result.contents[0].text.as_ref().unwrap();
         assert!(text.contains("detected"));
     }
+
+    #[tokio::test]
+    async fn test_read_usage_log() {
+        let ctx = ToolContext::new(Arc::new(DaemonClient::new_mock(DiskState::mock_detected())));
+
+        let result = read_resource(&ctx, "sigil://usage-log?tx_hash=pending")
+            .await
+            .unwrap();
+        assert_eq!(result.contents[0].uri, "sigil://usage-log?tx_hash=pending");
+        let page: serde_json::Value =
+            serde_json::from_str(result.contents[0].text.as_ref().unwrap()).unwrap();
+        assert_eq!(page["total"], 1);
+        assert_eq!(
+            page["entries"][0]["description"],
+            "Approve USDC for Uniswap"
+        );
+
+        assert!(read_resource(&ctx, "sigil://usage-log?order=asc")
+            .await
+            .is_err());
+    }
 }
@@ -2,15 +2,31 @@
 //!
 //! The main server that handles the MCP protocol over various transports.
 
+use std::collections::VecDeque;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use std::time::Duration;
+use tokio::sync::{mpsc, RwLock};
 use tracing::{debug, error, info, warn};
 
+use sigil_cli::client::DaemonEvent;
+use sigil_cli::decode::SelectorRegistry;
+
 use crate::client::{ClientError, DaemonClient};
+use crate::confirmation::{ConfirmationPolicy, Confirmer};
 use crate::handlers::{handle_notification, handle_request, McpServerState};
-use crate::protocol::{JsonRpcNotification, JsonRpcRequest, JsonRpcResponse, JSONRPC_VERSION};
+use crate::peer::{ClientPeer, PendingRequests};
+use crate::progress::InFlightRequests;
+use crate::protocol::{
+    JsonRpcError, JsonRpcNotification, JsonRpcRequest, JsonRpcResponse, RequestId,
+    ResourcesUpdatedNotification,
+};
+use crate::resources;
 use crate::tools::DiskState;
-use crate::transport::stdio::AsyncStdioTransport;
+use crate::transport::http::{self, HttpConfig};
+use crate::transport::stdio::{create_stdio_channels, StdioReceiver, StdioSender};
+
+/// Delay before resubscribing after the daemon event stream drops
+const EVENT_RECONNECT_DELAY: Duration = Duration::from_secs(5);
 
 /// MCP Server
 pub struct McpServer {
@@ -43,103 +59,139 @@ impl McpServer {
     #[allow(dead_code)]
     pub fn with_disk_state(disk_state: DiskState) -> Self {
         Self {
-            state: Arc::new(RwLock::new(McpServerState {
-                protocol_version: None,
-                initialized: false,
-                client_capabilities: None,
-                client_info: None,
-                daemon_client: Arc::new(DaemonClient::new_mock(disk_state)),
-                subscriptions: std::collections::HashMap::new(),
-            })),
+            state: Arc::new(RwLock::new(McpServerState::with_client(Arc::new(
+                DaemonClient::new_mock(disk_state),
+            )))),
+        }
+    }
+
+    /// Ask the user to confirm signatures matching this policy
+    pub fn with_confirmation_policy(mut self, policy: ConfirmationPolicy) -> Self {
+        // The state isn't shared until the server runs
+        if let Some(state) = Arc::get_mut(&mut self.state) {
+            state.get_mut().confirmer = Arc::new(Confirmer::new(policy));
         }
+        self
+    }
+
+    /// Decode transactions with this selector registry
+    pub fn with_selector_registry(mut self, registry: SelectorRegistry) -> Self {
+        // The state isn't shared until the server runs
+        if let Some(state) = Arc::get_mut(&mut self.state) {
+            state.get_mut().selectors = Arc::new(registry);
+        }
+        self
     }
 
     /// Run the server using stdio transport
     pub async fn run_stdio(&self) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
         info!("Starting Sigil MCP server (stdio transport)");
 
-        let mut transport = AsyncStdioTransport::new();
+        let (sender, incoming) = create_stdio_channels(64);
+        let mut io = StdioSession {
+            pending: Arc::new(PendingRequests::new()),
+            in_flight: Arc::clone(&self.state.read().await.in_flight),
+            sender,
+            incoming,
+            backlog: VecDeque::new(),
+        };
+        self.state.write().await.peer = Some(ClientPeer::new(
+            Arc::clone(&io.pending),
+            io.sender.channel(),
+        ));
+
+        let daemon_client = Arc::clone(&self.state.read().await.daemon_client);
+        let mut events = spawn_event_forwarder(daemon_client);
 
         loop {
-            // Read message
-            let message = match transport.read_message().await {
-                Ok(Some(msg)) => msg,
-                Ok(None) => {
-                    info!("EOF received, shutting down");
-                    break;
-                }
-                Err(e) => {
-                    error!("Error reading message: {}", e);
-                    continue;
-                }
-            };
-
-            // Parse JSON
-            let parsed: Result<serde_json::Value, _> = serde_json::from_str(&message);
-            let json = match parsed {
-                Ok(v) => v,
-                Err(e) => {
-                    warn!("Failed to parse JSON: {}", e);
-                    let error_response = JsonRpcResponse {
-                        jsonrpc: JSONRPC_VERSION.to_string(),
-                        id: crate::protocol::RequestId::Null,
-                        result: None,
-                        error: Some(crate::protocol::JsonRpcError::parse_error()),
-                    };
-                    if let Err(e) = transport.write_response(&error_response).await {
-                        error!("Failed to write error response: {}", e);
+            if let Some(message) = io.backlog.pop_front() {
+                self.handle_message(&mut io, message).await;
+                continue;
+            }
+
+            tokio::select! {
+                message = io.incoming.recv() => match message {
+                    Some(message) => self.handle_message(&mut io, message).await,
+                    None => {
+                        info!("EOF received, shutting down");
+                        break;
                     }
-                    continue;
-                }
-            };
-
-            // Determine message type
-            if json.get("id").is_some() && json.get("method").is_some() {
-                // It's a request
-                let request: JsonRpcRequest = match serde_json::from_value(json) {
-                    Ok(r) => r,
-                    Err(e) => {
-                        warn!("Failed to parse request: {}", e);
-                        continue;
+                },
+                Some(event) = events.recv() => {
+                    let notifications = event_notifications(&*self.state.read().await, &event);
+                    for notification in notifications {
+                        if let Err(e) = io.sender.send_notification(&notification).await {
+                            error!("Failed to write notification: {}", e);
+                        }
                     }
-                };
+                }
+            }
+        }
 
-                let mut state = self.state.write().await;
-                let response = handle_request(&mut state, &request).await;
+        info!("Sigil MCP server stopped");
+        Ok(())
+    }
 
-                if let Err(e) = transport.write_response(&response).await {
-                    error!("Failed to write response: {}", e);
+    /// Run the server using the Streamable HTTP transport
+    ///
+    /// Each HTTP session gets its own protocol state; all of them share this
+    /// server's daemon client, confirmation policy and selector registry.
+    pub async fn run_http(
+        &self,
+        config: HttpConfig,
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        info!("Starting Sigil MCP server (Streamable HTTP transport)");
+
+        let template = self.state.read().await.new_session();
+        http::serve(config, template).await?;
+
+        info!("Sigil MCP server stopped");
+        Ok(())
+    }
+
+    /// Handle a single message read from the transport
+    async fn handle_message(&self, io: &mut StdioSession, message: String) {
+        match parse_message(&message) {
+            Err(error) => {
+                let error_response = JsonRpcResponse::error(RequestId::Null, error);
+                if let Err(e) = io.sender.send_response(&error_response).await {
+                    error!("Failed to write error response: {}", e);
                 }
-            } else if json.get("method").is_some() && json.get("id").is_none() {
-                // It's a notification
-                let notification: JsonRpcNotification = match serde_json::from_value(json) {
-                    Ok(n) => n,
-                    Err(e) => {
-                        warn!("Failed to parse notification: {}", e);
-                        continue;
+            }
+            Ok(ClientMessage::Request(request)) => {
+                let handling = async {
+                    let mut state = self.state.write().await;
+                    handle_request(&mut state, &request).await
+                };
+                tokio::pin!(handling);
+
+                // Keep reading so the client can answer requests made by the handler
+                let response = loop {
+                    tokio::select! {
+                        response = &mut handling => break response,
+                        Some(message) = io.incoming.recv() => io.route(message),
                     }
                 };
 
+                if let Err(e) = io.sender.send_response(&response).await {
+                    error!("Failed to write response: {}", e);
+                }
+            }
+            Ok(ClientMessage::Notification(notification)) => {
                 let mut state = self.state.write().await;
                 if let Some(response_notification) =
                     handle_notification(&mut state, &notification).await
                 {
-                    if let Err(e) = transport.write_notification(&response_notification).await {
+                    if let Err(e) = io.sender.send_notification(&response_notification).await {
                         error!("Failed to write notification: {}", e);
                     }
                 }
-            } else if json.get("id").is_some() && json.get("result").is_some()
-                || json.get("error").is_some()
-            {
-                // It's a response (from client to server's request)
-                debug!("Received response from client (ignored for now)");
-            } else {
-                warn!("Unknown message type: {:?}", json);
             }
+            Ok(ClientMessage::Response(response)) => {
+                io.pending.resolve(response);
+            }
+            Ok(ClientMessage::Invalid) => {}
         }
-
-        info!("Sigil MCP server stopped");
-        Ok(())
     }
 
     /// Get current disk state
@@ -149,6 +201,160 @@ impl McpServer {
     }
 }
 
+/// Channels of a running stdio session
+struct StdioSession {
+    sender: StdioSender,
+    incoming: StdioReceiver,
+    pending: Arc<PendingRequests>,
+    in_flight: Arc<InFlightRequests>,
+
+    /// Messages read while a request was being handled, processed next
+    backlog: VecDeque<String>,
+}
+
+impl StdioSession {
+    /// Deliver responses and cancellations straight away and queue
+    /// everything else
+    fn route(&mut self, message: String) {
+        match parse_message(&message) {
+            Ok(ClientMessage::Response(response)) => {
+                self.pending.resolve(response);
+            }
+            Ok(ClientMessage::Notification(notification))
+                if notification.method == "notifications/cancelled"
+                    && self.in_flight.cancel(&notification) => {}
+            _ => self.backlog.push_back(message),
+        }
+    }
+}
+
+/// A message received from the client
+pub(crate) enum ClientMessage {
+    Request(JsonRpcRequest),
+    Notification(JsonRpcNotification),
+    /// Response to a request the server sent
+    Response(JsonRpcResponse),
+    /// Valid JSON that isn't a JSON-RPC message we understand
+    Invalid,
+}
+
+/// Classify a raw message, or return the parse error to send back
+pub(crate) fn parse_message(message: &str) -> Result<ClientMessage, JsonRpcError> {
+    let json: serde_json::Value = serde_json::from_str(message).map_err(|e| {
+        warn!("Failed to parse JSON: {}", e);
+        JsonRpcError::parse_error()
+    })?;
+
+    // Determine message type
+    let message = if json.get("id").is_some() && json.get("method").is_some() {
+        match serde_json::from_value(json) {
+            Ok(request) => ClientMessage::Request(request),
+            Err(e) => {
+                warn!("Failed to parse request: {}", e);
+                ClientMessage::Invalid
+            }
+        }
+    } else if json.get("method").is_some() && json.get("id").is_none() {
+        match serde_json::from_value(json) {
+            Ok(notification) => ClientMessage::Notification(notification),
+            Err(e) => {
+                warn!("Failed to parse notification: {}", e);
+                ClientMessage::Invalid
+            }
+        }
+    } else if json.get("id").is_some() && json.get("result").is_some()
+        || json.get("error").is_some()
+    {
+        match serde_json::from_value(json) {
+            Ok(response) => ClientMessage::Response(response),
+            Err(e) => {
+                warn!("Failed to parse response: {}", e);
+                ClientMessage::Invalid
+            }
+        }
+    } else {
+        warn!("Unknown message type: {:?}", json);
+        ClientMessage::Invalid
+    };
+    Ok(message)
+}
+
+/// Forward daemon events to the server loop, resubscribing if the stream drops
+///
+/// The returned channel closes immediately in mock mode.
+pub(crate) fn spawn_event_forwarder(
+    daemon_client: Arc<DaemonClient>,
+) -> mpsc::Receiver<DaemonEvent> {
+    let (tx, rx) = mpsc::channel(32);
+
+    tokio::spawn(async move {
+        loop {
+            match daemon_client.watch_events().await {
+                Ok(None) => return,
+                Ok(Some(mut stream)) => {
+                    debug!("Subscribed to daemon events");
+                    loop {
+                        match stream.next().await {
+                            Ok(Some(event)) => {
+                                if tx.send(event).await.is_err() {
+                                    return;
+                                }
+                            }
+                            Ok(None) => break,
+                            Err(e) => {
+                                debug!("Daemon event stream ended: {}", e);
+                                break;
+                            }
+                        }
+                    }
+                }
+                Err(e) => debug!("Daemon event subscription unavailable: {}", e),
+            }
+
+            if tx.is_closed() {
+                return;
+            }
+            tokio::time::sleep(EVENT_RECONNECT_DELAY).await;
+        }
+    });
+
+    rx
+}
+
+/// Notifications to send to the client for a daemon event
+pub(crate) fn event_notifications(
+    state: &McpServerState,
+    event: &DaemonEvent,
+) -> Vec<JsonRpcNotification> {
+    if !state.initialized {
+        return Vec::new();
+    }
+
+    let mut subscribed: Vec<&String> = state
+        .subscriptions
+        .iter()
+        .filter(|uri| resources::affects(event, uri))
+        .collect();
+    subscribed.sort();
+
+    let mut notifications: Vec<JsonRpcNotification> = subscribed
+        .into_iter()
+        .map(|uri| {
+            let params = ResourcesUpdatedNotification { uri: uri.clone() };
+            JsonRpcNotification::new("notifications/resources/updated")
+                .with_params(serde_json::to_value(params).unwrap_or_default())
+        })
+        .collect();
+
+    if resources::changes_resource_list(event) {
+        notifications.push(JsonRpcNotification::new(
+            "notifications/resources/list_changed",
+        ));
+    }
+
+    notifications
+}
+
 impl Default for McpServer {
     fn default() -> Self {
         Self::new()
@@ -172,4 +378,44 @@ mod tests {
         let disk_state = server.get_disk_state().await.unwrap();
         assert!(!disk_state.detected);
     }
+
+    #[tokio::test]
+    async fn test_event_notifications_for_subscribers() {
+        let server = McpServer::with_disk_state(DiskState::mock_detected());
+        let mut state = server.state.write().await;
+        let event = DaemonEvent::PresigConsumed {
+            child_id: "abcd".to_string(),
+            presig_index: 1,
+            presigs_remaining: 99,
+        };
+
+        state
+            .subscriptions
+            .insert("sigil://disk/status".to_string());
+        state
+            .subscriptions
+            .insert("sigil://children/ef01".to_string());
+        assert!(event_notifications(&state, &event).is_empty());
+
+        state.initialized = true;
+        let notifications = event_notifications(&state, &event);
+        assert_eq!(notifications.len(), 1);
+        assert_eq!(notifications[0].method, "notifications/resources/updated");
+        assert_eq!(
+            notifications[0].params,
+            Some(serde_json::json!({ "uri": "sigil://disk/status" }))
+        );
+
+        // Removal changes every child resource as well as the disk status
+        let notifications = event_notifications(&state, &DaemonEvent::DiskRemoved);
+        assert_eq!(notifications.len(), 3);
+        assert_eq!(
+            notifications[0].params,
+            Some(serde_json::json!({ "uri": "sigil://children/ef01" }))
+        );
+        assert_eq!(
+            notifications[2].method,
+            "notifications/resources/list_changed"
+        );
+    }
 }
@@ -49,6 +49,20 @@ pub fn tool_definition() -> Tool {
                 "is_valid": {
                     "type": "boolean",
                     "description": "Whether the disk passes validation"
+                },
+                "disks": {
+                    "type": "array",
+                    "description": "Every inserted disk, when more than one is inserted",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "child_id": { "type": "string" },
+                            "presigs_remaining": { "type": "integer" },
+                            "presigs_total": { "type": "integer" },
+                            "days_until_expiry": { "type": "integer" },
+                            "is_valid": { "type": "boolean" }
+                        }
+                    }
                 }
             },
             "required": ["detected"]
@@ -91,6 +105,49 @@ pub async fn execute(ctx: &ToolContext) -> ToolsCallResult {
         );
     }
 
+    if state.disks.len() > 1 {
+        let disks: Vec<_> = state
+            .disks
+            .iter()
+            .map(|disk| {
+                serde_json::json!({
+                    "child_id": disk.child_id,
+                    "presigs_remaining": disk.presigs_remaining,
+                    "presigs_total": disk.presigs_total,
+                    "days_until_expiry": disk.days_until_expiry,
+                    "is_valid": disk.is_valid
+                })
+            })
+            .collect();
+
+        let mut status_lines = vec![format!("{} Sigil disks detected", state.disks.len())];
+        for disk in &state.disks {
+            status_lines.push(format!(
+                "├─ sigil_{}: {}/{} presigs, {} days, {}",
+                disk.child_id.as_deref().unwrap_or("unknown"),
+                disk.presigs_remaining.unwrap_or(0),
+                disk.presigs_total.unwrap_or(0),
+                disk.days_until_expiry.unwrap_or(0),
+                if disk.is_valid == Some(true) {
+                    "✓ Valid"
+                } else {
+                    "✗ Invalid"
+                }
+            ));
+        }
+        status_lines.push("└─ Pass child_id to signing tools to choose a disk".to_string());
+
+        let result = serde_json::json!({
+            "detected": true,
+            "disks": disks
+        });
+
+        return ToolsCallResult::success_with_structured(
+            vec![ToolContent::text(status_lines.join("\n"))],
+            result,
+        );
+    }
+
     let result = serde_json::json!({
         "detected": true,
         "child_id": state.child_id,
@@ -154,9 +211,7 @@ mod tests {
     async fn test_check_disk_detected() {
         use crate::client::DaemonClient;
 
-        let ctx = ToolContext {
-            daemon_client: Arc::new(DaemonClient::new_mock(DiskState::mock_detected())),
-        };
+        let ctx = ToolContext::new(Arc::new(DaemonClient::new_mock(DiskState::mock_detected())));
 
         let result = execute(&ctx).await;
         assert!(result.is_error.is_none() || result.is_error == Some(false));
@@ -167,9 +222,7 @@ mod tests {
     async fn test_check_disk_not_detected() {
         use crate::client::DaemonClient;
 
-        let ctx = ToolContext {
-            daemon_client: Arc::new(DaemonClient::new_mock(DiskState::default())),
-        };
+        let ctx = ToolContext::new(Arc::new(DaemonClient::new_mock(DiskState::default())));
 
         let result = execute(&ctx).await;
         assert!(result.is_error.is_none() || result.is_error == Some(false));
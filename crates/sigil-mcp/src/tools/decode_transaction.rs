@@ -0,0 +1,244 @@
+//! Decode transaction tool
+//!
+//! Decodes an unsigned EVM transaction, or bare calldata, against the
+//! server's selector registry so the agent (and the user) can see what a
+//! signature would authorize before asking for it.
+
+use crate::protocol::{Tool, ToolAnnotations, ToolContent, ToolsCallResult};
+use serde::Deserialize;
+use sigil_cli::decode::{self, CallInput, DecodedCall, DecodedTransaction, Severity};
+
+use super::ToolContext;
+
+/// Decode transaction input parameters
+#[derive(Debug, Deserialize)]
+pub struct DecodeTransactionParams {
+    /// Unsigned transaction (hex); takes precedence over `data`
+    #[serde(default)]
+    pub transaction: Option<String>,
+
+    /// Calldata (hex)
+    #[serde(default)]
+    pub data: Option<String>,
+
+    /// Contract the calldata is sent to
+    #[serde(default)]
+    pub to: Option<String>,
+
+    /// Value sent with the calldata, in wei (decimal string)
+    #[serde(default)]
+    pub value: Option<String>,
+
+    /// Chain the calldata is sent on
+    #[serde(default)]
+    pub chain_id: Option<u64>,
+}
+
+/// Get the tool definition
+pub fn tool_definition() -> Tool {
+    Tool {
+        name: "sigil_decode_transaction".to_string(),
+        title: Some("Decode EVM Transaction".to_string()),
+        description:
+            "Decode an unsigned EVM transaction (legacy, EIP-2930 or EIP-1559) or raw calldata \
+             using the local selector registry (ERC-20, ERC-721/1155, Permit2, common routers, \
+             Safe). Shows the method, recipient and amounts, and flags risky patterns such as \
+             unlimited approvals, setApprovalForAll and delegatecalls to unknown contracts. \
+             Does not use a presignature. Pass the same transaction to sigil_sign_evm to sign \
+             it with this summary in the audit log."
+                .to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "transaction": {
+                    "type": "string",
+                    "pattern": "^(0x)?[a-fA-F0-9]+$",
+                    "description": "RLP-encoded unsigned transaction (hex)"
+                },
+                "data": {
+                    "type": "string",
+                    "pattern": "^(0x)?[a-fA-F0-9]*$",
+                    "description": "Calldata to decode when there is no encoded transaction (hex)"
+                },
+                "to": {
+                    "type": "string",
+                    "description": "Contract the calldata is sent to"
+                },
+                "value": {
+                    "type": "string",
+                    "pattern": "^[0-9]+$",
+                    "description": "Value sent with the calldata, in wei"
+                },
+                "chain_id": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "description": "Chain the calldata is sent on"
+                }
+            },
+            "additionalProperties": false
+        }),
+        output_schema: Some(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "kind": { "type": "string" },
+                "chain_id": { "type": ["integer", "null"] },
+                "to": { "type": ["string", "null"] },
+                "to_name": { "type": ["string", "null"] },
+                "value": { "type": "string" },
+                "signing_hash": {
+                    "type": ["string", "null"],
+                    "description": "Hash sigil_sign_evm signs for this transaction"
+                },
+                "call": { "type": ["object", "null"] },
+                "risks": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "code": { "type": "string" },
+                            "severity": { "type": "string", "enum": ["warning", "high"] },
+                            "message": { "type": "string" }
+                        }
+                    }
+                },
+                "summary": { "type": "string" }
+            },
+            "required": ["kind", "value", "risks", "summary"]
+        })),
+        annotations: Some(ToolAnnotations {
+            read_only_hint: Some(true),
+            destructive_hint: Some(false),
+            idempotent_hint: Some(true),
+            open_world_hint: Some(false),
+        }),
+    }
+}
+
+/// Decode the transaction or calldata in the parameters
+fn decode(
+    ctx: &ToolContext,
+    params: &DecodeTransactionParams,
+) -> Result<DecodedTransaction, String> {
+    let decoded = match (&params.transaction, &params.data) {
+        (Some(transaction), _) => decode::decode_transaction(transaction, &ctx.selectors),
+        (None, Some(data)) => decode::decode_calldata(
+            &CallInput {
+                chain_id: params.chain_id,
+                to: params.to.clone(),
+                value: params.value.clone(),
+                data: data.clone(),
+            },
+            &ctx.selectors,
+        ),
+        (None, None) => return Err("Provide either transaction or data".to_string()),
+    };
+    decoded.map_err(|e| format!("Failed to decode: {}", e))
+}
+
+/// Execute the decode transaction tool
+pub async fn execute(ctx: &ToolContext, arguments: serde_json::Value) -> ToolsCallResult {
+    let params: DecodeTransactionParams = match serde_json::from_value(arguments) {
+        Ok(p) => p,
+        Err(e) => {
+            return ToolsCallResult::error(format!("Invalid parameters: {}", e));
+        }
+    };
+
+    let decoded = match decode(ctx, &params) {
+        Ok(decoded) => decoded,
+        Err(msg) => return ToolsCallResult::error(msg),
+    };
+
+    let mut text = decoded.summary.clone();
+    if let Some(call) = &decoded.call {
+        push_call(&mut text, call, 0);
+    }
+    for risk in &decoded.risks {
+        let marker = match risk.severity {
+            Severity::High => "⚠ HIGH RISK",
+            Severity::Warning => "⚠",
+        };
+        text.push_str(&format!("\n{}: {}", marker, risk.message));
+    }
+    if let Some(hash) = &decoded.signing_hash {
+        text.push_str(&format!("\nSigning hash: {}", hash));
+    }
+
+    let result = serde_json::to_value(&decoded).unwrap_or_default();
+    ToolsCallResult::success_with_structured(vec![ToolContent::text(text)], result)
+}
+
+fn push_call(text: &mut String, call: &DecodedCall, depth: usize) {
+    let indent = "  ".repeat(depth);
+    text.push_str(&format!(
+        "\n{}├─ {}",
+        indent,
+        call.method
+            .as_deref()
+            .unwrap_or(&format!("unknown method {}", call.selector))
+    ));
+    for param in &call.params {
+        let value = match param.value.as_str() {
+            Some(value) => value.to_string(),
+            None => param.value.to_string(),
+        };
+        match &param.display {
+            Some(display) => text.push_str(&format!(
+                "\n{}│  {}: {} ({})",
+                indent, param.name, display, value
+            )),
+            None => text.push_str(&format!("\n{}│  {}: {}", indent, param.name, value)),
+        }
+    }
+    for nested in &call.calls {
+        push_call(text, nested, depth + 1);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::DaemonClient;
+    use crate::tools::DiskState;
+    use std::sync::Arc;
+
+    const UNLIMITED_APPROVE: &str = "0x095ea7b3\
+        0000000000000000000000001111111254eeb25477b68fb85ed929f73a960582\
+        ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffff";
+
+    #[tokio::test]
+    async fn test_decode_calldata_flags_unlimited_approval() {
+        let ctx = ToolContext::new(Arc::new(DaemonClient::new_mock(DiskState::mock_detected())));
+
+        let result = execute(
+            &ctx,
+            serde_json::json!({
+                "data": UNLIMITED_APPROVE,
+                "to": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+                "chain_id": 1
+            }),
+        )
+        .await;
+        assert!(result.is_error.is_none());
+
+        let structured = result.structured_content.unwrap();
+        assert_eq!(structured["to_name"], "USDC");
+        assert_eq!(structured["call"]["method"], "approve(address,uint256)");
+        assert_eq!(structured["risks"][0]["code"], "unlimited_approval");
+        assert_eq!(structured["risks"][0]["severity"], "high");
+        assert!(serde_json::to_string(&result.content)
+            .unwrap()
+            .contains("HIGH RISK"));
+    }
+
+    #[tokio::test]
+    async fn test_decode_requires_input() {
+        let ctx = ToolContext::new(Arc::new(DaemonClient::new_mock(DiskState::mock_detected())));
+
+        let result = execute(&ctx, serde_json::json!({})).await;
+        assert_eq!(result.is_error, Some(true));
+
+        let result = execute(&ctx, serde_json::json!({ "transaction": "0x03c0" })).await;
+        assert_eq!(result.is_error, Some(true));
+    }
+}
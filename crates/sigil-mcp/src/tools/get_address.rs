@@ -212,9 +212,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_get_address_default() {
-        let ctx = ToolContext {
-            daemon_client: Arc::new(DaemonClient::new_mock(DiskState::mock_detected())),
-        };
+        let ctx = ToolContext::new(Arc::new(DaemonClient::new_mock(DiskState::mock_detected())));
 
         let result = execute(&ctx, serde_json::json!({})).await;
         assert!(result.is_error.is_none() || result.is_error == Some(false));
@@ -222,9 +220,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_get_address_evm() {
-        let ctx = ToolContext {
-            daemon_client: Arc::new(DaemonClient::new_mock(DiskState::mock_detected())),
-        };
+        let ctx = ToolContext::new(Arc::new(DaemonClient::new_mock(DiskState::mock_detected())));
 
         let args = serde_json::json!({
             "format": "evm"
@@ -241,9 +237,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_get_address_cosmos() {
-        let ctx = ToolContext {
-            daemon_client: Arc::new(DaemonClient::new_mock(DiskState::mock_detected())),
-        };
+        let ctx = ToolContext::new(Arc::new(DaemonClient::new_mock(DiskState::mock_detected())));
 
         let args = serde_json::json!({
             "format": "cosmos",
@@ -261,9 +255,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_get_address_no_disk() {
-        let ctx = ToolContext {
-            daemon_client: Arc::new(DaemonClient::new_mock(DiskState::default())),
-        };
+        let ctx = ToolContext::new(Arc::new(DaemonClient::new_mock(DiskState::default())));
 
         let result = execute(&ctx, serde_json::json!({})).await;
         assert_eq!(result.is_error, Some(true));
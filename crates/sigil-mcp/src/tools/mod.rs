@@ -3,22 +3,61 @@
 //! This module defines all the tools that the Sigil MCP server exposes.
 
 mod check_disk;
+mod decode_transaction;
 mod get_address;
 mod sign_evm;
 mod sign_frost;
+pub mod signing_history;
 mod update_tx_hash;
 
 use crate::client::DaemonClient;
+use crate::confirmation::Confirmer;
+use crate::peer::ClientPeer;
+use crate::progress::{Cancellation, ProgressReporter};
 use crate::protocol::{Tool, ToolAnnotations, ToolContent, ToolsCallResult};
+use sigil_cli::decode::SelectorRegistry;
 use std::sync::Arc;
 
 /// Tool execution context
 pub struct ToolContext {
     /// Daemon client for signing operations
     pub daemon_client: Arc<DaemonClient>,
+
+    /// Decides which signatures need the user's confirmation
+    pub confirmer: Arc<Confirmer>,
+
+    /// Known selectors, contracts and tokens for decoding transactions
+    pub selectors: Arc<SelectorRegistry>,
+
+    /// Channel for asking the user, if the client supports elicitation
+    pub elicitation: Option<ClientPeer>,
+
+    /// Reports progress, if the client asked for it
+    pub progress: Option<ProgressReporter>,
+
+    /// Set when the client cancels the call
+    pub cancellation: Cancellation,
+}
+
+impl ToolContext {
+    /// Context with the default (no confirmation) policy, the built-in
+    /// selector registry, no elicitation and no progress reporting
+    pub fn new(daemon_client: Arc<DaemonClient>) -> Self {
+        Self {
+            daemon_client,
+            confirmer: Arc::new(Confirmer::default()),
+            selectors: Arc::new(SelectorRegistry::builtin()),
+            elicitation: None,
+            progress: None,
+            cancellation: Cancellation::new(),
+        }
+    }
 }
 
 /// Current disk state (simplified for MCP server)
+///
+/// When several disks are inserted the single-disk fields are unset and
+/// `disks` holds one state per disk; use [`DiskState::select`] to pick one.
 #[derive(Debug, Clone, Default)]
 pub struct DiskState {
     pub detected: bool,
@@ -29,6 +68,7 @@ pub struct DiskState {
     pub days_until_expiry: Option<u32>,
     pub is_valid: Option<bool>,
     pub public_key: Option<String>,
+    pub disks: Vec<DiskState>,
 }
 
 impl DiskState {
@@ -46,6 +86,7 @@ impl DiskState {
                 "0x04abc123def456789012345678901234567890123456789012345678901234567890"
                     .to_string(),
             ),
+            disks: Vec::new(),
         }
     }
 
@@ -53,16 +94,130 @@ impl DiskState {
     pub fn no_disk() -> Self {
         Self::default()
     }
+
+    /// Create a state with several disks inserted
+    pub fn multiple(disks: Vec<DiskState>) -> Self {
+        Self {
+            detected: !disks.is_empty(),
+            disks,
+            ..Default::default()
+        }
+    }
+
+    /// Short IDs of every inserted disk
+    pub fn child_ids(&self) -> Vec<&str> {
+        if self.disks.is_empty() {
+            self.child_id.as_deref().into_iter().collect()
+        } else {
+            self.disks
+                .iter()
+                .filter_map(|disk| disk.child_id.as_deref())
+                .collect()
+        }
+    }
+
+    /// Narrow the state to the disk a request targets
+    ///
+    /// `child_id` is a full or short hex child ID. Without one, the state is
+    /// only usable if at most one disk is inserted.
+    pub fn select(&self, child_id: Option<&str>) -> Result<DiskState, String> {
+        let Some(selector) = child_id else {
+            if self.disks.len() > 1 {
+                return Err(format!(
+                    "Multiple signing disks detected (sigil_{}). Specify child_id to choose one.",
+                    self.child_ids().join(", sigil_")
+                ));
+            }
+            return Ok(self.clone());
+        };
+
+        let candidates = if self.disks.is_empty() {
+            std::slice::from_ref(self)
+        } else {
+            self.disks.as_slice()
+        };
+
+        let mut matches = candidates.iter().filter(|disk| {
+            disk.child_id
+                .as_deref()
+                .is_some_and(|id| child_matches(id, selector))
+        });
+
+        match (matches.next(), matches.next()) {
+            (Some(disk), None) => Ok(disk.clone()),
+            (Some(_), Some(_)) => Err(format!(
+                "child_id '{}' matches more than one inserted disk",
+                selector
+            )),
+            (None, _) => Err(format!("No inserted disk matches child_id '{}'", selector)),
+        }
+    }
+}
+
+/// Tool result for a signing request the daemon is holding for approval
+///
+/// Reported as an error so the agent doesn't mistake it for a signature; the
+/// text tells it to repeat the call once a person has approved the request.
+fn approval_pending(approval_id: &str, expires_at: u64) -> ToolsCallResult {
+    ToolsCallResult::error(format!(
+        "Signing request {} is awaiting human approval. Ask the user to approve it \
+         (sigil approvals approve {} or the Sigil app), then call this tool again with \
+         the same arguments before {} (unix time) to receive the signature.",
+        approval_id, approval_id, expires_at
+    ))
+}
+
+/// Report a signing stage if the client asked for progress
+async fn report_stage(ctx: &ToolContext, stage: &str) {
+    if let Some(progress) = &ctx.progress {
+        progress.signing_stage(stage).await;
+    }
+}
+
+/// Result of a signing call cancelled before its presig was committed
+fn signing_cancelled() -> ToolsCallResult {
+    ToolsCallResult::error(
+        "Signing was cancelled before the signature was committed; no presignature was used.",
+    )
+}
+
+/// Line added to a signature whose cancellation arrived too late
+fn cancelled_after_commit(presig_index: u32) -> String {
+    format!(
+        "\n⚠ Cancellation arrived after the signature was committed: presig #{} was used \
+         and the signature is valid",
+        presig_index
+    )
+}
+
+/// Parse an optional decimal amount argument
+fn parse_amount(value: Option<&str>) -> Result<Option<u128>, ToolsCallResult> {
+    value
+        .map(|v| {
+            v.parse::<u128>().map_err(|_| {
+                ToolsCallResult::error(format!("Invalid value '{}': expected a decimal integer", v))
+            })
+        })
+        .transpose()
+}
+
+/// Whether a disk's (possibly shortened) ID matches a user-supplied selector
+fn child_matches(id: &str, selector: &str) -> bool {
+    let selector = selector.trim_start_matches("0x").to_ascii_lowercase();
+    let id = id.to_ascii_lowercase();
+    !selector.is_empty() && (id.starts_with(&selector) || selector.starts_with(&id))
 }
 
 /// Get all tool definitions
 pub fn get_all_tools() -> Vec<Tool> {
     vec![
         check_disk::tool_definition(),
+        decode_transaction::tool_definition(),
         sign_evm::tool_definition(),
         sign_frost::tool_definition(),
         get_address::tool_definition(),
         update_tx_hash::tool_definition(),
+        signing_history::tool_definition(),
         list_schemes_tool_definition(),
         get_presig_count_tool_definition(),
     ]
@@ -114,7 +269,12 @@ fn get_presig_count_tool_definition() -> Tool {
             .to_string(),
         input_schema: serde_json::json!({
             "type": "object",
-            "properties": {},
+            "properties": {
+                "child_id": {
+                    "type": "string",
+                    "description": "Disk to query (short or full child ID); required when several disks are inserted"
+                }
+            },
             "additionalProperties": false
         }),
         output_schema: Some(serde_json::json!({
@@ -165,8 +325,13 @@ pub async fn execute_list_schemes() -> ToolsCallResult {
 }
 
 /// Execute get presig count tool
-pub async fn execute_get_presig_count(ctx: &ToolContext) -> ToolsCallResult {
-    match ctx.daemon_client.get_presig_count().await {
+pub async fn execute_get_presig_count(
+    ctx: &ToolContext,
+    arguments: serde_json::Value,
+) -> ToolsCallResult {
+    let child_id = arguments.get("child_id").and_then(|v| v.as_str());
+
+    match ctx.daemon_client.get_presig_count(child_id).await {
         Ok((remaining, total)) => {
             let percentage = if total > 0 {
                 (remaining as f64 / total as f64) * 100.0
@@ -200,12 +365,14 @@ pub async fn execute_tool(
 ) -> ToolsCallResult {
     match name {
         "sigil_check_disk" => check_disk::execute(ctx).await,
+        "sigil_decode_transaction" => decode_transaction::execute(ctx, arguments).await,
         "sigil_sign_evm" => sign_evm::execute(ctx, arguments).await,
         "sigil_sign_frost" => sign_frost::execute(ctx, arguments).await,
         "sigil_get_address" => get_address::execute(ctx, arguments).await,
         "sigil_update_tx_hash" => update_tx_hash::execute(ctx, arguments).await,
+        "sigil_signing_history" => signing_history::execute(ctx, arguments).await,
         "sigil_list_schemes" => execute_list_schemes().await,
-        "sigil_get_presig_count" => execute_get_presig_count(ctx).await,
+        "sigil_get_presig_count" => execute_get_presig_count(ctx, arguments).await,
         _ => ToolsCallResult::error(format!("Unknown tool: {}", name)),
     }
 }
@@ -1,21 +1,41 @@
 //! Sign EVM transaction tool
 
+use crate::confirmation::SigningSummary;
 use crate::protocol::{Tool, ToolAnnotations, ToolContent, ToolsCallResult};
 use serde::Deserialize;
+use sigil_cli::decode::{self, Severity};
 
 use super::ToolContext;
 
 /// Sign EVM input parameters
 #[derive(Debug, Deserialize)]
 pub struct SignEvmParams {
-    /// Transaction hash to sign (hex with 0x prefix)
-    pub message_hash: String,
+    /// Transaction hash to sign (hex with 0x prefix); derived from
+    /// `transaction` when that is given
+    #[serde(default)]
+    pub message_hash: Option<String>,
+
+    /// Unsigned transaction (hex); decoded for the audit log and confirmation
+    #[serde(default)]
+    pub transaction: Option<String>,
 
     /// EIP-155 chain ID
     pub chain_id: u32,
 
     /// Human-readable description for audit log
     pub description: String,
+
+    /// Disk to use (short or full child ID); required when several disks are inserted
+    #[serde(default)]
+    pub child_id: Option<String>,
+
+    /// Value the transaction moves, in wei (decimal string)
+    #[serde(default)]
+    pub value: Option<String>,
+
+    /// Recipient address
+    #[serde(default)]
+    pub to: Option<String>,
 }
 
 /// Get the tool definition
@@ -25,8 +45,12 @@ pub fn tool_definition() -> Tool {
         title: Some("Sign EVM Transaction".to_string()),
         description:
             "Sign a transaction hash for EVM-compatible chains (Ethereum, Polygon, Arbitrum, etc.) \
-             using ECDSA. Requires a valid Sigil disk with remaining presignatures. \
-             Each call consumes one presignature."
+             using ECDSA. Prefer passing the unsigned transaction: it is decoded, its hash is \
+             signed, and the decoded summary is recorded in the audit log ahead of the \
+             description. Requires a valid Sigil disk with remaining presignatures. \
+             Each call consumes one presignature. High-risk signatures may ask the user to \
+             confirm first. If the daemon holds the request for human approval, call again \
+             with the same arguments once it is approved."
                 .to_string(),
         input_schema: serde_json::json!({
             "type": "object",
@@ -34,7 +58,12 @@ pub fn tool_definition() -> Tool {
                 "message_hash": {
                     "type": "string",
                     "pattern": "^0x[a-fA-F0-9]{64}$",
-                    "description": "32-byte transaction hash to sign (hex with 0x prefix)"
+                    "description": "32-byte transaction hash to sign (hex with 0x prefix); optional when transaction is given, and must match it if both are"
+                },
+                "transaction": {
+                    "type": "string",
+                    "pattern": "^(0x)?[a-fA-F0-9]+$",
+                    "description": "RLP-encoded unsigned transaction (hex); its decoded method, amounts and risks are logged and shown for confirmation"
                 },
                 "chain_id": {
                     "type": "integer",
@@ -45,9 +74,22 @@ pub fn tool_definition() -> Tool {
                     "type": "string",
                     "maxLength": 256,
                     "description": "Human-readable description for the audit log (e.g., 'Transfer 0.1 ETH to vitalik.eth')"
+                },
+                "child_id": {
+                    "type": "string",
+                    "description": "Disk to sign with (short or full child ID); required when several disks are inserted"
+                },
+                "value": {
+                    "type": "string",
+                    "pattern": "^[0-9]+$",
+                    "description": "Amount the transaction moves, in wei; requests above the daemon's approval threshold wait for a human"
+                },
+                "to": {
+                    "type": "string",
+                    "description": "Recipient address, shown to the user when confirmation is required"
                 }
             },
-            "required": ["message_hash", "chain_id", "description"]
+            "required": ["chain_id", "description"]
         }),
         output_schema: Some(serde_json::json!({
             "type": "object",
@@ -75,6 +117,10 @@ pub fn tool_definition() -> Tool {
                 "proof_hash": {
                     "type": "string",
                     "description": "ZK proof hash for audit verification (hex)"
+                },
+                "cancelled_after_commit": {
+                    "type": "boolean",
+                    "description": "The call was cancelled too late to stop the signature"
                 }
             },
             "required": ["signature", "v", "r", "s", "presig_index"]
@@ -100,14 +146,91 @@ pub async fn execute(ctx: &ToolContext, arguments: serde_json::Value) -> ToolsCa
         }
     };
 
+    let decoded = match &params.transaction {
+        Some(transaction) => match decode::decode_transaction(transaction, &ctx.selectors) {
+            Ok(decoded) => Some(decoded),
+            Err(e) => return ToolsCallResult::error(format!("Invalid transaction: {}", e)),
+        },
+        None => None,
+    };
+
+    let message_hash = match (&decoded, &params.message_hash) {
+        (Some(decoded), message_hash) => {
+            let Some(signing_hash) = decoded.signing_hash.clone() else {
+                return ToolsCallResult::error("Invalid transaction: it is already signed");
+            };
+            if message_hash
+                .as_ref()
+                .is_some_and(|hash| !hash.eq_ignore_ascii_case(&signing_hash))
+            {
+                return ToolsCallResult::error(format!(
+                    "message_hash does not match the transaction, whose signing hash is {}",
+                    signing_hash
+                ));
+            }
+            if let Some(chain_id) = decoded.chain_id {
+                if chain_id != params.chain_id as u64 {
+                    return ToolsCallResult::error(format!(
+                        "chain_id {} does not match the transaction's chain ID {}",
+                        params.chain_id, chain_id
+                    ));
+                }
+            }
+            signing_hash
+        }
+        (None, Some(message_hash)) => message_hash.clone(),
+        (None, None) => {
+            return ToolsCallResult::error("Provide message_hash or transaction");
+        }
+    };
+
     // Validate message hash format
-    if !params.message_hash.starts_with("0x") || params.message_hash.len() != 66 {
+    if !message_hash.starts_with("0x") || message_hash.len() != 66 {
         return ToolsCallResult::error(
             "Invalid message_hash: must be 32 bytes hex with 0x prefix (66 characters total)",
         );
     }
 
+    // What the transaction really does goes first, then the agent's note
+    let description = match &decoded {
+        Some(decoded) => decoded.signing_description(&params.description),
+        None => params.description.clone(),
+    };
+    let value = decoded
+        .as_ref()
+        .map(|decoded| decoded.value.clone())
+        .or_else(|| params.value.clone());
+    // A token transfer is checked by its recipient and amount, not by the
+    // token contract it calls; other contract calls can't be checked at all
+    let transfer = decoded
+        .as_ref()
+        .and_then(|decoded| decoded.token_transfer());
+    let destination = match transfer {
+        Some(transfer) => Some(transfer.recipient.clone()),
+        None => decoded
+            .as_ref()
+            .and_then(|decoded| decoded.to.clone())
+            .or_else(|| params.to.clone()),
+    };
+    let unchecked_call = decoded
+        .as_ref()
+        .filter(|_| transfer.is_none())
+        .and_then(|decoded| decoded.call.as_ref())
+        .map(|call| {
+            call.label
+                .clone()
+                .or_else(|| call.method.clone())
+                .unwrap_or_else(|| format!("Unknown method {}", call.selector))
+        });
+    let risks = decoded
+        .iter()
+        .flat_map(|decoded| &decoded.risks)
+        .filter(|risk| risk.severity == Severity::High)
+        .map(|risk| risk.message.clone())
+        .collect();
+
     // Check disk status first
+    super::report_stage(ctx, "reading_disk").await;
     let state = match ctx.daemon_client.get_disk_status().await {
         Ok(s) => s,
         Err(ClientError::DaemonNotRunning) => {
@@ -126,6 +249,11 @@ pub async fn execute(ctx: &ToolContext, arguments: serde_json::Value) -> ToolsCa
         );
     }
 
+    let state = match state.select(params.child_id.as_deref()) {
+        Ok(state) => state,
+        Err(msg) => return ToolsCallResult::error(msg),
+    };
+
     if state.is_valid != Some(true) {
         return ToolsCallResult::error(
             "Signing disk is invalid or expired. Please use a valid Sigil disk.",
@@ -149,10 +277,50 @@ pub async fn execute(ctx: &ToolContext, arguments: serde_json::Value) -> ToolsCa
         }
     }
 
+    let amount = match transfer {
+        Some(transfer) => transfer.amount.parse().ok(),
+        None => match super::parse_amount(value.as_deref()) {
+            Ok(amount) => amount,
+            Err(result) => return result,
+        },
+    };
+    let summary = SigningSummary {
+        chain: params.chain_id.to_string(),
+        chain_name: format!("{} ({})", get_chain_name(params.chain_id), params.chain_id),
+        description: description.clone(),
+        message_hash: message_hash.clone(),
+        destination,
+        value: amount,
+        token: transfer.map(|transfer| transfer.token.clone()),
+        unchecked_call,
+        risks,
+        presigs_remaining: remaining,
+    };
+    super::report_stage(ctx, "checking_policy").await;
+    if let Err(reason) = ctx
+        .confirmer
+        .confirm(ctx.elicitation.as_ref(), &summary)
+        .await
+    {
+        return ToolsCallResult::error(reason);
+    }
+    if ctx.cancellation.is_cancelled() {
+        return super::signing_cancelled();
+    }
+
     // Call daemon to sign
     let sign_result = match ctx
         .daemon_client
-        .sign(&params.message_hash, params.chain_id, &params.description)
+        .sign(
+            &message_hash,
+            params.chain_id,
+            &description,
+            params.child_id.as_deref(),
+            value.as_deref(),
+            params.transaction.as_deref(),
+            ctx.progress.as_ref(),
+            &ctx.cancellation,
+        )
         .await
     {
         Ok(r) => r,
@@ -162,11 +330,20 @@ pub async fn execute(ctx: &ToolContext, arguments: serde_json::Value) -> ToolsCa
         Err(ClientError::SigningFailed(msg)) => {
             return ToolsCallResult::error(format!("Signing failed: {}", msg));
         }
+        Err(ClientError::Cancelled) => return super::signing_cancelled(),
+        Err(ClientError::ApprovalPending {
+            approval_id,
+            expires_at,
+        }) => {
+            return super::approval_pending(&approval_id, expires_at);
+        }
         Err(e) => {
             return ToolsCallResult::error(format!("Signing error: {}", e));
         }
     };
 
+    ctx.confirmer.remember(&summary);
+
     // Parse signature into v, r, s components
     // Signature format from daemon: hex string (may or may not have 0x prefix)
     let sig_hex = sign_result.signature.trim_start_matches("0x");
@@ -191,12 +368,13 @@ pub async fn execute(ctx: &ToolContext, arguments: serde_json::Value) -> ToolsCa
         "presig_index": sign_result.presig_index,
         "proof_hash": sign_result.proof_hash,
         "chain_id": params.chain_id,
-        "message_hash": params.message_hash
+        "message_hash": message_hash,
+        "cancelled_after_commit": sign_result.cancelled_after_commit
     });
 
     let chain_name = get_chain_name(params.chain_id);
 
-    let text = format!(
+    let mut text = format!(
         "✓ Transaction signed successfully\n\
          ├─ Chain: {} (ID: {})\n\
          ├─ Hash: {}...{}\n\
@@ -204,12 +382,15 @@ pub async fn execute(ctx: &ToolContext, arguments: serde_json::Value) -> ToolsCa
          └─ Description: {}",
         chain_name,
         params.chain_id,
-        &params.message_hash[..10],
-        &params.message_hash[58..],
+        &message_hash[..10],
+        &message_hash[58..],
         sign_result.presig_index,
         remaining - 1,
-        params.description
+        description
     );
+    if sign_result.cancelled_after_commit {
+        text.push_str(&super::cancelled_after_commit(sign_result.presig_index));
+    }
 
     ToolsCallResult::success_with_structured(vec![ToolContent::text(text)], result)
 }
@@ -242,14 +423,13 @@ fn get_chain_name(chain_id: u32) -> &'static str {
 mod tests {
     use super::*;
     use crate::client::DaemonClient;
+    use crate::confirmation::{ConfirmationPolicy, Confirmer};
     use crate::tools::DiskState;
     use std::sync::Arc;
 
     #[tokio::test]
     async fn test_sign_evm_success() {
-        let ctx = ToolContext {
-            daemon_client: Arc::new(DaemonClient::new_mock(DiskState::mock_detected())),
-        };
+        let ctx = ToolContext::new(Arc::new(DaemonClient::new_mock(DiskState::mock_detected())));
 
         let args = serde_json::json!({
             "message_hash": "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef",
@@ -261,11 +441,26 @@ mod tests {
         assert!(result.is_error.is_none() || result.is_error == Some(false));
     }
 
+    #[tokio::test]
+    async fn test_sign_evm_cancelled_before_commit() {
+        let ctx = ToolContext::new(Arc::new(DaemonClient::new_mock(DiskState::mock_detected())));
+        ctx.cancellation.cancel();
+
+        let args = serde_json::json!({
+            "message_hash": "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef",
+            "chain_id": 1,
+            "description": "Test transfer"
+        });
+
+        let result = execute(&ctx, args).await;
+        assert_eq!(result.is_error, Some(true));
+        assert!(serde_json::to_string(&result.content)
+            .unwrap()
+            .contains("no presignature was used"));
+    }
     #[tokio::test]
     async fn test_sign_evm_no_disk() {
-        let ctx = ToolContext {
-            daemon_client: Arc::new(DaemonClient::new_mock(DiskState::default())),
-        };
+        let ctx = ToolContext::new(Arc::new(DaemonClient::new_mock(DiskState::default())));
 
         let args = serde_json::json!({
             "message_hash": "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef",
@@ -279,9 +474,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_sign_evm_invalid_hash() {
-        let ctx = ToolContext {
-            daemon_client: Arc::new(DaemonClient::new_mock(DiskState::mock_detected())),
-        };
+        let ctx = ToolContext::new(Arc::new(DaemonClient::new_mock(DiskState::mock_detected())));
 
         let args = serde_json::json!({
             "message_hash": "invalid",
@@ -292,4 +485,120 @@ mod tests {
         let result = execute(&ctx, args).await;
         assert_eq!(result.is_error, Some(true));
     }
+
+    #[tokio::test]
+    async fn test_sign_evm_refused_without_confirmation() {
+        let mut ctx =
+            ToolContext::new(Arc::new(DaemonClient::new_mock(DiskState::mock_detected())));
+        ctx.confirmer = Arc::new(Confirmer::new(ConfirmationPolicy {
+            value_thresholds: [("1".to_string(), 1_000)].into(),
+            ..Default::default()
+        }));
+
+        let args = |value: &str| {
+            serde_json::json!({
+                "message_hash": "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef",
+                "chain_id": 1,
+                "description": "Test transfer",
+                "value": value
+            })
+        };
+
+        // Below the threshold no confirmation is needed
+        let result = execute(&ctx, args("1000")).await;
+        assert!(result.is_error.is_none());
+
+        // Above it, a client without elicitation is refused
+        let result = execute(&ctx, args("1001")).await;
+        assert_eq!(result.is_error, Some(true));
+        assert!(serde_json::to_string(&result.content)
+            .unwrap()
+            .contains("does not support elicitation"));
+    }
+
+    /// Unsigned EIP-1559 transaction approving an unlimited USDC allowance
+    const APPROVE_TX: &str =
+        "0x02f8690180808504a817c80082ea6094a0b86991c6218b36c1d19d4a2e9eb0ce3606eb4880\
+        b844095ea7b30000000000000000000000001111111254eeb25477b68fb85ed929f73a960582\
+        ffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffffc0";
+
+    #[tokio::test]
+    async fn test_sign_evm_decodes_transaction() {
+        let ctx = ToolContext::new(Arc::new(DaemonClient::new_mock(DiskState::mock_detected())));
+        let args = serde_json::json!({
+            "transaction": APPROVE_TX,
+            "chain_id": 1,
+            "description": "Allow the router to swap"
+        });
+
+        let result = execute(&ctx, args.clone()).await;
+        assert!(result.is_error.is_none());
+        let text = serde_json::to_string(&result.content).unwrap();
+        assert!(text.contains("Token approve on USDC"));
+        assert!(text.contains("| Allow the router to swap"));
+
+        // The hash must match the transaction, and so must the chain
+        let mut wrong_hash = args.clone();
+        wrong_hash["message_hash"] = serde_json::json!(format!("0x{}", "ab".repeat(32)));
+        assert_eq!(execute(&ctx, wrong_hash).await.is_error, Some(true));
+        let mut wrong_chain = args;
+        wrong_chain["chain_id"] = serde_json::json!(137);
+        assert_eq!(execute(&ctx, wrong_chain).await.is_error, Some(true));
+
+        let result = execute(
+            &ctx,
+            serde_json::json!({ "chain_id": 1, "description": "x" }),
+        )
+        .await;
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[tokio::test]
+    async fn test_sign_evm_confirms_risky_transaction() {
+        let mut ctx =
+            ToolContext::new(Arc::new(DaemonClient::new_mock(DiskState::mock_detected())));
+        ctx.confirmer = Arc::new(Confirmer::new(ConfirmationPolicy {
+            confirm_risky_calls: true,
+            ..Default::default()
+        }));
+
+        let result = execute(
+            &ctx,
+            serde_json::json!({
+                "transaction": APPROVE_TX,
+                "chain_id": 1,
+                "description": "Allow the router to swap"
+            }),
+        )
+        .await;
+        assert_eq!(result.is_error, Some(true));
+        assert!(serde_json::to_string(&result.content)
+            .unwrap()
+            .contains("does not support elicitation"));
+    }
+
+    #[tokio::test]
+    async fn test_sign_evm_requires_child_with_multiple_disks() {
+        let second = DiskState {
+            child_id: Some("9c0d1e2f".to_string()),
+            ..DiskState::mock_detected()
+        };
+        let ctx = ToolContext::new(Arc::new(DaemonClient::new_mock(DiskState::multiple(vec![
+            DiskState::mock_detected(),
+            second,
+        ]))));
+
+        let args = serde_json::json!({
+            "message_hash": "0x1234567890abcdef1234567890abcdef1234567890abcdef1234567890abcdef",
+            "chain_id": 1,
+            "description": "Test transfer"
+        });
+        let result = execute(&ctx, args.clone()).await;
+        assert_eq!(result.is_error, Some(true));
+
+        let mut args = args;
+        args["child_id"] = serde_json::json!("9c0d");
+        let result = execute(&ctx, args).await;
+        assert!(result.is_error.is_none() || result.is_error == Some(false));
+    }
 }
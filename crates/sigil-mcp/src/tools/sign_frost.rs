@@ -1,5 +1,6 @@
 //! Sign with FROST threshold signatures tool
 
+use crate::confirmation::SigningSummary;
 use crate::protocol::{Tool, ToolAnnotations, ToolContent, ToolsCallResult};
 use serde::Deserialize;
 
@@ -51,6 +52,18 @@ pub struct SignFrostParams {
 
     /// Human-readable description for audit log
     pub description: String,
+
+    /// Disk to use (short or full child ID); required when several disks are inserted
+    #[serde(default)]
+    pub child_id: Option<String>,
+
+    /// Amount moved, in the chain's smallest unit (decimal string)
+    #[serde(default)]
+    pub value: Option<String>,
+
+    /// Recipient address
+    #[serde(default)]
+    pub to: Option<String>,
 }
 
 /// Get the tool definition
@@ -61,7 +74,8 @@ pub fn tool_definition() -> Tool {
         description:
             "Sign a message using FROST threshold signatures. Supports multiple signature schemes: \
              Taproot (Bitcoin BIP-340), Ed25519 (Solana, Cosmos), and Ristretto255 (Zcash shielded). \
-             Each call consumes one presignature from the disk."
+             Each call consumes one presignature from the disk. High-risk signatures may ask \
+             the user to confirm first."
                 .to_string(),
         input_schema: serde_json::json!({
             "type": "object",
@@ -80,6 +94,19 @@ pub fn tool_definition() -> Tool {
                     "type": "string",
                     "maxLength": 256,
                     "description": "Human-readable description for the audit log"
+                },
+                "child_id": {
+                    "type": "string",
+                    "description": "Disk to sign with (short or full child ID); required when several disks are inserted"
+                },
+                "value": {
+                    "type": "string",
+                    "pattern": "^[0-9]+$",
+                    "description": "Amount moved, in the chain's smallest unit (satoshis, lamports, ...)"
+                },
+                "to": {
+                    "type": "string",
+                    "description": "Recipient address, shown to the user when confirmation is required"
                 }
             },
             "required": ["scheme", "message_hash", "description"]
@@ -102,6 +129,10 @@ pub fn tool_definition() -> Tool {
                 "presig_index": {
                     "type": "integer",
                     "description": "Index of the presignature used"
+                },
+                "cancelled_after_commit": {
+                    "type": "boolean",
+                    "description": "The call was cancelled too late to stop the signature"
                 }
             },
             "required": ["scheme", "signature", "signature_length", "presig_index"]
@@ -133,6 +164,7 @@ pub async fn execute(ctx: &ToolContext, arguments: serde_json::Value) -> ToolsCa
     }
 
     // Check disk status
+    super::report_stage(ctx, "reading_disk").await;
     let state = match ctx.daemon_client.get_disk_status().await {
         Ok(s) => s,
         Err(ClientError::DaemonNotRunning) => {
@@ -151,6 +183,11 @@ pub async fn execute(ctx: &ToolContext, arguments: serde_json::Value) -> ToolsCa
         );
     }
 
+    let state = match state.select(params.child_id.as_deref()) {
+        Ok(state) => state,
+        Err(msg) => return ToolsCallResult::error(msg),
+    };
+
     if state.is_valid != Some(true) {
         return ToolsCallResult::error(
             "Signing disk is invalid or expired. Please use a valid Sigil disk.",
@@ -176,10 +213,47 @@ pub async fn execute(ctx: &ToolContext, arguments: serde_json::Value) -> ToolsCa
         }
     }
 
+    let value = match super::parse_amount(params.value.as_deref()) {
+        Ok(value) => value,
+        Err(result) => return result,
+    };
+    let summary = SigningSummary {
+        chain: params.scheme.as_str().to_string(),
+        chain_name: params.scheme.supported_chains().join(", "),
+        description: params.description.clone(),
+        message_hash: params.message_hash.clone(),
+        destination: params.to.clone(),
+        value,
+        token: None,
+        unchecked_call: None,
+        risks: Vec::new(),
+        presigs_remaining: remaining,
+    };
+    super::report_stage(ctx, "checking_policy").await;
+    if let Err(reason) = ctx
+        .confirmer
+        .confirm(ctx.elicitation.as_ref(), &summary)
+        .await
+    {
+        return ToolsCallResult::error(reason);
+    }
+    if ctx.cancellation.is_cancelled() {
+        return super::signing_cancelled();
+    }
+
     // Call daemon to sign (chain_id = 0 for non-EVM chains)
     let sign_result = match ctx
         .daemon_client
-        .sign(&params.message_hash, 0, &params.description)
+        .sign(
+            &params.message_hash,
+            0,
+            &params.description,
+            params.child_id.as_deref(),
+            None,
+            None,
+            ctx.progress.as_ref(),
+            &ctx.cancellation,
+        )
         .await
     {
         Ok(r) => r,
@@ -189,11 +263,20 @@ pub async fn execute(ctx: &ToolContext, arguments: serde_json::Value) -> ToolsCa
         Err(ClientError::SigningFailed(msg)) => {
             return ToolsCallResult::error(format!("Signing failed: {}", msg));
         }
+        Err(ClientError::Cancelled) => return super::signing_cancelled(),
+        Err(ClientError::ApprovalPending {
+            approval_id,
+            expires_at,
+        }) => {
+            return super::approval_pending(&approval_id, expires_at);
+        }
         Err(e) => {
             return ToolsCallResult::error(format!("Signing error: {}", e));
         }
     };
 
+    ctx.confirmer.remember(&summary);
+
     let sig_len = params.scheme.signature_length();
     let signature = if sign_result.signature.starts_with("0x") {
         sign_result.signature.clone()
@@ -206,7 +289,8 @@ pub async fn execute(ctx: &ToolContext, arguments: serde_json::Value) -> ToolsCa
         "signature": signature,
         "signature_length": sig_len,
         "presig_index": sign_result.presig_index,
-        "message_hash": params.message_hash
+        "message_hash": params.message_hash,
+        "cancelled_after_commit": sign_result.cancelled_after_commit
     });
 
     let chains = params.scheme.supported_chains().join(", ");
@@ -220,7 +304,7 @@ pub async fn execute(ctx: &ToolContext, arguments: serde_json::Value) -> ToolsCa
         params.message_hash.clone()
     };
 
-    let text = format!(
+    let mut text = format!(
         "✓ FROST signature created\n\
          ├─ Scheme: {} ({} chains)\n\
          ├─ Hash: {}\n\
@@ -236,6 +320,9 @@ pub async fn execute(ctx: &ToolContext, arguments: serde_json::Value) -> ToolsCa
         remaining - 1,
         params.description
     );
+    if sign_result.cancelled_after_commit {
+        text.push_str(&super::cancelled_after_commit(sign_result.presig_index));
+    }
 
     ToolsCallResult::success_with_structured(vec![ToolContent::text(text)], result)
 }
@@ -257,14 +344,15 @@ mod tests {
             days_until_expiry: Some(30),
             is_valid: Some(true),
             public_key: Some("0x02abc123".to_string()),
+            disks: Vec::new(),
         }
     }
 
     #[tokio::test]
     async fn test_sign_frost_taproot() {
-        let ctx = ToolContext {
-            daemon_client: Arc::new(DaemonClient::new_mock(create_frost_disk("taproot"))),
-        };
+        let ctx = ToolContext::new(Arc::new(DaemonClient::new_mock(create_frost_disk(
+            "taproot",
+        ))));
 
         let args = serde_json::json!({
             "scheme": "taproot",
@@ -278,9 +366,9 @@ mod tests {
 
     #[tokio::test]
     async fn test_sign_frost_ed25519() {
-        let ctx = ToolContext {
-            daemon_client: Arc::new(DaemonClient::new_mock(create_frost_disk("ed25519"))),
-        };
+        let ctx = ToolContext::new(Arc::new(DaemonClient::new_mock(create_frost_disk(
+            "ed25519",
+        ))));
 
         let args = serde_json::json!({
             "scheme": "ed25519",
@@ -294,9 +382,9 @@ mod tests {
 
     #[tokio::test]
     async fn test_sign_frost_scheme_mismatch() {
-        let ctx = ToolContext {
-            daemon_client: Arc::new(DaemonClient::new_mock(create_frost_disk("taproot"))),
-        };
+        let ctx = ToolContext::new(Arc::new(DaemonClient::new_mock(create_frost_disk(
+            "taproot",
+        ))));
 
         let args = serde_json::json!({
             "scheme": "ed25519",  // Mismatch!
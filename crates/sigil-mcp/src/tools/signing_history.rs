@@ -0,0 +1,365 @@
+//! Signing history tool
+//!
+//! Pages through the selected disk's usage log, newest first. The
+//! `sigil://usage-log` resource template serves the same pages.
+
+use crate::protocol::{Tool, ToolAnnotations, ToolContent, ToolsCallResult};
+use serde::Deserialize;
+use sigil_cli::client::{UsageEntry, UsageQuery};
+use sigil_core::TxHashStatus;
+
+use super::ToolContext;
+
+/// Entries per page when no limit is given
+pub const DEFAULT_PAGE_SIZE: u32 = 20;
+
+/// Most entries one page may hold
+pub const MAX_PAGE_SIZE: u32 = 100;
+
+/// Signing history input parameters
+#[derive(Debug, Default, Deserialize)]
+pub struct SigningHistoryParams {
+    /// Disk to read (short or full child ID); required when several disks are inserted
+    #[serde(default)]
+    pub child_id: Option<String>,
+
+    /// Only signatures made at or after this Unix time
+    #[serde(default)]
+    pub since: Option<u64>,
+
+    /// Only signatures made before this Unix time
+    #[serde(default)]
+    pub until: Option<u64>,
+
+    /// Only signatures for this chain (0 for FROST)
+    #[serde(default)]
+    pub chain_id: Option<u32>,
+
+    /// Only signatures whose transaction hash is pending or filled
+    #[serde(default)]
+    pub tx_hash_status: Option<TxHashStatus>,
+
+    /// Only signatures whose description contains this text
+    #[serde(default)]
+    pub search: Option<String>,
+
+    /// `next_cursor` from the previous page
+    #[serde(default)]
+    pub cursor: Option<String>,
+
+    /// Entries per page
+    #[serde(default)]
+    pub limit: Option<u32>,
+}
+
+impl SigningHistoryParams {
+    /// Parse the query of a `sigil://usage-log?...` URI
+    pub fn from_uri_query(query: &str) -> Result<Self, String> {
+        let mut params = Self::default();
+        for pair in query.split('&').filter(|pair| !pair.is_empty()) {
+            let (key, value) = pair.split_once('=').unwrap_or((pair, ""));
+            let value = percent_decode(value)?;
+            match key {
+                "child_id" => params.child_id = Some(value),
+                "since" => params.since = Some(parse_number(key, &value)?),
+                "until" => params.until = Some(parse_number(key, &value)?),
+                "chain_id" => params.chain_id = Some(parse_number(key, &value)?),
+                "tx_hash" => params.tx_hash_status = Some(value.parse()?),
+                "search" => params.search = Some(value),
+                "cursor" => params.cursor = Some(value),
+                "limit" => params.limit = Some(parse_number(key, &value)?),
+                _ => return Err(format!("Unknown usage log parameter: {}", key)),
+            }
+        }
+        Ok(params)
+    }
+}
+
+fn parse_number<T: std::str::FromStr>(key: &str, value: &str) -> Result<T, String> {
+    value
+        .parse()
+        .map_err(|_| format!("Invalid {}: {}", key, value))
+}
+
+/// Decode `%XX` escapes and `+` in a URI query value
+fn percent_decode(value: &str) -> Result<String, String> {
+    let mut bytes = Vec::with_capacity(value.len());
+    let mut rest = value.bytes();
+    while let Some(byte) = rest.next() {
+        match byte {
+            b'+' => bytes.push(b' '),
+            b'%' => {
+                let hex = [rest.next().unwrap_or(0), rest.next().unwrap_or(0)];
+                let decoded = std::str::from_utf8(&hex)
+                    .ok()
+                    .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                    .ok_or_else(|| format!("Invalid escape in '{}'", value))?;
+                bytes.push(decoded);
+            }
+            byte => bytes.push(byte),
+        }
+    }
+    String::from_utf8(bytes).map_err(|_| format!("Invalid UTF-8 in '{}'", value))
+}
+
+/// Get the tool definition
+pub fn tool_definition() -> Tool {
+    Tool {
+        name: "sigil_signing_history".to_string(),
+        title: Some("Signing History".to_string()),
+        description:
+            "List signatures from the disk's usage log, newest first. Filter by time range, \
+             chain, whether the transaction hash has been recorded, or text in the \
+             description. Pass next_cursor back as cursor to get the next page."
+                .to_string(),
+        input_schema: serde_json::json!({
+            "type": "object",
+            "properties": {
+                "child_id": {
+                    "type": "string",
+                    "description": "Disk to read (short or full child ID); required when several disks are inserted"
+                },
+                "since": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "description": "Only signatures made at or after this Unix time"
+                },
+                "until": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "description": "Only signatures made before this Unix time"
+                },
+                "chain_id": {
+                    "type": "integer",
+                    "minimum": 0,
+                    "description": "Only signatures for this chain (0 for FROST signatures)"
+                },
+                "tx_hash_status": {
+                    "type": "string",
+                    "enum": ["pending", "filled"],
+                    "description": "Only signatures still waiting for a transaction hash (pending) or with one recorded (filled)"
+                },
+                "search": {
+                    "type": "string",
+                    "description": "Only signatures whose description contains this text (case-insensitive)"
+                },
+                "cursor": {
+                    "type": "string",
+                    "description": "next_cursor from the previous page"
+                },
+                "limit": {
+                    "type": "integer",
+                    "minimum": 1,
+                    "maximum": MAX_PAGE_SIZE,
+                    "default": DEFAULT_PAGE_SIZE,
+                    "description": "Entries per page"
+                }
+            },
+            "additionalProperties": false
+        }),
+        output_schema: Some(serde_json::json!({
+            "type": "object",
+            "properties": {
+                "child_id": {
+                    "type": "string",
+                    "description": "Disk the entries were read from"
+                },
+                "entries": {
+                    "type": "array",
+                    "items": {
+                        "type": "object",
+                        "properties": {
+                            "presig_index": { "type": "integer" },
+                            "timestamp": { "type": "integer", "description": "Unix time of signing" },
+                            "chain_id": { "type": "integer" },
+                            "description": { "type": "string" },
+                            "message_hash": { "type": "string" },
+                            "signature": { "type": "string" },
+                            "tx_hash": {
+                                "type": ["string", "null"],
+                                "description": "Transaction hash, or null until recorded"
+                            },
+                            "proof_hash": { "type": "string" }
+                        },
+                        "required": ["presig_index", "timestamp", "chain_id", "description", "tx_hash"]
+                    }
+                },
+                "total": {
+                    "type": "integer",
+                    "description": "Matching entries across all pages"
+                },
+                "next_cursor": {
+                    "type": ["string", "null"],
+                    "description": "Cursor for the next page, or null on the last page"
+                }
+            },
+            "required": ["child_id", "entries", "total", "next_cursor"]
+        })),
+        annotations: Some(ToolAnnotations {
+            read_only_hint: Some(true),
+            destructive_hint: Some(false),
+            idempotent_hint: Some(true),
+            open_world_hint: Some(false),
+        }),
+    }
+}
+
+/// Read one page of history as the tool's structured output
+pub async fn query(
+    ctx: &ToolContext,
+    params: SigningHistoryParams,
+) -> Result<serde_json::Value, String> {
+    let offset = match params.cursor.as_deref() {
+        None | Some("") => 0,
+        Some(cursor) => cursor
+            .parse::<u32>()
+            .map_err(|_| format!("Invalid cursor: {}", cursor))?,
+    };
+    let limit = params
+        .limit
+        .unwrap_or(DEFAULT_PAGE_SIZE)
+        .clamp(1, MAX_PAGE_SIZE);
+
+    let query = UsageQuery {
+        child_id: params.child_id,
+        since: params.since,
+        until: params.until,
+        chain_id: params.chain_id,
+        tx_hash: params.tx_hash_status,
+        search: params.search.filter(|search| !search.is_empty()),
+        offset,
+        limit,
+    };
+    let page = ctx
+        .daemon_client
+        .query_usage_log(&query)
+        .await
+        .map_err(|e| format!("Failed to read usage log: {}", e))?;
+
+    let end = offset + page.entries.len() as u32;
+    let next_cursor = (end < page.total).then(|| end.to_string());
+    Ok(serde_json::json!({
+        "child_id": page.child_id,
+        "entries": page.entries.iter().map(entry_json).collect::<Vec<_>>(),
+        "total": page.total,
+        "next_cursor": next_cursor
+    }))
+}
+
+fn entry_json(entry: &UsageEntry) -> serde_json::Value {
+    serde_json::json!({
+        "presig_index": entry.presig_index,
+        "timestamp": entry.timestamp,
+        "chain_id": entry.chain_id,
+        "description": entry.description,
+        "message_hash": format!("0x{}", entry.message_hash),
+        "signature": format!("0x{}", entry.signature),
+        "tx_hash": entry.tx_hash.as_ref().map(|tx_hash| format!("0x{}", tx_hash)),
+        "proof_hash": format!("0x{}", entry.proof_hash)
+    })
+}
+
+/// Execute the signing history tool
+pub async fn execute(ctx: &ToolContext, arguments: serde_json::Value) -> ToolsCallResult {
+    let params: SigningHistoryParams = match serde_json::from_value(arguments) {
+        Ok(p) => p,
+        Err(e) => {
+            return ToolsCallResult::error(format!("Invalid parameters: {}", e));
+        }
+    };
+
+    let result = match query(ctx, params).await {
+        Ok(result) => result,
+        Err(msg) => return ToolsCallResult::error(msg),
+    };
+
+    let entries = result["entries"].as_array().cloned().unwrap_or_default();
+    let mut text = format!(
+        "Signing history for sigil_{} ({} of {} matching)",
+        result["child_id"].as_str().unwrap_or("unknown"),
+        entries.len(),
+        result["total"]
+    );
+    for entry in &entries {
+        text.push_str(&format!(
+            "\n├─ #{} at {} [chain {}] {} (tx {})",
+            entry["presig_index"],
+            entry["timestamp"],
+            entry["chain_id"],
+            entry["description"].as_str().unwrap_or_default(),
+            entry["tx_hash"].as_str().unwrap_or("pending")
+        ));
+    }
+    if let Some(cursor) = result["next_cursor"].as_str() {
+        text.push_str(&format!("\n└─ More entries: pass cursor \"{}\"", cursor));
+    }
+
+    ToolsCallResult::success_with_structured(vec![ToolContent::text(text)], result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::DaemonClient;
+    use crate::tools::DiskState;
+    use std::sync::Arc;
+
+    #[tokio::test]
+    async fn test_signing_history_pages_and_filters() {
+        let ctx = ToolContext::new(Arc::new(DaemonClient::new_mock(DiskState::mock_detected())));
+
+        let result = execute(&ctx, serde_json::json!({ "limit": 2 })).await;
+        assert!(result.is_error.is_none() || result.is_error == Some(false));
+        let page = result.structured_content.unwrap();
+        assert_eq!(page["total"], 3);
+        assert_eq!(page["entries"].as_array().unwrap().len(), 2);
+        assert_eq!(page["entries"][0]["presig_index"], 2);
+        assert_eq!(page["entries"][0]["tx_hash"], serde_json::Value::Null);
+        assert_eq!(page["next_cursor"], "2");
+
+        let result = execute(&ctx, serde_json::json!({ "limit": 2, "cursor": "2" })).await;
+        let page = result.structured_content.unwrap();
+        assert_eq!(page["entries"][0]["presig_index"], 0);
+        assert_eq!(page["next_cursor"], serde_json::Value::Null);
+
+        let result = execute(
+            &ctx,
+            serde_json::json!({ "chain_id": 1, "tx_hash_status": "filled", "search": "ETH" }),
+        )
+        .await;
+        let page = result.structured_content.unwrap();
+        assert_eq!(page["total"], 1);
+        assert_eq!(page["entries"][0]["presig_index"], 0);
+    }
+
+    #[tokio::test]
+    async fn test_signing_history_rejects_bad_input() {
+        let ctx = ToolContext::new(Arc::new(DaemonClient::new_mock(DiskState::mock_detected())));
+
+        let result = execute(&ctx, serde_json::json!({ "tx_hash_status": "sent" })).await;
+        assert_eq!(result.is_error, Some(true));
+
+        let result = execute(&ctx, serde_json::json!({ "cursor": "next" })).await;
+        assert_eq!(result.is_error, Some(true));
+
+        let ctx = ToolContext::new(Arc::new(DaemonClient::new_mock(DiskState::default())));
+        let result = execute(&ctx, serde_json::json!({})).await;
+        assert_eq!(result.is_error, Some(true));
+    }
+
+    #[test]
+    fn test_params_from_uri_query() {
+        let params = SigningHistoryParams::from_uri_query(
+            "chain_id=137&tx_hash=pending&search=Swap%20USDC+now&cursor=20",
+        )
+        .unwrap();
+        assert_eq!(params.chain_id, Some(137));
+        assert_eq!(params.tx_hash_status, Some(TxHashStatus::Pending));
+        assert_eq!(params.search.as_deref(), Some("Swap USDC now"));
+        assert_eq!(params.cursor.as_deref(), Some("20"));
+
+        assert!(SigningHistoryParams::from_uri_query("colour=red").is_err());
+        assert!(SigningHistoryParams::from_uri_query("since=yesterday").is_err());
+        assert!(SigningHistoryParams::from_uri_query("search=%zz").is_err());
+    }
+}
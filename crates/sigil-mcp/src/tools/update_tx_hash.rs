@@ -13,6 +13,10 @@ pub struct UpdateTxHashParams {
 
     /// Actual transaction hash after broadcast
     pub tx_hash: String,
+
+    /// Disk to use (short or full child ID); required when several disks are inserted
+    #[serde(default)]
+    pub child_id: Option<String>,
 }
 
 /// Get the tool definition
@@ -37,6 +41,10 @@ pub fn tool_definition() -> Tool {
                     "type": "string",
                     "pattern": "^0x[a-fA-F0-9]{64}$",
                     "description": "The transaction hash from the blockchain after broadcast (hex with 0x prefix)"
+                },
+                "child_id": {
+                    "type": "string",
+                    "description": "Disk that produced the signature (short or full child ID); required when several disks are inserted"
                 }
             },
             "required": ["presig_index", "tx_hash"]
@@ -106,10 +114,19 @@ pub async fn execute(ctx: &ToolContext, arguments: serde_json::Value) -> ToolsCa
         );
     }
 
+    let state = match state.select(params.child_id.as_deref()) {
+        Ok(state) => state,
+        Err(msg) => return ToolsCallResult::error(msg),
+    };
+
     // Update the tx hash in the daemon
     if let Err(e) = ctx
         .daemon_client
-        .update_tx_hash(params.presig_index, &params.tx_hash)
+        .update_tx_hash(
+            params.child_id.as_deref(),
+            params.presig_index,
+            &params.tx_hash,
+        )
         .await
     {
         return ToolsCallResult::error(format!("Failed to update tx hash: {}", e));
@@ -145,9 +162,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_update_tx_hash_success() {
-        let ctx = ToolContext {
-            daemon_client: Arc::new(DaemonClient::new_mock(DiskState::mock_detected())),
-        };
+        let ctx = ToolContext::new(Arc::new(DaemonClient::new_mock(DiskState::mock_detected())));
 
         let args = serde_json::json!({
             "presig_index": 153,
@@ -165,9 +180,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_update_tx_hash_invalid() {
-        let ctx = ToolContext {
-            daemon_client: Arc::new(DaemonClient::new_mock(DiskState::mock_detected())),
-        };
+        let ctx = ToolContext::new(Arc::new(DaemonClient::new_mock(DiskState::mock_detected())));
 
         let args = serde_json::json!({
             "presig_index": 153,
@@ -180,9 +193,7 @@ mod tests {
 
     #[tokio::test]
     async fn test_update_tx_hash_no_disk() {
-        let ctx = ToolContext {
-            daemon_client: Arc::new(DaemonClient::new_mock(DiskState::default())),
-        };
+        let ctx = ToolContext::new(Arc::new(DaemonClient::new_mock(DiskState::default())));
 
         let args = serde_json::json!({
             "presig_index": 153,
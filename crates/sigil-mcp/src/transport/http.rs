@@ -0,0 +1,793 @@
+//! Streamable HTTP transport for MCP
+//!
+//! Clients POST each JSON-RPC message to a single endpoint and get the
+//! response back as JSON. Messages the server sends on its own (resource
+//! notifications) go out on an SSE stream the client opens with GET on the
+//! same endpoint. A session starts with `initialize`, is named by the
+//! `Mcp-Session-Id` header on every later request, and ends with DELETE or
+//! after sitting idle. Stream events carry IDs, so a client that reconnects
+//! with `Last-Event-ID` receives what it missed.
+//!
+//! The server binds to localhost or a Unix socket by default. Requests from
+//! browser origins that aren't allowed are refused (this blocks DNS
+//! rebinding), and when a token is configured every request must present it
+//! as a bearer token.
+
+use axum::{
+    body::Body,
+    extract::{Request, State},
+    http::{header, HeaderMap, HeaderValue, StatusCode},
+    middleware::{self, Next},
+    response::{
+        sse::{Event, KeepAlive, Sse},
+        IntoResponse, Response,
+    },
+    routing::post,
+    Json, Router,
+};
+use std::collections::{HashMap, VecDeque};
+use std::convert::Infallible;
+use std::io;
+use std::net::SocketAddr;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex as StdMutex};
+use std::time::{Duration, Instant};
+use tokio::sync::{mpsc, Mutex, RwLock};
+use tracing::{debug, info, warn};
+
+use crate::handlers::{handle_notification, handle_request, McpServerState};
+use crate::peer::{ClientPeer, PendingRequests};
+use crate::progress::InFlightRequests;
+use crate::protocol::{
+    JsonRpcError, JsonRpcNotification, JsonRpcRequest, JsonRpcResponse, RequestId,
+};
+use crate::server::{event_notifications, parse_message, spawn_event_forwarder, ClientMessage};
+
+/// Path the MCP endpoint is served on
+pub const MCP_ENDPOINT: &str = "/mcp";
+
+/// Header carrying the session ID
+pub const SESSION_ID_HEADER: &str = "mcp-session-id";
+
+/// Environment variable holding the bearer token HTTP clients must send
+pub const HTTP_TOKEN_ENV: &str = "SIGIL_MCP_TOKEN";
+
+/// Default TCP port
+pub const DEFAULT_HTTP_PORT: u16 = 3000;
+
+/// Server messages kept per session for resumption
+const EVENT_BUFFER_LEN: usize = 256;
+
+/// Sessions with no requests and no open stream for this long are dropped
+const SESSION_IDLE_TIMEOUT: Duration = Duration::from_secs(30 * 60);
+
+/// How often idle sessions are swept
+const SESSION_SWEEP_INTERVAL: Duration = Duration::from_secs(60);
+
+/// Upper bound on concurrent sessions
+const MAX_SESSIONS: usize = 64;
+
+/// Where the HTTP transport listens
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HttpListen {
+    Tcp(SocketAddr),
+    Unix(PathBuf),
+}
+
+/// Streamable HTTP transport settings
+#[derive(Debug, Clone)]
+pub struct HttpConfig {
+    /// Listening address
+    pub listen: HttpListen,
+
+    /// Bearer token every request must carry, if set
+    pub bearer_token: Option<String>,
+
+    /// Browser origins allowed to call the endpoint (e.g. `http://localhost:5173`)
+    pub allowed_origins: Vec<String>,
+}
+
+impl Default for HttpConfig {
+    fn default() -> Self {
+        Self {
+            listen: HttpListen::Tcp(SocketAddr::from(([127, 0, 0, 1], DEFAULT_HTTP_PORT))),
+            bearer_token: None,
+            allowed_origins: Vec::new(),
+        }
+    }
+}
+
+impl HttpConfig {
+    /// Refuse to expose signing beyond this host without a token
+    pub fn validate(&self) -> io::Result<()> {
+        if let HttpListen::Tcp(addr) = &self.listen {
+            if !addr.ip().is_loopback() && self.bearer_token.is_none() {
+                return Err(io::Error::new(
+                    io::ErrorKind::InvalidInput,
+                    format!(
+                        "Refusing to listen on {} without a bearer token; \
+                         set one or bind to localhost",
+                        addr
+                    ),
+                ));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Server messages waiting for, or already sent on, a session's stream
+#[derive(Default)]
+struct Outbox {
+    next_id: u64,
+    buffered: VecDeque<(u64, String)>,
+    stream: Option<mpsc::Sender<(u64, String)>>,
+}
+
+/// One client's MCP session
+struct Session {
+    state: Mutex<McpServerState>,
+    outbox: StdMutex<Outbox>,
+    last_active: StdMutex<Instant>,
+
+    /// Requests sent to the client during a tool call
+    pending: Arc<PendingRequests>,
+
+    /// Requests being handled, reachable without the state lock
+    in_flight: Arc<InFlightRequests>,
+}
+
+impl Session {
+    fn new(state: McpServerState) -> Self {
+        Self {
+            in_flight: Arc::clone(&state.in_flight),
+            state: Mutex::new(state),
+            outbox: StdMutex::new(Outbox::default()),
+            last_active: StdMutex::new(Instant::now()),
+            pending: Arc::new(PendingRequests::new()),
+        }
+    }
+
+    fn touch(&self) {
+        *self.last_active.lock().unwrap() = Instant::now();
+    }
+
+    fn is_idle(&self, now: Instant) -> bool {
+        let outbox = self.outbox.lock().unwrap();
+        let streaming = outbox.stream.as_ref().is_some_and(|tx| !tx.is_closed());
+        !streaming && now.duration_since(*self.last_active.lock().unwrap()) > SESSION_IDLE_TIMEOUT
+    }
+
+    /// Queue a server message, sending it now if a stream is open
+    fn push(&self, message: &JsonRpcNotification) {
+        let Ok(json) = serde_json::to_string(message) else {
+            return;
+        };
+        let mut outbox = self.outbox.lock().unwrap();
+        outbox.next_id += 1;
+        let id = outbox.next_id;
+        if outbox.buffered.len() == EVENT_BUFFER_LEN {
+            outbox.buffered.pop_front();
+        }
+        outbox.buffered.push_back((id, json.clone()));
+
+        // A full or closed stream still has the message buffered for resumption
+        if let Some(tx) = &outbox.stream {
+            if tx.try_send((id, json)).is_err() {
+                debug!(
+                    "Session stream not keeping up; event {} left for resumption",
+                    id
+                );
+            }
+        }
+    }
+
+    /// Open the session's stream, replacing any earlier one
+    ///
+    /// With `last_event_id`, buffered messages after it are replayed first.
+    fn open_stream(&self, last_event_id: Option<u64>) -> mpsc::Receiver<(u64, String)> {
+        let (tx, rx) = mpsc::channel(EVENT_BUFFER_LEN);
+        let mut outbox = self.outbox.lock().unwrap();
+        if let Some(last) = last_event_id {
+            for (id, json) in outbox.buffered.iter().filter(|(id, _)| *id > last) {
+                let _ = tx.try_send((*id, json.clone()));
+            }
+        }
+        outbox.stream = Some(tx);
+        rx
+    }
+}
+
+/// Sessions and settings shared by all requests
+struct Shared {
+    /// State new sessions are created from
+    template: McpServerState,
+    sessions: RwLock<HashMap<String, Arc<Session>>>,
+    bearer_token: Option<String>,
+    allowed_origins: Vec<String>,
+}
+
+impl Shared {
+    fn new(config: &HttpConfig, template: McpServerState) -> Self {
+        Self {
+            template,
+            sessions: RwLock::new(HashMap::new()),
+            bearer_token: config.bearer_token.clone(),
+            allowed_origins: config.allowed_origins.clone(),
+        }
+    }
+
+    async fn session(&self, headers: &HeaderMap) -> Result<Arc<Session>, Response> {
+        let id = headers
+            .get(SESSION_ID_HEADER)
+            .and_then(|v| v.to_str().ok())
+            .ok_or_else(|| {
+                error_response(StatusCode::BAD_REQUEST, "Missing Mcp-Session-Id header")
+            })?;
+        let session =
+            self.sessions.read().await.get(id).cloned().ok_or_else(|| {
+                error_response(StatusCode::NOT_FOUND, "Unknown or expired session")
+            })?;
+        session.touch();
+        Ok(session)
+    }
+
+    /// Send daemon event notifications to every session that wants them
+    async fn broadcast(&self, event: &sigil_cli::client::DaemonEvent) {
+        let sessions: Vec<_> = self.sessions.read().await.values().cloned().collect();
+        for session in sessions {
+            let notifications = event_notifications(&*session.state.lock().await, event);
+            for notification in &notifications {
+                session.push(notification);
+            }
+        }
+    }
+
+    async fn sweep(&self) {
+        let now = Instant::now();
+        self.sessions.write().await.retain(|id, session| {
+            let keep = !session.is_idle(now);
+            if !keep {
+                debug!("Dropping idle MCP session {}", id);
+            }
+            keep
+        });
+    }
+}
+
+/// Serve MCP over Streamable HTTP until the listener fails
+///
+/// Each session starts from a fresh copy of `template`.
+pub async fn serve(config: HttpConfig, template: McpServerState) -> io::Result<()> {
+    config.validate()?;
+    let daemon_client = Arc::clone(&template.daemon_client);
+    let shared = Arc::new(Shared::new(&config, template));
+
+    // Fan daemon events out to sessions and expire idle ones
+    let mut events = spawn_event_forwarder(daemon_client);
+    let background = Arc::clone(&shared);
+    tokio::spawn(async move {
+        let mut sweep = tokio::time::interval(SESSION_SWEEP_INTERVAL);
+        let mut events_open = true;
+        loop {
+            tokio::select! {
+                event = events.recv(), if events_open => match event {
+                    Some(event) => background.broadcast(&event).await,
+                    None => events_open = false,
+                },
+                _ = sweep.tick() => background.sweep().await,
+            }
+        }
+    });
+
+    let app = router(shared);
+    match &config.listen {
+        HttpListen::Tcp(addr) => {
+            let listener = tokio::net::TcpListener::bind(addr).await?;
+            info!(
+                "MCP HTTP transport listening on http://{}{}",
+                addr, MCP_ENDPOINT
+            );
+            axum::serve(listener, app).await
+        }
+        #[cfg(unix)]
+        HttpListen::Unix(path) => serve_unix(path, app).await,
+        #[cfg(not(unix))]
+        HttpListen::Unix(_) => Err(io::Error::new(
+            io::ErrorKind::Unsupported,
+            "Unix sockets are not supported on this platform",
+        )),
+    }
+}
+
+/// Serve on a Unix socket only the current user can connect to
+#[cfg(unix)]
+async fn serve_unix(path: &PathBuf, app: Router) -> io::Result<()> {
+    use hyper_util::rt::{TokioExecutor, TokioIo};
+    use hyper_util::server::conn::auto;
+    use hyper_util::service::TowerToHyperService;
+    use std::os::unix::fs::PermissionsExt;
+
+    // Remove a stale socket left by an earlier run
+    if path.exists() {
+        std::fs::remove_file(path)?;
+    }
+    let listener = tokio::net::UnixListener::bind(path)?;
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    info!("MCP HTTP transport listening on {:?}", path);
+
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let service = TowerToHyperService::new(app.clone());
+        tokio::spawn(async move {
+            if let Err(e) = auto::Builder::new(TokioExecutor::new())
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                debug!("MCP connection ended: {}", e);
+            }
+        });
+    }
+}
+
+fn router(shared: Arc<Shared>) -> Router {
+    Router::new()
+        .route(
+            MCP_ENDPOINT,
+            post(handle_post).get(handle_get).delete(handle_delete),
+        )
+        .layer(middleware::from_fn_with_state(Arc::clone(&shared), guard))
+        .with_state(shared)
+}
+
+/// Check the request's origin and bearer token
+async fn guard(State(shared): State<Arc<Shared>>, request: Request, next: Next) -> Response {
+    let headers = request.headers();
+
+    if let Some(origin) = headers.get(header::ORIGIN) {
+        let allowed = origin
+            .to_str()
+            .is_ok_and(|origin| shared.allowed_origins.iter().any(|o| o == origin));
+        if !allowed {
+            warn!("Refusing MCP request from origin {:?}", origin);
+            return error_response(StatusCode::FORBIDDEN, "Origin not allowed");
+        }
+    }
+
+    if let Some(token) = &shared.bearer_token {
+        let presented = headers
+            .get(header::AUTHORIZATION)
+            .and_then(|v| v.to_str().ok())
+            .and_then(|v| v.strip_prefix("Bearer "));
+        if !presented.is_some_and(|p| constant_time_eq(p.as_bytes(), token.as_bytes())) {
+            let mut response =
+                error_response(StatusCode::UNAUTHORIZED, "Missing or invalid bearer token");
+            response
+                .headers_mut()
+                .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+            return response;
+        }
+    }
+
+    next.run(request).await
+}
+
+/// Handle one JSON-RPC message from the client
+async fn handle_post(
+    State(shared): State<Arc<Shared>>,
+    headers: HeaderMap,
+    body: String,
+) -> Response {
+    let message = match parse_message(&body) {
+        Ok(message) => message,
+        Err(error) => {
+            return (
+                StatusCode::BAD_REQUEST,
+                Json(JsonRpcResponse::error(RequestId::Null, error)),
+            )
+                .into_response()
+        }
+    };
+
+    if let ClientMessage::Request(request) = &message {
+        if request.method == "initialize" {
+            return initialize(&shared, request).await;
+        }
+    }
+
+    let session = match shared.session(&headers).await {
+        Ok(session) => session,
+        Err(response) => return response,
+    };
+
+    match message {
+        ClientMessage::Request(request) => {
+            if request.method == "tools/call" && accepts_event_stream(&headers) {
+                return call_tool_streaming(session, request);
+            }
+            let response = handle_request(&mut *session.state.lock().await, &request).await;
+            Json(response).into_response()
+        }
+        ClientMessage::Notification(notification) => {
+            // The cancelled request may be holding the state lock
+            if notification.method == "notifications/cancelled"
+                && session.in_flight.cancel(&notification)
+            {
+                return StatusCode::ACCEPTED.into_response();
+            }
+            let reply = handle_notification(&mut *session.state.lock().await, &notification).await;
+            if let Some(reply) = reply {
+                session.push(&reply);
+            }
+            StatusCode::ACCEPTED.into_response()
+        }
+        ClientMessage::Response(response) => {
+            session.pending.resolve(response);
+            StatusCode::ACCEPTED.into_response()
+        }
+        ClientMessage::Invalid => (
+            StatusCode::BAD_REQUEST,
+            Json(JsonRpcResponse::error(
+                RequestId::Null,
+                JsonRpcError::invalid_request(),
+            )),
+        )
+            .into_response(),
+    }
+}
+
+/// Run a tool call, answering with an SSE stream
+///
+/// Requests the tool makes of the client (elicitation) go out on the stream
+/// ahead of the final response; the client answers them with separate POSTs.
+fn call_tool_streaming(session: Arc<Session>, request: JsonRpcRequest) -> Response {
+    let (tx, rx) = mpsc::channel(EVENT_BUFFER_LEN);
+    let peer = ClientPeer::new(Arc::clone(&session.pending), tx.clone());
+
+    tokio::spawn(async move {
+        let response = {
+            let mut state = session.state.lock().await;
+            state.peer = Some(peer);
+            let response = handle_request(&mut state, &request).await;
+            state.peer = None;
+            response
+        };
+        if let Ok(json) = serde_json::to_string(&response) {
+            if tx.send(json).await.is_err() {
+                debug!("Client went away before tool call {} finished", request.id);
+            }
+        }
+    });
+
+    let events = futures_util::stream::unfold(rx, |mut rx| async move {
+        let json = rx.recv().await?;
+        Some((Ok::<_, Infallible>(Event::default().data(json)), rx))
+    });
+    Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+/// Start a session; it is kept only if initialization succeeds
+async fn initialize(shared: &Shared, request: &JsonRpcRequest) -> Response {
+    if shared.sessions.read().await.len() >= MAX_SESSIONS {
+        shared.sweep().await;
+        if shared.sessions.read().await.len() >= MAX_SESSIONS {
+            return error_response(StatusCode::SERVICE_UNAVAILABLE, "Too many MCP sessions");
+        }
+    }
+
+    let mut state = shared.template.new_session();
+    let response = handle_request(&mut state, request).await;
+    if response.error.is_some() {
+        return Json(response).into_response();
+    }
+
+    let id = uuid::Uuid::new_v4().to_string();
+    shared
+        .sessions
+        .write()
+        .await
+        .insert(id.clone(), Arc::new(Session::new(state)));
+    info!("Started MCP session {}", id);
+
+    let mut response = Json(response).into_response();
+    if let Ok(value) = HeaderValue::from_str(&id) {
+        response.headers_mut().insert(SESSION_ID_HEADER, value);
+    }
+    response
+}
+
+/// Open the session's server-to-client event stream
+async fn handle_get(State(shared): State<Arc<Shared>>, headers: HeaderMap) -> Response {
+    if !accepts_event_stream(&headers) {
+        return error_response(
+            StatusCode::NOT_ACCEPTABLE,
+            "Accept must include text/event-stream",
+        );
+    }
+
+    let session = match shared.session(&headers).await {
+        Ok(session) => session,
+        Err(response) => return response,
+    };
+    let last_event_id = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.parse().ok());
+
+    let rx = session.open_stream(last_event_id);
+    let events = futures_util::stream::unfold(rx, |mut rx| async move {
+        let (id, json) = rx.recv().await?;
+        let event = Event::default().id(id.to_string()).data(json);
+        Some((Ok::<_, Infallible>(event), rx))
+    });
+
+    Sse::new(events)
+        .keep_alive(KeepAlive::default())
+        .into_response()
+}
+
+/// End a session
+async fn handle_delete(State(shared): State<Arc<Shared>>, headers: HeaderMap) -> Response {
+    let Some(id) = headers.get(SESSION_ID_HEADER).and_then(|v| v.to_str().ok()) else {
+        return error_response(StatusCode::BAD_REQUEST, "Missing Mcp-Session-Id header");
+    };
+    match shared.sessions.write().await.remove(id) {
+        Some(_) => {
+            info!("Ended MCP session {}", id);
+            StatusCode::NO_CONTENT.into_response()
+        }
+        None => error_response(StatusCode::NOT_FOUND, "Unknown or expired session"),
+    }
+}
+
+fn accepts_event_stream(headers: &HeaderMap) -> bool {
+    headers
+        .get(header::ACCEPT)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.contains("text/event-stream"))
+}
+
+fn error_response(status: StatusCode, message: &str) -> Response {
+    let body = serde_json::json!({ "error": message });
+    Response::builder()
+        .status(status)
+        .header(header::CONTENT_TYPE, "application/json")
+        .body(Body::from(body.to_string()))
+        .unwrap_or_else(|_| status.into_response())
+}
+
+/// Compare tokens without leaking where they differ
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    if a.len() != b.len() {
+        return false;
+    }
+    a.iter().zip(b).fold(0u8, |acc, (x, y)| acc | (x ^ y)) == 0
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::client::DaemonClient;
+    use crate::tools::DiskState;
+    use tower::ServiceExt;
+
+    fn app(config: &HttpConfig) -> Router {
+        let client = Arc::new(DaemonClient::new_mock(DiskState::mock_detected()));
+        router(Arc::new(Shared::new(
+            config,
+            McpServerState::with_client(client),
+        )))
+    }
+
+    fn post_request(session: Option<&str>, body: serde_json::Value) -> Request {
+        let mut builder = Request::builder()
+            .method("POST")
+            .uri(MCP_ENDPOINT)
+            .header(header::CONTENT_TYPE, "application/json")
+            .header(header::ACCEPT, "application/json, text/event-stream");
+        if let Some(session) = session {
+            builder = builder.header(SESSION_ID_HEADER, session);
+        }
+        builder.body(Body::from(body.to_string())).unwrap()
+    }
+
+    fn initialize_body() -> serde_json::Value {
+        serde_json::json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": crate::protocol::MCP_PROTOCOL_VERSION,
+                "capabilities": {},
+                "clientInfo": { "name": "test", "version": "1.0" }
+            }
+        })
+    }
+
+    async fn json_body(response: Response) -> serde_json::Value {
+        let bytes = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        serde_json::from_slice(&bytes).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_session_lifecycle() {
+        let app = app(&HttpConfig::default());
+
+        let response = app
+            .clone()
+            .oneshot(post_request(None, initialize_body()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let session = response.headers()[SESSION_ID_HEADER]
+            .to_str()
+            .unwrap()
+            .to_string();
+        assert!(json_body(response).await["result"]["serverInfo"].is_object());
+
+        let initialized = serde_json::json!({
+            "jsonrpc": "2.0",
+            "method": "notifications/initialized"
+        });
+        let response = app
+            .clone()
+            .oneshot(post_request(Some(&session), initialized))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::ACCEPTED);
+
+        let list = serde_json::json!({ "jsonrpc": "2.0", "id": 2, "method": "tools/list" });
+        let response = app
+            .clone()
+            .oneshot(post_request(Some(&session), list.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert!(json_body(response).await["result"]["tools"].is_array());
+
+        // Requests outside a session are refused
+        let response = app
+            .clone()
+            .oneshot(post_request(None, list.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+        let response = app
+            .clone()
+            .oneshot(post_request(Some("nope"), list.clone()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+
+        let delete = Request::builder()
+            .method("DELETE")
+            .uri(MCP_ENDPOINT)
+            .header(SESSION_ID_HEADER, &session)
+            .body(Body::empty())
+            .unwrap();
+        let response = app.clone().oneshot(delete).await.unwrap();
+        assert_eq!(response.status(), StatusCode::NO_CONTENT);
+
+        let response = app
+            .oneshot(post_request(Some(&session), list))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn test_origin_and_bearer_token() {
+        let config = HttpConfig {
+            bearer_token: Some("secret".to_string()),
+            allowed_origins: vec!["http://localhost:5173".to_string()],
+            ..HttpConfig::default()
+        };
+        let app = app(&config);
+
+        let response = app
+            .clone()
+            .oneshot(post_request(None, initialize_body()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+        assert_eq!(response.headers()[header::WWW_AUTHENTICATE], "Bearer");
+
+        let mut request = post_request(None, initialize_body());
+        request.headers_mut().insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer secret"),
+        );
+        request.headers_mut().insert(
+            header::ORIGIN,
+            HeaderValue::from_static("http://evil.example"),
+        );
+        let response = app.clone().oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        let mut request = post_request(None, initialize_body());
+        request.headers_mut().insert(
+            header::AUTHORIZATION,
+            HeaderValue::from_static("Bearer secret"),
+        );
+        request.headers_mut().insert(
+            header::ORIGIN,
+            HeaderValue::from_static("http://localhost:5173"),
+        );
+        let response = app.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn test_stream_resumes_after_last_event_id() {
+        let session = Session::new(McpServerState::new_with_mock());
+        for _ in 0..3 {
+            session.push(&JsonRpcNotification::new(
+                "notifications/resources/list_changed",
+            ));
+        }
+
+        let mut rx = session.open_stream(Some(1));
+        assert_eq!(rx.recv().await.unwrap().0, 2);
+        assert_eq!(rx.recv().await.unwrap().0, 3);
+
+        session.push(&JsonRpcNotification::new(
+            "notifications/resources/list_changed",
+        ));
+        let (id, json) = rx.recv().await.unwrap();
+        assert_eq!(id, 4);
+        assert!(json.contains("list_changed"));
+
+        // A new stream replaces the old one
+        let _replacement = session.open_stream(None);
+        assert!(rx.recv().await.is_none());
+    }
+
+    #[tokio::test]
+    async fn test_notifications_follow_session_subscriptions() {
+        let shared = Shared::new(&HttpConfig::default(), McpServerState::new_with_mock());
+        let subscribed = Arc::new(Session::new(McpServerState::new_with_mock()));
+        let other = Arc::new(Session::new(McpServerState::new_with_mock()));
+        {
+            let mut state = subscribed.state.lock().await;
+            state.initialized = true;
+            state
+                .subscriptions
+                .insert("sigil://presigs/info".to_string());
+        }
+        other.state.lock().await.initialized = true;
+        {
+            let mut sessions = shared.sessions.write().await;
+            sessions.insert("a".to_string(), Arc::clone(&subscribed));
+            sessions.insert("b".to_string(), Arc::clone(&other));
+        }
+
+        let event = sigil_cli::client::DaemonEvent::PresigConsumed {
+            child_id: "abcd".to_string(),
+            presig_index: 1,
+            presigs_remaining: 99,
+        };
+        shared.broadcast(&event).await;
+
+        let mut rx = subscribed.open_stream(Some(0));
+        let (_, json) = rx.recv().await.unwrap();
+        assert!(json.contains("sigil://presigs/info"));
+        assert!(other.outbox.lock().unwrap().buffered.is_empty());
+    }
+
+    #[test]
+    fn test_remote_listen_requires_token() {
+        let mut config = HttpConfig {
+            listen: HttpListen::Tcp("0.0.0.0:3000".parse().unwrap()),
+            ..HttpConfig::default()
+        };
+        assert!(config.validate().is_err());
+
+        config.bearer_token = Some("secret".to_string());
+        assert!(config.validate().is_ok());
+        assert!(HttpConfig::default().validate().is_ok());
+    }
+}
@@ -1,7 +1,10 @@
 //! MCP Transport implementations
 //!
-//! Supports stdio and HTTP+SSE transports as per MCP specification.
+//! Supports the stdio and Streamable HTTP transports from the MCP
+//! specification.
 
+pub mod http;
 pub mod stdio;
 
+pub use http::{HttpConfig, HttpListen, HTTP_TOKEN_ENV};
 pub use stdio::*;
@@ -159,6 +159,11 @@ pub struct StdioSender {
 }
 
 impl StdioSender {
+    /// The underlying channel, for handing to a [`crate::peer::ClientPeer`]
+    pub fn channel(&self) -> mpsc::Sender<String> {
+        self.tx.clone()
+    }
+
     /// Send a message
     pub async fn send(&self, message: String) -> Result<(), mpsc::error::SendError<String>> {
         self.tx.send(message).await
@@ -190,10 +195,30 @@ impl StdioSender {
 /// This spawns background tasks for reading from stdin and writing to stdout,
 /// returning channel-based sender and receiver for use in async code.
 pub fn create_stdio_channels(buffer_size: usize) -> (StdioSender, StdioReceiver) {
-    let (in_tx, in_rx) = mpsc::channel(buffer_size);
     let (out_tx, mut out_rx) = mpsc::channel::<String>(buffer_size);
 
-    // Spawn stdin reader task
+    // Spawn stdout writer task
+    tokio::spawn(async move {
+        let mut transport = AsyncStdioTransport::new();
+        while let Some(msg) = out_rx.recv().await {
+            if let Err(e) = transport.write_message(&msg).await {
+                error!("Error writing to stdout: {}", e);
+                break;
+            }
+        }
+        debug!("Stdout writer task ended");
+    });
+
+    (StdioSender { tx: out_tx }, spawn_stdin_reader(buffer_size))
+}
+
+/// Spawn a background task reading messages from stdin
+///
+/// Unlike [`AsyncStdioTransport::read_message`], the returned receiver is
+/// cancel-safe and can be used as a `tokio::select!` branch.
+pub fn spawn_stdin_reader(buffer_size: usize) -> StdioReceiver {
+    let (in_tx, in_rx) = mpsc::channel(buffer_size);
+
     tokio::spawn(async move {
         let mut transport = AsyncStdioTransport::new();
         loop {
@@ -216,19 +241,7 @@ pub fn create_stdio_channels(buffer_size: usize) -> (StdioSender, StdioReceiver)
         }
     });
 
-    // Spawn stdout writer task
-    tokio::spawn(async move {
-        let mut transport = AsyncStdioTransport::new();
-        while let Some(msg) = out_rx.recv().await {
-            if let Err(e) = transport.write_message(&msg).await {
-                error!("Error writing to stdout: {}", e);
-                break;
-            }
-        }
-        debug!("Stdout writer task ended");
-    });
-
-    (StdioSender { tx: out_tx }, StdioReceiver { rx: in_rx })
+    StdioReceiver { rx: in_rx }
 }
 
 #[cfg(test)]
@@ -7,7 +7,6 @@ use sigil_mcp::client::DaemonClient;
 use sigil_mcp::handlers::{handle_notification, handle_request, McpServerState};
 use sigil_mcp::protocol::*;
 use sigil_mcp::tools::DiskState;
-use std::collections::HashMap;
 use std::sync::Arc;
 
 /// Helper to create a test server state
@@ -28,10 +27,7 @@ fn create_initialized_state_no_disk() -> McpServerState {
     McpServerState {
         protocol_version: Some(MCP_PROTOCOL_VERSION.to_string()),
         initialized: true,
-        client_capabilities: None,
-        client_info: None,
-        daemon_client: Arc::new(DaemonClient::new_mock(DiskState::default())),
-        subscriptions: HashMap::new(),
+        ..McpServerState::with_client(Arc::new(DaemonClient::new_mock(DiskState::default())))
     }
 }
 
@@ -226,6 +222,52 @@ async fn test_tools_call_sign_evm_validation() {
     assert_eq!(result["isError"], true);
 }
 
+#[tokio::test]
+async fn test_tools_call_sign_evm_reports_progress() {
+    use sigil_mcp::peer::{ClientPeer, PendingRequests};
+
+    let mut state = create_initialized_state();
+    let (tx, mut rx) = tokio::sync::mpsc::channel(16);
+    state.peer = Some(ClientPeer::new(Arc::new(PendingRequests::new()), tx));
+
+    let request = JsonRpcRequest {
+        jsonrpc: "2.0".to_string(),
+        id: RequestId::Number(1),
+        method: "tools/call".to_string(),
+        params: Some(serde_json::json!({
+            "name": "sigil_sign_evm",
+            "arguments": {
+                "message_hash": format!("0x{}", "ab".repeat(32)),
+                "chain_id": 1,
+                "description": "Test"
+            },
+            "_meta": { "progressToken": 42 }
+        })),
+    };
+
+    let response = handle_request(&mut state, &request).await;
+    assert_ne!(response.result.unwrap()["isError"], true);
+
+    let mut messages = Vec::new();
+    while let Ok(json) = rx.try_recv() {
+        let notification: JsonRpcNotification = serde_json::from_str(&json).unwrap();
+        assert_eq!(notification.method, "notifications/progress");
+        let params = notification.params.unwrap();
+        assert_eq!(params["progressToken"], 42);
+        messages.push(params["message"].as_str().unwrap().to_string());
+    }
+    assert_eq!(
+        messages,
+        [
+            "Reading disk",
+            "Checking policy",
+            "Signing",
+            "Generating proof",
+            "Writing disk"
+        ]
+    );
+}
+
 #[tokio::test]
 async fn test_tools_call_unknown_tool() {
     let mut state = create_initialized_state();
@@ -353,6 +395,42 @@ async fn test_resources_templates_list() {
     assert!(has_children_template);
 }
 
+#[tokio::test]
+async fn test_resources_subscribe_and_unsubscribe() {
+    let mut state = create_initialized_state();
+
+    let request = |method: &str, uri: &str| JsonRpcRequest {
+        jsonrpc: "2.0".to_string(),
+        id: RequestId::Number(1),
+        method: method.to_string(),
+        params: Some(serde_json::json!({ "uri": uri })),
+    };
+
+    let response = handle_request(
+        &mut state,
+        &request("resources/subscribe", "sigil://children/abcd"),
+    )
+    .await;
+    assert!(response.error.is_none());
+    assert!(state.subscriptions.contains("sigil://children/abcd"));
+
+    let response = handle_request(
+        &mut state,
+        &request("resources/subscribe", "sigil://unknown"),
+    )
+    .await;
+    assert_eq!(response.error.unwrap().code, -32002);
+    assert_eq!(state.subscriptions.len(), 1);
+
+    let response = handle_request(
+        &mut state,
+        &request("resources/unsubscribe", "sigil://children/abcd"),
+    )
+    .await;
+    assert!(response.error.is_none());
+    assert!(state.subscriptions.is_empty());
+}
+
 // ============================================================================
 // Prompts Tests
 // ============================================================================
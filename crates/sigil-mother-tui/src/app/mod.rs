@@ -4,12 +4,15 @@ mod config;
 mod state;
 
 pub use config::{ConfigError, MountMethodConfig, TuiConfig};
-pub use state::{AppState, Screen};
+pub use state::{
+    AppState, AuthorizePrompt, GatedTask, JournalView, ReconciliationView, Screen, SecuritySettings,
+};
 
 use std::time::{Duration, Instant};
 
 use crossterm::event::{self, Event, KeyCode, KeyEventKind};
 use ratatui::prelude::*;
+use sigil_mother::{MAX_PIN_LENGTH, MIN_PIN_LENGTH};
 
 use crate::ui;
 
@@ -100,7 +103,11 @@ impl App {
             Screen::DiskManagement => self.handle_disk_management_key(key),
             Screen::DiskSelect => self.handle_disk_select_key(key),
             Screen::DiskFormat => self.handle_disk_format_key(key),
+            Screen::Reconciliation => self.handle_reconciliation_key(key),
+            Screen::Reports => self.handle_reports_key(key),
+            Screen::Settings => self.handle_settings_key(key),
             Screen::QrDisplay => self.handle_qr_display_key(key),
+            Screen::Authorize => self.handle_authorize_key(key),
             Screen::Help => self.handle_help_key(key),
         }
     }
@@ -116,15 +123,11 @@ impl App {
 
     fn handle_dashboard_key(&mut self, key: KeyCode) {
         match key {
-            KeyCode::Up | KeyCode::Char('k') => {
-                if self.state.menu_index > 0 {
-                    self.state.menu_index -= 1;
-                }
+            KeyCode::Up | KeyCode::Char('k') if self.state.menu_index > 0 => {
+                self.state.menu_index -= 1;
             }
-            KeyCode::Down | KeyCode::Char('j') => {
-                if self.state.menu_index < 6 {
-                    self.state.menu_index += 1;
-                }
+            KeyCode::Down | KeyCode::Char('j') if self.state.menu_index < 7 => {
+                self.state.menu_index += 1;
             }
             KeyCode::Enter => {
                 match self.state.menu_index {
@@ -135,10 +138,21 @@ impl App {
                     }
                     1 => self.state.current_screen = Screen::ChildList, // Children
                     2 => self.state.current_screen = Screen::AgentList, // Agents
-                    3 => {} // Reconciliation (not implemented)
-                    4 => {} // Reports (not implemented)
-                    5 => self.state.current_screen = Screen::Help, // Help
-                    6 => self.should_quit = true, // Quit
+                    3 => self.state.request_gated(GatedTask::Reconcile), // Reconciliation
+                    4 => {
+                        // Reports
+                        self.state.clear_messages();
+                        self.state.run_journal_verification();
+                        self.state.current_screen = Screen::Reports;
+                    }
+                    5 => {
+                        // Settings
+                        self.state.clear_messages();
+                        self.state.load_security_settings();
+                        self.state.current_screen = Screen::Settings;
+                    }
+                    6 => self.state.current_screen = Screen::Help, // Help
+                    7 => self.should_quit = true,                  // Quit
                     _ => {}
                 }
             }
@@ -159,10 +173,8 @@ impl App {
             KeyCode::Esc | KeyCode::Char('b') => {
                 self.state.current_screen = Screen::Dashboard;
             }
-            KeyCode::Up | KeyCode::Char('k') => {
-                if self.state.agent_list_index > 0 {
-                    self.state.agent_list_index -= 1;
-                }
+            KeyCode::Up | KeyCode::Char('k') if self.state.agent_list_index > 0 => {
+                self.state.agent_list_index -= 1;
             }
             KeyCode::Down | KeyCode::Char('j') => {
                 let agent_count = self.state.agent_registry.list_all().len();
@@ -170,10 +182,8 @@ impl App {
                     self.state.agent_list_index += 1;
                 }
             }
-            KeyCode::Enter => {
-                if !self.state.agent_registry.list_all().is_empty() {
-                    self.state.current_screen = Screen::AgentDetail;
-                }
+            KeyCode::Enter if !self.state.agent_registry.list_all().is_empty() => {
+                self.state.current_screen = Screen::AgentDetail;
             }
             KeyCode::Char('n') => {
                 self.state.current_screen = Screen::AgentCreate;
@@ -189,15 +199,11 @@ impl App {
             KeyCode::Esc | KeyCode::Char('b') => {
                 self.state.current_screen = Screen::AgentList;
             }
-            KeyCode::Up | KeyCode::Char('k') => {
-                if self.state.agent_action_index > 0 {
-                    self.state.agent_action_index -= 1;
-                }
+            KeyCode::Up | KeyCode::Char('k') if self.state.agent_action_index > 0 => {
+                self.state.agent_action_index -= 1;
             }
-            KeyCode::Down | KeyCode::Char('j') => {
-                if self.state.agent_action_index < 3 {
-                    self.state.agent_action_index += 1;
-                }
+            KeyCode::Down | KeyCode::Char('j') if self.state.agent_action_index < 3 => {
+                self.state.agent_action_index += 1;
             }
             KeyCode::Enter => {
                 match self.state.agent_action_index {
@@ -223,18 +229,14 @@ impl App {
                     KeyCode::Esc => {
                         self.state.current_screen = Screen::AgentList;
                     }
-                    KeyCode::Enter => {
-                        if !self.state.agent_name_input.is_empty() {
-                            self.state.agent_create_step = 1;
-                        }
+                    KeyCode::Enter if !self.state.agent_name_input.is_empty() => {
+                        self.state.agent_create_step = 1;
                     }
                     KeyCode::Backspace => {
                         self.state.agent_name_input.pop();
                     }
-                    KeyCode::Char(c) => {
-                        if self.state.agent_name_input.len() < 32 {
-                            self.state.agent_name_input.push(c);
-                        }
+                    KeyCode::Char(c) if self.state.agent_name_input.len() < 32 => {
+                        self.state.agent_name_input.push(c);
                     }
                     _ => {}
                 }
@@ -269,8 +271,8 @@ impl App {
                 self.state.nullify_confirmed = true;
             }
             KeyCode::Enter if self.state.nullify_confirmed => {
-                self.nullify_selected_agent();
-                self.state.current_screen = Screen::AgentList;
+                self.state.nullify_confirmed = false;
+                self.state.request_gated(GatedTask::NullifyAgent);
             }
             _ => {}
         }
@@ -281,10 +283,8 @@ impl App {
             KeyCode::Esc | KeyCode::Char('b') => {
                 self.state.current_screen = Screen::Dashboard;
             }
-            KeyCode::Up | KeyCode::Char('k') => {
-                if self.state.child_list_index > 0 {
-                    self.state.child_list_index -= 1;
-                }
+            KeyCode::Up | KeyCode::Char('k') if self.state.child_list_index > 0 => {
+                self.state.child_list_index -= 1;
             }
             KeyCode::Down | KeyCode::Char('j') => {
                 let child_count = self.state.child_registry.list_all().len();
@@ -305,20 +305,130 @@ impl App {
         }
     }
 
+    fn handle_reconciliation_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc | KeyCode::Char('b') => {
+                self.state.current_screen = Screen::Dashboard;
+            }
+            KeyCode::Char('a') | KeyCode::Char('r') => {
+                self.state.request_gated(GatedTask::Reconcile);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_reports_key(&mut self, key: KeyCode) {
+        match key {
+            KeyCode::Esc | KeyCode::Char('b') => {
+                self.state.current_screen = Screen::Dashboard;
+            }
+            KeyCode::Char('v') | KeyCode::Char('r') => {
+                self.state.clear_messages();
+                self.state.run_journal_verification();
+            }
+            KeyCode::Char('e') => {
+                self.state.clear_messages();
+                self.state.request_gated(GatedTask::ExportJournal);
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_settings_key(&mut self, key: KeyCode) {
+        let settings = &mut self.state.settings;
+        match settings.step {
+            0 => match key {
+                KeyCode::Esc | KeyCode::Char('b') => {
+                    self.state.current_screen = Screen::Dashboard;
+                }
+                KeyCode::Up | KeyCode::Char('k') if settings.index > 0 => {
+                    settings.index -= 1;
+                }
+                KeyCode::Down | KeyCode::Char('j') if settings.index < 1 => {
+                    settings.index += 1;
+                }
+                KeyCode::Left | KeyCode::Right | KeyCode::Char(' ') => settings.cycle(),
+                KeyCode::Enter if settings.has_changes() => {
+                    settings.step = if settings.needs_duress_pin() { 1 } else { 2 };
+                    self.state.clear_messages();
+                }
+                _ => {}
+            },
+            step => {
+                let input = if step == 1 {
+                    &mut settings.duress_pin_input
+                } else {
+                    &mut settings.pin_input
+                };
+                match key {
+                    KeyCode::Esc => settings.clear_inputs(),
+                    KeyCode::Backspace => {
+                        input.pop();
+                    }
+                    KeyCode::Char(c) if c.is_ascii_digit() && input.len() < MAX_PIN_LENGTH => {
+                        input.push(c);
+                    }
+                    KeyCode::Enter if input.len() >= MIN_PIN_LENGTH => {
+                        if step == 1 {
+                            settings.step = 2;
+                        } else {
+                            self.state.apply_security_settings();
+                        }
+                    }
+                    _ => {}
+                }
+            }
+        }
+    }
+
     fn handle_qr_display_key(&mut self, key: KeyCode) {
         match key {
             KeyCode::Esc | KeyCode::Enter => {
                 self.state.current_screen = Screen::Dashboard;
             }
-            KeyCode::Left => {
-                if self.state.qr_chunk_index > 0 {
-                    self.state.qr_chunk_index -= 1;
-                }
+            KeyCode::Left if self.state.qr_chunk_index > 0 => {
+                self.state.qr_chunk_index -= 1;
             }
-            KeyCode::Right => {
-                if self.state.qr_chunk_index < self.state.qr_total_chunks.saturating_sub(1) {
-                    self.state.qr_chunk_index += 1;
-                }
+            KeyCode::Right
+                if self.state.qr_chunk_index < self.state.qr_total_chunks.saturating_sub(1) =>
+            {
+                self.state.qr_chunk_index += 1;
+            }
+            _ => {}
+        }
+    }
+
+    fn handle_authorize_key(&mut self, key: KeyCode) {
+        let Some(prompt) = self.state.authorize_prompt.as_mut() else {
+            self.state.current_screen = Screen::Dashboard;
+            return;
+        };
+
+        match (prompt.field, key) {
+            (_, KeyCode::Esc) => {
+                self.state.cancel_authorization();
+                self.state.status_message = Some("Cancelled".to_string());
+            }
+            (0, KeyCode::Enter | KeyCode::Tab) if !prompt.operator_input.is_empty() => {
+                prompt.field = 1;
+            }
+            (0, KeyCode::Backspace) => {
+                prompt.operator_input.pop();
+            }
+            (0, KeyCode::Char(c)) if prompt.operator_input.len() < 32 => {
+                prompt.operator_input.push(c);
+            }
+            (1, KeyCode::BackTab) if prompt.session().is_some() => prompt.field = 0,
+            (1, KeyCode::Backspace) => {
+                prompt.pin_input.pop();
+            }
+            (1, KeyCode::Char(c))
+                if c.is_ascii_digit() && prompt.pin_input.len() < MAX_PIN_LENGTH =>
+            {
+                prompt.pin_input.push(c);
+            }
+            (1, KeyCode::Enter) if prompt.pin_input.len() >= MIN_PIN_LENGTH => {
+                self.state.submit_authorization();
             }
             _ => {}
         }
@@ -338,15 +448,11 @@ impl App {
             KeyCode::Esc | KeyCode::Char('b') => {
                 self.state.current_screen = Screen::Dashboard;
             }
-            KeyCode::Up | KeyCode::Char('k') => {
-                if self.state.disk_action_index > 0 {
-                    self.state.disk_action_index -= 1;
-                }
+            KeyCode::Up | KeyCode::Char('k') if self.state.disk_action_index > 0 => {
+                self.state.disk_action_index -= 1;
             }
-            KeyCode::Down | KeyCode::Char('j') => {
-                if self.state.disk_action_index < 5 {
-                    self.state.disk_action_index += 1;
-                }
+            KeyCode::Down | KeyCode::Char('j') if self.state.disk_action_index < 5 => {
+                self.state.disk_action_index += 1;
             }
             KeyCode::Char('r') => {
                 // Refresh disk status
@@ -397,11 +503,10 @@ impl App {
             KeyCode::Esc | KeyCode::Char('b') => {
                 self.state.current_screen = Screen::DiskManagement;
             }
-            KeyCode::Up | KeyCode::Char('k') => {
-                if self.state.device_select_index > 0 {
+            KeyCode::Up | KeyCode::Char('k')
+                if self.state.device_select_index > 0 => {
                     self.state.device_select_index -= 1;
                 }
-            }
             KeyCode::Down | KeyCode::Char('j') => {
                 let count = self.state.available_devices.len();
                 if count > 0 && self.state.device_select_index < count - 1 {
@@ -412,13 +517,12 @@ impl App {
                 // Refresh device list
                 self.state.refresh_available_devices();
             }
-            KeyCode::Enter => {
+            KeyCode::Enter
                 // Select the device
-                if !self.state.available_devices.is_empty() {
+                if !self.state.available_devices.is_empty() => {
                     self.state.select_device(self.state.device_select_index);
                     self.state.current_screen = Screen::DiskManagement;
                 }
-            }
             _ => {}
         }
     }
@@ -429,15 +533,15 @@ impl App {
                 self.state.format_confirmed = false;
                 self.state.current_screen = Screen::DiskManagement;
             }
-            KeyCode::Up | KeyCode::Char('k') => {
-                if !self.state.format_confirmed && self.state.format_type_index > 0 {
-                    self.state.format_type_index -= 1;
-                }
+            KeyCode::Up | KeyCode::Char('k')
+                if !self.state.format_confirmed && self.state.format_type_index > 0 =>
+            {
+                self.state.format_type_index -= 1;
             }
-            KeyCode::Down | KeyCode::Char('j') => {
-                if !self.state.format_confirmed && self.state.format_type_index < 1 {
-                    self.state.format_type_index += 1;
-                }
+            KeyCode::Down | KeyCode::Char('j')
+                if !self.state.format_confirmed && self.state.format_type_index < 1 =>
+            {
+                self.state.format_type_index += 1;
             }
             KeyCode::Char('y') if !self.state.format_confirmed => {
                 self.state.format_confirmed = true;
@@ -536,16 +640,4 @@ impl App {
         self.state.agent_create_step = 0;
         self.state.status_message = Some("Agent created successfully".to_string());
     }
-
-    /// Nullify the currently selected agent
-    fn nullify_selected_agent(&mut self) {
-        let agents = self.state.agent_registry.list_all();
-        if let Some(entry) = agents.get(self.state.agent_list_index) {
-            let agent_id = entry.agent_id;
-            if self.state.agent_registry.nullify_agent(&agent_id).is_ok() {
-                self.state.status_message = Some("Agent nullified".to_string());
-            }
-        }
-        self.state.nullify_confirmed = false;
-    }
 }
@@ -1,7 +1,14 @@
 //! Application state
 
+use sigil_core::{AuditBundle, ChildId, DiskFormat};
 use sigil_mother::{
-    AgentRegistry, BlockDevice, ChildRegistry, DiskStatus, FloppyManager, MountMethod,
+    journal,
+    reconciliation::{self, Anomaly, ReconciliationAnalysis},
+    storage::MasterShardData,
+    AgentRegistry, AuthError, BlockDevice, Ceremony, CeremonyJournal, ChildRegistry, DiskStatus,
+    DuressAction, EncryptedMotherStorage, FloppyManager, JournalEntry, JournalExport,
+    JournalVerification, MountMethod, PinManager, QuorumAction, QuorumManager, QuorumSession,
+    ReconciliationRecommendation, SessionConfig, StorageProfile,
 };
 
 use super::config::TuiConfig;
@@ -43,13 +50,232 @@ pub enum Screen {
     /// Disk format confirmation
     DiskFormat,
 
+    /// Reconciliation of the mounted disk
+    Reconciliation,
+
+    /// Ceremony journal report
+    Reports,
+
+    /// Security settings (duress PIN, wipe threshold)
+    Settings,
+
     /// QR code display
     QrDisplay,
 
+    /// Operator quorum or mother PIN entry for a gated action
+    Authorize,
+
     /// Help screen
     Help,
 }
 
+/// Result of reconciling the mounted disk
+pub struct ReconciliationView {
+    /// Child the disk belongs to
+    pub child_id: ChildId,
+
+    /// Usage log analysis of the disk
+    pub analysis: ReconciliationAnalysis,
+
+    /// Audit divergences (None when no agent audit bundle was found)
+    pub audit_anomalies: Option<Vec<Anomaly>>,
+
+    /// Number of records in the agent audit bundle
+    pub audit_records: usize,
+
+    /// Recommendation based on the most severe anomaly
+    pub recommendation: ReconciliationRecommendation,
+}
+
+/// Sensitive action that needs an operator quorum or the mother PIN
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GatedTask {
+    /// Nullify the selected agent
+    NullifyAgent,
+
+    /// Export the ceremony journal to the mounted disk
+    ExportJournal,
+
+    /// Reconcile the mounted disk
+    Reconcile,
+}
+
+impl GatedTask {
+    /// Quorum action this task requires, if it is quorum-gated
+    pub fn quorum_action(self) -> Option<QuorumAction> {
+        match self {
+            GatedTask::NullifyAgent => Some(QuorumAction::Nullify),
+            GatedTask::ExportJournal => Some(QuorumAction::ExportJournal),
+            GatedTask::Reconcile => None,
+        }
+    }
+
+    /// Whether the task records a ceremony, and so needs the master shard
+    fn is_journaled(self) -> bool {
+        match self {
+            GatedTask::NullifyAgent | GatedTask::Reconcile => true,
+            GatedTask::ExportJournal => false,
+        }
+    }
+
+    /// Short description for display
+    pub fn label(self) -> &'static str {
+        match self {
+            GatedTask::NullifyAgent => "Nullify agent",
+            GatedTask::ExportJournal => "Export ceremony journal",
+            GatedTask::Reconcile => "Reconcile disk",
+        }
+    }
+
+    /// Screen to show once the task has run or been cancelled
+    fn return_screen(self) -> Screen {
+        match self {
+            GatedTask::NullifyAgent => Screen::AgentList,
+            GatedTask::ExportJournal => Screen::Reports,
+            GatedTask::Reconcile => Screen::Reconciliation,
+        }
+    }
+}
+
+/// Credentials being collected for a gated task
+pub struct AuthorizePrompt {
+    /// Task that runs once authorized
+    pub task: GatedTask,
+
+    /// Quorum configuration and approvals so far, or `None` when the mother
+    /// PIN authorizes the task
+    quorum: Option<(QuorumManager, QuorumSession)>,
+
+    /// Field being edited (0 = operator ID, 1 = PIN)
+    pub field: u8,
+
+    /// Operator ID input buffer
+    pub operator_input: String,
+
+    /// PIN input buffer
+    pub pin_input: String,
+}
+
+impl AuthorizePrompt {
+    /// Quorum approvals collected so far, if the task needs a quorum
+    pub fn session(&self) -> Option<&QuorumSession> {
+        self.quorum.as_ref().map(|(_, session)| session)
+    }
+}
+
+/// Credentials a gated task was authorized with
+struct Authorization {
+    /// Profile the credentials unlocked
+    profile: StorageProfile,
+
+    /// Profile the ceremony is journaled in, if the task is journaled
+    journal: Option<JournalAuthority>,
+}
+
+impl Authorization {
+    /// Authorization for the primary profile without a journal entry
+    fn primary() -> Self {
+        Self {
+            profile: StorageProfile::Primary,
+            journal: None,
+        }
+    }
+}
+
+/// Unlocked profile a journaled task records its ceremony in
+struct JournalAuthority {
+    /// Storage directory of the unlocked profile
+    path: std::path::PathBuf,
+
+    /// Master shard the journal signing key is derived from
+    master: MasterShardData,
+
+    /// Operator(s) recorded in the journal
+    operator: String,
+}
+
+/// Result of verifying the ceremony journal
+pub struct JournalView {
+    /// Chain and signature verification outcome
+    pub verification: JournalVerification,
+
+    /// Most recent entries, newest first
+    pub recent: Vec<JournalEntry>,
+}
+
+/// Operator recorded for ceremonies authorized by the mother PIN alone
+const LOCAL_OPERATOR: &str = "local";
+
+/// Wipe-after-failures thresholds offered in settings
+pub const WIPE_THRESHOLD_OPTIONS: [Option<u32>; 4] = [None, Some(5), Some(10), Some(20)];
+
+/// Security settings editor state
+#[derive(Default)]
+pub struct SecuritySettings {
+    /// Selected row (0 = duress PIN, 1 = wipe threshold)
+    pub index: usize,
+
+    /// Editing step (0 = choose, 1 = new duress PIN, 2 = current PIN)
+    pub step: u8,
+
+    /// Duress action currently saved
+    pub current_duress: Option<DuressAction>,
+
+    /// Wipe threshold currently saved
+    pub current_wipe_after: Option<u32>,
+
+    /// Duress action being configured
+    pub duress: Option<DuressAction>,
+
+    /// Wipe threshold being configured
+    pub wipe_after: Option<u32>,
+
+    /// New duress PIN input buffer
+    pub duress_pin_input: String,
+
+    /// Current PIN input buffer
+    pub pin_input: String,
+}
+
+impl SecuritySettings {
+    /// Whether the pending changes need a new duress PIN
+    pub fn needs_duress_pin(&self) -> bool {
+        self.duress.is_some() && self.duress != self.current_duress
+    }
+
+    /// Whether anything differs from the saved settings
+    pub fn has_changes(&self) -> bool {
+        self.duress != self.current_duress || self.wipe_after != self.current_wipe_after
+    }
+
+    /// Cycle the selected setting to its next value
+    pub fn cycle(&mut self) {
+        match self.index {
+            0 => {
+                self.duress = match self.duress {
+                    None => Some(DuressAction::Decoy),
+                    Some(DuressAction::Decoy) => Some(DuressAction::Wipe),
+                    Some(DuressAction::Wipe) => None,
+                }
+            }
+            _ => {
+                let pos = WIPE_THRESHOLD_OPTIONS
+                    .iter()
+                    .position(|o| *o == self.wipe_after)
+                    .unwrap_or(0);
+                self.wipe_after = WIPE_THRESHOLD_OPTIONS[(pos + 1) % WIPE_THRESHOLD_OPTIONS.len()];
+            }
+        }
+    }
+
+    /// Clear PIN input buffers
+    pub fn clear_inputs(&mut self) {
+        self.duress_pin_input.clear();
+        self.pin_input.clear();
+        self.step = 0;
+    }
+}
+
 /// Application state
 pub struct AppState {
     /// Current screen
@@ -121,6 +347,18 @@ pub struct AppState {
     /// Currently selected device path (persisted)
     pub selected_device_path: Option<String>,
 
+    /// Latest reconciliation of the mounted disk
+    pub reconciliation: Option<ReconciliationView>,
+
+    /// Latest ceremony journal verification
+    pub journal: Option<JournalView>,
+
+    /// Security settings being edited
+    pub settings: SecuritySettings,
+
+    /// Credentials being collected, if a gated task is pending
+    pub authorize_prompt: Option<AuthorizePrompt>,
+
     /// TUI configuration (persisted)
     pub config: TuiConfig,
 }
@@ -181,6 +419,10 @@ impl AppState {
             available_devices,
             device_select_index: 0,
             selected_device_path,
+            reconciliation: None,
+            journal: None,
+            settings: SecuritySettings::default(),
+            authorize_prompt: None,
             config,
         }
     }
@@ -229,6 +471,413 @@ impl AppState {
             .and_then(|path| self.available_devices.iter().find(|d| &d.path == path))
     }
 
+    /// Reconcile the mounted disk against the agent audit bundle beside it
+    fn run_reconciliation(&mut self) -> Option<Ceremony> {
+        self.refresh_disk_status();
+        self.reconciliation = None;
+
+        let Some(mount_point) = self
+            .disk_status
+            .as_ref()
+            .and_then(|s| s.mount_point())
+            .map(|p| p.to_path_buf())
+        else {
+            self.error_message = Some("Mount a Sigil disk to reconcile".to_string());
+            return None;
+        };
+
+        let disk = match std::fs::read(mount_point.join("sigil.disk"))
+            .map_err(|e| e.to_string())
+            .and_then(|bytes| DiskFormat::from_bytes(&bytes).map_err(|e| e.to_string()))
+        {
+            Ok(disk) => disk,
+            Err(e) => {
+                self.error_message = Some(format!("Failed to read sigil.disk: {}", e));
+                return None;
+            }
+        };
+
+        let child_id = disk.header.child_id;
+        let analysis = reconciliation::analyze_disk(&disk);
+
+        let bundle_path = mount_point.join(AuditBundle::default_file_name(&child_id));
+        let (audit_anomalies, audit_records) = if bundle_path.exists() {
+            match std::fs::read_to_string(&bundle_path)
+                .map_err(sigil_mother::MotherError::from)
+                .and_then(|content| reconciliation::parse_audit_bundle(&content))
+            {
+                Ok(bundle) => {
+                    let pinned_key =
+                        sigil_mother::MotherStorage::new(EncryptedMotherStorage::default_path())
+                            .and_then(|storage| storage.load_audit_key());
+                    let anomalies = match pinned_key {
+                        Ok(pinned_key) => reconciliation::compare_audit_bundle(
+                            &disk,
+                            &bundle,
+                            pinned_key.as_ref(),
+                        ),
+                        Err(e) => vec![Anomaly::AuditBundleInvalid {
+                            reason: format!("Failed to load pinned audit key: {}", e),
+                        }],
+                    };
+                    (Some(anomalies), bundle.records.len())
+                }
+                Err(e) => (
+                    Some(vec![Anomaly::AuditBundleInvalid {
+                        reason: e.to_string(),
+                    }]),
+                    0,
+                ),
+            }
+        } else {
+            (None, 0)
+        };
+
+        let mut all_anomalies = analysis.anomalies.clone();
+        all_anomalies.extend(audit_anomalies.iter().flatten().cloned());
+        let recommendation = ReconciliationRecommendation::from_anomalies(&all_anomalies);
+        let ceremony = Ceremony::Reconcile {
+            child_id: child_id.to_hex(),
+            log_entries: analysis.log_entries,
+            anomalies: all_anomalies.len(),
+            recommendation: format!("{:?}", recommendation),
+        };
+
+        self.status_message = Some(format!("Reconciled disk for child {}", child_id.short()));
+        self.reconciliation = Some(ReconciliationView {
+            child_id,
+            analysis,
+            audit_anomalies,
+            audit_records,
+            recommendation,
+        });
+        Some(ceremony)
+    }
+
+    /// Verify the ceremony journal against its recorded public key
+    pub fn run_journal_verification(&mut self) {
+        self.journal = None;
+
+        let result =
+            CeremonyJournal::new(&EncryptedMotherStorage::default_path()).and_then(|journal| {
+                let key = journal.recorded_public_key()?.ok_or_else(|| {
+                    sigil_mother::MotherError::Storage("No journal key recorded".to_string())
+                })?;
+                let entries = journal.entries()?;
+                let verification = sigil_mother::journal::verify_entries(&entries, &key);
+                Ok((verification, entries))
+            });
+
+        match result {
+            Ok((verification, entries)) => {
+                self.status_message =
+                    Some(format!("Verified {} journal entries", verification.entries));
+                self.journal = Some(JournalView {
+                    verification,
+                    recent: entries.into_iter().rev().take(50).collect(),
+                });
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Journal verification failed: {}", e));
+            }
+        }
+    }
+
+    /// Export the ceremony journal to the mounted disk for an auditor
+    fn export_journal(&mut self, profile: StorageProfile) {
+        self.refresh_disk_status();
+
+        let Some(mount_point) = self
+            .disk_status
+            .as_ref()
+            .and_then(|s| s.mount_point())
+            .map(|p| p.to_path_buf())
+        else {
+            self.error_message = Some("Mount a disk to export the journal".to_string());
+            return;
+        };
+
+        let result =
+            EncryptedMotherStorage::for_profile(EncryptedMotherStorage::default_path(), profile)
+                .map_err(sigil_mother::MotherError::from)
+                .and_then(|storage| CeremonyJournal::new(storage.base_path()))
+                .and_then(|journal| {
+                    let key = journal.recorded_public_key()?.ok_or_else(|| {
+                        sigil_mother::MotherError::Storage("No journal key recorded".to_string())
+                    })?;
+                    let export = journal.export(&key)?;
+                    let path = mount_point.join(JournalExport::default_file_name());
+                    std::fs::write(&path, export.to_json()?)?;
+                    Ok(path)
+                });
+
+        match result {
+            Ok(path) => self.status_message = Some(format!("Journal exported to {:?}", path)),
+            Err(e) => self.error_message = Some(format!("Journal export failed: {}", e)),
+        }
+    }
+
+    /// Load the saved security settings into the editor
+    pub fn load_security_settings(&mut self) {
+        match PinManager::new() {
+            Ok(manager) => {
+                let settings = &mut self.settings;
+                settings.current_duress = manager.duress_action();
+                settings.current_wipe_after = manager.wipe_after_failures();
+                settings.duress = settings.current_duress;
+                settings.wipe_after = settings.current_wipe_after;
+                settings.clear_inputs();
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to load PIN settings: {}", e));
+            }
+        }
+    }
+
+    /// Save the pending security settings, authorised by the current PIN
+    pub fn apply_security_settings(&mut self) {
+        let result = PinManager::new().and_then(|mut manager| {
+            let settings = &self.settings;
+            if settings.duress != settings.current_duress {
+                match settings.duress {
+                    Some(action) => manager.set_duress_pin(
+                        &settings.pin_input,
+                        &settings.duress_pin_input,
+                        action,
+                    )?,
+                    None => manager.clear_duress_pin(&settings.pin_input)?,
+                }
+            }
+            if settings.wipe_after != settings.current_wipe_after {
+                manager.set_wipe_after_failures(&settings.pin_input, settings.wipe_after)?;
+            }
+            Ok(())
+        });
+
+        match result {
+            Ok(()) => {
+                self.status_message = Some("Security settings saved".to_string());
+                self.load_security_settings();
+            }
+            Err(e) => {
+                self.settings.clear_inputs();
+                self.error_message = Some(format!("Failed to save settings: {}", e));
+            }
+        }
+    }
+
+    /// Run a sensitive task once authorized
+    ///
+    /// Quorum-gated tasks collect operator approvals when a quorum is
+    /// configured. Journaled tasks otherwise ask for the mother PIN, so the
+    /// ceremony can be signed with the unlocked master shard.
+    pub fn request_gated(&mut self, task: GatedTask) {
+        let quorum = match task.quorum_action() {
+            Some(_) => QuorumManager::new(&EncryptedMotherStorage::default_path()).and_then(|m| {
+                let required = m.requires_approval(&PinManager::new()?)?;
+                Ok(required.then_some(m))
+            }),
+            None => Ok(None),
+        };
+
+        let quorum = match quorum {
+            Ok(Some(manager)) => match manager.begin_session(SessionConfig::default()) {
+                Ok(session) => Some((manager, session)),
+                Err(e) => {
+                    self.error_message = Some(format!("Quorum unavailable: {}", e));
+                    return;
+                }
+            },
+            Ok(None) if !task.is_journaled() => {
+                self.run_gated(task, Authorization::primary());
+                return;
+            }
+            Ok(None) => None,
+            Err(e) => {
+                self.error_message = Some(format!("Quorum check failed: {}", e));
+                return;
+            }
+        };
+
+        let field = if quorum.is_some() { 0 } else { 1 };
+        self.authorize_prompt = Some(AuthorizePrompt {
+            task,
+            quorum,
+            field,
+            operator_input: String::new(),
+            pin_input: String::new(),
+        });
+        self.current_screen = Screen::Authorize;
+    }
+
+    /// Check the entered credentials, running the task once authorized
+    pub fn submit_authorization(&mut self) {
+        let Some(prompt) = self.authorize_prompt.as_mut() else {
+            return;
+        };
+
+        let operator_id = std::mem::take(&mut prompt.operator_input);
+        let pin = std::mem::take(&mut prompt.pin_input);
+        let task = prompt.task;
+
+        let result = match prompt.quorum.as_mut() {
+            Some((manager, session)) => {
+                prompt.field = 0;
+                match manager.approve(session, &operator_id, &pin) {
+                    Ok(()) if !session.is_satisfied() => {
+                        self.error_message = None;
+                        self.status_message = Some(format!("Approved by {}", operator_id));
+                        return;
+                    }
+                    Ok(()) => Self::quorum_authorization(task, session),
+                    Err(e) => Err(e),
+                }
+            }
+            None => Self::pin_authorization(task, &pin),
+        };
+
+        match result {
+            Ok(authorization) => {
+                self.authorize_prompt = None;
+                self.error_message = None;
+                self.run_gated(task, authorization);
+            }
+            Err(
+                e @ (AuthError::IncorrectPin(_)
+                | AuthError::UnknownOperator(_)
+                | AuthError::DuplicateApproval(_)
+                | AuthError::InvalidPinFormat
+                | AuthError::LockedOut(_)),
+            ) => {
+                self.error_message = Some(e.to_string());
+            }
+            Err(e) => {
+                self.cancel_authorization();
+                self.error_message = Some(format!("Authorization failed: {}", e));
+            }
+        }
+    }
+
+    /// Abandon the pending gated task
+    pub fn cancel_authorization(&mut self) {
+        if let Some(prompt) = self.authorize_prompt.take() {
+            self.current_screen = prompt.task.return_screen();
+        }
+    }
+
+    /// Authorize a task with a satisfied quorum session
+    ///
+    /// The reconstructed storage key is the real mother PIN key, so the
+    /// ceremony is recorded in the primary profile.
+    fn quorum_authorization(
+        task: GatedTask,
+        session: &QuorumSession,
+    ) -> Result<Authorization, AuthError> {
+        let action = task
+            .quorum_action()
+            .ok_or_else(|| AuthError::StorageError("Task is not quorum-gated".to_string()))?;
+        let approval = session.authorize(action)?;
+        if !task.is_journaled() {
+            return Ok(Authorization::primary());
+        }
+
+        let storage = EncryptedMotherStorage::for_profile(
+            EncryptedMotherStorage::default_path(),
+            StorageProfile::Primary,
+        )?;
+        Ok(Authorization {
+            profile: StorageProfile::Primary,
+            journal: Some(JournalAuthority {
+                master: storage.load_master_shard(&session.open_session()?)?,
+                path: storage.base_path().clone(),
+                operator: approval.approvers.join(","),
+            }),
+        })
+    }
+
+    /// Authorize a task with the mother PIN
+    ///
+    /// The ceremony is recorded in whichever profile the PIN unlocks. The
+    /// decoy profile starts out empty, so under duress the task runs
+    /// without a journal entry instead of failing visibly.
+    fn pin_authorization(task: GatedTask, pin: &str) -> Result<Authorization, AuthError> {
+        let unlocked = PinManager::new()?.unlock(pin)?;
+        let storage = EncryptedMotherStorage::for_profile(
+            EncryptedMotherStorage::default_path(),
+            unlocked.profile,
+        )?;
+        let journal = if !task.is_journaled()
+            || (unlocked.profile == StorageProfile::Decoy && !storage.has_master_shard())
+        {
+            None
+        } else {
+            Some(JournalAuthority {
+                master: storage.load_master_shard_with_key(&unlocked.encryption_key)?,
+                path: storage.base_path().clone(),
+                operator: LOCAL_OPERATOR.to_string(),
+            })
+        };
+
+        Ok(Authorization {
+            profile: unlocked.profile,
+            journal,
+        })
+    }
+
+    /// Run an authorized task and journal the ceremony it performed
+    ///
+    /// Under duress nothing real changes: mutations of the in-memory
+    /// registries become no-ops and exports read the decoy profile.
+    fn run_gated(&mut self, task: GatedTask, authorization: Authorization) {
+        let duress = authorization.profile == StorageProfile::Decoy;
+        let ceremony = match task {
+            GatedTask::NullifyAgent if duress => {
+                self.nullify_confirmed = false;
+                if self.selected_agent().is_some() {
+                    self.status_message = Some("Agent nullified".to_string());
+                }
+                None
+            }
+            GatedTask::NullifyAgent => self.nullify_selected_agent(),
+            GatedTask::ExportJournal => {
+                self.export_journal(authorization.profile);
+                None
+            }
+            GatedTask::Reconcile => self.run_reconciliation(),
+        };
+        self.current_screen = task.return_screen();
+
+        let (Some(ceremony), Some(authority)) = (ceremony, authorization.journal) else {
+            return;
+        };
+        let result = journal::journal_signing_key(&authority.master).and_then(|key| {
+            CeremonyJournal::new(&authority.path)?.append(&key, &authority.operator, ceremony)
+        });
+        if let Err(e) = result {
+            self.error_message = Some(format!("Failed to record journal entry: {}", e));
+        }
+    }
+
+    /// Nullify the currently selected agent
+    fn nullify_selected_agent(&mut self) -> Option<Ceremony> {
+        self.nullify_confirmed = false;
+        let agents = self.agent_registry.list_all();
+        let agent_id = agents.get(self.agent_list_index)?.agent_id;
+        match self.agent_registry.nullify_agent(&agent_id) {
+            Ok(()) => {
+                self.status_message = Some("Agent nullified".to_string());
+                Some(Ceremony::AgentNullify {
+                    agent_id: agent_id.to_hex(),
+                    accumulator_version: self.agent_registry.accumulator_version(),
+                })
+            }
+            Err(e) => {
+                self.error_message = Some(format!("Failed to nullify agent: {}", e));
+                None
+            }
+        }
+    }
+
     /// Get currently selected agent (if any)
     pub fn selected_agent(&self) -> Option<&sigil_core::agent::AgentRegistryEntry> {
         let agents = self.agent_registry.list_all();
@@ -21,7 +21,11 @@ pub fn render(frame: &mut Frame, state: &mut AppState) {
         Screen::DiskManagement => screens::disk::status::render(frame, state),
         Screen::DiskSelect => screens::disk::select::render(frame, state),
         Screen::DiskFormat => screens::disk::format::render(frame, state),
+        Screen::Reconciliation => screens::reconciliation::render(frame, state),
+        Screen::Reports => screens::reports::render(frame, state),
+        Screen::Settings => screens::settings::render(frame, state),
         Screen::QrDisplay => screens::qr::display::render(frame, state),
+        Screen::Authorize => screens::authorize::render(frame, state),
         Screen::Help => screens::help::render(frame, state),
     }
 }
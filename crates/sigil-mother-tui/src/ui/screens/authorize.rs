@@ -0,0 +1,150 @@
+//! Gated action authorization screen
+//!
+//! Collects operator IDs and PINs until enough operators have approved a
+//! gated action, or the mother PIN when no quorum is required.
+
+use ratatui::prelude::*;
+use ratatui::widgets::{Block, Borders, Paragraph};
+
+use crate::app::{AppState, AuthorizePrompt};
+use crate::ui::components::header;
+
+/// Render the authorization screen
+pub fn render(frame: &mut Frame, state: &mut AppState) {
+    let area = frame.area();
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Length(7), // Progress
+            Constraint::Min(8),    // Credential entry
+            Constraint::Length(3), // Help bar
+        ])
+        .split(area);
+
+    let Some(prompt) = &state.authorize_prompt else {
+        return;
+    };
+
+    // Header
+    let title = match prompt.session() {
+        Some(_) => "Operator Quorum",
+        None => "Mother PIN",
+    };
+    header::render(frame, chunks[0], title);
+
+    render_progress(frame, chunks[1], prompt);
+    render_entry(frame, chunks[2], state, prompt);
+
+    // Help bar
+    let help_text = match (prompt.field, prompt.session()) {
+        (0, _) => " Type operator ID, then [Enter] | [Esc] Cancel ",
+        (_, Some(_)) => " Type PIN, then [Enter] | [Shift+Tab] Operator | [Esc] Cancel ",
+        (_, None) => " Type PIN, then [Enter] | [Esc] Cancel ",
+    };
+    let help =
+        Paragraph::new(help_text).style(Style::default().fg(Color::White).bg(Color::DarkGray));
+    frame.render_widget(help, chunks[3]);
+}
+
+/// Render the gated action and approvals collected so far
+fn render_progress(frame: &mut Frame, area: Rect, prompt: &AuthorizePrompt) {
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("  Action:   "),
+            Span::styled(
+                prompt.task.label(),
+                Style::default()
+                    .fg(Color::Yellow)
+                    .add_modifier(Modifier::BOLD),
+            ),
+        ]),
+    ];
+
+    match prompt.session() {
+        Some(session) => {
+            let approvers = session.approvers();
+            let approved = if approvers.is_empty() {
+                "none yet".to_string()
+            } else {
+                approvers.join(", ")
+            };
+            lines.push(Line::from(format!("  Approved: {}", approved)));
+            lines.push(Line::from(format!(
+                "  Needed:   {} more operator(s)",
+                session.approvals_needed()
+            )));
+        }
+        None => lines.push(Line::from(
+            "  Enter the mother PIN to sign the ceremony journal",
+        )),
+    }
+
+    let content = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(" Approval Required "),
+    );
+
+    frame.render_widget(content, area);
+}
+
+/// Render the operator ID and masked PIN fields
+fn render_entry(frame: &mut Frame, area: Rect, state: &AppState, prompt: &AuthorizePrompt) {
+    let field_style = |active: bool| {
+        if active {
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD)
+        } else {
+            Style::default().fg(Color::White)
+        }
+    };
+    let cursor = |active: bool| if active { "_" } else { "" };
+
+    let mut lines = vec![Line::from("")];
+    if prompt.session().is_some() {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "  Operator: {}{}",
+                prompt.operator_input,
+                cursor(prompt.field == 0)
+            ),
+            field_style(prompt.field == 0),
+        )));
+    }
+    lines.push(Line::from(Span::styled(
+        format!(
+            "  PIN:      {}{}",
+            "*".repeat(prompt.pin_input.len()),
+            cursor(prompt.field == 1)
+        ),
+        field_style(prompt.field == 1),
+    )));
+
+    if let Some(error) = &state.error_message {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("  {}", error),
+            Style::default().fg(Color::Red),
+        )));
+    } else if let Some(status) = &state.status_message {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("  {}", status),
+            Style::default().fg(Color::Green),
+        )));
+    }
+
+    let content = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(" Credentials "),
+    );
+
+    frame.render_widget(content, area);
+}
@@ -7,12 +7,13 @@ use crate::app::AppState;
 use crate::ui::components::header;
 
 /// Menu items
-const MENU_ITEMS: [&str; 7] = [
+const MENU_ITEMS: [&str; 8] = [
     "Disk         - Mount/unmount/format floppy",
     "Children     - Manage child disks",
     "Agents       - Manage signing agents",
     "Reconcile    - Reconcile returned disks",
-    "Reports      - Generate audit reports",
+    "Reports      - Verify ceremony journal",
+    "Settings     - Duress PIN and wipe protection",
     "Help         - View documentation",
     "Quit         - Exit application",
 ];
@@ -1,9 +1,13 @@
 //! Screen implementations
 
 pub mod agents;
+pub mod authorize;
 pub mod children;
 pub mod dashboard;
 pub mod disk;
 pub mod help;
 pub mod qr;
+pub mod reconciliation;
+pub mod reports;
+pub mod settings;
 pub mod splash;
@@ -1,176 +1,154 @@
-//! Reconciliation workflow screen
+//! Reconciliation screen
+//!
+//! Analyzes the mounted disk's usage log and, when the agent's audit bundle
+//! has been copied onto the disk, diffs the two.
 
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Borders, Paragraph, Wrap};
+use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph};
 
-use crate::app::App;
-use crate::ui::layout::{render_footer, render_header, ScreenLayout};
+use sigil_mother::reconciliation::{Anomaly, AnomalySeverity};
+use sigil_mother::ReconciliationRecommendation;
 
-/// Draw the reconciliation screen
-pub fn draw(frame: &mut Frame, area: Rect, app: &App) {
-    let theme = &app.theme;
-    let layout = ScreenLayout::new(area);
+use crate::app::{AppState, ReconciliationView};
+use crate::ui::components::header;
 
-    // Header
-    render_header(
-        frame,
-        layout.header,
-        "Dashboard > Reconciliation",
-        None,
-        theme,
-    );
+/// Render the reconciliation screen
+pub fn render(frame: &mut Frame, state: &mut AppState) {
+    let area = frame.area();
 
-    // Main content
-    let content_chunks = Layout::default()
+    let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(8),  // Explanation
-            Constraint::Length(10), // Current analysis
-            Constraint::Min(5),     // Actions
+            Constraint::Length(3),  // Header
+            Constraint::Length(11), // Summary panel
+            Constraint::Min(6),     // Anomaly list
+            Constraint::Length(3),  // Help bar
         ])
-        .split(layout.content);
+        .split(area);
 
-    // Explanation panel
-    render_explanation(frame, content_chunks[0], app);
+    // Header
+    header::render(frame, chunks[0], "Reconciliation");
+
+    match &state.reconciliation {
+        Some(view) => {
+            render_summary(frame, chunks[1], view);
+            render_anomalies(frame, chunks[2], view);
+        }
+        None => {
+            let message = state
+                .error_message
+                .clone()
+                .unwrap_or_else(|| "Mount a Sigil disk and press [a] to analyze".to_string());
+            let panel = Paragraph::new(format!("\n  {}", message)).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .title(" Analysis "),
+            );
+            frame.render_widget(panel, chunks[1]);
+        }
+    }
 
-    // Analysis results
-    render_analysis(frame, content_chunks[1], app);
+    // Help bar
+    let help = Paragraph::new(" [a] Analyze Disk | [Esc] Back ")
+        .style(Style::default().fg(Color::White).bg(Color::DarkGray));
+    frame.render_widget(help, chunks[3]);
+}
 
-    // Actions
-    render_actions(frame, content_chunks[2], app);
+/// Render the summary panel
+fn render_summary(frame: &mut Frame, area: Rect, view: &ReconciliationView) {
+    let analysis = &view.analysis;
 
-    // Footer
-    let hints = &[
-        ("A", "Analyze Disk"),
-        ("R", "Refill"),
-        ("H", "History"),
-        ("Esc", "Back"),
-    ];
-    render_footer(frame, layout.footer, hints, theme);
-}
+    let (recommendation, color, reason) = match &view.recommendation {
+        ReconciliationRecommendation::RefillApproved => ("REFILL APPROVED", Color::Green, None),
+        ReconciliationRecommendation::ManualReview { reason } => {
+            ("MANUAL REVIEW", Color::Yellow, Some(reason))
+        }
+        ReconciliationRecommendation::Nullify { reason } => ("NULLIFY", Color::Red, Some(reason)),
+    };
 
-/// Render explanation panel
-fn render_explanation(frame: &mut Frame, area: Rect, app: &App) {
-    let theme = &app.theme;
-
-    let block = Block::default()
-        .title(" What is Reconciliation? ")
-        .title_style(theme.title())
-        .borders(Borders::ALL)
-        .border_style(theme.border());
-
-    let inner = block.inner(area);
-    frame.render_widget(block, area);
-
-    let text = r#"
-Reconciliation verifies that a returning disk has been used correctly.
-The Mother device analyzes the usage log for anomalies before allowing
-the disk to be refilled with new presignatures.
-
-This process detects:
-  • Missing log entries for used presigs
-  • Timestamp irregularities
-  • Count mismatches between header and actual usage
-"#;
-
-    let paragraph = Paragraph::new(text)
-        .style(theme.text())
-        .wrap(Wrap { trim: false });
-    frame.render_widget(paragraph, inner);
-}
+    let audit_status = match &view.audit_anomalies {
+        None => "No agent audit bundle found on disk".to_string(),
+        Some(anomalies) if anomalies.is_empty() => {
+            format!("{} records, matches disk log", view.audit_records)
+        }
+        Some(anomalies) => format!(
+            "{} records, {} divergences",
+            view.audit_records,
+            anomalies.len()
+        ),
+    };
 
-/// Render analysis results
-fn render_analysis(frame: &mut Frame, area: Rect, app: &App) {
-    let theme = &app.theme;
-
-    let block = Block::default()
-        .title(" Analysis Results ")
-        .title_style(theme.title())
-        .borders(Borders::ALL)
-        .border_style(theme.border());
-
-    let inner = block.inner(area);
-    frame.render_widget(block, area);
-
-    if app.state.disk_detected {
-        // Show mock analysis results
-        let lines = vec![
-            Line::from(vec![
-                Span::styled("Recommendation:  ", theme.text_secondary()),
-                Span::styled("✓ REFILL APPROVED", theme.success()),
-            ]),
-            Line::from(""),
-            Line::from(vec![Span::styled("Presig Usage:", theme.text_secondary())]),
-            Line::from(vec![Span::raw(
-                "  Fresh: 847    Used: 150    Voided: 3    Total: 1000",
-            )]),
-            Line::from(""),
-            Line::from(vec![
-                Span::styled("Log Entries:     ", theme.text_secondary()),
-                Span::styled("150 (matches used count ✓)", theme.success()),
-            ]),
-            Line::from(vec![
-                Span::styled("Anomalies:       ", theme.text_secondary()),
-                Span::styled("0 detected", theme.success()),
-            ]),
-        ];
-
-        let analysis = Paragraph::new(lines);
-        frame.render_widget(analysis, inner);
-    } else {
-        let msg = Paragraph::new("Insert a disk to analyze")
-            .style(theme.text_muted())
-            .alignment(Alignment::Center);
-        frame.render_widget(msg, inner);
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("  Recommendation: "),
+            Span::styled(
+                recommendation,
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(""),
+        Line::from(format!("  Child: {}", view.child_id.short())),
+        Line::from(format!(
+            "  Presigs: {} fresh, {} used, {} voided, {} total",
+            analysis.fresh_presigs,
+            analysis.used_presigs,
+            analysis.voided_presigs,
+            analysis.total_presigs
+        )),
+        Line::from(format!("  Log entries: {}", analysis.log_entries)),
+        Line::from(format!("  Agent audit: {}", audit_status)),
+    ];
+
+    if let Some(reason) = reason {
+        lines.push(Line::from(format!("  Reason: {}", reason)));
     }
+
+    let panel = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(" Analysis "),
+    );
+
+    frame.render_widget(panel, area);
 }
 
-/// Render available actions
-fn render_actions(frame: &mut Frame, area: Rect, app: &App) {
-    let theme = &app.theme;
-
-    let block = Block::default()
-        .title(" Actions ")
-        .title_style(theme.title())
-        .borders(Borders::ALL)
-        .border_style(theme.border());
-
-    let inner = block.inner(area);
-    frame.render_widget(block, area);
-
-    let actions = if app.state.disk_detected {
-        vec![
-            Line::from(vec![
-                Span::styled("[A] ", theme.text_highlight()),
-                Span::raw("Re-analyze Disk"),
-                Span::styled(" - Run fresh analysis", theme.text_muted()),
-            ]),
-            Line::from(vec![
-                Span::styled("[R] ", theme.text_highlight()),
-                Span::raw("Approve & Refill"),
-                Span::styled(
-                    " - Add new presignatures (requires approval)",
-                    theme.text_muted(),
-                ),
-            ]),
-            Line::from(vec![
-                Span::styled("[E] ", theme.text_highlight()),
-                Span::raw("Export Report"),
-                Span::styled(" - Save analysis to USB", theme.text_muted()),
-            ]),
-            Line::from(vec![
-                Span::styled("[H] ", theme.text_highlight()),
-                Span::raw("View History"),
-                Span::styled(" - Past reconciliations for this child", theme.text_muted()),
-            ]),
-        ]
+/// Render the list of anomalies, disk checks first then audit divergences
+fn render_anomalies(frame: &mut Frame, area: Rect, view: &ReconciliationView) {
+    let anomalies: Vec<&Anomaly> = view
+        .analysis
+        .anomalies
+        .iter()
+        .chain(view.audit_anomalies.iter().flatten())
+        .collect();
+
+    let items: Vec<ListItem> = if anomalies.is_empty() {
+        vec![ListItem::new("  No anomalies detected").style(Style::default().fg(Color::Green))]
     } else {
-        vec![Line::from(vec![Span::styled(
-            "Insert a disk to see available actions",
-            theme.text_muted(),
-        )])]
+        anomalies
+            .iter()
+            .map(|anomaly| {
+                let (label, color) = match anomaly.severity() {
+                    AnomalySeverity::Info => ("INFO    ", Color::Gray),
+                    AnomalySeverity::Warning => ("WARNING ", Color::Yellow),
+                    AnomalySeverity::Critical => ("CRITICAL", Color::Red),
+                };
+                ListItem::new(Line::from(vec![
+                    Span::styled(format!("  {} ", label), Style::default().fg(color)),
+                    Span::raw(format!("{:?}", anomaly)),
+                ]))
+            })
+            .collect()
     };
 
-    let actions_widget = Paragraph::new(actions);
-    frame.render_widget(actions_widget, inner);
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!(" Anomalies ({}) ", anomalies.len())),
+    );
+
+    frame.render_widget(list, area);
 }
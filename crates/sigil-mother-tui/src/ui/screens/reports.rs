@@ -1,190 +1,146 @@
 //! Reports screen
+//!
+//! Verifies the signed ceremony journal and exports it for auditors.
 
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
-
-use crate::app::App;
-use crate::ui::layout::{render_footer, render_header, ScreenLayout};
-
-/// Report types
-const REPORT_TYPES: &[(&str, &str)] = &[
-    (
-        "Child Inventory Report",
-        "Complete list of all children with status and presig counts",
-    ),
-    (
-        "Signature Audit Trail",
-        "All signatures with timestamps, chains, and TX hashes",
-    ),
-    (
-        "Reconciliation History",
-        "All reconciliation events and their outcomes",
-    ),
-    (
-        "Security Events Log",
-        "Authentication attempts and session activity",
-    ),
-];
-
-/// Draw the reports screen
-pub fn draw(frame: &mut Frame, area: Rect, app: &App) {
-    let theme = &app.theme;
-    let layout = ScreenLayout::new(area);
+use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph};
 
-    // Header
-    render_header(frame, layout.header, "Dashboard > Reports", None, theme);
-
-    // Main content
-    let content_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
-        .split(layout.content);
-
-    // Left: Report type selection
-    render_report_types(frame, content_chunks[0], app);
-
-    // Right: Options
-    render_options(frame, content_chunks[1], app);
-
-    // Footer
-    let hints = &[
-        ("↑/↓", "Navigate"),
-        ("Enter", "Generate"),
-        ("E", "Export to USB"),
-        ("Esc", "Back"),
-    ];
-    render_footer(frame, layout.footer, hints, theme);
-}
-
-/// Render report type selection
-fn render_report_types(frame: &mut Frame, area: Rect, app: &App) {
-    let theme = &app.theme;
-
-    let block = Block::default()
-        .title(" Report Type ")
-        .title_style(theme.title())
-        .borders(Borders::ALL)
-        .border_style(theme.border_focused());
-
-    let inner = block.inner(area);
-    frame.render_widget(block, area);
-
-    let items: Vec<ListItem> = REPORT_TYPES
-        .iter()
-        .enumerate()
-        .map(|(i, (name, desc))| {
-            let marker = if i == app.state.report_type_index {
-                "● "
-            } else {
-                "○ "
-            };
-            ListItem::new(vec![
-                Line::from(vec![Span::raw(marker), Span::styled(*name, theme.text())]),
-                Line::from(Span::styled(format!("  {}", desc), theme.text_muted())),
-                Line::from(""),
-            ])
-        })
-        .collect();
-
-    let list = List::new(items).highlight_style(theme.selection());
-
-    let mut state = ListState::default().with_selected(Some(app.state.report_type_index));
-    frame.render_stateful_widget(list, inner, &mut state);
-}
+use crate::app::{AppState, JournalView};
+use crate::ui::components::header;
 
-/// Render options panel
-fn render_options(frame: &mut Frame, area: Rect, app: &App) {
-    let theme = &app.theme;
+/// Render the reports screen
+pub fn render(frame: &mut Frame, state: &mut AppState) {
+    let area = frame.area();
 
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .constraints([
-            Constraint::Length(8), // Format options
-            Constraint::Length(6), // Export destination
-            Constraint::Min(3),    // Status
+            Constraint::Length(3), // Header
+            Constraint::Length(9), // Verification panel
+            Constraint::Min(6),    // Journal entries
+            Constraint::Length(3), // Help bar
         ])
         .split(area);
 
-    // Format options
-    {
-        let block = Block::default()
-            .title(" Options ")
-            .title_style(theme.title())
-            .borders(Borders::ALL)
-            .border_style(theme.border());
-
-        let inner = block.inner(chunks[0]);
-        frame.render_widget(block, chunks[0]);
-
-        let options = vec![
-            Line::from(vec![
-                Span::styled("Format:      ", theme.text_secondary()),
-                Span::styled("● JSON", theme.text_highlight()),
-                Span::raw("   ○ CSV   ○ PDF"),
-            ]),
-            Line::from(""),
-            Line::from(vec![
-                Span::styled("Date Range:  ", theme.text_secondary()),
-                Span::raw("[2025-01-01] to [2025-01-21]"),
-            ]),
-            Line::from(""),
-            Line::from(vec![
-                Span::styled("Children:    ", theme.text_secondary()),
-                Span::styled("● All", theme.text_highlight()),
-                Span::raw("   ○ Select specific..."),
-            ]),
-        ];
-
-        let options_widget = Paragraph::new(options);
-        frame.render_widget(options_widget, inner);
+    // Header
+    header::render(frame, chunks[0], "Ceremony Journal");
+
+    match &state.journal {
+        Some(view) => {
+            render_verification(frame, chunks[1], view, state.status_message.as_deref());
+            render_entries(frame, chunks[2], view);
+        }
+        None => {
+            let message = state
+                .error_message
+                .clone()
+                .unwrap_or_else(|| "Press [v] to verify the ceremony journal".to_string());
+            let panel = Paragraph::new(format!("\n  {}", message)).block(
+                Block::default()
+                    .borders(Borders::ALL)
+                    .border_style(Style::default().fg(Color::Cyan))
+                    .title(" Verification "),
+            );
+            frame.render_widget(panel, chunks[1]);
+        }
     }
 
-    // Export destination
-    {
-        let block = Block::default()
-            .title(" Export Destination ")
-            .title_style(theme.title())
-            .borders(Borders::ALL)
-            .border_style(theme.border());
-
-        let inner = block.inner(chunks[1]);
-        frame.render_widget(block, chunks[1]);
-
-        let dest = vec![
-            Line::from(vec![Span::raw("○ Display on screen")]),
-            Line::from(vec![Span::styled(
-                "● Export to USB drive",
-                theme.text_highlight(),
-            )]),
-            Line::from(vec![
-                Span::styled("  Detected: ", theme.text_muted()),
-                Span::raw("/media/usb/BACKUP (14.2 GB free)"),
-            ]),
-        ];
-
-        let dest_widget = Paragraph::new(dest);
-        frame.render_widget(dest_widget, inner);
+    // Help bar
+    let help = Paragraph::new(" [v] Verify | [e] Export to Disk | [Esc] Back ")
+        .style(Style::default().fg(Color::White).bg(Color::DarkGray));
+    frame.render_widget(help, chunks[3]);
+}
+
+/// Render the verification summary
+fn render_verification(frame: &mut Frame, area: Rect, view: &JournalView, status: Option<&str>) {
+    let verification = &view.verification;
+
+    let (label, color) = if verification.is_valid() {
+        ("INTACT", Color::Green)
+    } else {
+        ("TAMPERED", Color::Red)
+    };
+
+    let last = verification
+        .last_timestamp
+        .and_then(|ts| chrono::DateTime::from_timestamp(ts as i64, 0))
+        .map(|t| t.format("%Y-%m-%d %H:%M:%S UTC").to_string())
+        .unwrap_or_else(|| "-".to_string());
+
+    let mut lines = vec![
+        Line::from(""),
+        Line::from(vec![
+            Span::raw("  Journal: "),
+            Span::styled(
+                label,
+                Style::default().fg(color).add_modifier(Modifier::BOLD),
+            ),
+        ]),
+        Line::from(format!("  Entries: {}", verification.entries)),
+        Line::from(format!("  Last ceremony: {}", last)),
+        Line::from(format!(
+            "  Head hash: {}",
+            hex::encode(verification.head_hash)
+        )),
+    ];
+
+    if let Some(issue) = verification.issues.first() {
+        lines.push(Line::from(Span::styled(
+            format!(
+                "  Entry #{}: {} ({} problem(s) total)",
+                issue.sequence,
+                issue.reason,
+                verification.issues.len()
+            ),
+            Style::default().fg(Color::Red),
+        )));
+    } else if let Some(status) = status {
+        lines.push(Line::from(format!("  {}", status)));
     }
 
-    // Status
-    {
-        let block = Block::default()
+    let panel = Paragraph::new(lines).block(
+        Block::default()
             .borders(Borders::ALL)
-            .border_style(theme.border());
-
-        let inner = block.inner(chunks[2]);
-        frame.render_widget(block, chunks[2]);
-
-        let status = if let Some(msg) = &app.state.status_message {
-            Paragraph::new(msg.as_str())
-                .style(theme.success())
-                .alignment(Alignment::Center)
-        } else {
-            Paragraph::new("Press Enter to generate report")
-                .style(theme.text_muted())
-                .alignment(Alignment::Center)
-        };
-
-        frame.render_widget(status, inner);
-    }
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(" Verification "),
+    );
+
+    frame.render_widget(panel, area);
+}
+
+/// Render the most recent journal entries
+fn render_entries(frame: &mut Frame, area: Rect, view: &JournalView) {
+    let items: Vec<ListItem> = if view.recent.is_empty() {
+        vec![ListItem::new("  No ceremonies recorded")]
+    } else {
+        view.recent
+            .iter()
+            .map(|entry| {
+                let time = chrono::DateTime::from_timestamp(entry.timestamp as i64, 0)
+                    .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                    .unwrap_or_default();
+                ListItem::new(Line::from(vec![
+                    Span::styled(
+                        format!("  #{:<5} ", entry.sequence),
+                        Style::default().fg(Color::DarkGray),
+                    ),
+                    Span::raw(format!("{}  ", time)),
+                    Span::styled(
+                        format!("{:<20}", entry.ceremony.name()),
+                        Style::default().fg(Color::Cyan),
+                    ),
+                    Span::raw(format!(" by {}", entry.operator)),
+                ]))
+            })
+            .collect()
+    };
+
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(format!(" Recent Ceremonies ({}) ", view.recent.len())),
+    );
+
+    frame.render_widget(list, area);
 }
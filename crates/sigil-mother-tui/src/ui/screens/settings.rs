@@ -1,241 +1,176 @@
 //! Settings screen
+//!
+//! Configures the duress PIN and wipe-after-failures protection.
 
 use ratatui::prelude::*;
-use ratatui::widgets::{Block, Borders, List, ListItem, ListState, Paragraph};
-
-use crate::app::App;
-use crate::ui::layout::{render_footer, render_header, ScreenLayout};
-
-/// Settings menu items
-const SETTINGS_ITEMS: &[(&str, &str, &str)] = &[
-    ("🔐", "Change PIN", "Update your authentication PIN"),
-    ("⏱️", "Session Timeout", "Configure auto-lock timing"),
-    (
-        "📅",
-        "Expiry Settings",
-        "Default validity period for new children",
-    ),
-    (
-        "💾",
-        "Backup & Restore",
-        "Backup mother state to external storage",
-    ),
-    (
-        "⚠️",
-        "Factory Reset",
-        "Erase all data and start fresh (DANGEROUS)",
-    ),
-];
-
-/// Draw the settings screen
-pub fn draw(frame: &mut Frame, area: Rect, app: &App) {
-    let theme = &app.theme;
-    let layout = ScreenLayout::new(area);
+use ratatui::widgets::{Block, Borders, List, ListItem, Paragraph};
 
-    // Header
-    render_header(frame, layout.header, "Dashboard > Settings", None, theme);
+use sigil_mother::DuressAction;
 
-    // Main content
-    let content_chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
-        .split(layout.content);
+use crate::app::{AppState, SecuritySettings};
+use crate::ui::components::header;
 
-    // Left: Settings menu
-    render_menu(frame, content_chunks[0], app);
+/// Render the settings screen
+pub fn render(frame: &mut Frame, state: &mut AppState) {
+    let area = frame.area();
 
-    // Right: Detail panel
-    render_detail(frame, content_chunks[1], app);
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Length(3), // Header
+            Constraint::Length(6), // Settings list
+            Constraint::Min(8),    // Detail / PIN entry
+            Constraint::Length(3), // Help bar
+        ])
+        .split(area);
 
-    // Footer
-    let hints = &[("↑/↓", "Navigate"), ("Enter", "Select"), ("Esc", "Back")];
-    render_footer(frame, layout.footer, hints, theme);
-}
+    // Header
+    header::render(frame, chunks[0], "Security Settings");
+
+    render_settings(frame, chunks[1], &state.settings);
 
-/// Render settings menu
-fn render_menu(frame: &mut Frame, area: Rect, app: &App) {
-    let theme = &app.theme;
+    match state.settings.step {
+        0 => render_detail(frame, chunks[2], state),
+        step => render_pin_entry(frame, chunks[2], &state.settings, step),
+    }
 
-    let block = Block::default()
-        .title(" Settings ")
-        .title_style(theme.title())
-        .borders(Borders::ALL)
-        .border_style(theme.border_focused());
+    // Help bar
+    let help_text = match state.settings.step {
+        0 => " [↑/↓] Select | [←/→] Change | [Enter] Save | [Esc] Back ",
+        _ => " Type PIN, then press [Enter] | [Esc] Cancel ",
+    };
+    let help =
+        Paragraph::new(help_text).style(Style::default().fg(Color::White).bg(Color::DarkGray));
+    frame.render_widget(help, chunks[3]);
+}
 
-    let inner = block.inner(area);
-    frame.render_widget(block, area);
+/// Render the list of settings with their pending values
+fn render_settings(frame: &mut Frame, area: Rect, settings: &SecuritySettings) {
+    let duress = match settings.duress {
+        None => "Off".to_string(),
+        Some(DuressAction::Decoy) => "Unlock decoy profile".to_string(),
+        Some(DuressAction::Wipe) => "Erase storage, unlock decoy".to_string(),
+    };
+    let wipe = match settings.wipe_after {
+        None => "Off".to_string(),
+        Some(k) => format!("After {} failed attempts", k),
+    };
 
-    let items: Vec<ListItem> = SETTINGS_ITEMS
+    let rows = [("Duress PIN", duress), ("Wipe on failures", wipe)];
+    let items: Vec<ListItem> = rows
         .iter()
         .enumerate()
-        .map(|(i, (icon, title, _))| {
-            let style = if i == app.state.settings_index {
-                theme.selection()
-            } else if i == 4 {
-                // Factory reset is danger colored
-                theme.danger()
-            } else {
+        .map(|(i, (name, value))| {
+            let style = if i == settings.index {
                 Style::default()
+                    .fg(Color::Black)
+                    .bg(Color::Cyan)
+                    .add_modifier(Modifier::BOLD)
+            } else {
+                Style::default().fg(Color::White)
             };
-
-            ListItem::new(Line::from(vec![
-                Span::raw(format!("  {} ", icon)),
-                Span::styled(*title, style),
-            ]))
+            ListItem::new(format!("  {:<18} < {} >", name, value)).style(style)
         })
         .collect();
 
-    let list = List::new(items)
-        .highlight_style(theme.selection())
-        .highlight_symbol("▶ ");
-
-    let mut state = ListState::default().with_selected(Some(app.state.settings_index));
-    frame.render_stateful_widget(list, inner, &mut state);
-}
-
-/// Render detail panel for selected setting
-fn render_detail(frame: &mut Frame, area: Rect, app: &App) {
-    let theme = &app.theme;
-
-    let (_, title, _) = SETTINGS_ITEMS
-        .get(app.state.settings_index)
-        .unwrap_or(&("", "Settings", ""));
-
-    let block = Block::default()
-        .title(format!(" {} ", title))
-        .title_style(theme.title())
-        .borders(Borders::ALL)
-        .border_style(theme.border());
-
-    let inner = block.inner(area);
-    frame.render_widget(block, area);
-
-    // Content based on selected item
-    let content = match app.state.settings_index {
-        0 => render_change_pin_detail(theme),
-        1 => render_session_timeout_detail(theme),
-        2 => render_expiry_detail(theme),
-        3 => render_backup_detail(theme),
-        4 => render_factory_reset_detail(theme),
-        _ => vec![],
+    let title = if settings.has_changes() {
+        " Protection (unsaved) "
+    } else {
+        " Protection "
     };
-
-    let paragraph = Paragraph::new(content);
-    frame.render_widget(paragraph, inner);
-}
-
-fn render_change_pin_detail(theme: &crate::ui::Theme) -> Vec<Line<'static>> {
-    vec![
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "Change your authentication PIN.",
-            theme.text(),
-        )]),
-        Line::from(""),
-        Line::from(vec![Span::raw("You will need to:")]),
-        Line::from(vec![Span::raw("  1. Enter your current PIN")]),
-        Line::from(vec![Span::raw("  2. Enter a new PIN (6-12 digits)")]),
-        Line::from(vec![Span::raw("  3. Confirm the new PIN")]),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "Press Enter to change PIN.",
-            theme.text_highlight(),
-        )]),
-    ]
+    let list = List::new(items).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(title),
+    );
+
+    frame.render_widget(list, area);
 }
 
-fn render_session_timeout_detail(theme: &crate::ui::Theme) -> Vec<Line<'static>> {
-    vec![
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("Current timeout: ", theme.text_secondary()),
-            Span::raw("5 minutes"),
-        ]),
-        Line::from(""),
-        Line::from(vec![Span::raw("Available options:")]),
-        Line::from(vec![Span::raw("  ○ 2 minutes (high security)")]),
-        Line::from(vec![Span::styled(
-            "  ● 5 minutes (default)",
-            theme.text_highlight(),
-        )]),
-        Line::from(vec![Span::raw("  ○ 15 minutes (convenience)")]),
-        Line::from(vec![Span::raw("  ○ 30 minutes")]),
-    ]
-}
+/// Render the explanation for the selected setting
+fn render_detail(frame: &mut Frame, area: Rect, state: &AppState) {
+    let mut lines = match state.settings.index {
+        0 => vec![
+            Line::from(""),
+            Line::from("  A second PIN for use under coercion. Entering it looks"),
+            Line::from("  exactly like a normal login, but opens an empty decoy"),
+            Line::from("  profile instead of the real master shard and children."),
+            Line::from(""),
+            Line::from(Span::styled(
+                "  'Erase storage' irrecoverably destroys the master shard.",
+                Style::default().fg(Color::Yellow),
+            )),
+        ],
+        _ => vec![
+            Line::from(""),
+            Line::from("  Irrecoverably erase the master shard, registry and"),
+            Line::from("  PIN after this many consecutive failed attempts."),
+            Line::from(""),
+            Line::from(Span::styled(
+                "  Make sure a recovery backup exists before enabling.",
+                Style::default().fg(Color::Yellow),
+            )),
+        ],
+    };
 
-fn render_expiry_detail(theme: &crate::ui::Theme) -> Vec<Line<'static>> {
-    vec![
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "Default validity for new children:",
-            theme.text(),
-        )]),
-        Line::from(""),
-        Line::from(vec![
-            Span::styled("Validity Period:    ", theme.text_secondary()),
-            Span::raw("[30] days"),
-        ]),
-        Line::from(vec![
-            Span::styled("Max Signatures:     ", theme.text_secondary()),
-            Span::raw("[500] per disk"),
-        ]),
-        Line::from(vec![
-            Span::styled("Warning Period:     ", theme.text_secondary()),
-            Span::raw("[7] days before expiry"),
-        ]),
-        Line::from(vec![
-            Span::styled("Emergency Reserve:  ", theme.text_secondary()),
-            Span::raw("[50] presignatures"),
-        ]),
-    ]
+    if let Some(error) = &state.error_message {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("  {}", error),
+            Style::default().fg(Color::Red),
+        )));
+    } else if let Some(status) = &state.status_message {
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            format!("  {}", status),
+            Style::default().fg(Color::Green),
+        )));
+    }
+
+    let detail = Paragraph::new(lines).block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(" Details "),
+    );
+
+    frame.render_widget(detail, area);
 }
 
-fn render_backup_detail(theme: &crate::ui::Theme) -> Vec<Line<'static>> {
-    vec![
-        Line::from(""),
-        Line::from(vec![Span::styled("Backup Mother State", theme.text())]),
-        Line::from(""),
-        Line::from(vec![Span::raw("Creates a backup of:")]),
-        Line::from(vec![Span::raw("  • Master key shards (encrypted)")]),
-        Line::from(vec![Span::raw("  • Child registry")]),
-        Line::from(vec![Span::raw("  • Reconciliation history")]),
-        Line::from(vec![Span::raw("  • Configuration settings")]),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "⚠ Store backup securely - it contains",
-            theme.warning(),
-        )]),
-        Line::from(vec![Span::styled(
-            "  sensitive cryptographic material.",
-            theme.warning(),
-        )]),
-    ]
-}
+/// Render a masked PIN entry field
+fn render_pin_entry(frame: &mut Frame, area: Rect, settings: &SecuritySettings, step: u8) {
+    let (title, prompt, input) = if step == 1 {
+        (
+            " New Duress PIN ",
+            "  Enter the duress PIN (must differ from your PIN).",
+            &settings.duress_pin_input,
+        )
+    } else {
+        (
+            " Confirm With PIN ",
+            "  Enter your current PIN to save these settings.",
+            &settings.pin_input,
+        )
+    };
 
-fn render_factory_reset_detail(theme: &crate::ui::Theme) -> Vec<Line<'static>> {
-    vec![
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "⚠ DANGER: Factory Reset",
-            theme.danger(),
-        )]),
+    let content = Paragraph::new(vec![
         Line::from(""),
-        Line::from(vec![Span::styled(
-            "This will PERMANENTLY DELETE:",
-            theme.danger(),
-        )]),
-        Line::from(vec![Span::raw("  • Master key shards")]),
-        Line::from(vec![Span::raw("  • All child registrations")]),
-        Line::from(vec![Span::raw("  • Reconciliation history")]),
-        Line::from(vec![Span::raw("  • All configuration")]),
+        Line::from(prompt),
         Line::from(""),
-        Line::from(vec![Span::styled(
-            "This action CANNOT be undone.",
-            theme.danger(),
-        )]),
-        Line::from(vec![Span::styled(
-            "All associated funds may be LOST.",
-            theme.danger(),
-        )]),
-    ]
+        Line::from(Span::styled(
+            format!("  > {}_", "*".repeat(input.len())),
+            Style::default()
+                .fg(Color::Cyan)
+                .add_modifier(Modifier::BOLD),
+        )),
+    ])
+    .block(
+        Block::default()
+            .borders(Borders::ALL)
+            .border_style(Style::default().fg(Color::Cyan))
+            .title(title),
+    );
+
+    frame.render_widget(content, area);
 }
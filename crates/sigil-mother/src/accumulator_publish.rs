@@ -196,20 +196,24 @@ impl AccumulatorPublisher {
     }
 
     /// Export accumulator to a file
+    ///
+    /// Returns the SHA-256 of the bytes written, for the ceremony journal.
     pub fn export_to_file(
         &self,
         accumulator: &RsaAccumulator,
         path: &std::path::Path,
-    ) -> Result<()> {
+    ) -> Result<[u8; 32]> {
+        use sha2::{Digest, Sha256};
+
         // Sign the export
         let signature = self.sign_accumulator(accumulator);
         let export = AccumulatorExport::new(accumulator.clone(), signature);
 
         // Write to file
         let bytes = export.to_bytes();
-        std::fs::write(path, bytes).map_err(MotherError::Io)?;
+        std::fs::write(path, &bytes).map_err(MotherError::Io)?;
 
-        Ok(())
+        Ok(Sha256::digest(&bytes).into())
     }
 
     /// Export accumulator to JSON string (for display or transfer)
@@ -332,6 +336,24 @@ mod tests {
         assert_eq!(recovered.accumulator.modulus, accumulator.modulus);
     }
 
+    #[test]
+    fn test_export_to_file_returns_hash() {
+        use sha2::{Digest, Sha256};
+
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("accumulator.bin");
+        let publisher = AccumulatorPublisher::new([0x01; 32]);
+        let accumulator = test_accumulator();
+
+        let hash = publisher.export_to_file(&accumulator, &path).unwrap();
+        let bytes = std::fs::read(&path).unwrap();
+        assert_eq!(hash, <[u8; 32]>::from(Sha256::digest(&bytes)));
+        assert_eq!(
+            load_from_file(&path).unwrap().accumulator.version,
+            accumulator.version
+        );
+    }
+
     #[test]
     fn test_to_stored_accumulator() {
         let accumulator = test_accumulator();
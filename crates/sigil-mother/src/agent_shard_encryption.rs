@@ -210,7 +210,7 @@ pub fn decode_from_qr(qr_data: &str) -> Result<EncryptedAgentShard> {
 }
 
 /// Generate a random passcode
-fn generate_passcode<R: Rng>(rng: &mut R) -> Passcode {
+pub(crate) fn generate_passcode<R: Rng>(rng: &mut R) -> Passcode {
     let passcode: String = (0..PASSCODE_LENGTH)
         .map(|_| {
             let idx = rng.gen_range(0..PASSCODE_CHARS.len());
@@ -221,7 +221,7 @@ fn generate_passcode<R: Rng>(rng: &mut R) -> Passcode {
 }
 
 /// Derive encryption key from passcode using Argon2id
-fn derive_key(passcode: &str, salt: &[u8; 16]) -> Result<[u8; 32]> {
+pub(crate) fn derive_key(passcode: &str, salt: &[u8; 16]) -> Result<[u8; 32]> {
     // Create Argon2id hasher with custom parameters
     let params = argon2::Params::new(
         ARGON2_MEMORY_KB,
@@ -306,7 +306,7 @@ mod base64_bytes_12 {
     }
 }
 
-mod base64_bytes_vec {
+pub(crate) mod base64_bytes_vec {
     use base64::Engine;
     use serde::{Deserialize, Deserializer, Serializer};
 
@@ -19,13 +19,16 @@ use chacha20poly1305::{
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 
-use super::{AuthError, Session};
+use super::{AuthError, Session, StorageProfile};
 use crate::registry::ChildRegistry;
-use crate::storage::MasterShardData;
+use crate::storage::{shred_file, MasterShardData};
 
 /// Size of the nonce for ChaCha20-Poly1305
 const NONCE_SIZE: usize = 12;
 
+/// Directory holding the decoy profile, relative to the base path
+const DECOY_PROFILE_DIR: &str = "profile";
+
 /// Encrypted storage header (stored alongside encrypted data)
 /// Reserved for future storage format migrations.
 #[allow(dead_code)]
@@ -53,6 +56,17 @@ impl EncryptedMotherStorage {
         Ok(Self { base_path })
     }
 
+    /// Open the storage for an unlocked profile
+    ///
+    /// The decoy profile unlocked by the duress PIN lives in its own
+    /// directory and starts out empty.
+    pub fn for_profile(base_path: PathBuf, profile: StorageProfile) -> Result<Self, AuthError> {
+        match profile {
+            StorageProfile::Primary => Self::new(base_path),
+            StorageProfile::Decoy => Self::new(base_path.join(DECOY_PROFILE_DIR)),
+        }
+    }
+
     /// Get the default storage path
     pub fn default_path() -> PathBuf {
         dirs::data_dir()
@@ -226,6 +240,28 @@ impl EncryptedMotherStorage {
         Ok(())
     }
 
+    /// Irrecoverably erase the master shard, registry and logs
+    ///
+    /// The encrypted master shard is overwritten with random bytes and
+    /// synced before removal. The decoy profile is erased as well.
+    pub fn secure_erase(&self) -> Result<(), AuthError> {
+        shred_file(&self.encrypted_shard_path())?;
+
+        let registry_path = self.registry_path();
+        if registry_path.exists() {
+            std::fs::remove_file(&registry_path)?;
+        }
+
+        for dir in ["reconciliation_logs", DECOY_PROFILE_DIR] {
+            let path = self.base_path.join(dir);
+            if path.exists() {
+                std::fs::remove_dir_all(&path)?;
+            }
+        }
+
+        Ok(())
+    }
+
     /// Get path to encrypted master shard file
     fn encrypted_shard_path(&self) -> PathBuf {
         self.base_path.join("master_shard.enc")
@@ -309,6 +345,24 @@ mod tests {
         assert!(matches!(result, Err(AuthError::DecryptionFailed)));
     }
 
+    #[test]
+    fn test_profiles_are_isolated() {
+        let temp_dir = tempdir().unwrap();
+        let base = temp_dir.path().to_path_buf();
+        let primary =
+            EncryptedMotherStorage::for_profile(base.clone(), StorageProfile::Primary).unwrap();
+        let decoy = EncryptedMotherStorage::for_profile(base, StorageProfile::Decoy).unwrap();
+
+        primary
+            .save_master_shard(&test_shard_data(), &test_key())
+            .unwrap();
+
+        // The decoy profile starts empty and never sees the real shard
+        assert!(primary.has_master_shard());
+        assert!(!decoy.has_master_shard());
+        assert_ne!(primary.base_path(), decoy.base_path());
+    }
+
     #[test]
     fn test_registry_operations() {
         let temp_dir = tempdir().unwrap();
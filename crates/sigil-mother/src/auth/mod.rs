@@ -10,15 +10,22 @@
 //! - Encryption key is derived from PIN using Argon2id
 //! - Progressive lockout protects against brute force
 //! - Session timeout limits exposure time
+//! - Optional M-of-N operator quorum splits the encryption key among operators
+//! - Optional duress PIN and wipe-after-failures defend against coercion
 
 mod encrypted_storage;
 mod lockout;
 mod pin;
+mod quorum;
 mod session;
 
 pub use encrypted_storage::EncryptedMotherStorage;
 pub use lockout::LockoutPolicy;
-pub use pin::{PinConfig, PinManager, MAX_PIN_LENGTH, MIN_PIN_LENGTH};
+pub use pin::{
+    DuressAction, PendingPinChange, PinConfig, PinManager, StorageProfile, UnlockOutcome,
+    MAX_PIN_LENGTH, MIN_PIN_LENGTH, MIN_WIPE_THRESHOLD,
+};
+pub use quorum::{QuorumAction, QuorumApproval, QuorumManager, QuorumSession, MAX_OPERATORS};
 pub use session::{Session, SessionConfig};
 
 use std::time::Instant;
@@ -69,6 +76,30 @@ pub enum AuthError {
 
     #[error("Crypto error: {0}")]
     CryptoError(String),
+
+    #[error("Operator quorum not configured")]
+    QuorumNotConfigured,
+
+    #[error("Operator quorum is enabled but its configuration is missing")]
+    QuorumConfigMissing,
+
+    #[error("Operator quorum not met ({approved} of {required} operators)")]
+    QuorumNotMet { approved: usize, required: usize },
+
+    #[error("Unknown operator: {0}")]
+    UnknownOperator(String),
+
+    #[error("Operator {0} has already approved this session")]
+    DuplicateApproval(String),
+
+    #[error("Invalid quorum configuration: {0}")]
+    InvalidQuorum(String),
+
+    #[error("Too many failed attempts - mother storage has been erased")]
+    StorageWiped,
+
+    #[error("Invalid setting: {0}")]
+    InvalidSetting(String),
 }
 
 impl From<std::io::Error> for AuthError {
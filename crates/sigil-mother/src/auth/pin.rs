@@ -2,6 +2,11 @@
 //!
 //! The PIN protects access to all mother device operations.
 //! It is hashed using Argon2id for secure storage.
+//!
+//! Two optional coercion defences are layered on top:
+//! - A duress PIN which, when entered, behaves like a normal login but
+//!   unlocks an empty decoy profile (optionally erasing the real storage first)
+//! - An irrecoverable wipe of the mother storage after K failed attempts
 
 use std::fs;
 use std::path::PathBuf;
@@ -14,12 +19,63 @@ use argon2::{
 use serde::{Deserialize, Serialize};
 use zeroize::Zeroizing;
 
-use super::{AuthError, LockoutPolicy};
+use super::{AuthError, EncryptedMotherStorage, LockoutPolicy};
+use crate::storage::MotherStorage;
 
 /// Minimum PIN length
 pub const MIN_PIN_LENGTH: usize = 6;
 /// Maximum PIN length
 pub const MAX_PIN_LENGTH: usize = 12;
+/// Smallest allowed wipe-after-failures threshold (leaves room for typos)
+pub const MIN_WIPE_THRESHOLD: u32 = 3;
+
+/// What happens when the duress PIN is entered
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DuressAction {
+    /// Unlock the decoy profile, leaving real storage untouched
+    Decoy,
+    /// Securely erase the real storage, then unlock the decoy profile
+    Wipe,
+}
+
+/// Storage profile unlocked by a PIN
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum StorageProfile {
+    /// The real mother storage
+    Primary,
+    /// The decoy profile unlocked by the duress PIN
+    Decoy,
+}
+
+/// Result of a successful unlock
+pub struct UnlockOutcome {
+    /// Encryption key for the unlocked profile
+    pub encryption_key: [u8; 32],
+    /// Which profile the key belongs to
+    ///
+    /// Callers should open `EncryptedMotherStorage::for_profile` with this
+    /// and must not reveal it to the user.
+    pub profile: StorageProfile,
+}
+
+/// PIN change that has been checked but not yet saved
+///
+/// Lets callers re-wrap data under the new key before the new PIN takes
+/// effect, and abandon the change if that fails.
+pub struct PendingPinChange {
+    /// Storage to save on commit
+    storage: PinStorage,
+    /// Encryption key the new PIN unlocks
+    encryption_key: Zeroizing<[u8; 32]>,
+}
+
+impl PendingPinChange {
+    /// Encryption key for the same profile under the new PIN
+    pub fn encryption_key(&self) -> &[u8; 32] {
+        &self.encryption_key
+    }
+}
 
 /// PIN configuration options
 #[derive(Clone, Debug)]
@@ -58,7 +114,7 @@ impl PinConfig {
 }
 
 /// PIN storage format (persisted to disk)
-#[derive(Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 struct PinStorage {
     /// Argon2id hash of the PIN
     hash: String,
@@ -69,11 +125,32 @@ struct PinStorage {
     failed_attempts: u32,
     /// Timestamp of last failed attempt (Unix epoch seconds)
     last_failed_attempt: Option<u64>,
+    /// Optional duress PIN
+    #[serde(default)]
+    duress: Option<DuressStorage>,
+    /// Erase storage after this many failed attempts
+    #[serde(default)]
+    wipe_after_failures: Option<u32>,
+    /// Operator quorum mode has been enabled for this device
+    #[serde(default)]
+    quorum_required: bool,
     /// Version for future migrations
     version: u32,
 }
 
-mod hex_salt {
+/// Duress PIN storage
+#[derive(Clone, Serialize, Deserialize)]
+struct DuressStorage {
+    /// Argon2id hash of the duress PIN
+    hash: String,
+    /// Salt for the decoy profile encryption key
+    #[serde(with = "hex_salt")]
+    encryption_salt: [u8; 32],
+    /// Action taken on entry
+    action: DuressAction,
+}
+
+pub(super) mod hex_salt {
     use serde::{Deserialize, Deserializer, Serializer};
 
     pub fn serialize<S>(bytes: &[u8; 32], serializer: S) -> Result<S::Ok, S::Error>
@@ -151,19 +228,16 @@ impl PinManager {
         Self::validate_pin(pin)?;
 
         // Hash the PIN using Argon2id
-        let salt = SaltString::generate(&mut OsRng);
-        let argon2 = Argon2::default();
-        let pin_bytes = Zeroizing::new(pin.as_bytes().to_vec());
-
-        let hash = argon2
-            .hash_password(&pin_bytes, &salt)
-            .map_err(|e| AuthError::CryptoError(format!("Failed to hash PIN: {}", e)))?
-            .to_string();
+        let hash = Self::hash_pin(pin)?;
 
         // Generate encryption salt (used for deriving encryption key)
-        let mut encryption_salt = [0u8; 32];
-        use rand::RngCore;
-        OsRng.fill_bytes(&mut encryption_salt);
+        let encryption_salt = Self::random_salt();
+
+        // Keep duress, wipe and quorum settings across PIN changes
+        let (duress, wipe_after_failures, quorum_required) = match self.storage.take() {
+            Some(old) => (old.duress, old.wipe_after_failures, old.quorum_required),
+            None => (None, None, false),
+        };
 
         // Create storage
         let storage = PinStorage {
@@ -171,6 +245,9 @@ impl PinManager {
             encryption_salt,
             failed_attempts: 0,
             last_failed_attempt: None,
+            duress,
+            wipe_after_failures,
+            quorum_required,
             version: 1,
         };
 
@@ -185,8 +262,20 @@ impl PinManager {
     /// Verify a PIN and return the encryption key on success
     ///
     /// The encryption key is derived from the PIN and can be used to
-    /// decrypt the master shard.
+    /// decrypt the master shard. When a duress PIN is configured, prefer
+    /// [`PinManager::unlock`], which also reports the unlocked profile.
     pub fn verify_pin(&mut self, pin: &str) -> Result<[u8; 32], AuthError> {
+        self.unlock(pin).map(|outcome| outcome.encryption_key)
+    }
+
+    /// Verify a PIN and unlock the matching storage profile
+    ///
+    /// The encryption key is derived from the PIN and decrypts the master
+    /// shard of the returned profile. Entering the duress PIN succeeds
+    /// exactly like the real PIN, but unlocks the decoy profile (after
+    /// erasing the real storage if the duress action is
+    /// [`DuressAction::Wipe`]).
+    pub fn unlock(&mut self, pin: &str) -> Result<UnlockOutcome, AuthError> {
         // Check if locked out
         if let Some(until) = self.lockout_until {
             if Instant::now() < until {
@@ -200,48 +289,225 @@ impl PinManager {
         // Get storage reference to check PIN
         let storage = self.storage.as_ref().ok_or(AuthError::PinNotSetUp)?;
 
-        // Parse the stored hash
-        let parsed_hash = PasswordHash::new(&storage.hash)
-            .map_err(|e| AuthError::CryptoError(format!("Invalid stored hash: {}", e)))?;
-
         // Copy encryption salt before verification (in case we need it for key derivation)
         let encryption_salt = storage.encryption_salt;
 
-        // Verify with constant-time comparison
-        let pin_bytes = Zeroizing::new(pin.as_bytes().to_vec());
-        let argon2 = Argon2::default();
-        let is_correct = argon2.verify_password(&pin_bytes, &parsed_hash).is_ok();
+        // Check both PINs every time so the duress path is not distinguishable
+        // by timing
+        let is_correct = Self::check_hash(&storage.hash, pin)?;
+        let duress = match &storage.duress {
+            Some(duress) => Some((
+                Self::check_hash(&duress.hash, pin)?,
+                duress.encryption_salt,
+                duress.action,
+            )),
+            None => None,
+        };
 
         if is_correct {
-            // Reset failed attempts on success
-            if let Some(storage) = self.storage.as_mut() {
-                storage.failed_attempts = 0;
-                storage.last_failed_attempt = None;
-            }
-            self.save_current_storage()?;
+            self.reset_failed_attempts()?;
 
             // Derive encryption key from PIN + encryption salt
             let encryption_key = Self::derive_encryption_key_static(pin, &encryption_salt)?;
-            Ok(encryption_key)
-        } else {
-            // Increment failed attempts
-            if let Some(storage) = self.storage.as_mut() {
-                storage.failed_attempts += 1;
-                storage.last_failed_attempt = Some(
-                    std::time::SystemTime::now()
-                        .duration_since(std::time::UNIX_EPOCH)
-                        .unwrap()
-                        .as_secs(),
-                );
+            return Ok(UnlockOutcome {
+                encryption_key,
+                profile: StorageProfile::Primary,
+            });
+        }
+
+        if let Some((true, decoy_salt, action)) = duress {
+            if action == DuressAction::Wipe {
+                self.wipe_mother_storage()?;
             }
-            self.save_current_storage()?;
+            self.reset_failed_attempts()?;
+
+            let encryption_key = Self::derive_encryption_key_static(pin, &decoy_salt)?;
+            return Ok(UnlockOutcome {
+                encryption_key,
+                profile: StorageProfile::Decoy,
+            });
+        }
 
-            // Check for lockout
-            self.check_lockout();
+        // Increment failed attempts
+        if let Some(storage) = self.storage.as_mut() {
+            storage.failed_attempts += 1;
+            storage.last_failed_attempt = Some(
+                std::time::SystemTime::now()
+                    .duration_since(std::time::UNIX_EPOCH)
+                    .unwrap()
+                    .as_secs(),
+            );
+        }
+        self.save_current_storage()?;
+
+        // Irrecoverable wipe once the configured threshold is reached
+        if let Some(storage) = &self.storage {
+            if let Some(threshold) = storage.wipe_after_failures {
+                if storage.failed_attempts >= threshold {
+                    self.wipe_mother_storage()?;
+                    self.factory_reset()?;
+                    return Err(AuthError::StorageWiped);
+                }
+            }
+        }
+
+        // Check for lockout
+        self.check_lockout();
+
+        let remaining = self.attempts_remaining();
+        Err(AuthError::IncorrectPin(remaining))
+    }
+
+    /// Configure a duress PIN (requires the real PIN)
+    pub fn set_duress_pin(
+        &mut self,
+        current_pin: &str,
+        duress_pin: &str,
+        action: DuressAction,
+    ) -> Result<(), AuthError> {
+        self.require_primary(current_pin)?;
+        Self::validate_pin(duress_pin)?;
+        if duress_pin == current_pin {
+            return Err(AuthError::InvalidSetting(
+                "Duress PIN must differ from the real PIN".to_string(),
+            ));
+        }
+
+        let duress = DuressStorage {
+            hash: Self::hash_pin(duress_pin)?,
+            encryption_salt: Self::random_salt(),
+            action,
+        };
+        if let Some(storage) = self.storage.as_mut() {
+            storage.duress = Some(duress);
+        }
+        self.save_current_storage()
+    }
 
-            let remaining = self.attempts_remaining();
-            Err(AuthError::IncorrectPin(remaining))
+    /// Remove the duress PIN (requires the real PIN)
+    pub fn clear_duress_pin(&mut self, current_pin: &str) -> Result<(), AuthError> {
+        self.require_primary(current_pin)?;
+        if let Some(storage) = self.storage.as_mut() {
+            storage.duress = None;
         }
+        self.save_current_storage()
+    }
+
+    /// Action of the configured duress PIN, if any
+    pub fn duress_action(&self) -> Option<DuressAction> {
+        self.storage
+            .as_ref()
+            .and_then(|s| s.duress.as_ref())
+            .map(|d| d.action)
+    }
+
+    /// Configure wipe after K failed attempts, or disable with `None`
+    /// (requires the real PIN)
+    pub fn set_wipe_after_failures(
+        &mut self,
+        current_pin: &str,
+        threshold: Option<u32>,
+    ) -> Result<(), AuthError> {
+        if let Some(k) = threshold {
+            if k < MIN_WIPE_THRESHOLD {
+                return Err(AuthError::InvalidSetting(format!(
+                    "Wipe threshold must be at least {}",
+                    MIN_WIPE_THRESHOLD
+                )));
+            }
+        }
+
+        self.require_primary(current_pin)?;
+        if let Some(storage) = self.storage.as_mut() {
+            storage.wipe_after_failures = threshold;
+        }
+        self.save_current_storage()
+    }
+
+    /// Configured wipe-after-failures threshold, if any
+    pub fn wipe_after_failures(&self) -> Option<u32> {
+        self.storage.as_ref().and_then(|s| s.wipe_after_failures)
+    }
+
+    /// Record that operator quorum mode is enabled (requires the real PIN)
+    ///
+    /// Once set, gated ceremonies refuse to run if the quorum configuration
+    /// goes missing. There is no way to clear it short of a factory reset.
+    pub fn enable_quorum(&mut self, current_pin: &str) -> Result<(), AuthError> {
+        self.require_primary(current_pin)?;
+        if let Some(storage) = self.storage.as_mut() {
+            storage.quorum_required = true;
+        }
+        self.save_current_storage()
+    }
+
+    /// Whether operator quorum mode has been enabled
+    pub fn quorum_required(&self) -> bool {
+        self.storage.as_ref().is_some_and(|s| s.quorum_required)
+    }
+
+    /// Verify that `pin` is the real PIN (not the duress PIN) and return
+    /// the primary profile's encryption key
+    ///
+    /// The duress PIN fails like a wrong PIN, so settings cannot be probed
+    /// and the real key cannot be reached under duress.
+    pub fn require_primary(&mut self, pin: &str) -> Result<[u8; 32], AuthError> {
+        let unlocked = self.unlock(pin)?;
+        match unlocked.profile {
+            StorageProfile::Primary => Ok(unlocked.encryption_key),
+            StorageProfile::Decoy => Err(AuthError::IncorrectPin(self.attempts_remaining())),
+        }
+    }
+
+    /// Securely erase the mother storage kept beside auth.json
+    ///
+    /// Covers the encrypted shard (both profiles) and the plaintext shard.
+    fn wipe_mother_storage(&self) -> Result<(), AuthError> {
+        if let Some(dir) = self.config.storage_path.parent() {
+            EncryptedMotherStorage::new(dir.to_path_buf())?.secure_erase()?;
+            MotherStorage::new(dir.to_path_buf())
+                .and_then(|storage| storage.erase_master_shard())
+                .map_err(|e| AuthError::StorageError(e.to_string()))?;
+        }
+        Ok(())
+    }
+
+    /// Reset the failed attempt counter after a successful unlock
+    fn reset_failed_attempts(&mut self) -> Result<(), AuthError> {
+        if let Some(storage) = self.storage.as_mut() {
+            storage.failed_attempts = 0;
+            storage.last_failed_attempt = None;
+        }
+        self.save_current_storage()
+    }
+
+    /// Hash a PIN with Argon2id
+    fn hash_pin(pin: &str) -> Result<String, AuthError> {
+        let salt = SaltString::generate(&mut OsRng);
+        let pin_bytes = Zeroizing::new(pin.as_bytes().to_vec());
+
+        Ok(Argon2::default()
+            .hash_password(&pin_bytes, &salt)
+            .map_err(|e| AuthError::CryptoError(format!("Failed to hash PIN: {}", e)))?
+            .to_string())
+    }
+
+    /// Check a PIN against a stored hash (constant-time comparison)
+    fn check_hash(hash: &str, pin: &str) -> Result<bool, AuthError> {
+        let parsed_hash = PasswordHash::new(hash)
+            .map_err(|e| AuthError::CryptoError(format!("Invalid stored hash: {}", e)))?;
+        let pin_bytes = Zeroizing::new(pin.as_bytes().to_vec());
+        Ok(Argon2::default()
+            .verify_password(&pin_bytes, &parsed_hash)
+            .is_ok())
+    }
+
+    /// Generate a random 32-byte salt
+    fn random_salt() -> [u8; 32] {
+        use rand::RngCore;
+        let mut salt = [0u8; 32];
+        OsRng.fill_bytes(&mut salt);
+        salt
     }
 
     /// Derive the encryption key from PIN and salt (static method)
@@ -272,7 +538,7 @@ impl PinManager {
     }
 
     /// Validate PIN format
-    fn validate_pin(pin: &str) -> Result<(), AuthError> {
+    pub(super) fn validate_pin(pin: &str) -> Result<(), AuthError> {
         if pin.len() < MIN_PIN_LENGTH || pin.len() > MAX_PIN_LENGTH {
             return Err(AuthError::InvalidPinLength(MIN_PIN_LENGTH, MAX_PIN_LENGTH));
         }
@@ -322,11 +588,14 @@ impl PinManager {
         Ok(())
     }
 
-    /// Get the number of remaining attempts before lockout
+    /// Get the number of remaining attempts before lockout (or wipe)
     pub fn attempts_remaining(&self) -> u32 {
         match &self.storage {
             Some(storage) => {
-                let max = self.config.lockout_policy.max_attempts();
+                let max = match storage.wipe_after_failures {
+                    Some(k) => k.min(self.config.lockout_policy.max_attempts()),
+                    None => self.config.lockout_policy.max_attempts(),
+                };
                 max.saturating_sub(storage.failed_attempts)
             }
             None => 3,
@@ -346,12 +615,51 @@ impl PinManager {
 
     /// Change PIN (requires current PIN verification first)
     pub fn change_pin(&mut self, current_pin: &str, new_pin: &str) -> Result<(), AuthError> {
+        let change = self.prepare_pin_change(current_pin, new_pin)?;
+        self.commit_pin_change(change)
+    }
+
+    /// Verify the current PIN and stage a change to a new one
+    ///
+    /// Nothing is saved until [`PinManager::commit_pin_change`].
+    pub fn prepare_pin_change(
+        &mut self,
+        current_pin: &str,
+        new_pin: &str,
+    ) -> Result<PendingPinChange, AuthError> {
         // Verify current PIN first
-        let _ = self.verify_pin(current_pin)?;
+        let profile = self.unlock(current_pin)?.profile;
+        Self::validate_pin(new_pin)?;
+        let hash = Self::hash_pin(new_pin)?;
+        let mut storage = self.storage.clone().ok_or(AuthError::PinNotSetUp)?;
+
+        let salt = match (profile, storage.duress.as_mut()) {
+            // Under duress, change the duress PIN so the change appears to work
+            (StorageProfile::Decoy, Some(duress)) => {
+                duress.hash = hash;
+                duress.encryption_salt
+            }
+            // New PIN, new encryption salt
+            _ => {
+                storage.hash = hash;
+                storage.encryption_salt = Self::random_salt();
+                storage.failed_attempts = 0;
+                storage.last_failed_attempt = None;
+                storage.encryption_salt
+            }
+        };
 
-        // Set new PIN (this will generate new encryption salt)
-        self.set_pin(new_pin)?;
+        Ok(PendingPinChange {
+            encryption_key: Zeroizing::new(Self::derive_encryption_key_static(new_pin, &salt)?),
+            storage,
+        })
+    }
 
+    /// Save a staged PIN change
+    pub fn commit_pin_change(&mut self, change: PendingPinChange) -> Result<(), AuthError> {
+        self.save_storage(&change.storage)?;
+        self.storage = Some(change.storage);
+        self.lockout_until = None;
         Ok(())
     }
 
@@ -419,6 +727,72 @@ mod tests {
         assert!(manager.set_pin("123456").is_ok());
     }
 
+    #[test]
+    fn test_duress_pin_unlocks_decoy() {
+        let temp_dir = tempdir().unwrap();
+        let config = PinConfig::with_path(temp_dir.path().join("auth.json"));
+        let mut manager = PinManager::with_config(config).unwrap();
+        manager.set_pin("123456").unwrap();
+
+        // Duress PIN cannot equal the real PIN, and needs the real PIN to set
+        assert!(manager
+            .set_duress_pin("123456", "123456", DuressAction::Decoy)
+            .is_err());
+        assert!(manager
+            .set_duress_pin("000000", "999999", DuressAction::Decoy)
+            .is_err());
+        manager
+            .set_duress_pin("123456", "999999", DuressAction::Decoy)
+            .unwrap();
+
+        let real = manager.unlock("123456").unwrap();
+        assert_eq!(real.profile, StorageProfile::Primary);
+        let decoy = manager.unlock("999999").unwrap();
+        assert_eq!(decoy.profile, StorageProfile::Decoy);
+        assert_ne!(real.encryption_key, decoy.encryption_key);
+
+        // The duress PIN cannot change settings
+        assert!(manager.clear_duress_pin("999999").is_err());
+        assert_eq!(manager.duress_action(), Some(DuressAction::Decoy));
+    }
+
+    #[test]
+    fn test_duress_wipe_and_failure_wipe() {
+        let temp_dir = tempdir().unwrap();
+        let storage = EncryptedMotherStorage::new(temp_dir.path().to_path_buf()).unwrap();
+        let shard = crate::storage::MasterShardData::new([5u8; 32], [2u8; 33]);
+
+        let config = PinConfig::with_path(temp_dir.path().join("auth.json"));
+        let mut manager = PinManager::with_config(config).unwrap();
+        manager.set_pin("123456").unwrap();
+        let key = manager.verify_pin("123456").unwrap();
+        storage.save_master_shard(&shard, &key).unwrap();
+
+        // Duress wipe looks like a successful login
+        manager
+            .set_duress_pin("123456", "999999", DuressAction::Wipe)
+            .unwrap();
+        assert!(manager.unlock("999999").is_ok());
+        assert!(!storage.has_master_shard());
+        assert!(manager.is_pin_set());
+
+        // Wipe after K failures also removes the PIN
+        storage.save_master_shard(&shard, &key).unwrap();
+        manager.set_wipe_after_failures("123456", Some(3)).unwrap();
+        assert!(manager.set_wipe_after_failures("123456", Some(1)).is_err());
+        assert!(matches!(
+            manager.verify_pin("111111"),
+            Err(AuthError::IncorrectPin(2))
+        ));
+        assert!(manager.verify_pin("111112").is_err());
+        assert!(matches!(
+            manager.verify_pin("111113"),
+            Err(AuthError::StorageWiped)
+        ));
+        assert!(!storage.has_master_shard());
+        assert!(!manager.is_pin_set());
+    }
+
     #[test]
     fn test_encryption_key_derivation() {
         let mut manager = test_manager();
@@ -429,4 +803,95 @@ mod tests {
         let key2 = manager.verify_pin("123456").unwrap();
         assert_eq!(key1, key2);
     }
+
+    #[test]
+    fn test_require_primary_refuses_duress() {
+        let temp_dir = tempdir().unwrap();
+        let config = PinConfig::with_path(temp_dir.path().join("auth.json"));
+        let mut manager = PinManager::with_config(config).unwrap();
+        manager.set_pin("123456").unwrap();
+        manager
+            .set_duress_pin("123456", "999999", DuressAction::Decoy)
+            .unwrap();
+
+        assert!(matches!(
+            manager.require_primary("999999"),
+            Err(AuthError::IncorrectPin(_))
+        ));
+        assert_eq!(
+            manager.require_primary("123456").unwrap(),
+            manager.verify_pin("123456").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_failure_wipe_removes_every_shard() {
+        let temp_dir = tempdir().unwrap();
+        let shard = crate::storage::MasterShardData::new([5u8; 32], [2u8; 33]);
+
+        let config = PinConfig::with_path(temp_dir.path().join("auth.json"));
+        let mut manager = PinManager::with_config(config).unwrap();
+        manager.set_pin("123456").unwrap();
+        manager
+            .set_duress_pin("123456", "999999", DuressAction::Decoy)
+            .unwrap();
+        manager.set_wipe_after_failures("123456", Some(3)).unwrap();
+
+        let primary = EncryptedMotherStorage::new(temp_dir.path().to_path_buf()).unwrap();
+        let decoy = EncryptedMotherStorage::for_profile(
+            temp_dir.path().to_path_buf(),
+            StorageProfile::Decoy,
+        )
+        .unwrap();
+        let plaintext = MotherStorage::new(temp_dir.path().to_path_buf()).unwrap();
+        plaintext.save_master_shard(&shard).unwrap();
+        primary
+            .save_master_shard(&shard, &manager.verify_pin("123456").unwrap())
+            .unwrap();
+        decoy
+            .save_master_shard(&shard, &manager.verify_pin("999999").unwrap())
+            .unwrap();
+
+        for pin in ["111111", "111112", "111113"] {
+            let _ = manager.verify_pin(pin);
+        }
+
+        assert!(!primary.has_master_shard());
+        assert!(!decoy.has_master_shard());
+        assert!(!temp_dir.path().join("master_shard.json").exists());
+        assert!(!temp_dir.path().join("master_shard.enc").exists());
+        assert!(!temp_dir.path().join("profile").exists());
+    }
+
+    #[test]
+    fn test_pending_pin_change() {
+        let mut manager = test_manager();
+        manager.set_pin("123456").unwrap();
+        manager
+            .set_duress_pin("123456", "999999", DuressAction::Decoy)
+            .unwrap();
+
+        // Abandoned changes leave the old PINs in place
+        let change = manager.prepare_pin_change("123456", "654321").unwrap();
+        let new_key = *change.encryption_key();
+        drop(change);
+        assert!(manager.verify_pin("654321").is_err());
+        assert!(manager.verify_pin("123456").is_ok());
+
+        let change = manager.prepare_pin_change("123456", "654321").unwrap();
+        assert_ne!(change.encryption_key(), &new_key);
+        let new_key = *change.encryption_key();
+        manager.commit_pin_change(change).unwrap();
+        assert_eq!(manager.verify_pin("654321").unwrap(), new_key);
+        assert!(manager.verify_pin("123456").is_err());
+
+        // Under duress only the duress PIN changes
+        let change = manager.prepare_pin_change("999999", "888888").unwrap();
+        let decoy_key = *change.encryption_key();
+        manager.commit_pin_change(change).unwrap();
+        let decoy = manager.unlock("888888").unwrap();
+        assert_eq!(decoy.profile, StorageProfile::Decoy);
+        assert_eq!(decoy.encryption_key, decoy_key);
+        assert_eq!(manager.verify_pin("654321").unwrap(), new_key);
+    }
 }
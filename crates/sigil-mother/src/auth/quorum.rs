@@ -0,0 +1,941 @@
+//! M-of-N operator quorum
+//!
+//! In quorum mode the storage encryption key is split with Shamir secret
+//! sharing over GF(256). Each of the N registered operators holds one share,
+//! wrapped under a key derived from their own PIN. Dangerous ceremonies
+//! require M distinct operators to authenticate within a single quorum
+//! session, which reconstructs the storage key.
+//!
+//! The quorum configuration is authenticated with an HMAC keyed by the storage
+//! key, so it can only be changed by a session that has already met quorum.
+//! Enabling quorum mode also sets a flag in the PIN storage, so deleting
+//! quorum.json blocks the gated ceremonies instead of silently disabling them.
+
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use argon2::Argon2;
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    ChaCha20Poly1305, Nonce,
+};
+use hmac::{Hmac, Mac};
+use rand::{rngs::OsRng, RngCore};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use zeroize::Zeroizing;
+
+use super::pin::hex_salt;
+use super::{AuthError, LockoutPolicy, PinManager, Session, SessionConfig};
+
+/// Size of the nonce for ChaCha20-Poly1305
+const NONCE_SIZE: usize = 12;
+
+/// Maximum number of operators (share indices are 1..=255)
+pub const MAX_OPERATORS: usize = 255;
+
+/// Ceremonies that require an operator quorum
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QuorumAction {
+    /// Export the agent master shard
+    ExportAgentShard,
+    /// Nullify a child or agent
+    Nullify,
+    /// Publish a new accumulator
+    PublishAccumulator,
+    /// Re-key the master shard
+    MasterRekey,
+    /// Change the quorum configuration itself
+    ChangeQuorum,
+    /// Export a migration bundle and retire this device
+    MigrateDevice,
+    /// Export the ceremony journal for an external auditor
+    ExportJournal,
+    /// Create a child disk from the master shard
+    CreateChild,
+}
+
+/// Proof that a quorum approved an action
+#[derive(Debug, Clone)]
+pub struct QuorumApproval {
+    /// The approved action
+    pub action: QuorumAction,
+    /// Operators who authenticated for this approval
+    pub approvers: Vec<String>,
+    /// Quorum configuration generation the approval was made under
+    pub generation: u64,
+}
+
+/// Operator credential (persisted to disk)
+#[derive(Clone, Serialize, Deserialize)]
+struct OperatorCredential {
+    /// Operator identifier
+    operator_id: String,
+    /// Shamir x-coordinate of this operator's share
+    share_index: u8,
+    /// Salt for deriving the wrapping key from the operator PIN
+    #[serde(with = "hex_salt")]
+    kdf_salt: [u8; 32],
+    /// Nonce || ChaCha20-Poly1305 ciphertext of the share (hex)
+    wrapped_share: String,
+    /// Number of failed attempts
+    failed_attempts: u32,
+    /// Timestamp of last failed attempt (Unix epoch seconds)
+    last_failed_attempt: Option<u64>,
+}
+
+/// Quorum storage format (persisted to disk)
+#[derive(Clone, Serialize, Deserialize)]
+struct QuorumStorage {
+    /// Incremented on every approved configuration change
+    generation: u64,
+    /// Number of operators required
+    threshold: u8,
+    /// Registered operators
+    operators: Vec<OperatorCredential>,
+    /// MAC over the configuration, keyed by the storage key
+    #[serde(with = "hex_salt")]
+    config_mac: [u8; 32],
+    /// Version for future migrations
+    version: u32,
+}
+
+impl QuorumStorage {
+    /// Configuration MAC under the storage key
+    fn mac(&self, storage_key: &[u8; 32]) -> Hmac<Sha256> {
+        let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(storage_key)
+            .expect("HMAC accepts keys of any length");
+        mac.update(b"sigil-quorum-config:");
+        mac.update(&self.generation.to_le_bytes());
+        mac.update(&[self.threshold]);
+        for op in &self.operators {
+            mac.update(&(op.operator_id.len() as u32).to_le_bytes());
+            mac.update(op.operator_id.as_bytes());
+            mac.update(&[op.share_index]);
+            mac.update(&op.kdf_salt);
+            mac.update(op.wrapped_share.as_bytes());
+        }
+        mac
+    }
+
+    /// Compute the configuration MAC under the storage key
+    fn compute_mac(&self, storage_key: &[u8; 32]) -> [u8; 32] {
+        self.mac(storage_key).finalize().into_bytes().into()
+    }
+
+    /// Check the stored MAC in constant time
+    fn verify_mac(&self, storage_key: &[u8; 32]) -> bool {
+        self.mac(storage_key).verify_slice(&self.config_mac).is_ok()
+    }
+}
+
+/// Manages operator credentials and quorum configuration
+pub struct QuorumManager {
+    /// Path to quorum.json
+    path: PathBuf,
+    /// Per-operator lockout policy
+    lockout_policy: LockoutPolicy,
+    /// Current storage state
+    storage: Option<QuorumStorage>,
+}
+
+/// Operator authentications collected towards a quorum
+pub struct QuorumSession {
+    /// Configuration generation this session was started under
+    generation: u64,
+    /// Number of operators required
+    threshold: u8,
+    /// Collected (operator_id, share_index, share)
+    shares: Vec<(String, u8, Zeroizing<[u8; 32]>)>,
+    /// Reconstructed storage key once quorum is met
+    storage_key: Option<Zeroizing<[u8; 32]>>,
+    /// When the session was started
+    started_at: Instant,
+    /// Session configuration
+    config: SessionConfig,
+}
+
+impl QuorumManager {
+    /// Open the quorum configuration in the given data directory
+    pub fn new(base_path: &Path) -> Result<Self, AuthError> {
+        fs::create_dir_all(base_path)?;
+        let path = base_path.join("quorum.json");
+
+        let storage = if path.exists() {
+            let contents = fs::read_to_string(&path)?;
+            Some(serde_json::from_str(&contents).map_err(|e| {
+                AuthError::StorageError(format!("Failed to parse quorum storage: {}", e))
+            })?)
+        } else {
+            None
+        };
+
+        Ok(Self {
+            path,
+            lockout_policy: LockoutPolicy::default(),
+            storage,
+        })
+    }
+
+    /// Use a custom per-operator lockout policy
+    pub fn with_lockout_policy(mut self, policy: LockoutPolicy) -> Self {
+        self.lockout_policy = policy;
+        self
+    }
+
+    /// Check if quorum mode is configured
+    pub fn is_configured(&self) -> bool {
+        self.storage.is_some()
+    }
+
+    /// Whether gated ceremonies need a quorum approval
+    ///
+    /// Fails closed when the PIN storage records that quorum mode was
+    /// enabled but the configuration is gone.
+    pub fn requires_approval(&self, pin_manager: &PinManager) -> Result<bool, AuthError> {
+        match (self.is_configured(), pin_manager.quorum_required()) {
+            (true, _) => Ok(true),
+            (false, true) => Err(AuthError::QuorumConfigMissing),
+            (false, false) => Ok(false),
+        }
+    }
+
+    /// Number of operators required (M)
+    pub fn threshold(&self) -> Option<u8> {
+        self.storage.as_ref().map(|s| s.threshold)
+    }
+
+    /// Registered operator IDs (N)
+    pub fn operator_ids(&self) -> Vec<String> {
+        self.storage
+            .as_ref()
+            .map(|s| s.operators.iter().map(|o| o.operator_id.clone()).collect())
+            .unwrap_or_default()
+    }
+
+    /// Current configuration generation
+    pub fn generation(&self) -> Option<u64> {
+        self.storage.as_ref().map(|s| s.generation)
+    }
+
+    /// Enable quorum mode, splitting the storage key among the operators
+    ///
+    /// `operators` is a list of (operator_id, PIN) pairs.
+    pub fn initialize(
+        &mut self,
+        storage_key: &[u8; 32],
+        threshold: u8,
+        operators: &[(String, String)],
+    ) -> Result<(), AuthError> {
+        if self.storage.is_some() {
+            return Err(AuthError::InvalidQuorum(
+                "Quorum already configured - use a quorum-approved change".to_string(),
+            ));
+        }
+
+        let storage = Self::build_storage(storage_key, 0, threshold, operators)?;
+        self.save_storage(&storage)?;
+        self.storage = Some(storage);
+        Ok(())
+    }
+
+    /// Start collecting operator authentications
+    pub fn begin_session(&self, config: SessionConfig) -> Result<QuorumSession, AuthError> {
+        let storage = self
+            .storage
+            .as_ref()
+            .ok_or(AuthError::QuorumNotConfigured)?;
+        Ok(QuorumSession {
+            generation: storage.generation,
+            threshold: storage.threshold,
+            shares: Vec::new(),
+            storage_key: None,
+            started_at: Instant::now(),
+            config,
+        })
+    }
+
+    /// Authenticate one operator and add their share to the session
+    pub fn approve(
+        &mut self,
+        session: &mut QuorumSession,
+        operator_id: &str,
+        pin: &str,
+    ) -> Result<(), AuthError> {
+        session.check_valid()?;
+
+        let storage = self
+            .storage
+            .as_mut()
+            .ok_or(AuthError::QuorumNotConfigured)?;
+        if storage.generation != session.generation {
+            return Err(AuthError::InvalidQuorum(
+                "Quorum configuration changed during session".to_string(),
+            ));
+        }
+        if session.shares.iter().any(|(id, _, _)| id == operator_id) {
+            return Err(AuthError::DuplicateApproval(operator_id.to_string()));
+        }
+
+        let lockout_policy = &self.lockout_policy;
+        let credential = storage
+            .operators
+            .iter_mut()
+            .find(|o| o.operator_id == operator_id)
+            .ok_or_else(|| AuthError::UnknownOperator(operator_id.to_string()))?;
+
+        // Per-operator lockout
+        let now = unix_now();
+        if let (Some(duration), Some(last)) = (
+            lockout_policy.lockout_duration(credential.failed_attempts),
+            credential.last_failed_attempt,
+        ) {
+            let until = last + duration.as_secs();
+            if now < until {
+                return Err(AuthError::LockedOut(until - now));
+            }
+        }
+
+        match unwrap_share(credential, pin) {
+            Ok(share) => {
+                credential.failed_attempts = 0;
+                credential.last_failed_attempt = None;
+                session
+                    .shares
+                    .push((operator_id.to_string(), credential.share_index, share));
+            }
+            Err(AuthError::DecryptionFailed) => {
+                credential.failed_attempts += 1;
+                credential.last_failed_attempt = Some(now);
+                let remaining = lockout_policy
+                    .max_attempts()
+                    .saturating_sub(credential.failed_attempts);
+                self.save_current_storage()?;
+                return Err(AuthError::IncorrectPin(remaining));
+            }
+            Err(e) => return Err(e),
+        }
+        self.save_current_storage()?;
+
+        // Reconstruct and check the key once enough operators have approved
+        if session.storage_key.is_none() && session.shares.len() >= session.threshold as usize {
+            let points: Vec<(u8, [u8; 32])> = session
+                .shares
+                .iter()
+                .map(|(_, x, share)| (*x, **share))
+                .collect();
+            let key = Zeroizing::new(gf256::combine(&points));
+
+            let storage = self
+                .storage
+                .as_ref()
+                .ok_or(AuthError::QuorumNotConfigured)?;
+            if !storage.verify_mac(&key) {
+                return Err(AuthError::InvalidQuorum(
+                    "Quorum configuration failed integrity check".to_string(),
+                ));
+            }
+            session.storage_key = Some(key);
+        }
+
+        Ok(())
+    }
+
+    /// Replace the quorum configuration (requires a satisfied session)
+    ///
+    /// Every operator in the new configuration must supply their PIN, since
+    /// their share is re-wrapped under it.
+    pub fn change_quorum(
+        &mut self,
+        session: &QuorumSession,
+        threshold: u8,
+        operators: &[(String, String)],
+    ) -> Result<(), AuthError> {
+        session.authorize(QuorumAction::ChangeQuorum)?;
+
+        let storage = self
+            .storage
+            .as_ref()
+            .ok_or(AuthError::QuorumNotConfigured)?;
+        if storage.generation != session.generation {
+            return Err(AuthError::InvalidQuorum(
+                "Quorum configuration changed during session".to_string(),
+            ));
+        }
+
+        let storage_key = session.storage_key()?;
+        let updated =
+            Self::build_storage(storage_key, storage.generation + 1, threshold, operators)?;
+        self.save_storage(&updated)?;
+        self.storage = Some(updated);
+        Ok(())
+    }
+
+    /// Re-split a new storage key among the current operators (requires a
+    /// satisfied session)
+    ///
+    /// Used when the storage key itself changes, e.g. after a PIN change.
+    /// Every current operator must supply their PIN, which is checked
+    /// against their current share so a typo can't lock them out.
+    pub fn rekey(
+        &mut self,
+        session: &QuorumSession,
+        new_storage_key: &[u8; 32],
+        operators: &[(String, String)],
+    ) -> Result<(), AuthError> {
+        session.authorize(QuorumAction::MasterRekey)?;
+
+        let storage = self
+            .storage
+            .as_ref()
+            .ok_or(AuthError::QuorumNotConfigured)?;
+        if storage.generation != session.generation {
+            return Err(AuthError::InvalidQuorum(
+                "Quorum configuration changed during session".to_string(),
+            ));
+        }
+
+        let current: BTreeSet<&str> = storage
+            .operators
+            .iter()
+            .map(|o| o.operator_id.as_str())
+            .collect();
+        let supplied: BTreeSet<&str> = operators.iter().map(|(id, _)| id.as_str()).collect();
+        if current != supplied || operators.len() != current.len() {
+            return Err(AuthError::InvalidQuorum(
+                "Re-keying must keep the current operator set".to_string(),
+            ));
+        }
+        for credential in &storage.operators {
+            let pin = operators
+                .iter()
+                .find(|(id, _)| *id == credential.operator_id)
+                .map(|(_, pin)| pin.as_str())
+                .unwrap_or_default();
+            match unwrap_share(credential, pin) {
+                Ok(_) => {}
+                Err(AuthError::DecryptionFailed) => {
+                    return Err(AuthError::InvalidQuorum(format!(
+                        "Incorrect PIN for operator '{}'",
+                        credential.operator_id
+                    )));
+                }
+                Err(e) => return Err(e),
+            }
+        }
+
+        let updated = Self::build_storage(
+            new_storage_key,
+            storage.generation + 1,
+            storage.threshold,
+            operators,
+        )?;
+        self.save_storage(&updated)?;
+        self.storage = Some(updated);
+        Ok(())
+    }
+
+    /// Split the storage key and wrap each share under its operator's PIN
+    fn build_storage(
+        storage_key: &[u8; 32],
+        generation: u64,
+        threshold: u8,
+        operators: &[(String, String)],
+    ) -> Result<QuorumStorage, AuthError> {
+        if operators.is_empty() || operators.len() > MAX_OPERATORS {
+            return Err(AuthError::InvalidQuorum(format!(
+                "Operator count must be 1-{}",
+                MAX_OPERATORS
+            )));
+        }
+        if threshold == 0 || threshold as usize > operators.len() {
+            return Err(AuthError::InvalidQuorum(format!(
+                "Threshold must be 1-{}",
+                operators.len()
+            )));
+        }
+
+        let mut seen = BTreeSet::new();
+        for (operator_id, pin) in operators {
+            if operator_id.is_empty() || !seen.insert(operator_id.as_str()) {
+                return Err(AuthError::InvalidQuorum(format!(
+                    "Operator IDs must be unique and non-empty: '{}'",
+                    operator_id
+                )));
+            }
+            PinManager::validate_pin(pin)?;
+        }
+
+        let shares = gf256::split(storage_key, threshold, operators.len() as u8);
+        let mut credentials = Vec::with_capacity(operators.len());
+        for ((operator_id, pin), (x, share)) in operators.iter().zip(shares.iter()) {
+            credentials.push(wrap_share(operator_id, pin, *x, share)?);
+        }
+
+        let mut storage = QuorumStorage {
+            generation,
+            threshold,
+            operators: credentials,
+            config_mac: [0u8; 32],
+            version: 1,
+        };
+        storage.config_mac = storage.compute_mac(storage_key);
+        Ok(storage)
+    }
+
+    /// Save current storage state to file
+    fn save_current_storage(&self) -> Result<(), AuthError> {
+        if let Some(storage) = &self.storage {
+            self.save_storage(storage)?;
+        }
+        Ok(())
+    }
+
+    /// Save storage to file
+    fn save_storage(&self, storage: &QuorumStorage) -> Result<(), AuthError> {
+        let contents = serde_json::to_string_pretty(storage)
+            .map_err(|e| AuthError::StorageError(format!("Failed to serialize: {}", e)))?;
+
+        // Write atomically
+        let temp_path = self.path.with_extension("json.tmp");
+        fs::write(&temp_path, &contents)?;
+        fs::rename(&temp_path, &self.path)?;
+
+        // Set restrictive permissions (Unix only)
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            fs::set_permissions(&self.path, fs::Permissions::from_mode(0o600))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl QuorumSession {
+    /// Operators who have authenticated in this session
+    pub fn approvers(&self) -> Vec<String> {
+        self.shares.iter().map(|(id, _, _)| id.clone()).collect()
+    }
+
+    /// Number of additional operators still required
+    pub fn approvals_needed(&self) -> usize {
+        (self.threshold as usize).saturating_sub(self.shares.len())
+    }
+
+    /// Check if enough operators have authenticated
+    pub fn is_satisfied(&self) -> bool {
+        self.storage_key.is_some()
+    }
+
+    /// Check if the session is still within its time limit
+    pub fn is_valid(&self) -> bool {
+        self.started_at.elapsed() <= self.config.max_duration
+    }
+
+    /// Authorize a quorum-protected action
+    pub fn authorize(&self, action: QuorumAction) -> Result<QuorumApproval, AuthError> {
+        self.check_valid()?;
+        if !self.is_satisfied() {
+            return Err(AuthError::QuorumNotMet {
+                approved: self.shares.len(),
+                required: self.threshold as usize,
+            });
+        }
+
+        Ok(QuorumApproval {
+            action,
+            approvers: self.approvers(),
+            generation: self.generation,
+        })
+    }
+
+    /// Open a storage session with the reconstructed key
+    pub fn open_session(&self) -> Result<Session, AuthError> {
+        Ok(Session::new(*self.storage_key()?, self.config.clone()))
+    }
+
+    /// Get the reconstructed storage key
+    fn storage_key(&self) -> Result<&[u8; 32], AuthError> {
+        self.check_valid()?;
+        self.storage_key.as_deref().ok_or(AuthError::QuorumNotMet {
+            approved: self.shares.len(),
+            required: self.threshold as usize,
+        })
+    }
+
+    /// Fail if the session has expired
+    fn check_valid(&self) -> Result<(), AuthError> {
+        if self.is_valid() {
+            Ok(())
+        } else {
+            Err(AuthError::SessionExpired)
+        }
+    }
+}
+
+/// Wrap a share under a key derived from the operator PIN
+fn wrap_share(
+    operator_id: &str,
+    pin: &str,
+    share_index: u8,
+    share: &[u8; 32],
+) -> Result<OperatorCredential, AuthError> {
+    let mut kdf_salt = [0u8; 32];
+    OsRng.fill_bytes(&mut kdf_salt);
+    let wrapping_key = derive_wrapping_key(pin, &kdf_salt)?;
+
+    let mut nonce_bytes = [0u8; NONCE_SIZE];
+    OsRng.fill_bytes(&mut nonce_bytes);
+    let nonce: Nonce = nonce_bytes.into();
+
+    let cipher = ChaCha20Poly1305::new_from_slice(wrapping_key.as_ref())
+        .map_err(|e| AuthError::CryptoError(format!("Invalid key: {}", e)))?;
+    let ciphertext = cipher
+        .encrypt(
+            &nonce,
+            Payload {
+                msg: share,
+                aad: operator_id.as_bytes(),
+            },
+        )
+        .map_err(|e| AuthError::CryptoError(format!("Encryption failed: {}", e)))?;
+
+    let mut wrapped = Vec::with_capacity(NONCE_SIZE + ciphertext.len());
+    wrapped.extend_from_slice(&nonce_bytes);
+    wrapped.extend_from_slice(&ciphertext);
+
+    Ok(OperatorCredential {
+        operator_id: operator_id.to_string(),
+        share_index,
+        kdf_salt,
+        wrapped_share: hex::encode(wrapped),
+        failed_attempts: 0,
+        last_failed_attempt: None,
+    })
+}
+
+/// Unwrap an operator's share with their PIN
+fn unwrap_share(
+    credential: &OperatorCredential,
+    pin: &str,
+) -> Result<Zeroizing<[u8; 32]>, AuthError> {
+    let wrapped = hex::decode(&credential.wrapped_share)
+        .map_err(|e| AuthError::StorageError(format!("Invalid wrapped share: {}", e)))?;
+    if wrapped.len() < NONCE_SIZE {
+        return Err(AuthError::StorageError(
+            "Wrapped share too short".to_string(),
+        ));
+    }
+
+    let wrapping_key = derive_wrapping_key(pin, &credential.kdf_salt)?;
+    let cipher = ChaCha20Poly1305::new_from_slice(wrapping_key.as_ref())
+        .map_err(|e| AuthError::CryptoError(format!("Invalid key: {}", e)))?;
+
+    let nonce_bytes: [u8; NONCE_SIZE] = wrapped[..NONCE_SIZE]
+        .try_into()
+        .map_err(|_| AuthError::StorageError("Invalid nonce".to_string()))?;
+    let nonce = Nonce::from(nonce_bytes);
+    let plaintext = Zeroizing::new(
+        cipher
+            .decrypt(
+                &nonce,
+                Payload {
+                    msg: &wrapped[NONCE_SIZE..],
+                    aad: credential.operator_id.as_bytes(),
+                },
+            )
+            .map_err(|_| AuthError::DecryptionFailed)?,
+    );
+
+    let share: [u8; 32] = plaintext
+        .as_slice()
+        .try_into()
+        .map_err(|_| AuthError::StorageError("Invalid share length".to_string()))?;
+    Ok(Zeroizing::new(share))
+}
+
+/// Derive a share wrapping key from an operator PIN
+fn derive_wrapping_key(pin: &str, salt: &[u8; 32]) -> Result<Zeroizing<[u8; 32]>, AuthError> {
+    let mut key = Zeroizing::new([0u8; 32]);
+    Argon2::default()
+        .hash_password_into(pin.as_bytes(), salt, key.as_mut())
+        .map_err(|e| AuthError::CryptoError(format!("Key derivation failed: {}", e)))?;
+    Ok(key)
+}
+
+/// Current Unix time in seconds
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_secs()
+}
+
+/// Shamir secret sharing over GF(2^8), byte-wise
+mod gf256 {
+    use rand::{rngs::OsRng, RngCore};
+    use zeroize::Zeroize;
+
+    /// Multiply in GF(2^8) with the AES polynomial
+    fn mul(mut a: u8, mut b: u8) -> u8 {
+        let mut product = 0u8;
+        for _ in 0..8 {
+            if b & 1 != 0 {
+                product ^= a;
+            }
+            let carry = a & 0x80;
+            a <<= 1;
+            if carry != 0 {
+                a ^= 0x1b;
+            }
+            b >>= 1;
+        }
+        product
+    }
+
+    /// Multiplicative inverse (a^254)
+    fn inv(a: u8) -> u8 {
+        let mut result = 1u8;
+        let mut base = a;
+        let mut exp = 254u8;
+        while exp > 0 {
+            if exp & 1 != 0 {
+                result = mul(result, base);
+            }
+            base = mul(base, base);
+            exp >>= 1;
+        }
+        result
+    }
+
+    /// Split a secret into `n` shares, any `m` of which reconstruct it
+    pub fn split(secret: &[u8; 32], m: u8, n: u8) -> Vec<(u8, [u8; 32])> {
+        let mut shares: Vec<(u8, [u8; 32])> = (1..=n).map(|x| (x, [0u8; 32])).collect();
+        let mut coeffs = vec![0u8; m as usize];
+
+        for (byte, &s) in secret.iter().enumerate() {
+            coeffs[0] = s;
+            OsRng.fill_bytes(&mut coeffs[1..]);
+
+            for (x, share) in shares.iter_mut() {
+                // Horner evaluation of the polynomial at x
+                let mut y = 0u8;
+                for &c in coeffs.iter().rev() {
+                    y = mul(y, *x) ^ c;
+                }
+                share[byte] = y;
+            }
+        }
+
+        coeffs.zeroize();
+        shares
+    }
+
+    /// Reconstruct the secret from shares by Lagrange interpolation at 0
+    pub fn combine(shares: &[(u8, [u8; 32])]) -> [u8; 32] {
+        let mut secret = [0u8; 32];
+
+        for (i, (xi, yi)) in shares.iter().enumerate() {
+            let mut basis = 1u8;
+            for (j, (xj, _)) in shares.iter().enumerate() {
+                if i != j {
+                    basis = mul(basis, mul(*xj, inv(xj ^ xi)));
+                }
+            }
+            for (out, y) in secret.iter_mut().zip(yi.iter()) {
+                *out ^= mul(*y, basis);
+            }
+        }
+
+        secret
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    fn operators() -> Vec<(String, String)> {
+        vec![
+            ("alice".to_string(), "111111".to_string()),
+            ("bob".to_string(), "222222".to_string()),
+            ("carol".to_string(), "333333".to_string()),
+        ]
+    }
+
+    #[test]
+    fn test_shamir_any_threshold_subset() {
+        let secret = [7u8; 32];
+        let shares = gf256::split(&secret, 2, 3);
+
+        assert_eq!(gf256::combine(&shares[0..2]), secret);
+        assert_eq!(gf256::combine(&shares[1..3]), secret);
+        assert_ne!(gf256::combine(&shares[0..1]), secret);
+    }
+
+    #[test]
+    fn test_quorum_requires_distinct_operators() {
+        let temp_dir = tempdir().unwrap();
+        let mut manager = QuorumManager::new(temp_dir.path()).unwrap();
+        let storage_key = [42u8; 32];
+        manager.initialize(&storage_key, 2, &operators()).unwrap();
+
+        let mut session = manager.begin_session(SessionConfig::default()).unwrap();
+        manager.approve(&mut session, "alice", "111111").unwrap();
+        assert!(matches!(
+            session.authorize(QuorumAction::ExportAgentShard),
+            Err(AuthError::QuorumNotMet {
+                approved: 1,
+                required: 2
+            })
+        ));
+
+        assert!(matches!(
+            manager.approve(&mut session, "alice", "111111"),
+            Err(AuthError::DuplicateApproval(_))
+        ));
+        assert!(matches!(
+            manager.approve(&mut session, "bob", "999999"),
+            Err(AuthError::IncorrectPin(_))
+        ));
+
+        manager.approve(&mut session, "carol", "333333").unwrap();
+        let approval = session.authorize(QuorumAction::Nullify).unwrap();
+        assert_eq!(approval.approvers, vec!["alice", "carol"]);
+        assert_eq!(
+            session.open_session().unwrap().encryption_key(),
+            &storage_key
+        );
+    }
+
+    #[test]
+    fn test_quorum_change_requires_quorum() {
+        let temp_dir = tempdir().unwrap();
+        let mut manager = QuorumManager::new(temp_dir.path()).unwrap();
+        let storage_key = [42u8; 32];
+        manager.initialize(&storage_key, 2, &operators()).unwrap();
+
+        let new_operators = vec![
+            ("alice".to_string(), "444444".to_string()),
+            ("dave".to_string(), "555555".to_string()),
+        ];
+
+        let mut session = manager.begin_session(SessionConfig::default()).unwrap();
+        manager.approve(&mut session, "bob", "222222").unwrap();
+        assert!(manager.change_quorum(&session, 1, &new_operators).is_err());
+
+        manager.approve(&mut session, "alice", "111111").unwrap();
+        manager.change_quorum(&session, 1, &new_operators).unwrap();
+
+        // Reload from disk and authenticate under the new configuration
+        let mut manager = QuorumManager::new(temp_dir.path()).unwrap();
+        assert_eq!(manager.generation(), Some(1));
+        assert_eq!(manager.threshold(), Some(1));
+
+        let mut session = manager.begin_session(SessionConfig::default()).unwrap();
+        manager.approve(&mut session, "dave", "555555").unwrap();
+        assert_eq!(
+            session.open_session().unwrap().encryption_key(),
+            &storage_key
+        );
+    }
+
+    #[test]
+    fn test_missing_config_fails_closed() {
+        let temp_dir = tempdir().unwrap();
+        let mut pin_manager = PinManager::with_config(super::super::PinConfig::with_path(
+            temp_dir.path().join("auth.json"),
+        ))
+        .unwrap();
+        pin_manager.set_pin("123456").unwrap();
+
+        let mut manager = QuorumManager::new(temp_dir.path()).unwrap();
+        assert!(!manager.requires_approval(&pin_manager).unwrap());
+
+        manager.initialize(&[42u8; 32], 2, &operators()).unwrap();
+        pin_manager.enable_quorum("123456").unwrap();
+        assert!(manager.requires_approval(&pin_manager).unwrap());
+
+        fs::remove_file(temp_dir.path().join("quorum.json")).unwrap();
+        let manager = QuorumManager::new(temp_dir.path()).unwrap();
+        assert!(matches!(
+            manager.requires_approval(&pin_manager),
+            Err(AuthError::QuorumConfigMissing)
+        ));
+    }
+
+    #[test]
+    fn test_rekey_keeps_operators() {
+        let temp_dir = tempdir().unwrap();
+        let mut manager = QuorumManager::new(temp_dir.path()).unwrap();
+        manager.initialize(&[42u8; 32], 2, &operators()).unwrap();
+
+        let mut session = manager.begin_session(SessionConfig::default()).unwrap();
+        manager.approve(&mut session, "alice", "111111").unwrap();
+        manager.approve(&mut session, "bob", "222222").unwrap();
+
+        assert!(manager
+            .rekey(&session, &[9u8; 32], &operators()[..2])
+            .is_err());
+        manager.rekey(&session, &[9u8; 32], &operators()).unwrap();
+
+        let mut session = manager.begin_session(SessionConfig::default()).unwrap();
+        manager.approve(&mut session, "bob", "222222").unwrap();
+        manager.approve(&mut session, "carol", "333333").unwrap();
+        assert_eq!(session.open_session().unwrap().encryption_key(), &[9u8; 32]);
+    }
+
+    #[test]
+    fn test_rekey_rejects_wrong_operator_pin() {
+        let temp_dir = tempdir().unwrap();
+        let mut manager = QuorumManager::new(temp_dir.path()).unwrap();
+        manager.initialize(&[42u8; 32], 2, &operators()).unwrap();
+
+        let mut session = manager.begin_session(SessionConfig::default()).unwrap();
+        manager.approve(&mut session, "alice", "111111").unwrap();
+        manager.approve(&mut session, "bob", "222222").unwrap();
+
+        let mut mistyped = operators();
+        mistyped[2].1 = "333334".to_string();
+        assert!(matches!(
+            manager.rekey(&session, &[9u8; 32], &mistyped),
+            Err(AuthError::InvalidQuorum(_))
+        ));
+
+        // Nothing was re-split, so the old key and PINs still work
+        assert_eq!(manager.generation(), Some(session.generation));
+        let mut session = manager.begin_session(SessionConfig::default()).unwrap();
+        manager.approve(&mut session, "carol", "333333").unwrap();
+        manager.approve(&mut session, "alice", "111111").unwrap();
+        assert_eq!(
+            session.open_session().unwrap().encryption_key(),
+            &[42u8; 32]
+        );
+    }
+
+    #[test]
+    fn test_tampered_config_rejected() {
+        let temp_dir = tempdir().unwrap();
+        let mut manager = QuorumManager::new(temp_dir.path()).unwrap();
+        manager.initialize(&[42u8; 32], 2, &operators()).unwrap();
+
+        // Bump the generation on disk without re-computing the MAC
+        let path = temp_dir.path().join("quorum.json");
+        let mut storage: QuorumStorage =
+            serde_json::from_str(&fs::read_to_string(&path).unwrap()).unwrap();
+        storage.generation = 7;
+        fs::write(&path, serde_json::to_string(&storage).unwrap()).unwrap();
+
+        let mut manager = QuorumManager::new(temp_dir.path()).unwrap();
+        let mut session = manager.begin_session(SessionConfig::default()).unwrap();
+        manager.approve(&mut session, "alice", "111111").unwrap();
+        assert!(matches!(
+            manager.approve(&mut session, "bob", "222222"),
+            Err(AuthError::InvalidQuorum(_))
+        ));
+    }
+}
@@ -13,12 +13,15 @@ use sigil_core::{
     crypto::DerivationPath,
     disk::{DiskFormat, DiskHeader},
     presig::PresigColdShare,
-    ChildId, PublicKey,
+    AuditBundle, ChildId, PublicKey,
 };
 
 use crate::error::{MotherError, Result};
 use crate::keygen::MasterKeyGenerator;
 use crate::presig_gen::PresigGenerator;
+use crate::reconciliation::{
+    analyze_disk, compare_audit_bundle, max_severity, Anomaly, AnomalySeverity,
+};
 use crate::storage::MotherStorage;
 
 #[cfg(feature = "zkvm")]
@@ -155,6 +158,7 @@ impl CreateChildCeremony {
         // 11. Register child in registry
         let mut registry = self.storage.load_registry()?;
         registry.register_child(child_id, derivation_path)?;
+        registry.record_cold_pubkey(&child_id, cold_child_pubkey)?;
         self.storage.save_registry(&registry)?;
 
         // 12. Save updated master shard (with incremented index)
@@ -288,6 +292,7 @@ impl CreateChildCeremony {
         // 11. Register child in registry
         let mut registry = self.storage.load_registry()?;
         registry.register_child(child_id, derivation_path)?;
+        registry.record_cold_pubkey(&child_id, cold_child_pubkey)?;
         self.storage.save_registry(&registry)?;
 
         // 12. Save updated master shard (with incremented index)
@@ -319,6 +324,9 @@ pub struct ReconciliationResult {
     /// Any anomalies detected
     pub anomalies: Vec<String>,
 
+    /// Divergences between the agent audit bundle and the disk log
+    pub audit_anomalies: Vec<Anomaly>,
+
     /// Recommendation
     pub recommendation: ReconciliationRecommendation,
 }
@@ -336,6 +344,30 @@ pub enum ReconciliationRecommendation {
     Nullify { reason: String },
 }
 
+impl ReconciliationRecommendation {
+    /// Recommend based on the most severe anomaly detected
+    pub fn from_anomalies(anomalies: &[Anomaly]) -> Self {
+        let reasons = || {
+            anomalies
+                .iter()
+                .filter(|a| a.severity() > AnomalySeverity::Info)
+                .map(|a| format!("{:?}", a))
+                .collect::<Vec<_>>()
+                .join("; ")
+        };
+
+        match max_severity(anomalies) {
+            None | Some(AnomalySeverity::Info) => ReconciliationRecommendation::RefillApproved,
+            Some(AnomalySeverity::Warning) => {
+                ReconciliationRecommendation::ManualReview { reason: reasons() }
+            }
+            Some(AnomalySeverity::Critical) => {
+                ReconciliationRecommendation::Nullify { reason: reasons() }
+            }
+        }
+    }
+}
+
 impl ReconcileCeremony {
     /// Create a new reconciliation ceremony
     pub fn new(storage: MotherStorage) -> Self {
@@ -344,7 +376,15 @@ impl ReconcileCeremony {
 
     /// Execute reconciliation
     pub fn execute(&mut self, disk: &DiskFormat) -> Result<ReconciliationResult> {
-        let mut anomalies = Vec::new();
+        self.execute_with_audit(disk, None)
+    }
+
+    /// Execute reconciliation, cross-checking the disk against the agent's audit bundle
+    pub fn execute_with_audit(
+        &mut self,
+        disk: &DiskFormat,
+        audit_bundle: Option<&AuditBundle>,
+    ) -> Result<ReconciliationResult> {
         let child_id = disk.header.child_id;
 
         // 1. Verify disk is registered
@@ -359,34 +399,10 @@ impl ReconcileCeremony {
         // 3. Verify mother signature on header
         // (placeholder - would verify actual signature)
 
-        // 4. Validate usage log
-        if let Err(e) = disk.usage_log.validate() {
-            anomalies.push(format!("Usage log validation failed: {}", e));
-        }
-
-        // 5. Cross-check presig usage
-        let marked_used = disk
-            .presigs
-            .iter()
-            .filter(|p| p.status == sigil_core::presig::PresigStatus::Used)
-            .count() as u32;
-
-        if marked_used != disk.header.presig_used {
-            anomalies.push(format!(
-                "Presig count mismatch: header={}, marked={}",
-                disk.header.presig_used, marked_used
-            ));
-        }
-
-        if marked_used != disk.usage_log.len() as u32 {
-            anomalies.push(format!(
-                "Usage log count mismatch: marked={}, logged={}",
-                marked_used,
-                disk.usage_log.len()
-            ));
-        }
+        // 4. Check the disk's own consistency
+        let analysis = analyze_disk(disk);
 
-        // 6. Verify signatures in usage log
+        // 5. Verify signatures in usage log
         let mut verified = 0;
         for _entry in &disk.usage_log.entries {
             // Would verify each signature against message_hash and child_pubkey
@@ -394,25 +410,39 @@ impl ReconcileCeremony {
             verified += 1;
         }
 
-        // 7. Determine recommendation
-        let recommendation = if anomalies.is_empty() {
-            ReconciliationRecommendation::RefillApproved
-        } else if anomalies.len() <= 2 {
-            ReconciliationRecommendation::ManualReview {
-                reason: anomalies.join("; "),
-            }
-        } else {
-            ReconciliationRecommendation::Nullify {
-                reason: format!("Multiple anomalies: {}", anomalies.join("; ")),
+        // 6. Diff against the agent-side audit log, trusting only the pinned key
+        let audit_anomalies = match audit_bundle {
+            Some(bundle) => {
+                let pinned_key = self.storage.load_audit_key()?;
+                compare_audit_bundle(disk, bundle, pinned_key.as_ref())
             }
+            None => Vec::new(),
         };
 
+        // 7. Determine recommendation from the most severe anomaly
+        let mut all_anomalies = analysis.anomalies;
+        all_anomalies.extend(audit_anomalies.iter().cloned());
+        let recommendation = ReconciliationRecommendation::from_anomalies(&all_anomalies);
+        let anomalies: Vec<String> = all_anomalies
+            .iter()
+            .map(|a| format!("[{:?}] {:?}", a.severity(), a))
+            .collect();
+
         // 8. Save reconciliation log
+        let audit_summary = match audit_bundle {
+            Some(bundle) => format!(
+                "{} records from auditor {}",
+                bundle.records.len(),
+                bundle.auditor_pubkey.to_hex()
+            ),
+            None => "not provided".to_string(),
+        };
         let log_entry = format!(
-            "Reconciliation at {}\nChild: {}\nSignatures: {}\nAnomalies: {:?}\nRecommendation: {:?}",
+            "Reconciliation at {}\nChild: {}\nSignatures: {}\nAudit bundle: {}\nAnomalies: {:?}\nRecommendation: {:?}",
             chrono::Utc::now(),
             child_id.short(),
             verified,
+            audit_summary,
             anomalies,
             recommendation
         );
@@ -423,6 +453,7 @@ impl ReconcileCeremony {
             valid: anomalies.is_empty(),
             signatures_verified: verified,
             anomalies,
+            audit_anomalies,
             recommendation,
         })
     }
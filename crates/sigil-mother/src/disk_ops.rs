@@ -4,10 +4,13 @@
 //! Designed for use on air-gapped systems where automatic mounting
 //! may not be configured.
 
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
 use std::process::Command;
 
 use serde::Deserialize;
+use sigil_core::disk::DiskFormat;
 
 use crate::error::{MotherError, Result};
 
@@ -916,6 +919,116 @@ impl FloppyManager {
 
         Ok(())
     }
+
+    /// Write a disk image straight to the device, with no filesystem
+    ///
+    /// The image is padded to whole sectors and written from offset 0 with
+    /// `O_DIRECT | O_SYNC` where the device supports it, then read back and
+    /// compared. The device must not be mounted.
+    pub fn write_raw(&self, disk: &DiskFormat) -> Result<()> {
+        if let Ok(Some(mount_point)) = get_mount_point(&self.device) {
+            return Err(MotherError::Io(std::io::Error::other(format!(
+                "{} is mounted at {}; unmount it before raw writes",
+                self.device,
+                mount_point.display()
+            ))));
+        }
+
+        let bytes = disk.to_bytes();
+        let len = bytes.len().div_ceil(RAW_SECTOR_SIZE) * RAW_SECTOR_SIZE;
+        let mut buf = AlignedBuf::new(len);
+        buf.as_mut_slice()[..bytes.len()].copy_from_slice(&bytes);
+
+        let mut file = open_raw(Path::new(&self.device), true)?;
+        file.write_all(buf.as_slice())?;
+        file.sync_all()?;
+        drop(file);
+
+        let mut verify = AlignedBuf::new(len);
+        let mut file = open_raw(Path::new(&self.device), false)?;
+        file.read_exact(verify.as_mut_slice())?;
+        if verify.as_slice() != buf.as_slice() {
+            return Err(MotherError::InvalidDiskFormat(format!(
+                "Read-back of {} does not match the written image",
+                self.device
+            )));
+        }
+
+        Ok(())
+    }
+
+    /// Read a disk image written by [`FloppyManager::write_raw`]
+    pub fn read_raw(&self) -> Result<DiskFormat> {
+        let mut file = open_raw(Path::new(&self.device), false)?;
+        let mut buf = AlignedBuf::new(RAW_READ_SIZE);
+        let mut filled = 0;
+        while filled < RAW_READ_SIZE {
+            match file.read(&mut buf.as_mut_slice()[filled..])? {
+                0 => break,
+                n => filled += n,
+            }
+        }
+
+        DiskFormat::from_bytes(&buf.as_slice()[..filled])
+            .map_err(|e| MotherError::InvalidDiskFormat(e.to_string()))
+    }
+}
+
+/// Sector size used for raw device I/O
+const RAW_SECTOR_SIZE: usize = 512;
+
+/// Buffer alignment for `O_DIRECT` (covers 4K-sector devices)
+const RAW_DIRECT_ALIGN: usize = 4096;
+
+/// Bytes read back from a raw device (a 1.44MB floppy)
+const RAW_READ_SIZE: usize = 1_474_560;
+
+/// Open a raw device, falling back to buffered I/O where `O_DIRECT` is refused
+fn open_raw(path: &Path, write: bool) -> Result<File> {
+    #[cfg(target_os = "linux")]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+
+        match OpenOptions::new()
+            .read(true)
+            .write(write)
+            .custom_flags(libc::O_DIRECT | libc::O_SYNC)
+            .open(path)
+        {
+            Ok(file) => return Ok(file),
+            Err(e) if e.raw_os_error() == Some(libc::EINVAL) => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    Ok(OpenOptions::new().read(true).write(write).open(path)?)
+}
+
+/// Zeroed buffer whose start is aligned for direct I/O
+struct AlignedBuf {
+    storage: Vec<u8>,
+    start: usize,
+    len: usize,
+}
+
+impl AlignedBuf {
+    fn new(len: usize) -> Self {
+        let storage = vec![0u8; len + RAW_DIRECT_ALIGN];
+        let start = storage.as_ptr().align_offset(RAW_DIRECT_ALIGN);
+        Self {
+            storage,
+            start,
+            len,
+        }
+    }
+
+    fn as_slice(&self) -> &[u8] {
+        &self.storage[self.start..self.start + self.len]
+    }
+
+    fn as_mut_slice(&mut self) -> &mut [u8] {
+        &mut self.storage[self.start..self.start + self.len]
+    }
 }
 
 /// Format type for disk formatting
@@ -1110,4 +1223,35 @@ mod tests {
         };
         assert_eq!(dev_plain.display_name(), "/dev/sda");
     }
+
+    #[test]
+    fn test_raw_write_round_trip() {
+        use sigil_core::{
+            crypto::DerivationPath, disk::DiskHeader, presig::PresigColdShare, ChildId, PublicKey,
+        };
+
+        let header = DiskHeader::new(
+            ChildId::new([7u8; 32]),
+            PublicKey::new([2u8; 33]),
+            DerivationPath::ethereum_hardened(0),
+            4,
+            1_700_000_000,
+        );
+        let presigs = (0..4)
+            .map(|i| PresigColdShare::new([i as u8; 33], [i as u8; 32], [i as u8; 32]))
+            .collect();
+        let disk = DiskFormat::new(header, presigs);
+
+        // A regular file stands in for the block device
+        let temp_dir = tempfile::TempDir::new().unwrap();
+        let device = temp_dir.path().join("fd0");
+        std::fs::write(&device, vec![0xFFu8; 4096]).unwrap();
+
+        let manager = FloppyManager::with_paths(device.to_str().unwrap(), temp_dir.path());
+        manager.write_raw(&disk).unwrap();
+
+        let read = manager.read_raw().unwrap();
+        assert_eq!(read.header.child_id, disk.header.child_id);
+        assert_eq!(read.presigs.len(), 4);
+    }
 }
@@ -0,0 +1,615 @@
+//! Signed, hash-chained ceremony journal
+//!
+//! Every ceremony performed on the mother device is appended to
+//! `journal.jsonl` as a structured record. Each record carries the hash of
+//! the previous record and an ECDSA signature by the mother journal key, so
+//! any edit, deletion or reordering of the journal is detectable.
+//!
+//! The journal key is derived from the cold master shard, which means only
+//! the mother device can extend the journal, while anyone holding the
+//! journal public key can verify an exported copy offline.
+
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use k256::ecdsa::{
+    signature::{Signer, Verifier},
+    Signature as K256Signature, SigningKey, VerifyingKey,
+};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use sigil_core::crypto::PublicKey;
+use sigil_core::types::{hex_bytes_32, Signature};
+
+use crate::error::{MotherError, Result};
+use crate::storage::MasterShardData;
+
+/// Journal file name inside the mother data directory
+pub const JOURNAL_FILE: &str = "journal.jsonl";
+
+/// File recording the journal public key, written on first append
+pub const JOURNAL_KEY_FILE: &str = "journal_key.pub";
+
+/// Journal export format version
+pub const JOURNAL_EXPORT_VERSION: u32 = 1;
+
+/// Hash linking the first entry of the journal
+pub const JOURNAL_GENESIS_HASH: [u8; 32] = [0u8; 32];
+
+/// A ceremony recorded in the journal, with its inputs and outputs
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(tag = "ceremony", rename_all = "snake_case")]
+pub enum Ceremony {
+    /// Master key generation
+    Init {
+        /// Master public key (hex)
+        master_pubkey: String,
+        /// Whether keys were generated on a Ledger
+        ledger: bool,
+    },
+
+    /// Child disk creation
+    CreateChild {
+        /// Child ID (hex)
+        child_id: String,
+        /// Derivation path allocated to the child
+        derivation_path: String,
+        /// Number of presignatures written
+        presig_count: u32,
+        /// Child public key (hex)
+        child_pubkey: String,
+    },
+
+    /// Reconciliation of a returned disk
+    Reconcile {
+        /// Child ID (hex)
+        child_id: String,
+        /// Number of usage log entries on the disk
+        log_entries: u32,
+        /// Number of anomalies found (disk and audit bundle)
+        anomalies: usize,
+        /// Resulting recommendation
+        recommendation: String,
+    },
+
+    /// Presignature refill
+    Refill {
+        /// Child ID (hex)
+        child_id: String,
+        /// Number of presignatures written
+        presig_count: u32,
+    },
+
+    /// Child nullification
+    Nullify {
+        /// Child ID (hex)
+        child_id: String,
+        /// Operator-supplied reason
+        reason: String,
+    },
+
+    /// Agent nullification (added to the accumulator)
+    AgentNullify {
+        /// Agent ID (hex)
+        agent_id: String,
+        /// Accumulator version after the nullification
+        accumulator_version: u64,
+    },
+
+    /// Accumulator export for agents
+    AccumulatorExport {
+        /// Accumulator version exported
+        accumulator_version: u64,
+        /// Hash of the exported accumulator (hex)
+        export_hash: String,
+    },
+
+    /// Agent master shard export
+    ExportAgentShard,
+
+    /// Agent audit key pinned for reconciliation
+    PinAuditKey {
+        /// Pinned audit public key (hex)
+        auditor_pubkey: String,
+    },
+
+    /// Mother PIN change
+    PinChange,
+
+    /// Operator quorum configuration change
+    QuorumChange {
+        /// Configuration generation after the change
+        generation: u64,
+        /// Operators required
+        threshold: u8,
+        /// Registered operator IDs
+        operators: Vec<String>,
+    },
+
+    /// State exported in a migration bundle; this device is retired
+    MigrationExport {
+        /// SHA-256 of the migration bundle (hex)
+        bundle_hash: String,
+        /// Reason recorded for retiring this device
+        reason: String,
+    },
+
+    /// State imported from a migration bundle onto this device
+    MigrationImport {
+        /// SHA-256 of the migration bundle (hex)
+        bundle_hash: String,
+        /// Unix timestamp the bundle was created on the old device
+        exported_at: u64,
+    },
+}
+
+impl Ceremony {
+    /// Short name of the ceremony for display
+    pub fn name(&self) -> &'static str {
+        match self {
+            Ceremony::Init { .. } => "init",
+            Ceremony::CreateChild { .. } => "create_child",
+            Ceremony::Reconcile { .. } => "reconcile",
+            Ceremony::Refill { .. } => "refill",
+            Ceremony::Nullify { .. } => "nullify",
+            Ceremony::AgentNullify { .. } => "agent_nullify",
+            Ceremony::AccumulatorExport { .. } => "accumulator_export",
+            Ceremony::ExportAgentShard => "export_agent_shard",
+            Ceremony::PinAuditKey { .. } => "pin_audit_key",
+            Ceremony::PinChange => "pin_change",
+            Ceremony::QuorumChange { .. } => "quorum_change",
+            Ceremony::MigrationExport { .. } => "migration_export",
+            Ceremony::MigrationImport { .. } => "migration_import",
+        }
+    }
+}
+
+/// A single signed journal entry
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct JournalEntry {
+    /// Position of this entry in the journal (starting at 0)
+    pub sequence: u64,
+
+    /// Unix timestamp of the ceremony
+    pub timestamp: u64,
+
+    /// Operator(s) who performed the ceremony
+    pub operator: String,
+
+    /// The ceremony and its inputs/outputs
+    #[serde(flatten)]
+    pub ceremony: Ceremony,
+
+    /// Hash of the previous entry
+    #[serde(with = "hex_bytes_32")]
+    pub prev_hash: [u8; 32],
+
+    /// Mother journal key signature over `entry_hash()`
+    pub signature: Signature,
+}
+
+/// The signed portion of a journal entry
+#[derive(Serialize)]
+struct SignedBody<'a> {
+    sequence: u64,
+    timestamp: u64,
+    operator: &'a str,
+    ceremony: &'a Ceremony,
+    #[serde(with = "hex_bytes_32")]
+    prev_hash: [u8; 32],
+}
+
+impl JournalEntry {
+    /// Hash of this entry (everything except the signature)
+    pub fn entry_hash(&self) -> [u8; 32] {
+        let body = SignedBody {
+            sequence: self.sequence,
+            timestamp: self.timestamp,
+            operator: &self.operator,
+            ceremony: &self.ceremony,
+            prev_hash: self.prev_hash,
+        };
+        // Serializing a borrowed struct cannot fail
+        let bytes = serde_json::to_vec(&body).unwrap_or_default();
+
+        let mut hasher = Sha256::new();
+        hasher.update(b"sigil-mother-journal:");
+        hasher.update(&bytes);
+        hasher.finalize().into()
+    }
+
+    /// Verify the signature on this entry
+    pub fn verify_signature(&self, key: &VerifyingKey) -> bool {
+        K256Signature::from_slice(self.signature.as_bytes())
+            .map(|sig| key.verify(&self.entry_hash(), &sig).is_ok())
+            .unwrap_or(false)
+    }
+}
+
+/// Derive the journal signing key from the master shard
+pub fn journal_signing_key(master: &MasterShardData) -> Result<SigningKey> {
+    let mut hasher = Sha256::new();
+    hasher.update(b"sigil-mother-journal-key:");
+    hasher.update(master.cold_master_shard);
+    let seed: [u8; 32] = hasher.finalize().into();
+
+    SigningKey::from_bytes(&seed.into())
+        .map_err(|e| MotherError::Crypto(format!("Invalid journal key: {}", e)))
+}
+
+/// Public key of a journal signing key
+pub fn journal_public_key(key: &SigningKey) -> PublicKey {
+    let point = key.verifying_key().to_encoded_point(true);
+    let mut bytes = [0u8; 33];
+    bytes.copy_from_slice(point.as_bytes());
+    PublicKey::new(bytes)
+}
+
+/// A problem found while verifying the journal
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JournalIssue {
+    /// Sequence number of the offending entry (its position if out of order)
+    pub sequence: u64,
+    /// Description of the problem
+    pub reason: String,
+}
+
+/// Outcome of verifying a journal
+#[derive(Debug, Clone)]
+pub struct JournalVerification {
+    /// Number of entries checked
+    pub entries: usize,
+    /// Hash of the last entry (genesis hash when empty)
+    pub head_hash: [u8; 32],
+    /// Timestamp of the last entry
+    pub last_timestamp: Option<u64>,
+    /// Problems found, in journal order
+    pub issues: Vec<JournalIssue>,
+}
+
+impl JournalVerification {
+    /// Whether the journal is intact
+    pub fn is_valid(&self) -> bool {
+        self.issues.is_empty()
+    }
+}
+
+/// Verify sequence numbers, hash links and signatures of a journal
+pub fn verify_entries(entries: &[JournalEntry], journal_key: &PublicKey) -> JournalVerification {
+    let mut issues = Vec::new();
+    let mut prev_hash = JOURNAL_GENESIS_HASH;
+    let mut prev_timestamp = 0u64;
+
+    let key = VerifyingKey::from_sec1_bytes(journal_key.as_bytes()).ok();
+    if key.is_none() {
+        issues.push(JournalIssue {
+            sequence: 0,
+            reason: "Invalid journal public key".to_string(),
+        });
+    }
+
+    for (i, entry) in entries.iter().enumerate() {
+        let position = i as u64;
+        if entry.sequence != position {
+            issues.push(JournalIssue {
+                sequence: position,
+                reason: format!("Expected sequence {}, found {}", position, entry.sequence),
+            });
+        }
+        if entry.prev_hash != prev_hash {
+            issues.push(JournalIssue {
+                sequence: entry.sequence,
+                reason: "Broken hash chain".to_string(),
+            });
+        }
+        if entry.timestamp < prev_timestamp {
+            issues.push(JournalIssue {
+                sequence: entry.sequence,
+                reason: "Timestamp earlier than previous entry".to_string(),
+            });
+        }
+        if let Some(key) = &key {
+            if !entry.verify_signature(key) {
+                issues.push(JournalIssue {
+                    sequence: entry.sequence,
+                    reason: "Invalid signature".to_string(),
+                });
+            }
+        }
+
+        prev_hash = entry.entry_hash();
+        prev_timestamp = entry.timestamp;
+    }
+
+    JournalVerification {
+        entries: entries.len(),
+        head_hash: prev_hash,
+        last_timestamp: entries.last().map(|e| e.timestamp),
+        issues,
+    }
+}
+
+/// Append-only ceremony journal stored in the mother data directory
+pub struct CeremonyJournal {
+    /// Path to journal.jsonl
+    path: PathBuf,
+    /// Path to journal_key.pub
+    key_path: PathBuf,
+}
+
+impl CeremonyJournal {
+    /// Open the journal in the given data directory
+    pub fn new(base_path: &Path) -> Result<Self> {
+        fs::create_dir_all(base_path)?;
+        Ok(Self {
+            path: base_path.join(JOURNAL_FILE),
+            key_path: base_path.join(JOURNAL_KEY_FILE),
+        })
+    }
+
+    /// Journal public key recorded on first append
+    ///
+    /// Lets tools without access to the master shard (such as the TUI
+    /// reports screen) verify the journal.
+    pub fn recorded_public_key(&self) -> Result<Option<PublicKey>> {
+        if !self.key_path.exists() {
+            return Ok(None);
+        }
+        let content = fs::read_to_string(&self.key_path)?;
+        let bytes: [u8; 33] = hex::decode(content.trim())
+            .map_err(|e| MotherError::Storage(format!("Invalid journal key file: {}", e)))?
+            .try_into()
+            .map_err(|_| MotherError::Storage("Invalid journal key length".to_string()))?;
+        Ok(Some(PublicKey::new(bytes)))
+    }
+
+    /// Path to the journal file
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    /// Read all entries
+    pub fn entries(&self) -> Result<Vec<JournalEntry>> {
+        if !self.path.exists() {
+            return Ok(Vec::new());
+        }
+
+        let content = fs::read_to_string(&self.path)?;
+        content
+            .lines()
+            .filter(|line| !line.trim().is_empty())
+            .map(|line| serde_json::from_str(line).map_err(MotherError::from))
+            .collect()
+    }
+
+    /// Sign and append a ceremony record
+    ///
+    /// The entry is flushed to disk before returning.
+    pub fn append(
+        &self,
+        key: &SigningKey,
+        operator: &str,
+        ceremony: Ceremony,
+    ) -> Result<JournalEntry> {
+        if !self.key_path.exists() {
+            fs::write(
+                &self.key_path,
+                hex::encode(journal_public_key(key).as_bytes()),
+            )?;
+        }
+
+        let entries = self.entries()?;
+        let (sequence, prev_hash) = match entries.last() {
+            Some(last) => (last.sequence + 1, last.entry_hash()),
+            None => (0, JOURNAL_GENESIS_HASH),
+        };
+
+        let mut entry = JournalEntry {
+            sequence,
+            timestamp: unix_now(),
+            operator: operator.to_string(),
+            ceremony,
+            prev_hash,
+            signature: Signature::new([0u8; 64]),
+        };
+        let sig: K256Signature = key.sign(&entry.entry_hash());
+        entry.signature = Signature::new(sig.to_bytes().into());
+
+        let mut line = serde_json::to_string(&entry)?;
+        line.push('\n');
+
+        let mut file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        file.write_all(line.as_bytes())?;
+        file.sync_all()?;
+
+        Ok(entry)
+    }
+
+    /// Verify the journal against the journal public key
+    pub fn verify(&self, journal_key: &PublicKey) -> Result<JournalVerification> {
+        Ok(verify_entries(&self.entries()?, journal_key))
+    }
+
+    /// Export the journal for an external auditor
+    pub fn export(&self, journal_key: &PublicKey) -> Result<JournalExport> {
+        let entries = self.entries()?;
+        let head_hash = entries
+            .last()
+            .map(|e| e.entry_hash())
+            .unwrap_or(JOURNAL_GENESIS_HASH);
+
+        Ok(JournalExport {
+            version: JOURNAL_EXPORT_VERSION,
+            journal_pubkey: *journal_key,
+            exported_at: unix_now(),
+            head_hash,
+            entries,
+        })
+    }
+}
+
+/// Self-contained journal export, verifiable offline
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JournalExport {
+    /// Export format version
+    pub version: u32,
+
+    /// Mother journal public key
+    pub journal_pubkey: PublicKey,
+
+    /// Unix timestamp of the export
+    pub exported_at: u64,
+
+    /// Hash of the last entry at export time
+    #[serde(with = "hex_bytes_32")]
+    pub head_hash: [u8; 32],
+
+    /// All journal entries
+    pub entries: Vec<JournalEntry>,
+}
+
+impl JournalExport {
+    /// Default file name for an export
+    pub fn default_file_name() -> String {
+        format!(
+            "sigil_journal_{}.json",
+            chrono::Local::now().format("%Y-%m-%d")
+        )
+    }
+
+    /// Verify the export, optionally against a pinned journal key
+    ///
+    /// Auditors should pin the journal key recorded at mother setup rather
+    /// than trusting the key carried in the export.
+    pub fn verify(&self, pinned_key: Option<&PublicKey>) -> JournalVerification {
+        let key = pinned_key.unwrap_or(&self.journal_pubkey);
+        let mut result = verify_entries(&self.entries, key);
+
+        if self.version != JOURNAL_EXPORT_VERSION {
+            result.issues.push(JournalIssue {
+                sequence: 0,
+                reason: format!("Unsupported export version {}", self.version),
+            });
+        }
+        if pinned_key.is_some_and(|k| *k != self.journal_pubkey) {
+            result.issues.push(JournalIssue {
+                sequence: 0,
+                reason: "Export key does not match pinned journal key".to_string(),
+            });
+        }
+        if result.head_hash != self.head_hash {
+            result.issues.push(JournalIssue {
+                sequence: self.entries.len() as u64,
+                reason: "Head hash does not match entries (truncated export?)".to_string(),
+            });
+        }
+
+        result
+    }
+
+    /// Serialize to pretty JSON
+    pub fn to_json(&self) -> Result<String> {
+        Ok(serde_json::to_string_pretty(self)?)
+    }
+
+    /// Parse from JSON
+    pub fn from_json(json: &str) -> Result<Self> {
+        Ok(serde_json::from_str(json)?)
+    }
+}
+
+/// Current Unix timestamp in seconds
+fn unix_now() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap()
+        .as_secs()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_key() -> SigningKey {
+        journal_signing_key(&MasterShardData::new([9u8; 32], [2u8; 33])).unwrap()
+    }
+
+    fn populated_journal(dir: &Path, key: &SigningKey) -> CeremonyJournal {
+        let journal = CeremonyJournal::new(dir).unwrap();
+        journal
+            .append(
+                key,
+                "alice",
+                Ceremony::CreateChild {
+                    child_id: hex::encode([1u8; 32]),
+                    derivation_path: "m/44'/60'/0'/0'/0'".to_string(),
+                    presig_count: 100,
+                    child_pubkey: hex::encode([2u8; 33]),
+                },
+            )
+            .unwrap();
+        journal
+            .append(
+                key,
+                "alice,bob",
+                Ceremony::Nullify {
+                    child_id: hex::encode([1u8; 32]),
+                    reason: "lost disk".to_string(),
+                },
+            )
+            .unwrap();
+        journal
+    }
+
+    #[test]
+    fn test_journal_chain_verifies() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = test_key();
+        let journal = populated_journal(dir.path(), &key);
+
+        let recorded = journal.recorded_public_key().unwrap().unwrap();
+        assert_eq!(recorded, journal_public_key(&key));
+
+        let result = journal.verify(&recorded).unwrap();
+        assert!(result.is_valid(), "{:?}", result.issues);
+        assert_eq!(result.entries, 2);
+
+        let export = journal.export(&journal_public_key(&key)).unwrap();
+        let parsed = JournalExport::from_json(&export.to_json().unwrap()).unwrap();
+        assert!(parsed.verify(Some(&journal_public_key(&key))).is_valid());
+    }
+
+    #[test]
+    fn test_journal_tampering_detected() {
+        let dir = tempfile::tempdir().unwrap();
+        let key = test_key();
+        let journal = populated_journal(dir.path(), &key);
+        let pubkey = journal_public_key(&key);
+
+        // Edit an input of the first ceremony
+        let content = fs::read_to_string(journal.path()).unwrap();
+        fs::write(journal.path(), content.replacen("100", "200", 1)).unwrap();
+        let result = journal.verify(&pubkey).unwrap();
+        assert!(!result.is_valid());
+        assert_eq!(result.issues[0].sequence, 0);
+
+        // Dropping the first entry breaks the chain
+        let mut export = populated_journal(tempfile::tempdir().unwrap().path(), &key)
+            .export(&pubkey)
+            .unwrap();
+        export.entries.remove(0);
+        assert!(!export.verify(Some(&pubkey)).is_valid());
+
+        // A different mother key is rejected
+        let other = journal_signing_key(&MasterShardData::new([8u8; 32], [2u8; 33])).unwrap();
+        let export = populated_journal(tempfile::tempdir().unwrap().path(), &key)
+            .export(&pubkey)
+            .unwrap();
+        assert!(!export.verify(Some(&journal_public_key(&other))).is_valid());
+    }
+}
@@ -6,6 +6,8 @@
 //! - Presignature generation
 //! - Reconciliation and refill
 //! - Nullification
+//! - Signed ceremony journal
+//! - Encrypted migration bundles for replacing a mother device
 //! - Agent registry and management
 //!
 //! # Security Model
@@ -35,8 +37,10 @@ pub mod disk_ops;
 pub mod error;
 #[cfg(any(feature = "ledger", feature = "trezor", feature = "pkcs11"))]
 pub mod hardware;
+pub mod journal;
 pub mod keygen;
 pub mod ledger; // Backwards compatibility re-export
+pub mod migration;
 pub mod nullification;
 pub mod presig_gen;
 pub mod reconciliation;
@@ -52,10 +56,13 @@ pub use agent_shard_encryption::{
     Passcode, ENCRYPTED_SHARD_PREFIX,
 };
 pub use auth::{
-    AuthError, AuthState, EncryptedMotherStorage, LockoutPolicy, PinConfig, PinManager, Session,
-    SessionConfig, MAX_PIN_LENGTH, MIN_PIN_LENGTH,
+    AuthError, AuthState, DuressAction, EncryptedMotherStorage, LockoutPolicy, PinConfig,
+    PinManager, QuorumAction, QuorumApproval, QuorumManager, QuorumSession, Session, SessionConfig,
+    StorageProfile, MAX_PIN_LENGTH, MIN_PIN_LENGTH,
+};
+pub use ceremony::{
+    CreateChildCeremony, ReconcileCeremony, ReconciliationRecommendation, RefillCeremony,
 };
-pub use ceremony::{CreateChildCeremony, ReconcileCeremony, RefillCeremony};
 pub use disk_ops::{
     get_device_info, get_mount_point, list_all_block_devices, list_removable_devices, BlockDevice,
     DiskStatus, FloppyManager, FormatType, MountMethod, FLOPPY_SIZE_144MB, FLOPPY_SIZE_TOLERANCE,
@@ -63,7 +70,9 @@ pub use disk_ops::{
 pub use error::{MotherError, Result};
 #[cfg(any(feature = "ledger", feature = "trezor", feature = "pkcs11"))]
 pub use hardware::HardwareSigner;
+pub use journal::{Ceremony, CeremonyJournal, JournalEntry, JournalExport, JournalVerification};
 pub use keygen::MasterKeyGenerator;
+pub use migration::{MigrationBundle, MigrationManifest, MigrationPayload};
 pub use nullification::{NullificationManager, NullificationResult};
 pub use presig_gen::PresigGenerator;
 pub use registry::ChildRegistry;
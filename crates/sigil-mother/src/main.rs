@@ -1,13 +1,24 @@
 //! Sigil Mother - Air-gapped mother device CLI
 
 use clap::{Parser, Subcommand};
-use std::path::PathBuf;
+use std::io::{self, BufRead, Write};
+use std::path::{Path, PathBuf};
 use tracing::{error, info, warn};
 use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
 
 use sigil_mother::{
+    accumulator_publish::AccumulatorPublisher,
+    auth::{
+        AuthError, EncryptedMotherStorage, PinConfig, PinManager, QuorumAction, QuorumManager,
+        SessionConfig, StorageProfile,
+    },
     ceremony::{CreateChildCeremony, ReconcileCeremony, RefillCeremony},
+    disk_ops::{FloppyManager, DEFAULT_MOUNT_POINT},
+    journal::{self, Ceremony, CeremonyJournal, JournalExport},
     keygen::MasterKeyGenerator,
+    migration::{
+        MigrationBundle, MigrationPayload, MIGRATION_FLOPPY_PART_CHARS, MIGRATION_QR_PART_CHARS,
+    },
     reconciliation,
     storage::MotherStorage,
 };
@@ -30,6 +41,10 @@ struct Cli {
     /// Path to mother device storage
     #[arg(long, default_value = "./sigil_mother_data")]
     data_dir: PathBuf,
+
+    /// Operator performing the ceremony (recorded in the journal)
+    #[arg(long, global = true, default_value = "local")]
+    operator: String,
 }
 
 #[derive(Subcommand)]
@@ -58,6 +73,10 @@ enum Commands {
         #[arg(long)]
         output: PathBuf,
 
+        /// Treat the output as a raw block device (no filesystem)
+        #[arg(long)]
+        raw: bool,
+
         /// Output path for agent shares (JSON)
         #[arg(long)]
         agent_output: PathBuf,
@@ -76,6 +95,25 @@ enum Commands {
         /// Path to disk image
         #[arg(long)]
         disk: PathBuf,
+
+        /// Treat the disk path as a raw block device (no filesystem)
+        #[arg(long)]
+        raw: bool,
+
+        /// Agent audit bundle (JSON export or QR payload lines)
+        #[arg(long)]
+        audit_bundle: Option<PathBuf>,
+    },
+
+    /// Pin the agent audit key that reconciliation bundles must be signed with
+    PinAuditKey {
+        /// Compressed audit public key (hex), as printed by `sigil export-audit`
+        #[arg(long)]
+        key: String,
+
+        /// Replace an already pinned key
+        #[arg(long)]
+        replace: bool,
     },
 
     /// Refill a child disk after reconciliation
@@ -84,6 +122,10 @@ enum Commands {
         #[arg(long)]
         disk: PathBuf,
 
+        /// Treat the disk path as a raw block device (no filesystem)
+        #[arg(long)]
+        raw: bool,
+
         /// Number of presignatures to generate
         #[arg(long, default_value = "1000")]
         presig_count: u32,
@@ -110,6 +152,107 @@ enum Commands {
         #[arg(long)]
         output: PathBuf,
     },
+
+    /// Enable M-of-N operator quorum for sensitive ceremonies
+    QuorumInit {
+        /// Number of operators required (M)
+        #[arg(long)]
+        threshold: u8,
+
+        /// Operator ID (repeat once per operator, N total)
+        #[arg(long = "member", required = true)]
+        operators: Vec<String>,
+    },
+
+    /// Show operator quorum configuration
+    QuorumStatus,
+
+    /// Change the operator quorum (requires the current quorum)
+    QuorumChange {
+        /// New number of operators required (M)
+        #[arg(long)]
+        threshold: u8,
+
+        /// Operator ID (repeat once per operator, N total)
+        #[arg(long = "member", required = true)]
+        operators: Vec<String>,
+    },
+
+    /// Publish the signed agent accumulator for distribution to agents
+    PublishAccumulator {
+        /// Output path for the accumulator export
+        #[arg(long)]
+        output: PathBuf,
+    },
+
+    /// Change the mother PIN and re-key the storage encryption key
+    ChangePin,
+
+    /// Verify the signed ceremony journal
+    VerifyJournal {
+        /// Verify an exported journal instead of the local one
+        #[arg(long)]
+        file: Option<PathBuf>,
+
+        /// Pinned journal public key (hex) to verify an export against
+        #[arg(long)]
+        pubkey: Option<String>,
+    },
+
+    /// Export the ceremony journal for an external auditor
+    ExportJournal {
+        /// Output path for the export
+        #[arg(long)]
+        output: Option<PathBuf>,
+    },
+
+    /// Export an encrypted migration bundle and retire this device
+    ExportMigration {
+        /// Directory to write the bundle parts to
+        #[arg(long)]
+        output_dir: PathBuf,
+
+        /// Split into parts small enough for QR codes
+        #[arg(long)]
+        qr: bool,
+
+        /// Maximum characters per part (overrides --qr)
+        #[arg(long)]
+        part_size: Option<usize>,
+
+        /// Reason for retiring this device
+        #[arg(long, default_value = "Migrated to replacement device")]
+        reason: String,
+    },
+
+    /// Import a migration bundle onto this (uninitialized) device
+    ImportMigration {
+        /// Bundle part files (in any order)
+        #[arg(required = true)]
+        parts: Vec<PathBuf>,
+    },
+}
+
+impl Commands {
+    /// Whether the command changes mother state (refused once retired)
+    fn mutates_state(&self) -> bool {
+        matches!(
+            self,
+            Commands::Init { .. }
+                | Commands::CreateChild { .. }
+                | Commands::Reconcile { .. }
+                | Commands::PinAuditKey { .. }
+                | Commands::Refill { .. }
+                | Commands::Nullify { .. }
+                | Commands::ExportAgentShard { .. }
+                | Commands::QuorumInit { .. }
+                | Commands::QuorumChange { .. }
+                | Commands::PublishAccumulator { .. }
+                | Commands::ChangePin
+                | Commands::ExportMigration { .. }
+                | Commands::ImportMigration { .. }
+        )
+    }
 }
 
 #[tokio::main]
@@ -126,6 +269,12 @@ async fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
     let storage = MotherStorage::new(cli.data_dir.clone())?;
 
+    if storage.is_retired() && cli.command.mutates_state() {
+        error!("This mother device has been retired after a migration export.");
+        error!("Use the replacement device; only read-only commands are available here.");
+        return Ok(());
+    }
+
     match cli.command {
         Commands::Init { ledger } => {
             if storage.has_master_shard() {
@@ -148,6 +297,14 @@ async fn main() -> anyhow::Result<()> {
                     );
 
                     storage.save_master_shard(&cold_shard)?;
+                    record_ceremony(
+                        &storage,
+                        &cli.operator,
+                        Ceremony::Init {
+                            master_pubkey: hex::encode(output.master_pubkey.as_bytes()),
+                            ledger: true,
+                        },
+                    )?;
 
                     println!("\n=== Master Key Generated (Ledger) ===\n");
                     println!(
@@ -178,6 +335,14 @@ async fn main() -> anyhow::Result<()> {
                 let output = MasterKeyGenerator::generate()?;
 
                 storage.save_master_shard(&output.cold_master_shard)?;
+                record_ceremony(
+                    &storage,
+                    &cli.operator,
+                    Ceremony::Init {
+                        master_pubkey: hex::encode(output.master_pubkey.as_bytes()),
+                        ledger: false,
+                    },
+                )?;
 
                 println!("\n=== Master Key Generated ===\n");
                 println!(
@@ -246,6 +411,7 @@ async fn main() -> anyhow::Result<()> {
                 return Ok(());
             }
 
+            let storage = unlock_storage(storage)?;
             let master = storage.load_master_shard()?;
             let registry = storage.load_registry()?;
             let (active, suspended, nullified) = registry.count_by_status();
@@ -271,9 +437,13 @@ async fn main() -> anyhow::Result<()> {
         Commands::CreateChild {
             presig_count,
             output,
+            raw,
             agent_output,
             agent_shard,
         } => {
+            let (storage, operator) = require_quorum(storage, QuorumAction::CreateChild)?;
+            let operator = operator.unwrap_or_else(|| cli.operator.clone());
+
             info!("Creating new child disk with {} presigs...", presig_count);
 
             // Parse agent shard if provided
@@ -284,12 +454,15 @@ async fn main() -> anyhow::Result<()> {
                 bytes
             });
 
-            let mut ceremony = CreateChildCeremony::new(storage);
+            let mut ceremony = CreateChildCeremony::new(storage.clone());
             let result = ceremony.execute_with_agent_shard(presig_count, agent_shard_bytes)?;
 
             // Write disk image
-            let disk_bytes = result.disk.to_bytes();
-            std::fs::write(&output, &disk_bytes)?;
+            if raw {
+                raw_device(&output)?.write_raw(&result.disk)?;
+            } else {
+                std::fs::write(&output, result.disk.to_bytes())?;
+            }
             info!("Disk image written to {:?}", output);
 
             // Write agent shares
@@ -297,6 +470,17 @@ async fn main() -> anyhow::Result<()> {
             std::fs::write(&agent_output, &agent_json)?;
             info!("Agent shares written to {:?}", agent_output);
 
+            record_ceremony(
+                &storage,
+                &operator,
+                Ceremony::CreateChild {
+                    child_id: result.child_id.to_hex(),
+                    derivation_path: result.derivation_path.to_string_path(),
+                    presig_count,
+                    child_pubkey: hex::encode(result.child_pubkey.as_bytes()),
+                },
+            )?;
+
             println!("\n=== Child Created ===\n");
             println!("Child ID: {}", result.child_id.short());
             println!(
@@ -343,11 +527,27 @@ async fn main() -> anyhow::Result<()> {
             }
         }
 
-        Commands::Reconcile { disk } => {
+        Commands::Reconcile {
+            disk,
+            raw,
+            audit_bundle,
+        } => {
             info!("Loading disk from {:?}...", disk);
 
-            let disk_bytes = std::fs::read(&disk)?;
-            let disk_format = sigil_core::DiskFormat::from_bytes(&disk_bytes)?;
+            let disk_format = if raw {
+                raw_device(&disk)?.read_raw()?
+            } else {
+                sigil_core::DiskFormat::from_bytes(&std::fs::read(&disk)?)?
+            };
+
+            let bundle = match audit_bundle {
+                Some(path) => {
+                    info!("Loading agent audit bundle from {:?}...", path);
+                    let content = std::fs::read_to_string(&path)?;
+                    Some(reconciliation::parse_audit_bundle(&content)?)
+                }
+                None => None,
+            };
 
             // Run analysis
             let analysis = reconciliation::analyze_disk(&disk_format);
@@ -355,28 +555,91 @@ async fn main() -> anyhow::Result<()> {
             println!("{}", report);
 
             // Run ceremony
-            let mut ceremony = ReconcileCeremony::new(storage);
-            let result = ceremony.execute(&disk_format)?;
+            let storage = unlock_storage(storage)?;
+            let mut ceremony = ReconcileCeremony::new(storage.clone());
+            let result = ceremony.execute_with_audit(&disk_format, bundle.as_ref())?;
+
+            if bundle.is_some() {
+                if result.audit_anomalies.is_empty() {
+                    println!("✓ Agent audit log matches disk usage log");
+                } else {
+                    println!(
+                        "✗ {} audit divergences detected:",
+                        result.audit_anomalies.len()
+                    );
+                    for anomaly in &result.audit_anomalies {
+                        println!("  [{:?}] {:?}", anomaly.severity(), anomaly);
+                    }
+                }
+            }
+
+            record_ceremony(
+                &storage,
+                &cli.operator,
+                Ceremony::Reconcile {
+                    child_id: disk_format.header.child_id.to_hex(),
+                    log_entries: analysis.log_entries,
+                    anomalies: analysis.anomalies.len() + result.audit_anomalies.len(),
+                    recommendation: format!("{:?}", result.recommendation),
+                },
+            )?;
 
             println!("\nRecommendation: {:?}", result.recommendation);
         }
 
+        Commands::PinAuditKey { key, replace } => {
+            let key = sigil_core::PublicKey::from_hex(key.trim())?;
+
+            if let Some(pinned) = storage.load_audit_key()? {
+                if pinned == key {
+                    println!("Audit key {} is already pinned", key.to_hex());
+                    return Ok(());
+                }
+                if !replace {
+                    anyhow::bail!(
+                        "Audit key {} is already pinned. Use --replace to change it.",
+                        pinned.to_hex()
+                    );
+                }
+            }
+
+            let storage = unlock_storage(storage)?;
+            storage.pin_audit_key(&key)?;
+            record_ceremony(
+                &storage,
+                &cli.operator,
+                Ceremony::PinAuditKey {
+                    auditor_pubkey: key.to_hex(),
+                },
+            )?;
+
+            println!("✓ Pinned agent audit key {}", key.to_hex());
+        }
+
         Commands::Refill {
             disk,
+            raw,
             presig_count,
             agent_output,
         } => {
             info!("Loading disk from {:?}...", disk);
 
-            let disk_bytes = std::fs::read(&disk)?;
-            let mut disk_format = sigil_core::DiskFormat::from_bytes(&disk_bytes)?;
+            let mut disk_format = if raw {
+                raw_device(&disk)?.read_raw()?
+            } else {
+                sigil_core::DiskFormat::from_bytes(&std::fs::read(&disk)?)?
+            };
 
-            let mut ceremony = RefillCeremony::new(storage);
+            let storage = unlock_storage(storage)?;
+            let mut ceremony = RefillCeremony::new(storage.clone());
             let agent_shares = ceremony.execute(&mut disk_format, presig_count)?;
 
             // Write updated disk
-            let updated_bytes = disk_format.to_bytes();
-            std::fs::write(&disk, &updated_bytes)?;
+            if raw {
+                raw_device(&disk)?.write_raw(&disk_format)?;
+            } else {
+                std::fs::write(&disk, disk_format.to_bytes())?;
+            }
             info!("Disk updated at {:?}", disk);
 
             // Write new agent shares
@@ -384,12 +647,24 @@ async fn main() -> anyhow::Result<()> {
             std::fs::write(&agent_output, &agent_json)?;
             info!("New agent shares written to {:?}", agent_output);
 
+            record_ceremony(
+                &storage,
+                &cli.operator,
+                Ceremony::Refill {
+                    child_id: disk_format.header.child_id.to_hex(),
+                    presig_count,
+                },
+            )?;
+
             println!("\n=== Refill Complete ===\n");
             println!("New presigs: {}", presig_count);
             println!("Agent shares: {:?}", agent_output);
         }
 
         Commands::Nullify { child_id, reason } => {
+            let (storage, operator) = require_quorum(storage, QuorumAction::Nullify)?;
+            let operator = operator.unwrap_or_else(|| cli.operator.clone());
+
             let mut registry = storage.load_registry()?;
 
             // Find child by short ID
@@ -414,6 +689,14 @@ async fn main() -> anyhow::Result<()> {
             )?;
 
             storage.save_registry(&registry)?;
+            record_ceremony(
+                &storage,
+                &operator,
+                Ceremony::Nullify {
+                    child_id: child_id.to_hex(),
+                    reason: reason.clone(),
+                },
+            )?;
 
             warn!("Child {} has been NULLIFIED", child_id.short());
             warn!("Reason: {}", reason);
@@ -426,7 +709,11 @@ async fn main() -> anyhow::Result<()> {
             );
             warn!("⚠️  The agent shard gives the agent partial signing capability.");
 
+            let (storage, operator) = require_quorum(storage, QuorumAction::ExportAgentShard)?;
+            let operator = operator.unwrap_or_else(|| cli.operator.clone());
+
             let _master = storage.load_master_shard()?;
+            record_ceremony(&storage, &operator, Ceremony::ExportAgentShard)?;
 
             // In a real implementation, we would derive and export the agent shard
             // For this demo, we show a warning
@@ -434,7 +721,468 @@ async fn main() -> anyhow::Result<()> {
             println!("In production, the agent shard would be generated during 'init'");
             println!("and must be securely transferred to the agent device.");
         }
+
+        Commands::QuorumInit {
+            threshold,
+            operators,
+        } => {
+            let mut quorum = QuorumManager::new(&cli.data_dir)?;
+            if quorum.is_configured() {
+                error!("Operator quorum already configured. Use 'quorum-change' instead.");
+                return Ok(());
+            }
+
+            // Split the real PIN's storage key, so encrypted storage remains
+            // readable under quorum. The duress PIN is refused like a wrong
+            // PIN. The PIN storage also records that quorum mode is on, so a
+            // deleted quorum.json fails closed.
+            let mut pin_manager = pin_manager(&cli.data_dir)?;
+            let pin = if pin_manager.is_pin_set() {
+                prompt_secret("Current mother PIN: ")?
+            } else {
+                println!("No mother PIN is set. Choose one to enable quorum mode.");
+                let pin = prompt_new_pin()?;
+                pin_manager.set_pin(&pin)?;
+                pin
+            };
+            let storage_key = pin_manager.require_primary(&pin)?;
+
+            let credentials = prompt_operator_pins(&operators)?;
+            quorum.initialize(&storage_key, threshold, &credentials)?;
+            pin_manager.enable_quorum(&pin)?;
+
+            // Only the storage key opens the master shard from now on
+            let storage = storage.seal_master_shard(&storage_key)?;
+            if storage.has_master_shard() {
+                record_ceremony(
+                    &storage,
+                    &cli.operator,
+                    Ceremony::QuorumChange {
+                        generation: quorum.generation().unwrap_or(0),
+                        threshold,
+                        operators: operators.clone(),
+                    },
+                )?;
+            }
+
+            println!("\n=== Operator Quorum Enabled ===\n");
+            println!("Threshold: {} of {}", threshold, operators.len());
+            println!("Operators: {}", operators.join(", "));
+        }
+
+        Commands::QuorumStatus => {
+            let quorum = QuorumManager::new(&cli.data_dir)?;
+            let quorum_required = pin_manager(&cli.data_dir)?.quorum_required();
+
+            println!("\n=== Operator Quorum ===\n");
+            match quorum.threshold() {
+                Some(threshold) => {
+                    let operators = quorum.operator_ids();
+                    println!("Threshold: {} of {}", threshold, operators.len());
+                    println!("Generation: {}", quorum.generation().unwrap_or(0));
+                    println!("Operators: {}", operators.join(", "));
+                }
+                None if quorum_required => {
+                    println!("MISSING: quorum mode is enabled but quorum.json is gone.");
+                    println!("Gated ceremonies are blocked until it is restored.");
+                }
+                None => println!("Not configured (single-PIN mode)"),
+            }
+        }
+
+        Commands::QuorumChange {
+            threshold,
+            operators,
+        } => {
+            let mut quorum = QuorumManager::new(&cli.data_dir)?;
+            let session = collect_quorum(&mut quorum, QuorumAction::ChangeQuorum)?;
+
+            println!("\nQuorum met. Enter PINs for the new operator set.");
+            let credentials = prompt_operator_pins(&operators)?;
+            quorum.change_quorum(&session, threshold, &credentials)?;
+            let storage = storage.seal_master_shard(session.open_session()?.encryption_key())?;
+            if storage.has_master_shard() {
+                record_ceremony(
+                    &storage,
+                    &session.approvers().join(","),
+                    Ceremony::QuorumChange {
+                        generation: quorum.generation().unwrap_or(0),
+                        threshold,
+                        operators: operators.clone(),
+                    },
+                )?;
+            }
+
+            println!("\n=== Operator Quorum Updated ===\n");
+            println!("Threshold: {} of {}", threshold, operators.len());
+            println!("Generation: {}", quorum.generation().unwrap_or(0));
+        }
+
+        Commands::PublishAccumulator { output } => {
+            let (storage, operator) = require_quorum(storage, QuorumAction::PublishAccumulator)?;
+            let operator = operator.unwrap_or_else(|| cli.operator.clone());
+
+            let Some(registry) = storage.load_agent_registry()? else {
+                error!("No agents registered. Nothing to publish.");
+                return Ok(());
+            };
+            let master = storage.load_master_shard()?;
+            let accumulator = registry.get_accumulator();
+            let export_hash = AccumulatorPublisher::new(master.cold_master_shard)
+                .export_to_file(accumulator, &output)?;
+            record_ceremony(
+                &storage,
+                &operator,
+                Ceremony::AccumulatorExport {
+                    accumulator_version: accumulator.version,
+                    export_hash: hex::encode(export_hash),
+                },
+            )?;
+
+            println!("\n=== Accumulator Published ===\n");
+            println!("Version: {}", accumulator.version);
+            println!("Nullified agents: {}", registry.nullified_agents().len());
+            println!("Output: {:?}", output);
+        }
+
+        Commands::ChangePin => {
+            let mut pin_manager = pin_manager(&cli.data_dir)?;
+            if !pin_manager.is_pin_set() {
+                error!("No mother PIN is set.");
+                return Ok(());
+            }
+
+            let current_pin = prompt_secret("Current mother PIN: ")?;
+            let unlocked = pin_manager.unlock(&current_pin)?;
+            let primary = unlocked.profile == StorageProfile::Primary;
+
+            // A new PIN derives a new storage key, which the quorum must
+            // re-split. Under duress only the decoy profile is re-keyed, but
+            // it looks the same.
+            let mut quorum = QuorumManager::new(&cli.data_dir)?;
+            let session = if primary && quorum.requires_approval(&pin_manager)? {
+                Some(collect_quorum(&mut quorum, QuorumAction::MasterRekey)?)
+            } else {
+                None
+            };
+
+            let encrypted =
+                EncryptedMotherStorage::for_profile(cli.data_dir.clone(), unlocked.profile)?;
+            let shard = if encrypted.has_master_shard() {
+                Some(encrypted.load_master_shard_with_key(&unlocked.encryption_key)?)
+            } else {
+                None
+            };
+
+            let new_pin = prompt_new_pin()?;
+            let operator_pins = match &session {
+                Some(_) => {
+                    println!("\nEnter every operator's PIN to re-split the new storage key.");
+                    prompt_operator_pins(&quorum.operator_ids())?
+                }
+                None => Vec::new(),
+            };
+
+            // Re-split the quorum first: it checks every operator PIN, and a
+            // failure there must leave the old PIN in place. The new PIN is
+            // only saved once everything is wrapped under its key.
+            let change = pin_manager.prepare_pin_change(&current_pin, &new_pin)?;
+            let new_key = *change.encryption_key();
+            if let Some(session) = &session {
+                quorum.rekey(session, &new_key, &operator_pins)?;
+            }
+            if let Some(shard) = &shard {
+                encrypted.save_master_shard(shard, &new_key)?;
+            }
+            pin_manager.commit_pin_change(change)?;
+            if primary && storage.has_master_shard() {
+                let storage = storage.with_shard_key(&new_key);
+                let operator = session
+                    .as_ref()
+                    .map(|s| s.approvers().join(","))
+                    .unwrap_or_else(|| cli.operator.clone());
+                record_ceremony(&storage, &operator, Ceremony::PinChange)?;
+            }
+
+            println!("✓ Mother PIN changed");
+            if session.is_some() {
+                println!("Quorum generation: {}", quorum.generation().unwrap_or(0));
+            }
+        }
+
+        Commands::VerifyJournal { file, pubkey } => {
+            let pinned = match pubkey {
+                Some(hex_key) => {
+                    let bytes = hex::decode(hex_key.strip_prefix("0x").unwrap_or(&hex_key))?;
+                    Some(sigil_core::PublicKey::new(bytes.try_into().map_err(
+                        |_| anyhow::anyhow!("Journal public key must be 33 bytes"),
+                    )?))
+                }
+                None => None,
+            };
+
+            let result = match file {
+                Some(path) => {
+                    let export = JournalExport::from_json(&std::fs::read_to_string(&path)?)?;
+                    if pinned.is_none() {
+                        warn!("No --pubkey given: trusting the key embedded in the export");
+                    }
+                    export.verify(pinned.as_ref())
+                }
+                None => {
+                    let storage = unlock_storage(storage)?;
+                    let key = journal::journal_signing_key(&storage.load_master_shard()?)?;
+                    CeremonyJournal::new(&cli.data_dir)?
+                        .verify(&journal::journal_public_key(&key))?
+                }
+            };
+
+            println!("\n=== Ceremony Journal ===\n");
+            println!("Entries: {}", result.entries);
+            println!("Head: {}", hex::encode(result.head_hash));
+            if result.is_valid() {
+                println!("\n✓ Journal intact");
+            } else {
+                println!("\n✗ {} problem(s) found:", result.issues.len());
+                for issue in &result.issues {
+                    println!("  #{}: {}", issue.sequence, issue.reason);
+                }
+                std::process::exit(1);
+            }
+        }
+
+        Commands::ExportJournal { output } => {
+            let (storage, _) = require_quorum(storage, QuorumAction::ExportJournal)?;
+
+            let key = journal::journal_signing_key(&storage.load_master_shard()?)?;
+            let pubkey = journal::journal_public_key(&key);
+            let export = CeremonyJournal::new(&cli.data_dir)?.export(&pubkey)?;
+
+            let output =
+                output.unwrap_or_else(|| PathBuf::from(JournalExport::default_file_name()));
+            std::fs::write(&output, export.to_json()?)?;
+
+            println!("\n=== Journal Exported ===\n");
+            println!("Entries: {}", export.entries.len());
+            println!("Journal public key: {}", hex::encode(pubkey.as_bytes()));
+            println!("Output: {:?}", output);
+        }
+
+        Commands::ExportMigration {
+            output_dir,
+            qr,
+            part_size,
+            reason,
+        } => {
+            warn!("⚠️  DANGER: Exporting the complete mother state, including the master shard.");
+            warn!("⚠️  This device will be RETIRED and refuse further ceremonies.");
+
+            let (storage, operator) = require_quorum(storage, QuorumAction::MigrateDevice)?;
+            let operator = operator.unwrap_or_else(|| cli.operator.clone());
+
+            let payload = MigrationPayload::collect(&storage)?;
+            let (bundle, passcode) = MigrationBundle::seal(&payload)?;
+            let part_size = part_size.unwrap_or(if qr {
+                MIGRATION_QR_PART_CHARS
+            } else {
+                MIGRATION_FLOPPY_PART_CHARS
+            });
+            let parts = bundle.to_parts(part_size)?;
+
+            std::fs::create_dir_all(&output_dir)?;
+            for (i, part) in parts.iter().enumerate() {
+                let path = output_dir.join(format!("sigil_migration_{:03}.txt", i + 1));
+                std::fs::write(&path, part)?;
+            }
+
+            let bundle_hash = hex::encode(bundle.bundle_hash()?);
+            record_ceremony(
+                &storage,
+                &operator,
+                Ceremony::MigrationExport {
+                    bundle_hash: bundle_hash.clone(),
+                    reason: reason.clone(),
+                },
+            )?;
+            storage.mark_retired(&reason)?;
+
+            println!("\n=== Migration Bundle Exported ===\n");
+            println!("Master pubkey: 0x{}", bundle.manifest.master_pubkey);
+            println!("Children: {}", bundle.manifest.child_count);
+            println!("Agents: {}", bundle.manifest.agent_count);
+            println!("Parts: {} in {:?}", parts.len(), output_dir);
+            println!("Bundle hash: {}", bundle_hash);
+            println!("\nPasscode: {}", passcode.display_formatted());
+            println!("\n⚠️  Transfer the passcode separately from the bundle parts.");
+            println!("⚠️  This device is now retired.");
+        }
+
+        Commands::ImportMigration { parts } => {
+            if storage.has_master_shard() {
+                error!("Master shard already exists. Refusing to import over it.");
+                return Ok(());
+            }
+
+            let mut lines = Vec::new();
+            for path in &parts {
+                let content = std::fs::read_to_string(path)?;
+                lines.extend(
+                    content
+                        .lines()
+                        .filter(|l| !l.trim().is_empty())
+                        .map(str::to_string),
+                );
+            }
+            let bundle = MigrationBundle::from_parts(&lines)?;
+
+            println!("\n=== Migration Bundle ===\n");
+            println!("Master pubkey: 0x{}", bundle.manifest.master_pubkey);
+            println!("Children: {}", bundle.manifest.child_count);
+            println!("Agents: {}", bundle.manifest.agent_count);
+            if let Some(version) = bundle.manifest.accumulator_version {
+                println!("Accumulator version: {}", version);
+            }
+
+            let passcode = prompt_secret("\nPasscode: ")?;
+            let payload = bundle.open(&passcode)?;
+            payload.install(&storage)?;
+
+            record_ceremony(
+                &storage,
+                &cli.operator,
+                Ceremony::MigrationImport {
+                    bundle_hash: hex::encode(bundle.bundle_hash()?),
+                    exported_at: bundle.manifest.created_at,
+                },
+            )?;
+
+            println!("\n✓ Bundle verified and imported");
+            println!("Run 'verify-journal' to confirm the ceremony history.");
+        }
     }
 
     Ok(())
 }
+
+/// PIN manager for the mother PIN kept in the data directory
+fn pin_manager(data_dir: &Path) -> anyhow::Result<PinManager> {
+    Ok(PinManager::with_config(PinConfig::with_path(
+        data_dir.join("auth.json"),
+    ))?)
+}
+
+/// Floppy manager addressing `path` as a raw block device
+fn raw_device(path: &Path) -> anyhow::Result<FloppyManager> {
+    let device = path
+        .to_str()
+        .ok_or_else(|| anyhow::anyhow!("Device path is not valid UTF-8: {:?}", path))?;
+    Ok(FloppyManager::with_paths(device, DEFAULT_MOUNT_POINT))
+}
+
+/// Sign and append a ceremony record to the journal
+fn record_ceremony(
+    storage: &MotherStorage,
+    operator: &str,
+    ceremony: Ceremony,
+) -> anyhow::Result<()> {
+    let key = journal::journal_signing_key(&storage.load_master_shard()?)?;
+    let entry = CeremonyJournal::new(storage.base_path())?.append(&key, operator, ceremony)?;
+    info!(
+        "Journal entry #{} recorded ({})",
+        entry.sequence,
+        entry.ceremony.name()
+    );
+    Ok(())
+}
+
+/// Read a line from stdin after printing a prompt
+fn prompt(label: &str) -> anyhow::Result<String> {
+    print!("{}", label);
+    io::stdout().flush()?;
+    let mut line = String::new();
+    io::stdin().lock().read_line(&mut line)?;
+    Ok(line.trim().to_string())
+}
+
+/// Read a PIN or passcode without echoing it to the terminal
+fn prompt_secret(label: &str) -> anyhow::Result<String> {
+    Ok(rpassword::prompt_password(label)?.trim().to_string())
+}
+
+/// Prompt for a new PIN twice
+fn prompt_new_pin() -> anyhow::Result<String> {
+    let pin = prompt_secret("New PIN: ")?;
+    if prompt_secret("Confirm new PIN: ")? != pin {
+        return Err(AuthError::PinMismatch.into());
+    }
+    Ok(pin)
+}
+
+/// Prompt for a PIN for each operator
+fn prompt_operator_pins(operators: &[String]) -> anyhow::Result<Vec<(String, String)>> {
+    operators
+        .iter()
+        .map(|id| {
+            let pin = prompt_secret(&format!("PIN for operator {}: ", id))?;
+            Ok((id.clone(), pin))
+        })
+        .collect()
+}
+
+/// Collect operator approvals until the quorum is met
+fn collect_quorum(
+    quorum: &mut QuorumManager,
+    action: QuorumAction,
+) -> anyhow::Result<sigil_mother::QuorumSession> {
+    let mut session = quorum.begin_session(SessionConfig::default())?;
+
+    println!("\n=== Operator Quorum Required: {:?} ===\n", action);
+    while !session.is_satisfied() {
+        println!("{} more approval(s) needed", session.approvals_needed());
+        let operator_id = prompt("Operator ID: ")?;
+        let pin = prompt_secret("PIN: ")?;
+        match quorum.approve(&mut session, &operator_id, &pin) {
+            Ok(()) => println!("✓ Approved by {}", operator_id),
+            Err(
+                e @ (AuthError::IncorrectPin(_)
+                | AuthError::UnknownOperator(_)
+                | AuthError::DuplicateApproval(_)
+                | AuthError::InvalidPinFormat),
+            ) => warn!("{}", e),
+            Err(e) => return Err(e.into()),
+        }
+    }
+
+    let approval = session.authorize(action)?;
+    info!("{:?} approved by {}", action, approval.approvers.join(", "));
+    Ok(session)
+}
+
+/// Gate a sensitive ceremony on the operator quorum, if one is configured
+///
+/// The storage key the quorum reconstructs opens the sealed master shard
+/// (sealing it first on devices that enabled quorum before sealing existed).
+/// Returns the approving operators when a quorum was collected.
+fn require_quorum(
+    storage: MotherStorage,
+    action: QuorumAction,
+) -> anyhow::Result<(MotherStorage, Option<String>)> {
+    let data_dir = storage.base_path().clone();
+    let mut quorum = QuorumManager::new(&data_dir)?;
+    if !quorum.requires_approval(&pin_manager(&data_dir)?)? {
+        return Ok((unlock_storage(storage)?, None));
+    }
+    let session = collect_quorum(&mut quorum, action)?;
+    let storage = storage.seal_master_shard(session.open_session()?.encryption_key())?;
+    Ok((storage, Some(session.approvers().join(","))))
+}
+
+/// Unlock a sealed master shard with the mother PIN
+fn unlock_storage(storage: MotherStorage) -> anyhow::Result<MotherStorage> {
+    if !storage.is_sealed() {
+        return Ok(storage);
+    }
+    let pin = prompt_secret("Mother PIN: ")?;
+    let key = pin_manager(storage.base_path())?.require_primary(&pin)?;
+    Ok(storage.with_shard_key(&key))
+}
@@ -0,0 +1,624 @@
+//! Mother device migration bundles
+//!
+//! Moves a mother's complete state (master shard, child and agent
+//! registries, accumulator, zkVM proofs and ceremony journal) to new
+//! hardware as a single encrypted bundle.
+//!
+//! The bundle is encrypted with ChaCha20-Poly1305 under a key derived from
+//! a generated passcode via Argon2id, exactly like agent shard exports. The
+//! plaintext manifest is bound to the ciphertext as associated data, so it
+//! cannot be altered without failing decryption.
+//!
+//! Large bundles are split into numbered parts for removable media or QR
+//! sequences: `SIGIL:MIGRATE:1:NNN/MMM:<base64>`.
+
+use std::fs;
+use std::path::{Component, Path, PathBuf};
+
+use chacha20poly1305::{
+    aead::{Aead, KeyInit, Payload},
+    ChaCha20Poly1305, Nonce,
+};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use sigil_core::agent::AgentStatus;
+use sigil_core::crypto::DerivationPath;
+
+use crate::agent_registry::AgentRegistry;
+use crate::agent_shard_encryption::{base64_bytes_vec, derive_key, generate_passcode, Passcode};
+use crate::error::{MotherError, Result};
+use crate::journal::{JOURNAL_FILE, JOURNAL_KEY_FILE};
+use crate::keygen::MasterKeyGenerator;
+use crate::registry::ChildRegistry;
+use crate::storage::{MasterShardData, MotherStorage};
+
+/// Migration bundle format version
+pub const MIGRATION_BUNDLE_VERSION: u8 = 1;
+
+/// Prefix for migration bundle parts
+pub const MIGRATION_PREFIX: &str = "SIGIL:MIGRATE:1:";
+
+/// Part size suited to QR sequences (Version 40, Low ECC, minus header)
+pub const MIGRATION_QR_PART_CHARS: usize = 2900;
+
+/// Part size suited to a 1.44MB floppy
+pub const MIGRATION_FLOPPY_PART_CHARS: usize = 1_400_000;
+
+/// Directories and files carried alongside the registries
+const MIGRATED_PATHS: &[&str] = &["proofs", JOURNAL_FILE, JOURNAL_KEY_FILE];
+
+/// Plaintext description of a bundle (authenticated, not encrypted)
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MigrationManifest {
+    /// Format version
+    pub version: u8,
+
+    /// Master public key of the migrated mother (hex)
+    pub master_pubkey: String,
+
+    /// Unix timestamp of the export
+    pub created_at: u64,
+
+    /// Number of registered children
+    pub child_count: usize,
+
+    /// Number of registered agents
+    pub agent_count: usize,
+
+    /// Accumulator version (None when no agent registry exists)
+    pub accumulator_version: Option<u64>,
+
+    /// Number of extra files (proofs, journal)
+    pub file_count: usize,
+}
+
+/// A file copied verbatim from the mother data directory
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MigrationFile {
+    /// Path relative to the data directory
+    pub path: String,
+
+    /// File contents
+    #[serde(with = "base64_bytes_vec")]
+    pub data: Vec<u8>,
+}
+
+/// The encrypted contents of a bundle
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MigrationPayload {
+    /// Master shard
+    pub master_shard: MasterShardData,
+
+    /// Child registry
+    pub child_registry: ChildRegistry,
+
+    /// Agent registry and accumulator
+    pub agent_registry: Option<AgentRegistry>,
+
+    /// Proofs and ceremony journal
+    pub files: Vec<MigrationFile>,
+}
+
+impl MigrationPayload {
+    /// Collect the full state of a mother data directory
+    pub fn collect(storage: &MotherStorage) -> Result<Self> {
+        let mut files = Vec::new();
+        for name in MIGRATED_PATHS {
+            collect_files(storage.base_path(), Path::new(name), &mut files)?;
+        }
+
+        Ok(Self {
+            master_shard: storage.load_master_shard()?,
+            child_registry: storage.load_registry()?,
+            agent_registry: storage.load_agent_registry()?,
+            files,
+        })
+    }
+
+    /// Build the manifest describing this payload
+    pub fn manifest(&self) -> MigrationManifest {
+        MigrationManifest {
+            version: MIGRATION_BUNDLE_VERSION,
+            master_pubkey: hex::encode(self.master_shard.master_pubkey),
+            created_at: std::time::SystemTime::now()
+                .duration_since(std::time::UNIX_EPOCH)
+                .unwrap()
+                .as_secs(),
+            child_count: self.child_registry.children.len(),
+            agent_count: self
+                .agent_registry
+                .as_ref()
+                .map(|r| r.agents.len())
+                .unwrap_or(0),
+            accumulator_version: self
+                .agent_registry
+                .as_ref()
+                .map(|r| r.accumulator_version()),
+            file_count: self.files.len(),
+        }
+    }
+
+    /// Check the payload is internally consistent and matches the manifest
+    ///
+    /// Returns a list of problems (empty when consistent).
+    pub fn verify_consistency(&self, manifest: &MigrationManifest) -> Vec<String> {
+        let mut issues = Vec::new();
+        let master = &self.master_shard;
+
+        if manifest.master_pubkey != hex::encode(master.master_pubkey) {
+            issues.push("Manifest master pubkey does not match master shard".to_string());
+        }
+        if manifest.child_count != self.child_registry.children.len() {
+            issues.push("Manifest child count does not match registry".to_string());
+        }
+        if manifest.file_count != self.files.len() {
+            issues.push("Manifest file count does not match payload".to_string());
+        }
+
+        // Children must have been derived from this master
+        for (id, child) in &self.child_registry.children {
+            if *id != child.child_id.to_hex() {
+                issues.push(format!("Child {} is stored under the wrong key", id));
+            }
+            let path = child.derivation_path.to_string_path();
+            let index = child.derivation_path.components[3] & !DerivationPath::HARDENED;
+            if index >= master.next_child_index {
+                issues.push(format!(
+                    "Child {} uses index {} beyond the master's next index {}",
+                    child.child_id.short(),
+                    index,
+                    master.next_child_index
+                ));
+            }
+            if !self.child_registry.used_paths.contains(&path) {
+                issues.push(format!(
+                    "Child {} path {} not recorded as used",
+                    child.child_id.short(),
+                    path
+                ));
+            }
+
+            // Re-derive the mother-side key from this master shard
+            let derived =
+                MasterKeyGenerator::derive_child(&master.cold_master_shard, &child.derivation_path)
+                    .map(|(_, pubkey)| pubkey);
+            match (self.child_registry.cold_pubkeys.get(id), derived) {
+                (Some(recorded), Ok(derived)) if *recorded == derived => {}
+                (Some(_), Ok(_)) => issues.push(format!(
+                    "Child {} pubkey was not derived from this master shard",
+                    child.child_id.short()
+                )),
+                (None, _) => issues.push(format!(
+                    "Child {} has no recorded pubkey to check against the master shard",
+                    child.child_id.short()
+                )),
+                (_, Err(e)) => issues.push(format!(
+                    "Child {} pubkey could not be derived: {}",
+                    child.child_id.short(),
+                    e
+                )),
+            }
+        }
+
+        // The accumulator must contain exactly the nullified agents
+        match &self.agent_registry {
+            Some(registry) => {
+                let version = registry.accumulator_version();
+                if manifest.accumulator_version != Some(version) {
+                    issues.push("Manifest accumulator version does not match".to_string());
+                }
+                if manifest.agent_count != registry.agents.len() {
+                    issues.push("Manifest agent count does not match registry".to_string());
+                }
+                if version != registry.nullified_ids.len() as u64 {
+                    issues.push(format!(
+                        "Accumulator version {} but {} nullified agents",
+                        version,
+                        registry.nullified_ids.len()
+                    ));
+                }
+                for (id, agent) in &registry.agents {
+                    let listed = registry.nullified_ids.contains(&agent.agent_id);
+                    match agent.status {
+                        AgentStatus::Nullified {
+                            nullified_at_version,
+                            ..
+                        } => {
+                            if !listed {
+                                issues.push(format!("Nullified agent {} not in accumulator", id));
+                            }
+                            if nullified_at_version > version {
+                                issues.push(format!(
+                                    "Agent {} nullified at future accumulator version {}",
+                                    id, nullified_at_version
+                                ));
+                            }
+                        }
+                        _ if listed => {
+                            issues.push(format!("Agent {} in accumulator but not nullified", id));
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            None => {
+                if manifest.accumulator_version.is_some() || manifest.agent_count != 0 {
+                    issues.push("Manifest lists agents but bundle has no registry".to_string());
+                }
+            }
+        }
+
+        issues
+    }
+
+    /// Write the payload into an empty mother data directory
+    pub fn install(&self, storage: &MotherStorage) -> Result<()> {
+        if storage.has_master_shard() {
+            return Err(MotherError::Storage(
+                "Target already holds a master shard - refusing to overwrite".to_string(),
+            ));
+        }
+
+        for file in &self.files {
+            let relative = safe_relative_path(&file.path)?;
+            let path = storage.base_path().join(relative);
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)?;
+            }
+            fs::write(&path, &file.data)?;
+        }
+
+        storage.save_registry(&self.child_registry)?;
+        if let Some(registry) = &self.agent_registry {
+            storage.save_agent_registry(registry)?;
+        }
+        // Master shard last, so a partial import never looks initialized
+        storage.save_master_shard(&self.master_shard)?;
+
+        Ok(())
+    }
+}
+
+/// Encrypted, authenticated migration bundle
+#[derive(Clone, Serialize, Deserialize)]
+pub struct MigrationBundle {
+    /// Authenticated manifest
+    pub manifest: MigrationManifest,
+
+    /// Salt used for key derivation
+    #[serde(with = "base64_bytes_vec")]
+    pub salt: Vec<u8>,
+
+    /// Nonce for ChaCha20-Poly1305
+    #[serde(with = "base64_bytes_vec")]
+    pub nonce: Vec<u8>,
+
+    /// Encrypted payload (includes auth tag)
+    #[serde(with = "base64_bytes_vec")]
+    pub ciphertext: Vec<u8>,
+}
+
+impl MigrationBundle {
+    /// Encrypt a payload under a freshly generated passcode
+    ///
+    /// Refuses payloads that would fail the import-side consistency check,
+    /// so a device is never retired for a bundle it cannot be restored from.
+    pub fn seal(payload: &MigrationPayload) -> Result<(Self, Passcode)> {
+        let manifest = payload.manifest();
+        let issues = payload.verify_consistency(&manifest);
+        if !issues.is_empty() {
+            return Err(MotherError::Storage(format!(
+                "Mother state inconsistent: {}",
+                issues.join("; ")
+            )));
+        }
+
+        let mut rng = rand::thread_rng();
+        let passcode = generate_passcode(&mut rng);
+
+        let mut salt = [0u8; 16];
+        rng.fill_bytes(&mut salt);
+        let mut nonce_bytes = [0u8; 12];
+        rng.fill_bytes(&mut nonce_bytes);
+
+        let aad = serde_json::to_vec(&manifest)?;
+        let plaintext = zeroize::Zeroizing::new(serde_json::to_vec(payload)?);
+
+        let key = zeroize::Zeroizing::new(derive_key(passcode.as_str(), &salt)?);
+        let cipher = ChaCha20Poly1305::new_from_slice(key.as_ref())
+            .map_err(|e| MotherError::Crypto(format!("Failed to create cipher: {}", e)))?;
+        let ciphertext = cipher
+            .encrypt(
+                &Nonce::from(nonce_bytes),
+                Payload {
+                    msg: &plaintext,
+                    aad: &aad,
+                },
+            )
+            .map_err(|e| MotherError::Crypto(format!("Encryption failed: {}", e)))?;
+
+        Ok((
+            Self {
+                manifest,
+                salt: salt.to_vec(),
+                nonce: nonce_bytes.to_vec(),
+                ciphertext,
+            },
+            passcode,
+        ))
+    }
+
+    /// Decrypt the payload and check it against the manifest
+    ///
+    /// The passcode may be entered with or without the display dashes.
+    pub fn open(&self, passcode: &str) -> Result<MigrationPayload> {
+        if self.manifest.version != MIGRATION_BUNDLE_VERSION {
+            return Err(MotherError::Storage(format!(
+                "Unsupported migration bundle version {}",
+                self.manifest.version
+            )));
+        }
+
+        let passcode: String = passcode
+            .chars()
+            .filter(|c| c.is_ascii_alphanumeric())
+            .map(|c| c.to_ascii_uppercase())
+            .collect();
+        let salt: [u8; 16] = self
+            .salt
+            .as_slice()
+            .try_into()
+            .map_err(|_| MotherError::Storage("Invalid salt length".to_string()))?;
+        let nonce: [u8; 12] = self
+            .nonce
+            .as_slice()
+            .try_into()
+            .map_err(|_| MotherError::Storage("Invalid nonce length".to_string()))?;
+
+        let aad = serde_json::to_vec(&self.manifest)?;
+        let key = zeroize::Zeroizing::new(derive_key(&passcode, &salt)?);
+        let cipher = ChaCha20Poly1305::new_from_slice(key.as_ref())
+            .map_err(|e| MotherError::Crypto(format!("Failed to create cipher: {}", e)))?;
+        let plaintext = zeroize::Zeroizing::new(
+            cipher
+                .decrypt(
+                    &Nonce::from(nonce),
+                    Payload {
+                        msg: &self.ciphertext,
+                        aad: &aad,
+                    },
+                )
+                .map_err(|_| {
+                    MotherError::Crypto(
+                        "Decryption failed - wrong passcode or tampered bundle".to_string(),
+                    )
+                })?,
+        );
+
+        let payload: MigrationPayload = serde_json::from_slice(&plaintext)?;
+        let issues = payload.verify_consistency(&self.manifest);
+        if !issues.is_empty() {
+            return Err(MotherError::Storage(format!(
+                "Migration bundle inconsistent: {}",
+                issues.join("; ")
+            )));
+        }
+
+        Ok(payload)
+    }
+
+    /// SHA-256 of the serialized bundle, recorded in the ceremony journal
+    pub fn bundle_hash(&self) -> Result<[u8; 32]> {
+        Ok(Sha256::digest(serde_json::to_vec(self)?).into())
+    }
+
+    /// Split into numbered parts of at most `max_chars` payload characters
+    pub fn to_parts(&self, max_chars: usize) -> Result<Vec<String>> {
+        let json = serde_json::to_vec(self)?;
+        let encoded = base64::Engine::encode(&base64::engine::general_purpose::STANDARD, &json);
+
+        let chunks: Vec<&str> = encoded
+            .as_bytes()
+            .chunks(max_chars.max(1))
+            .map(|c| std::str::from_utf8(c).unwrap_or_default())
+            .collect();
+        if chunks.len() > 999 {
+            return Err(MotherError::Storage(format!(
+                "Bundle needs {} parts; use a larger part size",
+                chunks.len()
+            )));
+        }
+
+        let total = chunks.len();
+        Ok(chunks
+            .iter()
+            .enumerate()
+            .map(|(i, chunk)| format!("{}{:03}/{:03}:{}", MIGRATION_PREFIX, i + 1, total, chunk))
+            .collect())
+    }
+
+    /// Reassemble a bundle from its parts (in any order)
+    pub fn from_parts(parts: &[String]) -> Result<Self> {
+        let mut indexed: Vec<(usize, usize, &str)> = Vec::with_capacity(parts.len());
+        for part in parts {
+            let body = part.trim().strip_prefix(MIGRATION_PREFIX).ok_or_else(|| {
+                MotherError::InvalidDiskFormat("Invalid migration part prefix".to_string())
+            })?;
+            let (header, data) = body.split_once(':').ok_or_else(|| {
+                MotherError::InvalidDiskFormat("Missing migration part header".to_string())
+            })?;
+            let (index, total) = header.split_once('/').ok_or_else(|| {
+                MotherError::InvalidDiskFormat("Invalid migration part header".to_string())
+            })?;
+            let parse = |s: &str| {
+                s.parse::<usize>().map_err(|_| {
+                    MotherError::InvalidDiskFormat("Invalid migration part number".to_string())
+                })
+            };
+            indexed.push((parse(index)?, parse(total)?, data));
+        }
+
+        indexed.sort_by_key(|(index, _, _)| *index);
+        let total = indexed.first().map(|(_, t, _)| *t).unwrap_or(0);
+        let complete = indexed.len() == total
+            && indexed
+                .iter()
+                .enumerate()
+                .all(|(i, (index, t, _))| *index == i + 1 && *t == total);
+        if !complete {
+            return Err(MotherError::InvalidDiskFormat(format!(
+                "Incomplete migration bundle: {} of {} parts",
+                indexed.len(),
+                total
+            )));
+        }
+
+        let encoded: String = indexed.iter().map(|(_, _, data)| *data).collect();
+        let json = base64::Engine::decode(&base64::engine::general_purpose::STANDARD, encoded)
+            .map_err(|e| MotherError::Serialization(format!("Base64 decode failed: {}", e)))?;
+        Ok(serde_json::from_slice(&json)?)
+    }
+}
+
+/// Recursively collect files under `relative` in the data directory
+fn collect_files(base: &Path, relative: &Path, files: &mut Vec<MigrationFile>) -> Result<()> {
+    let path = base.join(relative);
+    if path.is_dir() {
+        let mut entries: Vec<_> = fs::read_dir(&path)?.collect::<std::io::Result<_>>()?;
+        entries.sort_by_key(|e| e.file_name());
+        for entry in entries {
+            collect_files(base, &relative.join(entry.file_name()), files)?;
+        }
+    } else if path.is_file() {
+        files.push(MigrationFile {
+            path: relative.to_string_lossy().into_owned(),
+            data: fs::read(&path)?,
+        });
+    }
+    Ok(())
+}
+
+/// Reject absolute paths and `..` components from a bundle
+fn safe_relative_path(path: &str) -> Result<PathBuf> {
+    let path = PathBuf::from(path);
+    if path.components().all(|c| matches!(c, Component::Normal(_))) {
+        Ok(path)
+    } else {
+        Err(MotherError::Storage(format!(
+            "Unsafe path in migration bundle: {}",
+            path.display()
+        )))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sigil_core::{agent::AgentId, ChildId};
+
+    fn populated_storage(dir: &Path) -> MotherStorage {
+        let storage = MotherStorage::new(dir.to_path_buf()).unwrap();
+        let mut master = MasterShardData::new([3u8; 32], [2u8; 33]);
+
+        let mut children = ChildRegistry::new();
+        let child_id = ChildId::new([7u8; 32]);
+        let path = DerivationPath::ethereum_hardened(master.allocate_child_index());
+        let (_, cold_pubkey) =
+            MasterKeyGenerator::derive_child(&master.cold_master_shard, &path).unwrap();
+        children.register_child(child_id, path).unwrap();
+        children.record_cold_pubkey(&child_id, cold_pubkey).unwrap();
+
+        let mut agents = AgentRegistry::new();
+        agents
+            .register_agent(AgentId::new([1u8; 32]), "a".to_string())
+            .unwrap();
+        agents
+            .register_agent(AgentId::new([2u8; 32]), "b".to_string())
+            .unwrap();
+        agents.nullify_agent(&AgentId::new([2u8; 32])).unwrap();
+
+        storage.save_master_shard(&master).unwrap();
+        storage.save_registry(&children).unwrap();
+        storage.save_agent_registry(&agents).unwrap();
+        fs::create_dir_all(dir.join("proofs/keygen")).unwrap();
+        fs::write(dir.join("proofs/keygen/proof.bin"), b"proof").unwrap();
+        storage
+    }
+
+    #[test]
+    fn test_migration_round_trip() {
+        let src = tempfile::tempdir().unwrap();
+        let dst = tempfile::tempdir().unwrap();
+        let storage = populated_storage(src.path());
+
+        let payload = MigrationPayload::collect(&storage).unwrap();
+        assert!(payload.verify_consistency(&payload.manifest()).is_empty());
+
+        let (bundle, passcode) = MigrationBundle::seal(&payload).unwrap();
+        let parts = bundle.to_parts(512).unwrap();
+        assert!(parts.len() > 1);
+
+        let mut shuffled = parts.clone();
+        shuffled.reverse();
+        let restored = MigrationBundle::from_parts(&shuffled).unwrap();
+        assert!(MigrationBundle::from_parts(&parts[1..]).is_err());
+        assert!(restored.open("WRONG-PASSCODE").is_err());
+
+        let opened = restored
+            .open(&passcode.display_formatted().to_lowercase())
+            .unwrap();
+        let target = MotherStorage::new(dst.path().to_path_buf()).unwrap();
+        opened.install(&target).unwrap();
+
+        assert_eq!(target.load_master_shard().unwrap().master_pubkey, [2u8; 33]);
+        assert_eq!(target.load_registry().unwrap().children.len(), 1);
+        assert_eq!(
+            fs::read(dst.path().join("proofs/keygen/proof.bin")).unwrap(),
+            b"proof"
+        );
+        // Refuses to overwrite an initialized mother
+        assert!(opened.install(&target).is_err());
+    }
+
+    #[test]
+    fn test_migration_detects_inconsistency() {
+        let src = tempfile::tempdir().unwrap();
+        let storage = populated_storage(src.path());
+        let mut payload = MigrationPayload::collect(&storage).unwrap();
+        let manifest = payload.manifest();
+
+        // Child beyond the master's allocated indices
+        payload.master_shard.next_child_index = 0;
+        // Accumulator that lost a nullified agent
+        payload
+            .agent_registry
+            .as_mut()
+            .unwrap()
+            .nullified_ids
+            .clear();
+
+        let issues = payload.verify_consistency(&manifest);
+        assert_eq!(issues.len(), 3, "{:?}", issues);
+
+        // Children registered under a different master shard
+        let mut foreign = MigrationPayload::collect(&storage).unwrap();
+        foreign.master_shard.cold_master_shard = [4u8; 32];
+        let issues = foreign.verify_consistency(&manifest);
+        assert_eq!(issues.len(), 1, "{:?}", issues);
+        assert!(issues[0].contains("not derived from this master shard"));
+
+        // Children without a recorded pubkey cannot be checked
+        foreign = MigrationPayload::collect(&storage).unwrap();
+        foreign.child_registry.cold_pubkeys.clear();
+        assert_eq!(foreign.verify_consistency(&manifest).len(), 1);
+        assert!(MigrationBundle::seal(&foreign).is_err());
+
+        // Tampering with the manifest breaks authentication
+        let (mut bundle, passcode) =
+            MigrationBundle::seal(&MigrationPayload::collect(&storage).unwrap()).unwrap();
+        bundle.manifest.child_count = 5;
+        assert!(bundle.open(passcode.as_str()).is_err());
+    }
+}
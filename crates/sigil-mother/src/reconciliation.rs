@@ -2,7 +2,24 @@
 //!
 //! Helpers for analyzing and validating disk state during reconciliation.
 
-use sigil_core::{disk::DiskFormat, presig::PresigStatus};
+use sigil_core::{
+    audit::AUDIT_QR_PREFIX, disk::DiskFormat, presig::PresigStatus, AuditBundle, ChildId, PublicKey,
+};
+
+use crate::error::Result;
+
+/// How serious an anomaly is for the refill decision
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum AnomalySeverity {
+    /// Worth noting, does not block refill
+    Info,
+
+    /// Needs manual review before refill
+    Warning,
+
+    /// Evidence of tampering, child should be nullified
+    Critical,
+}
 
 /// Anomaly types that can be detected during reconciliation
 #[derive(Debug, Clone)]
@@ -30,6 +47,62 @@ pub enum Anomaly {
 
     /// Voided presig with log entry (shouldn't happen)
     VoidedWithLog { presig_index: u32 },
+
+    /// Usage log failed its own consistency checks
+    UsageLogInvalid { reason: String },
+
+    /// No agent audit key has been pinned, so the bundle cannot be trusted
+    AuditKeyNotPinned { found: PublicKey },
+
+    /// Agent audit bundle was signed by a key other than the pinned one
+    AuditKeyMismatch {
+        expected: PublicKey,
+        found: PublicKey,
+    },
+
+    /// Agent audit bundle failed signature or hash chain verification
+    AuditBundleInvalid { reason: String },
+
+    /// Agent audit bundle is for a different child
+    AuditChildMismatch { expected: ChildId, found: ChildId },
+
+    /// Agent recorded a signature that is missing from the disk log
+    AuditEntryMissingFromDisk { presig_index: u32 },
+
+    /// Disk log has an entry the agent never recorded
+    AuditEntryMissingFromAgent { presig_index: u32 },
+
+    /// Disk log entry differs from the agent's record
+    AuditEntryMismatch { presig_index: u32, field: String },
+}
+
+impl Anomaly {
+    /// Severity of this anomaly
+    pub fn severity(&self) -> AnomalySeverity {
+        match self {
+            Anomaly::TimestampAnomaly { .. } => AnomalySeverity::Info,
+            Anomaly::MissingLogEntry { .. }
+            | Anomaly::OrphanLogEntry { .. }
+            | Anomaly::PresigGap { .. }
+            | Anomaly::CountMismatch { .. }
+            | Anomaly::VoidedWithLog { .. }
+            | Anomaly::UsageLogInvalid { .. }
+            | Anomaly::AuditKeyNotPinned { .. }
+            // The agent may have failed to append after the disk write
+            | Anomaly::AuditEntryMissingFromAgent { .. } => AnomalySeverity::Warning,
+            Anomaly::InvalidSignature { .. }
+            | Anomaly::AuditBundleInvalid { .. }
+            | Anomaly::AuditKeyMismatch { .. }
+            | Anomaly::AuditChildMismatch { .. }
+            | Anomaly::AuditEntryMissingFromDisk { .. }
+            | Anomaly::AuditEntryMismatch { .. } => AnomalySeverity::Critical,
+        }
+    }
+}
+
+/// Highest severity among a set of anomalies
+pub fn max_severity(anomalies: &[Anomaly]) -> Option<AnomalySeverity> {
+    anomalies.iter().map(Anomaly::severity).max()
 }
 
 /// Detailed reconciliation analysis
@@ -75,6 +148,12 @@ pub fn analyze_disk(disk: &DiskFormat) -> ReconciliationAnalysis {
 
     let log_count = disk.usage_log.len() as u32;
 
+    if let Err(e) = disk.usage_log.validate() {
+        anomalies.push(Anomaly::UsageLogInvalid {
+            reason: e.to_string(),
+        });
+    }
+
     // Check header vs actual count
     if disk.header.presig_used != used_count {
         anomalies.push(Anomaly::CountMismatch {
@@ -152,6 +231,111 @@ pub fn analyze_disk(disk: &DiskFormat) -> ReconciliationAnalysis {
     }
 }
 
+/// Diff the agent's audit bundle against the disk's usage log
+///
+/// The bundle is only trusted when it was signed by the pinned agent audit
+/// key; without a pin it is reported and skipped. Only records whose R point
+/// matches the presig currently on disk at that index are compared, so
+/// records from earlier refills are ignored.
+pub fn compare_audit_bundle(
+    disk: &DiskFormat,
+    bundle: &AuditBundle,
+    pinned_key: Option<&PublicKey>,
+) -> Vec<Anomaly> {
+    let mut anomalies = Vec::new();
+
+    match pinned_key {
+        None => {
+            anomalies.push(Anomaly::AuditKeyNotPinned {
+                found: bundle.auditor_pubkey,
+            });
+            return anomalies;
+        }
+        Some(expected) if *expected != bundle.auditor_pubkey => {
+            anomalies.push(Anomaly::AuditKeyMismatch {
+                expected: *expected,
+                found: bundle.auditor_pubkey,
+            });
+            return anomalies;
+        }
+        Some(_) => {}
+    }
+
+    if let Err(e) = bundle.verify() {
+        anomalies.push(Anomaly::AuditBundleInvalid {
+            reason: e.to_string(),
+        });
+        return anomalies;
+    }
+
+    if bundle.child_id != disk.header.child_id {
+        anomalies.push(Anomaly::AuditChildMismatch {
+            expected: disk.header.child_id,
+            found: bundle.child_id,
+        });
+        return anomalies;
+    }
+
+    let current: Vec<_> = bundle
+        .records
+        .iter()
+        .filter(|r| {
+            disk.presigs
+                .get(r.presig_index as usize)
+                .map(|p| p.r_point == r.r_point)
+                .unwrap_or(false)
+        })
+        .collect();
+
+    for record in &current {
+        let presig_index = record.presig_index;
+        let Some(entry) = disk.usage_log.find_by_presig_index(presig_index) else {
+            anomalies.push(Anomaly::AuditEntryMissingFromDisk { presig_index });
+            continue;
+        };
+
+        let fields = [
+            ("timestamp", entry.timestamp == record.timestamp),
+            ("message_hash", entry.message_hash == record.message_hash),
+            ("signature", entry.signature == record.signature),
+            ("chain_id", entry.chain_id == record.chain_id),
+            ("zkproof_hash", entry.zkproof_hash == record.zkproof_hash),
+            ("description", entry.description == record.description),
+        ];
+        for (field, matches) in fields {
+            if !matches {
+                anomalies.push(Anomaly::AuditEntryMismatch {
+                    presig_index,
+                    field: field.to_string(),
+                });
+            }
+        }
+    }
+
+    for entry in &disk.usage_log.entries {
+        if !current.iter().any(|r| r.presig_index == entry.presig_index) {
+            anomalies.push(Anomaly::AuditEntryMissingFromAgent {
+                presig_index: entry.presig_index,
+            });
+        }
+    }
+
+    anomalies
+}
+
+/// Parse an audit bundle exported by the daemon
+///
+/// Accepts either the JSON export or scanned QR payloads, one per line.
+pub fn parse_audit_bundle(input: &str) -> Result<AuditBundle> {
+    let trimmed = input.trim();
+    if trimmed.starts_with(AUDIT_QR_PREFIX) {
+        let chunks: Vec<&str> = trimmed.lines().filter(|l| !l.trim().is_empty()).collect();
+        Ok(AuditBundle::from_qr_chunks(&chunks)?)
+    } else {
+        Ok(serde_json::from_str(trimmed)?)
+    }
+}
+
 /// Generate a human-readable reconciliation report
 pub fn generate_report(analysis: &ReconciliationAnalysis) -> String {
     let mut report = String::new();
@@ -173,7 +357,12 @@ pub fn generate_report(analysis: &ReconciliationAnalysis) -> String {
             analysis.anomalies.len()
         ));
         for (i, anomaly) in analysis.anomalies.iter().enumerate() {
-            report.push_str(&format!("  {}. {:?}\n", i + 1, anomaly));
+            report.push_str(&format!(
+                "  {}. [{:?}] {:?}\n",
+                i + 1,
+                anomaly.severity(),
+                anomaly
+            ));
         }
     }
 
@@ -234,4 +423,92 @@ mod tests {
             .iter()
             .any(|a| matches!(a, Anomaly::CountMismatch { .. })));
     }
+
+    fn signed_disk_and_bundle(used: u32) -> (DiskFormat, AuditBundle) {
+        use k256::ecdsa::SigningKey;
+        use sigil_core::{
+            audit::GENESIS_HASH,
+            types::{ChainId, MessageHash, Signature, TxHash, ZkProofHash},
+            AuditRecord, UsageLogEntry,
+        };
+
+        let mut disk = create_test_disk(10, used);
+        let key = SigningKey::from_bytes(&[5u8; 32].into()).unwrap();
+
+        let mut records = Vec::new();
+        let mut prev = GENESIS_HASH;
+        for i in 0..used {
+            let entry = UsageLogEntry::new(
+                i,
+                1_700_000_000 + i as u64,
+                MessageHash::new([i as u8; 32]),
+                Signature::new([i as u8; 64]),
+                ChainId::ETHEREUM,
+                TxHash::new([0u8; 32]),
+                ZkProofHash::new([0u8; 32]),
+                "test".to_string(),
+            );
+            let r_point = disk.presigs[i as usize].r_point;
+            let mut record = AuditRecord::from_usage_entry(
+                i as u64,
+                disk.header.child_id,
+                r_point,
+                &entry,
+                prev,
+            );
+            record.sign(&key);
+            prev = record.record_hash();
+            records.push(record);
+            disk.usage_log.push(entry).unwrap();
+        }
+
+        let bundle = AuditBundle::new(disk.header.child_id, &key, 1_700_001_000, records);
+        (disk, bundle)
+    }
+
+    #[test]
+    fn test_audit_bundle_matches_disk() {
+        let (disk, bundle) = signed_disk_and_bundle(3);
+        assert!(compare_audit_bundle(&disk, &bundle, Some(&bundle.auditor_pubkey)).is_empty());
+    }
+
+    #[test]
+    fn test_audit_bundle_requires_pinned_key() {
+        let (disk, bundle) = signed_disk_and_bundle(3);
+
+        let anomalies = compare_audit_bundle(&disk, &bundle, None);
+        assert!(matches!(
+            anomalies.as_slice(),
+            [Anomaly::AuditKeyNotPinned { .. }]
+        ));
+        assert_eq!(max_severity(&anomalies), Some(AnomalySeverity::Warning));
+
+        // A bundle re-signed by another key verifies on its own but is rejected
+        let other = k256::ecdsa::SigningKey::from_bytes(&[6u8; 32].into()).unwrap();
+        let forged = AuditBundle::new(bundle.child_id, &other, bundle.exported_at, vec![]);
+        let anomalies = compare_audit_bundle(&disk, &forged, Some(&bundle.auditor_pubkey));
+        assert!(matches!(
+            anomalies.as_slice(),
+            [Anomaly::AuditKeyMismatch { .. }]
+        ));
+        assert_eq!(max_severity(&anomalies), Some(AnomalySeverity::Critical));
+    }
+
+    #[test]
+    fn test_audit_detects_tampered_disk_log() {
+        let (mut disk, bundle) = signed_disk_and_bundle(3);
+
+        disk.usage_log.entries[1].chain_id = sigil_core::types::ChainId::BASE;
+        disk.usage_log.entries.remove(2);
+
+        let anomalies = compare_audit_bundle(&disk, &bundle, Some(&bundle.auditor_pubkey));
+        assert!(anomalies.iter().any(|a| matches!(
+            a,
+            Anomaly::AuditEntryMismatch { presig_index: 1, field } if field == "chain_id"
+        )));
+        assert!(anomalies
+            .iter()
+            .any(|a| matches!(a, Anomaly::AuditEntryMissingFromDisk { presig_index: 2 })));
+        assert_eq!(max_severity(&anomalies), Some(AnomalySeverity::Critical));
+    }
 }
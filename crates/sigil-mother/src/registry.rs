@@ -7,7 +7,7 @@ use std::collections::HashMap;
 
 use sigil_core::{
     child::{ChildRegistryEntry, ChildStatus, NullificationReason},
-    crypto::DerivationPath,
+    crypto::{DerivationPath, PublicKey},
     ChildId,
 };
 
@@ -21,6 +21,13 @@ pub struct ChildRegistry {
 
     /// Derivation paths that have been used
     pub used_paths: Vec<String>,
+
+    /// Mother-side child public keys, by child ID (hex)
+    ///
+    /// Derived from the cold master shard, so a migrated registry can be
+    /// checked against the master it claims to belong to.
+    #[serde(default)]
+    pub cold_pubkeys: HashMap<String, PublicKey>,
 }
 
 impl ChildRegistry {
@@ -29,6 +36,7 @@ impl ChildRegistry {
         Self {
             children: HashMap::new(),
             used_paths: Vec::new(),
+            cold_pubkeys: HashMap::new(),
         }
     }
 
@@ -64,6 +72,16 @@ impl ChildRegistry {
         Ok(())
     }
 
+    /// Record the mother-side public key of a registered child
+    pub fn record_cold_pubkey(&mut self, child_id: &ChildId, pubkey: PublicKey) -> Result<()> {
+        let id_hex = child_id.to_hex();
+        if !self.children.contains_key(&id_hex) {
+            return Err(MotherError::ChildNotFound(id_hex));
+        }
+        self.cold_pubkeys.insert(id_hex, pubkey);
+        Ok(())
+    }
+
     /// Get a child by ID
     pub fn get_child(&self, child_id: &ChildId) -> Result<&ChildRegistryEntry> {
         let id_hex = child_id.to_hex();
@@ -199,6 +217,13 @@ mod tests {
 
         assert!(registry.get_child(&child_id).is_ok());
         assert!(registry.can_sign(&child_id).unwrap());
+
+        let pubkey = PublicKey::new([2u8; 33]);
+        registry.record_cold_pubkey(&child_id, pubkey).unwrap();
+        assert_eq!(registry.cold_pubkeys.get(&child_id.to_hex()), Some(&pubkey));
+        assert!(registry
+            .record_cold_pubkey(&ChildId::new([9u8; 32]), pubkey)
+            .is_err());
     }
 
     #[test]
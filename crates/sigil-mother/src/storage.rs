@@ -1,20 +1,31 @@
 //! Mother device storage
 //!
-//! Manages persistent storage for the mother device's master shard
-//! and child registry.
+//! Manages persistent storage for the mother device's master shard,
+//! child registry and agent registry.
 
+use rand::RngCore;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
-use zeroize::{Zeroize, ZeroizeOnDrop};
+use std::path::{Path, PathBuf};
+use zeroize::{Zeroize, ZeroizeOnDrop, Zeroizing};
 
+use crate::agent_registry::AgentRegistry;
+use crate::auth::EncryptedMotherStorage;
 use crate::error::{MotherError, Result};
 use crate::registry::ChildRegistry;
 use sigil_core::types::{hex_bytes_32, hex_bytes_33};
+use sigil_core::PublicKey;
 
 /// Mother device storage
+///
+/// Once sealed, the master shard is kept only in encrypted storage under the
+/// mother PIN's storage key, which an operator quorum can also reconstruct.
+#[derive(Clone)]
 pub struct MotherStorage {
     /// Base path for storage
     base_path: PathBuf,
+
+    /// Storage key opening the sealed master shard
+    shard_key: Option<Zeroizing<[u8; 32]>>,
 }
 
 /// Master shard data (encrypted at rest in production)
@@ -40,16 +51,39 @@ impl MotherStorage {
     /// Create a new storage instance
     pub fn new(base_path: PathBuf) -> Result<Self> {
         std::fs::create_dir_all(&base_path)?;
-        Ok(Self { base_path })
+        Ok(Self {
+            base_path,
+            shard_key: None,
+        })
+    }
+
+    /// Open the sealed master shard with the storage key
+    pub fn with_shard_key(mut self, key: &[u8; 32]) -> Self {
+        self.shard_key = Some(Zeroizing::new(*key));
+        self
     }
 
     /// Check if master shard exists
     pub fn has_master_shard(&self) -> bool {
-        self.master_shard_path().exists()
+        self.master_shard_path().exists() || self.is_sealed()
+    }
+
+    /// Whether the master shard is sealed in encrypted storage
+    pub fn is_sealed(&self) -> bool {
+        self.sealed_storage()
+            .map(|sealed| sealed.has_master_shard())
+            .unwrap_or(false)
     }
 
     /// Load master shard
     pub fn load_master_shard(&self) -> Result<MasterShardData> {
+        if self.is_sealed() {
+            let key = self.shard_key.as_ref().ok_or_else(|| {
+                MotherError::Storage("Master shard is sealed; unlock it first".to_string())
+            })?;
+            return Ok(self.sealed_storage()?.load_master_shard_with_key(key)?);
+        }
+
         let path = self.master_shard_path();
         if !path.exists() {
             return Err(MotherError::MasterKeyNotInitialized);
@@ -61,7 +95,16 @@ impl MotherStorage {
     }
 
     /// Save master shard
+    ///
+    /// Stays sealed if it already was.
     pub fn save_master_shard(&self, data: &MasterShardData) -> Result<()> {
+        if self.is_sealed() {
+            let key = self.shard_key.as_ref().ok_or_else(|| {
+                MotherError::Storage("Master shard is sealed; unlock it first".to_string())
+            })?;
+            return Ok(self.sealed_storage()?.save_master_shard(data, key)?);
+        }
+
         let path = self.master_shard_path();
         let content = serde_json::to_string_pretty(data)?;
 
@@ -97,6 +140,95 @@ impl MotherStorage {
         Ok(())
     }
 
+    /// Load agent registry (None if agents were never registered)
+    pub fn load_agent_registry(&self) -> Result<Option<AgentRegistry>> {
+        let path = self.agent_registry_path();
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        Ok(Some(serde_json::from_str(&content)?))
+    }
+
+    /// Save agent registry
+    pub fn save_agent_registry(&self, registry: &AgentRegistry) -> Result<()> {
+        let path = self.agent_registry_path();
+        let content = serde_json::to_string_pretty(registry)?;
+
+        let temp_path = path.with_extension("json.tmp");
+        std::fs::write(&temp_path, &content)?;
+        std::fs::rename(&temp_path, &path)?;
+
+        Ok(())
+    }
+
+    /// Load the pinned agent audit key (None if no key was pinned)
+    pub fn load_audit_key(&self) -> Result<Option<PublicKey>> {
+        let path = self.audit_key_path();
+        if !path.exists() {
+            return Ok(None);
+        }
+
+        let content = std::fs::read_to_string(&path)?;
+        Ok(Some(serde_json::from_str(&content)?))
+    }
+
+    /// Pin the agent audit key that reconciliation bundles must be signed with
+    pub fn pin_audit_key(&self, key: &PublicKey) -> Result<()> {
+        let path = self.audit_key_path();
+        let content = serde_json::to_string_pretty(key)?;
+
+        let temp_path = path.with_extension("json.tmp");
+        std::fs::write(&temp_path, &content)?;
+        std::fs::rename(&temp_path, &path)?;
+
+        Ok(())
+    }
+
+    /// Check if this device has been retired by a migration
+    pub fn is_retired(&self) -> bool {
+        self.retired_marker_path().exists()
+    }
+
+    /// Mark this device as retired (its state now lives on another mother)
+    pub fn mark_retired(&self, reason: &str) -> Result<()> {
+        std::fs::write(self.retired_marker_path(), reason)?;
+        Ok(())
+    }
+
+    /// Get the base path
+    pub fn base_path(&self) -> &PathBuf {
+        &self.base_path
+    }
+
+    /// Securely erase the plaintext master shard, if present
+    pub fn erase_master_shard(&self) -> Result<()> {
+        shred_file(&self.master_shard_path())?;
+        Ok(())
+    }
+
+    /// Move the plaintext master shard into encrypted storage under `key`
+    ///
+    /// The plaintext copy is shredded once the sealed copy reads back. With
+    /// no plaintext shard this only attaches the key.
+    pub fn seal_master_shard(self, key: &[u8; 32]) -> Result<Self> {
+        let storage = self.with_shard_key(key);
+        if storage.master_shard_path().exists() {
+            let master = storage.load_master_shard()?;
+            let sealed = storage.sealed_storage()?;
+            sealed.save_master_shard(&master, key)?;
+            sealed.load_master_shard_with_key(key)?;
+            storage.erase_master_shard()?;
+        }
+        Ok(storage)
+    }
+
+    /// Encrypted storage holding the sealed master shard
+    fn sealed_storage(&self) -> Result<EncryptedMotherStorage> {
+        Ok(EncryptedMotherStorage::new(self.base_path.clone())?)
+    }
+
     /// Save reconciliation log entry
     pub fn save_reconciliation_log(&self, child_id: &str, log_entry: &str) -> Result<()> {
         let log_dir = self.base_path.join("reconciliation_logs");
@@ -122,6 +254,42 @@ impl MotherStorage {
     fn registry_path(&self) -> PathBuf {
         self.base_path.join("child_registry.json")
     }
+
+    /// Get path to agent registry file
+    fn agent_registry_path(&self) -> PathBuf {
+        self.base_path.join("agent_registry.json")
+    }
+
+    /// Get path to the pinned agent audit key
+    fn audit_key_path(&self) -> PathBuf {
+        self.base_path.join("audit_key.json")
+    }
+
+    /// Get path to the retirement marker
+    fn retired_marker_path(&self) -> PathBuf {
+        self.base_path.join("RETIRED")
+    }
+}
+
+/// Overwrite a file with random bytes, sync it and remove it
+///
+/// Missing files are ignored.
+pub(crate) fn shred_file(path: &Path) -> std::io::Result<()> {
+    use std::io::Write;
+
+    if !path.exists() {
+        return Ok(());
+    }
+
+    let len = std::fs::metadata(path)?.len() as usize;
+    let mut noise = vec![0u8; len];
+    rand::rngs::OsRng.fill_bytes(&mut noise);
+
+    let mut file = std::fs::OpenOptions::new().write(true).open(path)?;
+    file.write_all(&noise)?;
+    file.sync_all()?;
+    drop(file);
+    std::fs::remove_file(path)
 }
 
 impl MasterShardData {
@@ -147,3 +315,40 @@ impl MasterShardData {
         index
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_sealed_master_shard() {
+        let temp_dir = tempdir().unwrap();
+        let storage = MotherStorage::new(temp_dir.path().to_path_buf()).unwrap();
+        storage
+            .save_master_shard(&MasterShardData::new([5u8; 32], [2u8; 33]))
+            .unwrap();
+
+        let storage = storage.seal_master_shard(&[9u8; 32]).unwrap();
+        assert!(storage.is_sealed());
+        assert!(!temp_dir.path().join("master_shard.json").exists());
+
+        // Without the key, file access alone doesn't reach the shard
+        let locked = MotherStorage::new(temp_dir.path().to_path_buf()).unwrap();
+        assert!(locked.has_master_shard());
+        assert!(locked.load_master_shard().is_err());
+        assert!(locked
+            .clone()
+            .with_shard_key(&[8u8; 32])
+            .load_master_shard()
+            .is_err());
+
+        // Saves stay sealed
+        let mut master = storage.load_master_shard().unwrap();
+        master.next_child_index = 3;
+        storage.save_master_shard(&master).unwrap();
+        assert!(!temp_dir.path().join("master_shard.json").exists());
+        let reopened = locked.with_shard_key(&[9u8; 32]);
+        assert_eq!(reopened.load_master_shard().unwrap().next_child_index, 3);
+    }
+}
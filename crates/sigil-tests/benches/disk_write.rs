@@ -0,0 +1,135 @@
+//! Latency of recording one signature on a file-backed disk image
+//!
+//! Compares rewriting the whole image (the previous behaviour) with writing
+//! only the dirty regions. Besides wall-clock latency it reports the bytes
+//! written per signature and what that costs at floppy transfer rates, since
+//! a tmpfs-backed temp dir hides the cost of syncing. Point `SIGIL_BENCH_DIR`
+//! at a mounted floppy or USB stick to measure real media:
+//!
+//! ```bash
+//! SIGIL_BENCH_DIR=/media/floppy cargo bench -p sigil-tests --bench disk_write
+//! ```
+
+use std::time::{Duration, Instant};
+
+use sigil_core::{
+    crypto::DerivationPath,
+    presig::PresigColdShare,
+    types::{ChainId, MessageHash, Signature, TxHash, ZkProofHash},
+    usage::UsageLogEntry,
+    ChildId, DiskFormat, DiskHeader, PublicKey,
+};
+use sigil_daemon::storage::{DiskBackend, FileImage};
+
+/// Signatures recorded per strategy
+const SIGNATURES: u32 = 200;
+
+/// Sustained transfer rate of a 1.44MB floppy drive (500 kbit/s)
+const FLOPPY_BYTES_PER_SEC: f64 = 62_500.0;
+
+/// Latency and volume of the writes for one strategy
+struct Sample {
+    latencies: Vec<Duration>,
+    bytes_written: usize,
+}
+
+fn fresh_disk() -> DiskFormat {
+    let header = DiskHeader::new(
+        ChildId::new([0x42; 32]),
+        PublicKey::new([2u8; 33]),
+        DerivationPath::ethereum_hardened(0),
+        1000,
+        1_700_000_000,
+    );
+    let presigs = (0..1000)
+        .map(|i| PresigColdShare::new([i as u8; 33], [i as u8; 32], [i as u8; 32]))
+        .collect();
+    DiskFormat::new(header, presigs)
+}
+
+/// Apply one signature's worth of changes to the disk
+fn sign(disk: &mut DiskFormat, index: u32) {
+    disk.mark_presig_used(index).unwrap();
+    disk.usage_log
+        .push(UsageLogEntry::new(
+            index,
+            1_700_000_000 + index as u64,
+            MessageHash::new([index as u8; 32]),
+            Signature::new([1u8; 64]),
+            ChainId::ETHEREUM,
+            TxHash::new([0u8; 32]),
+            ZkProofHash::new([2u8; 32]),
+            format!("benchmark transfer {}", index),
+        ))
+        .unwrap();
+}
+
+/// Time `SIGNATURES` writes with one strategy
+fn run(incremental: bool) -> Sample {
+    let dir = match std::env::var_os("SIGIL_BENCH_DIR") {
+        Some(base) => tempfile::TempDir::new_in(base).unwrap(),
+        None => tempfile::TempDir::new().unwrap(),
+    };
+    let path = dir.path().join("sigil.disk");
+
+    let mut disk = fresh_disk();
+    std::fs::write(&path, disk.to_bytes()).unwrap();
+    let image = FileImage::new(path);
+
+    let mut latencies = Vec::with_capacity(SIGNATURES as usize);
+    let mut bytes_written = 0;
+    for index in 0..SIGNATURES {
+        let previous = disk.clone();
+        sign(&mut disk, index);
+
+        let start = Instant::now();
+        if incremental {
+            let regions = disk.dirty_regions(&previous).unwrap();
+            image.write_regions(&regions).unwrap();
+            bytes_written += regions.iter().map(|r| r.bytes.len()).sum::<usize>();
+        } else {
+            let bytes = disk.to_bytes();
+            image.write_image(&bytes).unwrap();
+            image.verify_at(0, &bytes).unwrap();
+            bytes_written += bytes.len();
+        }
+        latencies.push(start.elapsed());
+    }
+
+    let on_disk = DiskFormat::from_bytes(&image.read_image().unwrap()).unwrap();
+    assert_eq!(on_disk.header.presig_used, SIGNATURES);
+    assert_eq!(on_disk.usage_log.len(), SIGNATURES as usize);
+
+    Sample {
+        latencies,
+        bytes_written,
+    }
+}
+
+/// Print a summary and return the modelled floppy time per signature
+fn report(name: &str, mut sample: Sample) -> f64 {
+    let latencies = &mut sample.latencies;
+    latencies.sort();
+    let mean = latencies.iter().sum::<Duration>() / latencies.len() as u32;
+    let p50 = latencies[latencies.len() / 2];
+    let p99 = latencies[latencies.len() * 99 / 100];
+    let bytes = sample.bytes_written / SIGNATURES as usize;
+    let floppy_secs = bytes as f64 / FLOPPY_BYTES_PER_SEC;
+    println!(
+        "{:<12} mean {:>10.3?}  p50 {:>10.3?}  p99 {:>10.3?}  {:>7} B/sig  ~{:.3}s on floppy",
+        name, mean, p50, p99, bytes, floppy_secs
+    );
+    floppy_secs
+}
+
+fn main() {
+    println!("Recording {} signatures on a file-backed image", SIGNATURES);
+
+    let full = report("full", run(false));
+    let incremental = report("incremental", run(true));
+
+    println!(
+        "incremental writes move {:.0}x less data per signature",
+        full / incremental
+    );
+}
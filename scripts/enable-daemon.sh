@@ -22,6 +22,7 @@ set -euo pipefail
 DAEMON_BINARY="/usr/local/bin/sigil-daemon"
 SERVICE_NAME="sigil-daemon"
 SERVICE_FILE="/etc/systemd/system/${SERVICE_NAME}.service"
+SOCKET_FILE="/etc/systemd/system/${SERVICE_NAME}.socket"
 UDEV_RULES_FILE="/etc/udev/rules.d/99-sigil.rules"
 CONFIG_DIR="/etc/sigil"
 DATA_DIR="/var/lib/sigil"
@@ -29,7 +30,8 @@ SIGIL_GROUP="sigil"
 
 # Script directory (for finding source files)
 SCRIPT_DIR="$(cd "$(dirname "${BASH_SOURCE[0]}")" && pwd)"
-SOURCE_SERVICE_FILE="${SCRIPT_DIR}/udev-rules/sigil-daemon.service"
+SOURCE_SERVICE_FILE="${SCRIPT_DIR}/../crates/sigil-daemon/systemd/sigil-daemon.service"
+SOURCE_SOCKET_FILE="${SCRIPT_DIR}/../crates/sigil-daemon/systemd/sigil-daemon.socket"
 SOURCE_UDEV_RULES="${SCRIPT_DIR}/udev-rules/99-sigil.rules"
 
 # Colors for output
@@ -91,6 +93,10 @@ check_source_files() {
     if [[ ! -f "$SOURCE_SERVICE_FILE" ]]; then
         die "Service file not found at $SOURCE_SERVICE_FILE"
     fi
+
+    if [[ ! -f "$SOURCE_SOCKET_FILE" ]]; then
+        die "Socket file not found at $SOURCE_SOCKET_FILE"
+    fi
     if [[ ! -f "$SOURCE_UDEV_RULES" ]]; then
         die "udev rules file not found at $SOURCE_UDEV_RULES"
     fi
@@ -157,14 +163,18 @@ setup_directories() {
     fi
 }
 
-# Install systemd service file
+# Install systemd service and socket units
 install_service() {
-    info "Installing systemd service..."
+    info "Installing systemd units..."
 
-    # Copy service file
+    # Copy service and socket files
     cp "$SOURCE_SERVICE_FILE" "$SERVICE_FILE"
     chmod 644 "$SERVICE_FILE"
     success "Installed service file: $SERVICE_FILE"
+
+    cp "$SOURCE_SOCKET_FILE" "$SOCKET_FILE"
+    chmod 644 "$SOCKET_FILE"
+    success "Installed socket file: $SOCKET_FILE"
 }
 
 # Install udev rules
@@ -190,10 +200,13 @@ enable_daemon() {
     systemctl daemon-reload
     success "Reloaded systemd configuration"
 
-    # Enable the service (start on boot)
-    systemctl enable "$SERVICE_NAME"
+    # Enable the socket and service (start on boot)
+    systemctl enable "${SERVICE_NAME}.socket" "$SERVICE_NAME"
     success "Enabled $SERVICE_NAME to start on boot"
 
+    # systemd owns the IPC socket; start it before the daemon
+    systemctl start "${SERVICE_NAME}.socket"
+
     # Start the service now
     if systemctl is-active --quiet "$SERVICE_NAME"; then
         systemctl restart "$SERVICE_NAME"
@@ -208,9 +221,9 @@ enable_daemon() {
 disable_daemon() {
     info "Disabling and stopping daemon..."
 
-    # Stop the service if running
+    # Stop the service if running (stopping the socket too, so it isn't re-activated)
     if systemctl is-active --quiet "$SERVICE_NAME"; then
-        systemctl stop "$SERVICE_NAME"
+        systemctl stop "${SERVICE_NAME}.socket" "$SERVICE_NAME"
         success "Stopped $SERVICE_NAME"
     else
         success "$SERVICE_NAME was not running"
@@ -218,7 +231,7 @@ disable_daemon() {
 
     # Disable the service (don't start on boot)
     if systemctl is-enabled --quiet "$SERVICE_NAME" 2>/dev/null; then
-        systemctl disable "$SERVICE_NAME"
+        systemctl disable "${SERVICE_NAME}.socket" "$SERVICE_NAME"
         success "Disabled $SERVICE_NAME from starting on boot"
     else
         success "$SERVICE_NAME was not enabled"
@@ -228,6 +241,7 @@ disable_daemon() {
     info "Note: Service file and udev rules were NOT removed."
     info "To completely uninstall, manually remove:"
     info "  - $SERVICE_FILE"
+    info "  - $SOCKET_FILE"
     info "  - $UDEV_RULES_FILE"
 }
 